@@ -1,14 +1,22 @@
-use numelace_core::{Digit, Position};
-use numelace_game::{Game, GameError, RuleCheckPolicy};
+use numelace_core::{Digit, DigitPositions, Position};
+use numelace_game::{Game, GameError, InputOperation};
+use web_time::Instant;
 
 use crate::{
     action::{
-        Action, ActionRequestQueue, AppAction, BoardMutationAction, FlowAction, HistoryAction,
-        InputModeAction, NotesFillScope, PuzzleLifecycleAction, SelectionAction, StateQueryAction,
-        UiAction, UpdateStateAction,
+        Action, ActionRequestQueue, AppAction, BoardMutationAction, ConfirmKind, FlowAction,
+        HistoryAction, InputModeAction, ModalRequest, NoteKind, NotesFillScope, Notification,
+        NotificationKind, PuzzleLifecycleAction, ScriptResult, SelectionAction, StateQueryAction,
+        UiAction, UpdateStateAction, recording::Capture,
+    },
+    bug_report,
+    campaign::PACKS,
+    flow::{self, FlowGroup, script},
+    state::{
+        AppState, AppStateAccess, GameFinish, GenerationTraceView, GhostType, HintHeatMap,
+        HistorySnapshot, HistorySource, InputMode, NewGameOptions, PuzzleDifficulty, PuzzleSource,
+        SolveStepKind, TimedGame, UiState, fingerprint, today,
     },
-    flow,
-    state::{AppState, AppStateAccess, GhostType, InputMode, UiState},
 };
 
 #[derive(Debug)]
@@ -28,12 +36,52 @@ pub(crate) fn handle_all(
 }
 
 pub(crate) fn handle(app_state: &mut AppState, ui_state: &mut UiState, action: Action) {
+    let is_move = match &action {
+        Action::App(action) => action.is_move(),
+        Action::Flow(action) => action.is_move(),
+        Action::Ui(_) => false,
+    };
+    if is_move && ui_state.read_only_reason(app_state).is_some() {
+        return;
+    }
+    dispatch(app_state, ui_state, action);
+}
+
+/// Handles an action of the replayed recording, which moves even though the
+/// game is read-only for the player.
+pub(crate) fn handle_replayed(app_state: &mut AppState, ui_state: &mut UiState, action: Action) {
+    dispatch(app_state, ui_state, action);
+}
+
+fn dispatch(app_state: &mut AppState, ui_state: &mut UiState, action: Action) {
     let mut ctx = ActionContext {
         app_state: app_state.access(),
         ui_state,
     };
     ctx.ui_state.conflict_ghost = None;
+    ctx.ui_state.revision += 1;
+    ctx.ui_state.action_log.record(&action);
+    let capture = ctx
+        .ui_state
+        .recorder
+        .as_ref()
+        .and_then(|_| Capture::of(&action));
+    let attempted_goal = ctx
+        .ui_state
+        .script_prompt
+        .as_ref()
+        .and_then(|prompt| prompt.goal)
+        .filter(|goal| goal.is_attempted_by(&action));
     ctx.handle_action(action);
+    if let Some(goal) = attempted_goal
+        && goal.is_met(ctx.app_state.as_ref())
+    {
+        script::respond(ctx.ui_state, ScriptResult::Completed);
+    }
+    if let (Some(recorder), Some(capture)) = (&mut ctx.ui_state.recorder, capture) {
+        let app_state = ctx.app_state.as_ref();
+        recorder.record(capture, &app_state.game, &app_state.settings);
+    }
 }
 
 impl ActionContext<'_> {
@@ -48,6 +96,17 @@ impl ActionContext<'_> {
 
 impl AppAction {
     fn execute(self, app_state: &mut AppState, ui_state: &mut UiState) {
+        let was_solved = app_state.game.is_solved();
+        // Loading a solved board is not solving it.
+        let is_move = self.is_move();
+        // A race swaps boards between players, which one log cannot follow.
+        let logged = self
+            .solve_step_kind()
+            .filter(|_| app_state.race.is_none())
+            .map(|kind| {
+                let source = HistorySource::new(&app_state.game, app_state.selected_cell());
+                (kind, HistorySnapshot::new(&source))
+            });
         match self {
             AppAction::BoardMutation(action) => {
                 action.execute(app_state, ui_state);
@@ -61,8 +120,82 @@ impl AppAction {
             AppAction::InputMode(action) => action.execute(app_state),
             AppAction::UpdateState(action) => action.execute(app_state),
             AppAction::CancelContextual => execute_cancel_contextual(app_state, ui_state),
+            AppAction::PassTurn => execute_pass_turn(app_state, ui_state),
+        }
+        if let Some((kind, before)) = logged {
+            ui_state.solve_log.record(
+                kind,
+                &before,
+                &app_state.game,
+                app_state.selected_cell(),
+                Instant::now(),
+            );
+        }
+        match app_state.finish_game() {
+            Some(GameFinish::RaceTurn(_)) => {
+                let notification = Notification::new(NotificationKind::RaceTurnFinished);
+                ui_state.notifications.push(notification, Instant::now());
+                execute_pass_turn(app_state, ui_state);
+            }
+            Some(GameFinish::Race) if ui_state.active_modal.is_none() => {
+                ui_state.active_modal = Some(ModalRequest::RaceResult);
+            }
+            _ => {}
+        }
+        if is_move && !was_solved && app_state.game.is_solved() {
+            execute_solved(app_state, ui_state);
+        }
+        if app_state.complete_campaign_puzzle() {
+            let notification = Notification::new(NotificationKind::CampaignPuzzleSolved);
+            ui_state.notifications.push(notification, Instant::now());
         }
     }
+
+    /// Returns how the solve log counts the action, or `None` if it is not
+    /// logged.
+    fn solve_step_kind(&self) -> Option<SolveStepKind> {
+        let kind = match self {
+            AppAction::BoardMutation(
+                BoardMutationAction::ApplyTechniqueStep(_)
+                | BoardMutationAction::RevealCell { .. }
+                | BoardMutationAction::RevealAll,
+            )
+            | AppAction::UpdateState(UpdateStateAction::RecordHintTechnique { .. }) => {
+                SolveStepKind::Hint
+            }
+            AppAction::BoardMutation(_) => SolveStepKind::Move,
+            AppAction::History(HistoryAction::Undo | HistoryAction::UndoSteps(_)) => {
+                SolveStepKind::Undo
+            }
+            AppAction::History(HistoryAction::Redo) => SolveStepKind::Redo,
+            _ => return None,
+        };
+        Some(kind)
+    }
+}
+
+/// Wraps up the puzzle solved by the last move: records it in the statistics
+/// and shows the result.
+///
+/// The clock was already stopped by [`AppState::finish_game`]. The board stays
+/// editable unless the player starts the review from the result dialog, so
+/// the puzzle can be solved again after an undo; only the first solve of each
+/// puzzle is wrapped up.
+fn execute_solved(app_state: &mut AppState, ui_state: &mut UiState) {
+    if app_state.solved_recorded {
+        return;
+    }
+    app_state.solved_recorded = true;
+    app_state.record_solved_puzzle();
+    if ui_state.active_modal.is_none() {
+        ui_state.active_modal = Some(ModalRequest::SolvedResult);
+    }
+}
+
+fn execute_pass_turn(app_state: &mut AppState, ui_state: &mut UiState) {
+    if app_state.pass_turn() {
+        ui_state.hint_state = None;
+    }
 }
 
 fn execute_cancel_contextual(app_state: &mut AppState, ui_state: &mut UiState) {
@@ -70,6 +203,14 @@ fn execute_cancel_contextual(app_state: &mut AppState, ui_state: &mut UiState) {
         ui_state.hint_state = None;
         return;
     }
+    if ui_state.scratchpad.tool.is_some() {
+        ui_state.scratchpad.tool = None;
+        return;
+    }
+    if app_state.armed_digit().is_some() {
+        app_state.disarm_digit();
+        return;
+    }
     if app_state.selected_cell().is_some() {
         app_state.clear_selected_cell();
         return;
@@ -90,45 +231,103 @@ fn execute_fill_cell(
         Ok(_) => {
             app_state.update_selected_digit();
         }
-        Err(GameError::ConflictingDigit) => {
-            assert_eq!(app_state.rule_check_policy(), RuleCheckPolicy::Strict);
+        Err(GameError::ConflictingDigit { .. } | GameError::WrongDigit { .. }) => {
+            assert!(app_state.rule_check_policy().checks_rules());
             ui_state.conflict_ghost = Some((pos, GhostType::Digit(digit)));
         }
         Err(_) => {}
     }
 }
 
+fn execute_toggle_note(
+    app_state: &mut AppState,
+    ui_state: &mut UiState,
+    pos: Position,
+    digit: Digit,
+    kind: NoteKind,
+) {
+    let policy = app_state.rule_check_policy();
+    let result = match kind {
+        NoteKind::Corner => app_state.game.toggle_note(pos, digit, policy),
+        NoteKind::Center => app_state.game.toggle_center_note(pos, digit, policy),
+    };
+    if let Err(GameError::ConflictingDigit { .. }) = result {
+        assert!(policy.checks_rules());
+        ui_state.conflict_ghost = Some((pos, GhostType::Note(digit)));
+    }
+}
+
+/// Toggles `digit` as a note of `kind` in every selected cell without a digit.
+///
+/// The selection is toggled as a whole: the note is removed if every such cell
+/// has it, and added to the cells missing it otherwise.
+fn execute_toggle_selection_notes(
+    app_state: &mut AppState,
+    ui_state: &mut UiState,
+    digit: Digit,
+    kind: NoteKind,
+) {
+    let game = &app_state.game;
+    let has_note = |pos| match kind {
+        NoteKind::Corner => game
+            .cell(pos)
+            .as_notes()
+            .is_some_and(|notes| notes.contains(digit)),
+        NoteKind::Center => game.center_notes(pos).contains(digit),
+    };
+    let cells = app_state.selected_cells() & game.empty_positions();
+    let remove = cells.into_iter().all(has_note);
+    let targets: DigitPositions = cells
+        .into_iter()
+        .filter(|&pos| has_note(pos) == remove)
+        .collect();
+    for pos in targets {
+        execute_toggle_note(app_state, ui_state, pos, digit, kind);
+    }
+}
+
+/// Enters `digit`, or the selected digit, at `position`, or across the
+/// selection, in the current input mode or the other one when `swap` is set.
+fn execute_request_digit(
+    app_state: &mut AppState,
+    ui_state: &mut UiState,
+    digit: Option<Digit>,
+    swap: bool,
+    position: Option<Position>,
+) {
+    let Some(pos) = position.or_else(|| app_state.selected_cell()) else {
+        return;
+    };
+    if let Some(digit) = digit.or_else(|| app_state.selected_digit()) {
+        match app_state.input_mode.swapped(swap) {
+            InputMode::Fill => execute_fill_cell(app_state, ui_state, pos, digit),
+            InputMode::Notes if position.is_none() => {
+                execute_toggle_selection_notes(app_state, ui_state, digit, NoteKind::Corner);
+            }
+            InputMode::Notes => {
+                execute_toggle_note(app_state, ui_state, pos, digit, NoteKind::Corner);
+            }
+        }
+    }
+    if app_state.selected_cell() != Some(pos) {
+        app_state.set_selected_cell(pos);
+    }
+}
+
 impl BoardMutationAction {
     fn execute(self, app_state: &mut AppState, ui_state: &mut UiState) {
         let game_snapshot = app_state.game.clone();
         match self {
             BoardMutationAction::RequestDigit {
                 digit,
-                swap_input_mode: swap,
+                swap_input_mode,
                 position,
-            } => {
-                if let Some(pos) = position.or_else(|| app_state.selected_cell()) {
-                    if let Some(digit) = digit.or_else(|| app_state.selected_digit()) {
-                        match app_state.input_mode.swapped(swap) {
-                            InputMode::Fill => execute_fill_cell(app_state, ui_state, pos, digit),
-                            InputMode::Notes => {
-                                let policy = app_state.rule_check_policy();
-                                if let Err(GameError::ConflictingDigit) =
-                                    app_state.game.toggle_note(pos, digit, policy)
-                                {
-                                    assert_eq!(policy, RuleCheckPolicy::Strict);
-                                    ui_state.conflict_ghost = Some((pos, GhostType::Note(digit)));
-                                }
-                            }
-                        }
-                    }
-                    if app_state.selected_cell() != Some(pos) {
-                        app_state.set_selected_cell(pos);
-                    }
-                }
+            } => execute_request_digit(app_state, ui_state, digit, swap_input_mode, position),
+            BoardMutationAction::ToggleNote { digit, kind } => {
+                execute_toggle_selection_notes(app_state, ui_state, digit, kind);
             }
             BoardMutationAction::ClearCell => {
-                if let Some(pos) = app_state.selected_cell() {
+                for pos in app_state.selected_cells() {
                     let _ = app_state.game.clear_cell(pos);
                 }
             }
@@ -155,17 +354,34 @@ impl BoardMutationAction {
                     app_state.game.auto_fill_notes_empty_cells();
                 }
                 NotesFillScope::SelectedCell => {
-                    if let Some(pos) = app_state.selected_cell() {
+                    for pos in app_state.selected_cells() {
                         let _ = app_state.game.auto_fill_cell_notes(pos);
                     }
                 }
             },
             BoardMutationAction::ResetInputs => {
-                for pos in Position::ALL {
-                    let _ = app_state.game.clear_cell(pos);
-                }
+                app_state.game.clear_inputs();
                 app_state.apply_new_game_settings();
             }
+            BoardMutationAction::ClearMistakes => {
+                app_state.game.clear_mistakes();
+            }
+            BoardMutationAction::RemoveIllegalNotes => {
+                app_state.game.remove_illegal_notes();
+            }
+            BoardMutationAction::RevealCell { position } => {
+                let options = &app_state.input_digit_options();
+                if let Ok(InputOperation::Set) = app_state.game.reveal_cell(position, options) {
+                    app_state.hint_usage.record_reveal();
+                    app_state.record_hinted_cells(DigitPositions::from_elem(position));
+                }
+            }
+            BoardMutationAction::RevealAll => {
+                let options = &app_state.input_digit_options();
+                if app_state.game.reveal_all(options) > 0 {
+                    app_state.hint_usage.record_reveal();
+                }
+            }
             BoardMutationAction::ApplyTechniqueStep(step) => {
                 let options = &app_state.input_digit_options();
                 let _ = app_state.game.apply_technique_step(step.as_ref(), options);
@@ -180,16 +396,87 @@ impl BoardMutationAction {
 
 impl PuzzleLifecycleAction {
     fn execute(self, app_state: &mut AppState, ui_state: &mut UiState) {
+        // Background flows and sketches belong to the puzzle being replaced.
+        ui_state.executor.cancel_group(FlowGroup::Background);
+        ui_state.scratchpad.clear();
+        ui_state.deduction_depths = None;
+        ui_state.deduction_depths_request = None;
+        app_state.reviewing = false;
+        app_state.solved_recorded = false;
         match self {
-            PuzzleLifecycleAction::StartNewGame(puzzle) => {
-                let game = Game::new(puzzle);
+            PuzzleLifecycleAction::StartNewGame {
+                puzzle,
+                chess,
+                difficulty,
+                mode,
+                generated_with,
+            } => {
+                app_state.seed = Some(puzzle.seed);
+                app_state.generated_with = generated_with.map(|options| *options);
+                let game = Game::new(*puzzle).with_chess_rules(chess);
                 app_state.game = game;
+                app_state.settings.switch_assist_profile(difficulty);
+                app_state.clear_selected_cell_and_digit();
+                app_state.apply_new_game_settings();
+                app_state.reset_history();
+                app_state.difficulty = None;
+                app_state.hint_heat_map = HintHeatMap::default();
+                app_state.timed = mode.is_timed().then(TimedGame::default);
+                app_state.race = None;
+                app_state.campaign_puzzle = None;
+                if mode.is_hot_seat_race() {
+                    app_state.start_race();
+                }
+                ui_state.hint_state = None;
+            }
+            PuzzleLifecycleAction::LoadGame(game) => {
+                // A board loaded solved was not solved here.
+                app_state.solved_recorded = game.is_solved();
+                // Puzzles played before keep their difficulty; others get
+                // theirs, and their assist profile, once graded.
+                let difficulty = fingerprint(&game)
+                    .and_then(|fingerprint| app_state.puzzle_metadata.get(fingerprint))
+                    .and_then(|metadata| metadata.difficulty);
+                if let Some(difficulty) = difficulty {
+                    app_state
+                        .settings
+                        .switch_assist_profile(difficulty.profile_preset());
+                }
+                app_state.game = *game;
+                app_state.difficulty = difficulty;
+                app_state.seed = None;
+                app_state.generated_with = None;
+                app_state.hint_heat_map = HintHeatMap::default();
+                app_state.timed = None;
+                app_state.race = None;
+                app_state.campaign_puzzle = None;
+                app_state.clear_selected_cell_and_digit();
+                app_state.reset_history();
+                ui_state.hint_state = None;
+            }
+            PuzzleLifecycleAction::StartCampaignPuzzle { puzzle, game } => {
+                let difficulty = PACKS[puzzle.pack].difficulty;
+                app_state.game = *game;
+                app_state.seed = None;
+                app_state.generated_with = None;
+                app_state.settings.switch_assist_profile(difficulty);
                 app_state.clear_selected_cell_and_digit();
                 app_state.apply_new_game_settings();
                 app_state.reset_history();
+                app_state.hint_heat_map = HintHeatMap::default();
+                app_state.timed = None;
+                app_state.race = None;
+                app_state.campaign_puzzle = Some(puzzle);
+                // Bundled puzzles are graded ahead of time.
+                app_state.set_difficulty(Some(PuzzleDifficulty::Rated(difficulty)));
+                ui_state.hint_state = None;
+            }
+            PuzzleLifecycleAction::RestoreState(state) => {
+                app_state.restore(*state);
                 ui_state.hint_state = None;
             }
         }
+        ui_state.solve_log.reset(&app_state.game, Instant::now());
     }
 }
 
@@ -211,12 +498,25 @@ impl HistoryAction {
 }
 
 impl StateQueryAction {
-    fn execute(self, app_state: &mut AppState, _ui_state: &mut UiState) {
+    fn execute(self, app_state: &mut AppState, ui_state: &mut UiState) {
         match self {
             StateQueryAction::BuildUndoGames { responder } => {
                 let games = app_state.build_undo_games();
                 let _ = responder.send(games);
             }
+            StateQueryAction::CheckProgress => {
+                let game = &app_state.game;
+                let kind = if !game.has_solution() {
+                    NotificationKind::ProgressCheckUnavailable
+                } else if game.mistake_positions().is_empty() {
+                    NotificationKind::ProgressOnTrack
+                } else {
+                    NotificationKind::ProgressOffTrack
+                };
+                ui_state
+                    .notifications
+                    .push(Notification::new(kind), Instant::now());
+            }
         }
     }
 }
@@ -227,7 +527,7 @@ impl SelectionAction {
 
         match self {
             SelectionAction::SelectOrClearCell(pos) => {
-                if app_state.selected_cell() == Some(pos) {
+                if app_state.selected_cells() == DigitPositions::from_elem(pos) {
                     if app_state.game.cell(pos).as_digit() == app_state.selected_digit() {
                         app_state.clear_selected_cell_and_digit();
                     } else {
@@ -237,12 +537,26 @@ impl SelectionAction {
                     app_state.set_selected_cell(pos);
                 }
             }
+            SelectionAction::ToggleCellInSelection(pos) => {
+                app_state.toggle_cell_in_selection(pos);
+            }
             SelectionAction::MoveSelection(move_direction) => {
                 let pos = app_state.selected_cell().unwrap_or(DEFAULT_POSITION);
                 if let Some(new_pos) = move_direction.apply_to(pos) {
                     app_state.set_selected_cell(new_pos);
                 }
             }
+            SelectionAction::ExtendSelection(move_direction) => match app_state.selected_cell() {
+                Some(pos) => {
+                    if let Some(new_pos) = move_direction.apply_to(pos) {
+                        app_state.extend_selection(new_pos);
+                    }
+                }
+                None => app_state.set_selected_cell(DEFAULT_POSITION),
+            },
+            SelectionAction::ToggleArmedDigit(digit) => {
+                app_state.toggle_armed_digit(digit);
+            }
         }
     }
 }
@@ -262,7 +576,40 @@ impl UpdateStateAction {
                 app_state.new_game_options = new_game_options;
             }
             UpdateStateAction::UpdateSettings(settings) => {
-                app_state.settings = settings;
+                if settings.input.order.is_cell_first() {
+                    app_state.disarm_digit();
+                }
+                app_state.settings = *settings;
+            }
+            UpdateStateAction::SetPuzzleDifficulty(difficulty) => {
+                app_state.set_difficulty(difficulty);
+            }
+            UpdateStateAction::SwitchAssistProfile(difficulty) => {
+                app_state
+                    .settings
+                    .switch_assist_profile(difficulty.profile_preset());
+            }
+            UpdateStateAction::RecordHintTechnique { key, cells } => {
+                app_state.hint_usage.record(&key);
+                app_state.record_hinted_cells(cells);
+            }
+            UpdateStateAction::ClearHintUsage => {
+                app_state.hint_usage.clear();
+            }
+            UpdateStateAction::MarkTutorialSeen => {
+                app_state.tutorial_seen = true;
+            }
+            UpdateStateAction::RecordImportedPuzzle(fingerprint) => {
+                app_state
+                    .puzzle_metadata
+                    .add(fingerprint, PuzzleSource::Shared, &today());
+            }
+            UpdateStateAction::StartReview => {
+                app_state.reviewing = app_state.game.is_solved();
+            }
+            UpdateStateAction::ToggleCandidateHeatmap => {
+                let assist = &mut app_state.settings.assist;
+                assist.candidate_heatmap = !assist.candidate_heatmap;
             }
         }
     }
@@ -271,6 +618,9 @@ impl UpdateStateAction {
 impl UiAction {
     fn execute(self, ui_state: &mut UiState) {
         match self {
+            UiAction::ShowScreen(screen) => {
+                ui_state.screen = screen;
+            }
             UiAction::OpenModal(modal_request) => {
                 ui_state.active_modal = Some(modal_request);
             }
@@ -289,19 +639,98 @@ impl UiAction {
             UiAction::ClearHintState => {
                 ui_state.hint_state = None;
             }
+            UiAction::SetNoteAudit(note_audit) => {
+                ui_state.note_audit = note_audit;
+            }
+            UiAction::SetDeductionDepths(depths) => {
+                ui_state.deduction_depths = depths;
+            }
+            UiAction::SelectLesson(id) => {
+                ui_state.learn.select(id);
+            }
+            UiAction::ShowLessonExample { example, stage } => {
+                ui_state.learn.example = example;
+                ui_state.learn.stage = stage;
+            }
+            UiAction::ShowScriptPrompt(prompt) => {
+                ui_state.script_prompt = Some(prompt);
+            }
+            UiAction::RespondScriptPrompt(result) => {
+                script::respond(ui_state, result);
+            }
+            UiAction::ClearScriptPrompt => {
+                ui_state.script_prompt = None;
+            }
+            UiAction::ShowGenerationTrace(trace) => {
+                ui_state.generation_trace = Some(GenerationTraceView::new(*trace));
+            }
+            UiAction::CloseGenerationTrace => {
+                ui_state.generation_trace = None;
+            }
+            UiAction::ToggleScratchTool(tool) => ui_state.scratchpad.toggle_tool(tool),
+            UiAction::AddScratchStroke(stroke) => ui_state.scratchpad.add(stroke),
+            UiAction::ClearScratchpad => ui_state.scratchpad.clear(),
+            UiAction::ToggleInfoPanel => ui_state.show_info_panel = !ui_state.show_info_panel,
+            UiAction::ReviewSolveStep(step) => ui_state.solve_log.review(step),
+            UiAction::CopyToClipboard(content) => {
+                ui_state.pending_clipboard = Some(content);
+            }
+            UiAction::ShowNotification(notification) => {
+                ui_state.notifications.push(notification, Instant::now());
+            }
+            UiAction::DismissNotification { id } => {
+                ui_state.notifications.dismiss(id);
+            }
         }
     }
 }
 
+/// Starts the new game flow, or generates a puzzle from `options` right away if
+/// given.
+fn spawn_new_game(app_state: &AppState, ui_state: &mut UiState, options: Option<NewGameOptions>) {
+    flow::tasks::spawn_new_game_flow(
+        &mut ui_state.executor,
+        &app_state.game,
+        &app_state.settings.solver.priority,
+        ui_state.trace_generator,
+        options,
+    );
+}
+
 impl FlowAction {
     fn execute(self, app_state: &AppState, ui_state: &mut UiState) {
         match self {
-            FlowAction::StartNewGame => {
-                flow::tasks::spawn_new_game_flow(&mut ui_state.executor, &app_state.game);
+            FlowAction::StartNewGame => spawn_new_game(app_state, ui_state, None),
+            FlowAction::RegeneratePuzzle => {
+                if let Some(options) = app_state.generated_with.clone() {
+                    spawn_new_game(app_state, ui_state, Some(options));
+                }
             }
             FlowAction::ResetInputs => {
                 flow::tasks::spawn_reset_inputs_flow(&mut ui_state.executor);
             }
+            FlowAction::AuditNotes => {
+                flow::tasks::spawn_audit_notes_flow(
+                    &mut ui_state.executor,
+                    app_state.game.illegal_notes(),
+                );
+            }
+            FlowAction::RevealSelectedCell => {
+                if let Some(position) = app_state.selected_cell() {
+                    flow::tasks::spawn_reveal_flow(
+                        &mut ui_state.executor,
+                        ConfirmKind::RevealCell,
+                        BoardMutationAction::RevealCell { position },
+                    );
+                }
+            }
+            FlowAction::RevealAll => {
+                flow::tasks::spawn_reveal_flow(
+                    &mut ui_state.executor,
+                    ConfirmKind::RevealAll,
+                    BoardMutationAction::RevealAll,
+                );
+            }
             FlowAction::CheckSolvability => {
                 flow::tasks::spawn_check_solvability_flow(&mut ui_state.executor, &app_state.game);
             }
@@ -309,22 +738,93 @@ impl FlowAction {
                 flow::tasks::spawn_hint_flow(
                     &mut ui_state.executor,
                     &app_state.game,
+                    &app_state.settings.solver.priority,
                     ui_state.hint_state.clone(),
+                    app_state.settings.assist.hint_detail.first_stage(),
+                );
+            }
+            FlowAction::Export(content) => {
+                flow::tasks::spawn_export_flow(&mut ui_state.executor, &app_state.game, content);
+            }
+            FlowAction::CopyShareLink { include_progress } => {
+                flow::tasks::spawn_copy_share_link_flow(
+                    &mut ui_state.executor,
+                    &app_state.game,
+                    include_progress,
+                );
+            }
+            FlowAction::ShareBoardImage => {
+                flow::tasks::spawn_share_board_image_flow(&mut ui_state.executor, &app_state.game);
+            }
+            FlowAction::LoadSharedPuzzle(code) => {
+                flow::tasks::spawn_load_shared_puzzle_flow(
+                    &mut ui_state.executor,
+                    &app_state.game,
+                    &app_state.puzzle_metadata,
+                    &app_state.settings.solver.priority,
+                    &code,
+                );
+            }
+            FlowAction::RecoverProgress { state, shared_code } => {
+                flow::tasks::spawn_recover_progress_flow(
+                    &mut ui_state.executor,
+                    state,
+                    shared_code,
+                    app_state.game.is_initialized(),
+                );
+            }
+            FlowAction::ExportSettings => {
+                flow::tasks::spawn_export_settings_flow(
+                    &mut ui_state.executor,
+                    &app_state.settings,
+                );
+            }
+            FlowAction::StartCampaignPuzzle(puzzle) => {
+                flow::tasks::spawn_start_campaign_puzzle_flow(
+                    &mut ui_state.executor,
+                    &app_state.game,
+                    puzzle,
                 );
             }
+            FlowAction::ImportSettings => {
+                flow::tasks::spawn_import_settings_flow(&mut ui_state.executor);
+            }
+            FlowAction::StartTutorial => {
+                flow::tasks::spawn_tutorial_flow(&mut ui_state.executor, &app_state.game);
+            }
+            FlowAction::ShowBugReport => {
+                let report = bug_report::build(app_state, &ui_state.action_log);
+                ui_state.active_modal = Some(ModalRequest::BugReport { report });
+            }
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use numelace_core::{Digit, DigitGrid, Position};
-    use numelace_game::{CellState, Game};
+    use std::future;
 
-    use super::handle;
+    use numelace_core::{
+        ChessRules, Digit, DigitGrid, DigitPositions, DigitSet, Position, RegionLayout,
+    };
+    use numelace_game::{CellState, Game, InputDigitOptions};
+    use numelace_generator::{GeneratedPuzzle, PuzzleSeed};
+
+    use super::{handle, handle_replayed};
     use crate::{
-        action::{BoardMutationAction, ConfirmKind, ModalRequest, NotesFillScope, UiAction},
-        state::{AppState, GhostType, UiState},
+        action::{
+            Action, AppAction, BoardMutationAction, ConfirmKind, FlowAction, HistoryAction,
+            ModalRequest, MoveDirection, NoteKind, NotesFillScope, NotificationKind,
+            PuzzleLifecycleAction, ScriptGoal, ScriptPrompt, ScriptResult, SelectionAction,
+            StateQueryAction, UiAction, UpdateStateAction,
+        },
+        flow::{FlowGroup, FlowPriority},
+        i18n::Text,
+        state::{
+            AppState, CampaignPuzzle, DifficultyPreset, GameMode, GhostType, InputMode,
+            NewGameOptions, Player, PuzzleDifficulty, PuzzleSource, ReadOnlyReason, ScratchStroke,
+            ScratchTool, SolveStepKind, TimedGame, UiState, ValidationMode, fingerprint,
+        },
     };
 
     fn fixed_game() -> Game {
@@ -342,7 +842,7 @@ mod tests {
         .parse()
         .unwrap();
         let solution: DigitGrid =
-            "185362947793148526246795183564239871931874265827516394318427659672951438459683712"
+            "815362947793841526246795813564239178938174265127586394381427659672958431459613782"
                 .parse()
                 .unwrap();
         let filled: DigitGrid = "\
@@ -362,6 +862,114 @@ mod tests {
         Game::from_problem_filled_notes(&problem, &solution, &filled, &notes).unwrap()
     }
 
+    #[test]
+    fn script_prompt_is_answered_once_its_goal_is_met() {
+        let mut app_state = AppState::new(fixed_game());
+        let mut ui_state = UiState::new();
+        let (responder, mut receiver) = futures_channel::oneshot::channel();
+        ui_state.script_prompt = Some(ScriptPrompt {
+            text: Text::TutorialPlaceDigit,
+            goal: Some(ScriptGoal::PlaceDigit),
+            step: 0,
+            step_count: 1,
+            responder: Some(responder),
+        });
+        let request = |row, col| BoardMutationAction::RequestDigit {
+            digit: Some(Digit::D8),
+            swap_input_mode: false,
+            position: Some(Position::new(row, col)),
+        };
+
+        // Entering a digit on a given cell fills nothing.
+        handle(&mut app_state, &mut ui_state, request(0, 1).into());
+        assert_eq!(receiver.try_recv().unwrap(), None);
+        handle(&mut app_state, &mut ui_state, request(0, 0).into());
+        assert_eq!(receiver.try_recv().unwrap(), Some(ScriptResult::Completed));
+    }
+
+    #[test]
+    fn armed_digit_is_entered_into_tapped_cells() {
+        let mut app_state = AppState::new(fixed_game());
+        let mut ui_state = UiState::new();
+
+        handle(
+            &mut app_state,
+            &mut ui_state,
+            SelectionAction::ToggleArmedDigit(Digit::D2).into(),
+        );
+        assert_eq!(app_state.armed_digit(), Some(Digit::D2));
+        assert_eq!(app_state.selected_digit(), Some(Digit::D2));
+
+        for pos in [Position::new(0, 0), Position::new(1, 3)] {
+            let digit = app_state.armed_digit();
+            handle(
+                &mut app_state,
+                &mut ui_state,
+                BoardMutationAction::RequestDigit {
+                    digit,
+                    swap_input_mode: false,
+                    position: Some(pos),
+                }
+                .into(),
+            );
+            assert!(matches!(
+                app_state.game.cell(pos),
+                CellState::Filled(Digit::D2)
+            ));
+        }
+        assert_eq!(app_state.armed_digit(), Some(Digit::D2));
+
+        handle(
+            &mut app_state,
+            &mut ui_state,
+            AppAction::CancelContextual.into(),
+        );
+        assert_eq!(app_state.armed_digit(), None);
+        assert_eq!(app_state.selected_cell(), Some(Position::new(1, 3)));
+    }
+
+    #[test]
+    fn scratchpad_is_set_aside_by_cancel_and_cleared_by_a_new_puzzle() {
+        let mut app_state = AppState::new(fixed_game());
+        let mut ui_state = UiState::new();
+        handle(
+            &mut app_state,
+            &mut ui_state,
+            SelectionAction::ToggleArmedDigit(Digit::D2).into(),
+        );
+        handle(
+            &mut app_state,
+            &mut ui_state,
+            UiAction::ToggleScratchTool(ScratchTool::Line).into(),
+        );
+        handle(
+            &mut app_state,
+            &mut ui_state,
+            UiAction::AddScratchStroke(ScratchStroke::Line {
+                from: Position::new(0, 0),
+                to: Position::new(4, 4),
+            })
+            .into(),
+        );
+
+        // Cancelling leaves the scratchpad tool before the armed digit.
+        handle(
+            &mut app_state,
+            &mut ui_state,
+            AppAction::CancelContextual.into(),
+        );
+        assert_eq!(ui_state.scratchpad.tool, None);
+        assert_eq!(app_state.armed_digit(), Some(Digit::D2));
+        assert_eq!(ui_state.scratchpad.strokes().len(), 1);
+
+        handle(
+            &mut app_state,
+            &mut ui_state,
+            PuzzleLifecycleAction::LoadGame(Box::new(fixed_game())).into(),
+        );
+        assert_eq!(ui_state.scratchpad.strokes(), []);
+    }
+
     #[test]
     fn conflicting_digit_sets_ghost_and_requests_save() {
         let mut app_state = AppState::new(fixed_game());
@@ -391,68 +999,338 @@ mod tests {
     }
 
     #[test]
-    fn auto_fill_cell_without_selection_is_noop() {
+    fn solution_validation_blocks_wrong_digit() {
         let mut app_state = AppState::new(fixed_game());
+        app_state.settings.assist.block_rule_violations = true;
+        app_state.settings.assist.validation_mode = ValidationMode::Solution;
         let mut ui_state = UiState::new();
-        let before = app_state.game.clone();
+        // The solution has 7 at (1, 0); 2 breaks no rule there.
+        let position = Position::new(1, 0);
 
-        handle(
-            &mut app_state,
-            &mut ui_state,
-            BoardMutationAction::AutoFillNotes {
-                scope: NotesFillScope::SelectedCell,
+        let request = |digit| {
+            BoardMutationAction::RequestDigit {
+                digit: Some(digit),
+                swap_input_mode: false,
+                position: Some(position),
             }
-            .into(),
+            .into()
+        };
+
+        handle(&mut app_state, &mut ui_state, request(Digit::D2));
+        assert_eq!(
+            ui_state.conflict_ghost,
+            Some((position, GhostType::Digit(Digit::D2)))
         );
+        assert!(app_state.game.cell(position).is_empty());
 
-        assert_eq!(app_state.game, before);
+        handle(&mut app_state, &mut ui_state, request(Digit::D7));
+        assert_eq!(app_state.game.cell(position).as_digit(), Some(Digit::D7));
     }
 
     #[test]
-    fn reset_current_puzzle_auto_fills_notes_when_enabled() {
+    fn toggle_note_ignores_the_input_mode() {
         let mut app_state = AppState::new(fixed_game());
-        app_state
-            .settings
-            .assist
-            .notes
-            .auto_fill_notes_on_new_or_reset = true;
         let mut ui_state = UiState::new();
+        let position = Position::new(1, 0);
+        app_state.set_selected_cell(position);
+        assert_eq!(app_state.input_mode, InputMode::Fill);
 
-        handle(
-            &mut app_state,
-            &mut ui_state,
-            BoardMutationAction::ResetInputs.into(),
+        let toggle = || {
+            BoardMutationAction::ToggleNote {
+                digit: Digit::D7,
+                kind: NoteKind::Corner,
+            }
+            .into()
+        };
+        handle(&mut app_state, &mut ui_state, toggle());
+        assert_eq!(
+            app_state.game.cell(position).as_notes(),
+            Some(DigitSet::from_elem(Digit::D7))
         );
-
-        let any_notes = Position::ALL
-            .into_iter()
-            .any(|pos| app_state.game.cell(pos).is_notes());
-        assert!(any_notes);
+        handle(&mut app_state, &mut ui_state, toggle());
+        assert!(app_state.game.cell(position).is_empty());
     }
 
     #[test]
-    fn same_digit_request_does_not_add_history_entry() {
+    fn notes_are_toggled_across_the_selection() {
         let mut app_state = AppState::new(fixed_game());
-        app_state.set_selected_cell(Position::new(0, 0));
         let mut ui_state = UiState::new();
-
+        let (first, second) = (Position::new(1, 0), Position::new(2, 0));
+        app_state.set_selected_cell(first);
         handle(
             &mut app_state,
             &mut ui_state,
-            BoardMutationAction::RequestDigit {
-                digit: Some(Digit::D2),
-                swap_input_mode: false,
-                position: None,
-            }
-            .into(),
+            SelectionAction::ToggleCellInSelection(second).into(),
         );
+        let toggle = |kind| BoardMutationAction::ToggleNote {
+            digit: Digit::D4,
+            kind,
+        };
 
+        // A cell missing the note gets it; once all have it, it is removed.
         handle(
             &mut app_state,
             &mut ui_state,
-            BoardMutationAction::RequestDigit {
-                digit: Some(Digit::D2),
-                swap_input_mode: false,
+            toggle(NoteKind::Corner).into(),
+        );
+        handle(
+            &mut app_state,
+            &mut ui_state,
+            SelectionAction::ToggleCellInSelection(Position::new(3, 0)).into(),
+        );
+        handle(
+            &mut app_state,
+            &mut ui_state,
+            toggle(NoteKind::Corner).into(),
+        );
+        assert_eq!(
+            app_state.game.cell(Position::new(3, 0)).as_notes(),
+            Some(DigitSet::from_elem(Digit::D4))
+        );
+        handle(
+            &mut app_state,
+            &mut ui_state,
+            toggle(NoteKind::Corner).into(),
+        );
+        for pos in app_state.selected_cells() {
+            assert!(app_state.game.cell(pos).is_empty());
+        }
+
+        handle(
+            &mut app_state,
+            &mut ui_state,
+            toggle(NoteKind::Center).into(),
+        );
+        for pos in [first, second] {
+            assert!(app_state.game.cell(pos).is_empty());
+            assert_eq!(
+                app_state.game.center_notes(pos),
+                DigitSet::from_elem(Digit::D4)
+            );
+        }
+    }
+
+    #[test]
+    fn selection_is_extended_and_shrunk() {
+        let mut app_state = AppState::new(fixed_game());
+        let mut ui_state = UiState::new();
+        app_state.set_selected_cell(Position::new(4, 4));
+        handle(
+            &mut app_state,
+            &mut ui_state,
+            SelectionAction::ExtendSelection(MoveDirection::Right).into(),
+        );
+        assert_eq!(app_state.selected_cell(), Some(Position::new(4, 5)));
+        assert_eq!(app_state.selected_cells().len(), 2);
+
+        handle(
+            &mut app_state,
+            &mut ui_state,
+            SelectionAction::ToggleCellInSelection(Position::new(4, 5)).into(),
+        );
+        assert_eq!(app_state.selected_cell(), Some(Position::new(4, 4)));
+        assert_eq!(
+            app_state.selected_cells(),
+            DigitPositions::from_elem(Position::new(4, 4))
+        );
+    }
+
+    #[test]
+    fn auto_fill_and_clear_cover_the_selection() {
+        let mut app_state = AppState::new(fixed_game());
+        let mut ui_state = UiState::new();
+        let cells = [Position::new(1, 0), Position::new(5, 5)];
+        app_state.set_selected_cell(cells[0]);
+        app_state.toggle_cell_in_selection(cells[1]);
+
+        handle(
+            &mut app_state,
+            &mut ui_state,
+            BoardMutationAction::AutoFillNotes {
+                scope: NotesFillScope::SelectedCell,
+            }
+            .into(),
+        );
+        for pos in cells {
+            assert!(app_state.game.cell(pos).is_notes());
+        }
+
+        handle(
+            &mut app_state,
+            &mut ui_state,
+            BoardMutationAction::ClearCell.into(),
+        );
+        for pos in cells {
+            assert!(app_state.game.cell(pos).is_empty());
+        }
+    }
+
+    #[test]
+    fn auto_fill_cell_without_selection_is_noop() {
+        let mut app_state = AppState::new(fixed_game());
+        let mut ui_state = UiState::new();
+        let before = app_state.game.clone();
+
+        handle(
+            &mut app_state,
+            &mut ui_state,
+            BoardMutationAction::AutoFillNotes {
+                scope: NotesFillScope::SelectedCell,
+            }
+            .into(),
+        );
+
+        assert_eq!(app_state.game, before);
+    }
+
+    #[test]
+    fn reset_current_puzzle_auto_fills_notes_when_enabled() {
+        let mut app_state = AppState::new(fixed_game());
+        app_state
+            .settings
+            .assist
+            .notes
+            .auto_fill_notes_on_new_or_reset = true;
+        let mut ui_state = UiState::new();
+
+        handle(
+            &mut app_state,
+            &mut ui_state,
+            BoardMutationAction::ResetInputs.into(),
+        );
+
+        let any_notes = Position::ALL
+            .into_iter()
+            .any(|pos| app_state.game.cell(pos).is_notes());
+        assert!(any_notes);
+    }
+
+    #[test]
+    fn clear_mistakes_keeps_correct_digits_in_one_history_entry() {
+        let mut app_state = AppState::new(fixed_game());
+        let mut ui_state = UiState::new();
+        for (position, digit) in [
+            (Position::new(1, 0), Digit::D7),
+            (Position::new(1, 1), Digit::D2),
+        ] {
+            handle(
+                &mut app_state,
+                &mut ui_state,
+                BoardMutationAction::RequestDigit {
+                    digit: Some(digit),
+                    swap_input_mode: false,
+                    position: Some(position),
+                }
+                .into(),
+            );
+        }
+
+        handle(
+            &mut app_state,
+            &mut ui_state,
+            BoardMutationAction::ClearMistakes.into(),
+        );
+
+        assert_eq!(
+            app_state.game.cell(Position::new(1, 0)).as_digit(),
+            Some(Digit::D7)
+        );
+        assert!(app_state.game.cell(Position::new(1, 1)).is_empty());
+        assert!(app_state.undo());
+        assert_eq!(
+            app_state.game.cell(Position::new(1, 1)).as_digit(),
+            Some(Digit::D2)
+        );
+    }
+
+    #[test]
+    fn remove_illegal_notes_in_one_history_entry() {
+        let problem: DigitGrid = format!(".1{}", ".".repeat(79)).parse().unwrap();
+        let mut notes = [[0u16; 9]; 9];
+        notes[0][8] = 0b0_0000_0011;
+        let game =
+            Game::from_problem_filled_notes(&problem, &DigitGrid::new(), &DigitGrid::new(), &notes)
+                .unwrap();
+        let mut app_state = AppState::new(game);
+        let mut ui_state = UiState::new();
+        let position = Position::new(0, 8);
+        assert_eq!(app_state.game.illegal_notes().len(), 1);
+
+        handle(
+            &mut app_state,
+            &mut ui_state,
+            BoardMutationAction::RemoveIllegalNotes.into(),
+        );
+
+        assert_eq!(
+            app_state.game.cell(position).as_notes(),
+            Some(DigitSet::from_iter([Digit::D2]))
+        );
+        assert!(app_state.undo());
+        assert_eq!(app_state.game.illegal_notes().len(), 1);
+    }
+
+    #[test]
+    fn reveals_fill_answers_and_are_counted() {
+        let mut app_state = AppState::new(fixed_game());
+        let mut ui_state = UiState::new();
+        let position = Position::new(1, 1);
+        let answer = app_state.game.solution_digit_at(position);
+
+        handle(
+            &mut app_state,
+            &mut ui_state,
+            BoardMutationAction::RevealCell { position }.into(),
+        );
+        assert_eq!(app_state.game.cell(position).as_digit(), answer);
+        assert_eq!(app_state.hint_usage.reveals(), 1);
+
+        // Revealing an answered cell changes nothing and is not counted.
+        handle(
+            &mut app_state,
+            &mut ui_state,
+            BoardMutationAction::RevealCell { position }.into(),
+        );
+        assert_eq!(app_state.hint_usage.reveals(), 1);
+
+        handle(
+            &mut app_state,
+            &mut ui_state,
+            BoardMutationAction::RevealAll.into(),
+        );
+        let game = &app_state.game;
+        assert!(Position::ALL.into_iter().all(|pos| {
+            game.cell(pos).is_given() || game.cell(pos).as_digit() == game.solution_digit_at(pos)
+        }));
+        assert_eq!(app_state.hint_usage.reveals(), 2);
+        assert!(app_state.undo());
+        assert_eq!(app_state.game.cell(position).as_digit(), answer);
+        assert!(app_state.game.cell(Position::new(0, 0)).is_empty());
+    }
+
+    #[test]
+    fn same_digit_request_does_not_add_history_entry() {
+        let mut app_state = AppState::new(fixed_game());
+        app_state.set_selected_cell(Position::new(0, 0));
+        let mut ui_state = UiState::new();
+
+        handle(
+            &mut app_state,
+            &mut ui_state,
+            BoardMutationAction::RequestDigit {
+                digit: Some(Digit::D2),
+                swap_input_mode: false,
+                position: None,
+            }
+            .into(),
+        );
+
+        handle(
+            &mut app_state,
+            &mut ui_state,
+            BoardMutationAction::RequestDigit {
+                digit: Some(Digit::D2),
+                swap_input_mode: false,
                 position: None,
             }
             .into(),
@@ -480,4 +1358,543 @@ mod tests {
 
         assert!(ui_state.active_modal.is_none());
     }
+
+    #[test]
+    fn load_game_replaces_game_and_resets_history() {
+        let mut app_state = AppState::new(Game::new_empty());
+        let mut ui_state = UiState::new();
+        app_state.set_selected_cell(Position::new(0, 0));
+        app_state.difficulty = Some(PuzzleDifficulty::BeyondTechniques);
+        app_state.seed = Some(PuzzleSeed::from([1; 32]));
+        app_state.generated_with = Some(NewGameOptions::default());
+
+        handle(
+            &mut app_state,
+            &mut ui_state,
+            PuzzleLifecycleAction::LoadGame(Box::new(fixed_game())).into(),
+        );
+
+        assert!(app_state.game.is_initialized());
+        assert!(matches!(
+            app_state.game.cell(Position::new(0, 1)),
+            CellState::Given(Digit::D1)
+        ));
+        assert_eq!(app_state.selected_cell(), None);
+        assert!(!app_state.can_undo());
+        assert_eq!(app_state.difficulty, None);
+        assert!(app_state.seed.is_none());
+        assert!(app_state.generated_with.is_none());
+    }
+
+    #[test]
+    fn new_game_keeps_its_options_apart_from_the_saved_ones() {
+        let mut app_state = AppState::new(Game::new_empty());
+        let mut ui_state = UiState::new();
+        let solution = almost_solved_game().solution().clone();
+        let mut problem = solution.clone();
+        problem.set(Position::new(0, 0), None);
+        let puzzle = GeneratedPuzzle {
+            seed: PuzzleSeed::from([1; 32]),
+            problem,
+            solution,
+            regions: RegionLayout::standard(),
+        };
+        let generated_with = NewGameOptions {
+            seed: puzzle.seed.to_string(),
+            anti_king: true,
+            ..NewGameOptions::default()
+        };
+
+        handle(
+            &mut app_state,
+            &mut ui_state,
+            PuzzleLifecycleAction::StartNewGame {
+                puzzle: Box::new(puzzle),
+                chess: ChessRules::new(),
+                difficulty: DifficultyPreset::Basic,
+                mode: GameMode::Classic,
+                generated_with: Some(Box::new(generated_with.clone())),
+            }
+            .into(),
+        );
+
+        assert_eq!(app_state.generated_with, Some(generated_with));
+        assert_eq!(app_state.new_game_options, NewGameOptions::default());
+    }
+
+    /// Game missing only the 8 at (0, 1).
+    fn almost_solved_game() -> Game {
+        let solution: DigitGrid =
+            "185362947793148526246795183564239871931874265827516394318427659672951438459683712"
+                .parse()
+                .unwrap();
+        let mut problem = solution.clone();
+        problem.set(Position::new(0, 1), None);
+        Game::from_problem_filled_notes(&problem, &solution, &DigitGrid::new(), &[[0; 9]; 9])
+            .unwrap()
+    }
+
+    fn solved_game() -> Game {
+        let mut game = almost_solved_game();
+        game.set_digit(
+            Position::new(0, 1),
+            Digit::D8,
+            &InputDigitOptions::default(),
+        )
+        .unwrap();
+        game
+    }
+
+    fn fill_last_cell() -> Action {
+        BoardMutationAction::RequestDigit {
+            digit: Some(Digit::D8),
+            swap_input_mode: false,
+            position: Some(Position::new(0, 1)),
+        }
+        .into()
+    }
+
+    #[test]
+    fn solving_timed_game_stops_clock_and_shows_result() {
+        let mut app_state = AppState::new(almost_solved_game());
+        app_state.timed = Some(TimedGame::default());
+        let mut ui_state = UiState::new();
+
+        let difficulty = PuzzleDifficulty::Rated(DifficultyPreset::Basic);
+        handle(
+            &mut app_state,
+            &mut ui_state,
+            UpdateStateAction::SetPuzzleDifficulty(Some(difficulty)).into(),
+        );
+        let timed = app_state.timed.unwrap();
+        assert!(timed.par.is_some_and(|par| !par.is_zero()));
+        assert!(!timed.finished);
+
+        handle(&mut app_state, &mut ui_state, fill_last_cell());
+        assert!(app_state.timed.unwrap().finished);
+        assert!(matches!(
+            ui_state.active_modal,
+            Some(ModalRequest::SolvedResult)
+        ));
+    }
+
+    #[test]
+    fn solving_wraps_up_the_puzzle_once() {
+        let mut app_state = AppState::new(almost_solved_game());
+        let mut ui_state = UiState::new();
+        let basic = PuzzleDifficulty::Rated(DifficultyPreset::Basic);
+        app_state.set_difficulty(Some(basic));
+
+        handle(&mut app_state, &mut ui_state, fill_last_cell());
+        assert_eq!(app_state.puzzle_metadata.solved_count(), 1);
+        assert!(matches!(
+            ui_state.active_modal,
+            Some(ModalRequest::SolvedResult)
+        ));
+        ui_state.active_modal = None;
+
+        // The board stays editable, so the puzzle can be solved again. A
+        // second wrap-up would record the new difficulty and show the result
+        // again.
+        let expert = PuzzleDifficulty::Rated(DifficultyPreset::Expert);
+        app_state.set_difficulty(Some(expert));
+        handle(&mut app_state, &mut ui_state, HistoryAction::Undo.into());
+        handle(&mut app_state, &mut ui_state, fill_last_cell());
+        assert!(app_state.game.is_solved());
+        let entry = app_state
+            .puzzle_metadata
+            .get(fingerprint(&app_state.game).unwrap())
+            .unwrap();
+        assert_eq!(entry.difficulty, Some(basic));
+        assert_eq!(app_state.puzzle_metadata.solved_count(), 1);
+        assert!(ui_state.active_modal.is_none());
+        assert_eq!(ui_state.notifications.active().len(), 0);
+
+        // Loading a solved board is not solving it.
+        let mut app_state = AppState::new(Game::new_empty());
+        let mut ui_state = UiState::new();
+        let solved = Box::new(solved_game());
+        handle(
+            &mut app_state,
+            &mut ui_state,
+            PuzzleLifecycleAction::LoadGame(solved).into(),
+        );
+        assert_eq!(app_state.puzzle_metadata.solved_count(), 0);
+        assert!(ui_state.active_modal.is_none());
+    }
+
+    #[test]
+    fn loading_switches_the_assist_profile() {
+        let expert = PuzzleDifficulty::Rated(DifficultyPreset::Expert);
+        let mut app_state = AppState::new(Game::new_empty());
+        let mut ui_state = UiState::new();
+        app_state.settings.assist_profiles.enabled = true;
+        app_state
+            .settings
+            .switch_assist_profile(DifficultyPreset::Basic);
+        assert!(app_state.settings.assist.block_rule_violations);
+
+        // A puzzle rated before switches as soon as it is loaded.
+        app_state
+            .puzzle_metadata
+            .add(
+                fingerprint(&fixed_game()).unwrap(),
+                PuzzleSource::Shared,
+                "2026-10-15",
+            )
+            .difficulty = Some(expert);
+        handle(
+            &mut app_state,
+            &mut ui_state,
+            PuzzleLifecycleAction::LoadGame(Box::new(fixed_game())).into(),
+        );
+        assert_eq!(app_state.difficulty, Some(expert));
+        assert_eq!(
+            app_state.settings.assist_profiles.active,
+            Some(DifficultyPreset::Expert)
+        );
+        assert!(!app_state.settings.assist.block_rule_violations);
+        assert!(!app_state.settings.assist.highlight.conflict);
+
+        // Other puzzles keep the current profile until their grade arrives.
+        let basic = PuzzleDifficulty::Rated(DifficultyPreset::Basic);
+        handle(
+            &mut app_state,
+            &mut ui_state,
+            PuzzleLifecycleAction::LoadGame(Box::new(solved_game())).into(),
+        );
+        assert_eq!(app_state.difficulty, None);
+        assert!(!app_state.settings.assist.block_rule_violations);
+        handle(
+            &mut app_state,
+            &mut ui_state,
+            UpdateStateAction::SwitchAssistProfile(basic).into(),
+        );
+        assert_eq!(
+            app_state.settings.assist_profiles.active,
+            Some(DifficultyPreset::Basic)
+        );
+        assert!(app_state.settings.assist.block_rule_violations);
+    }
+
+    #[test]
+    fn race_passes_board_and_shows_result_when_both_finish() {
+        let mut app_state = AppState::new(almost_solved_game());
+        app_state.start_race();
+        let mut ui_state = UiState::new();
+
+        handle(&mut app_state, &mut ui_state, AppAction::PassTurn.into());
+        assert_eq!(app_state.race.as_ref().unwrap().current(), Player::Two);
+
+        handle(&mut app_state, &mut ui_state, fill_last_cell());
+        let race = app_state.race.as_ref().unwrap();
+        assert!(race.clock(Player::Two).finished);
+        assert_eq!(race.current(), Player::One);
+        assert!(!app_state.game.is_solved());
+        assert_eq!(ui_state.notifications.active().len(), 1);
+        assert!(ui_state.active_modal.is_none());
+
+        // The finished player's board cannot be handed back.
+        handle(&mut app_state, &mut ui_state, AppAction::PassTurn.into());
+        assert_eq!(app_state.race.as_ref().unwrap().current(), Player::One);
+
+        handle(&mut app_state, &mut ui_state, fill_last_cell());
+        assert!(app_state.race.as_ref().unwrap().is_finished());
+        assert!(matches!(
+            ui_state.active_modal,
+            Some(ModalRequest::RaceResult)
+        ));
+    }
+
+    #[test]
+    fn solved_game_stays_editable_until_the_review_starts() {
+        let mut app_state = AppState::new(almost_solved_game());
+        let mut ui_state = UiState::new();
+
+        handle(&mut app_state, &mut ui_state, fill_last_cell());
+        assert!(app_state.game.is_solved());
+        assert_eq!(ui_state.read_only_reason(&app_state), None);
+        handle(&mut app_state, &mut ui_state, HistoryAction::Undo.into());
+        assert!(!app_state.game.is_solved());
+        handle(&mut app_state, &mut ui_state, fill_last_cell());
+        assert!(app_state.game.is_solved());
+
+        handle(
+            &mut app_state,
+            &mut ui_state,
+            UpdateStateAction::StartReview.into(),
+        );
+        assert_eq!(
+            ui_state.read_only_reason(&app_state),
+            Some(ReadOnlyReason::Review)
+        );
+
+        // Moves are dropped, but the board can still be browsed.
+        handle(&mut app_state, &mut ui_state, HistoryAction::Undo.into());
+        handle(
+            &mut app_state,
+            &mut ui_state,
+            BoardMutationAction::ClearCell.into(),
+        );
+        assert!(app_state.game.is_solved());
+        let position = Position::new(4, 4);
+        handle(
+            &mut app_state,
+            &mut ui_state,
+            SelectionAction::SelectOrClearCell(position).into(),
+        );
+        assert_eq!(app_state.selected_cell(), Some(position));
+
+        handle(
+            &mut app_state,
+            &mut ui_state,
+            PuzzleLifecycleAction::LoadGame(Box::new(almost_solved_game())).into(),
+        );
+        assert_eq!(ui_state.read_only_reason(&app_state), None);
+        handle(&mut app_state, &mut ui_state, fill_last_cell());
+        assert!(app_state.game.is_solved());
+    }
+
+    #[test]
+    fn check_progress_compares_digits_with_the_solution() {
+        let check = |game: Game| {
+            let mut app_state = AppState::new(game);
+            let mut ui_state = UiState::new();
+            handle(
+                &mut app_state,
+                &mut ui_state,
+                StateQueryAction::CheckProgress.into(),
+            );
+            let kinds: Vec<_> = ui_state
+                .notifications
+                .active()
+                .iter()
+                .map(|entry| entry.notification.kind)
+                .collect();
+            kinds
+        };
+
+        assert_eq!(
+            check(almost_solved_game()),
+            [NotificationKind::ProgressOnTrack]
+        );
+        let mut game = almost_solved_game();
+        game.set_digit(
+            Position::new(0, 1),
+            Digit::D9,
+            &InputDigitOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(check(game), [NotificationKind::ProgressOffTrack]);
+        assert_eq!(
+            check(Game::new_empty()),
+            [NotificationKind::ProgressCheckUnavailable]
+        );
+    }
+
+    #[test]
+    fn solve_log_records_moves_for_the_review() {
+        let mut app_state = AppState::new(fixed_game());
+        let mut ui_state = UiState::new();
+        handle(
+            &mut app_state,
+            &mut ui_state,
+            PuzzleLifecycleAction::LoadGame(Box::new(almost_solved_game())).into(),
+        );
+        // Any other digit conflicts in the full row, so let it through.
+        app_state.settings.assist.block_rule_violations = false;
+
+        let mistake = BoardMutationAction::RequestDigit {
+            digit: Some(Digit::D9),
+            swap_input_mode: false,
+            position: Some(Position::new(0, 1)),
+        };
+        handle(&mut app_state, &mut ui_state, mistake.into());
+        handle(&mut app_state, &mut ui_state, HistoryAction::Undo.into());
+        handle(
+            &mut app_state,
+            &mut ui_state,
+            SelectionAction::SelectOrClearCell(Position::new(4, 4)).into(),
+        );
+        handle(&mut app_state, &mut ui_state, fill_last_cell());
+
+        let kinds: Vec<_> = ui_state
+            .solve_log
+            .steps()
+            .iter()
+            .map(|step| step.kind)
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                SolveStepKind::Mistake,
+                SolveStepKind::Undo,
+                SolveStepKind::Move
+            ]
+        );
+
+        handle(
+            &mut app_state,
+            &mut ui_state,
+            UiAction::ReviewSolveStep(1).into(),
+        );
+        assert_eq!(ui_state.solve_log.reviewed(), Some(1));
+        let reviewed = ui_state.solve_log.game_at(1, &app_state.game).unwrap();
+        assert_eq!(
+            reviewed.cell(Position::new(0, 1)).as_digit(),
+            Some(Digit::D9)
+        );
+
+        handle(
+            &mut app_state,
+            &mut ui_state,
+            PuzzleLifecycleAction::LoadGame(Box::new(almost_solved_game())).into(),
+        );
+        assert_eq!(ui_state.solve_log.steps().len(), 0);
+        assert_eq!(ui_state.solve_log.reviewed(), None);
+    }
+
+    fn reviewed_game() -> (AppState, UiState) {
+        let app_state = AppState::new(fixed_game());
+        let mut ui_state = UiState::new();
+        ui_state.read_only = Some(ReadOnlyReason::Review);
+        (app_state, ui_state)
+    }
+
+    /// Returns whether a modal flow is running, by trying to start another one.
+    fn is_modal_flow_running(ui_state: &mut UiState) -> bool {
+        ui_state
+            .executor
+            .spawn(FlowGroup::Modal, FlowPriority::Normal, |_| {
+                future::pending()
+            })
+            .is_none()
+    }
+
+    #[test]
+    fn hint_flow_does_not_start_in_a_read_only_game() {
+        let (mut app_state, mut ui_state) = reviewed_game();
+        handle(
+            &mut app_state,
+            &mut ui_state,
+            Action::Flow(FlowAction::Hint),
+        );
+        assert!(!is_modal_flow_running(&mut ui_state));
+
+        ui_state.read_only = None;
+        handle(
+            &mut app_state,
+            &mut ui_state,
+            Action::Flow(FlowAction::Hint),
+        );
+        assert!(is_modal_flow_running(&mut ui_state));
+    }
+
+    #[test]
+    fn reveal_all_flow_does_not_start_in_a_read_only_game() {
+        let (mut app_state, mut ui_state) = reviewed_game();
+        handle(
+            &mut app_state,
+            &mut ui_state,
+            Action::Flow(FlowAction::RevealAll),
+        );
+        assert!(!is_modal_flow_running(&mut ui_state));
+
+        ui_state.read_only = None;
+        handle(
+            &mut app_state,
+            &mut ui_state,
+            Action::Flow(FlowAction::RevealAll),
+        );
+        assert!(is_modal_flow_running(&mut ui_state));
+    }
+
+    #[test]
+    fn audit_notes_flow_does_not_start_in_a_read_only_game() {
+        let (mut app_state, mut ui_state) = reviewed_game();
+        handle(
+            &mut app_state,
+            &mut ui_state,
+            Action::Flow(FlowAction::AuditNotes),
+        );
+        assert!(!is_modal_flow_running(&mut ui_state));
+
+        ui_state.read_only = None;
+        handle(
+            &mut app_state,
+            &mut ui_state,
+            Action::Flow(FlowAction::AuditNotes),
+        );
+        assert!(is_modal_flow_running(&mut ui_state));
+    }
+
+    #[test]
+    fn hint_technique_is_not_recorded_in_a_read_only_game() {
+        let (mut app_state, mut ui_state) = reviewed_game();
+        let record = || {
+            UpdateStateAction::RecordHintTechnique {
+                key: "hidden_single".to_owned(),
+                cells: DigitPositions::EMPTY,
+            }
+            .into()
+        };
+
+        handle(&mut app_state, &mut ui_state, record());
+        assert!(app_state.hint_usage.is_empty());
+        assert_eq!(ui_state.solve_log.steps().len(), 0);
+
+        ui_state.read_only = None;
+        handle(&mut app_state, &mut ui_state, record());
+        assert_eq!(app_state.hint_usage.total(), 1);
+    }
+
+    #[test]
+    fn replayed_moves_pass_through_a_read_only_game() {
+        let mut app_state = AppState::new(almost_solved_game());
+        let mut ui_state = UiState::new();
+        ui_state.read_only = Some(ReadOnlyReason::Replay);
+
+        handle(&mut app_state, &mut ui_state, fill_last_cell());
+        assert!(!app_state.game.is_solved());
+
+        handle_replayed(&mut app_state, &mut ui_state, fill_last_cell());
+        assert!(app_state.game.is_solved());
+        assert_eq!(ui_state.read_only, Some(ReadOnlyReason::Replay));
+    }
+
+    #[test]
+    fn solving_campaign_puzzle_records_progress() {
+        let mut app_state = AppState::new(fixed_game());
+        let mut ui_state = UiState::new();
+        let puzzle = CampaignPuzzle { pack: 0, index: 0 };
+
+        handle(
+            &mut app_state,
+            &mut ui_state,
+            PuzzleLifecycleAction::StartCampaignPuzzle {
+                puzzle,
+                game: Box::new(almost_solved_game()),
+            }
+            .into(),
+        );
+        assert_eq!(app_state.campaign_puzzle, Some(puzzle));
+        assert!(app_state.difficulty.is_some());
+        assert!(!app_state.campaign.is_solved(puzzle));
+
+        handle(&mut app_state, &mut ui_state, fill_last_cell());
+        assert!(app_state.campaign.is_solved(puzzle));
+        assert!(
+            app_state
+                .campaign
+                .is_unlocked(CampaignPuzzle { pack: 0, index: 1 })
+        );
+        assert_eq!(ui_state.notifications.active().len(), 1);
+
+        handle(
+            &mut app_state,
+            &mut ui_state,
+            PuzzleLifecycleAction::LoadGame(Box::new(fixed_game())).into(),
+        );
+        assert_eq!(app_state.campaign_puzzle, None);
+    }
 }
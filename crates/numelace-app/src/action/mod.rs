@@ -1,21 +1,34 @@
 use std::mem;
 
-use numelace_core::{Digit, Position};
+use numelace_core::{
+    ChessRules, Digit, DigitPositions, DigitSet, Position, PositionIndexedArray, PuzzleFingerprint,
+};
 use numelace_game::Game;
-use numelace_generator::GeneratedPuzzle;
-use numelace_solver::BoxedTechniqueStep;
+use numelace_generator::{GeneratedPuzzle, GenerationTrace};
+use numelace_solver::{BoxedTechniqueStep, technique::TechniqueId};
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    state::{HintState, NewGameOptions, Settings},
-    worker::tasks::SolvabilityStatsDto,
+    export::ExportContent,
+    i18n::Text,
+    state::{
+        AppState, CampaignPuzzle, ClipboardContent, DifficultyPreset, GameMode, HintStage,
+        HintState, NewGameOptions, PuzzleDifficulty, ScratchStroke, ScratchTool, Screen, Settings,
+    },
+    worker::tasks::{ContradictionWitnessDto, SolvabilityStatsDto},
 };
 
 pub(crate) mod handler;
+pub(crate) mod recording;
 
 #[derive(Debug, derive_more::From)]
 pub(crate) enum Action {
+    /// Changes to the app state, recorded for replay.
     App(AppAction),
+    /// Changes to the UI state only.
     Ui(UiAction),
+    /// Side effects: flows that open dialogs, use the worker or reach the
+    /// platform. Their outcomes come back as app actions.
     Flow(FlowAction),
 }
 
@@ -29,6 +42,23 @@ pub(crate) enum AppAction {
     InputMode(InputModeAction),
     UpdateState(UpdateStateAction),
     CancelContextual,
+    /// Hands the board to the other player of a hot-seat race.
+    PassTurn,
+}
+
+impl AppAction {
+    /// Returns whether the action changes the board, its undo history or the
+    /// hints used on it, which a read-only game does not allow.
+    #[must_use]
+    pub(crate) fn is_move(&self) -> bool {
+        matches!(
+            self,
+            Self::BoardMutation(_)
+                | Self::History(_)
+                | Self::PassTurn
+                | Self::UpdateState(UpdateStateAction::RecordHintTechnique { .. })
+        )
+    }
 }
 
 #[derive(Debug)]
@@ -38,6 +68,11 @@ pub(crate) enum BoardMutationAction {
         swap_input_mode: bool,
         position: Option<Position>,
     },
+    /// Toggles a note in the selected cells, whatever the input mode.
+    ToggleNote {
+        digit: Digit,
+        kind: NoteKind,
+    },
     ClearCell,
     AdvanceCell {
         position: Option<Position>,
@@ -46,12 +81,32 @@ pub(crate) enum BoardMutationAction {
         scope: NotesFillScope,
     },
     ResetInputs,
+    ClearMistakes,
+    RemoveIllegalNotes,
+    RevealCell {
+        position: Position,
+    },
+    RevealAll,
     ApplyTechniqueStep(BoxedTechniqueStep),
 }
 
 #[derive(Debug)]
 pub(crate) enum PuzzleLifecycleAction {
-    StartNewGame(GeneratedPuzzle),
+    StartNewGame {
+        puzzle: Box<GeneratedPuzzle>,
+        chess: ChessRules,
+        difficulty: DifficultyPreset,
+        mode: GameMode,
+        /// Options that generate `puzzle` again; `None` for a bundled puzzle.
+        generated_with: Option<Box<NewGameOptions>>,
+    },
+    LoadGame(Box<Game>),
+    StartCampaignPuzzle {
+        puzzle: CampaignPuzzle,
+        game: Box<Game>,
+    },
+    /// Replaces the whole state, including settings and undo history.
+    RestoreState(Box<AppState>),
 }
 
 #[derive(Debug)]
@@ -63,13 +118,23 @@ pub(crate) enum HistoryAction {
 
 #[derive(Debug)]
 pub(crate) enum StateQueryAction {
-    BuildUndoGames { responder: UndoGamesResponder },
+    BuildUndoGames {
+        responder: UndoGamesResponder,
+    },
+    /// Compares the entered digits with the stored solution and reports the
+    /// result in a notification, without running the solver.
+    CheckProgress,
 }
 
 #[derive(Debug)]
 pub(crate) enum SelectionAction {
     SelectOrClearCell(Position),
+    /// Adds a cell to the selection, or removes it if it is selected.
+    ToggleCellInSelection(Position),
     MoveSelection(MoveDirection),
+    /// Moves the selected cell, keeping the cells selected so far.
+    ExtendSelection(MoveDirection),
+    ToggleArmedDigit(Digit),
 }
 
 #[derive(Debug)]
@@ -80,25 +145,128 @@ pub(crate) enum InputModeAction {
 #[derive(Debug)]
 pub(crate) enum UpdateStateAction {
     UpdateNewGameOptions(NewGameOptions),
-    UpdateSettings(Settings),
+    UpdateSettings(Box<Settings>),
+    SetPuzzleDifficulty(Option<PuzzleDifficulty>),
+    /// Switches the assist settings to the profile that fits a loaded puzzle
+    /// once its difficulty is known.
+    SwitchAssistProfile(PuzzleDifficulty),
+    /// Counts a hint showing the technique with the given step key, which
+    /// points at `cells`.
+    RecordHintTechnique {
+        key: String,
+        cells: DigitPositions,
+    },
+    ToggleCandidateHeatmap,
+    ClearHintUsage,
+    /// Records that the tutorial ran, so it is not started on launch again.
+    MarkTutorialSeen,
+    /// Adds a puzzle imported from a share link to the metadata store.
+    RecordImportedPuzzle(PuzzleFingerprint),
+    /// Keeps the solved puzzle on screen for review, read-only until another
+    /// puzzle is loaded.
+    StartReview,
 }
 
 #[derive(Debug)]
 pub(crate) enum UiAction {
+    ShowScreen(Screen),
     OpenModal(ModalRequest),
     CloseModal,
-    StartSpinner { id: SpinnerId, kind: SpinnerKind },
-    StopSpinner { id: SpinnerId },
+    StartSpinner {
+        id: SpinnerId,
+        kind: SpinnerKind,
+    },
+    StopSpinner {
+        id: SpinnerId,
+    },
     SetHintState(Option<HintState>),
     ClearHintState,
+    SetNoteAudit(Option<Vec<(Position, DigitSet)>>),
+    /// Shows the trial depths of the hardness overlay, or none if the
+    /// analysis gave up on the board.
+    SetDeductionDepths(Option<PositionIndexedArray<u8>>),
+    /// Opens the lesson for the technique on the Learn screen.
+    SelectLesson(TechniqueId),
+    /// Shows an example of the current lesson revealed up to `stage`.
+    ShowLessonExample {
+        example: usize,
+        stage: HintStage,
+    },
+    /// Shows the prompt of a scripted flow over the board.
+    ShowScriptPrompt(ScriptPrompt),
+    /// Answers the shown script prompt, e.g. when the player skips the script.
+    RespondScriptPrompt(ScriptResult),
+    ClearScriptPrompt,
+    /// Shows the generator trace of the new puzzle, in developer mode.
+    ShowGenerationTrace(Box<GenerationTrace>),
+    CloseGenerationTrace,
+    /// Selects a scratchpad tool, or deselects it if it is already selected.
+    ToggleScratchTool(ScratchTool),
+    AddScratchStroke(ScratchStroke),
+    ClearScratchpad,
+    /// Shows or hides the game info sidebar.
+    ToggleInfoPanel,
+    /// Shows the board after the given number of logged steps during the
+    /// post-solve review.
+    ReviewSolveStep(usize),
+    CopyToClipboard(ClipboardContent),
+    ShowNotification(Notification),
+    DismissNotification {
+        id: NotificationId,
+    },
 }
 
 #[derive(Debug)]
 pub(crate) enum FlowAction {
     StartNewGame,
+    /// Starts a new game with the seed of the current puzzle filled in, so
+    /// the same puzzle, or one from another entered seed, is generated.
+    RegeneratePuzzle,
     ResetInputs,
+    AuditNotes,
+    RevealSelectedCell,
+    RevealAll,
     CheckSolvability,
     Hint,
+    Export(ExportContent),
+    CopyShareLink {
+        include_progress: bool,
+    },
+    ShareBoardImage,
+    LoadSharedPuzzle(String),
+    /// Offers to restore progress recovered from the journal, then opens
+    /// the shared puzzle from the page URL, if any.
+    RecoverProgress {
+        state: Box<AppState>,
+        shared_code: Option<String>,
+    },
+    ExportSettings,
+    ImportSettings,
+    /// Starts a puzzle of the bundled packs after confirming.
+    StartCampaignPuzzle(CampaignPuzzle),
+    /// Opens the bug report bundle for copying into an issue.
+    ShowBugReport,
+    /// Walks the player through the basics on an easy puzzle.
+    StartTutorial,
+}
+
+impl FlowAction {
+    /// Returns whether the flow works towards a move, which a read-only game
+    /// does not allow.
+    ///
+    /// Such flows are not started at all, rather than asking for confirmation
+    /// or showing a hint and dropping the move at the end.
+    #[must_use]
+    pub(crate) fn is_move(&self) -> bool {
+        matches!(
+            self,
+            Self::ResetInputs
+                | Self::AuditNotes
+                | Self::RevealSelectedCell
+                | Self::RevealAll
+                | Self::Hint
+        )
+    }
 }
 
 impl From<BoardMutationAction> for Action {
@@ -159,6 +327,89 @@ pub(crate) enum SpinnerKind {
     CheckSolvability,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct NotificationId(u64);
+
+impl NotificationId {
+    #[must_use]
+    pub(crate) fn new(value: u64) -> Self {
+        Self(value)
+    }
+}
+
+/// Event reported by a notification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum NotificationKind {
+    ShareLinkCopied,
+    BoardImageCopied,
+    Autosaved,
+    SettingsExported,
+    SettingsImported,
+    NewGameFailed,
+    SolvabilityCheckFailed,
+    SettingsExportFailed,
+    SettingsImportFailed,
+    RaceTurnFinished,
+    CampaignPuzzleSolved,
+    CampaignPuzzleLoadFailed,
+    ProgressOnTrack,
+    ProgressOffTrack,
+    ProgressCheckUnavailable,
+}
+
+impl NotificationKind {
+    #[must_use]
+    pub(crate) fn level(self) -> NotificationLevel {
+        match self {
+            Self::ShareLinkCopied
+            | Self::BoardImageCopied
+            | Self::Autosaved
+            | Self::SettingsExported
+            | Self::SettingsImported
+            | Self::RaceTurnFinished
+            | Self::CampaignPuzzleSolved
+            | Self::ProgressOnTrack => NotificationLevel::Info,
+            Self::NewGameFailed
+            | Self::SolvabilityCheckFailed
+            | Self::SettingsExportFailed
+            | Self::SettingsImportFailed
+            | Self::CampaignPuzzleLoadFailed
+            | Self::ProgressOffTrack
+            | Self::ProgressCheckUnavailable => NotificationLevel::Error,
+        }
+    }
+}
+
+/// Severity of a notification; decides its styling and how long it stays on screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum NotificationLevel {
+    Info,
+    Error,
+}
+
+/// Non-modal message (toast) shown until dismissed or expired.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Notification {
+    pub(crate) kind: NotificationKind,
+    /// Untranslated details, e.g. a worker error message.
+    pub(crate) detail: Option<String>,
+}
+
+impl Notification {
+    #[must_use]
+    pub(crate) fn new(kind: NotificationKind) -> Self {
+        Self { kind, detail: None }
+    }
+
+    #[must_use]
+    pub(crate) fn with_detail(self, detail: impl Into<String>) -> Self {
+        Self {
+            detail: Some(detail.into()),
+            ..self
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, derive_more::IsVariant)]
 pub(crate) enum ConfirmResult {
     Confirmed,
@@ -175,16 +426,51 @@ pub(crate) type ConfirmResponder = Responder<ConfirmResult>;
 pub(crate) type AlertResponder = Responder<AlertResult>;
 pub(crate) type UndoGamesResponder = Responder<Vec<Game>>;
 pub(crate) type NewGameOptionsResponder = Responder<Option<NewGameOptions>>;
+pub(crate) type ScriptResponder = Responder<ScriptResult>;
 
+/// Player action a script step waits for.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ScriptGoal {
+    SelectCell,
+    PlaceDigit,
+    ToggleNote,
+    Undo,
+    RequestHint,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, derive_more::IsVariant)]
+pub(crate) enum ScriptResult {
+    /// The player reached the goal or read the prompt.
+    Completed,
+    Skipped,
+}
+
+/// Prompt of a scripted flow, shown until the flow moves on.
+#[derive(Debug)]
+pub(crate) struct ScriptPrompt {
+    pub(crate) text: Text,
+    /// Action the prompt waits for; `None` for prompts the player only reads.
+    pub(crate) goal: Option<ScriptGoal>,
+    /// Zero-based index of the step in the script.
+    pub(crate) step: usize,
+    pub(crate) step_count: usize,
+    pub(crate) responder: Option<ScriptResponder>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) enum ConfirmKind {
     NewGame,
     ResetInputs,
-    SolvabilityInconsistent,
-    SolvabilityNoSolution,
+    RevealCell,
+    RevealAll,
+    IllegalNotesFound { count: usize },
+    SolvabilityInconsistent { witness: ContradictionWitnessDto },
+    SolvabilityNoSolution { witness: ContradictionWitnessDto },
     SolvabilityNotesMaybeIncorrect,
-    HintInconsistent,
+    HintInconsistent { witness: ContradictionWitnessDto },
     HintNotesMaybeIncorrect,
+    LoadSharedPuzzle { solved_on: Option<String> },
+    RecoverProgress,
 }
 
 #[derive(Debug, Clone)]
@@ -193,11 +479,17 @@ pub(crate) enum AlertKind {
     SolvabilityUndoNotice { steps: usize },
     SolvabilityUndoNotFound,
     SolvabilityAlreadySolved,
+    SolvabilityUndetermined,
     HintUndoNotice { steps: usize },
     HintStuckNoStep,
     HintStuckAfterRollback,
     HintInconsistentAfterRollback,
     HintAlreadySolved,
+    HintUndetermined,
+    NoIllegalNotes,
+    ExportSaved { path: String },
+    ExportFailed { message: String },
+    SharedPuzzleInvalid { message: String },
 }
 
 #[derive(Debug)]
@@ -215,9 +507,15 @@ pub(crate) enum ModalRequest {
         responder: Option<NewGameOptionsResponder>,
     },
     Settings,
+    Statistics,
+    SolvedResult,
+    RaceResult,
+    BugReport {
+        report: String,
+    },
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, derive_more::IsVariant)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, derive_more::IsVariant, Serialize, Deserialize)]
 pub(crate) enum MoveDirection {
     Up,
     Down,
@@ -236,13 +534,26 @@ impl MoveDirection {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, derive_more::IsVariant)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, derive_more::IsVariant, Serialize, Deserialize)]
 pub(crate) enum NotesFillScope {
     AllCells,
     EmptyCells,
+    /// Every selected cell.
     SelectedCell,
 }
 
+/// Which of a cell's two sets of notes an input writes to.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Hash, derive_more::IsVariant, Serialize, Deserialize,
+)]
+pub(crate) enum NoteKind {
+    /// Candidates written in the corners of the cell.
+    #[default]
+    Corner,
+    /// Candidates written in the middle of the cell.
+    Center,
+}
+
 #[derive(Debug, Default)]
 pub(crate) struct ActionRequestQueue {
     actions: Vec<Action>,
@@ -0,0 +1,502 @@
+//! Recording app actions and replaying them.
+//!
+//! Only [`AppAction`]s are recorded. They are the state transitions of the
+//! app; UI and flow actions only drive dialogs, the worker and the platform,
+//! and whatever they decide comes back as an app action, which is recorded in
+//! turn. Replaying a recording against a fresh state therefore reproduces the
+//! board without running any flow.
+//!
+//! Puzzle lifecycle actions are recorded as the board and settings they leave
+//! behind, so a replay does not depend on the generator or the bundled packs.
+//! The timed and race modes of a new game are not part of the board and are
+//! not replayed.
+
+use std::{collections::VecDeque, time::Duration};
+
+use numelace_core::{Digit, Position, PositionNewError};
+use numelace_game::{Game, StateCodeError};
+use numelace_solver::TechniqueStepData;
+use serde::{Deserialize, Serialize};
+use web_time::Instant;
+
+use crate::{
+    action::{
+        Action, AppAction, BoardMutationAction, HistoryAction, InputModeAction, MoveDirection,
+        NoteKind, NotesFillScope, PuzzleLifecycleAction, SelectionAction, UpdateStateAction,
+    },
+    persistence::dto::{DigitDto, DigitParseError, PositionDto, SettingsDto},
+    state::Settings,
+};
+
+/// An app action in a form that can be written to a recording.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub(crate) enum RecordedAction {
+    RequestDigit {
+        digit: Option<DigitDto>,
+        swap_input_mode: bool,
+        position: Option<PositionDto>,
+    },
+    ToggleNote {
+        digit: DigitDto,
+        /// Absent in recordings made before center notes existed.
+        #[serde(default)]
+        kind: NoteKind,
+    },
+    ClearCell,
+    AdvanceCell {
+        position: Option<PositionDto>,
+    },
+    AutoFillNotes {
+        scope: NotesFillScope,
+    },
+    ResetInputs,
+    ClearMistakes,
+    RemoveIllegalNotes,
+    RevealCell {
+        position: PositionDto,
+    },
+    RevealAll,
+    ApplyTechniqueStep {
+        step: TechniqueStepData,
+    },
+    /// The board left behind by a puzzle lifecycle action, as a state code.
+    LoadGame {
+        code: String,
+    },
+    Undo,
+    UndoSteps {
+        steps: usize,
+    },
+    Redo,
+    SelectOrClearCell {
+        position: PositionDto,
+    },
+    ToggleCellInSelection {
+        position: PositionDto,
+    },
+    MoveSelection {
+        direction: MoveDirection,
+    },
+    ExtendSelection {
+        direction: MoveDirection,
+    },
+    ToggleArmedDigit {
+        digit: DigitDto,
+    },
+    ToggleInputMode,
+    UpdateSettings {
+        settings: SettingsDto,
+    },
+    RecordHintTechnique {
+        key: String,
+        /// Absent in recordings made before hinted cells were tracked.
+        #[serde(default)]
+        cells: Vec<PositionDto>,
+    },
+    ToggleCandidateHeatmap,
+    ClearHintUsage,
+    CancelContextual,
+    PassTurn,
+}
+
+#[derive(Debug, derive_more::Display, derive_more::Error, derive_more::From)]
+pub(crate) enum RecordedActionError {
+    #[display("invalid position: {_0}")]
+    Position(PositionNewError),
+    #[display("invalid digit: {_0}")]
+    Digit(DigitParseError),
+    #[display("invalid state code: {_0}")]
+    StateCode(StateCodeError),
+}
+
+/// What to record for an action, decided before it is handled.
+#[derive(Debug)]
+pub(crate) enum Capture {
+    Action(Box<RecordedAction>),
+    /// Record the board and settings once the action has been handled.
+    Board,
+    /// Record the settings once the action has been handled.
+    Settings,
+}
+
+impl Capture {
+    /// Returns what to record for `action`, or `None` if it is not recorded.
+    #[must_use]
+    pub(crate) fn of(action: &Action) -> Option<Self> {
+        let Action::App(action) = action else {
+            return None;
+        };
+        let recorded = match action {
+            AppAction::BoardMutation(action) => capture_board_mutation(action)?,
+            AppAction::PuzzleLifecycle(_) => return Some(Self::Board),
+            AppAction::UpdateState(UpdateStateAction::SwitchAssistProfile(_)) => {
+                return Some(Self::Settings);
+            }
+            AppAction::History(HistoryAction::Undo) => RecordedAction::Undo,
+            AppAction::History(HistoryAction::UndoSteps(steps)) => {
+                RecordedAction::UndoSteps { steps: *steps }
+            }
+            AppAction::History(HistoryAction::Redo) => RecordedAction::Redo,
+            AppAction::Selection(SelectionAction::SelectOrClearCell(position)) => {
+                RecordedAction::SelectOrClearCell {
+                    position: (*position).into(),
+                }
+            }
+            AppAction::Selection(SelectionAction::ToggleCellInSelection(position)) => {
+                RecordedAction::ToggleCellInSelection {
+                    position: (*position).into(),
+                }
+            }
+            AppAction::Selection(SelectionAction::MoveSelection(direction)) => {
+                RecordedAction::MoveSelection {
+                    direction: *direction,
+                }
+            }
+            AppAction::Selection(SelectionAction::ExtendSelection(direction)) => {
+                RecordedAction::ExtendSelection {
+                    direction: *direction,
+                }
+            }
+            AppAction::Selection(SelectionAction::ToggleArmedDigit(digit)) => {
+                RecordedAction::ToggleArmedDigit {
+                    digit: (*digit).into(),
+                }
+            }
+            AppAction::InputMode(InputModeAction::ToggleInputMode) => {
+                RecordedAction::ToggleInputMode
+            }
+            AppAction::UpdateState(action) => capture_update_state(action)?,
+            AppAction::CancelContextual => RecordedAction::CancelContextual,
+            AppAction::PassTurn => RecordedAction::PassTurn,
+            AppAction::StateQuery(_) => return None,
+        };
+        Some(Self::Action(Box::new(recorded)))
+    }
+}
+
+fn capture_board_mutation(action: &BoardMutationAction) -> Option<RecordedAction> {
+    let recorded = match action {
+        BoardMutationAction::RequestDigit {
+            digit,
+            swap_input_mode,
+            position,
+        } => RecordedAction::RequestDigit {
+            digit: digit.map(DigitDto::from),
+            swap_input_mode: *swap_input_mode,
+            position: position.map(PositionDto::from),
+        },
+        BoardMutationAction::ToggleNote { digit, kind } => RecordedAction::ToggleNote {
+            digit: (*digit).into(),
+            kind: *kind,
+        },
+        BoardMutationAction::ClearCell => RecordedAction::ClearCell,
+        BoardMutationAction::AdvanceCell { position } => RecordedAction::AdvanceCell {
+            position: position.map(PositionDto::from),
+        },
+        BoardMutationAction::AutoFillNotes { scope } => {
+            RecordedAction::AutoFillNotes { scope: *scope }
+        }
+        BoardMutationAction::ResetInputs => RecordedAction::ResetInputs,
+        BoardMutationAction::ClearMistakes => RecordedAction::ClearMistakes,
+        BoardMutationAction::RemoveIllegalNotes => RecordedAction::RemoveIllegalNotes,
+        BoardMutationAction::RevealCell { position } => RecordedAction::RevealCell {
+            position: (*position).into(),
+        },
+        BoardMutationAction::RevealAll => RecordedAction::RevealAll,
+        BoardMutationAction::ApplyTechniqueStep(step) => RecordedAction::ApplyTechniqueStep {
+            step: TechniqueStepData::from_step(&**step)?,
+        },
+    };
+    Some(recorded)
+}
+
+/// New game options, the puzzle difficulty and the tutorial flag only affect
+/// dialogs and labels, so they are not recorded. Neither is the review, since
+/// a replayed game is read-only anyway.
+fn capture_update_state(action: &UpdateStateAction) -> Option<RecordedAction> {
+    let recorded = match action {
+        UpdateStateAction::UpdateSettings(settings) => RecordedAction::UpdateSettings {
+            settings: settings.as_ref().into(),
+        },
+        UpdateStateAction::RecordHintTechnique { key, cells } => {
+            RecordedAction::RecordHintTechnique {
+                key: key.clone(),
+                cells: cells.iter().map(PositionDto::from).collect(),
+            }
+        }
+        UpdateStateAction::ToggleCandidateHeatmap => RecordedAction::ToggleCandidateHeatmap,
+        UpdateStateAction::ClearHintUsage => RecordedAction::ClearHintUsage,
+        UpdateStateAction::UpdateNewGameOptions(_)
+        | UpdateStateAction::SetPuzzleDifficulty(_)
+        | UpdateStateAction::SwitchAssistProfile(_)
+        | UpdateStateAction::MarkTutorialSeen
+        | UpdateStateAction::RecordImportedPuzzle(_)
+        | UpdateStateAction::StartReview => return None,
+    };
+    Some(recorded)
+}
+
+impl TryFrom<RecordedAction> for Action {
+    type Error = RecordedActionError;
+
+    fn try_from(value: RecordedAction) -> Result<Self, Self::Error> {
+        let action = match value {
+            RecordedAction::RequestDigit {
+                digit,
+                swap_input_mode,
+                position,
+            } => BoardMutationAction::RequestDigit {
+                digit: digit.map(Digit::try_from).transpose()?,
+                swap_input_mode,
+                position: position.map(Position::try_from).transpose()?,
+            }
+            .into(),
+            RecordedAction::ToggleNote { digit, kind } => BoardMutationAction::ToggleNote {
+                digit: digit.try_into()?,
+                kind,
+            }
+            .into(),
+            RecordedAction::ClearCell => BoardMutationAction::ClearCell.into(),
+            RecordedAction::AdvanceCell { position } => BoardMutationAction::AdvanceCell {
+                position: position.map(Position::try_from).transpose()?,
+            }
+            .into(),
+            RecordedAction::AutoFillNotes { scope } => {
+                BoardMutationAction::AutoFillNotes { scope }.into()
+            }
+            RecordedAction::ResetInputs => BoardMutationAction::ResetInputs.into(),
+            RecordedAction::ClearMistakes => BoardMutationAction::ClearMistakes.into(),
+            RecordedAction::RemoveIllegalNotes => BoardMutationAction::RemoveIllegalNotes.into(),
+            RecordedAction::RevealCell { position } => BoardMutationAction::RevealCell {
+                position: position.try_into()?,
+            }
+            .into(),
+            RecordedAction::RevealAll => BoardMutationAction::RevealAll.into(),
+            RecordedAction::ApplyTechniqueStep { step } => {
+                BoardMutationAction::ApplyTechniqueStep(Box::new(step)).into()
+            }
+            RecordedAction::LoadGame { code } => {
+                PuzzleLifecycleAction::LoadGame(Box::new(Game::from_state_code(&code)?)).into()
+            }
+            RecordedAction::Undo => HistoryAction::Undo.into(),
+            RecordedAction::UndoSteps { steps } => HistoryAction::UndoSteps(steps).into(),
+            RecordedAction::Redo => HistoryAction::Redo.into(),
+            RecordedAction::SelectOrClearCell { position } => {
+                SelectionAction::SelectOrClearCell(position.try_into()?).into()
+            }
+            RecordedAction::ToggleCellInSelection { position } => {
+                SelectionAction::ToggleCellInSelection(position.try_into()?).into()
+            }
+            RecordedAction::MoveSelection { direction } => {
+                SelectionAction::MoveSelection(direction).into()
+            }
+            RecordedAction::ExtendSelection { direction } => {
+                SelectionAction::ExtendSelection(direction).into()
+            }
+            RecordedAction::ToggleArmedDigit { digit } => {
+                SelectionAction::ToggleArmedDigit(digit.try_into()?).into()
+            }
+            RecordedAction::ToggleInputMode => InputModeAction::ToggleInputMode.into(),
+            RecordedAction::UpdateSettings { settings } => {
+                UpdateStateAction::UpdateSettings(Box::new(settings.into())).into()
+            }
+            RecordedAction::RecordHintTechnique { key, cells } => {
+                let cells = cells
+                    .into_iter()
+                    .map(Position::try_from)
+                    .collect::<Result<_, _>>()?;
+                UpdateStateAction::RecordHintTechnique { key, cells }.into()
+            }
+            RecordedAction::ToggleCandidateHeatmap => {
+                UpdateStateAction::ToggleCandidateHeatmap.into()
+            }
+            RecordedAction::ClearHintUsage => UpdateStateAction::ClearHintUsage.into(),
+            RecordedAction::CancelContextual => AppAction::CancelContextual.into(),
+            RecordedAction::PassTurn => AppAction::PassTurn.into(),
+        };
+        Ok(action)
+    }
+}
+
+/// A recorded action with the time since the recording started.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct RecordedEntry {
+    at_ms: u64,
+    action: RecordedAction,
+}
+
+/// A replayable log of app actions.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub(crate) struct Recording {
+    entries: Vec<RecordedEntry>,
+}
+
+impl Recording {
+    pub(crate) fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    #[must_use]
+    pub(crate) fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("recordings always serialize")
+    }
+}
+
+/// Converts a replayed entry, skipping it with a warning if it is invalid.
+fn entry_action(entry: RecordedEntry) -> Option<Action> {
+    Action::try_from(entry.action)
+        .inspect_err(|err| log::warn!("skipping recorded action: {err}"))
+        .ok()
+}
+
+/// Records the app actions handled from its creation on.
+#[derive(Debug)]
+pub(crate) struct ActionRecorder {
+    started: Instant,
+    recording: Recording,
+}
+
+impl ActionRecorder {
+    /// Starts recording from the current board and settings.
+    #[must_use]
+    pub(crate) fn new(game: &Game, settings: &Settings) -> Self {
+        let mut recorder = Self {
+            started: Instant::now(),
+            recording: Recording::default(),
+        };
+        recorder.record_board(game, settings);
+        recorder
+    }
+
+    #[must_use]
+    pub(crate) fn recording(&self) -> &Recording {
+        &self.recording
+    }
+
+    /// Records a captured action; `game` and `settings` are the state after
+    /// handling it.
+    pub(crate) fn record(&mut self, capture: Capture, game: &Game, settings: &Settings) {
+        match capture {
+            Capture::Action(action) => self.push(*action),
+            Capture::Board => self.record_board(game, settings),
+            Capture::Settings => self.push(RecordedAction::UpdateSettings {
+                settings: settings.into(),
+            }),
+        }
+    }
+
+    fn record_board(&mut self, game: &Game, settings: &Settings) {
+        if game.is_initialized() {
+            self.push(RecordedAction::LoadGame {
+                code: game.to_state_code(),
+            });
+        }
+        self.push(RecordedAction::UpdateSettings {
+            settings: settings.into(),
+        });
+    }
+
+    fn push(&mut self, action: RecordedAction) {
+        let at_ms = u64::try_from(self.started.elapsed().as_millis()).unwrap_or(u64::MAX);
+        self.recording.entries.push(RecordedEntry { at_ms, action });
+    }
+}
+
+/// Replays a recording in real time.
+#[derive(Debug)]
+pub(crate) struct Replay {
+    started: Instant,
+    entries: VecDeque<RecordedEntry>,
+}
+
+impl Replay {
+    #[must_use]
+    pub(crate) fn new(recording: Recording) -> Self {
+        Self {
+            started: Instant::now(),
+            entries: recording.entries.into(),
+        }
+    }
+
+    /// Takes the actions due at `now`, and returns them with the time until
+    /// the next one is due.
+    pub(crate) fn take_due(&mut self, now: Instant) -> (Vec<Action>, Option<Duration>) {
+        let elapsed = now.saturating_duration_since(self.started);
+        let mut actions = vec![];
+        while let Some(entry) = self.entries.front() {
+            let at = Duration::from_millis(entry.at_ms);
+            if at > elapsed {
+                return (actions, Some(at.saturating_sub(elapsed)));
+            }
+            actions.extend(self.entries.pop_front().and_then(entry_action));
+        }
+        (actions, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use numelace_core::{Digit, DigitGrid, Position};
+
+    use super::*;
+    use crate::{
+        action::handler::handle,
+        state::{AppState, UiState},
+    };
+
+    const SOLUTION: &str =
+        "185362947793148526246795183564239871931874265827516394318427659672951438459683712";
+
+    fn game() -> Game {
+        let solution: DigitGrid = SOLUTION.parse().unwrap();
+        let problem: DigitGrid = format!("{}{}", ".".repeat(9), &SOLUTION[9..])
+            .parse()
+            .unwrap();
+        Game::from_problem_filled_notes(&problem, &solution, &DigitGrid::new(), &[[0; 9]; 9])
+            .unwrap()
+    }
+
+    #[test]
+    fn replaying_a_recording_reproduces_the_board() {
+        let mut app_state = AppState::new(game());
+        let mut ui_state = UiState::new();
+        ui_state.recorder = Some(ActionRecorder::new(&app_state.game, &app_state.settings));
+        let actions: [Action; 5] = [
+            SelectionAction::SelectOrClearCell(Position::new(0, 0)).into(),
+            BoardMutationAction::RequestDigit {
+                digit: Some(Digit::D1),
+                swap_input_mode: false,
+                position: None,
+            }
+            .into(),
+            SelectionAction::MoveSelection(MoveDirection::Right).into(),
+            InputModeAction::ToggleInputMode.into(),
+            BoardMutationAction::RequestDigit {
+                digit: Some(Digit::D8),
+                swap_input_mode: false,
+                position: None,
+            }
+            .into(),
+        ];
+        for action in actions {
+            handle(&mut app_state, &mut ui_state, action);
+        }
+        let json = ui_state.recorder.as_ref().unwrap().recording().to_json();
+
+        let mut replayed = AppState::new(Game::new_empty());
+        let mut replay_ui_state = UiState::new();
+        let mut replay = Replay::new(Recording::from_json(&json).unwrap());
+        let (actions, next) = replay.take_due(Instant::now() + Duration::from_secs(60));
+        assert_eq!(next, None);
+        for action in actions {
+            handle(&mut replayed, &mut replay_ui_state, action);
+        }
+        assert_eq!(
+            replayed.game.to_state_code(),
+            app_state.game.to_state_code()
+        );
+        assert_eq!(replayed.selected_cell(), Some(Position::new(0, 1)));
+        assert_eq!(replayed.input_mode, app_state.input_mode);
+    }
+}
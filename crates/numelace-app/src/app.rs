@@ -9,18 +9,35 @@
 //! - Candidate marks, undo/redo, hints, mistake detection.
 //! - Save/load, timer/statistics, and web/WASM support.
 
-use std::time::Duration;
+use std::{
+    mem,
+    sync::Arc,
+    task::{Wake, Waker},
+    time::Duration,
+};
 
 use eframe::{
     App, CreationContext, Frame, Storage,
-    egui::{CentralPanel, Ui},
+    egui::{CentralPanel, Context, Ui, ViewportCommand},
 };
 use numelace_game::Game;
+use web_time::Instant;
 
 use crate::{
-    action::{self, ActionRequestQueue, FlowAction},
-    persistence::storage,
-    state::{AppState, UiState},
+    action::{
+        self, ActionRequestQueue, FlowAction, Notification, NotificationKind,
+        PuzzleLifecycleAction,
+        recording::{ActionRecorder, Replay},
+    },
+    developer::DeveloperOptions,
+    flow, i18n,
+    persistence::{
+        dto::PersistedState,
+        journal, storage,
+        sync::{self, Pulled, StateSync, SyncOptions, SyncWorker},
+    },
+    share,
+    state::{AnimationPolicy, AppState, ClipboardContent, ReadOnlyReason, Screen, UiState},
     ui, view_model_builder, worker,
 };
 
@@ -28,25 +45,127 @@ use crate::{
 pub struct NumelaceApp {
     app_state: AppState,
     ui_state: UiState,
+    grid_cache: view_model_builder::GridCache,
+    developer: DeveloperOptions,
+    sync: Option<SyncWorker>,
 }
 
 const MAX_ACTION_HANDLING_ITERATIONS: usize = 10;
 
+/// How often to check whether the worker is ready while it warms up.
+const WORKER_READY_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How often flows are polled while background work is outstanding.
+///
+/// Responses wake the UI as they arrive; this only catches the deadlines
+/// nothing wakes for, such as flow timeouts and retry backoffs.
+const PENDING_WORK_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Waker handed to flows that requests a repaint, so the next frame polls
+/// them.
+struct RepaintWaker(Context);
+
+impl Wake for RepaintWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.request_repaint();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.request_repaint();
+    }
+}
+
 impl NumelaceApp {
     pub fn new(cc: &CreationContext<'_>) -> Self {
-        let _ = worker::warm_up();
-        let app_state = cc
-            .storage
-            .and_then(storage::load_state)
-            .unwrap_or_else(|| AppState::new_with_settings_applied(Game::new_empty()));
-        let ui_state = UiState::new();
+        worker::warm_up();
+        ui::fonts::install(&cc.egui_ctx);
+        let developer = DeveloperOptions::from_env();
+        let mut ui_state = UiState::new();
+        let waker = Waker::from(Arc::new(RepaintWaker(cc.egui_ctx.clone())));
+        ui_state.executor.set_waker(waker.clone());
+        let mut sync = None;
+        let app_state = if let Some(recording) = developer.load_replay() {
+            // The recording starts by loading its board.
+            ui_state.replay = Some(Replay::new(recording));
+            ui_state.read_only = Some(ReadOnlyReason::Replay);
+            ui_state.requested_initial_new_game = true;
+            AppState::new_with_settings_applied(Game::new_empty())
+        } else {
+            ui_state.shared_code = share::take_shared_code();
+            ui_state.recovered_state = journal::load().map(Box::new);
+            ui_state.keep_journal = ui_state.recovered_state.is_some();
+            // The synced state is merged in by `poll_sync` once it arrives.
+            sync =
+                StateSync::new(SyncOptions::from_env()).map(|state_sync| state_sync.spawn(waker));
+            cc.storage
+                .and_then(storage::load_state)
+                .unwrap_or_else(|| AppState::new_with_settings_applied(Game::new_empty()))
+        };
+        ui_state.system_prefers_reduced_motion = ui::animation::system_prefers_reduced_motion();
+        ui_state.trace_generator = developer.traces_generator();
+        if developer.records() {
+            ui_state.recorder = Some(ActionRecorder::new(&app_state.game, &app_state.settings));
+        }
+        Self {
+            developer,
+            sync,
+            ..Self::from_state(app_state, ui_state)
+        }
+    }
+
+    /// Creates the app from already loaded state.
+    ///
+    /// Unlike [`NumelaceApp::new`], this touches neither storage, the journal,
+    /// nor the page URL, so tests can drive the app headlessly.
+    #[must_use]
+    pub(crate) fn from_state(app_state: AppState, ui_state: UiState) -> Self {
         Self {
             app_state,
             ui_state,
+            grid_cache: view_model_builder::GridCache::default(),
+            developer: DeveloperOptions::default(),
+            sync: None,
+        }
+    }
+
+    /// Handles the replayed actions that are due.
+    ///
+    /// They bypass the read-only game, which only keeps the player's own
+    /// moves from interfering with the recording.
+    fn poll_replay(&mut self, ctx: &Context) {
+        let Some(replay) = &mut self.ui_state.replay else {
+            return;
+        };
+        let (actions, next) = replay.take_due(Instant::now());
+        for action in actions {
+            action::handler::handle_replayed(&mut self.app_state, &mut self.ui_state, action);
+        }
+        if let Some(next) = next {
+            ctx.request_repaint_after(next);
+        }
+    }
+
+    /// Merges the state pulled from the sync backend once it arrives.
+    ///
+    /// A synced board saved after the local one replaces it; otherwise only
+    /// the progress recorded on other devices is taken over.
+    fn poll_sync(&mut self) {
+        let Some(remote) = self.sync.as_mut().and_then(SyncWorker::take_pulled) else {
+            return;
+        };
+        match sync::resolve_pulled(&self.app_state, remote) {
+            Ok(Pulled::Remote(state)) => action::handler::handle(
+                &mut self.app_state,
+                &mut self.ui_state,
+                PuzzleLifecycleAction::RestoreState(state).into(),
+            ),
+            Ok(Pulled::Local(state)) => self.app_state.access().as_mut().adopt_progress(*state),
+            Err(err) => log::warn!("failed to restore the synced state: {err}"),
         }
     }
 
     fn poll_and_handle_actions(&mut self, action_queue: &mut ActionRequestQueue) {
+        self.request_deduction_depths();
         self.ui_state.executor.poll(action_queue);
         for _ in 0..MAX_ACTION_HANDLING_ITERATIONS {
             if action_queue.is_empty() {
@@ -66,41 +185,187 @@ impl NumelaceApp {
         }
     }
 
+    /// Records when the background worker finishes warming up.
+    fn poll_worker_readiness(&mut self, ctx: &Context) {
+        if self.ui_state.worker_readiness.is_ready() {
+            return;
+        }
+        self.ui_state.worker_readiness = worker::readiness();
+        if !self.ui_state.worker_readiness.is_ready() {
+            ctx.request_repaint_after(WORKER_READY_POLL_INTERVAL);
+        }
+    }
+
+    /// Starts a hardness overlay analysis when the candidates changed since
+    /// the last one started.
+    ///
+    /// The analysis runs in the worker, cancelling the one it supersedes; the
+    /// overlay keeps showing the previous depths until it returns.
+    fn request_deduction_depths(&mut self) {
+        if !self.app_state.settings.assist.deduction_depth_overlay {
+            return;
+        }
+        let candidates = self.app_state.game.to_candidate_grid();
+        if let Some((requested, flow)) = self.ui_state.deduction_depths_request.take() {
+            if requested == candidates {
+                self.ui_state.deduction_depths_request = Some((requested, flow));
+                return;
+            }
+            self.ui_state.executor.cancel(flow);
+        }
+        let executor = &mut self.ui_state.executor;
+        self.ui_state.deduction_depths_request =
+            flow::tasks::spawn_analyze_deduction_depths_flow(executor, &self.app_state.game)
+                .map(|flow| (candidates, flow));
+    }
+
+    /// Applies the UI scale setting as the egui zoom factor.
+    ///
+    /// The zoom factor is only set when the setting changes, so keyboard zoom
+    /// keeps working until the user picks another scale.
+    fn apply_ui_zoom(&mut self, ctx: &Context) {
+        let ui_zoom = self.app_state.settings.display.ui_zoom;
+        if self.ui_state.applied_ui_zoom != Some(ui_zoom) {
+            ctx.set_zoom_factor(ui_zoom);
+            self.ui_state.applied_ui_zoom = Some(ui_zoom);
+        }
+    }
+
+    /// Keeps the minimum window size in line with the digit size and the
+    /// board coordinates, which both change how much room the board needs.
+    fn apply_min_window_size(&mut self, ctx: &Context) {
+        let settings = &self.app_state.settings;
+        let size =
+            ui::game_screen::min_window_size(&settings.display, settings.grid_style.coordinates);
+        if self.ui_state.applied_min_window_size != Some(size) {
+            ctx.send_viewport_cmd(ViewportCommand::MinInnerSize(size));
+            self.ui_state.applied_min_window_size = Some(size);
+        }
+    }
+
+    /// Resolves the motion setting to this frame's animation policy and
+    /// applies it to the egui styles when it changes.
+    fn apply_animation_policy(&mut self, ctx: &Context) -> AnimationPolicy {
+        let animation = AnimationPolicy::resolve(
+            self.app_state.settings.motion,
+            self.ui_state.system_prefers_reduced_motion,
+        );
+        if self.ui_state.applied_animation != Some(animation) {
+            ui::animation::apply_to_styles(ctx, animation);
+            self.ui_state.applied_animation = Some(animation);
+        }
+        animation
+    }
+
+    /// Advances the running clock of a timed game or race while it is being played.
+    ///
+    /// The clock pauses while the window is in the background or another
+    /// screen covers the board. Ticks do not mark the state dirty, so the
+    /// time is saved along with the next change or autosave rather than on
+    /// every frame.
+    fn tick_clock(&mut self, ctx: &Context) {
+        let now = Instant::now();
+        let last_tick = self.ui_state.last_clock_tick.take();
+        let focused = ctx.input(|i| i.viewport().focused.unwrap_or(true));
+        let playing = self.ui_state.screen == Screen::Game && self.app_state.game.is_initialized();
+        if !playing || !focused {
+            return;
+        }
+        let Some(clock) = self.app_state.active_clock_mut() else {
+            return;
+        };
+        if clock.finished {
+            return;
+        }
+        if let Some(last_tick) = last_tick {
+            clock.tick(now - last_tick);
+        }
+        self.ui_state.last_clock_tick = Some(now);
+        ctx.request_repaint_after(Duration::from_secs(1));
+    }
+
+    /// Records changed state in the eframe storage and the journal.
+    ///
+    /// eframe only writes its storage to disk on `save`, so the journal keeps
+    /// the changes made in between recoverable after a crash.
     fn apply_persistence(&mut self, frame: &mut Frame) {
         if self.app_state.is_dirty()
+            && !self.developer.replays()
             && let Some(storage) = frame.storage_mut()
         {
-            self.save(storage);
+            self.app_state.stamp_saved_at();
+            storage::save_state(storage, &self.app_state);
+            journal::write(&self.app_state);
             self.app_state.clear_dirty();
+            self.ui_state.saved_since_autosave = true;
+            self.ui_state.keep_journal = false;
         }
     }
-}
 
-impl App for NumelaceApp {
-    fn save(&mut self, storage: &mut dyn Storage) {
-        storage::save_state(storage, &self.app_state);
-    }
-
-    fn auto_save_interval(&self) -> Duration {
-        Duration::from_secs(30)
+    /// Requests the flow that fills the board on the first frame: recovery,
+    /// a shared puzzle, the tutorial on the first launch, or a new game.
+    fn request_initial_flow(&mut self, action_queue: &mut ActionRequestQueue) {
+        if let Some(state) = self.ui_state.recovered_state.take() {
+            // The recovery flow opens the shared puzzle or starts a new game as needed.
+            let shared_code = self.ui_state.shared_code.take();
+            action_queue.request(FlowAction::RecoverProgress { state, shared_code }.into());
+            self.ui_state.requested_initial_new_game = true;
+        } else if let Some(code) = self.ui_state.shared_code.take() {
+            // The shared puzzle flow starts a new game itself if nothing gets loaded.
+            action_queue.request(FlowAction::LoadSharedPuzzle(code).into());
+            self.ui_state.requested_initial_new_game = true;
+        } else if !self.app_state.game.is_initialized() && !self.ui_state.requested_initial_new_game
+        {
+            // The tutorial brings its own puzzle on the first launch.
+            let action = if self.app_state.tutorial_seen {
+                FlowAction::StartNewGame
+            } else {
+                FlowAction::StartTutorial
+            };
+            action_queue.request(action.into());
+            self.ui_state.requested_initial_new_game = true;
+        }
     }
 
-    fn ui(&mut self, ui: &mut Ui, frame: &mut Frame) {
+    /// Runs one frame: handles input and pending actions, then draws the UI.
+    ///
+    /// The next frame is only requested when something needs it: state that
+    /// changed after drawing, outstanding background work, a running clock or
+    /// an animation. Otherwise the app stays idle until the next input event.
+    ///
+    /// Persistence is left to the caller, which has access to the eframe
+    /// storage.
+    pub(crate) fn show(&mut self, ui: &mut Ui) {
         let ctx = ui.ctx().clone();
         let mut action_queue = ActionRequestQueue::default();
 
-        if !self.app_state.game.is_initialized() && !self.ui_state.requested_initial_new_game {
-            action_queue.request(FlowAction::StartNewGame.into());
-            self.ui_state.requested_initial_new_game = true;
-        }
+        self.poll_replay(&ctx);
+        self.poll_sync();
+        self.request_initial_flow(&mut action_queue);
 
         self.poll_and_handle_actions(&mut action_queue);
+        self.poll_worker_readiness(&ctx);
+        self.apply_ui_zoom(&ctx);
+        self.apply_min_window_size(&ctx);
+        let animation = self.apply_animation_policy(&ctx);
+        i18n::set_language(self.app_state.settings.language);
 
-        let allow_input =
-            self.ui_state.active_modal.is_none() && !self.ui_state.spinner_state.is_active();
+        let allow_input = self.ui_state.screen == Screen::Game
+            && self.ui_state.active_modal.is_none()
+            && !self.ui_state.spinner_state.is_active();
         let base_input_mode = self.app_state.input_mode;
+        let input_order = self.app_state.settings.input.order;
+        let armed_digit = self.app_state.armed_digit();
+        let key_bindings = self.app_state.settings.input.key_bindings.clone();
         let input_context = ctx.input(|i| {
-            let context = ui::input::build_input_context(i, allow_input, base_input_mode);
+            let context = ui::input::build_input_context(
+                i,
+                allow_input,
+                base_input_mode,
+                input_order,
+                armed_digit,
+                key_bindings,
+            );
             if allow_input {
                 ui::input::handle_input(i, &context, &mut action_queue);
                 self.handle_actions(&mut action_queue);
@@ -108,34 +373,125 @@ impl App for NumelaceApp {
             context
         });
 
-        let game_screen_vm = view_model_builder::build_game_screen_view_model(
-            &self.app_state,
-            &self.ui_state,
-            &input_context,
-        );
+        self.tick_clock(&ctx);
+        if let Some(remaining) = self.ui_state.notifications.expire(Instant::now()) {
+            ctx.request_repaint_after(remaining);
+        }
+        match self.ui_state.screen {
+            Screen::Game => {
+                let game_screen_vm = view_model_builder::build_game_screen_view_model(
+                    &self.app_state,
+                    &self.ui_state,
+                    &input_context,
+                    &mut self.grid_cache,
+                );
+                CentralPanel::default().show(ui, |ui| {
+                    ui::game_screen::show(ui, &game_screen_vm, &mut action_queue);
+                });
+            }
+            Screen::Campaign => {
+                let campaign_screen_vm = view_model_builder::build_campaign_screen_view_model(
+                    &self.app_state,
+                    &self.ui_state,
+                );
+                CentralPanel::default().show(ui, |ui| {
+                    ui::campaign_screen::show(ui, &campaign_screen_vm, &mut action_queue);
+                });
+            }
+            Screen::Learn => {
+                let learn_grid = view_model_builder::build_learn_grid(&self.ui_state.learn);
+                let learn_screen_vm = view_model_builder::build_learn_screen_view_model(
+                    &self.app_state,
+                    &self.ui_state,
+                    &learn_grid,
+                    &input_context,
+                );
+                CentralPanel::default().show(ui, |ui| {
+                    ui::learn_screen::show(ui, &learn_screen_vm, &mut action_queue);
+                });
+            }
+        }
 
-        CentralPanel::default().show(ui, |ui| {
-            ui::game_screen::show(ui, &game_screen_vm, &mut action_queue);
-        });
+        if let Some(prompt) = &self.ui_state.script_prompt {
+            ui::script_prompt::show(&ctx, prompt, &mut action_queue);
+        }
 
         if let Some(modal_request) = &mut self.ui_state.active_modal {
-            let new_game_options_vm =
-                view_model_builder::build_new_game_options_view_model(&self.app_state);
-            let settings_vm = view_model_builder::build_settings_view_model(&self.app_state);
-            ui::modal::show(
-                &ctx,
-                &mut action_queue,
-                modal_request,
-                &new_game_options_vm,
-                &settings_vm,
+            let modal_vms = view_model_builder::build_modal_view_models(
+                &self.app_state,
+                self.ui_state.worker_readiness,
             );
+            ui::modal::show(&ctx, &mut action_queue, modal_request, &modal_vms);
+        }
+
+        if let Some(view) = &mut self.ui_state.generation_trace {
+            ui::generation_trace::show(&ctx, view, &mut action_queue);
         }
 
         if let Some(spinner) = self.ui_state.spinner_state.active_kind() {
-            ui::spinner::show(&ctx, spinner);
+            ui::spinner::show(&ctx, spinner, animation);
         }
 
+        // Actions handled after drawing leave this frame stale, so draw another.
+        let drawn_revision = self.ui_state.revision;
         self.poll_and_handle_actions(&mut action_queue);
+        if self.ui_state.revision != drawn_revision {
+            ctx.request_repaint();
+        }
+        if worker::has_pending_requests() {
+            ctx.request_repaint_after(PENDING_WORK_POLL_INTERVAL);
+        }
+        match self.ui_state.pending_clipboard.take() {
+            Some(ClipboardContent::Text(text)) => ctx.copy_text(text),
+            Some(ClipboardContent::Image(image)) => ctx.copy_image(image),
+            None => {}
+        }
+    }
+}
+
+impl App for NumelaceApp {
+    /// Called by eframe on its autosave interval and on shutdown.
+    ///
+    /// Progress is written on every change by `apply_persistence`; this
+    /// discards the journal, which eframe's storage now covers, and confirms
+    /// to the player, at most once per interval, that progress reached disk.
+    fn save(&mut self, storage: &mut dyn Storage) {
+        if let Some(recorder) = &self.ui_state.recorder {
+            self.developer.save_recording(recorder.recording());
+        }
+        if self.developer.replays() {
+            return;
+        }
+        storage::save_state(storage, &self.app_state);
+        if let Some(sync) = &self.sync {
+            sync.queue(PersistedState::from(&self.app_state));
+        }
+        if !self.ui_state.keep_journal {
+            journal::clear();
+        }
+        if mem::take(&mut self.ui_state.saved_since_autosave) {
+            let notification = Notification::new(NotificationKind::Autosaved);
+            self.ui_state
+                .notifications
+                .push(notification, Instant::now());
+        }
+    }
+
+    fn on_exit(&mut self) {
+        if let Some(sync) = &mut self.sync {
+            sync.finish();
+        }
+    }
+
+    fn auto_save_interval(&self) -> Duration {
+        self.app_state.settings.autosave.interval()
+    }
+
+    fn ui(&mut self, ui: &mut Ui, frame: &mut Frame) {
+        self.show(ui);
         self.apply_persistence(frame);
     }
 }
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests;
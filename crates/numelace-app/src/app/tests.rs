@@ -0,0 +1,215 @@
+//! End-to-end tests driving [`NumelaceApp`] headlessly through egui's test
+//! harness.
+//!
+//! The tests interact through the same accessibility tree a screen reader
+//! sees: buttons are found by their label and grid cells by their accessible
+//! description, so they also guard those labels. Clicks are sent as AccessKit
+//! actions, which reach widgets inside modal scroll areas where synthesized
+//! pointer events do not. Apps built here keep the default English texts.
+
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+use eframe::egui::{Key, Vec2};
+use egui_kittest::{Harness, kittest::Queryable as _};
+use numelace_core::{Digit, DigitGrid, Position};
+use numelace_game::{CellState, Game};
+
+use super::NumelaceApp;
+use crate::{
+    i18n::{Text, tr},
+    state::{AppState, HintStage, UiState},
+    ui::icon,
+};
+
+const SOLUTION: &str =
+    "185362947793148526246795183564239871931874265827516394318427659672951438459683712";
+
+/// Upper bound for background work such as puzzle generation.
+const WORK_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// A puzzle whose first row is empty, with every other cell given.
+fn nearly_solved_game() -> Game {
+    let solution: DigitGrid = SOLUTION.parse().unwrap();
+    let problem: DigitGrid = format!("{}{}", ".".repeat(9), &SOLUTION[9..])
+        .parse()
+        .unwrap();
+    let filled = DigitGrid::new();
+    let notes = [[0u16; 9]; 9];
+    Game::from_problem_filled_notes(&problem, &solution, &filled, &notes).unwrap()
+}
+
+fn harness(app_state: AppState) -> Harness<'static, NumelaceApp> {
+    let app = NumelaceApp::from_state(app_state, UiState::new());
+    let mut harness = Harness::builder()
+        .with_size(Vec2::new(800.0, 1000.0))
+        .build_ui_state(|ui, app: &mut NumelaceApp| app.show(ui), app);
+    settle(&mut harness);
+    harness
+}
+
+fn game_harness() -> Harness<'static, NumelaceApp> {
+    harness(AppState::new(nearly_solved_game()))
+}
+
+/// Steps frames until `done` holds, giving background work time to finish.
+///
+/// # Panics
+///
+/// Panics if `done` does not hold within [`WORK_TIMEOUT`].
+#[track_caller]
+fn step_until(harness: &mut Harness<'_, NumelaceApp>, done: impl Fn(&NumelaceApp) -> bool) {
+    let start = Instant::now();
+    while !done(harness.state()) {
+        assert!(
+            start.elapsed() < WORK_TIMEOUT,
+            "condition not met within {WORK_TIMEOUT:?}"
+        );
+        harness.step();
+        thread::sleep(Duration::from_millis(10));
+    }
+    settle(harness);
+}
+
+fn cell_label(pos: Position) -> String {
+    format!("row {} column {},", pos.row() + 1, pos.col() + 1)
+}
+
+fn click_cell(harness: &mut Harness<'_, NumelaceApp>, pos: Position) {
+    harness
+        .get_by_label_contains(&cell_label(pos))
+        .click_accesskit();
+    settle(harness);
+}
+
+/// Clicks the widget labeled exactly `label`.
+fn click(harness: &mut Harness<'_, NumelaceApp>, label: &str) {
+    harness.get_by_label(label).click_accesskit();
+    settle(harness);
+}
+
+/// Runs frames until the UI stops requesting repaints.
+///
+/// Spinners keep repainting while background work runs, so this gives up
+/// quietly after the harness's step limit; use [`step_until`] to wait for the
+/// work itself.
+fn settle(harness: &mut Harness<'_, NumelaceApp>) {
+    let _ = harness.run_ok();
+}
+
+fn press_key(harness: &mut Harness<'_, NumelaceApp>, key: Key) {
+    harness.key_press(key);
+    settle(harness);
+}
+
+fn cell(harness: &Harness<'_, NumelaceApp>, pos: Position) -> CellState {
+    *harness.state().app_state.game.cell(pos)
+}
+
+#[test]
+fn first_frame_asks_for_and_generates_a_new_game() {
+    let mut app_state = AppState::new(Game::new_empty());
+    app_state.tutorial_seen = true;
+    let mut harness = harness(app_state);
+    // Generating waits for the worker to warm up.
+    step_until(&mut harness, |app| app.ui_state.worker_readiness.is_ready());
+
+    click(
+        &mut harness,
+        &format!("{} {}", icon::CHECK, tr(Text::Generate)),
+    );
+    step_until(&mut harness, |app| app.app_state.game.is_initialized());
+
+    let game = &harness.state().app_state.game;
+    assert!(!game.is_solved());
+    assert!(
+        Position::ALL
+            .into_iter()
+            .any(|pos| game.cell(pos).is_given())
+    );
+    assert!(harness.state().ui_state.active_modal.is_none());
+}
+
+#[test]
+fn first_launch_starts_the_tutorial_until_skipped() {
+    let mut harness = harness(AppState::new(Game::new_empty()));
+    step_until(&mut harness, |app| app.ui_state.script_prompt.is_some());
+    assert!(harness.state().app_state.game.is_initialized());
+    assert!(!harness.state().app_state.tutorial_seen);
+
+    click(&mut harness, tr(Text::ScriptSkip));
+    step_until(&mut harness, |app| app.app_state.tutorial_seen);
+    assert!(harness.state().ui_state.script_prompt.is_none());
+}
+
+#[test]
+fn digits_are_entered_into_the_clicked_cell_and_undone() {
+    let mut harness = game_harness();
+    let pos = Position::new(0, 2);
+
+    click_cell(&mut harness, pos);
+    assert_eq!(harness.state().app_state.selected_cell(), Some(pos));
+    press_key(&mut harness, Key::Num5);
+    assert_eq!(cell(&harness, pos), CellState::Filled(Digit::D5));
+    harness.get_by_label(&format!("{} filled 5", cell_label(pos)));
+
+    click(&mut harness, icon::ARROW_UNDO);
+    assert_eq!(cell(&harness, pos), CellState::Empty);
+
+    click(&mut harness, icon::ARROW_REDO);
+    assert_eq!(cell(&harness, pos), CellState::Filled(Digit::D5));
+}
+
+#[test]
+fn hint_button_steps_through_a_hint_until_it_is_applied() {
+    let mut harness = game_harness();
+    let empty_cells = |app: &NumelaceApp| {
+        Position::ALL
+            .into_iter()
+            .filter(|&pos| app.app_state.game.cell(pos).is_empty())
+            .count()
+    };
+
+    click(&mut harness, icon::LIGHTBULB);
+    step_until(&mut harness, |app| app.ui_state.hint_state.is_some());
+    assert_eq!(empty_cells(harness.state()), 9);
+
+    // Each further press reveals the next stage; the last one applies the step.
+    let stage = |app: &NumelaceApp| app.ui_state.hint_state.as_ref().map(|hint| hint.stage);
+    while stage(harness.state()) != Some(HintStage::Stage3Apply) {
+        let before = stage(harness.state());
+        click(&mut harness, icon::LIGHTBULB);
+        assert!(stage(harness.state()) > before, "hint did not advance");
+    }
+    assert_eq!(empty_cells(harness.state()), 8);
+    assert_eq!(harness.state().app_state.hint_usage.total(), 1);
+}
+
+#[test]
+fn settings_changes_apply_to_the_app() {
+    let mut harness = game_harness();
+    let before = harness
+        .state()
+        .app_state
+        .settings
+        .assist
+        .block_rule_violations;
+
+    click(&mut harness, icon::GEAR_NO_HUB);
+    assert!(harness.state().ui_state.active_modal.is_some());
+    click(&mut harness, tr(Text::BlockRuleViolations));
+    assert_eq!(
+        harness
+            .state()
+            .app_state
+            .settings
+            .assist
+            .block_rule_violations,
+        !before
+    );
+
+    press_key(&mut harness, Key::Escape);
+    assert!(harness.state().ui_state.active_modal.is_none());
+}
@@ -1,13 +1,17 @@
 //! Numelace web worker entry point for background puzzle generation.
 //!
 //! This binary is built only for WASM targets and handles offloaded work requests.
+//! The app may run several instances of it side by side.
 #![allow(clippy::missing_panics_doc, clippy::missing_errors_doc)]
 
 #[cfg(target_arch = "wasm32")]
 mod wasm32 {
+    use std::ops::ControlFlow;
+
+    use js_sys::{Array, Uint8Array};
     use numelace_app::{
         version,
-        worker_api::{WorkRequest, WorkResponse},
+        worker_api::{WorkFrame, WorkRequest},
     };
     use wasm_bindgen::{JsCast, prelude::*};
     use web_sys::{DedicatedWorkerGlobalScope, MessageEvent};
@@ -19,12 +23,37 @@ mod wasm32 {
         let global_for_callback = global.clone();
 
         let onmessage = Closure::wrap(Box::new(move |event: MessageEvent| {
-            let response = match serde_wasm_bindgen::from_value::<WorkRequest>(event.data()) {
-                Ok(req) => req.handle(),
-                Err(_) => WorkResponse::deserialization_error(),
+            // Frames are sent as CBOR bytes whose buffer is transferred, not copied.
+            let post = |frame: WorkFrame| {
+                // A frame that cannot be encoded is answered with an error frame
+                // rather than a panic, which would kill the worker.
+                let encoded = frame.to_bytes().map_err(|err| err.to_string());
+                let encoded = match encoded {
+                    Ok(encoded) => encoded,
+                    Err(err) => {
+                        log::error!("failed to encode worker response: {err}");
+                        match frame.into_serialization_error().to_bytes() {
+                            Ok(encoded) => encoded,
+                            Err(_) => return ControlFlow::Break(()),
+                        }
+                    }
+                };
+                let bytes = Uint8Array::from(encoded.as_slice());
+                let _ = global_for_callback
+                    .post_message_with_transfer(&bytes, &Array::of1(&bytes.buffer()));
+                ControlFlow::Continue(())
             };
-            let _ =
-                global_for_callback.post_message(&serde_wasm_bindgen::to_value(&response).unwrap());
+            let request = event
+                .data()
+                .dyn_into::<Uint8Array>()
+                .ok()
+                .and_then(|bytes| WorkRequest::from_bytes(&bytes.to_vec()).ok());
+            match request {
+                Some(req) => req.handle(post),
+                None => {
+                    let _ = post(WorkFrame::deserialization_error());
+                }
+            }
         }) as Box<dyn FnMut(MessageEvent)>);
 
         global.set_onmessage(Some(onmessage.as_ref().unchecked_ref()));
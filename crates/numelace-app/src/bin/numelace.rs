@@ -6,17 +6,15 @@ use numelace_app::NumelaceApp;
 
 #[cfg(not(target_arch = "wasm32"))]
 fn main() -> eframe::Result<()> {
-    const APP_ID: &str = "io.github.gifnksm.numelace";
-
     better_panic::install();
     env_logger::init();
 
     let options = eframe::NativeOptions {
         viewport: eframe::egui::ViewportBuilder::default()
-            .with_app_id(APP_ID)
+            .with_app_id(numelace_app::APP_ID)
             .with_resizable(true)
             .with_inner_size((800.0, 600.0))
-            .with_min_inner_size((400.0, 300.0))
+            .with_min_inner_size(numelace_app::MIN_WINDOW_SIZE)
             .with_icon(
                 eframe::icon_data::from_png_bytes(include_bytes!(
                     "../../../../assets/icon-256.png"
@@ -0,0 +1,80 @@
+//! Bug report bundles for pasting into GitHub issues.
+//!
+//! A bundle is plain text holding everything needed to reproduce a problem:
+//! the app version, the platform, the current game as a state code and the
+//! seed it was generated from, the settings, and the redacted log of recent
+//! actions.
+
+use std::fmt::Write as _;
+
+use crate::{
+    persistence::settings_file,
+    state::{ActionLog, AppState},
+    version,
+};
+
+/// Assembles the bug report bundle for the current state.
+#[must_use]
+pub(crate) fn build(app_state: &AppState, action_log: &ActionLog) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "Numelace bug report");
+    let _ = writeln!(out, "Version: {}", version::build_version());
+    let _ = writeln!(out, "Platform: {}", platform());
+    let _ = writeln!(out);
+    let _ = writeln!(out, "Game state code:");
+    let _ = writeln!(out, "{}", app_state.game.to_state_code());
+    if let Some(seed) = app_state.seed {
+        let _ = writeln!(out, "Seed: {seed}");
+    }
+    let _ = writeln!(out);
+    let _ = writeln!(out, "Settings:");
+    let _ = writeln!(
+        out,
+        "{}",
+        settings_file::encode_settings(&app_state.settings)
+    );
+    let _ = writeln!(out);
+    let _ = writeln!(out, "Recent actions (oldest first):");
+    for entry in action_log.entries() {
+        let _ = writeln!(out, "- {entry}");
+    }
+    out
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn platform() -> String {
+    format!("{} {}", std::env::consts::OS, std::env::consts::ARCH)
+}
+
+#[cfg(target_arch = "wasm32")]
+fn platform() -> String {
+    let user_agent = web_sys::window().and_then(|window| window.navigator().user_agent().ok());
+    match user_agent {
+        Some(user_agent) => format!("web ({user_agent})"),
+        None => "web".to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use numelace_game::Game;
+    use numelace_generator::PuzzleSeed;
+
+    use super::*;
+    use crate::action::AppAction;
+
+    #[test]
+    fn bug_report_bundles_game_settings_and_actions() {
+        let mut app_state = AppState::new(Game::new_empty());
+        app_state.seed = Some(PuzzleSeed::from([1; 32]));
+        let mut action_log = ActionLog::default();
+        action_log.record(&AppAction::CancelContextual.into());
+
+        let report = build(&app_state, &action_log);
+        assert!(report.contains(&version::build_version()));
+        assert!(report.contains(&app_state.game.to_state_code()));
+        assert!(report.contains(&format!("Seed: {}", "01".repeat(32))));
+        assert!(report.contains("numelace-settings"));
+        assert!(report.ends_with("- App::CancelContextual\n"));
+    }
+}
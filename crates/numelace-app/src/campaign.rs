@@ -0,0 +1,142 @@
+//! Bundled puzzle packs played in order as a campaign.
+//!
+//! Each pack is a text file under `assets/packs` embedded at compile time.
+//! A line holds the givens and the solution of one puzzle as 81-character
+//! grids separated by a space; lines starting with `#` are comments. Storing
+//! the solution keeps loading a puzzle free of solver work, and every pack
+//! is checked by the tests below against the difficulty it claims.
+
+use numelace_core::{DigitGrid, DigitGridParseError};
+use numelace_game::{Game, GameError};
+
+use crate::{i18n::Text, state::DifficultyPreset};
+
+/// Ordered puzzles sharing a difficulty.
+#[derive(Debug)]
+pub(crate) struct PuzzlePack {
+    /// Stable key for saved progress; never change it once released.
+    pub(crate) id: &'static str,
+    pub(crate) title: Text,
+    pub(crate) difficulty: DifficultyPreset,
+    source: &'static str,
+}
+
+/// Packs in the order they unlock.
+pub(crate) const PACKS: &[PuzzlePack] = &[
+    PuzzlePack {
+        id: "first_steps",
+        title: Text::PackFirstSteps,
+        difficulty: DifficultyPreset::Basic,
+        source: include_str!("../../../assets/packs/first_steps.txt"),
+    },
+    PuzzlePack {
+        id: "pairs_and_pointing",
+        title: Text::PackPairsAndPointing,
+        difficulty: DifficultyPreset::Intermediate,
+        source: include_str!("../../../assets/packs/pairs_and_pointing.txt"),
+    },
+    PuzzlePack {
+        id: "subsets_and_wings",
+        title: Text::PackSubsetsAndWings,
+        difficulty: DifficultyPreset::UpperIntermediate,
+        source: include_str!("../../../assets/packs/subsets_and_wings.txt"),
+    },
+    PuzzlePack {
+        id: "advanced_patterns",
+        title: Text::PackAdvancedPatterns,
+        difficulty: DifficultyPreset::Advanced,
+        source: include_str!("../../../assets/packs/advanced_patterns.txt"),
+    },
+];
+
+#[derive(Debug, derive_more::Display, derive_more::Error, derive_more::From)]
+pub(crate) enum PackError {
+    #[display("puzzle {_0} does not exist in this pack")]
+    #[from(skip)]
+    NoSuchPuzzle(#[error(not(source))] usize),
+    #[display("malformed puzzle line")]
+    MalformedLine,
+    #[display("failed to parse puzzle grid: {_0}")]
+    Parse(DigitGridParseError),
+    #[display("failed to set up puzzle: {_0}")]
+    Game(GameError),
+}
+
+impl PuzzlePack {
+    #[must_use]
+    pub(crate) fn find(id: &str) -> Option<usize> {
+        PACKS.iter().position(|pack| pack.id == id)
+    }
+
+    fn lines(&self) -> impl Iterator<Item = &'static str> {
+        self.source
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+    }
+
+    #[must_use]
+    pub(crate) fn puzzle_count(&self) -> usize {
+        self.lines().count()
+    }
+
+    /// Builds a fresh game for the puzzle at `index`.
+    pub(crate) fn puzzle(&self, index: usize) -> Result<Game, PackError> {
+        let line = self
+            .lines()
+            .nth(index)
+            .ok_or(PackError::NoSuchPuzzle(index))?;
+        let (problem, solution) = line.split_once(' ').ok_or(PackError::MalformedLine)?;
+        let problem: DigitGrid = problem.parse()?;
+        let solution: DigitGrid = solution.trim().parse()?;
+        let game =
+            Game::from_problem_filled_notes(&problem, &solution, &DigitGrid::new(), &[[0; 9]; 9])?;
+        Ok(game)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use numelace_core::Position;
+    use numelace_game::CellState;
+    use numelace_solver::TechniquePriority;
+
+    use super::PACKS;
+    use crate::{
+        state::PuzzleDifficulty,
+        worker::tasks::{GradePuzzleRequestDto, grade_puzzle},
+    };
+
+    #[test]
+    fn pack_ids_are_unique() {
+        for (i, pack) in PACKS.iter().enumerate() {
+            assert!(PACKS[..i].iter().all(|other| other.id != pack.id));
+        }
+    }
+
+    #[test]
+    fn bundled_puzzles_match_their_pack() {
+        let priority = TechniquePriority::new();
+        for pack in PACKS {
+            assert_ne!(pack.puzzle_count(), 0, "{} is empty", pack.id);
+            for index in 0..pack.puzzle_count() {
+                let game = pack.puzzle(index).unwrap();
+                // The solution agrees with every given.
+                for pos in Position::ALL {
+                    if let CellState::Given(digit) = game.cell(pos) {
+                        assert_eq!(game.solution()[pos], Some(*digit), "{} #{index}", pack.id);
+                    }
+                }
+                let request = GradePuzzleRequestDto::new(&game, &priority).unwrap();
+                let difficulty = PuzzleDifficulty::from(grade_puzzle(&request).unwrap());
+                assert_eq!(
+                    difficulty,
+                    PuzzleDifficulty::Rated(pack.difficulty),
+                    "{} #{index}",
+                    pack.id
+                );
+            }
+        }
+        assert!(PACKS[0].puzzle(PACKS[0].puzzle_count()).is_err());
+    }
+}
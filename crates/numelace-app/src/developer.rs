@@ -0,0 +1,74 @@
+//! Developer mode, enabled through environment variables.
+//!
+//! - `NUMELACE_RECORD=<path>` records the app actions of the session and
+//!   writes them to `<path>` whenever the app saves.
+//! - `NUMELACE_REPLAY=<path>` starts from a fresh state and replays the
+//!   recording at `<path>` in real time, for reproducing bugs and for demos.
+//!   Nothing is loaded from or saved to storage, so a replay leaves the
+//!   player's progress alone.
+//! - `NUMELACE_GENERATOR_TRACE=1` traces the generator for every new puzzle
+//!   and shows the trace in a window: the removal order, the uniqueness
+//!   checks, and the candidate entropy of the board at each step.
+//!
+//! All of them only work in native builds, which can read the environment and files.
+
+use std::{env, fs, path::PathBuf};
+
+use crate::action::recording::Recording;
+
+#[derive(Debug, Default)]
+pub(crate) struct DeveloperOptions {
+    record_path: Option<PathBuf>,
+    replay_path: Option<PathBuf>,
+    trace_generator: bool,
+}
+
+impl DeveloperOptions {
+    #[must_use]
+    pub(crate) fn from_env() -> Self {
+        Self {
+            record_path: env::var_os("NUMELACE_RECORD").map(PathBuf::from),
+            replay_path: env::var_os("NUMELACE_REPLAY").map(PathBuf::from),
+            trace_generator: env::var_os("NUMELACE_GENERATOR_TRACE").is_some(),
+        }
+    }
+
+    /// Returns whether new puzzles come with a generator trace.
+    #[must_use]
+    pub(crate) fn traces_generator(&self) -> bool {
+        self.trace_generator
+    }
+
+    #[must_use]
+    pub(crate) fn records(&self) -> bool {
+        self.record_path.is_some()
+    }
+
+    /// Returns whether the session is a replay, which must not touch storage.
+    #[must_use]
+    pub(crate) fn replays(&self) -> bool {
+        self.replay_path.is_some()
+    }
+
+    /// Reads the recording to replay, if any.
+    #[must_use]
+    pub(crate) fn load_replay(&self) -> Option<Recording> {
+        let path = self.replay_path.as_ref()?;
+        let json = fs::read_to_string(path)
+            .inspect_err(|err| log::error!("failed to read {}: {err}", path.display()))
+            .ok()?;
+        Recording::from_json(&json)
+            .inspect_err(|err| log::error!("invalid recording {}: {err}", path.display()))
+            .ok()
+    }
+
+    /// Writes `recording` to the record path, if any.
+    pub(crate) fn save_recording(&self, recording: &Recording) {
+        let Some(path) = &self.record_path else {
+            return;
+        };
+        if let Err(err) = fs::write(path, recording.to_json()) {
+            log::error!("failed to write {}: {err}", path.display());
+        }
+    }
+}
@@ -0,0 +1,419 @@
+use ab_glyph::{Font as _, FontRef, PxScale, ScaleFont as _, point};
+use numelace_core::{Digit, EdgeKind, Position};
+use numelace_game::Game;
+
+const CELL_SIZE: u32 = 80;
+const MARGIN: u32 = 24;
+const THIN_LINE: u32 = 1;
+const THICK_LINE: u32 = 4;
+const GRID_SIDE: u32 = CELL_SIZE * 9;
+/// Side length of the rendered image in pixels.
+pub(crate) const IMAGE_SIDE: u32 = GRID_SIDE + MARGIN * 2;
+
+const CAGE_INSET: u32 = 6;
+const CAGE_DASH: u32 = 6;
+
+type Rgb = [u8; 3];
+
+const PAPER: Rgb = [0xff, 0xff, 0xff];
+const INK: Rgb = [0x00, 0x00, 0x00];
+const THIN_INK: Rgb = [0x90, 0x90, 0x90];
+const FILLED_INK: Rgb = [0x1f, 0x4e, 0x9c];
+const NOTE_INK: Rgb = [0x50, 0x50, 0x50];
+
+/// An opaque RGBA image.
+#[derive(Debug, Clone)]
+pub(crate) struct BoardImage {
+    pub(crate) width: u32,
+    pub(crate) height: u32,
+    pub(crate) rgba: Vec<u8>,
+}
+
+/// Renders the current board, including digits and notes, to an image.
+///
+/// The size is fixed, so the result does not depend on the window size.
+#[must_use]
+pub(crate) fn render_image(game: &Game) -> BoardImage {
+    let mut canvas = Canvas::new(IMAGE_SIDE, IMAGE_SIDE);
+    draw_cells(&mut canvas, game);
+    draw_cages(&mut canvas, game);
+    draw_grid_lines(&mut canvas, game);
+    draw_edges(&mut canvas, game);
+    canvas.into_image()
+}
+
+/// Encodes `image` as a PNG file.
+pub(crate) fn encode_png(image: &BoardImage) -> Result<Vec<u8>, png::EncodingError> {
+    let mut bytes = Vec::new();
+    let mut encoder = png::Encoder::new(&mut bytes, image.width, image.height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&image.rgba)?;
+    writer.finish()?;
+    Ok(bytes)
+}
+
+fn cell_origin(pos: Position) -> (u32, u32) {
+    (
+        MARGIN + u32::from(pos.col()) * CELL_SIZE,
+        MARGIN + u32::from(pos.row()) * CELL_SIZE,
+    )
+}
+
+#[expect(clippy::cast_precision_loss)]
+fn cell_center(pos: Position) -> (f32, f32) {
+    let (x, y) = cell_origin(pos);
+    ((x + CELL_SIZE / 2) as f32, (y + CELL_SIZE / 2) as f32)
+}
+
+fn draw_cells(canvas: &mut Canvas, game: &Game) {
+    let font = FontRef::try_from_slice(epaint_default_fonts::UBUNTU_LIGHT)
+        .expect("bundled font should be valid");
+    for pos in Position::ALL {
+        let cell = game.cell(pos);
+        let (cx, cy) = cell_center(pos);
+        if let Some(digit) = cell.as_digit() {
+            let color = if cell.is_given() { INK } else { FILLED_INK };
+            canvas.draw_digit(&font, digit, (cx, cy), 56.0, color);
+        } else if let Some(notes) = cell.as_notes() {
+            #[expect(clippy::cast_precision_loss)]
+            let step = CELL_SIZE as f32 / 3.0;
+            for digit in notes {
+                let index = digit.value() - 1;
+                let nx = cx + step * (f32::from(index % 3) - 1.0);
+                let ny = cy + step * (f32::from(index / 3) - 1.0);
+                canvas.draw_digit(&font, digit, (nx, ny), 20.0, NOTE_INK);
+            }
+        }
+    }
+}
+
+/// Draws thin lines between all cells and thick lines along region borders.
+fn draw_grid_lines(canvas: &mut Canvas, game: &Game) {
+    let regions = game.regions();
+    for pos in Position::ALL {
+        let (x, y) = cell_origin(pos);
+        if let Some(right) = pos.right() {
+            let x = x + CELL_SIZE;
+            if regions.region_index(right) == regions.region_index(pos) {
+                canvas.fill_rect(x, y, THIN_LINE, CELL_SIZE, THIN_INK);
+            } else {
+                let half = THICK_LINE / 2;
+                canvas.fill_rect(x - half, y - half, THICK_LINE, CELL_SIZE + THICK_LINE, INK);
+            }
+        }
+        if let Some(down) = pos.down() {
+            let y = y + CELL_SIZE;
+            if regions.region_index(down) == regions.region_index(pos) {
+                canvas.fill_rect(x, y, CELL_SIZE, THIN_LINE, THIN_INK);
+            } else {
+                let half = THICK_LINE / 2;
+                canvas.fill_rect(x - half, y - half, CELL_SIZE + THICK_LINE, THICK_LINE, INK);
+            }
+        }
+    }
+    let outer = THICK_LINE + 2;
+    let start = MARGIN - outer / 2;
+    let side = GRID_SIDE + outer;
+    canvas.fill_rect(start, start, side, outer, INK);
+    canvas.fill_rect(start, start + side - outer, side, outer, INK);
+    canvas.fill_rect(start, start, outer, side, INK);
+    canvas.fill_rect(start + side - outer, start, outer, side, INK);
+}
+
+fn draw_cages(canvas: &mut Canvas, game: &Game) {
+    let font = FontRef::try_from_slice(epaint_default_fonts::UBUNTU_LIGHT)
+        .expect("bundled font should be valid");
+    for cage in game.cages() {
+        let positions = cage.positions();
+        let in_cage = |neighbor: Option<Position>| neighbor.is_some_and(|p| positions.contains(p));
+        for pos in positions {
+            let (x, y) = cell_origin(pos);
+            let (left, top) = (x + CAGE_INSET, y + CAGE_INSET);
+            let len = CELL_SIZE - CAGE_INSET * 2;
+            let (right, bottom) = (left + len, top + len);
+            if !in_cage(pos.up()) {
+                canvas.dashed_hline(left, top, len);
+            }
+            if !in_cage(pos.down()) {
+                canvas.dashed_hline(left, bottom, len);
+            }
+            if !in_cage(pos.left()) {
+                canvas.dashed_vline(left, top, len);
+            }
+            if !in_cage(pos.right()) {
+                canvas.dashed_vline(right, top, len);
+            }
+        }
+        let (x, y) = cell_origin(cage.anchor());
+        canvas.fill_rect(x + 2, y + 2, 22, 16, PAPER);
+        #[expect(clippy::cast_precision_loss)]
+        let origin = ((x + 4) as f32, (y + 16) as f32);
+        canvas.draw_text(&font, &cage.sum().to_string(), origin, 16.0, INK);
+    }
+}
+
+fn draw_edges(canvas: &mut Canvas, game: &Game) {
+    #[expect(clippy::cast_precision_loss)]
+    let size = CELL_SIZE as f32 * 0.2;
+    for edge in game.edge_constraints() {
+        let (x1, y1) = cell_center(edge.first());
+        let (x2, y2) = cell_center(edge.second());
+        let center = (f32::midpoint(x1, x2), f32::midpoint(y1, y2));
+        match edge.kind() {
+            EdgeKind::Consecutive => {
+                canvas.fill_circle(center, size * 0.5, PAPER);
+                canvas.stroke_circle(center, size * 0.5, 1.5, INK);
+            }
+            EdgeKind::LessThan | EdgeKind::GreaterThan => {
+                // Unit vector from the first cell towards the second one.
+                let (ax, ay) = if edge.is_vertical() {
+                    (1.0, 0.0)
+                } else {
+                    (0.0, 1.0)
+                };
+                // The chevron opens towards the larger digit.
+                let sign = if edge.kind() == EdgeKind::LessThan {
+                    1.0
+                } else {
+                    -1.0
+                };
+                let half = size * 0.5;
+                let tip = (center.0 - sign * ax * half, center.1 - sign * ay * half);
+                let back = (center.0 + sign * ax * half, center.1 + sign * ay * half);
+                let (bx, by) = (-ay * half, ax * half);
+                canvas.stroke_segment((back.0 + bx, back.1 + by), tip, 2.5, INK);
+                canvas.stroke_segment(tip, (back.0 - bx, back.1 - by), 2.5, INK);
+            }
+        }
+    }
+}
+
+/// A minimal software canvas with anti-aliased shapes and glyphs.
+struct Canvas {
+    width: u32,
+    height: u32,
+    pixels: Vec<Rgb>,
+}
+
+impl Canvas {
+    fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![PAPER; (width * height) as usize],
+        }
+    }
+
+    fn into_image(self) -> BoardImage {
+        let rgba = self
+            .pixels
+            .iter()
+            .flat_map(|&[r, g, b]| [r, g, b, 0xff])
+            .collect();
+        BoardImage {
+            width: self.width,
+            height: self.height,
+            rgba,
+        }
+    }
+
+    /// Blends `color` into the pixel at (`x`, `y`) with the given coverage.
+    fn blend(&mut self, x: u32, y: u32, color: Rgb, coverage: f32) {
+        if x >= self.width || y >= self.height || coverage <= 0.0 {
+            return;
+        }
+        let alpha = coverage.min(1.0);
+        let pixel = &mut self.pixels[(y * self.width + x) as usize];
+        for (dst, src) in pixel.iter_mut().zip(color) {
+            let value = f32::from(*dst) * (1.0 - alpha) + f32::from(src) * alpha;
+            #[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            {
+                *dst = value.round() as u8;
+            }
+        }
+    }
+
+    fn fill_rect(&mut self, x: u32, y: u32, width: u32, height: u32, color: Rgb) {
+        for py in y..y + height {
+            for px in x..x + width {
+                self.blend(px, py, color, 1.0);
+            }
+        }
+    }
+
+    fn dashed_hline(&mut self, x: u32, y: u32, len: u32) {
+        for start in (x..x + len).step_by((CAGE_DASH * 2) as usize) {
+            let dash = CAGE_DASH.min(x + len - start);
+            self.fill_rect(start, y, dash, THIN_LINE, INK);
+        }
+    }
+
+    fn dashed_vline(&mut self, x: u32, y: u32, len: u32) {
+        for start in (y..y + len).step_by((CAGE_DASH * 2) as usize) {
+            let dash = CAGE_DASH.min(y + len - start);
+            self.fill_rect(x, start, THIN_LINE, dash, INK);
+        }
+    }
+
+    /// Calls `coverage` for every pixel in the bounding box with the distance
+    /// from the pixel center, blending `color` with the returned coverage.
+    fn shade<F>(&mut self, min: (f32, f32), max: (f32, f32), color: Rgb, mut coverage: F)
+    where
+        F: FnMut(f32, f32) -> f32,
+    {
+        #[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let (x0, y0, x1, y1) = (
+            min.0.floor().max(0.0) as u32,
+            min.1.floor().max(0.0) as u32,
+            max.0.ceil().max(0.0) as u32,
+            max.1.ceil().max(0.0) as u32,
+        );
+        for y in y0..=y1 {
+            for x in x0..=x1 {
+                #[expect(clippy::cast_precision_loss)]
+                let (px, py) = (x as f32 + 0.5, y as f32 + 0.5);
+                let value = coverage(px, py);
+                self.blend(x, y, color, value);
+            }
+        }
+    }
+
+    fn fill_circle(&mut self, (cx, cy): (f32, f32), radius: f32, color: Rgb) {
+        let min = (cx - radius - 1.0, cy - radius - 1.0);
+        let max = (cx + radius + 1.0, cy + radius + 1.0);
+        self.shade(min, max, color, |x, y| {
+            let distance = (x - cx).hypot(y - cy);
+            (radius - distance + 0.5).clamp(0.0, 1.0)
+        });
+    }
+
+    fn stroke_circle(&mut self, (cx, cy): (f32, f32), radius: f32, width: f32, color: Rgb) {
+        let reach = radius + width;
+        let min = (cx - reach - 1.0, cy - reach - 1.0);
+        let max = (cx + reach + 1.0, cy + reach + 1.0);
+        self.shade(min, max, color, |x, y| {
+            let distance = ((x - cx).hypot(y - cy) - radius).abs();
+            (width / 2.0 - distance + 0.5).clamp(0.0, 1.0)
+        });
+    }
+
+    fn stroke_segment(&mut self, a: (f32, f32), b: (f32, f32), width: f32, color: Rgb) {
+        let min = (a.0.min(b.0) - width, a.1.min(b.1) - width);
+        let max = (a.0.max(b.0) + width, a.1.max(b.1) + width);
+        let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+        let len_sq = (dx * dx + dy * dy).max(f32::EPSILON);
+        self.shade(min, max, color, |x, y| {
+            let t = (((x - a.0) * dx + (y - a.1) * dy) / len_sq).clamp(0.0, 1.0);
+            let distance = (x - (a.0 + t * dx)).hypot(y - (a.1 + t * dy));
+            (width / 2.0 - distance + 0.5).clamp(0.0, 1.0)
+        });
+    }
+
+    /// Draws `digit` centered on `center`.
+    fn draw_digit(
+        &mut self,
+        font: &FontRef<'_>,
+        digit: Digit,
+        center: (f32, f32),
+        size: f32,
+        color: Rgb,
+    ) {
+        let glyph = font
+            .glyph_id(char::from(b'0' + digit.value()))
+            .with_scale_and_position(PxScale::from(size), point(0.0, 0.0));
+        let Some(outlined) = font.outline_glyph(glyph) else {
+            return;
+        };
+        let bounds = outlined.px_bounds();
+        let origin = (
+            center.0 - f32::midpoint(bounds.min.x, bounds.max.x),
+            center.1 - f32::midpoint(bounds.min.y, bounds.max.y),
+        );
+        let glyph = font
+            .glyph_id(char::from(b'0' + digit.value()))
+            .with_scale_and_position(PxScale::from(size), point(origin.0, origin.1));
+        self.draw_glyph(font, glyph, color);
+    }
+
+    /// Draws `text` with its baseline starting at `origin`.
+    fn draw_text(
+        &mut self,
+        font: &FontRef<'_>,
+        text: &str,
+        origin: (f32, f32),
+        size: f32,
+        color: Rgb,
+    ) {
+        let scale = PxScale::from(size);
+        let mut x = origin.0;
+        for ch in text.chars() {
+            let id = font.glyph_id(ch);
+            let glyph = id.with_scale_and_position(scale, point(x, origin.1));
+            x += font.as_scaled(scale).h_advance(id);
+            self.draw_glyph(font, glyph, color);
+        }
+    }
+
+    fn draw_glyph(&mut self, font: &FontRef<'_>, glyph: ab_glyph::Glyph, color: Rgb) {
+        let Some(outlined) = font.outline_glyph(glyph) else {
+            return;
+        };
+        let bounds = outlined.px_bounds();
+        outlined.draw(|x, y, coverage| {
+            #[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let (px, py) = (
+                (bounds.min.x.max(0.0) as u32) + x,
+                (bounds.min.y.max(0.0) as u32) + y,
+            );
+            self.blend(px, py, color, coverage);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use numelace_core::{DigitGrid, Position};
+    use numelace_game::Game;
+
+    use super::{IMAGE_SIDE, cell_origin, encode_png, render_image};
+
+    fn game() -> Game {
+        let problem: DigitGrid = format!("1{}", ".".repeat(80)).parse().unwrap();
+        let solution: DigitGrid =
+            "185362947793148526246795183564239871931874265827516394318427659672951438459683712"
+                .parse()
+                .unwrap();
+        let filled = DigitGrid::new();
+        let notes = [[0u16; 9]; 9];
+        Game::from_problem_filled_notes(&problem, &solution, &filled, &notes).unwrap()
+    }
+
+    fn has_ink(image: &super::BoardImage, pos: Position) -> bool {
+        let (x0, y0) = cell_origin(pos);
+        (y0 + 10..y0 + 70).any(|y| {
+            (x0 + 10..x0 + 70).any(|x| {
+                let index = ((y * image.width + x) * 4) as usize;
+                image.rgba[index] < 0x80
+            })
+        })
+    }
+
+    #[test]
+    fn render_image_has_fixed_size_and_draws_digits() {
+        let image = render_image(&game());
+
+        assert_eq!((image.width, image.height), (IMAGE_SIDE, IMAGE_SIDE));
+        assert_eq!(image.rgba.len(), (IMAGE_SIDE * IMAGE_SIDE * 4) as usize);
+        assert!(has_ink(&image, Position::new(0, 0)));
+        assert!(!has_ink(&image, Position::new(4, 4)));
+    }
+
+    #[test]
+    fn encode_png_writes_png_signature() {
+        let png = encode_png(&render_image(&game())).unwrap();
+
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+    }
+}
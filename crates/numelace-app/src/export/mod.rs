@@ -0,0 +1,92 @@
+//! Export of the current puzzle to files outside the app.
+//!
+//! Renderers here are standalone and do not depend on egui painting, so the
+//! output is independent of the window size and theme. Saving is delegated to
+//! platform modules: native builds ask where to save in the system file
+//! dialog, web builds trigger a browser download. Opening files, used to
+//! import settings, mirrors this with the system file dialog or the browser's
+//! file picker.
+
+mod image;
+mod platform;
+mod svg;
+
+pub(crate) use self::{
+    image::{BoardImage, encode_png, render_image},
+    svg::render_svg,
+};
+
+/// What to include when exporting a puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ExportContent {
+    /// Only the givens, for printing a blank puzzle.
+    Givens,
+    /// Givens plus the player's digits and notes.
+    Progress,
+}
+
+impl ExportContent {
+    #[must_use]
+    pub(crate) fn file_name(self) -> &'static str {
+        match self {
+            Self::Givens => "numelace-puzzle.svg",
+            Self::Progress => "numelace-progress.svg",
+        }
+    }
+}
+
+#[derive(Debug, derive_more::Display, derive_more::Error)]
+#[display("failed to save {file_name}: {message}")]
+pub(crate) struct ExportError {
+    file_name: String,
+    message: String,
+}
+
+impl ExportError {
+    fn new(file_name: &str, message: String) -> Self {
+        Self {
+            file_name: file_name.to_owned(),
+            message,
+        }
+    }
+}
+
+#[derive(Debug, derive_more::Display, derive_more::Error)]
+#[display("failed to open {file_name}: {message}")]
+pub(crate) struct OpenFileError {
+    file_name: String,
+    message: String,
+}
+
+impl OpenFileError {
+    fn new(file_name: &str, message: String) -> Self {
+        Self {
+            file_name: file_name.to_owned(),
+            message,
+        }
+    }
+}
+
+/// Saves `contents`, suggesting `file_name`.
+///
+/// Returns the path the file was written to, or `None` when the user cancels
+/// the dialog or the browser handles the download.
+pub(crate) async fn save_file(
+    file_name: &str,
+    mime_type: &str,
+    contents: &[u8],
+) -> Result<Option<String>, ExportError> {
+    platform::save_file(file_name, mime_type, contents).await
+}
+
+/// Reads the file the user chooses to import.
+///
+/// Native builds suggest `file_name` and filter on its extension; web builds
+/// let the user pick any file matching `accept`. Returns `None` when the user
+/// cancels the picker.
+pub(crate) async fn open_file(
+    file_name: &str,
+    accept: &str,
+) -> Result<Option<Vec<u8>>, OpenFileError> {
+    platform::open_file(file_name, accept).await
+}
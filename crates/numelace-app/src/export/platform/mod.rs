@@ -0,0 +1,9 @@
+#[cfg(not(target_arch = "wasm32"))]
+mod native;
+#[cfg(target_arch = "wasm32")]
+mod wasm;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(super) use native::{open_file, save_file};
+#[cfg(target_arch = "wasm32")]
+pub(super) use wasm::{open_file, save_file};
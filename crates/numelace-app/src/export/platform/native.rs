@@ -0,0 +1,46 @@
+use std::path::Path;
+
+use rfd::AsyncFileDialog;
+
+use crate::export::{ExportError, OpenFileError};
+
+pub(in crate::export) async fn save_file(
+    file_name: &str,
+    _mime_type: &str,
+    contents: &[u8],
+) -> Result<Option<String>, ExportError> {
+    let Some(file) = dialog(file_name).save_file().await else {
+        return Ok(None);
+    };
+    let path = file.path();
+    std::fs::write(path, contents)
+        .map_err(|err| ExportError::new(&path.display().to_string(), err.to_string()))?;
+    log::info!("Exported {}", path.display());
+    Ok(Some(path.display().to_string()))
+}
+
+pub(in crate::export) async fn open_file(
+    file_name: &str,
+    _accept: &str,
+) -> Result<Option<Vec<u8>>, OpenFileError> {
+    let Some(file) = dialog(file_name).pick_file().await else {
+        return Ok(None);
+    };
+    let path = file.path();
+    let contents = std::fs::read(path)
+        .map_err(|err| OpenFileError::new(&path.display().to_string(), err.to_string()))?;
+    log::info!("Imported {}", path.display());
+    Ok(Some(contents))
+}
+
+/// File dialog suggesting `file_name` and filtering on its extension.
+fn dialog(file_name: &str) -> AsyncFileDialog {
+    let dialog = AsyncFileDialog::new().set_file_name(file_name);
+    match Path::new(file_name)
+        .extension()
+        .and_then(|ext| ext.to_str())
+    {
+        Some(ext) => dialog.add_filter(ext.to_uppercase(), &[ext]),
+        None => dialog,
+    }
+}
@@ -0,0 +1,79 @@
+use std::{cell::RefCell, rc::Rc};
+
+use futures_channel::oneshot;
+use wasm_bindgen::{JsCast as _, JsValue, closure::Closure};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{Blob, BlobPropertyBag, Document, HtmlAnchorElement, HtmlInputElement, Url};
+
+use crate::export::{ExportError, OpenFileError};
+
+pub(in crate::export) async fn save_file(
+    file_name: &str,
+    mime_type: &str,
+    contents: &[u8],
+) -> Result<Option<String>, ExportError> {
+    download(file_name, mime_type, contents)
+        .map_err(|err| ExportError::new(file_name, format!("{err:?}")))?;
+    Ok(None)
+}
+
+fn download(file_name: &str, mime_type: &str, contents: &[u8]) -> Result<(), JsValue> {
+    let parts = js_sys::Array::of1(&js_sys::Uint8Array::from(contents));
+    let options = BlobPropertyBag::new();
+    options.set_type(mime_type);
+    let blob = Blob::new_with_u8_array_sequence_and_options(&parts, &options)?;
+    let url = Url::create_object_url_with_blob(&blob)?;
+
+    let anchor = document()?
+        .create_element("a")?
+        .dyn_into::<HtmlAnchorElement>()?;
+    anchor.set_href(&url);
+    anchor.set_download(file_name);
+    anchor.click();
+
+    Url::revoke_object_url(&url)
+}
+
+pub(in crate::export) async fn open_file(
+    file_name: &str,
+    accept: &str,
+) -> Result<Option<Vec<u8>>, OpenFileError> {
+    pick_file(accept)
+        .await
+        .map_err(|err| OpenFileError::new(file_name, format!("{err:?}")))
+}
+
+async fn pick_file(accept: &str) -> Result<Option<Vec<u8>>, JsValue> {
+    let input = document()?
+        .create_element("input")?
+        .dyn_into::<HtmlInputElement>()?;
+    input.set_type("file");
+    input.set_accept(accept);
+
+    // Both `change` and `cancel` end the picker; the closure is kept alive
+    // until one of them fires.
+    let (tx, rx) = oneshot::channel();
+    let tx = Rc::new(RefCell::new(Some(tx)));
+    let on_done = Closure::<dyn FnMut()>::new(move || {
+        if let Some(tx) = tx.borrow_mut().take() {
+            let _ = tx.send(());
+        }
+    });
+    let callback = on_done.as_ref().unchecked_ref();
+    input.add_event_listener_with_callback("change", callback)?;
+    input.add_event_listener_with_callback("cancel", callback)?;
+    input.click();
+    let _ = rx.await;
+
+    let Some(file) = input.files().and_then(|files| files.get(0)) else {
+        return Ok(None);
+    };
+    let buffer = JsFuture::from(file.array_buffer()).await?;
+    Ok(Some(js_sys::Uint8Array::new(&buffer).to_vec()))
+}
+
+fn document() -> Result<Document, JsValue> {
+    web_sys::window()
+        .and_then(|window| window.document())
+        .ok_or_else(|| JsValue::from_str("document is not available"))
+}
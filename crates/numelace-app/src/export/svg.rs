@@ -0,0 +1,294 @@
+use std::fmt::Write as _;
+
+use numelace_core::{Digit, EdgeKind, Position};
+use numelace_game::Game;
+
+use super::ExportContent;
+use crate::i18n::{Text, tr};
+
+// Page geometry in millimetres (A4 portrait).
+const PAGE_WIDTH: f32 = 210.0;
+const PAGE_HEIGHT: f32 = 297.0;
+const CELL_SIZE: f32 = 19.0;
+const GRID_SIDE: f32 = CELL_SIZE * 9.0;
+const GRID_LEFT: f32 = (PAGE_WIDTH - GRID_SIDE) / 2.0;
+const GRID_TOP: f32 = 45.0;
+
+const THIN_LINE: f32 = 0.3;
+const THICK_LINE: f32 = 1.2;
+const CAGE_INSET: f32 = CELL_SIZE * 0.08;
+
+const PAPER: &str = "#ffffff";
+const INK: &str = "#000000";
+const THIN_INK: &str = "#707070";
+const FILLED_INK: &str = "#1f4e9c";
+const NOTE_INK: &str = "#505050";
+
+/// Renders `game` as a standalone A4 SVG document.
+///
+/// [`ExportContent::Givens`] prints a blank puzzle; [`ExportContent::Progress`]
+/// also includes the player's digits and notes.
+#[must_use]
+pub(crate) fn render_svg(game: &Game, content: ExportContent) -> String {
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{PAGE_WIDTH}mm" height="{PAGE_HEIGHT}mm" viewBox="0 0 {PAGE_WIDTH} {PAGE_HEIGHT}" font-family="sans-serif">"#
+    );
+    let _ = writeln!(
+        svg,
+        r#"<rect width="{PAGE_WIDTH}" height="{PAGE_HEIGHT}" fill="{PAPER}"/>"#
+    );
+    write_header(&mut svg, game);
+    write_grid_lines(&mut svg, game);
+    write_cages(&mut svg, game);
+    write_edges(&mut svg, game);
+    write_cells(&mut svg, game, content);
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn cell_origin(pos: Position) -> (f32, f32) {
+    (
+        GRID_LEFT + f32::from(pos.col()) * CELL_SIZE,
+        GRID_TOP + f32::from(pos.row()) * CELL_SIZE,
+    )
+}
+
+fn cell_center(pos: Position) -> (f32, f32) {
+    let (x, y) = cell_origin(pos);
+    (x + CELL_SIZE / 2.0, y + CELL_SIZE / 2.0)
+}
+
+fn write_header(svg: &mut String, game: &Game) {
+    let _ = writeln!(
+        svg,
+        r#"<text x="{GRID_LEFT}" y="{}" font-size="10" font-weight="bold" fill="{INK}">Numelace</text>"#,
+        GRID_TOP - 15.0
+    );
+    let chess = game.chess_rules();
+    let rules = [
+        chess.anti_knight().then(|| tr(Text::AntiKnight)),
+        chess.anti_king().then(|| tr(Text::AntiKing)),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>();
+    if !rules.is_empty() {
+        let _ = writeln!(
+            svg,
+            r#"<text x="{GRID_LEFT}" y="{}" font-size="5" fill="{THIN_INK}">{}</text>"#,
+            GRID_TOP - 6.0,
+            escape(&rules.join(" / "))
+        );
+    }
+}
+
+/// Draws thin lines between all cells and thick lines along region borders.
+fn write_grid_lines(svg: &mut String, game: &Game) {
+    let regions = game.regions();
+    for pos in Position::ALL {
+        let (x, y) = cell_origin(pos);
+        if let Some(right) = pos.right() {
+            let width = if regions.region_index(right) == regions.region_index(pos) {
+                THIN_LINE
+            } else {
+                THICK_LINE
+            };
+            let x = x + CELL_SIZE;
+            line(svg, (x, y), (x, y + CELL_SIZE), width);
+        }
+        if let Some(down) = pos.down() {
+            let width = if regions.region_index(down) == regions.region_index(pos) {
+                THIN_LINE
+            } else {
+                THICK_LINE
+            };
+            let y = y + CELL_SIZE;
+            line(svg, (x, y), (x + CELL_SIZE, y), width);
+        }
+    }
+    let _ = writeln!(
+        svg,
+        r#"<rect x="{GRID_LEFT}" y="{GRID_TOP}" width="{GRID_SIDE}" height="{GRID_SIDE}" fill="none" stroke="{INK}" stroke-width="{}"/>"#,
+        THICK_LINE * 1.5
+    );
+}
+
+fn line(svg: &mut String, (x1, y1): (f32, f32), (x2, y2): (f32, f32), width: f32) {
+    let color = if width > THIN_LINE { INK } else { THIN_INK };
+    let _ = writeln!(
+        svg,
+        r#"<line x1="{x1}" y1="{y1}" x2="{x2}" y2="{y2}" stroke="{color}" stroke-width="{width}" stroke-linecap="square"/>"#
+    );
+}
+
+fn write_cages(svg: &mut String, game: &Game) {
+    for cage in game.cages() {
+        let positions = cage.positions();
+        let in_cage = |neighbor: Option<Position>| neighbor.is_some_and(|p| positions.contains(p));
+        for pos in positions {
+            let (x, y) = cell_origin(pos);
+            let (left, top) = (x + CAGE_INSET, y + CAGE_INSET);
+            let (right, bottom) = (x + CELL_SIZE - CAGE_INSET, y + CELL_SIZE - CAGE_INSET);
+            let edges = [
+                (pos.up(), (left, top), (right, top)),
+                (pos.down(), (left, bottom), (right, bottom)),
+                (pos.left(), (left, top), (left, bottom)),
+                (pos.right(), (right, top), (right, bottom)),
+            ];
+            for (neighbor, (x1, y1), (x2, y2)) in edges {
+                if !in_cage(neighbor) {
+                    let _ = writeln!(
+                        svg,
+                        r#"<line x1="{x1}" y1="{y1}" x2="{x2}" y2="{y2}" stroke="{INK}" stroke-width="{THIN_LINE}" stroke-dasharray="1 1"/>"#
+                    );
+                }
+            }
+        }
+        let (x, y) = cell_origin(cage.anchor());
+        let _ = writeln!(
+            svg,
+            r#"<text x="{}" y="{}" font-size="3.5" fill="{INK}" stroke="{PAPER}" stroke-width="0.8" paint-order="stroke">{}</text>"#,
+            x + CAGE_INSET * 0.6,
+            y + CAGE_INSET * 0.6 + 3.0,
+            cage.sum()
+        );
+    }
+}
+
+fn write_edges(svg: &mut String, game: &Game) {
+    let size = CELL_SIZE * 0.2;
+    for edge in game.edge_constraints() {
+        let (x1, y1) = cell_center(edge.first());
+        let (x2, y2) = cell_center(edge.second());
+        let (cx, cy) = (f32::midpoint(x1, x2), f32::midpoint(y1, y2));
+        match edge.kind() {
+            EdgeKind::Consecutive => {
+                let _ = writeln!(
+                    svg,
+                    r#"<circle cx="{cx}" cy="{cy}" r="{}" fill="{PAPER}" stroke="{INK}" stroke-width="{THIN_LINE}"/>"#,
+                    size * 0.5
+                );
+            }
+            EdgeKind::LessThan | EdgeKind::GreaterThan => {
+                // Unit vector from the first cell towards the second one.
+                let (ax, ay) = if edge.is_vertical() {
+                    (1.0, 0.0)
+                } else {
+                    (0.0, 1.0)
+                };
+                // The chevron opens towards the larger digit.
+                let sign = if edge.kind() == EdgeKind::LessThan {
+                    1.0
+                } else {
+                    -1.0
+                };
+                let half = size * 0.5;
+                let tip = (cx - sign * ax * half, cy - sign * ay * half);
+                let back = (cx + sign * ax * half, cy + sign * ay * half);
+                // `across` is `along` rotated by 90 degrees.
+                let (bx, by) = (-ay * half, ax * half);
+                let _ = writeln!(
+                    svg,
+                    r#"<polyline points="{},{} {},{} {},{}" fill="none" stroke="{INK}" stroke-width="{}"/>"#,
+                    back.0 + bx,
+                    back.1 + by,
+                    tip.0,
+                    tip.1,
+                    back.0 - bx,
+                    back.1 - by,
+                    THIN_LINE * 2.0
+                );
+            }
+        }
+    }
+}
+
+fn write_cells(svg: &mut String, game: &Game, content: ExportContent) {
+    for pos in Position::ALL {
+        let cell = game.cell(pos);
+        let (cx, cy) = cell_center(pos);
+        if cell.is_given() {
+            if let Some(digit) = cell.as_digit() {
+                write_digit(svg, (cx, cy), digit, INK, true);
+            }
+            continue;
+        }
+        if content == ExportContent::Givens {
+            continue;
+        }
+        if let Some(digit) = cell.as_digit() {
+            write_digit(svg, (cx, cy), digit, FILLED_INK, false);
+        } else if let Some(notes) = cell.as_notes() {
+            let (x, y) = cell_origin(pos);
+            let step = CELL_SIZE / 3.0;
+            for digit in notes {
+                let index = digit.value() - 1;
+                let nx = x + step * (f32::from(index % 3) + 0.5);
+                let ny = y + step * (f32::from(index / 3) + 0.5);
+                let _ = writeln!(
+                    svg,
+                    r#"<text x="{nx}" y="{ny}" font-size="4" fill="{NOTE_INK}" text-anchor="middle" dominant-baseline="central">{digit}</text>"#
+                );
+            }
+        }
+    }
+}
+
+fn write_digit(svg: &mut String, (x, y): (f32, f32), digit: Digit, color: &str, bold: bool) {
+    let weight = if bold { "bold" } else { "normal" };
+    let _ = writeln!(
+        svg,
+        r#"<text x="{x}" y="{y}" font-size="12" font-weight="{weight}" fill="{color}" text-anchor="middle" dominant-baseline="central">{digit}</text>"#
+    );
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use numelace_core::DigitGrid;
+    use numelace_game::Game;
+
+    use super::render_svg;
+    use crate::export::ExportContent;
+
+    fn game() -> Game {
+        let problem: DigitGrid = format!("1{}", ".".repeat(80)).parse().unwrap();
+        let solution: DigitGrid =
+            "185362947793148526246795183564239871931874265827516394318427659672951438459683712"
+                .parse()
+                .unwrap();
+        let filled: DigitGrid = format!(".8{}", ".".repeat(79)).parse().unwrap();
+        let mut notes = [[0u16; 9]; 9];
+        notes[8][8] = 0b0_0000_0110;
+        Game::from_problem_filled_notes(&problem, &solution, &filled, &notes).unwrap()
+    }
+
+    fn digit_texts(svg: &str) -> usize {
+        svg.matches("font-size=\"12\"").count()
+    }
+
+    #[test]
+    fn givens_only_omits_player_input() {
+        let svg = render_svg(&game(), ExportContent::Givens);
+
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert_eq!(digit_texts(&svg), 1);
+        assert!(!svg.contains("font-size=\"4\""));
+    }
+
+    #[test]
+    fn progress_includes_filled_digits_and_notes() {
+        let svg = render_svg(&game(), ExportContent::Progress);
+
+        assert_eq!(digit_texts(&svg), 2);
+        assert_eq!(svg.matches("font-size=\"4\"").count(), 2);
+    }
+}
@@ -4,17 +4,80 @@ use std::{
     pin::Pin,
     rc::Rc,
     task::{Context, Waker},
+    time::Duration,
 };
 
-use crate::action::{Action, ActionRequestQueue};
+use crate::action::{Action, ActionRequestQueue, ModalRequest, UiAction};
+
+/// Identifier of a spawned flow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct FlowId(u64);
+
+/// Mutual-exclusion group of a flow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FlowGroup {
+    /// Flows that drive modal dialogs or spinners; at most one runs at a time.
+    Modal,
+    /// Flows that only wait for background work and never open a modal.
+    ///
+    /// Any number may run side by side. They work on the current puzzle, so
+    /// replacing the puzzle should cancel them.
+    Background,
+    /// Scripted flows that guide the player, such as the tutorial.
+    ///
+    /// At most one runs at a time, alongside the modal flows it prompts the
+    /// player to start, and it survives puzzle changes.
+    Script,
+}
+
+impl FlowGroup {
+    /// Returns whether flows of this group exclude each other.
+    fn is_exclusive(self) -> bool {
+        matches!(self, Self::Modal | Self::Script)
+    }
+}
+
+/// Priority used to resolve a conflict within an exclusive group such as [`FlowGroup::Modal`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum FlowPriority {
+    /// Rejected while another modal flow runs.
+    Normal,
+    /// Cancels a running modal flow of lower priority.
+    High,
+}
+
+/// Time limits for background work awaited by flows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct FlowTimeouts {
+    pub(crate) generate_puzzle: Duration,
+    pub(crate) check_solvability: Duration,
+    pub(crate) grade_puzzle: Duration,
+    pub(crate) analyze_deduction_depths: Duration,
+}
+
+impl Default for FlowTimeouts {
+    fn default() -> Self {
+        // Generous limits: they only catch a worker that stopped responding,
+        // and slow devices may need a while for hard puzzles.
+        Self {
+            generate_puzzle: Duration::from_secs(60),
+            check_solvability: Duration::from_secs(60),
+            grade_puzzle: Duration::from_secs(30),
+            analyze_deduction_depths: Duration::from_secs(30),
+        }
+    }
+}
 
 /// Lightweight async flow executor for UI orchestration.
 ///
 /// This executor is polled from the app update loop and drives flow futures
-/// that request UI actions and await UI events.
+/// that request UI actions and await UI events. Flows are spawned into a
+/// [`FlowGroup`]; modal flows exclude each other, with [`FlowPriority`]
+/// deciding whether a new flow is rejected or replaces the running one.
 pub(crate) struct FlowExecutor {
     state: Rc<RefCell<FlowState>>,
     tasks: Vec<FlowTask>,
+    waker: Waker,
 }
 
 impl std::fmt::Debug for FlowExecutor {
@@ -34,39 +97,121 @@ impl Default for FlowExecutor {
 impl FlowExecutor {
     #[must_use]
     pub(crate) fn new() -> Self {
-        Self {
-            state: Rc::new(RefCell::new(FlowState::default())),
-            tasks: Vec::new(),
-        }
+        Self::with_timeouts(FlowTimeouts::default())
     }
 
-    /// Returns a handle for flows to request actions and await events.
+    /// Creates an executor whose flows give up on background work after `timeouts`.
     #[must_use]
-    pub(crate) fn handle(&self) -> FlowHandle {
-        FlowHandle {
-            state: Rc::clone(&self.state),
+    pub(crate) fn with_timeouts(timeouts: FlowTimeouts) -> Self {
+        let state = FlowState {
+            timeouts,
+            ..FlowState::default()
+        };
+        Self {
+            state: Rc::new(RefCell::new(state)),
+            tasks: Vec::new(),
+            waker: Waker::noop().clone(),
         }
     }
 
-    /// Returns true if no flows are currently running.
-    #[must_use]
-    pub(crate) fn is_idle(&self) -> bool {
-        self.tasks.is_empty()
+    /// Sets the waker passed to flows when they are polled.
+    ///
+    /// Background work wakes it when a response arrives, so the app can
+    /// schedule the next poll. Without one, flows only advance when the app
+    /// polls for another reason.
+    pub(crate) fn set_waker(&mut self, waker: Waker) {
+        self.waker = waker;
     }
 
-    /// Spawn a new flow future.
-    pub(crate) fn spawn(&mut self, future: impl Future<Output = ()> + 'static) {
+    /// Spawns the flow built by `start`, unless its group is busy.
+    ///
+    /// `start` is only called when the flow is accepted, so it can prepare
+    /// expensive inputs lazily. Returns the ID of the new flow, or `None` when
+    /// a flow of an exclusive group with the same or higher priority is
+    /// already running.
+    pub(crate) fn spawn<F>(
+        &mut self,
+        group: FlowGroup,
+        priority: FlowPriority,
+        start: impl FnOnce(FlowHandle) -> F,
+    ) -> Option<FlowId>
+    where
+        F: Future<Output = ()> + 'static,
+    {
+        if group.is_exclusive() {
+            let running = self
+                .tasks
+                .iter()
+                .find(|task| task.group == group)
+                .map(|task| (task.id, task.priority));
+            if let Some((id, running_priority)) = running {
+                if running_priority >= priority {
+                    return None;
+                }
+                self.cancel(id);
+            }
+        }
+        let id = self.state.borrow_mut().next_id();
+        let handle = FlowHandle {
+            id,
+            state: Rc::clone(&self.state),
+        };
+        let future = start(handle);
         self.tasks.push(FlowTask {
+            id,
+            group,
+            priority,
             future: Box::pin(future),
         });
+        Some(id)
+    }
+
+    /// Cancels the flow `id`, closing the modal it opened.
+    ///
+    /// Returns false if the flow has already finished.
+    pub(crate) fn cancel(&mut self, id: FlowId) -> bool {
+        let Some(index) = self.tasks.iter().position(|task| task.id == id) else {
+            return false;
+        };
+        // Dropping the future runs its guards, e.g. stopping spinners.
+        drop(self.tasks.swap_remove(index));
+        let mut state = self.state.borrow_mut();
+        if state.modal_owner == Some(id) {
+            state.modal_owner = None;
+            state.pending_actions.push(UiAction::CloseModal.into());
+        }
+        true
+    }
+
+    /// Cancels every running flow of `group`.
+    pub(crate) fn cancel_group(&mut self, group: FlowGroup) {
+        let ids = self
+            .tasks
+            .iter()
+            .filter(|task| task.group == group)
+            .map(|task| task.id)
+            .collect::<Vec<_>>();
+        for id in ids {
+            self.cancel(id);
+        }
     }
 
     /// Poll all active flows and drain any queued actions into the UI action queue.
+    ///
+    /// Flows spawned through [`FlowHandle::spawn_background`] start here, so
+    /// they run after the actions their parent requested before spawning
+    /// them have been handled.
     pub(crate) fn poll(&mut self, action_queue: &mut ActionRequestQueue) {
         self.drain_actions(action_queue);
 
-        let waker = Waker::noop();
-        let mut cx = Context::from_waker(waker);
+        let spawns = std::mem::take(&mut self.state.borrow_mut().pending_spawns);
+        for future in spawns {
+            self.spawn(FlowGroup::Background, FlowPriority::Normal, |_handle| {
+                future
+            });
+        }
+
+        let mut cx = Context::from_waker(&self.waker);
 
         let mut i = 0;
         while i < self.tasks.len() {
@@ -92,6 +237,7 @@ impl FlowExecutor {
 /// Flow handle used by async flows to request actions and await events.
 #[derive(Clone)]
 pub(crate) struct FlowHandle {
+    id: FlowId,
     state: Rc<RefCell<FlowState>>,
 }
 
@@ -99,13 +245,154 @@ impl FlowHandle {
     pub(crate) fn request_action(&self, action: Action) {
         self.state.borrow_mut().pending_actions.push(action);
     }
+
+    /// Opens `request` as the active modal, owned by this flow until closed.
+    pub(crate) fn open_modal(&self, request: ModalRequest) {
+        self.state.borrow_mut().modal_owner = Some(self.id);
+        self.request_action(UiAction::OpenModal(request).into());
+    }
+
+    /// Closes the modal opened by [`open_modal`](Self::open_modal).
+    pub(crate) fn close_modal(&self) {
+        let mut state = self.state.borrow_mut();
+        if state.modal_owner == Some(self.id) {
+            state.modal_owner = None;
+        }
+        state.pending_actions.push(UiAction::CloseModal.into());
+    }
+
+    #[must_use]
+    pub(crate) fn timeouts(&self) -> FlowTimeouts {
+        self.state.borrow().timeouts
+    }
+
+    /// Spawns a [`FlowGroup::Background`] flow that outlives this one.
+    pub(crate) fn spawn_background(&self, future: impl Future<Output = ()> + 'static) {
+        self.state
+            .borrow_mut()
+            .pending_spawns
+            .push(Box::pin(future));
+    }
 }
 
+type BoxedFlow = Pin<Box<dyn Future<Output = ()>>>;
+
 struct FlowTask {
-    future: Pin<Box<dyn Future<Output = ()>>>,
+    id: FlowId,
+    group: FlowGroup,
+    priority: FlowPriority,
+    future: BoxedFlow,
 }
 
 #[derive(Default)]
 struct FlowState {
+    last_id: u64,
+    timeouts: FlowTimeouts,
     pending_actions: Vec<Action>,
+    pending_spawns: Vec<BoxedFlow>,
+    /// Flow whose modal is currently open.
+    modal_owner: Option<FlowId>,
+}
+
+impl FlowState {
+    fn next_id(&mut self) -> FlowId {
+        self.last_id += 1;
+        FlowId(self.last_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future;
+
+    use super::{FlowExecutor, FlowGroup, FlowPriority};
+    use crate::action::{
+        Action, ActionRequestQueue, AlertKind, AppAction, ModalRequest, UiAction, UpdateStateAction,
+    };
+
+    fn alert() -> ModalRequest {
+        ModalRequest::Alert {
+            kind: AlertKind::HintAlreadySolved,
+            responder: None,
+        }
+    }
+
+    #[test]
+    fn modal_flows_exclude_each_other_unless_preempted() {
+        let mut executor = FlowExecutor::new();
+        let mut queue = ActionRequestQueue::default();
+
+        let first = executor.spawn(FlowGroup::Modal, FlowPriority::Normal, |handle| {
+            handle.open_modal(alert());
+            future::pending()
+        });
+        assert!(first.is_some());
+        executor.poll(&mut queue);
+        queue.take_all();
+
+        let rejected = executor.spawn(FlowGroup::Modal, FlowPriority::Normal, |_| async {});
+        assert_eq!(rejected, None);
+        let background = executor.spawn(FlowGroup::Background, FlowPriority::Normal, |_| {
+            future::pending()
+        });
+        assert!(background.is_some());
+
+        let preempting =
+            executor.spawn(FlowGroup::Modal, FlowPriority::High, |_| future::pending());
+        assert!(preempting.is_some());
+        assert!(!executor.cancel(first.unwrap()));
+        executor.poll(&mut queue);
+        let actions = queue.take_all();
+        assert_eq!(actions.len(), 1);
+        assert!(matches!(actions[0], Action::Ui(UiAction::CloseModal)));
+        assert!(executor.cancel(background.unwrap()));
+    }
+
+    #[test]
+    fn background_spawns_start_on_next_poll() {
+        let mut executor = FlowExecutor::new();
+        let mut queue = ActionRequestQueue::default();
+
+        executor.spawn(FlowGroup::Modal, FlowPriority::Normal, |handle| {
+            let child = handle.clone();
+            async move {
+                handle.request_action(UpdateStateAction::ClearHintUsage.into());
+                handle.spawn_background(async move {
+                    child.request_action(UpdateStateAction::ToggleCandidateHeatmap.into());
+                });
+            }
+        });
+        executor.poll(&mut queue);
+        let actions = queue.take_all();
+        assert_eq!(actions.len(), 1);
+        assert!(matches!(
+            actions[0],
+            Action::App(AppAction::UpdateState(UpdateStateAction::ClearHintUsage))
+        ));
+
+        executor.poll(&mut queue);
+        let actions = queue.take_all();
+        assert_eq!(actions.len(), 1);
+        assert!(matches!(
+            actions[0],
+            Action::App(AppAction::UpdateState(
+                UpdateStateAction::ToggleCandidateHeatmap
+            ))
+        ));
+    }
+
+    #[test]
+    fn cancel_group_drops_background_flows() {
+        let mut executor = FlowExecutor::new();
+        let ids = [(); 2].map(|()| {
+            executor
+                .spawn(FlowGroup::Background, FlowPriority::Normal, |_| {
+                    future::pending()
+                })
+                .unwrap()
+        });
+
+        executor.cancel_group(FlowGroup::Background);
+        assert!(ids.into_iter().all(|id| !executor.cancel(id)));
+    }
 }
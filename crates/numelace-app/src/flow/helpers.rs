@@ -2,51 +2,48 @@ use std::{
     future::Future,
     pin::Pin,
     task::{Context, Poll},
+    time::Duration,
 };
 
 use futures_channel::oneshot;
 use numelace_game::Game;
 use portable_atomic::AtomicU64;
+use web_time::Instant;
 
 use crate::{
     action::{
-        AlertKind, AlertResult, ConfirmKind, ConfirmResult, ModalRequest, SpinnerId, SpinnerKind,
-        StateQueryAction, UiAction,
+        AlertKind, AlertResult, ConfirmKind, ConfirmResult, ModalRequest, Notification,
+        NotificationKind, SpinnerId, SpinnerKind, StateQueryAction, UiAction,
     },
     flow::FlowHandle,
+    worker::WorkError,
 };
 
 pub(super) async fn show_confirm_dialog(handle: &FlowHandle, kind: ConfirmKind) -> ConfirmResult {
     let (responder, receiver) = oneshot::channel();
-    handle.request_action(
-        UiAction::OpenModal(ModalRequest::Confirm {
-            kind,
-            responder: Some(responder),
-        })
-        .into(),
-    );
+    handle.open_modal(ModalRequest::Confirm {
+        kind,
+        responder: Some(responder),
+    });
     let result = match receiver.await {
         Ok(result) => result,
         Err(_) => ConfirmResult::Cancelled,
     };
-    handle.request_action(UiAction::CloseModal.into());
+    handle.close_modal();
     result
 }
 
 pub(super) async fn show_alert_dialog(handle: &FlowHandle, kind: AlertKind) -> AlertResult {
     let (responder, receiver) = oneshot::channel();
-    handle.request_action(
-        UiAction::OpenModal(ModalRequest::Alert {
-            kind,
-            responder: Some(responder),
-        })
-        .into(),
-    );
+    handle.open_modal(ModalRequest::Alert {
+        kind,
+        responder: Some(responder),
+    });
     let result = match receiver.await {
         Ok(result) => result,
         Err(_) => AlertResult::Ok,
     };
-    handle.request_action(UiAction::CloseModal.into());
+    handle.close_modal();
     result
 }
 
@@ -56,6 +53,60 @@ pub(super) async fn request_undo_games(handle: &FlowHandle) -> Option<Vec<Game>>
     receiver.await.ok()
 }
 
+pub(super) fn notify(handle: &FlowHandle, notification: Notification) {
+    handle.request_action(UiAction::ShowNotification(notification).into());
+}
+
+/// Logs a failed operation and reports it to the user as a notification.
+pub(super) fn notify_error(handle: &FlowHandle, kind: NotificationKind, err: &WorkError) {
+    log::warn!("{kind:?}: {err}");
+    notify(handle, Notification::new(kind).with_detail(err.to_string()));
+}
+
+/// Fails background work with [`WorkError::TimedOut`] if it takes longer than `timeout`.
+///
+/// The clock starts on the first poll, so time spent before the flow awaits
+/// the work does not count.
+#[must_use]
+pub(super) fn with_timeout<F, T>(timeout: Duration, future: F) -> WithTimeoutFuture<F>
+where
+    F: Future<Output = Result<T, WorkError>>,
+{
+    WithTimeoutFuture {
+        timeout,
+        deadline: None,
+        inner: Box::pin(future),
+    }
+}
+
+/// Awaitable wrapper that gives up on background work after a time limit.
+pub(super) struct WithTimeoutFuture<F> {
+    timeout: Duration,
+    deadline: Option<Instant>,
+    inner: Pin<Box<F>>,
+}
+
+impl<F, T> Future for WithTimeoutFuture<F>
+where
+    F: Future<Output = Result<T, WorkError>>,
+{
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let now = Instant::now();
+        let timeout = self.timeout;
+        let deadline = *self.deadline.get_or_insert(now + timeout);
+
+        if let Poll::Ready(result) = self.inner.as_mut().poll(cx) {
+            return Poll::Ready(result);
+        }
+        if now >= deadline {
+            return Poll::Ready(Err(WorkError::TimedOut));
+        }
+        Poll::Pending
+    }
+}
+
 #[must_use]
 pub(super) fn with_spinner<F>(
     handle: &FlowHandle,
@@ -142,3 +193,42 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        future::{self, Future},
+        pin::pin,
+        task::{Context, Poll, Waker},
+        time::Duration,
+    };
+
+    use super::with_timeout;
+    use crate::worker::WorkError;
+
+    #[test]
+    fn with_timeout_fails_pending_work_after_deadline() {
+        let mut cx = Context::from_waker(Waker::noop());
+
+        let ready = pin!(with_timeout(
+            Duration::ZERO,
+            future::ready(Ok::<_, WorkError>(1))
+        ));
+        assert!(matches!(ready.poll(&mut cx), Poll::Ready(Ok(1))));
+
+        let mut pending = pin!(with_timeout(
+            Duration::from_secs(3600),
+            future::pending::<Result<(), WorkError>>(),
+        ));
+        assert!(pending.as_mut().poll(&mut cx).is_pending());
+
+        let expired = pin!(with_timeout(
+            Duration::ZERO,
+            future::pending::<Result<(), WorkError>>(),
+        ));
+        assert!(matches!(
+            expired.poll(&mut cx),
+            Poll::Ready(Err(WorkError::TimedOut))
+        ));
+    }
+}
@@ -2,4 +2,5 @@ pub(crate) use self::executor::*;
 
 mod executor;
 mod helpers;
+pub(crate) mod script;
 pub(crate) mod tasks;
@@ -0,0 +1,202 @@
+//! Scripted flows that walk the player through the app one prompt at a time.
+//!
+//! A script is a list of [`ScriptStep`]s. Each step shows a prompt over the
+//! board and waits either for the player to read it or for a
+//! [`ScriptGoal`], an ordinary action such as placing a digit. The action
+//! handler checks the goal of the shown prompt after every action and answers
+//! the prompt once the goal is met, so scripts observe the app without
+//! changing how actions are handled.
+
+use futures_channel::oneshot;
+
+use crate::{
+    action::{
+        Action, AppAction, BoardMutationAction, HistoryAction, ScriptGoal, ScriptPrompt,
+        ScriptResult, SelectionAction, UiAction,
+    },
+    flow::FlowHandle,
+    i18n::Text,
+    state::{AppState, UiState},
+};
+
+/// One prompt of a script.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ScriptStep {
+    pub(crate) text: Text,
+    pub(crate) goal: Option<ScriptGoal>,
+}
+
+impl ScriptStep {
+    /// A prompt the player only reads.
+    #[must_use]
+    pub(crate) const fn note(text: Text) -> Self {
+        Self { text, goal: None }
+    }
+
+    /// A prompt that waits for the player to reach `goal`.
+    #[must_use]
+    pub(crate) const fn task(text: Text, goal: ScriptGoal) -> Self {
+        Self {
+            text,
+            goal: Some(goal),
+        }
+    }
+}
+
+/// Shows the steps in order, returning [`ScriptResult::Skipped`] as soon as
+/// the player skips one.
+pub(crate) async fn run_script(handle: &FlowHandle, steps: &[ScriptStep]) -> ScriptResult {
+    let mut result = ScriptResult::Completed;
+    for (index, step) in steps.iter().enumerate() {
+        let (responder, receiver) = oneshot::channel();
+        let prompt = ScriptPrompt {
+            text: step.text,
+            goal: step.goal,
+            step: index,
+            step_count: steps.len(),
+            responder: Some(responder),
+        };
+        handle.request_action(UiAction::ShowScriptPrompt(prompt).into());
+        if receiver.await.unwrap_or(ScriptResult::Skipped).is_skipped() {
+            result = ScriptResult::Skipped;
+            break;
+        }
+    }
+    handle.request_action(UiAction::ClearScriptPrompt.into());
+    result
+}
+
+impl ScriptGoal {
+    /// Returns whether `action` may reach the goal, checked before the
+    /// action is handled.
+    #[must_use]
+    pub(crate) fn is_attempted_by(self, action: &Action) -> bool {
+        match self {
+            Self::SelectCell => matches!(
+                action,
+                Action::App(AppAction::Selection(
+                    SelectionAction::SelectOrClearCell(_) | SelectionAction::MoveSelection(_)
+                ))
+            ),
+            Self::PlaceDigit | Self::ToggleNote => matches!(
+                action,
+                Action::App(AppAction::BoardMutation(
+                    BoardMutationAction::RequestDigit { .. }
+                        | BoardMutationAction::AdvanceCell { .. }
+                ))
+            ),
+            Self::Undo => matches!(
+                action,
+                Action::App(AppAction::History(
+                    HistoryAction::Undo | HistoryAction::UndoSteps(_)
+                ))
+            ),
+            Self::RequestHint => {
+                matches!(action, Action::Ui(UiAction::SetHintState(Some(_))))
+            }
+        }
+    }
+
+    /// Returns whether the goal is reached after handling an action that
+    /// attempted it.
+    #[must_use]
+    pub(crate) fn is_met(self, app_state: &AppState) -> bool {
+        let selected = app_state
+            .selected_cell()
+            .map(|pos| *app_state.game.cell(pos));
+        match self {
+            Self::SelectCell => selected.is_some(),
+            Self::PlaceDigit => selected.is_some_and(|cell| cell.as_filled().is_some()),
+            Self::ToggleNote => {
+                selected.is_some_and(|cell| cell.as_notes().is_some_and(|notes| !notes.is_empty()))
+            }
+            Self::Undo | Self::RequestHint => true,
+        }
+    }
+}
+
+/// Answers the shown prompt, letting its script move on.
+pub(crate) fn respond(ui_state: &mut UiState, result: ScriptResult) {
+    if let Some(responder) = ui_state
+        .script_prompt
+        .as_mut()
+        .and_then(|prompt| prompt.responder.take())
+    {
+        let _ = responder.send(result);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, rc::Rc};
+
+    use super::*;
+    use crate::{
+        action::{ActionRequestQueue, ScriptGoal},
+        flow::{FlowExecutor, FlowGroup, FlowPriority},
+    };
+
+    const STEPS: [ScriptStep; 2] = [
+        ScriptStep::note(Text::TutorialWelcome),
+        ScriptStep::task(Text::TutorialUndo, ScriptGoal::Undo),
+    ];
+
+    /// Polls the executor and applies the script prompt actions it requests.
+    fn poll(executor: &mut FlowExecutor, ui_state: &mut UiState) {
+        let mut queue = ActionRequestQueue::default();
+        executor.poll(&mut queue);
+        for action in queue.take_all() {
+            match action {
+                Action::Ui(UiAction::ShowScriptPrompt(prompt)) => {
+                    ui_state.script_prompt = Some(prompt);
+                }
+                Action::Ui(UiAction::ClearScriptPrompt) => ui_state.script_prompt = None,
+                _ => {}
+            }
+        }
+    }
+
+    fn spawn(executor: &mut FlowExecutor) -> Rc<Cell<Option<ScriptResult>>> {
+        let result = Rc::new(Cell::new(None));
+        let output = Rc::clone(&result);
+        executor.spawn(
+            FlowGroup::Script,
+            FlowPriority::Normal,
+            |handle| async move {
+                output.set(Some(run_script(&handle, &STEPS).await));
+            },
+        );
+        result
+    }
+
+    #[test]
+    fn steps_advance_when_answered() {
+        let mut executor = FlowExecutor::new();
+        let mut ui_state = UiState::new();
+        let result = spawn(&mut executor);
+
+        poll(&mut executor, &mut ui_state);
+        assert_eq!(ui_state.script_prompt.as_ref().unwrap().step, 0);
+        respond(&mut ui_state, ScriptResult::Completed);
+        poll(&mut executor, &mut ui_state);
+        let prompt = ui_state.script_prompt.as_ref().unwrap();
+        assert_eq!((prompt.step, prompt.goal), (1, Some(ScriptGoal::Undo)));
+        respond(&mut ui_state, ScriptResult::Completed);
+        poll(&mut executor, &mut ui_state);
+        assert!(ui_state.script_prompt.is_none());
+        assert_eq!(result.get(), Some(ScriptResult::Completed));
+    }
+
+    #[test]
+    fn skipping_ends_the_script() {
+        let mut executor = FlowExecutor::new();
+        let mut ui_state = UiState::new();
+        let result = spawn(&mut executor);
+
+        poll(&mut executor, &mut ui_state);
+        respond(&mut ui_state, ScriptResult::Skipped);
+        poll(&mut executor, &mut ui_state);
+        assert!(ui_state.script_prompt.is_none());
+        assert_eq!(result.get(), Some(ScriptResult::Skipped));
+    }
+}
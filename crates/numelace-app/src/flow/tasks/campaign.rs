@@ -0,0 +1,51 @@
+use numelace_game::Game;
+
+use crate::{
+    action::{ConfirmKind, Notification, NotificationKind, PuzzleLifecycleAction, UiAction},
+    campaign::PACKS,
+    flow::{FlowExecutor, FlowGroup, FlowHandle, FlowPriority, helpers},
+    state::{CampaignPuzzle, Screen},
+};
+
+/// Spawn a flow that starts a puzzle of the bundled packs.
+///
+/// Like the new game flow, it confirms before replacing a game in progress.
+pub(crate) fn spawn_start_campaign_puzzle_flow(
+    executor: &mut FlowExecutor,
+    game: &Game,
+    puzzle: CampaignPuzzle,
+) {
+    let in_progress = game.is_initialized() && !game.is_solved();
+    executor.spawn(FlowGroup::Modal, FlowPriority::Normal, |handle| {
+        start_campaign_puzzle_flow(handle, puzzle, in_progress)
+    });
+}
+
+async fn start_campaign_puzzle_flow(handle: FlowHandle, puzzle: CampaignPuzzle, in_progress: bool) {
+    if in_progress {
+        let result = helpers::show_confirm_dialog(&handle, ConfirmKind::NewGame).await;
+        if !result.is_confirmed() {
+            return;
+        }
+    }
+    match PACKS[puzzle.pack].puzzle(puzzle.index) {
+        Ok(game) => {
+            handle.request_action(
+                PuzzleLifecycleAction::StartCampaignPuzzle {
+                    puzzle,
+                    game: Box::new(game),
+                }
+                .into(),
+            );
+            handle.request_action(UiAction::ShowScreen(Screen::Game).into());
+        }
+        Err(err) => {
+            log::warn!("failed to load campaign puzzle: {err}");
+            helpers::notify(
+                &handle,
+                Notification::new(NotificationKind::CampaignPuzzleLoadFailed)
+                    .with_detail(err.to_string()),
+            );
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use numelace_game::Game;
+
+use crate::{
+    action::UiAction,
+    flow::{FlowExecutor, FlowGroup, FlowHandle, FlowId, FlowPriority, helpers},
+    worker::{self, tasks::DeductionDepthsRequestDto},
+};
+
+/// Spawns a background flow that analyzes `game` for the hardness overlay.
+pub(crate) fn spawn_analyze_deduction_depths_flow(
+    executor: &mut FlowExecutor,
+    game: &Game,
+) -> Option<FlowId> {
+    executor.spawn(FlowGroup::Background, FlowPriority::Normal, |handle| {
+        analyze_deduction_depths_flow(handle, game.into())
+    })
+}
+
+/// Async flow that runs the analysis in the worker and shows its depths.
+///
+/// A board the analysis gives up on is left without an overlay. Failures are
+/// logged and dropped, keeping the depths shown before.
+async fn analyze_deduction_depths_flow(handle: FlowHandle, request: DeductionDepthsRequestDto) {
+    let work = helpers::with_timeout(
+        handle.timeouts().analyze_deduction_depths,
+        worker::request_deduction_depths(request),
+    );
+    let depths = match work.await {
+        Ok(depths) => depths.map(TryInto::try_into).transpose(),
+        Err(err) => {
+            log::warn!("failed to analyze the deduction depths: {err}");
+            return;
+        }
+    };
+    match depths {
+        Ok(depths) => handle.request_action(UiAction::SetDeductionDepths(depths).into()),
+        Err(err) => log::warn!("failed to decode the deduction depths: {err}"),
+    }
+}
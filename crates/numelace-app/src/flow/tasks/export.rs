@@ -0,0 +1,75 @@
+use eframe::egui::ColorImage;
+use numelace_game::Game;
+
+use crate::{
+    action::{AlertKind, Notification, NotificationKind, UiAction},
+    export::{self, BoardImage, ExportContent},
+    flow::{FlowExecutor, FlowGroup, FlowHandle, FlowPriority, helpers},
+    state::ClipboardContent,
+};
+
+const BOARD_IMAGE_FILE_NAME: &str = "numelace-board.png";
+
+/// Spawn an SVG export flow unless another modal flow is active.
+pub(crate) fn spawn_export_flow(executor: &mut FlowExecutor, game: &Game, content: ExportContent) {
+    executor.spawn(FlowGroup::Modal, FlowPriority::Normal, |handle| {
+        let svg = export::render_svg(game, content);
+        export_flow(handle, content, svg)
+    });
+}
+
+/// Async flow that saves a rendered export and reports where it went.
+async fn export_flow(handle: FlowHandle, content: ExportContent, svg: String) {
+    let saved = export::save_file(content.file_name(), "image/svg+xml", svg.as_bytes()).await;
+    let kind = match saved {
+        Ok(Some(path)) => AlertKind::ExportSaved { path },
+        // The browser shows its own download UI.
+        Ok(None) => return,
+        Err(err) => {
+            log::warn!("{err}");
+            AlertKind::ExportFailed {
+                message: err.to_string(),
+            }
+        }
+    };
+    helpers::show_alert_dialog(&handle, kind).await;
+}
+
+/// Spawn a flow that shares a PNG image of the board.
+///
+/// Native builds copy the image to the clipboard; web builds download it,
+/// since browsers only allow writing images to the clipboard asynchronously.
+pub(crate) fn spawn_share_board_image_flow(executor: &mut FlowExecutor, game: &Game) {
+    executor.spawn(FlowGroup::Modal, FlowPriority::Normal, |handle| {
+        share_board_image_flow(handle, export::render_image(game))
+    });
+}
+
+async fn share_board_image_flow(handle: FlowHandle, image: BoardImage) {
+    let kind = if cfg!(target_arch = "wasm32") {
+        let saved = match export::encode_png(&image) {
+            Ok(png) => export::save_file(BOARD_IMAGE_FILE_NAME, "image/png", &png)
+                .await
+                .map_err(|err| err.to_string()),
+            Err(err) => Err(err.to_string()),
+        };
+        match saved {
+            Ok(Some(path)) => AlertKind::ExportSaved { path },
+            Ok(None) => return,
+            Err(message) => {
+                log::warn!("failed to save board image: {message}");
+                AlertKind::ExportFailed { message }
+            }
+        }
+    } else {
+        let size = [image.width as usize, image.height as usize];
+        let image = ColorImage::from_rgba_unmultiplied(size, &image.rgba);
+        handle.request_action(UiAction::CopyToClipboard(ClipboardContent::Image(image)).into());
+        helpers::notify(
+            &handle,
+            Notification::new(NotificationKind::BoardImageCopied),
+        );
+        return;
+    };
+    helpers::show_alert_dialog(&handle, kind).await;
+}
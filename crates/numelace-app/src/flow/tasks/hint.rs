@@ -1,46 +1,64 @@
-use numelace_core::{ConsistencyError, Position};
-use numelace_game::{CellState, Game};
+use std::time::Duration;
+
+use numelace_core::{DigitPositions, Position};
+use numelace_game::{Game, Hint, HintError};
 use numelace_solver::{
-    BoxedTechniqueStep, SolverError, TechniqueGrid, TechniqueSolver, technique::NakedSingle,
+    BoxedTechniqueStep, SolverBudget, TechniqueApplication, TechniquePriority, TechniqueStep,
 };
 
 use crate::{
     action::{
         AlertKind, BoardMutationAction, ConfirmKind, HistoryAction, NotesFillScope, UiAction,
+        UpdateStateAction,
     },
-    flow::{FlowExecutor, FlowHandle, helpers},
+    flow::{FlowExecutor, FlowGroup, FlowHandle, FlowPriority, helpers},
     state::{HintStage, HintState},
+    worker::tasks::ContradictionWitnessDto,
 };
 
+/// Time limit of a hint search.
+///
+/// Each technique is quick on its own, but chain searches on an open board
+/// can add up; such searches are reported as undetermined instead of freezing
+/// the hint.
+const HINT_BUDGET: SolverBudget = SolverBudget::new().with_max_duration(Duration::from_secs(5));
+
 struct HintRequest {
     game: Game,
+    priority: TechniquePriority,
     hint_state: Option<HintState>,
+    /// Stage a newly found hint starts at.
+    first_stage: HintStage,
 }
 
-#[derive(Debug, derive_more::Display, derive_more::Error, derive_more::From)]
-enum HintStepError {
-    #[display("inconsistency detected: {_0}")]
-    Inconsistent(#[from] ConsistencyError),
-    #[display("hint step conflicts with solution")]
-    SolutionMismatch,
+/// Cells to point out in the inconsistency dialog.
+fn hint_witness(err: &HintError) -> ContradictionWitnessDto {
+    match err {
+        HintError::Inconsistent(err) => ContradictionWitnessDto {
+            invalid_cells: err.positions().iter().map(Position::index).collect(),
+            conflicting_entries: vec![],
+        },
+        HintError::SolutionMismatch | HintError::Undetermined => ContradictionWitnessDto::default(),
+    }
 }
 
-/// Spawn a hint flow if no other flows are active.
+/// Spawn a hint flow unless another modal flow is active.
 pub(crate) fn spawn_hint_flow(
     executor: &mut FlowExecutor,
     game: &Game,
+    priority: &TechniquePriority,
     hint_state: Option<HintState>,
+    first_stage: HintStage,
 ) {
-    if !executor.is_idle() {
-        return;
-    }
-    let is_solved = game.is_solved();
-    let handle = executor.handle();
-    let request = HintRequest {
-        game: game.clone(),
-        hint_state,
-    };
-    executor.spawn(hint_flow(handle, request, is_solved));
+    executor.spawn(FlowGroup::Modal, FlowPriority::Normal, |handle| {
+        let request = HintRequest {
+            game: game.clone(),
+            priority: priority.clone(),
+            hint_state,
+            first_stage,
+        };
+        hint_flow(handle, request, game.is_solved())
+    });
 }
 
 async fn hint_flow(handle: FlowHandle, request: HintRequest, is_solved: bool) {
@@ -54,31 +72,55 @@ async fn hint_flow(handle: FlowHandle, request: HintRequest, is_solved: bool) {
             stage: HintStage::Stage3Apply,
             ..
         }) => {
-            let result = find_hint_step(&request.game);
+            let result = request.game.find_hint(&request.priority, HINT_BUDGET);
 
             match result {
-                Ok(Some((true, step))) => {
+                Ok(Some(Hint {
+                    step,
+                    with_notes: true,
+                })) => {
+                    handle.request_action(
+                        UpdateStateAction::RecordHintTechnique {
+                            key: step.technique_key().to_owned(),
+                            cells: application_cells(step.as_ref()),
+                        }
+                        .into(),
+                    );
                     let hint_state = HintState {
-                        stage: HintStage::Stage1,
+                        stage: request.first_stage,
                         step,
                     };
                     handle.request_action(UiAction::SetHintState(Some(hint_state)).into());
                 }
-                Ok(Some((false, _step))) => handle_hint_notes_maybe_incorrect(&handle).await,
+                Ok(Some(Hint {
+                    with_notes: false, ..
+                })) => handle_hint_notes_maybe_incorrect(&handle).await,
                 Ok(None) => {
                     handle.request_action(UiAction::ClearHintState.into());
                     let _ = helpers::show_alert_dialog(&handle, AlertKind::HintStuckNoStep).await;
                 }
-                Err(HintStepError::Inconsistent(_) | HintStepError::SolutionMismatch) => {
-                    let result =
-                        helpers::show_confirm_dialog(&handle, ConfirmKind::HintInconsistent).await;
+                Err(HintError::Undetermined) => {
+                    handle.request_action(UiAction::ClearHintState.into());
+                    let _ = helpers::show_alert_dialog(&handle, AlertKind::HintUndetermined).await;
+                }
+                Err(err) => {
+                    let witness = hint_witness(&err);
+                    let result = helpers::show_confirm_dialog(
+                        &handle,
+                        ConfirmKind::HintInconsistent { witness },
+                    )
+                    .await;
                     if result.is_confirmed() {
-                        handle_hint_undo(&handle).await;
+                        handle_hint_undo(&handle, &request.priority, request.first_stage).await;
                     }
                 }
             }
         }
         Some(mut hint_state) => match hint_state.stage {
+            HintStage::Stage0 => {
+                hint_state.stage = HintStage::Stage1;
+                handle.request_action(UiAction::SetHintState(Some(hint_state)).into());
+            }
             HintStage::Stage1 => {
                 hint_state.stage = HintStage::Stage2;
                 handle.request_action(UiAction::SetHintState(Some(hint_state)).into());
@@ -99,6 +141,21 @@ async fn hint_flow(handle: FlowHandle, request: HintRequest, is_solved: bool) {
     }
 }
 
+/// Returns the cells a step places a digit in or removes candidates from.
+fn application_cells(step: &dyn TechniqueStep) -> DigitPositions {
+    step.application()
+        .into_iter()
+        .fold(
+            DigitPositions::new(),
+            |cells, application| match application {
+                TechniqueApplication::Placement { position, .. } => {
+                    cells | DigitPositions::from_elem(position)
+                }
+                TechniqueApplication::CandidateElimination { positions, .. } => cells | positions,
+            },
+        )
+}
+
 async fn handle_hint_notes_maybe_incorrect(handle: &FlowHandle) {
     let result = helpers::show_confirm_dialog(handle, ConfirmKind::HintNotesMaybeIncorrect).await;
     if result.is_confirmed() {
@@ -112,78 +169,11 @@ async fn handle_hint_notes_maybe_incorrect(handle: &FlowHandle) {
     handle.request_action(UiAction::SetHintState(None).into());
 }
 
-fn find_naked_single_hint(game: &Game, grid: &TechniqueGrid) -> Option<BoxedTechniqueStep> {
-    // Naked single hints must consider placement validity even when no eliminations occur.
-    // The solver's NakedSingle::find_step intentionally gates on eliminations, which can
-    // skip valid placements once peers already lack that candidate.
-    for pos in Position::ALL {
-        match game.cell(pos) {
-            CellState::Empty | CellState::Notes(_) => {
-                // Empty/notes cells are valid hint targets.
-            }
-            CellState::Given(_) | CellState::Filled(_) => continue,
-        }
-
-        let Some(step) = NakedSingle::build_step(grid, pos) else {
-            continue;
-        };
-
-        return Some(step);
-    }
-
-    None
-}
-
-fn find_hint_step_from_grid(
-    game: &Game,
-    grid: &TechniqueGrid,
-    solver: &TechniqueSolver,
-) -> Result<Option<BoxedTechniqueStep>, HintStepError> {
-    grid.check_consistency()?;
-
-    if let Some(step) = find_naked_single_hint(game, grid) {
-        if game.verify_hint_step(step.as_ref()) {
-            return Ok(Some(step));
-        }
-        return Err(HintStepError::SolutionMismatch);
-    }
-
-    let step = solver.find_step(grid).map_err(|err| match err {
-        SolverError::Inconsistent(consistency) => HintStepError::Inconsistent(consistency),
-    })?;
-
-    match step {
-        Some(step) => {
-            if game.verify_hint_step(step.as_ref()) {
-                return Ok(Some(step));
-            }
-            Err(HintStepError::SolutionMismatch)
-        }
-        None => Ok(None),
-    }
-}
-
-fn find_hint_step(game: &Game) -> Result<Option<(bool, BoxedTechniqueStep)>, HintStepError> {
-    let solver = TechniqueSolver::with_all_techniques();
-    let grid_with_notes = TechniqueGrid::from(game.to_candidate_grid_with_notes());
-
-    // Notes-derived grids can be stale; treat inconsistency or solution mismatch as a signal
-    // to fall back to the no-notes grid before surfacing an error.
-    match find_hint_step_from_grid(game, &grid_with_notes, &solver) {
-        Ok(Some(step_with_notes)) => return Ok(Some((true, step_with_notes))),
-        Ok(None) | Err(HintStepError::Inconsistent(_) | HintStepError::SolutionMismatch) => {}
-    }
-
-    let grid = TechniqueGrid::from(game.to_candidate_grid());
-
-    if let Some(step) = find_hint_step_from_grid(game, &grid, &solver)? {
-        return Ok(Some((false, step)));
-    }
-
-    Ok(None)
-}
-
-async fn handle_hint_undo(handle: &FlowHandle) {
+async fn handle_hint_undo(
+    handle: &FlowHandle,
+    priority: &TechniquePriority,
+    first_stage: HintStage,
+) {
     let Some(games) = helpers::request_undo_games(handle).await else {
         return;
     };
@@ -191,8 +181,8 @@ async fn handle_hint_undo(handle: &FlowHandle) {
         return;
     }
 
-    let outcome = scan_hint_rollback(&games);
-    apply_hint_rollback_result(handle, outcome).await;
+    let outcome = scan_hint_rollback(&games, priority);
+    apply_hint_rollback_result(handle, outcome, first_stage).await;
 }
 
 enum HintRollbackOutcome {
@@ -209,19 +199,28 @@ enum HintRollbackOutcome {
     Inconsistent,
 }
 
-fn scan_hint_rollback(games: &[Game]) -> HintRollbackOutcome {
+fn scan_hint_rollback(games: &[Game], priority: &TechniquePriority) -> HintRollbackOutcome {
     let mut first_consistent_index = None;
 
     for (index, game) in games.iter().enumerate() {
-        match find_hint_step(game) {
-            Ok(Some((true, step))) => return HintRollbackOutcome::FoundWithNotes { index, step },
-            Ok(Some((false, _))) => return HintRollbackOutcome::FoundWithoutNotes { index },
+        match game.find_hint(priority, HINT_BUDGET) {
+            Ok(Some(Hint {
+                step,
+                with_notes: true,
+            })) => return HintRollbackOutcome::FoundWithNotes { index, step },
+            Ok(Some(Hint {
+                with_notes: false, ..
+            })) => return HintRollbackOutcome::FoundWithoutNotes { index },
             Ok(None) => {
                 if first_consistent_index.is_none() {
                     first_consistent_index = Some(index);
                 }
             }
-            Err(HintStepError::Inconsistent(_) | HintStepError::SolutionMismatch) => {}
+            // A board the solver cannot decide is passed over like an
+            // inconsistent one.
+            Err(
+                HintError::Inconsistent(_) | HintError::SolutionMismatch | HintError::Undetermined,
+            ) => {}
         }
     }
 
@@ -232,7 +231,11 @@ fn scan_hint_rollback(games: &[Game]) -> HintRollbackOutcome {
     HintRollbackOutcome::Inconsistent
 }
 
-async fn apply_hint_rollback_result(handle: &FlowHandle, outcome: HintRollbackOutcome) {
+async fn apply_hint_rollback_result(
+    handle: &FlowHandle,
+    outcome: HintRollbackOutcome,
+    first_stage: HintStage,
+) {
     match outcome {
         HintRollbackOutcome::FoundWithNotes { index, step } => {
             handle.request_action(HistoryAction::UndoSteps(index).into());
@@ -244,7 +247,7 @@ async fn apply_hint_rollback_result(handle: &FlowHandle, outcome: HintRollbackOu
             }
 
             let hint_state = HintState {
-                stage: HintStage::Stage1,
+                stage: first_stage,
                 step,
             };
             handle.request_action(UiAction::SetHintState(Some(hint_state)).into());
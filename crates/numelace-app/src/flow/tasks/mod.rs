@@ -1,19 +1,28 @@
-pub(crate) use self::{hint::*, new_game::*, solvability::*};
+pub(crate) use self::{
+    campaign::*, deduction_depth::*, export::*, hint::*, new_game::*, notes_audit::*, recovery::*,
+    settings_file::*, share::*, solvability::*, tutorial::*,
+};
 use crate::{
-    action::{BoardMutationAction, ConfirmKind},
-    flow::{FlowExecutor, FlowHandle, helpers},
+    action::{BoardMutationAction, ConfirmKind, UpdateStateAction},
+    flow::{FlowExecutor, FlowGroup, FlowHandle, FlowPriority, helpers},
+    state::PuzzleDifficulty,
+    worker::{self, tasks::GradePuzzleRequestDto},
 };
 
+mod campaign;
+mod deduction_depth;
+mod export;
 mod hint;
 mod new_game;
+mod notes_audit;
+mod recovery;
+mod settings_file;
+mod share;
 mod solvability;
+mod tutorial;
 
 pub(crate) fn spawn_reset_inputs_flow(executor: &mut FlowExecutor) {
-    if !executor.is_idle() {
-        return;
-    }
-    let handle = executor.handle();
-    executor.spawn(reset_inputs_flow(handle));
+    executor.spawn(FlowGroup::Modal, FlowPriority::Normal, reset_inputs_flow);
 }
 
 async fn reset_inputs_flow(handle: FlowHandle) {
@@ -23,3 +32,51 @@ async fn reset_inputs_flow(handle: FlowHandle) {
     }
     handle.request_action(BoardMutationAction::ResetInputs.into());
 }
+
+/// Asks for confirmation, then reveals answers with `action`.
+pub(crate) fn spawn_reveal_flow(
+    executor: &mut FlowExecutor,
+    kind: ConfirmKind,
+    action: BoardMutationAction,
+) {
+    executor.spawn(FlowGroup::Modal, FlowPriority::Normal, move |handle| {
+        reveal_flow(handle, kind, action)
+    });
+}
+
+async fn reveal_flow(handle: FlowHandle, kind: ConfirmKind, action: BoardMutationAction) {
+    let result = helpers::show_confirm_dialog(&handle, kind).await;
+    if !result.is_confirmed() {
+        return;
+    }
+    handle.request_action(action.into());
+}
+
+/// Rates a new or loaded puzzle in the background and records the result.
+///
+/// Loaded puzzles set `switch_profile` to also switch to the assist profile
+/// of the rated difficulty; new games switched when they started.
+///
+/// Runs as a background flow, so it is cancelled when another puzzle replaces
+/// this one. Failures only leave the difficulty unknown, so they are logged
+/// and dropped.
+async fn grade_puzzle_flow(
+    handle: FlowHandle,
+    request: GradePuzzleRequestDto,
+    switch_profile: bool,
+) {
+    let work = helpers::with_timeout(
+        handle.timeouts().grade_puzzle,
+        worker::request_grade_puzzle(request),
+    );
+    match work.await {
+        Ok(grade) => {
+            let difficulty = PuzzleDifficulty::from(grade);
+            handle.request_action(UpdateStateAction::SetPuzzleDifficulty(Some(difficulty)).into());
+            if switch_profile {
+                handle.request_action(UpdateStateAction::SwitchAssistProfile(difficulty).into());
+            }
+        }
+        Err(err) => log::warn!("failed to grade the puzzle: {err}"),
+    }
+}
@@ -1,12 +1,19 @@
 use futures_channel::oneshot;
 use numelace_game::Game;
-use numelace_generator::GeneratedPuzzle;
+use numelace_generator::{ClueSymmetry, GeneratedPuzzle, GenerationTrace};
+use numelace_solver::TechniquePriority;
 
 use crate::{
-    action::{ConfirmKind, ModalRequest, PuzzleLifecycleAction, SpinnerKind, UiAction},
-    flow::{FlowExecutor, FlowHandle, helpers},
-    state::NewGameOptions,
-    worker,
+    action::{
+        ConfirmKind, ModalRequest, NotificationKind, PuzzleLifecycleAction, SpinnerKind, UiAction,
+    },
+    flow::{FlowExecutor, FlowGroup, FlowHandle, FlowPriority, helpers},
+    puzzle_db,
+    state::{DifficultyPreset, NewGameOptions},
+    worker::{
+        self, WorkError,
+        tasks::{GeneratePuzzleRequestDto, GradePuzzleRequestDto},
+    },
 };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, derive_more::IsVariant)]
@@ -16,11 +23,19 @@ enum GameState {
     Solved,
 }
 
-/// Spawn a new game flow if no other flows are active.
-pub(crate) fn spawn_new_game_flow(executor: &mut FlowExecutor, game: &Game) {
-    if !executor.is_idle() {
-        return;
-    }
+/// Spawn a new game flow, cancelling any ordinary modal flow in its way.
+///
+/// With `trace`, the generator trace of the new puzzle is shown as well.
+/// With `options`, the options modal is skipped and the puzzle is generated
+/// from them, e.g. to generate the current puzzle again; the saved options are
+/// left alone.
+pub(crate) fn spawn_new_game_flow(
+    executor: &mut FlowExecutor,
+    game: &Game,
+    priority: &TechniquePriority,
+    trace: bool,
+    options: Option<NewGameOptions>,
+) {
     let game_state = if !game.is_initialized() {
         GameState::Uninitialized
     } else if game.is_solved() {
@@ -28,14 +43,22 @@ pub(crate) fn spawn_new_game_flow(executor: &mut FlowExecutor, game: &Game) {
     } else {
         GameState::InProgress
     };
-    let handle = executor.handle();
-    executor.spawn(new_game_flow(handle, game_state));
+    executor.spawn(FlowGroup::Modal, FlowPriority::High, |handle| {
+        new_game_flow(handle, game_state, priority.clone(), trace, options)
+    });
 }
 
 /// Async flow for new game confirmation + work dispatch.
 ///
-/// On confirm, it runs the background request and awaits the response.
-async fn new_game_flow(handle: FlowHandle, game_state: GameState) {
+/// On confirm, it runs the background request and awaits the response, falling
+/// back to a bundled puzzle if the worker cannot generate one.
+async fn new_game_flow(
+    handle: FlowHandle,
+    game_state: GameState,
+    priority: TechniquePriority,
+    trace: bool,
+    regenerate: Option<NewGameOptions>,
+) {
     if game_state.is_in_progress() {
         let result = helpers::show_confirm_dialog(&handle, ConfirmKind::NewGame).await;
         if !result.is_confirmed() {
@@ -43,17 +66,94 @@ async fn new_game_flow(handle: FlowHandle, game_state: GameState) {
         }
     }
 
-    let can_cancel = !game_state.is_uninitialized();
-    let Some(options) = show_new_game_options_modal(&handle, can_cancel).await else {
-        return;
+    let options = if let Some(options) = regenerate {
+        options
+    } else {
+        let can_cancel = !game_state.is_uninitialized();
+        let Some(options) = show_new_game_options_modal(&handle, can_cancel).await else {
+            return;
+        };
+        options
+    };
+
+    let fallback = database_preset(&options);
+    let chess = options.chess_rules();
+    let difficulty = options.difficulty;
+    let mode = options.mode;
+    let request = GeneratePuzzleRequestDto {
+        trace,
+        ..GeneratePuzzleRequestDto::new(options.clone(), &priority)
     };
+    let work = helpers::with_timeout(
+        handle.timeouts().generate_puzzle,
+        worker::request_generate_puzzle(request),
+    );
+    let mut generation_trace = None;
+    let puzzle = helpers::with_spinner(&handle, SpinnerKind::NewGame, work)
+        .await
+        .and_then(|mut dto| {
+            generation_trace = dto.trace.take();
+            GeneratedPuzzle::try_from(dto).map_err(|err| {
+                log::warn!("failed to deserialize generated puzzle dto: {err}");
+                WorkError::DeserializationFailed
+            })
+        });
+    let (puzzle, generated_with) = match puzzle {
+        Ok(puzzle) => {
+            let generated_with = NewGameOptions {
+                seed: puzzle.seed.to_string(),
+                ..options
+            };
+            (puzzle, Some(Box::new(generated_with)))
+        }
+        Err(err) => {
+            let bundled = fallback
+                .filter(|_| err.is_unavailable())
+                .and_then(puzzle_db::sample);
+            let Some(puzzle) = bundled else {
+                helpers::notify_error(&handle, NotificationKind::NewGameFailed, &err);
+                return;
+            };
+            log::info!("using a bundled puzzle after generation failed: {err}");
+            (puzzle, None)
+        }
+    };
+    // Grading the generated puzzle shows its difficulty and sets the par times of timed games and races.
+    let game = Game::new(puzzle.clone()).with_chess_rules(chess);
+    let grade_request = GradePuzzleRequestDto::new(&game, &priority);
+    handle.request_action(
+        PuzzleLifecycleAction::StartNewGame {
+            puzzle: Box::new(puzzle),
+            chess,
+            difficulty,
+            mode,
+            generated_with,
+        }
+        .into(),
+    );
+    if let Some(request) = grade_request {
+        handle.spawn_background(super::grade_puzzle_flow(handle.clone(), request, false));
+    }
+    match generation_trace.map(GenerationTrace::try_from) {
+        Some(Ok(trace)) => {
+            handle.request_action(UiAction::ShowGenerationTrace(Box::new(trace)).into());
+        }
+        Some(Err(err)) => log::warn!("failed to deserialize generation trace dto: {err}"),
+        None => {}
+    }
+}
 
-    let work = worker::request_generate_puzzle(options.into());
-    let response = helpers::with_spinner(&handle, SpinnerKind::NewGame, work).await;
-    let dto = response.unwrap();
-    let puzzle = GeneratedPuzzle::try_from(dto)
-        .unwrap_or_else(|err| panic!("failed to deserialize generated puzzle dto: {err}"));
-    handle.request_action(PuzzleLifecycleAction::StartNewGame(puzzle).into());
+/// Returns the preset to draw a bundled puzzle from if generation cannot run.
+///
+/// Bundled puzzles only stand in for plain preset games: a seed asks for one
+/// specific puzzle, and the database holds no chess, jigsaw or symmetric
+/// puzzles.
+fn database_preset(options: &NewGameOptions) -> Option<DifficultyPreset> {
+    let plain = options.seed.is_empty()
+        && options.chess_rules().is_empty()
+        && !options.jigsaw
+        && options.symmetry == ClueSymmetry::None;
+    (plain && options.difficulty != DifficultyPreset::Custom).then_some(options.difficulty)
 }
 
 async fn show_new_game_options_modal(
@@ -61,14 +161,11 @@ async fn show_new_game_options_modal(
     can_cancel: bool,
 ) -> Option<NewGameOptions> {
     let (responder, receiver) = oneshot::channel();
-    handle.request_action(
-        UiAction::OpenModal(ModalRequest::NewGameOptions {
-            can_cancel,
-            responder: Some(responder),
-        })
-        .into(),
-    );
+    handle.open_modal(ModalRequest::NewGameOptions {
+        can_cancel,
+        responder: Some(responder),
+    });
     let result = receiver.await.unwrap_or_default();
-    handle.request_action(UiAction::CloseModal.into());
+    handle.close_modal();
     result
 }
@@ -0,0 +1,33 @@
+use numelace_core::{DigitSet, Position};
+
+use crate::{
+    action::{AlertKind, BoardMutationAction, ConfirmKind, UiAction},
+    flow::{FlowExecutor, FlowGroup, FlowHandle, FlowPriority, helpers},
+};
+
+/// Spawn a flow that reports the given illegal notes and offers to remove them.
+pub(crate) fn spawn_audit_notes_flow(
+    executor: &mut FlowExecutor,
+    illegal_notes: Vec<(Position, DigitSet)>,
+) {
+    executor.spawn(FlowGroup::Modal, FlowPriority::Normal, |handle| {
+        audit_notes_flow(handle, illegal_notes)
+    });
+}
+
+/// Async flow that highlights illegal notes while asking whether to remove them.
+async fn audit_notes_flow(handle: FlowHandle, illegal_notes: Vec<(Position, DigitSet)>) {
+    if illegal_notes.is_empty() {
+        helpers::show_alert_dialog(&handle, AlertKind::NoIllegalNotes).await;
+        return;
+    }
+
+    let count = illegal_notes.iter().map(|(_, digits)| digits.len()).sum();
+    handle.request_action(UiAction::SetNoteAudit(Some(illegal_notes)).into());
+    let result =
+        helpers::show_confirm_dialog(&handle, ConfirmKind::IllegalNotesFound { count }).await;
+    handle.request_action(UiAction::SetNoteAudit(None).into());
+    if result.is_confirmed() {
+        handle.request_action(BoardMutationAction::RemoveIllegalNotes.into());
+    }
+}
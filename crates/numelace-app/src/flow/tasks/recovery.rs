@@ -0,0 +1,46 @@
+use crate::{
+    action::{ConfirmKind, FlowAction, PuzzleLifecycleAction},
+    flow::{FlowExecutor, FlowGroup, FlowHandle, FlowPriority, helpers},
+    persistence::journal,
+    state::AppState,
+};
+
+/// Spawn a flow that offers to restore progress recovered from the journal.
+///
+/// Runs on the first frame with the same precedence as opening a shared
+/// link, which it hands the link to once the player has decided.
+pub(crate) fn spawn_recover_progress_flow(
+    executor: &mut FlowExecutor,
+    state: Box<AppState>,
+    shared_code: Option<String>,
+    is_initialized: bool,
+) {
+    executor.spawn(FlowGroup::Modal, FlowPriority::High, |handle| {
+        recover_progress_flow(handle, state, shared_code, is_initialized)
+    });
+}
+
+/// Async flow for restoring recovered progress.
+///
+/// Declining discards the journal, so the offer is not repeated on the next
+/// start. Falls back to the new game flow when nothing is restored and there
+/// is no game to continue.
+async fn recover_progress_flow(
+    handle: FlowHandle,
+    state: Box<AppState>,
+    shared_code: Option<String>,
+    is_initialized: bool,
+) {
+    let result = helpers::show_confirm_dialog(&handle, ConfirmKind::RecoverProgress).await;
+    let restored = result.is_confirmed();
+    if restored {
+        handle.request_action(PuzzleLifecycleAction::RestoreState(state).into());
+    } else {
+        journal::clear();
+    }
+    if let Some(code) = shared_code {
+        handle.request_action(FlowAction::LoadSharedPuzzle(code).into());
+    } else if !restored && !is_initialized {
+        handle.request_action(FlowAction::StartNewGame.into());
+    }
+}
@@ -0,0 +1,114 @@
+use crate::{
+    action::{Notification, NotificationKind, UpdateStateAction},
+    export::{self, OpenFileError},
+    flow::{FlowExecutor, FlowGroup, FlowHandle, FlowPriority, helpers},
+    persistence::settings_file::{self, SETTINGS_FILE_NAME, SettingsFileError},
+    state::Settings,
+};
+
+/// Spawn a flow that saves the settings to a file.
+///
+/// Results are reported as notifications, so the flow runs alongside the
+/// settings modal it is started from.
+pub(crate) fn spawn_export_settings_flow(executor: &mut FlowExecutor, settings: &Settings) {
+    executor.spawn(FlowGroup::Background, FlowPriority::Normal, |handle| {
+        let contents = settings_file::encode_settings(settings);
+        async move {
+            let saved =
+                export::save_file(SETTINGS_FILE_NAME, "application/json", contents.as_bytes())
+                    .await;
+            let notification = match saved {
+                Ok(Some(path)) => {
+                    Notification::new(NotificationKind::SettingsExported).with_detail(path)
+                }
+                // Cancelled, or the browser shows its own download UI.
+                Ok(None) => return,
+                Err(err) => {
+                    log::warn!("{err}");
+                    Notification::new(NotificationKind::SettingsExportFailed)
+                        .with_detail(err.to_string())
+                }
+            };
+            helpers::notify(&handle, notification);
+        }
+    });
+}
+
+/// Spawn a flow that replaces the settings with the contents of a file.
+pub(crate) fn spawn_import_settings_flow(executor: &mut FlowExecutor) {
+    executor.spawn(
+        FlowGroup::Background,
+        FlowPriority::Normal,
+        import_settings_flow,
+    );
+}
+
+#[derive(Debug, derive_more::Display, derive_more::From)]
+enum ImportSettingsError {
+    Open(OpenFileError),
+    Decode(SettingsFileError),
+}
+
+async fn import_settings_flow(handle: FlowHandle) {
+    let opened = export::open_file(SETTINGS_FILE_NAME, ".json,application/json").await;
+
+    let notification = match read_settings(opened) {
+        Ok(Some(settings)) => {
+            handle.request_action(UpdateStateAction::UpdateSettings(Box::new(settings)).into());
+            Notification::new(NotificationKind::SettingsImported)
+        }
+        Ok(None) => return,
+        Err(err) => {
+            log::warn!("failed to import settings: {err}");
+            Notification::new(NotificationKind::SettingsImportFailed).with_detail(err.to_string())
+        }
+    };
+    helpers::notify(&handle, notification);
+}
+
+/// Decodes the settings file the player picked; `None` if they cancelled.
+fn read_settings(
+    opened: Result<Option<Vec<u8>>, OpenFileError>,
+) -> Result<Option<Settings>, ImportSettingsError> {
+    let Some(contents) = opened? else {
+        return Ok(None);
+    };
+    Ok(Some(settings_file::decode_settings(&contents)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ImportSettingsError, read_settings};
+    use crate::persistence::settings_file::SettingsFileError;
+
+    #[test]
+    fn import_reports_files_from_a_newer_version() {
+        let contents =
+            br#"{"format":"numelace-settings","version":2,"settings":{"language":"Japanese"}}"#;
+
+        let err = read_settings(Ok(Some(contents.to_vec()))).unwrap_err();
+
+        assert!(matches!(
+            err,
+            ImportSettingsError::Decode(SettingsFileError::UnsupportedVersion { version: 2 })
+        ));
+        assert!(err.to_string().contains("version 2"), "{err}");
+    }
+
+    #[test]
+    fn import_reports_unknown_formats() {
+        let contents = br#"{"format":"numelace-board","version":1,"settings":{}}"#;
+
+        let err = read_settings(Ok(Some(contents.to_vec()))).unwrap_err();
+
+        assert!(matches!(
+            err,
+            ImportSettingsError::Decode(SettingsFileError::UnknownFormat)
+        ));
+    }
+
+    #[test]
+    fn cancelled_import_changes_nothing() {
+        assert!(read_settings(Ok(None)).unwrap().is_none());
+    }
+}
@@ -0,0 +1,126 @@
+use numelace_core::PuzzleFingerprint;
+use numelace_game::{Game, StateCodeError};
+use numelace_solver::TechniquePriority;
+
+use crate::{
+    action::{
+        AlertKind, ConfirmKind, FlowAction, Notification, NotificationKind, PuzzleLifecycleAction,
+        UiAction, UpdateStateAction,
+    },
+    flow::{FlowExecutor, FlowGroup, FlowHandle, FlowPriority, helpers},
+    share,
+    state::{ClipboardContent, PuzzleMetadataStore, fingerprint},
+    worker::tasks::GradePuzzleRequestDto,
+};
+
+/// Spawn a flow that copies a share link for the current puzzle.
+///
+/// The flow only reports back through a notification, so it runs alongside
+/// modal flows.
+pub(crate) fn spawn_copy_share_link_flow(
+    executor: &mut FlowExecutor,
+    game: &Game,
+    include_progress: bool,
+) {
+    executor.spawn(FlowGroup::Background, FlowPriority::Normal, |handle| {
+        let mut game = game.clone();
+        if !include_progress {
+            game.clear_inputs();
+        }
+        let url = share::share_url(&game.to_state_code());
+        async move {
+            handle.request_action(UiAction::CopyToClipboard(ClipboardContent::Text(url)).into());
+            helpers::notify(
+                &handle,
+                Notification::new(NotificationKind::ShareLinkCopied),
+            );
+        }
+    });
+}
+
+/// Spawn a flow that offers to replace the current game with a shared puzzle.
+///
+/// Opening a link takes precedence over ordinary modal flows, which are
+/// cancelled instead of swallowing the link. The dialog mentions when the
+/// player already solved the puzzle, and a confirmed classic puzzle is added
+/// to the metadata store.
+pub(crate) fn spawn_load_shared_puzzle_flow(
+    executor: &mut FlowExecutor,
+    game: &Game,
+    puzzle_metadata: &PuzzleMetadataStore,
+    priority: &TechniquePriority,
+    code: &str,
+) {
+    executor.spawn(FlowGroup::Modal, FlowPriority::High, |handle| {
+        let shared = Game::from_state_code(code).map(|game| {
+            let fingerprint = fingerprint(&game);
+            let solved_on = fingerprint
+                .and_then(|fingerprint| puzzle_metadata.get(fingerprint))
+                .and_then(|entry| entry.solved_on.clone());
+            SharedPuzzle {
+                game,
+                fingerprint,
+                solved_on,
+            }
+        });
+        load_shared_puzzle_flow(handle, shared, priority.clone(), game.is_initialized())
+    });
+}
+
+/// A decoded shared puzzle and what the metadata store knows about it.
+#[derive(Debug)]
+struct SharedPuzzle {
+    game: Game,
+    /// `None` for variant puzzles, which are not fingerprinted.
+    fingerprint: Option<PuzzleFingerprint>,
+    solved_on: Option<String>,
+}
+
+/// Async flow for loading a shared puzzle.
+///
+/// Falls back to the new game flow when nothing is loaded and there is no
+/// game to continue.
+async fn load_shared_puzzle_flow(
+    handle: FlowHandle,
+    shared: Result<SharedPuzzle, StateCodeError>,
+    priority: TechniquePriority,
+    is_initialized: bool,
+) {
+    match shared {
+        Ok(SharedPuzzle {
+            game,
+            fingerprint,
+            solved_on,
+        }) => {
+            let kind = ConfirmKind::LoadSharedPuzzle { solved_on };
+            let result = helpers::show_confirm_dialog(&handle, kind).await;
+            if result.is_confirmed() {
+                if let Some(fingerprint) = fingerprint {
+                    handle.request_action(
+                        UpdateStateAction::RecordImportedPuzzle(fingerprint).into(),
+                    );
+                }
+                let request = GradePuzzleRequestDto::new(&game, &priority);
+                handle.request_action(PuzzleLifecycleAction::LoadGame(Box::new(game)).into());
+                if let Some(request) = request {
+                    handle.spawn_background(super::grade_puzzle_flow(
+                        handle.clone(),
+                        request,
+                        true,
+                    ));
+                }
+                return;
+            }
+        }
+        Err(err) => {
+            log::warn!("failed to decode shared puzzle: {err}");
+            let kind = AlertKind::SharedPuzzleInvalid {
+                message: err.to_string(),
+            };
+            helpers::show_alert_dialog(&handle, kind).await;
+        }
+    }
+    if !is_initialized {
+        handle.request_action(FlowAction::StartNewGame.into());
+    }
+}
@@ -2,24 +2,24 @@ use numelace_game::Game;
 
 use crate::{
     action::{
-        AlertKind, BoardMutationAction, ConfirmKind, HistoryAction, NotesFillScope, SpinnerKind,
+        AlertKind, BoardMutationAction, ConfirmKind, HistoryAction, NotesFillScope,
+        NotificationKind, SpinnerKind,
     },
-    flow::{FlowExecutor, FlowHandle, helpers},
+    flow::{FlowExecutor, FlowGroup, FlowHandle, FlowPriority, helpers},
     worker::{
         self,
-        tasks::{CandidateGridPairDto, SolvabilityResultDto, SolvabilityUndoScanResultDto},
+        tasks::{
+            CandidateGridPairsDto, SolvabilityRequestDto, SolvabilityResultDto,
+            SolvabilityUndoScanResultDto,
+        },
     },
 };
 
-/// Spawn a solvability check flow if no other flows are active.
+/// Spawn a solvability check flow unless another modal flow is active.
 pub(crate) fn spawn_check_solvability_flow(executor: &mut FlowExecutor, game: &Game) {
-    if !executor.is_idle() {
-        return;
-    }
-    let is_solved = game.is_solved();
-    let handle = executor.handle();
-    let request = game.into();
-    executor.spawn(check_solvability_flow(handle, request, is_solved));
+    executor.spawn(FlowGroup::Modal, FlowPriority::Normal, |handle| {
+        check_solvability_flow(handle, game.into(), game.is_solved())
+    });
 }
 
 /// Async flow for solvability check work dispatch.
@@ -27,29 +27,36 @@ pub(crate) fn spawn_check_solvability_flow(executor: &mut FlowExecutor, game: &G
 /// Runs the background request and awaits the response.
 async fn check_solvability_flow(
     handle: FlowHandle,
-    request: CandidateGridPairDto,
+    request: SolvabilityRequestDto,
     is_solved: bool,
 ) {
     if is_solved {
         let _ = helpers::show_alert_dialog(&handle, AlertKind::SolvabilityAlreadySolved).await;
         return;
     }
-    let work = worker::request_solvability(request);
-    let state = helpers::with_spinner(&handle, SpinnerKind::CheckSolvability, work)
-        .await
-        .unwrap();
+    let work = helpers::with_timeout(
+        handle.timeouts().check_solvability,
+        worker::request_solvability(request),
+    );
+    let state = match helpers::with_spinner(&handle, SpinnerKind::CheckSolvability, work).await {
+        Ok(state) => state,
+        Err(err) => {
+            helpers::notify_error(&handle, NotificationKind::SolvabilityCheckFailed, &err);
+            return;
+        }
+    };
 
     match state {
-        SolvabilityResultDto::Inconsistent => {
-            let result =
-                helpers::show_confirm_dialog(&handle, ConfirmKind::SolvabilityInconsistent).await;
+        SolvabilityResultDto::Inconsistent { witness } => {
+            let kind = ConfirmKind::SolvabilityInconsistent { witness };
+            let result = helpers::show_confirm_dialog(&handle, kind).await;
             if result.is_confirmed() {
                 handle_solvability_undo(&handle).await;
             }
         }
-        SolvabilityResultDto::NoSolution => {
-            let result =
-                helpers::show_confirm_dialog(&handle, ConfirmKind::SolvabilityNoSolution).await;
+        SolvabilityResultDto::NoSolution { witness } => {
+            let kind = ConfirmKind::SolvabilityNoSolution { witness };
+            let result = helpers::show_confirm_dialog(&handle, kind).await;
             if result.is_confirmed() {
                 handle_solvability_undo(&handle).await;
             }
@@ -77,6 +84,9 @@ async fn check_solvability_flow(
                 );
             }
         }
+        SolvabilityResultDto::Undetermined => {
+            let _ = helpers::show_alert_dialog(&handle, AlertKind::SolvabilityUndetermined).await;
+        }
     }
 }
 
@@ -88,10 +98,18 @@ async fn handle_solvability_undo(handle: &FlowHandle) {
         return;
     }
 
-    let work = worker::request_solvability_undo_scan(games.into());
-    let result = helpers::with_spinner(handle, SpinnerKind::CheckSolvability, work)
-        .await
-        .unwrap();
+    let request = CandidateGridPairsDto::from(games);
+    let work = helpers::with_timeout(
+        handle.timeouts().check_solvability,
+        worker::request_solvability_undo_scan(request),
+    );
+    let result = match helpers::with_spinner(handle, SpinnerKind::CheckSolvability, work).await {
+        Ok(result) => result,
+        Err(err) => {
+            helpers::notify_error(handle, NotificationKind::SolvabilityCheckFailed, &err);
+            return;
+        }
+    };
     apply_solvability_undo_result(handle, result).await;
 }
 
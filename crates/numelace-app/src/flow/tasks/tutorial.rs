@@ -0,0 +1,59 @@
+use numelace_game::Game;
+
+use crate::{
+    action::{
+        ConfirmKind, FlowAction, PuzzleLifecycleAction, ScriptGoal, UiAction, UpdateStateAction,
+    },
+    campaign::PACKS,
+    flow::{
+        FlowExecutor, FlowGroup, FlowHandle, FlowPriority, helpers,
+        script::{self, ScriptStep},
+    },
+    i18n::Text,
+    state::Screen,
+};
+
+const STEPS: [ScriptStep; 7] = [
+    ScriptStep::note(Text::TutorialWelcome),
+    ScriptStep::task(Text::TutorialSelectCell, ScriptGoal::SelectCell),
+    ScriptStep::task(Text::TutorialPlaceDigit, ScriptGoal::PlaceDigit),
+    ScriptStep::task(Text::TutorialToggleNote, ScriptGoal::ToggleNote),
+    ScriptStep::task(Text::TutorialUndo, ScriptGoal::Undo),
+    ScriptStep::task(Text::TutorialHint, ScriptGoal::RequestHint),
+    ScriptStep::note(Text::TutorialDone),
+];
+
+/// Spawn the tutorial, replacing a tutorial that is already running.
+///
+/// The tutorial plays the first puzzle of the bundled packs, confirming
+/// before it replaces a game in progress.
+pub(crate) fn spawn_tutorial_flow(executor: &mut FlowExecutor, game: &Game) {
+    let in_progress = game.is_initialized() && !game.is_solved();
+    executor.spawn(FlowGroup::Script, FlowPriority::High, |handle| {
+        tutorial_flow(handle, in_progress)
+    });
+}
+
+async fn tutorial_flow(handle: FlowHandle, in_progress: bool) {
+    if in_progress {
+        let result = helpers::show_confirm_dialog(&handle, ConfirmKind::NewGame).await;
+        if !result.is_confirmed() {
+            return;
+        }
+    }
+    match PACKS[0].puzzle(0) {
+        Ok(game) => {
+            handle.request_action(PuzzleLifecycleAction::LoadGame(Box::new(game)).into());
+        }
+        Err(err) => {
+            // Fall back to a generated puzzle, so a first launch still gets a game.
+            log::warn!("failed to load the tutorial puzzle: {err}");
+            handle.request_action(FlowAction::StartNewGame.into());
+            return;
+        }
+    }
+    handle.request_action(UiAction::ShowScreen(Screen::Game).into());
+    // Skipping counts too: the player has seen enough to decline it.
+    let _ = script::run_script(&handle, &STEPS).await;
+    handle.request_action(UpdateStateAction::MarkTutorialSeen.into());
+}
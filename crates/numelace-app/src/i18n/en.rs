@@ -0,0 +1,646 @@
+use super::{TechniqueText, Text};
+
+#[expect(clippy::too_many_lines)]
+pub(super) fn text(text: Text) -> &'static str {
+    match text {
+        Text::Ok => "OK",
+        Text::Cancel => "Cancel",
+        Text::Close => "Close",
+        Text::Undo => "Undo",
+        Text::Redo => "Redo",
+        Text::NewGame => "New Game",
+        Text::ResetInputs => "Reset Inputs",
+        Text::Settings => "Settings",
+        Text::ExportSettings => "Export…",
+        Text::ExportSettingsTooltip => {
+            "Save these settings to a file to use them on another device"
+        }
+        Text::ImportSettings => "Import…",
+        Text::ImportSettingsTooltip => "Replace these settings with a previously exported file",
+        Text::Notes => "Notes",
+        Text::Appearance => "Appearance",
+        Text::AntiKnight => "Anti-Knight",
+        Text::AntiKing => "Anti-King",
+        Text::Jigsaw => "Jigsaw",
+
+        Text::CheckSolvability => "Check solvability",
+        Text::CheckSolvabilityTooltip => "Check whether the current board still has a solution.",
+        Text::Hint => "Hint",
+        Text::HintTooltip => "Get a hint (stage 1).",
+        Text::CommandCategoryEdit => "Editing",
+        Text::CommandCategoryGame => "Game",
+        Text::KeyboardShortcuts => "Keyboard shortcuts",
+        Text::CornerNoteKeys => "Corner notes",
+        Text::CenterNoteKeys => "Center notes",
+        Text::PressAKey => "Press a key… (Esc cancels)",
+        Text::KeyReserved => "That key is used for moving and entering digits.",
+        Text::Unbound => "Not assigned",
+        Text::ResetShortcut => "Restore the default shortcut",
+        Text::More => "More",
+        Text::AutoFillAllCells => "Auto-fill notes (all cells)",
+        Text::AutoFillAllCellsTooltip => {
+            "Automatically fill in notes for all cells based on the current board state."
+        }
+        Text::AutoFillEmptyCells => "Auto-fill notes (empty cells)",
+        Text::AutoFillEmptyCellsTooltip => {
+            "Automatically fill in notes for empty cells based on the current board state"
+        }
+        Text::AutoFillSelectedCell => "Auto-fill notes (selected cells)",
+        Text::AutoFillSelectedCellTooltip => {
+            "Automatically fill in notes for the selected cells based on the current board state"
+        }
+        Text::ClearMistakes => "Clear mistakes",
+        Text::ClearMistakesTooltip => {
+            "Remove entered digits that differ from the solution, keeping correct digits and notes"
+        }
+        Text::CheckProgress => "Am I on track?",
+        Text::CheckProgressTooltip => {
+            "Quickly compare your digits with the solution, without revealing which ones differ (Ctrl+Shift+K)"
+        }
+        Text::AuditNotes => "Check notes",
+        Text::AuditNotesTooltip => {
+            "Find notes ruled out by digits already placed in the same row, column, box, or chess move"
+        }
+        Text::RevealCell => "Reveal cell",
+        Text::RevealCellTooltip => "Fill the selected cell with its answer",
+        Text::RevealAll => "Reveal solution",
+        Text::RevealAllTooltip => "Fill every cell with its answer",
+        Text::Statistics => "Statistics",
+        Text::StatisticsTooltip => {
+            "Show which techniques your hints needed. Data stays on this device."
+        }
+        Text::BugReport => "Bug report",
+        Text::BugReportTooltip => {
+            "Collect the game, settings, and recent actions for a GitHub issue"
+        }
+        Text::BugReportLabel => {
+            "Paste this text into a new issue on GitHub. Nothing is sent automatically."
+        }
+        Text::CopyReport => "Copy",
+        Text::HintsRequested => "Hints requested: {count}",
+        Text::RevealsUsed => "Reveals used: {count}",
+        Text::HintedTechniques => "Techniques you struggle with",
+        Text::NoHintsYet => "No hints requested yet.",
+        Text::HintsByHouse => "Hints by house",
+        Text::PuzzlesSolved => "Puzzles solved: {count}",
+        Text::BestTimes => "Best times by difficulty",
+        Text::NoSolvedPuzzlesYet => "No puzzles solved yet.",
+        Text::HouseRows => "Rows",
+        Text::HouseColumns => "Columns",
+        Text::HouseBoxes => "Boxes",
+        Text::ClearStatistics => "Clear statistics",
+        Text::ThemeSystemTooltip => "Follow the system theme preference.",
+        Text::ThemeDarkTooltip => "Use dark mode theme",
+        Text::ThemeLightTooltip => "Use light mode theme",
+        Text::Export => "Export",
+        Text::ExportPuzzle => "Printable puzzle (SVG)",
+        Text::ExportPuzzleTooltip => "Export the givens as an A4 page for printing",
+        Text::ExportProgress => "Printable progress (SVG)",
+        Text::ExportProgressTooltip => "Export the current board with your digits and notes",
+        Text::Share => "Share",
+        Text::CopyPuzzleLink => "Copy puzzle link",
+        Text::CopyPuzzleLinkTooltip => "Copy a link that opens this puzzle from the start",
+        Text::CopyProgressLink => "Copy link with progress",
+        Text::CopyProgressLinkTooltip => "Copy a link that includes your digits and notes",
+        Text::CopyBoardImage => "Board image",
+        Text::CopyBoardImageTooltip => "Copy a PNG image of the board (downloads it on the web)",
+
+        Text::KeyArm => "Arm digit {digit} (then tap cells to enter it)",
+        Text::KeyDisarm => "Disarm digit {digit}",
+        Text::KeySetDigit => "Set digit {digit}",
+        Text::KeySetDigitUnexpected => "Set digit {digit} (unexpected state)",
+        Text::KeySetDigitAlreadySet => "Set digit {digit} (already set)",
+        Text::KeySetDigitBlockedByConflict => "Set digit {digit} (blocked by rule violation)",
+        Text::KeySetDigitBlockedByWrongDigit => "Set digit {digit} (blocked: not the answer)",
+        Text::KeySetDigitBlockedByGiven => "Set digit {digit} (blocked by pre-filled cell)",
+        Text::KeySetDigitBlockedByUnexpected => "Set digit {digit} (blocked by unexpected state)",
+        Text::KeySetDigitNoCell => "Set digit {digit} (blocked by no cell selected)",
+        Text::KeyAddNote => "Add note {digit}",
+        Text::KeyRemoveNote => "Remove note {digit}",
+        Text::KeyToggleNoteUnexpected => "Toggle note {digit} (blocked by unexpected state)",
+        Text::KeyAddNoteBlockedByConflict => "Add note {digit} (blocked by rule violation)",
+        Text::KeyAddNoteBlockedByFilled => "Add note {digit} (blocked by filled cell)",
+        Text::KeyToggleNoteNoCell => "Toggle note {digit} (blocked by no cell selected)",
+        Text::KeyCandidateCount => "{count} empty cell(s) can still take {digit}",
+        Text::KeyDigitCompleted => "All nine {digit}s are placed",
+        Text::KeyClearCell => "Clear cell (digit and notes)",
+        Text::KeyClearCellDisabled => "Clear cell (no removable cell selected)",
+        Text::KeyToggleInputMode => "Toggle Fill/Notes mode",
+
+        Text::CellPosition => "row {row} column {col}",
+        Text::CellGiven => "given {digit}",
+        Text::CellFilled => "filled {digit}",
+        Text::CellEmpty => "empty",
+        Text::CellCandidates => "candidates {digits}",
+        Text::CellConflict => "conflict",
+        Text::CellHinted => "hinted {count} times",
+
+        Text::StatusInProgress => "Game in progress...",
+        Text::StatusConflict => "Conflicts on the board",
+        Text::ReadOnly => "Read-only",
+        Text::ReadOnlyReplayTooltip => "A recording is being replayed; moves are disabled",
+        Text::ReadOnlyReviewTooltip => {
+            "Reviewing the solved puzzle; start a new game to play again"
+        }
+        Text::ReviewFirstTooltip => "Show the starting board",
+        Text::ReviewPreviousTooltip => "Step back",
+        Text::ReviewNextTooltip => "Step forward",
+        Text::ReviewLastTooltip => "Show the solved board",
+        Text::ReviewTimelineTooltip => {
+            "Drag to step through your solve. Red marks are mistakes, yellow marks are hints"
+        }
+        Text::ReviewStep => "Step {step} of {total}",
+        Text::ReviewMistake => "Mistake",
+        Text::ReviewHint => "Hint used",
+        Text::StatusSolved => "Solved! Congratulations!",
+        Text::StatusHint => "Hint:",
+        Text::HintLookHere => "Something can be found in the highlighted area",
+        Text::HintFocusArea => "Focus on the highlighted area",
+        Text::HintApplicable => "{technique} is applicable here",
+        Text::HintPreviewing => "Previewing {technique} changes",
+        Text::HintApplied => "Applied {technique}",
+
+        Text::Generating => "Generating...",
+        Text::GeneratingNewGame => "Generating new game...",
+        Text::PreparingGenerator => "Preparing the puzzle generator...",
+        Text::CheckingSolvability => "Checking Solvability...",
+        Text::CheckingSolvabilityWait => "Please wait while we analyze the current board.",
+        Text::CheckingSolvabilitySlow => "This may take a few seconds.",
+
+        Text::NewGameConfirmHeading => "New Game?",
+        Text::NewGameConfirmLabel => "Start a new game? Current progress will be lost.",
+        Text::ResetInputsConfirmHeading => "Reset Inputs?",
+        Text::ResetInputsConfirmLabel => {
+            "Clear all your inputs and return to the initial puzzle state?"
+        }
+        Text::RevealCellConfirmHeading => "Reveal Cell?",
+        Text::RevealCellConfirmLabel => {
+            "Fill the selected cell with its answer? This is counted in your statistics."
+        }
+        Text::IllegalNotesFound => "Impossible Notes Found",
+        Text::IllegalNotesFoundLabel => {
+            "The highlighted notes are ruled out by digits already placed. Remove them?"
+        }
+        Text::IllegalNotesCount => "Impossible notes: {count}",
+        Text::RemoveIllegalNotes => "Remove",
+        Text::NotesLookFine => "Notes Look Fine",
+        Text::NoIllegalNotesLabel => "None of your notes is ruled out by a placed digit.",
+        Text::RevealAllConfirmHeading => "Reveal Solution?",
+        Text::RevealAllConfirmLabel => {
+            "Fill every cell with its answer? This is counted in your statistics."
+        }
+        Text::BoardInconsistent => "Board Inconsistent",
+        Text::BoardInconsistentLabel => {
+            "A conflict or a no-candidate cell was detected. We recommend undoing to the last consistent state."
+        }
+        Text::NoSolutionFound => "No Solution Found",
+        Text::NoSolutionFoundLabel => {
+            "No solution exists from the current state. We recommend undoing to the last solvable state."
+        }
+        Text::InvalidCells => "Cells breaking the rules: {cells}",
+        Text::ConflictingEntries => "Removing these entries makes the puzzle solvable: {cells}",
+        Text::NotesMayBeIncorrect => "Notes May Be Incorrect",
+        Text::SolvableIgnoringNotesLabel => {
+            "A solution exists when ignoring notes. Rebuild candidates now?"
+        }
+        Text::HintIgnoringNotesLabel => {
+            "A hint exists when ignoring notes. Rebuild candidates now?"
+        }
+        Text::Rebuild => "Rebuild",
+        Text::Solvable => "Solvable",
+        Text::SolvableLabel => "A solution is still possible from the current state.",
+        Text::UndoComplete => "Undo Complete",
+        Text::UndidToSolvable => "Undid {steps} step(s) to return to a solvable state.",
+        Text::UndoNoSolvableState => "Undo did not find a solvable state.",
+        Text::AlreadySolved => "Already Solved",
+        Text::AlreadySolvedNoCheck => {
+            "The puzzle is already solved, so no solvability check is needed."
+        }
+        Text::CheckUnavailable => "Check Unavailable",
+        Text::CheckUndeterminedLabel => {
+            "The board is too open to decide whether it can still be solved. Try again after filling in more cells."
+        }
+        Text::UndidToConsistent => "Undid {steps} step(s) to return to a consistent state.",
+        Text::NoHintFound => "No Hint Found",
+        Text::NoApplicableTechniques => "No applicable techniques found from the current state.",
+        Text::HintUndeterminedLabel => {
+            "The search for the next step took too long. Try again after filling in more cells."
+        }
+        Text::ConflictResolvedNoStep => "The conflict was resolved, but no next step is available.",
+        Text::UndoNoConsistentState => "Undo did not find a consistent state.",
+        Text::AlreadySolvedNoHint => "The puzzle is already solved, so no hint is available.",
+        Text::ExportSaved => "Export saved",
+        Text::ExportSavedLabel => "Saved to {path}",
+        Text::ExportFailed => "Export failed",
+        Text::SharedPuzzleInvalid => "Invalid shared puzzle",
+        Text::LoadSharedPuzzle => "Load puzzle",
+        Text::LoadSharedPuzzleHeading => "Load shared puzzle?",
+        Text::LoadSharedPuzzleLabel => "This will replace the current game with the shared puzzle.",
+        Text::AlreadySolvedOn => "You already solved this puzzle on {date}.",
+        Text::RecoverProgress => "Restore",
+        Text::RecoverProgressHeading => "Restore unsaved progress?",
+        Text::RecoverProgressLabel => {
+            "Numelace did not shut down cleanly last time. Restore the progress made since the last save, including undo history?"
+        }
+        Text::SolvingDetails => "Solving details",
+        Text::TotalSteps => "Total steps: {steps}",
+        Text::NoTechniquesApplied => "No techniques applied.",
+
+        Text::ShareLinkCopied => "Share link copied to the clipboard",
+        Text::BoardImageCopied => "Board image copied to the clipboard",
+        Text::Autosaved => "Progress saved",
+        Text::SettingsExported => "Settings exported",
+        Text::SettingsImported => "Settings imported",
+        Text::SettingsExportFailed => "Could not export settings",
+        Text::SettingsImportFailed => "Could not import settings",
+        Text::NewGameFailed => "Could not generate a new puzzle",
+        Text::SolvabilityCheckFailed => "Could not check solvability",
+        Text::ProgressOnTrack => "On track: every digit matches the solution",
+        Text::ProgressOffTrack => "Off track: some digits do not match the solution",
+        Text::ProgressCheckUnavailable => "This puzzle has no stored solution to compare with",
+        Text::TimedMode => "Timed mode",
+        Text::TimedModeTooltip => {
+            "Track your play time and compare it against a par time estimated from the puzzle's difficulty."
+        }
+        Text::PlayTimeTooltip => "Play time / par time",
+        Text::SolvedResultHeading => "Puzzle complete",
+        Text::SolvedResultReviewHint => {
+            "Review to step back through your solve; the board then stays read-only."
+        }
+        Text::SolvedResultReview => "Review",
+        Text::YourTime => "Your time",
+        Text::ParTime => "Par time",
+        Text::ParTimeUnknown => "Not rated",
+        Text::RatingOutstanding => "Outstanding! Well under par.",
+        Text::RatingUnderPar => "Under par. Nicely done!",
+        Text::RatingOverPar => "A little over par.",
+        Text::RatingWellOverPar => "Well over par. Keep practicing!",
+        Text::GameMode => "Mode",
+        Text::ModeClassic => "Classic",
+        Text::ModeClassicTooltip => "Solve at your own pace without a clock.",
+        Text::HotSeatRace => "Hot-seat race",
+        Text::HotSeatRaceTooltip => {
+            "Two players take turns on this device, each solving their own copy of the puzzle. The faster total time wins."
+        }
+        Text::PlayerOne => "Player 1",
+        Text::PlayerTwo => "Player 2",
+        Text::PassTurnTooltip => "Pass the board to {player}",
+        Text::RaceStatusTooltip => "Play time and progress of each player",
+        Text::RaceStandings => "Race standings",
+        Text::RaceResultHeading => "Race result",
+        Text::RaceInProgress => "The race is still on.",
+        Text::RaceWinner => "{player} wins!",
+        Text::RaceTie => "It's a tie!",
+        Text::RaceTime => "Time",
+        Text::RaceProgress => "Progress",
+        Text::RaceTurnFinished => "Solved! Pass the device to the other player.",
+        Text::Campaign => "Campaign",
+        Text::CampaignTooltip => "Play the bundled puzzle packs in order",
+        Text::CampaignDescription => {
+            "Solve the puzzles of each pack in order. Completing a pack unlocks the next one."
+        }
+        Text::BackToGame => "Back to game",
+        Text::PackFirstSteps => "First Steps",
+        Text::PackPairsAndPointing => "Pairs and Pointing",
+        Text::PackSubsetsAndWings => "Subsets and Wings",
+        Text::PackAdvancedPatterns => "Advanced Patterns",
+        Text::PackLocked => "Solve every puzzle in the previous pack to unlock.",
+        Text::PuzzleLocked => "Solve the previous puzzle to unlock",
+        Text::CampaignPuzzleNumber => "Puzzle {count}",
+        Text::BestTime => "Best time: {time}",
+        Text::NowPlaying => "Now playing",
+        Text::CampaignPuzzleSolved => "Campaign puzzle solved",
+        Text::CampaignPuzzleLoadFailed => "Could not load the campaign puzzle",
+        Text::Learn => "Learn",
+        Text::LearnTooltip => "Learn the solving techniques with worked examples",
+        Text::LearnDescription => {
+            "Pick a technique to read how it works and step through its examples."
+        }
+        Text::LessonExample => "Example {index}",
+        Text::LessonNoExamples => "This technique has no examples yet.",
+        Text::LessonStageFocus => "The pattern is in the highlighted area.",
+        Text::LessonStageCells => "These cells form the pattern.",
+        Text::LessonStageDigits => "These candidates form the pattern.",
+        Text::LessonStagePreview => "The pattern leads to the marked change.",
+        Text::LessonPreviousStage => "Back",
+        Text::LessonNextStage => "Next",
+        Text::TutorialWelcome => {
+            "Welcome to Numelace! Fill the grid so that every row, column and box holds the digits 1 to 9 once. This short tutorial shows the basics on an easy puzzle."
+        }
+        Text::TutorialSelectCell => "Click or tap an empty cell to select it.",
+        Text::TutorialPlaceDigit => {
+            "Enter a digit with the keypad or the number keys to fill the selected cell."
+        }
+        Text::TutorialToggleNote => {
+            "Select another empty cell and jot down a candidate: switch to notes with the keypad's pencil button or the S key and enter a digit, or press a digit key while holding Shift."
+        }
+        Text::TutorialUndo => {
+            "Changed your mind? Undo the last input with the undo button or Ctrl+Z."
+        }
+        Text::TutorialHint => "Stuck? Ask for a hint with the light bulb button or Ctrl+J.",
+        Text::TutorialDone => {
+            "That's all! Finish this puzzle or start a new game from the toolbar. You can replay the tutorial from the settings."
+        }
+        Text::ScriptStepCount => "Step {index} of {count}",
+        Text::ScriptContinue => "Continue",
+        Text::ScriptSkip => "Skip tutorial",
+        Text::GenerationTrace => "Generator trace",
+        Text::TraceStrategies => "Fill: {fill} / Carve: {carve}",
+        Text::TraceUniquenessChecks => "Uniqueness checks: {count} ({kept} kept)",
+        Text::TraceSolverRuns => "Solver runs: {count}",
+        Text::TraceStep => "Step",
+        Text::TraceSolution => "Complete solution before any removal.",
+        Text::TraceStepKept => "Removed {cells}; entropy {bits} bits.",
+        Text::TraceStepRejected => "Kept {cells}, which broke uniqueness; entropy {bits} bits.",
+        Text::TraceShading => "Cell shading:",
+        Text::TraceShadingCandidates => "Candidates",
+        Text::TraceShadingRemovalOrder => "Removal order",
+        Text::ReplayTutorial => "Replay tutorial",
+        Text::ReplayTutorialTooltip => "Walk through the basics again on an easy puzzle",
+
+        Text::NewGameDescription => "Choose difficulty and techniques to generate a new puzzle.",
+        Text::Difficulty => "Difficulty",
+        Text::DifficultyBeyondTechniques => "Needs guessing",
+        Text::DifficultyBasic => "Basic",
+        Text::DifficultyIntermediate => "Intermediate",
+        Text::DifficultyUpperIntermediate => "Upper Intermediate",
+        Text::DifficultyAdvanced => "Advanced",
+        Text::DifficultyExpert => "Expert",
+        Text::Custom => "Custom",
+        Text::Techniques => "Techniques",
+        Text::ExtraRules => "Extra rules",
+        Text::AntiKnightTooltip => "Cells a knight's move apart cannot hold the same digit.",
+        Text::AntiKingTooltip => "Diagonally touching cells cannot hold the same digit.",
+        Text::JigsawTooltip => "Irregular regions of nine cells replace the 3×3 boxes.",
+        Text::Symmetry => "Symmetry of givens",
+        Text::SymmetryNone => "None",
+        Text::SymmetryRotational => "Rotational (180°)",
+        Text::SymmetryMirror => "Mirror (left-right)",
+        Text::SymmetryDiagonal => "Diagonal",
+        Text::SeedOptional => "Seed (optional)",
+        Text::SeedHint => "Leave blank for random",
+        Text::GenerationAttempts => "Generation attempts",
+        Text::Generate => "Generate",
+
+        Text::Language => "Language",
+        Text::Assist => "Assist",
+        Text::BlockRuleViolations => "Block rule violations",
+        Text::ValidationModeRules => "Check against the rules",
+        Text::ValidationModeSolution => "Check against the solution",
+        Text::ValidationModeSolutionTooltip => {
+            "Refuse any digit that is not the answer, so no wrong move can be made. Notes are not affected."
+        }
+        Text::Highlight => "Highlight",
+        Text::HighlightSelectedCell => "Selected cell outline",
+        Text::HighlightSelectedDigit => "Selected digit cells",
+        Text::HighlightSelectedDigitNote => "Selected digit notes",
+        Text::HighlightSelectedCellPeer => "Selected cell's row/col/box",
+        Text::HighlightSelectedDigitPeer => "Selected digit cells' row/col/box",
+        Text::HighlightConflict => "Conflicting cells/notes",
+        Text::HighlightSelectedDigitCandidate => "Cells where selected digit can go",
+        Text::HighlightBivalueCell => "Cells with two candidates",
+        Text::HighlightBivalueCellTooltip => {
+            "Bi-value cells are where many advanced techniques, such as Y-Wings and XY-Chains, start."
+        }
+        Text::HighlightIntensity => "Highlight intensity",
+        Text::BivalueSourceCandidates => "Count the remaining candidates",
+        Text::BivalueSourceNotes => "Count the notes",
+        Text::AutoRemovePeerNotes => "Auto-remove row/col/box notes on fill",
+        Text::AutoFillNotesOnNewOrReset => "Auto-fill notes on new game/reset",
+        Text::KeypadCandidateCounts => "Show remaining candidate cells on keypad",
+        Text::LockCompletedDigits => "Lock completed digits on keypad",
+        Text::LockCompletedDigitsTooltip => {
+            "Disable a digit's key once all nine of it are placed correctly, so a tenth is not entered by accident. Needs the puzzle's solution."
+        }
+        Text::CandidateHeatmap => "Candidate heatmap",
+        Text::CandidateHeatmapTooltip => {
+            "Tint empty cells by how many candidates they have left; the fewer, the warmer."
+        }
+        Text::GameInfo => "Game info",
+        Text::GameInfoTooltip => {
+            "Show a sidebar with the puzzle's difficulty, clues, seed, time, hints and progress."
+        }
+        Text::RegeneratePuzzle => "Regenerate from seed",
+        Text::RegeneratePuzzleTooltip => {
+            "Generate the current puzzle again from its seed and the options it was made with."
+        }
+        Text::InfoClues => "Clues",
+        Text::InfoSeed => "Seed",
+        Text::InfoSeedCopy => "Click to copy",
+        Text::InfoElapsed => "Elapsed",
+        Text::InfoHintsUsed => "Hints used",
+        Text::InfoPlacedDigits => "Placed digits",
+        Text::Scratchpad => "Scratchpad",
+        Text::ScratchPen => "Pen",
+        Text::ScratchPenTooltip => {
+            "Sketch freehand over the board. The sketches are not part of the game; select the pen again or press Esc to go back to entering digits."
+        }
+        Text::ScratchLine => "Cell-to-cell line",
+        Text::ScratchLineTooltip => {
+            "Drag from one cell to another to join their centers, for tracing chains by hand."
+        }
+        Text::ClearScratchpad => "Clear scratchpad",
+        Text::ClearScratchpadTooltip => "Erase every sketch from the board.",
+        Text::DeductionDepthOverlay => "Hardness overlay",
+        Text::DeductionDepthOverlayTooltip => {
+            "Tint empty cells by how deep a trial is needed to decide them with singles; the deeper, the warmer. Cells singles decide stay untinted."
+        }
+        Text::HintDetail => "First hint shows",
+        Text::HintDetailConditionCells => "Cells the technique uses",
+        Text::HintDetailFocusArea => "Only where to look",
+        Text::HintDetailFocusAreaTooltip => {
+            "Highlight just one row, column, box, or cell first. Ask for the hint again to see the cells the technique uses."
+        }
+        Text::AssistProfiles => "Remember assists per difficulty",
+        Text::AssistProfilesTooltip => {
+            "Save these assist settings for the current difficulty and restore them when starting a new game of the same difficulty."
+        }
+        Text::AssistProfileActive => "Editing the profile for {difficulty}",
+        Text::Solver => "Solver",
+        Text::SolverDescription => {
+            "Techniques used for hints and difficulty grading, tried from top to bottom."
+        }
+        Text::AssumeUniqueSolution => "Assume a unique solution",
+        Text::AssumeUniqueSolutionTooltip => {
+            "Enables techniques such as Unique Rectangle that rely on the puzzle having only one \
+             solution. Their hints can be wrong for puzzles with several solutions."
+        }
+        Text::MoveTechniqueUp => "Try this technique earlier",
+        Text::MoveTechniqueDown => "Try this technique later",
+        Text::ResetToDefaults => "Reset to defaults",
+        Text::Input => "Input",
+        Text::CellFirst => "Cell first",
+        Text::CellFirstTooltip => "Select a cell, then press a digit.",
+        Text::DigitFirst => "Digit first",
+        Text::DigitFirstTooltip => "Press a digit, then tap cells to enter it.",
+        Text::FontSize => "Text size",
+        Text::FontSizeNormal => "Normal",
+        Text::FontSizeLargePrint => "Large print",
+        Text::UiScale => "UI scale",
+        Text::Motion => "Motion",
+        Text::MotionSystemTooltip => "Follow the reduced-motion preference of the browser",
+        Text::MotionFull => "Full",
+        Text::MotionReduced => "Reduced",
+        Text::MotionReducedTooltip => {
+            "Turn off animations and repaint less often while waiting, to save power on slow devices"
+        }
+        Text::NoteLayoutMiniGrid => "3×3 grid",
+        Text::NoteLayoutLinear => "In a row",
+        Text::NoteLayoutLinearTooltip => {
+            "Write notes one after another in ascending order instead of giving each digit its own spot"
+        }
+        Text::ReserveNoteSpace => "Keep notes in place",
+        Text::ReserveNoteSpaceTooltip => {
+            "Lay out notes in the space all nine would take, so they do not shift when notes are added or removed"
+        }
+        Text::GivenStylePlain => "Color only",
+        Text::GivenStyleBold => "Bold",
+        Text::GivenStyleBoxed => "Boxed",
+        Text::BoardCoordinates => "Row and column numbers",
+        Text::BoardCoordinatesTooltip => {
+            "Number the rows on the left and the columns on the top, matching r1c1 in hints"
+        }
+        Text::Digits => "Digits",
+        Text::Saving => "Saving",
+        Text::AutosaveInterval => "Autosave interval",
+        Text::AutosaveIntervalTooltip => {
+            "How often progress is saved. Changes in between can still be restored after a crash."
+        }
+        Text::SecondsSuffix => " s",
+        Text::BoardColors => "Board colors",
+        Text::ThemeSystem => "System",
+        Text::ThemeLight => "Light",
+        Text::ThemeDark => "Dark",
+        Text::ThemeHighContrast => "High Contrast",
+        Text::ColorBackground => "Background",
+        Text::ColorGridLines => "Grid lines",
+        Text::ColorGivenDigits => "Given digits",
+        Text::ColorFilledDigits => "Filled digits",
+        Text::ColorSelectedCell => "Selected cell",
+        Text::ColorSelectedDigit => "Selected digit",
+        Text::ColorHouse => "Row/col/box",
+        Text::ColorConflicts => "Conflicts",
+        Text::ColorHints => "Hints",
+    }
+}
+
+#[expect(clippy::too_many_lines)]
+pub(super) fn technique(key: &str) -> Option<TechniqueText> {
+    let (name, description) = match key {
+        "full_house" => (
+            "Full House",
+            "A row, column, or box has one empty cell left, which takes the missing digit.",
+        ),
+        "naked_single" => (
+            "Naked Single",
+            "A cell has only one candidate left, so it must hold that digit.",
+        ),
+        "hidden_single" => (
+            "Hidden Single",
+            "A digit fits in only one cell of a row, column, or box.",
+        ),
+        "locked_candidates" => (
+            "Locked Candidates",
+            "A digit is confined to the intersection of a box and a line, so it is removed from the rest of the other house.",
+        ),
+        "locked_candidates_pointing" => (
+            "Locked Candidates (Pointing)",
+            "Within a box, a digit lies in a single row or column, so it is removed from the rest of that line.",
+        ),
+        "locked_candidates_claiming" => (
+            "Locked Candidates (Claiming)",
+            "Within a row or column, a digit lies in a single box, so it is removed from the rest of that box.",
+        ),
+        "naked_pair" => (
+            "Naked Pair",
+            "Two cells of a house share the same two candidates, which are removed from the other cells.",
+        ),
+        "hidden_pair" => (
+            "Hidden Pair",
+            "Two digits fit only in the same two cells of a house, so other candidates are removed from them.",
+        ),
+        "naked_triple" => (
+            "Naked Triple",
+            "Three cells of a house hold only three digits between them, which are removed from the other cells.",
+        ),
+        "hidden_triple" => (
+            "Hidden Triple",
+            "Three digits fit only in the same three cells of a house, so other candidates are removed from them.",
+        ),
+        "naked_quad" => (
+            "Naked Quad",
+            "Four cells of a house hold only four digits between them, which are removed from the other cells.",
+        ),
+        "hidden_quad" => (
+            "Hidden Quad",
+            "Four digits fit only in the same four cells of a house, so other candidates are removed from them.",
+        ),
+        "x_wing" => (
+            "X-Wing",
+            "A digit is limited to the same two columns in two rows (or vice versa), so it is removed elsewhere in those columns.",
+        ),
+        "skyscraper" => (
+            "Skyscraper",
+            "Two strong links on a digit share one end line; cells seeing both other ends cannot hold it.",
+        ),
+        "two_string_kite" => (
+            "2-String Kite",
+            "A row link and a column link on a digit meet in a box; cells seeing both free ends cannot hold it.",
+        ),
+        "y_wing" => (
+            "Y-Wing",
+            "A pivot cell and two wings force a digit into one of the wings; cells seeing both wings cannot hold it.",
+        ),
+        "unique_rectangle" => (
+            "Unique Rectangle",
+            "Four corners of a rectangle in two boxes cannot share the same two digits in a puzzle \
+             with one solution.",
+        ),
+        "swordfish" => (
+            "Swordfish",
+            "A digit is limited to the same three columns in three rows (or vice versa), so it is removed elsewhere in those columns.",
+        ),
+        "jellyfish" => (
+            "Jellyfish",
+            "A digit is limited to the same four columns in four rows (or vice versa), so it is removed elsewhere in those columns.",
+        ),
+        "remote_pair" => (
+            "Remote Pair",
+            "A chain of cells with the same two candidates alternates them; cells seeing both ends of opposite parity cannot hold either.",
+        ),
+        "w_wing" => (
+            "W-Wing",
+            "Two cells with the same two candidates, joined by a strong link on one digit, rule out the other digit in cells seeing both.",
+        ),
+        "x_chain" => (
+            "X-Chain",
+            "An alternating chain of links on one digit proves that one of its ends holds it; cells seeing both ends cannot.",
+        ),
+        "xy_chain" => (
+            "XY-Chain",
+            "A chain of two-candidate cells proves that one of its ends holds a digit; cells seeing both ends cannot.",
+        ),
+        "xyz_wing" => (
+            "XYZ-Wing",
+            "A three-candidate pivot and two wings force a digit into one of three cells; cells seeing all of them cannot hold it.",
+        ),
+        "wxyz_wing" => (
+            "WXYZ-Wing",
+            "Four cells holding four digits force a shared digit into one of them; cells seeing all of those cannot hold it.",
+        ),
+        "aic" => (
+            "Alternating Inference Chain",
+            "A chain of alternating strong and weak links proves that one of its ends is true; candidates conflicting with both ends are removed.",
+        ),
+        "cage_combination" => (
+            "Cage Combination",
+            "Cage cells can only hold digits from distinct combinations that add up to the cage sum.",
+        ),
+        "edge_relation" => (
+            "Edge Relation",
+            "Candidates that cannot satisfy a greater-than or consecutive marker with the neighboring cell are removed.",
+        ),
+        _ => return None,
+    };
+    Some(TechniqueText { name, description })
+}
@@ -0,0 +1,703 @@
+use super::{TechniqueText, Text};
+
+#[expect(clippy::too_many_lines)]
+pub(super) fn text(text: Text) -> &'static str {
+    match text {
+        Text::Ok => "OK",
+        Text::Cancel => "キャンセル",
+        Text::Close => "閉じる",
+        Text::Undo => "元に戻す",
+        Text::Redo => "やり直す",
+        Text::NewGame => "新しいゲーム",
+        Text::ResetInputs => "入力をリセット",
+        Text::Settings => "設定",
+        Text::ExportSettings => "エクスポート…",
+        Text::ExportSettingsTooltip => "他の端末で使えるように設定をファイルに保存します",
+        Text::ImportSettings => "インポート…",
+        Text::ImportSettingsTooltip => "エクスポートしたファイルの設定で置き換えます",
+        Text::Notes => "メモ",
+        Text::Appearance => "外観",
+        Text::AntiKnight => "アンチナイト",
+        Text::AntiKing => "アンチキング",
+        Text::Jigsaw => "ジグソー",
+
+        Text::CheckSolvability => "解けるか確認",
+        Text::CheckSolvabilityTooltip => "現在の盤面にまだ解があるか確認します。",
+        Text::Hint => "ヒントを見る",
+        Text::HintTooltip => "ヒントを表示します（段階 1）。",
+        Text::CommandCategoryEdit => "編集",
+        Text::CommandCategoryGame => "ゲーム",
+        Text::KeyboardShortcuts => "キーボードショートカット",
+        Text::CornerNoteKeys => "隅のメモ",
+        Text::CenterNoteKeys => "中央のメモ",
+        Text::PressAKey => "キーを押してください…（Esc で取り消し）",
+        Text::KeyReserved => "このキーは移動と数字の入力に使われています。",
+        Text::Unbound => "未割り当て",
+        Text::ResetShortcut => "初期のショートカットに戻す",
+        Text::More => "その他",
+        Text::AutoFillAllCells => "メモを自動入力（全マス）",
+        Text::AutoFillAllCellsTooltip => "現在の盤面から全マスのメモを自動で入力します。",
+        Text::AutoFillEmptyCells => "メモを自動入力（空きマス）",
+        Text::AutoFillEmptyCellsTooltip => "現在の盤面から空きマスのメモを自動で入力します。",
+        Text::AutoFillSelectedCell => "メモを自動入力（選択中のマス）",
+        Text::AutoFillSelectedCellTooltip => "現在の盤面から選択中のマスのメモを自動で入力します。",
+        Text::ClearMistakes => "間違いを消去",
+        Text::ClearMistakesTooltip => {
+            "解答と異なる入力済みの数字を消します。正しい数字とメモは残ります。"
+        }
+        Text::CheckProgress => "順調か確認",
+        Text::CheckProgressTooltip => {
+            "入力した数字が解答と合っているかをすばやく確認します。どの数字が違うかは表示しません（Ctrl+Shift+K）"
+        }
+        Text::AuditNotes => "メモを点検",
+        Text::AuditNotesTooltip => {
+            "同じ行・列・ブロックやチェスの利きに置かれた数字と矛盾するメモを探します"
+        }
+        Text::RevealCell => "マスの答えを表示",
+        Text::RevealCellTooltip => "選択中のマスに答えを入力します",
+        Text::RevealAll => "解答を表示",
+        Text::RevealAllTooltip => "すべてのマスに答えを入力します",
+        Text::Statistics => "統計",
+        Text::StatisticsTooltip => {
+            "ヒントで使われたテクニックを表示します。データはこの端末の外に送られません。"
+        }
+        Text::BugReport => "不具合の報告",
+        Text::BugReportTooltip => "GitHub の issue 用に、ゲーム・設定・最近の操作をまとめます",
+        Text::BugReportLabel => {
+            "この内容を GitHub の新しい issue に貼り付けてください。自動では送信されません。"
+        }
+        Text::CopyReport => "コピー",
+        Text::HintsRequested => "ヒントの利用回数: {count}",
+        Text::RevealsUsed => "答えの表示回数: {count}",
+        Text::HintedTechniques => "苦手なテクニック",
+        Text::NoHintsYet => "まだヒントを利用していません。",
+        Text::HintsByHouse => "ハウス別のヒント回数",
+        Text::PuzzlesSolved => "解いたパズル: {count}",
+        Text::BestTimes => "難易度別のベストタイム",
+        Text::NoSolvedPuzzlesYet => "まだパズルを解いていません。",
+        Text::HouseRows => "行",
+        Text::HouseColumns => "列",
+        Text::HouseBoxes => "ブロック",
+        Text::ClearStatistics => "統計を消去",
+        Text::ThemeSystemTooltip => "システムのテーマ設定に従います。",
+        Text::ThemeDarkTooltip => "ダークテーマを使います",
+        Text::ThemeLightTooltip => "ライトテーマを使います",
+        Text::Export => "エクスポート",
+        Text::ExportPuzzle => "印刷用の問題 (SVG)",
+        Text::ExportPuzzleTooltip => "初期値だけを A4 ページとして書き出します",
+        Text::ExportProgress => "印刷用の途中経過 (SVG)",
+        Text::ExportProgressTooltip => "入力した数字とメモを含めて現在の盤面を書き出します",
+        Text::Share => "共有",
+        Text::CopyPuzzleLink => "問題のリンクをコピー",
+        Text::CopyPuzzleLinkTooltip => "この問題を最初から開くリンクをコピーします",
+        Text::CopyProgressLink => "途中経過付きのリンクをコピー",
+        Text::CopyProgressLinkTooltip => "入力した数字とメモを含むリンクをコピーします",
+        Text::CopyBoardImage => "盤面の画像",
+        Text::CopyBoardImageTooltip => {
+            "盤面の PNG 画像をコピーします (Web 版ではダウンロードします)"
+        }
+
+        Text::KeyArm => "数字 {digit} を選択（続けてマスをタップして入力）",
+        Text::KeyDisarm => "数字 {digit} の選択を解除",
+        Text::KeySetDigit => "数字 {digit} を入力",
+        Text::KeySetDigitUnexpected => "数字 {digit} を入力（予期しない状態）",
+        Text::KeySetDigitAlreadySet => "数字 {digit} を入力（入力済み）",
+        Text::KeySetDigitBlockedByConflict => "数字 {digit} を入力（ルール違反のため不可）",
+        Text::KeySetDigitBlockedByWrongDigit => "数字 {digit} を入力（答えと異なるため不可）",
+        Text::KeySetDigitBlockedByGiven => "数字 {digit} を入力（初期配置のマスのため不可）",
+        Text::KeySetDigitBlockedByUnexpected => "数字 {digit} を入力（予期しない状態のため不可）",
+        Text::KeySetDigitNoCell => "数字 {digit} を入力（マスが選択されていません）",
+        Text::KeyAddNote => "メモ {digit} を追加",
+        Text::KeyRemoveNote => "メモ {digit} を削除",
+        Text::KeyToggleNoteUnexpected => "メモ {digit} を切り替え（予期しない状態のため不可）",
+        Text::KeyAddNoteBlockedByConflict => "メモ {digit} を追加（ルール違反のため不可）",
+        Text::KeyAddNoteBlockedByFilled => "メモ {digit} を追加（入力済みのマスのため不可）",
+        Text::KeyToggleNoteNoCell => "メモ {digit} を切り替え（マスが選択されていません）",
+        Text::KeyCandidateCount => "{digit} を入れられる空きマス: {count}",
+        Text::KeyDigitCompleted => "{digit} は9個すべて配置済みです",
+        Text::KeyClearCell => "マスを消去（数字とメモ）",
+        Text::KeyClearCellDisabled => "マスを消去（消去できるマスが選択されていません）",
+        Text::KeyToggleInputMode => "数字/メモ入力を切り替え",
+
+        Text::CellPosition => "{row}行{col}列",
+        Text::CellGiven => "初期値 {digit}",
+        Text::CellFilled => "入力 {digit}",
+        Text::CellEmpty => "空白",
+        Text::CellCandidates => "候補 {digits}",
+        Text::CellConflict => "矛盾あり",
+        Text::CellHinted => "ヒント{count}回",
+
+        Text::StatusInProgress => "プレイ中...",
+        Text::StatusConflict => "盤面に矛盾があります",
+        Text::ReadOnly => "閲覧のみ",
+        Text::ReadOnlyReplayTooltip => "記録を再生中のため、操作できません",
+        Text::ReadOnlyReviewTooltip => {
+            "クリアした盤面を振り返り中です。新しいゲームを始めると再びプレイできます"
+        }
+        Text::ReviewFirstTooltip => "最初の盤面を表示",
+        Text::ReviewPreviousTooltip => "1手戻る",
+        Text::ReviewNextTooltip => "1手進む",
+        Text::ReviewLastTooltip => "クリアした盤面を表示",
+        Text::ReviewTimelineTooltip => {
+            "ドラッグして解答の流れを振り返ります。赤はミス、黄色はヒントの箇所です"
+        }
+        Text::ReviewStep => "{step} / {total} 手目",
+        Text::ReviewMistake => "ミス",
+        Text::ReviewHint => "ヒント使用",
+        Text::StatusSolved => "クリア！おめでとうございます！",
+        Text::StatusHint => "ヒント:",
+        Text::HintLookHere => "強調表示された範囲に手がかりがあります",
+        Text::HintFocusArea => "強調表示された部分に注目",
+        Text::HintApplicable => "ここで {technique} が使えます",
+        Text::HintPreviewing => "{technique} による変更をプレビュー中",
+        Text::HintApplied => "{technique} を適用しました",
+
+        Text::Generating => "生成中...",
+        Text::GeneratingNewGame => "新しいゲームを生成しています...",
+        Text::PreparingGenerator => "パズル生成の準備をしています...",
+        Text::CheckingSolvability => "解の有無を確認中...",
+        Text::CheckingSolvabilityWait => "現在の盤面を解析しています。しばらくお待ちください。",
+        Text::CheckingSolvabilitySlow => "数秒かかる場合があります。",
+
+        Text::NewGameConfirmHeading => "新しいゲームを始めますか？",
+        Text::NewGameConfirmLabel => "新しいゲームを始めますか？現在の進行状況は失われます。",
+        Text::ResetInputsConfirmHeading => "入力をリセットしますか？",
+        Text::ResetInputsConfirmLabel => "すべての入力を消去して初期状態に戻しますか？",
+        Text::RevealCellConfirmHeading => "マスの答えを表示しますか？",
+        Text::RevealCellConfirmLabel => "選択中のマスに答えを入力しますか？統計に記録されます。",
+        Text::IllegalNotesFound => "ありえないメモがあります",
+        Text::IllegalNotesFoundLabel => {
+            "強調表示したメモは置かれた数字と矛盾します。削除しますか？"
+        }
+        Text::IllegalNotesCount => "ありえないメモ: {count}",
+        Text::RemoveIllegalNotes => "削除",
+        Text::NotesLookFine => "メモに問題はありません",
+        Text::NoIllegalNotesLabel => "置かれた数字と矛盾するメモはありません。",
+        Text::RevealAllConfirmHeading => "解答を表示しますか？",
+        Text::RevealAllConfirmLabel => "すべてのマスに答えを入力しますか？統計に記録されます。",
+        Text::BoardInconsistent => "盤面が矛盾しています",
+        Text::BoardInconsistentLabel => {
+            "矛盾または候補のないマスが見つかりました。最後の矛盾のない状態まで戻すことをおすすめします。"
+        }
+        Text::NoSolutionFound => "解が見つかりません",
+        Text::NoSolutionFoundLabel => {
+            "現在の状態からは解がありません。最後に解が存在した状態まで戻すことをおすすめします。"
+        }
+        Text::InvalidCells => "ルールに反しているマス: {cells}",
+        Text::ConflictingEntries => "次の入力を消すと解が存在します: {cells}",
+        Text::NotesMayBeIncorrect => "メモが誤っている可能性があります",
+        Text::SolvableIgnoringNotesLabel => {
+            "メモを無視すると解が存在します。候補を再構築しますか？"
+        }
+        Text::HintIgnoringNotesLabel => {
+            "メモを無視するとヒントが見つかります。候補を再構築しますか？"
+        }
+        Text::Rebuild => "再構築",
+        Text::Solvable => "解があります",
+        Text::SolvableLabel => "現在の状態からまだ解くことができます。",
+        Text::UndoComplete => "元に戻しました",
+        Text::UndidToSolvable => "解が存在する状態まで {steps} 手戻しました。",
+        Text::UndoNoSolvableState => "解が存在する状態が見つかりませんでした。",
+        Text::AlreadySolved => "解答済み",
+        Text::AlreadySolvedNoCheck => "パズルは解答済みのため、確認の必要はありません。",
+        Text::CheckUnavailable => "確認できません",
+        Text::CheckUndeterminedLabel => {
+            "盤面の自由度が高すぎて、解の有無を判定できませんでした。もう少しマスを埋めてから再度お試しください。"
+        }
+        Text::UndidToConsistent => "矛盾のない状態まで {steps} 手戻しました。",
+        Text::NoHintFound => "ヒントが見つかりません",
+        Text::NoApplicableTechniques => "現在の状態で使えるテクニックが見つかりませんでした。",
+        Text::HintUndeterminedLabel => {
+            "次の手の探索に時間がかかりすぎました。もう少しマスを埋めてから再度お試しください。"
+        }
+        Text::ConflictResolvedNoStep => "矛盾は解消しましたが、次の手が見つかりません。",
+        Text::UndoNoConsistentState => "矛盾のない状態が見つかりませんでした。",
+        Text::AlreadySolvedNoHint => "パズルは解答済みのため、ヒントはありません。",
+        Text::ExportSaved => "エクスポートしました",
+        Text::ExportSavedLabel => "{path} に保存しました",
+        Text::ExportFailed => "エクスポートに失敗しました",
+        Text::SharedPuzzleInvalid => "共有された問題が不正です",
+        Text::LoadSharedPuzzle => "問題を読み込む",
+        Text::LoadSharedPuzzleHeading => "共有された問題を読み込みますか？",
+        Text::LoadSharedPuzzleLabel => "現在のゲームは共有された問題で置き換えられます。",
+        Text::AlreadySolvedOn => "この問題は{date}に解いたことがあります。",
+        Text::RecoverProgress => "復元する",
+        Text::RecoverProgressHeading => "保存されていない進行状況を復元しますか？",
+        Text::RecoverProgressLabel => {
+            "前回は正常に終了しませんでした。最後の保存以降の進行状況を、取り消し履歴も含めて復元しますか？"
+        }
+        Text::SolvingDetails => "解法の詳細",
+        Text::TotalSteps => "総手数: {steps}",
+        Text::NoTechniquesApplied => "テクニックは使われませんでした。",
+
+        Text::ShareLinkCopied => "共有リンクをクリップボードにコピーしました",
+        Text::BoardImageCopied => "盤面の画像をクリップボードにコピーしました",
+        Text::Autosaved => "進行状況を保存しました",
+        Text::SettingsExported => "設定をエクスポートしました",
+        Text::SettingsImported => "設定をインポートしました",
+        Text::SettingsExportFailed => "設定をエクスポートできませんでした",
+        Text::SettingsImportFailed => "設定をインポートできませんでした",
+        Text::NewGameFailed => "新しいパズルを生成できませんでした",
+        Text::SolvabilityCheckFailed => "解けるかどうかを確認できませんでした",
+        Text::ProgressOnTrack => "順調です。すべての数字が解答と一致しています",
+        Text::ProgressOffTrack => "解答と一致しない数字があります",
+        Text::ProgressCheckUnavailable => "このパズルには照合できる解答がありません",
+        Text::TimedMode => "タイムアタック",
+        Text::TimedModeTooltip => "プレイ時間を計測し、難易度から見積もった目標タイムと比べます。",
+        Text::PlayTimeTooltip => "プレイ時間 / 目標タイム",
+        Text::SolvedResultHeading => "パズル完成",
+        Text::SolvedResultReviewHint => {
+            "振り返ると、解いた手順をたどれます。盤面はその後閲覧のみになります。"
+        }
+        Text::SolvedResultReview => "振り返る",
+        Text::YourTime => "あなたのタイム",
+        Text::ParTime => "目標タイム",
+        Text::ParTimeUnknown => "未判定",
+        Text::RatingOutstanding => "お見事！目標タイムを大きく上回りました。",
+        Text::RatingUnderPar => "目標タイム以内です。よくできました！",
+        Text::RatingOverPar => "目標タイムを少し超えました。",
+        Text::RatingWellOverPar => "目標タイムを大きく超えました。練習を続けましょう！",
+        Text::GameMode => "モード",
+        Text::ModeClassic => "通常",
+        Text::ModeClassicTooltip => "時間を気にせず自分のペースで解きます。",
+        Text::HotSeatRace => "交代対戦",
+        Text::HotSeatRaceTooltip => {
+            "2人のプレイヤーがこの端末で交代しながら、それぞれ同じパズルを解きます。合計タイムが短い方の勝ちです。"
+        }
+        Text::PlayerOne => "プレイヤー1",
+        Text::PlayerTwo => "プレイヤー2",
+        Text::PassTurnTooltip => "{player}に盤面を渡す",
+        Text::RaceStatusTooltip => "各プレイヤーのプレイ時間と進捗",
+        Text::RaceStandings => "対戦状況",
+        Text::RaceResultHeading => "対戦結果",
+        Text::RaceInProgress => "対戦はまだ続いています。",
+        Text::RaceWinner => "{player}の勝ち！",
+        Text::RaceTie => "引き分け！",
+        Text::RaceTime => "タイム",
+        Text::RaceProgress => "進捗",
+        Text::RaceTurnFinished => "完成！もう一人のプレイヤーに端末を渡してください。",
+        Text::Campaign => "キャンペーン",
+        Text::CampaignTooltip => "収録されたパズルパックを順番に遊ぶ",
+        Text::CampaignDescription => {
+            "各パックのパズルを順番に解きましょう。パックをすべて解くと次のパックが解放されます。"
+        }
+        Text::BackToGame => "ゲームに戻る",
+        Text::PackFirstSteps => "はじめの一歩",
+        Text::PackPairsAndPointing => "ペアとポインティング",
+        Text::PackSubsetsAndWings => "サブセットとウイング",
+        Text::PackAdvancedPatterns => "上級パターン",
+        Text::PackLocked => "前のパックのパズルをすべて解くと解放されます。",
+        Text::PuzzleLocked => "前のパズルを解くと解放されます",
+        Text::CampaignPuzzleNumber => "パズル {count}",
+        Text::BestTime => "ベストタイム: {time}",
+        Text::NowPlaying => "プレイ中",
+        Text::CampaignPuzzleSolved => "キャンペーンのパズルをクリアしました",
+        Text::CampaignPuzzleLoadFailed => "キャンペーンのパズルを読み込めませんでした",
+        Text::Learn => "学ぶ",
+        Text::LearnTooltip => "解き方のテクニックを例題で学ぶ",
+        Text::LearnDescription => "テクニックを選ぶと、仕組みの説明と例題を順に確認できます。",
+        Text::LessonExample => "例題 {index}",
+        Text::LessonNoExamples => "このテクニックにはまだ例題がありません。",
+        Text::LessonStageFocus => "ハイライトされた範囲にパターンがあります。",
+        Text::LessonStageCells => "これらのマスがパターンを作っています。",
+        Text::LessonStageDigits => "これらの候補がパターンを作っています。",
+        Text::LessonStagePreview => "パターンから印の付いた変化が導かれます。",
+        Text::LessonPreviousStage => "戻る",
+        Text::LessonNextStage => "次へ",
+        Text::TutorialWelcome => {
+            "Numelace へようこそ！どの行・列・ブロックにも 1 から 9 の数字が 1 つずつ入るようにマスを埋めましょう。このチュートリアルでは、やさしいパズルで基本の操作を紹介します。"
+        }
+        Text::TutorialSelectCell => "空いているマスをクリックまたはタップして選択しましょう。",
+        Text::TutorialPlaceDigit => {
+            "キーパッドか数字キーで数字を入力して、選択したマスを埋めましょう。"
+        }
+        Text::TutorialToggleNote => {
+            "別の空きマスを選んで候補をメモしましょう。キーパッドの鉛筆ボタンか S キーでメモに切り替えて数字を入力するか、Shift を押しながら数字キーを押します。"
+        }
+        Text::TutorialUndo => {
+            "気が変わったら、元に戻すボタンか Ctrl+Z で直前の入力を取り消せます。"
+        }
+        Text::TutorialHint => "行き詰まったら、電球ボタンか Ctrl+J でヒントを表示できます。",
+        Text::TutorialDone => {
+            "以上です！このパズルを解き進めるか、ツールバーから新しいゲームを始めましょう。チュートリアルは設定からもう一度見られます。"
+        }
+        Text::ScriptStepCount => "ステップ {index} / {count}",
+        Text::ScriptContinue => "続ける",
+        Text::ScriptSkip => "チュートリアルをスキップ",
+        Text::GenerationTrace => "生成トレース",
+        Text::TraceStrategies => "充填: {fill} / 削除: {carve}",
+        Text::TraceUniquenessChecks => "一意性チェック: {count}回（{kept}回採用）",
+        Text::TraceSolverRuns => "ソルバー実行: {count}回",
+        Text::TraceStep => "ステップ",
+        Text::TraceSolution => "削除前の完成盤面です。",
+        Text::TraceStepKept => "{cells}を削除しました。エントロピー {bits} ビット。",
+        Text::TraceStepRejected => {
+            "{cells}は一意性が崩れるため残しました。エントロピー {bits} ビット。"
+        }
+        Text::TraceShading => "マスの色分け:",
+        Text::TraceShadingCandidates => "候補数",
+        Text::TraceShadingRemovalOrder => "削除順",
+        Text::ReplayTutorial => "チュートリアルをもう一度",
+        Text::ReplayTutorialTooltip => "やさしいパズルで基本の操作をもう一度確認します",
+
+        Text::NewGameDescription => "難易度とテクニックを選んで新しいパズルを生成します。",
+        Text::Difficulty => "難易度",
+        Text::DifficultyBeyondTechniques => "推測が必要",
+        Text::DifficultyBasic => "初級",
+        Text::DifficultyIntermediate => "中級",
+        Text::DifficultyUpperIntermediate => "中上級",
+        Text::DifficultyAdvanced => "上級",
+        Text::DifficultyExpert => "達人",
+        Text::Custom => "カスタム",
+        Text::Techniques => "テクニック",
+        Text::ExtraRules => "追加ルール",
+        Text::AntiKnightTooltip => {
+            "桂馬飛び（ナイトの動き）の位置にあるマスには同じ数字が入りません。"
+        }
+        Text::AntiKingTooltip => "斜めに接するマスには同じ数字が入りません。",
+        Text::JigsawTooltip => "3×3 のブロックの代わりに、9 マスの不規則なブロックを使います。",
+        Text::Symmetry => "ヒントの対称性",
+        Text::SymmetryNone => "なし",
+        Text::SymmetryRotational => "点対称（180°回転）",
+        Text::SymmetryMirror => "線対称（左右）",
+        Text::SymmetryDiagonal => "線対称（対角線）",
+        Text::SeedOptional => "シード（任意）",
+        Text::SeedHint => "空欄ならランダム",
+        Text::GenerationAttempts => "生成の試行回数",
+        Text::Generate => "生成",
+
+        Text::Language => "言語",
+        Text::Assist => "アシスト",
+        Text::BlockRuleViolations => "ルール違反の入力を防ぐ",
+        Text::ValidationModeRules => "ルールと照合",
+        Text::ValidationModeSolution => "解答と照合",
+        Text::ValidationModeSolutionTooltip => {
+            "答えでない数字を入力できなくし、間違った手を打てないようにします。メモには影響しません。"
+        }
+        Text::Highlight => "強調表示",
+        Text::HighlightSelectedCell => "選択中のマスの枠",
+        Text::HighlightSelectedDigit => "選択中の数字のマス",
+        Text::HighlightSelectedDigitNote => "選択中の数字のメモ",
+        Text::HighlightSelectedCellPeer => "選択中のマスの行/列/ブロック",
+        Text::HighlightSelectedDigitPeer => "選択中の数字のマスの行/列/ブロック",
+        Text::HighlightConflict => "矛盾しているマス/メモ",
+        Text::HighlightSelectedDigitCandidate => "選択中の数字を入れられるマス",
+        Text::HighlightBivalueCell => "候補が 2 つのマス",
+        Text::HighlightBivalueCellTooltip => {
+            "候補が 2 つのマスは、Y-ウィングや XY-チェーン など多くの上級テクニックの出発点になります。"
+        }
+        Text::HighlightIntensity => "ハイライトの濃さ",
+        Text::BivalueSourceCandidates => "残りの候補で数える",
+        Text::BivalueSourceNotes => "メモで数える",
+        Text::AutoRemovePeerNotes => "入力時に行/列/ブロックのメモを自動削除",
+        Text::AutoFillNotesOnNewOrReset => "新しいゲーム/リセット時にメモを自動入力",
+        Text::KeypadCandidateCounts => "キーパッドに残りの候補マス数を表示",
+        Text::LockCompletedDigits => "揃った数字のキーを無効にする",
+        Text::LockCompletedDigitsTooltip => {
+            "9個すべて正しく配置された数字のキーを無効にし、誤って10個目を入れないようにします。パズルの解答が必要です。"
+        }
+        Text::CandidateHeatmap => "候補数ヒートマップ",
+        Text::CandidateHeatmapTooltip => {
+            "空きマスを残りの候補数で色分けします。候補が少ないほど暖色になります。"
+        }
+        Text::GameInfo => "ゲーム情報",
+        Text::GameInfoTooltip => {
+            "パズルの難易度・初期数字・シード・経過時間・ヒント・進み具合をサイドバーに表示します。"
+        }
+        Text::RegeneratePuzzle => "シードから再生成",
+        Text::RegeneratePuzzleTooltip => {
+            "今のパズルを、そのシードと生成時の設定から生成し直します。"
+        }
+        Text::InfoClues => "初期数字",
+        Text::InfoSeed => "シード",
+        Text::InfoSeedCopy => "クリックでコピー",
+        Text::InfoElapsed => "経過時間",
+        Text::InfoHintsUsed => "使ったヒント",
+        Text::InfoPlacedDigits => "配置済みの数字",
+        Text::Scratchpad => "スクラッチパッド",
+        Text::ScratchPen => "フリーハンド",
+        Text::ScratchPenTooltip => {
+            "盤面の上に自由に書き込みます。書き込みはゲームには含まれません。もう一度選ぶか Esc キーで数字の入力に戻ります。"
+        }
+        Text::ScratchLine => "マス間の線",
+        Text::ScratchLineTooltip => {
+            "マスからマスへドラッグして中心を線で結びます。チェーンを手でたどるときに使えます。"
+        }
+        Text::ClearScratchpad => "スクラッチパッドを消去",
+        Text::ClearScratchpadTooltip => "盤面の書き込みをすべて消します。",
+        Text::DeductionDepthOverlay => "難所オーバーレイ",
+        Text::DeductionDepthOverlayTooltip => {
+            "空きマスを、シングルで確定させるのに必要な仮置きの深さで色分けします。深いほど暖色になります。シングルだけで確定するマスは色が付きません。"
+        }
+        Text::HintDetail => "最初のヒントで表示する内容",
+        Text::HintDetailConditionCells => "テクニックに使うマス",
+        Text::HintDetailFocusArea => "注目する場所のみ",
+        Text::HintDetailFocusAreaTooltip => {
+            "まず行・列・ブロック・マスを1つだけ強調表示します。もう一度ヒントを求めると、テクニックに使うマスを表示します。"
+        }
+        Text::AssistProfiles => "難易度ごとに補助設定を記憶",
+        Text::AssistProfilesTooltip => {
+            "補助設定を現在の難易度用に保存し、同じ難易度の新しいゲームを始めたときに復元します。"
+        }
+        Text::AssistProfileActive => "{difficulty}用の設定を編集中",
+        Text::Solver => "ソルバー",
+        Text::SolverDescription => "ヒントと難易度判定に使うテクニック。上から順に試します。",
+        Text::AssumeUniqueSolution => "解が 1 つだけと仮定する",
+        Text::AssumeUniqueSolutionTooltip => {
+            "ユニークレクタングルなど、解が 1 つだけであることを前提にしたテクニックを使います。解が複数あるパズルではヒントが誤ることがあります。"
+        }
+        Text::MoveTechniqueUp => "このテクニックを先に試す",
+        Text::MoveTechniqueDown => "このテクニックを後に試す",
+        Text::ResetToDefaults => "初期設定に戻す",
+        Text::Input => "入力",
+        Text::CellFirst => "マスが先",
+        Text::CellFirstTooltip => "マスを選んでから数字を押します。",
+        Text::DigitFirst => "数字が先",
+        Text::DigitFirstTooltip => "数字を押してから、マスをタップして入力します。",
+        Text::FontSize => "文字サイズ",
+        Text::FontSizeNormal => "標準",
+        Text::FontSizeLargePrint => "大きな文字",
+        Text::UiScale => "UI の倍率",
+        Text::Motion => "動き",
+        Text::MotionSystemTooltip => "ブラウザーの「視差効果を減らす」設定に従います",
+        Text::MotionFull => "オン",
+        Text::MotionReduced => "オフ",
+        Text::MotionReducedTooltip => {
+            "アニメーションを止め、待機中の再描画を減らして、遅い端末での負荷を抑えます"
+        }
+        Text::NoteLayoutMiniGrid => "3×3 配置",
+        Text::NoteLayoutLinear => "横に並べる",
+        Text::NoteLayoutLinearTooltip => "数字ごとの定位置ではなく、小さい順に詰めてメモを書きます",
+        Text::ReserveNoteSpace => "メモの位置を固定する",
+        Text::ReserveNoteSpaceTooltip => {
+            "9個すべてのメモが入る場所を確保して並べ、メモの追加や削除で位置がずれないようにします"
+        }
+        Text::GivenStylePlain => "色のみ",
+        Text::GivenStyleBold => "太字",
+        Text::GivenStyleBoxed => "枠で囲む",
+        Text::BoardCoordinates => "行と列の番号",
+        Text::BoardCoordinatesTooltip => {
+            "盤面の左に行番号、上に列番号を表示します。ヒントの r1c1 表記に対応します"
+        }
+        Text::Digits => "数字",
+        Text::Saving => "保存",
+        Text::AutosaveInterval => "自動保存の間隔",
+        Text::AutosaveIntervalTooltip => {
+            "進行状況を保存する間隔。その間の変更も、異常終了後に復元できます。"
+        }
+        Text::SecondsSuffix => " 秒",
+        Text::BoardColors => "盤面の色",
+        Text::ThemeSystem => "システム",
+        Text::ThemeLight => "ライト",
+        Text::ThemeDark => "ダーク",
+        Text::ThemeHighContrast => "ハイコントラスト",
+        Text::ColorBackground => "背景",
+        Text::ColorGridLines => "罫線",
+        Text::ColorGivenDigits => "初期配置の数字",
+        Text::ColorFilledDigits => "入力した数字",
+        Text::ColorSelectedCell => "選択中のマス",
+        Text::ColorSelectedDigit => "選択中の数字",
+        Text::ColorHouse => "行/列/ブロック",
+        Text::ColorConflicts => "矛盾",
+        Text::ColorHints => "ヒント",
+    }
+}
+
+#[expect(clippy::too_many_lines)]
+pub(super) fn technique(key: &str) -> Option<TechniqueText> {
+    let (name, description) = match key {
+        "full_house" => (
+            "フルハウス",
+            "行・列・ブロックに空いたマスが 1 つだけ残っていて、足りない数字が入ります。",
+        ),
+        "naked_single" => (
+            "ネイキッドシングル",
+            "候補が 1 つしか残っていないマスには、その数字が入ります。",
+        ),
+        "hidden_single" => (
+            "ヒドゥンシングル",
+            "行・列・ブロックの中で、ある数字を入れられるマスが 1 つしかありません。",
+        ),
+        "locked_candidates" => (
+            "ロックドキャンディデート",
+            "ある数字がブロックと行/列の交差部分に限られるため、もう一方の範囲の残りから除外できます。",
+        ),
+        "locked_candidates_pointing" => (
+            "ロックドキャンディデート（ポインティング）",
+            "ブロック内で数字が 1 つの行/列に限られるため、その行/列の残りから除外できます。",
+        ),
+        "locked_candidates_claiming" => (
+            "ロックドキャンディデート（クレーミング）",
+            "行/列の中で数字が 1 つのブロックに限られるため、そのブロックの残りから除外できます。",
+        ),
+        "naked_pair" => (
+            "ネイキッドペア",
+            "同じ範囲の 2 マスが同じ 2 つの候補を持つため、他のマスからその候補を除外できます。",
+        ),
+        "hidden_pair" => (
+            "ヒドゥンペア",
+            "2 つの数字が同じ 2 マスにしか入らないため、そのマスの他の候補を除外できます。",
+        ),
+        "naked_triple" => (
+            "ネイキッドトリプル",
+            "同じ範囲の 3 マスの候補が 3 つの数字に限られるため、他のマスからそれらを除外できます。",
+        ),
+        "hidden_triple" => (
+            "ヒドゥントリプル",
+            "3 つの数字が同じ 3 マスにしか入らないため、そのマスの他の候補を除外できます。",
+        ),
+        "naked_quad" => (
+            "ネイキッドクアッド",
+            "同じ範囲の 4 マスの候補が 4 つの数字に限られるため、他のマスからそれらを除外できます。",
+        ),
+        "hidden_quad" => (
+            "ヒドゥンクアッド",
+            "4 つの数字が同じ 4 マスにしか入らないため、そのマスの他の候補を除外できます。",
+        ),
+        "x_wing" => (
+            "X-ウィング",
+            "2 つの行で数字が同じ 2 列に限られる（またはその逆）ため、その列の他のマスから除外できます。",
+        ),
+        "skyscraper" => (
+            "スカイスクレイパー",
+            "片端を共有する 2 つの強リンクについて、もう一方の両端から見えるマスにはその数字が入りません。",
+        ),
+        "two_string_kite" => (
+            "2-ストリングカイト",
+            "ブロックでつながる行と列のリンクについて、両方の自由端から見えるマスにはその数字が入りません。",
+        ),
+        "y_wing" => (
+            "Y-ウィング",
+            "軸のマスと 2 つの翼により数字がどちらかの翼に入るため、両方の翼から見えるマスには入りません。",
+        ),
+        "unique_rectangle" => (
+            "ユニークレクタングル",
+            "解が 1 つだけのパズルでは、2 つのブロックにまたがる長方形の 4 隅が同じ 2 つの数字だけになることはありません。",
+        ),
+        "swordfish" => (
+            "ソードフィッシュ",
+            "3 つの行で数字が同じ 3 列に限られる（またはその逆）ため、その列の他のマスから除外できます。",
+        ),
+        "jellyfish" => (
+            "ジェリーフィッシュ",
+            "4 つの行で数字が同じ 4 列に限られる（またはその逆）ため、その列の他のマスから除外できます。",
+        ),
+        "remote_pair" => (
+            "リモートペア",
+            "同じ 2 候補のマスの連鎖で候補が交互に決まるため、逆の偶奇の両端から見えるマスにはどちらも入りません。",
+        ),
+        "w_wing" => (
+            "W-ウィング",
+            "同じ 2 つの候補を持つ 2 マスが 1 つの数字の強リンクでつながると、両方から見えるマスにはもう一方の数字が入りません。",
+        ),
+        "x_chain" => (
+            "X-チェーン",
+            "1 つの数字のリンクの連鎖でどちらかの端に数字が入るため、両端から見えるマスには入りません。",
+        ),
+        "xy_chain" => (
+            "XY-チェーン",
+            "2 候補のマスの連鎖でどちらかの端に数字が入るため、両端から見えるマスには入りません。",
+        ),
+        "xyz_wing" => (
+            "XYZ-ウィング",
+            "3 候補の軸と 2 つの翼で数字が 3 マスのどれかに入るため、すべてから見えるマスには入りません。",
+        ),
+        "wxyz_wing" => (
+            "WXYZ-ウィング",
+            "4 つの数字を持つ 4 マスで共通の数字がどれかに入るため、それらすべてから見えるマスには入りません。",
+        ),
+        "aic" => (
+            "AIC (交互推論チェーン)",
+            "強リンクと弱リンクを交互につなぐ鎖でどちらかの端が正しいと決まるため、両端と矛盾する候補を除外します。",
+        ),
+        "cage_combination" => (
+            "ケージの組み合わせ",
+            "ケージのマスには、合計がケージの和になる異なる数字の組み合わせに含まれる数字しか入りません。",
+        ),
+        "edge_relation" => (
+            "辺の関係",
+            "隣のマスと不等号や連続の記号を満たせない候補を除外します。",
+        ),
+        _ => return None,
+    };
+    Some(TechniqueText { name, description })
+}
+
+pub(super) fn technique_explanation(key: &str) -> Option<&'static str> {
+    let explanation = match key {
+        "full_house" => {
+            "8 つのマスが埋まった行・列・ブロックを探します。そこに足りない数字は 1 つ、入れられるマスも 1 つだけなので、そこに書き込みます。"
+        }
+        "naked_single" => {
+            "行・列・ブロックに入っている数字で 8 つの数字が除外されたマスを探します。残った 1 つの数字だけがそのマスに入れられるので、それを書き込みます。"
+        }
+        "hidden_single" => {
+            "行・列・ブロックのどれかと、そこにまだ足りない数字を 1 つ選びます。その数字を入れられるマスが 1 つを除いてすべて除外されていれば、そのマスに他の候補があってもその数字が入ります。"
+        }
+        "locked_candidates" => {
+            "ポインティング: ブロック内で数字の候補がすべて 1 つの行/列にあれば、ブロックのその数字はその行/列に入るので、行/列の他のマスには入りません。クレーミングは逆向きで、行/列の中で数字の候補がすべて 1 つのブロックにあれば、そのブロックの他のマスには入りません。"
+        }
+        "naked_pair" => {
+            "2 つのマスには、順番はともかく 2 つの数字が入ります。どちらの順番でも、同じ範囲の他のマスにはどちらの数字も入りません。"
+        }
+        "hidden_pair" => {
+            "2 つの数字はどちらも 2 つのマスのどちらかに入るので、2 マスにはちょうどその数字が入ります。2 マスの他の候補はすべて除外できます。"
+        }
+        "naked_triple" => {
+            "3 マスすべてが 3 つの数字を持っている必要はなく、候補を合わせて 3 つの数字になれば十分です。3 マスがその数字を使い切るので、範囲の他のマスには入りません。"
+        }
+        "hidden_triple" => {
+            "範囲の中で 3 つの数字は 3 マス以外に行き場がないので、3 マスにはちょうどその数字が入ります。3 マスの他の候補を除外します。"
+        }
+        "naked_quad" => {
+            "ネイキッドトリプルのマスが 1 つ増えたものです。4 マスが 4 つの数字を使い切るので、範囲の他のマスにはどれも入りません。"
+        }
+        "hidden_quad" => {
+            "4 つの数字は 4 マスを分け合うしかないので、4 マスには他の数字が入る余地がありません。4 マスの他の候補を除外します。"
+        }
+        "x_wing" => {
+            "2 つの行のそれぞれで、数字は 2 つの列のどちらかに入ります。2 行で両方の列のその数字を使い切るので、その列の他のマスには入りません。行と列を入れ替えても同じです。"
+        }
+        "skyscraper" => {
+            "強リンクとは、ある数字が入るマスが 2 つしかない範囲のことです。片側の端がそろった平行な 2 つの強リンクを考えます。そろった端の両方に数字が入ることはないので、もう一方の端のどちらかに入り、その両方から見えるマスには入りません。"
+        }
+        "two_string_kite" => {
+            "数字の入るマスがそれぞれ 2 つしかない行と列で、片方の端が同じブロックにあるものを探します。その 2 つの端の両方には入らないので、残りの端のどちらかに入り、その両方から見えるマスには入りません。"
+        }
+        "y_wing" => {
+            "軸のマスの候補が XY で、軸から見える翼の一方が XZ、もう一方が YZ です。軸にどちらの数字が入っても、どちらかの翼に Z が残ります。両方の翼から見えるマスには Z が入りません。"
+        }
+        "unique_rectangle" => {
+            "3 つの隅の候補が同じ 2 つの数字だけなら、残りの隅にはそのどちらも入りません。2 つの隅がその 2 つの数字だけで、残りの 2 つの隅が同じ 3 つ目の数字を 1 つだけ加えた候補を持つなら、どちらかに 3 つ目の数字が入るので、両方から見えるマスには入りません。解が 1 つだけのパズルでのみ使えます。"
+        }
+        "swordfish" => {
+            "X-ウィングを 3 本に広げたものです。3 つの行がその数字を 3 つの列の異なる列に置くので、その列には他に入る場所がありません。"
+        }
+        "jellyfish" => {
+            "X-ウィングを 4 本に広げたものです。4 つの行が 4 つの列すべてでその数字を使い切るので、その列の他のマスには入りません。"
+        }
+        "remote_pair" => {
+            "鎖の隣り合うマスは互いに見えるので、ペアの異なる数字が入ります。そのため奇数本のリンクで離れた両端には 2 つの数字が 1 つずつ入り、両端から見えるマスにはどちらも入りません。"
+        }
+        "w_wing" => {
+            "互いに見えない 2 マスが A と B だけを持ち、A が 2 マスにしか入らない行・列・ブロックの両端がそれぞれを見ているとします。強リンクのどちらかの端に A が入るので、2 マスの一方は A ではなく B になります。両方から見えるマスには B が入りません。"
+        }
+        "x_chain" => {
+            "鎖は、2 マスのどちらかに必ず数字が入る強リンクと、高々 1 マスにしか入らない弱リンクを交互につなぎます。始点に数字が入らなければ、鎖をたどって終点に入ることが決まります。両端から見えるマスには入りません。"
+        }
+        "xy_chain" => {
+            "各マスは 2 つの候補を持ち、次のマスと 1 つを共有します。最初のマスが始めの数字でなければ、各マスが順に決まっていき、最後のマスに同じ数字が入ります。両端から見えるマスにはその数字が入りません。"
+        }
+        "xyz_wing" => {
+            "軸が XYZ、翼の一方が XZ、もう一方が YZ です。軸に何が入っても Z は軸か翼のどれかに入るので、3 マスすべてから見えるマスには Z が入りません。"
+        }
+        "wxyz_wing" => {
+            "蝶番のマスから見える 4 マスが合わせて 4 つの数字しか持たず、互いに見えないマスの間で重複できる数字は 1 つだけです。その数字はそれを持つマスのどれかに入るので、それらすべてから見えるマスには入りません。"
+        }
+        "aic" => {
+            "強リンクは、1 マスの 2 つの候補や、ハウス内で数字が入る 2 マスのように、どちらかが必ず正しい 2 つの候補を結びます。弱リンクは、両方が正しくはなれない 2 つの候補を結びます。これらを交互につなぐと、始点が正しくなければ終点が正しいと決まるので、両端のどちらとも両立しない候補を除外できます。"
+        }
+        _ => return None,
+    };
+    Some(explanation)
+}
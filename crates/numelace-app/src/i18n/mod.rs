@@ -0,0 +1,695 @@
+//! Localization of user-facing strings.
+//!
+//! Every string shown by the UI is identified by a [`Text`] key and looked up
+//! in the bundle of the current language, so a missing translation is a
+//! compile error rather than a runtime fallback. Strings with parameters use
+//! `{name}` placeholders that are filled by [`tr_args`].
+//!
+//! Solver techniques are localized separately by their
+//! [`technique_key`](numelace_solver::TechniqueStep::technique_key), falling
+//! back to the solver-provided English name for unknown keys.
+//!
+//! The current language is process-wide and is updated from the settings once
+//! per frame, so renderers can call [`tr`] without threading the language
+//! through every view model.
+
+use std::{
+    fmt::Display,
+    sync::atomic::{AtomicU8, Ordering},
+};
+
+use numelace_generator::ClueSymmetry;
+use numelace_solver::lesson::Lesson;
+
+use crate::state::{DifficultyPreset, GameMode, Language, Player, PuzzleDifficulty, ThemePreset};
+
+mod en;
+mod ja;
+
+static CURRENT_LANGUAGE: AtomicU8 = AtomicU8::new(0);
+
+/// Sets the language used by [`tr`] and the other lookup functions.
+pub(crate) fn set_language(language: Language) {
+    let value = match language {
+        Language::English => 0,
+        Language::Japanese => 1,
+    };
+    CURRENT_LANGUAGE.store(value, Ordering::Relaxed);
+}
+
+#[must_use]
+pub(crate) fn language() -> Language {
+    match CURRENT_LANGUAGE.load(Ordering::Relaxed) {
+        1 => Language::Japanese,
+        _ => Language::English,
+    }
+}
+
+/// Returns the string for `text` in the current language.
+#[must_use]
+pub(crate) fn tr(text: Text) -> &'static str {
+    text_in(language(), text)
+}
+
+/// Returns the string for `text` with each `{name}` placeholder replaced by its value.
+#[must_use]
+pub(crate) fn tr_args(text: Text, args: &[(&str, &dyn Display)]) -> String {
+    let mut result = tr(text).to_owned();
+    for (name, value) in args {
+        result = result.replace(&format!("{{{name}}}"), &value.to_string());
+    }
+    result
+}
+
+fn text_in(language: Language, text: Text) -> &'static str {
+    match language {
+        Language::English => en::text(text),
+        Language::Japanese => ja::text(text),
+    }
+}
+
+/// Localized name and short description of a solver technique.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct TechniqueText {
+    pub(crate) name: &'static str,
+    pub(crate) description: &'static str,
+}
+
+/// Looks up a technique by its technique or step key in the current language.
+#[must_use]
+pub(crate) fn technique(key: &str) -> Option<TechniqueText> {
+    technique_in(language(), key)
+}
+
+/// Returns the localized technique name, or `fallback` when the key is unknown.
+#[must_use]
+pub(crate) fn technique_name<'a>(key: &str, fallback: &'a str) -> &'a str {
+    technique(key).map_or(fallback, |text| text.name)
+}
+
+/// Returns the localized summary and explanation of a lesson, falling back to
+/// the solver-provided English text.
+#[must_use]
+pub(crate) fn lesson_text(lesson: &Lesson) -> (&'static str, &'static str) {
+    let key = lesson.id.as_str();
+    match language() {
+        Language::English => (lesson.summary, lesson.explanation),
+        Language::Japanese => (
+            technique(key).map_or(lesson.summary, |text| text.description),
+            ja::technique_explanation(key).unwrap_or(lesson.explanation),
+        ),
+    }
+}
+
+fn technique_in(language: Language, key: &str) -> Option<TechniqueText> {
+    match language {
+        Language::English => en::technique(key),
+        Language::Japanese => ja::technique(key),
+    }
+}
+
+#[must_use]
+pub(crate) fn difficulty(preset: DifficultyPreset) -> &'static str {
+    tr(match preset {
+        DifficultyPreset::Basic => Text::DifficultyBasic,
+        DifficultyPreset::Intermediate => Text::DifficultyIntermediate,
+        DifficultyPreset::UpperIntermediate => Text::DifficultyUpperIntermediate,
+        DifficultyPreset::Advanced => Text::DifficultyAdvanced,
+        DifficultyPreset::Expert => Text::DifficultyExpert,
+        DifficultyPreset::Custom => Text::Custom,
+    })
+}
+
+#[must_use]
+pub(crate) fn puzzle_difficulty(difficulty: PuzzleDifficulty) -> &'static str {
+    match difficulty {
+        PuzzleDifficulty::Rated(preset) => self::difficulty(preset),
+        PuzzleDifficulty::BeyondTechniques => tr(Text::DifficultyBeyondTechniques),
+    }
+}
+
+#[must_use]
+pub(crate) fn symmetry(symmetry: ClueSymmetry) -> &'static str {
+    tr(match symmetry {
+        ClueSymmetry::None => Text::SymmetryNone,
+        ClueSymmetry::Rotational => Text::SymmetryRotational,
+        ClueSymmetry::Mirror => Text::SymmetryMirror,
+        ClueSymmetry::Diagonal => Text::SymmetryDiagonal,
+    })
+}
+
+#[must_use]
+pub(crate) fn game_mode(mode: GameMode) -> &'static str {
+    tr(match mode {
+        GameMode::Classic => Text::ModeClassic,
+        GameMode::Timed => Text::TimedMode,
+        GameMode::HotSeatRace => Text::HotSeatRace,
+    })
+}
+
+#[must_use]
+pub(crate) fn player(player: Player) -> &'static str {
+    tr(match player {
+        Player::One => Text::PlayerOne,
+        Player::Two => Text::PlayerTwo,
+    })
+}
+
+#[must_use]
+pub(crate) fn theme_preset(preset: ThemePreset) -> &'static str {
+    tr(match preset {
+        ThemePreset::System => Text::ThemeSystem,
+        ThemePreset::Light => Text::ThemeLight,
+        ThemePreset::Dark => Text::ThemeDark,
+        ThemePreset::HighContrast => Text::ThemeHighContrast,
+        ThemePreset::Custom => Text::Custom,
+    })
+}
+
+/// Keys of user-facing strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Text {
+    // Common
+    Ok,
+    Cancel,
+    Close,
+    Undo,
+    Redo,
+    NewGame,
+    ResetInputs,
+    Settings,
+    ExportSettings,
+    ExportSettingsTooltip,
+    ImportSettings,
+    ImportSettingsTooltip,
+    Notes,
+    Appearance,
+    Custom,
+    AntiKnight,
+    AntiKing,
+    Jigsaw,
+
+    // Toolbar
+    CheckSolvability,
+    CheckSolvabilityTooltip,
+    Hint,
+    HintTooltip,
+    CommandCategoryEdit,
+    CommandCategoryGame,
+    KeyboardShortcuts,
+    CornerNoteKeys,
+    CenterNoteKeys,
+    PressAKey,
+    KeyReserved,
+    Unbound,
+    ResetShortcut,
+    More,
+    AutoFillAllCells,
+    AutoFillAllCellsTooltip,
+    AutoFillEmptyCells,
+    AutoFillEmptyCellsTooltip,
+    AutoFillSelectedCell,
+    AutoFillSelectedCellTooltip,
+    ClearMistakes,
+    ClearMistakesTooltip,
+    CheckProgress,
+    CheckProgressTooltip,
+    AuditNotes,
+    AuditNotesTooltip,
+    RevealCell,
+    RevealCellTooltip,
+    RevealAll,
+    RevealAllTooltip,
+    Statistics,
+    StatisticsTooltip,
+    BugReport,
+    BugReportTooltip,
+    BugReportLabel,
+    CopyReport,
+    HintsRequested,
+    RevealsUsed,
+    HintedTechniques,
+    NoHintsYet,
+    HintsByHouse,
+    PuzzlesSolved,
+    BestTimes,
+    NoSolvedPuzzlesYet,
+    HouseRows,
+    HouseColumns,
+    HouseBoxes,
+    ClearStatistics,
+    ThemeSystemTooltip,
+    ThemeDarkTooltip,
+    ThemeLightTooltip,
+    Export,
+    ExportPuzzle,
+    ExportPuzzleTooltip,
+    ExportProgress,
+    ExportProgressTooltip,
+    Share,
+    CopyPuzzleLink,
+    CopyPuzzleLinkTooltip,
+    CopyProgressLink,
+    CopyProgressLinkTooltip,
+    CopyBoardImage,
+    CopyBoardImageTooltip,
+
+    // Keypad
+    KeyArm,
+    KeyDisarm,
+    KeySetDigit,
+    KeySetDigitUnexpected,
+    KeySetDigitAlreadySet,
+    KeySetDigitBlockedByConflict,
+    KeySetDigitBlockedByWrongDigit,
+    KeySetDigitBlockedByGiven,
+    KeySetDigitBlockedByUnexpected,
+    KeySetDigitNoCell,
+    KeyAddNote,
+    KeyRemoveNote,
+    KeyToggleNoteUnexpected,
+    KeyAddNoteBlockedByConflict,
+    KeyAddNoteBlockedByFilled,
+    KeyToggleNoteNoCell,
+    KeyCandidateCount,
+    KeyDigitCompleted,
+    KeyClearCell,
+    KeyClearCellDisabled,
+    KeyToggleInputMode,
+
+    // Grid
+    CellPosition,
+    CellGiven,
+    CellFilled,
+    CellEmpty,
+    CellCandidates,
+    CellConflict,
+    CellHinted,
+
+    // Status line
+    StatusInProgress,
+    StatusConflict,
+    ReadOnly,
+    ReadOnlyReplayTooltip,
+    ReadOnlyReviewTooltip,
+    ReviewFirstTooltip,
+    ReviewPreviousTooltip,
+    ReviewNextTooltip,
+    ReviewLastTooltip,
+    ReviewTimelineTooltip,
+    ReviewStep,
+    ReviewMistake,
+    ReviewHint,
+    StatusSolved,
+    StatusHint,
+    HintLookHere,
+    HintFocusArea,
+    HintApplicable,
+    HintPreviewing,
+    HintApplied,
+
+    // Spinners
+    Generating,
+    GeneratingNewGame,
+    PreparingGenerator,
+    CheckingSolvability,
+    CheckingSolvabilityWait,
+    CheckingSolvabilitySlow,
+
+    // Dialogs
+    NewGameConfirmHeading,
+    NewGameConfirmLabel,
+    ResetInputsConfirmHeading,
+    ResetInputsConfirmLabel,
+    RevealCellConfirmHeading,
+    RevealCellConfirmLabel,
+    RevealAllConfirmHeading,
+    RevealAllConfirmLabel,
+    IllegalNotesFound,
+    IllegalNotesFoundLabel,
+    IllegalNotesCount,
+    RemoveIllegalNotes,
+    NotesLookFine,
+    NoIllegalNotesLabel,
+    BoardInconsistent,
+    BoardInconsistentLabel,
+    NoSolutionFound,
+    NoSolutionFoundLabel,
+    InvalidCells,
+    ConflictingEntries,
+    NotesMayBeIncorrect,
+    SolvableIgnoringNotesLabel,
+    HintIgnoringNotesLabel,
+    Rebuild,
+    Solvable,
+    SolvableLabel,
+    UndoComplete,
+    UndidToSolvable,
+    UndoNoSolvableState,
+    AlreadySolved,
+    AlreadySolvedNoCheck,
+    CheckUnavailable,
+    CheckUndeterminedLabel,
+    UndidToConsistent,
+    NoHintFound,
+    NoApplicableTechniques,
+    HintUndeterminedLabel,
+    ConflictResolvedNoStep,
+    UndoNoConsistentState,
+    AlreadySolvedNoHint,
+    ExportSaved,
+    ExportSavedLabel,
+    ExportFailed,
+    SharedPuzzleInvalid,
+    LoadSharedPuzzle,
+    LoadSharedPuzzleHeading,
+    LoadSharedPuzzleLabel,
+    AlreadySolvedOn,
+    RecoverProgress,
+    RecoverProgressHeading,
+    RecoverProgressLabel,
+    SolvingDetails,
+    TotalSteps,
+    NoTechniquesApplied,
+
+    // Notifications
+    ShareLinkCopied,
+    BoardImageCopied,
+    Autosaved,
+    SettingsExported,
+    SettingsImported,
+    SettingsExportFailed,
+    SettingsImportFailed,
+    NewGameFailed,
+    SolvabilityCheckFailed,
+    ProgressOnTrack,
+    ProgressOffTrack,
+    ProgressCheckUnavailable,
+
+    // Timed mode
+    TimedMode,
+    TimedModeTooltip,
+    PlayTimeTooltip,
+    SolvedResultHeading,
+    SolvedResultReviewHint,
+    SolvedResultReview,
+    YourTime,
+    ParTime,
+    ParTimeUnknown,
+    RatingOutstanding,
+    RatingUnderPar,
+    RatingOverPar,
+    RatingWellOverPar,
+
+    // Game modes and hot-seat race
+    GameMode,
+    ModeClassic,
+    ModeClassicTooltip,
+    HotSeatRace,
+    HotSeatRaceTooltip,
+    PlayerOne,
+    PlayerTwo,
+    PassTurnTooltip,
+    RaceStatusTooltip,
+    RaceStandings,
+    RaceResultHeading,
+    RaceInProgress,
+    RaceWinner,
+    RaceTie,
+    RaceTime,
+    RaceProgress,
+    RaceTurnFinished,
+
+    // Campaign
+    Campaign,
+    CampaignTooltip,
+    CampaignDescription,
+    BackToGame,
+    PackFirstSteps,
+    PackPairsAndPointing,
+    PackSubsetsAndWings,
+    PackAdvancedPatterns,
+    PackLocked,
+    PuzzleLocked,
+    CampaignPuzzleNumber,
+    BestTime,
+    NowPlaying,
+    CampaignPuzzleSolved,
+    CampaignPuzzleLoadFailed,
+
+    // Learn
+    Learn,
+    LearnTooltip,
+    LearnDescription,
+    LessonExample,
+    LessonNoExamples,
+    LessonStageFocus,
+    LessonStageCells,
+    LessonStageDigits,
+    LessonStagePreview,
+    LessonPreviousStage,
+    LessonNextStage,
+
+    // Tutorial
+    TutorialWelcome,
+    TutorialSelectCell,
+    TutorialPlaceDigit,
+    TutorialToggleNote,
+    TutorialUndo,
+    TutorialHint,
+    TutorialDone,
+    ScriptStepCount,
+    ScriptContinue,
+    ScriptSkip,
+    GenerationTrace,
+    TraceStrategies,
+    TraceUniquenessChecks,
+    TraceSolverRuns,
+    TraceStep,
+    TraceSolution,
+    TraceStepKept,
+    TraceStepRejected,
+    TraceShading,
+    TraceShadingCandidates,
+    TraceShadingRemovalOrder,
+    ReplayTutorial,
+    ReplayTutorialTooltip,
+
+    // New game options
+    NewGameDescription,
+    Difficulty,
+    DifficultyBeyondTechniques,
+    DifficultyBasic,
+    DifficultyIntermediate,
+    DifficultyUpperIntermediate,
+    DifficultyAdvanced,
+    DifficultyExpert,
+    Techniques,
+    ExtraRules,
+    AntiKnightTooltip,
+    AntiKingTooltip,
+    JigsawTooltip,
+    Symmetry,
+    SymmetryNone,
+    SymmetryRotational,
+    SymmetryMirror,
+    SymmetryDiagonal,
+    SeedOptional,
+    SeedHint,
+    GenerationAttempts,
+    Generate,
+
+    // Settings
+    Language,
+    Assist,
+    BlockRuleViolations,
+    ValidationModeRules,
+    ValidationModeSolution,
+    ValidationModeSolutionTooltip,
+    Highlight,
+    HighlightSelectedCell,
+    HighlightSelectedDigit,
+    HighlightSelectedDigitNote,
+    HighlightSelectedCellPeer,
+    HighlightSelectedDigitPeer,
+    HighlightConflict,
+    HighlightSelectedDigitCandidate,
+    HighlightBivalueCell,
+    HighlightBivalueCellTooltip,
+    HighlightIntensity,
+    BivalueSourceCandidates,
+    BivalueSourceNotes,
+    AutoRemovePeerNotes,
+    AutoFillNotesOnNewOrReset,
+    KeypadCandidateCounts,
+    LockCompletedDigits,
+    LockCompletedDigitsTooltip,
+    CandidateHeatmap,
+    CandidateHeatmapTooltip,
+    GameInfo,
+    GameInfoTooltip,
+    RegeneratePuzzle,
+    RegeneratePuzzleTooltip,
+    InfoClues,
+    InfoSeed,
+    InfoSeedCopy,
+    InfoElapsed,
+    InfoHintsUsed,
+    InfoPlacedDigits,
+    Scratchpad,
+    ScratchPen,
+    ScratchPenTooltip,
+    ScratchLine,
+    ScratchLineTooltip,
+    ClearScratchpad,
+    ClearScratchpadTooltip,
+    DeductionDepthOverlay,
+    DeductionDepthOverlayTooltip,
+    HintDetail,
+    HintDetailConditionCells,
+    HintDetailFocusArea,
+    HintDetailFocusAreaTooltip,
+    AssistProfiles,
+    AssistProfilesTooltip,
+    AssistProfileActive,
+    Solver,
+    SolverDescription,
+    AssumeUniqueSolution,
+    AssumeUniqueSolutionTooltip,
+    MoveTechniqueUp,
+    MoveTechniqueDown,
+    ResetToDefaults,
+    Input,
+    CellFirst,
+    CellFirstTooltip,
+    DigitFirst,
+    DigitFirstTooltip,
+    FontSize,
+    FontSizeNormal,
+    FontSizeLargePrint,
+    UiScale,
+    Motion,
+    MotionSystemTooltip,
+    MotionFull,
+    MotionReduced,
+    MotionReducedTooltip,
+    NoteLayoutMiniGrid,
+    NoteLayoutLinear,
+    NoteLayoutLinearTooltip,
+    ReserveNoteSpace,
+    ReserveNoteSpaceTooltip,
+    GivenStylePlain,
+    GivenStyleBold,
+    GivenStyleBoxed,
+    BoardCoordinates,
+    BoardCoordinatesTooltip,
+    Digits,
+    Saving,
+    AutosaveInterval,
+    AutosaveIntervalTooltip,
+    SecondsSuffix,
+    BoardColors,
+    ThemeSystem,
+    ThemeLight,
+    ThemeDark,
+    ThemeHighContrast,
+    ColorBackground,
+    ColorGridLines,
+    ColorGivenDigits,
+    ColorFilledDigits,
+    ColorSelectedCell,
+    ColorSelectedDigit,
+    ColorHouse,
+    ColorConflicts,
+    ColorHints,
+}
+
+#[cfg(test)]
+mod tests {
+    use numelace_solver::{lesson, technique::TechniqueId};
+
+    use super::{Text, ja, technique_in, text_in, tr_args};
+    use crate::state::Language;
+
+    #[test]
+    fn every_technique_is_localized() {
+        let keys = TechniqueId::ALL
+            .iter()
+            .map(|id| id.as_str())
+            .chain(["locked_candidates_pointing", "locked_candidates_claiming"])
+            .collect::<Vec<_>>();
+        for language in Language::all() {
+            for key in &keys {
+                assert!(
+                    technique_in(language, key).is_some(),
+                    "{key} is missing for {language:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn every_lesson_is_localized() {
+        for lesson in lesson::all_lessons() {
+            let key = lesson.id.as_str();
+            assert!(
+                ja::technique_explanation(key).is_some(),
+                "{key} explanation is missing for Japanese"
+            );
+        }
+    }
+
+    #[test]
+    fn placeholders_match_english() {
+        for text in [
+            Text::KeyArm,
+            Text::KeySetDigit,
+            Text::KeyCandidateCount,
+            Text::KeyDigitCompleted,
+            Text::CellPosition,
+            Text::CellGiven,
+            Text::CellFilled,
+            Text::CellCandidates,
+            Text::HintApplicable,
+            Text::UndidToSolvable,
+            Text::TotalSteps,
+            Text::ExportSavedLabel,
+            Text::InvalidCells,
+            Text::ConflictingEntries,
+            Text::LessonExample,
+            Text::ScriptStepCount,
+            Text::CellHinted,
+        ] {
+            let english = text_in(Language::English, text);
+            for language in Language::all() {
+                let localized = text_in(language, text);
+                for placeholder in [
+                    "{digit}",
+                    "{digits}",
+                    "{count}",
+                    "{row}",
+                    "{col}",
+                    "{technique}",
+                    "{steps}",
+                    "{path}",
+                    "{player}",
+                    "{index}",
+                ] {
+                    assert_eq!(
+                        english.contains(placeholder),
+                        localized.contains(placeholder),
+                        "{placeholder} mismatch in {text:?} for {language:?}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn tr_args_fills_placeholders() {
+        assert_eq!(
+            tr_args(Text::KeyCandidateCount, &[("count", &3), ("digit", &7)]),
+            "3 empty cell(s) can still take 7"
+        );
+    }
+}
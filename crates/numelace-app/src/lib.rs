@@ -3,8 +3,15 @@
 
 pub(crate) mod action;
 pub(crate) mod app;
+pub(crate) mod bug_report;
+pub(crate) mod campaign;
+pub(crate) mod developer;
+pub(crate) mod export;
 pub(crate) mod flow;
+pub(crate) mod i18n;
 pub(crate) mod persistence;
+pub(crate) mod puzzle_db;
+pub(crate) mod share;
 pub(crate) mod state;
 pub(crate) mod ui;
 pub(crate) mod undo_redo_stack;
@@ -14,3 +21,11 @@ pub(crate) mod worker;
 pub mod worker_api;
 
 pub use self::app::NumelaceApp;
+
+/// Application ID, also naming the directory eframe stores state in.
+pub const APP_ID: &str = "io.github.gifnksm.numelace";
+
+/// Smallest window size, in points, at the default display settings.
+///
+/// The app raises it while running when larger digits need bigger cells.
+pub const MIN_WINDOW_SIZE: (f32, f32) = (400.0, 300.0);
@@ -1,13 +1,35 @@
-use std::{collections::BTreeMap, fmt::Write, num::NonZero};
+use std::{
+    collections::{BTreeMap, BTreeSet, btree_map::Entry},
+    mem,
+    num::NonZero,
+    time::Duration,
+};
 
-use numelace_core::{Digit, DigitGrid, DigitGridParseError, Position, PositionNewError};
-use numelace_game::{CellState, Game, GameError};
-use numelace_solver::technique;
+use eframe::egui::Key;
+use numelace_core::{
+    Cage, CageError, CageSet, ChessRules, Digit, DigitGrid, DigitGridParseError, EdgeConstraint,
+    EdgeConstraintError, EdgeConstraintSet, EdgeKind, Position, PositionNewError,
+    PuzzleFingerprint, RegionLayout, RegionLayoutError,
+};
+use numelace_game::{Game, GameError, StateCodeError};
+use numelace_solver::{
+    TechniquePriority,
+    technique::{self, TechniqueId},
+};
 use serde::{Deserialize, Serialize};
 
-use crate::state::{
-    AppState, AssistSettings, DifficultyPreset, HighlightSettings, History, HistorySnapshot,
-    InputMode, NewGameOptions, NotesSettings, Settings,
+use crate::{
+    campaign::{PACKS, PuzzlePack},
+    state::{
+        AppState, AssistProfiles, AssistSettings, AutosaveSettings, BivalueSource,
+        CampaignProgress, CampaignPuzzle, CommandId, DifficultyPreset, DisplaySettings, GameMode,
+        GivenStyle, GridStyle, HighlightIntensity, HighlightSettings, HintDetail, HintHeatMap,
+        HintUsage, History, HistorySnapshot, HotSeatRace, HouseHints, InputMode, InputOrder,
+        InputSettings, KeyBindings, KeyTrigger, Language, MotionPreference, NewGameOptions,
+        NoteChord, NoteLayout, NotesSettings, Player, PuzzleDifficulty, PuzzleMetadata,
+        PuzzleMetadataStore, PuzzleSource, Settings, SolverSettings, ThemeColors, ThemePreset,
+        ThemeSettings, TimedGame, ValidationMode,
+    },
 };
 
 // DTO defaulting guidance:
@@ -30,6 +52,52 @@ pub(crate) struct PersistedState {
     settings: SettingsDto,
     #[serde(default)]
     history: HistoryDto,
+    #[serde(default)]
+    difficulty: Option<PuzzleDifficultyDto>,
+    /// Generator seed of the current puzzle, as hexadecimal.
+    #[serde(default)]
+    seed: Option<String>,
+    /// New game options that generate the current puzzle again.
+    #[serde(default)]
+    generated_with: Option<NewGameOptionsDto>,
+    /// Hint counts by technique step key.
+    #[serde(default)]
+    hint_usage: BTreeMap<String, u32>,
+    /// Number of cell and solution reveals.
+    #[serde(default)]
+    reveals: u32,
+    #[serde(default)]
+    hint_houses: HouseHintsDto,
+    /// Hints and reveals per cell of the current game, in position order.
+    #[serde(default)]
+    hint_heat_map: Vec<u8>,
+    #[serde(default)]
+    timed: Option<TimedGameDto>,
+    #[serde(default)]
+    race: Option<RaceDto>,
+    /// Solved puzzle indices by pack ID.
+    #[serde(default)]
+    campaign: BTreeMap<String, BTreeSet<usize>>,
+    #[serde(default)]
+    campaign_puzzle: Option<CampaignPuzzleDto>,
+    /// Puzzle metadata by fingerprint.
+    #[serde(default)]
+    puzzle_metadata: BTreeMap<String, PuzzleMetadataDto>,
+    /// Date first solved by puzzle fingerprint, read from saves written before
+    /// the metadata store.
+    #[serde(default, skip_serializing)]
+    solved_puzzles: BTreeMap<String, String>,
+    #[serde(default)]
+    tutorial_seen: bool,
+    /// Whether the solved puzzle on the board is being reviewed.
+    #[serde(default)]
+    reviewing: bool,
+    /// Whether solving the puzzle on the board was already recorded.
+    #[serde(default)]
+    solved_recorded: bool,
+    /// When a change was last saved, in milliseconds since the Unix epoch.
+    #[serde(default)]
+    saved_at: u64,
 }
 
 impl From<&AppState> for PersistedState {
@@ -42,27 +110,124 @@ impl From<&AppState> for PersistedState {
             new_game_options: NewGameOptionsDto::from(&value.new_game_options),
             settings: SettingsDto::from(&value.settings),
             history: HistoryDto::from(value.history()),
+            difficulty: value.difficulty.map(PuzzleDifficultyDto::from),
+            seed: value.seed.map(|seed| seed.to_string()),
+            generated_with: value.generated_with.as_ref().map(NewGameOptionsDto::from),
+            hint_usage: value.hint_usage.counts().clone(),
+            reveals: value.hint_usage.reveals(),
+            hint_houses: (*value.hint_usage.houses()).into(),
+            hint_heat_map: if value.hint_heat_map.is_empty() {
+                Vec::new()
+            } else {
+                value.hint_heat_map.counts()
+            },
+            timed: value.timed.map(TimedGameDto::from),
+            race: value.race.as_ref().map(RaceDto::from),
+            campaign: value.campaign.solved().clone(),
+            campaign_puzzle: value.campaign_puzzle.map(CampaignPuzzleDto::from),
+            puzzle_metadata: value
+                .puzzle_metadata
+                .entries()
+                .iter()
+                .map(|(fingerprint, entry)| (fingerprint.to_string(), entry.into()))
+                .collect(),
+            solved_puzzles: BTreeMap::new(),
+            tutorial_seen: value.tutorial_seen,
+            reviewing: value.reviewing,
+            solved_recorded: value.solved_recorded,
+            saved_at: value.saved_at,
+        }
+    }
+}
+
+impl PersistedState {
+    /// Returns when a change was last saved, in milliseconds since the Unix
+    /// epoch.
+    #[must_use]
+    pub(crate) fn saved_at(&self) -> u64 {
+        self.saved_at
+    }
+
+    /// Merges the progress recorded in `other`, keeping this state's board
+    /// and settings.
+    ///
+    /// Counts only ever grow, so the larger of each is kept. Puzzle metadata
+    /// is united, keeping the earlier dates and the faster time, and the
+    /// tutorial counts as seen if either side has seen it.
+    pub(crate) fn merge_progress(&mut self, mut other: PersistedState) {
+        self.migrate_solved_puzzles();
+        other.migrate_solved_puzzles();
+        for (key, count) in other.hint_usage {
+            let entry = self.hint_usage.entry(key).or_default();
+            *entry = (*entry).max(count);
+        }
+        self.reveals = self.reveals.max(other.reveals);
+        self.hint_houses = HouseHints::from(self.hint_houses)
+            .max(other.hint_houses.into())
+            .into();
+        self.tutorial_seen |= other.tutorial_seen;
+        for (pack, solved) in other.campaign {
+            self.campaign.entry(pack).or_default().extend(solved);
+        }
+        for (fingerprint, entry) in other.puzzle_metadata {
+            self.merge_puzzle_metadata(fingerprint, entry);
+        }
+    }
+
+    /// Moves the solved dates of older saves into the metadata store.
+    fn migrate_solved_puzzles(&mut self) {
+        for (fingerprint, date) in mem::take(&mut self.solved_puzzles) {
+            let legacy = PuzzleMetadataDto {
+                source: None,
+                difficulty: None,
+                added_on: date.clone(),
+                solved_on: Some(date),
+                best_time_ms: None,
+            };
+            self.merge_puzzle_metadata(fingerprint, legacy);
+        }
+    }
+
+    fn merge_puzzle_metadata(&mut self, fingerprint: String, entry: PuzzleMetadataDto) {
+        match self.puzzle_metadata.entry(fingerprint) {
+            Entry::Occupied(mut existing) => existing.get_mut().merge(entry),
+            Entry::Vacant(vacant) => {
+                vacant.insert(entry);
+            }
         }
     }
 }
 
 #[derive(Debug, derive_more::Display, derive_more::Error, derive_more::From)]
 pub(crate) enum AppStateConversionError {
-    #[display("failed to parse game data: {_0}")]
-    GameParse(DigitGridParseError),
+    #[display("failed to parse the saved {field} grid: {source}")]
+    #[from(skip)]
+    GameParse {
+        field: &'static str,
+        source: DigitGridParseError,
+    },
     #[display("failed to apply saved game data: {_0}")]
     GameRestore(GameError),
     #[display("failed to construct selected position: {_0}")]
     PositionNew(PositionNewError),
     #[display("failed to parse selected digit: {_0}")]
     DigitParse(DigitParseError),
+    #[display("failed to restore killer cages: {_0}")]
+    Cage(CageError),
+    #[display("failed to restore region layout: {_0}")]
+    RegionLayout(RegionLayoutError),
+    #[display("failed to restore edge constraints: {_0}")]
+    EdgeConstraint(EdgeConstraintError),
+    #[display("failed to decode the saved game: {_0}")]
+    StateCode(StateCodeError),
 }
 
 impl TryFrom<PersistedState> for AppState {
     type Error = AppStateConversionError;
 
-    fn try_from(value: PersistedState) -> Result<Self, Self::Error> {
-        Ok(AppState::from_parts(
+    fn try_from(mut value: PersistedState) -> Result<Self, Self::Error> {
+        value.migrate_solved_puzzles();
+        let mut state = AppState::from_parts(
             value.game.try_into()?,
             value.selected_cell.map(Position::try_from).transpose()?,
             value.selected_digit.map(Digit::try_from).transpose()?,
@@ -70,56 +235,301 @@ impl TryFrom<PersistedState> for AppState {
             value.new_game_options.into(),
             value.settings.into(),
             value.history.try_into()?,
+        );
+        state.difficulty = value.difficulty.and_then(PuzzleDifficultyDto::into_state);
+        state.seed = value.seed.and_then(|seed| seed.parse().ok());
+        state.generated_with = value.generated_with.map(NewGameOptions::from);
+        state.hint_usage =
+            HintUsage::from_counts(value.hint_usage, value.reveals, value.hint_houses.into());
+        state.hint_heat_map = HintHeatMap::from_counts(&value.hint_heat_map);
+        state.timed = value.timed.map(TimedGame::from);
+        state.race = value.race.map(HotSeatRace::try_from).transpose()?;
+        state.campaign = CampaignProgress::from_solved(value.campaign);
+        state.campaign_puzzle = value
+            .campaign_puzzle
+            .and_then(CampaignPuzzleDto::into_state);
+        state.puzzle_metadata = PuzzleMetadataStore::from_entries(
+            value
+                .puzzle_metadata
+                .into_iter()
+                .filter_map(|(fingerprint, entry)| {
+                    // Entries with unreadable keys cannot be looked up again.
+                    let fingerprint = fingerprint.parse::<PuzzleFingerprint>().ok()?;
+                    Some((fingerprint, entry.into()))
+                })
+                .collect(),
+        );
+        state.tutorial_seen = value.tutorial_seen;
+        state.reviewing = value.reviewing && state.game.is_solved();
+        // Saves from before the flag only know whether the board is solved.
+        state.solved_recorded = value.solved_recorded || state.game.is_solved();
+        state.saved_at = value.saved_at;
+        Ok(state)
+    }
+}
+
+/// Hints and reveals per house, indexed by row, column and box.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[serde(default)]
+pub(crate) struct HouseHintsDto {
+    rows: [u32; 9],
+    cols: [u32; 9],
+    boxes: [u32; 9],
+}
+
+impl Default for HouseHintsDto {
+    fn default() -> Self {
+        HouseHints::default().into()
+    }
+}
+
+impl From<HouseHints> for HouseHintsDto {
+    fn from(value: HouseHints) -> Self {
+        let HouseHints { rows, cols, boxes } = value;
+        Self { rows, cols, boxes }
+    }
+}
+
+impl From<HouseHintsDto> for HouseHints {
+    fn from(value: HouseHintsDto) -> Self {
+        let HouseHintsDto { rows, cols, boxes } = value;
+        Self { rows, cols, boxes }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub(crate) struct TimedGameDto {
+    elapsed_ms: u64,
+    #[serde(default)]
+    par_ms: Option<u64>,
+    #[serde(default)]
+    finished: bool,
+}
+
+impl From<TimedGame> for TimedGameDto {
+    fn from(value: TimedGame) -> Self {
+        let millis = |duration: Duration| u64::try_from(duration.as_millis()).unwrap_or(u64::MAX);
+        Self {
+            elapsed_ms: millis(value.elapsed),
+            par_ms: value.par.map(millis),
+            finished: value.finished,
+        }
+    }
+}
+
+impl From<TimedGameDto> for TimedGame {
+    fn from(value: TimedGameDto) -> Self {
+        Self {
+            elapsed: Duration::from_millis(value.elapsed_ms),
+            par: value.par_ms.map(Duration::from_millis),
+            finished: value.finished,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub(crate) struct CampaignPuzzleDto {
+    pack: String,
+    index: usize,
+}
+
+impl From<CampaignPuzzle> for CampaignPuzzleDto {
+    fn from(value: CampaignPuzzle) -> Self {
+        Self {
+            pack: PACKS[value.pack].id.to_owned(),
+            index: value.index,
+        }
+    }
+}
+
+impl CampaignPuzzleDto {
+    /// Returns `None` for packs or puzzles this build does not bundle.
+    fn into_state(self) -> Option<CampaignPuzzle> {
+        let pack = PuzzlePack::find(&self.pack)?;
+        (self.index < PACKS[pack].puzzle_count()).then_some(CampaignPuzzle {
+            pack,
+            index: self.index,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub(crate) enum PuzzleSourceDto {
+    Generated,
+    Shared,
+    Pack(CampaignPuzzleDto),
+}
+
+impl From<PuzzleSource> for PuzzleSourceDto {
+    fn from(value: PuzzleSource) -> Self {
+        match value {
+            PuzzleSource::Generated => Self::Generated,
+            PuzzleSource::Shared => Self::Shared,
+            PuzzleSource::Pack(puzzle) => Self::Pack(puzzle.into()),
+        }
+    }
+}
+
+impl PuzzleSourceDto {
+    /// Returns `None` for packs or puzzles this build does not bundle.
+    fn into_state(self) -> Option<PuzzleSource> {
+        match self {
+            Self::Generated => Some(PuzzleSource::Generated),
+            Self::Shared => Some(PuzzleSource::Shared),
+            Self::Pack(puzzle) => puzzle.into_state().map(PuzzleSource::Pack),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub(crate) struct PuzzleMetadataDto {
+    #[serde(default)]
+    source: Option<PuzzleSourceDto>,
+    #[serde(default)]
+    difficulty: Option<PuzzleDifficultyDto>,
+    added_on: String,
+    #[serde(default)]
+    solved_on: Option<String>,
+    #[serde(default)]
+    best_time_ms: Option<u64>,
+}
+
+impl PuzzleMetadataDto {
+    /// Merges the same puzzle's entry from another save, keeping this entry's
+    /// source and difficulty when it has them.
+    fn merge(&mut self, other: PuzzleMetadataDto) {
+        if self.source.is_none() {
+            self.source = other.source;
+        }
+        if self.difficulty.is_none() {
+            self.difficulty = other.difficulty;
+        }
+        self.added_on = self.added_on.clone().min(other.added_on);
+        self.solved_on = match (self.solved_on.take(), other.solved_on) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        self.best_time_ms = match (self.best_time_ms, other.best_time_ms) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+    }
+}
+
+impl From<&PuzzleMetadata> for PuzzleMetadataDto {
+    fn from(value: &PuzzleMetadata) -> Self {
+        Self {
+            source: value.source.map(PuzzleSourceDto::from),
+            difficulty: value.difficulty.map(PuzzleDifficultyDto::from),
+            added_on: value.added_on.clone(),
+            solved_on: value.solved_on.clone(),
+            best_time_ms: value
+                .best_time
+                .map(|time| u64::try_from(time.as_millis()).unwrap_or(u64::MAX)),
+        }
+    }
+}
+
+impl From<PuzzleMetadataDto> for PuzzleMetadata {
+    fn from(value: PuzzleMetadataDto) -> Self {
+        Self {
+            source: value.source.and_then(PuzzleSourceDto::into_state),
+            difficulty: value.difficulty.and_then(PuzzleDifficultyDto::into_state),
+            added_on: value.added_on,
+            solved_on: value.solved_on,
+            best_time: value.best_time_ms.map(Duration::from_millis),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub(crate) struct RaceDto {
+    /// Player at the board, 1 or 2.
+    current_player: u8,
+    clocks: [TimedGameDto; 2],
+    /// Board and history of the player who is not at the board.
+    parked_game: GameDto,
+    #[serde(default)]
+    parked_history: HistoryDto,
+}
+
+impl From<&HotSeatRace> for RaceDto {
+    fn from(value: &HotSeatRace) -> Self {
+        Self {
+            current_player: match value.current() {
+                Player::One => 1,
+                Player::Two => 2,
+            },
+            clocks: Player::ALL.map(|player| value.clock(player).into()),
+            parked_game: GameDto::from(value.parked_game()),
+            parked_history: HistoryDto::from(value.parked_history()),
+        }
+    }
+}
+
+impl TryFrom<RaceDto> for HotSeatRace {
+    type Error = AppStateConversionError;
+
+    fn try_from(value: RaceDto) -> Result<Self, Self::Error> {
+        let current = if value.current_player == 2 {
+            Player::Two
+        } else {
+            Player::One
+        };
+        Ok(HotSeatRace::from_parts(
+            current,
+            value.clocks.map(TimedGame::from),
+            value.parked_game.try_into()?,
+            value.parked_history.try_into()?,
         ))
     }
 }
 
+/// A saved game.
+///
+/// New saves hold only the state code (see [`Game::to_state_code`]); the
+/// separate grid and variant fields are read from saves written before it.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub(crate) struct GameDto {
+    /// Absent for uninitialized games.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    code: Option<String>,
+    #[serde(default, skip_serializing)]
     problem: String,
+    #[serde(default, skip_serializing)]
     solution: String,
+    #[serde(default, skip_serializing)]
     filled: String,
-    #[serde(default)]
+    #[serde(default, skip_serializing)]
     notes: [[u16; 9]; 9],
-    #[serde(default)]
+    #[serde(default, skip_serializing)]
     initialized: bool,
+    #[serde(default, skip_serializing)]
+    cages: Vec<CageDto>,
+    #[serde(default, skip_serializing)]
+    regions: Option<String>,
+    #[serde(default, skip_serializing)]
+    edges: Vec<EdgeConstraintDto>,
+    #[serde(default, skip_serializing)]
+    anti_knight: bool,
+    #[serde(default, skip_serializing)]
+    anti_king: bool,
 }
 
 impl From<&Game> for GameDto {
     fn from(value: &Game) -> Self {
-        let mut problem = String::with_capacity(81);
-        let solution = value.solution().to_string();
-        let mut filled = String::with_capacity(81);
-        let mut notes = [[0; 9]; 9];
-
-        for pos in Position::ALL {
-            match value.cell(pos) {
-                CellState::Given(digit) => {
-                    let _ = write!(problem, "{digit}");
-                    filled.push('.');
-                }
-                CellState::Filled(digit) => {
-                    problem.push('.');
-                    let _ = write!(filled, "{digit}");
-                }
-                CellState::Notes(digits) => {
-                    notes[usize::from(pos.row())][usize::from(pos.col())] = digits.bits();
-                    problem.push('.');
-                    filled.push('.');
-                }
-                CellState::Empty => {
-                    problem.push('.');
-                    filled.push('.');
-                }
-            }
-        }
-
         Self {
-            problem,
-            solution,
-            filled,
-            notes,
-            initialized: value.is_initialized(),
+            code: value.is_initialized().then(|| value.to_state_code()),
+            problem: String::new(),
+            solution: String::new(),
+            filled: String::new(),
+            notes: [[0; 9]; 9],
+            initialized: false,
+            cages: Vec::new(),
+            regions: None,
+            edges: Vec::new(),
+            anti_knight: false,
+            anti_king: false,
         }
     }
 }
@@ -134,16 +544,41 @@ impl TryFrom<GameDto> for Game {
     type Error = AppStateConversionError;
 
     fn try_from(value: GameDto) -> Result<Self, Self::Error> {
-        if value.initialized {
-            let problem: DigitGrid = value.problem.parse()?;
-            let solution: DigitGrid = value.solution.parse()?;
-            let filled: DigitGrid = value.filled.parse()?;
-            Ok(Game::from_problem_filled_notes(
-                &problem,
-                &solution,
-                &filled,
-                &value.notes,
-            )?)
+        if let Some(code) = &value.code {
+            Ok(Game::from_state_code(code)?)
+        } else if value.initialized {
+            let problem = parse_grid("problem", &value.problem)?;
+            let solution = parse_grid("solution", &value.solution)?;
+            let filled = parse_grid("filled", &value.filled)?;
+            let cages = value
+                .cages
+                .into_iter()
+                .map(Cage::try_from)
+                .collect::<Result<Vec<_>, _>>()?;
+            let cages = CageSet::from_cages(cages)?;
+            let regions = value
+                .regions
+                .as_deref()
+                .map(str::parse::<RegionLayout>)
+                .transpose()?
+                .unwrap_or_default();
+            let edges = value
+                .edges
+                .into_iter()
+                .map(EdgeConstraint::try_from)
+                .collect::<Result<Vec<_>, _>>()?;
+            let edges = EdgeConstraintSet::from_constraints(edges)?;
+            Ok(
+                Game::from_problem_filled_notes(&problem, &solution, &filled, &value.notes)?
+                    .with_cages(cages)
+                    .with_regions(regions)
+                    .with_edge_constraints(edges)
+                    .with_chess_rules(
+                        ChessRules::new()
+                            .with_anti_knight(value.anti_knight)
+                            .with_anti_king(value.anti_king),
+                    ),
+            )
         } else {
             // Uninitialized games are treated as empty, ignoring problem/solution/notes.
             Ok(Game::new_empty())
@@ -151,12 +586,102 @@ impl TryFrom<GameDto> for Game {
     }
 }
 
+fn parse_grid(field: &'static str, value: &str) -> Result<DigitGrid, AppStateConversionError> {
+    value
+        .parse()
+        .map_err(|source| AppStateConversionError::GameParse { field, source })
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub(crate) struct CageDto {
+    positions: Vec<PositionDto>,
+    sum: u8,
+}
+
+impl From<&Cage> for CageDto {
+    fn from(value: &Cage) -> Self {
+        Self {
+            positions: value.positions().iter().map(PositionDto::from).collect(),
+            sum: value.sum(),
+        }
+    }
+}
+
+impl TryFrom<CageDto> for Cage {
+    type Error = AppStateConversionError;
+
+    fn try_from(value: CageDto) -> Result<Self, Self::Error> {
+        let positions = value
+            .positions
+            .into_iter()
+            .map(Position::try_from)
+            .collect::<Result<_, _>>()?;
+        Ok(Cage::new(positions, value.sum)?)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub(crate) struct EdgeConstraintDto {
+    first: PositionDto,
+    second: PositionDto,
+    kind: EdgeKindDto,
+}
+
+impl From<&EdgeConstraint> for EdgeConstraintDto {
+    fn from(value: &EdgeConstraint) -> Self {
+        Self {
+            first: value.first().into(),
+            second: value.second().into(),
+            kind: value.kind().into(),
+        }
+    }
+}
+
+impl TryFrom<EdgeConstraintDto> for EdgeConstraint {
+    type Error = AppStateConversionError;
+
+    fn try_from(value: EdgeConstraintDto) -> Result<Self, Self::Error> {
+        let first = Position::try_from(value.first)?;
+        let second = Position::try_from(value.second)?;
+        Ok(EdgeConstraint::new(first, second, value.kind.into())?)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub(crate) enum EdgeKindDto {
+    LessThan,
+    GreaterThan,
+    Consecutive,
+}
+
+impl From<EdgeKind> for EdgeKindDto {
+    fn from(value: EdgeKind) -> Self {
+        match value {
+            EdgeKind::LessThan => Self::LessThan,
+            EdgeKind::GreaterThan => Self::GreaterThan,
+            EdgeKind::Consecutive => Self::Consecutive,
+        }
+    }
+}
+
+impl From<EdgeKindDto> for EdgeKind {
+    fn from(value: EdgeKindDto) -> Self {
+        match value {
+            EdgeKindDto::LessThan => Self::LessThan,
+            EdgeKindDto::GreaterThan => Self::GreaterThan,
+            EdgeKindDto::Consecutive => Self::Consecutive,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub(crate) struct HistorySnapshotDto {
     filled: String,
     #[serde(default)]
     notes: [[u16; 9]; 9],
     #[serde(default)]
+    center_notes: [[u16; 9]; 9],
+    #[serde(default)]
     selected_cell: Option<PositionDto>,
 }
 
@@ -165,6 +690,7 @@ impl From<&HistorySnapshot> for HistorySnapshotDto {
         Self {
             filled: value.filled.to_string(),
             notes: value.notes,
+            center_notes: value.center_notes,
             selected_cell: value.selected_at_change.map(PositionDto::from),
         }
     }
@@ -174,10 +700,11 @@ impl TryFrom<HistorySnapshotDto> for HistorySnapshot {
     type Error = AppStateConversionError;
 
     fn try_from(value: HistorySnapshotDto) -> Result<Self, Self::Error> {
-        let filled: DigitGrid = value.filled.parse()?;
+        let filled = parse_grid("history", &value.filled)?;
         Ok(Self {
             filled,
             notes: value.notes,
+            center_notes: value.center_notes,
             selected_at_change: value.selected_cell.map(Position::try_from).transpose()?,
         })
     }
@@ -306,6 +833,16 @@ pub(crate) struct NewGameOptionsDto {
     seed: String,
     #[serde(default = "NewGameOptions::default_max_attempts")]
     max_attempts: usize,
+    #[serde(default)]
+    anti_knight: bool,
+    #[serde(default)]
+    anti_king: bool,
+    #[serde(default)]
+    jigsaw: bool,
+    #[serde(default)]
+    symmetry: String,
+    #[serde(default)]
+    mode: String,
 }
 
 impl Default for NewGameOptionsDto {
@@ -321,6 +858,11 @@ impl From<&NewGameOptions> for NewGameOptionsDto {
             techniques,
             seed,
             max_attempts,
+            anti_knight,
+            anti_king,
+            jigsaw,
+            symmetry,
+            mode,
         } = value;
         let techniques = techniques
             .iter()
@@ -331,6 +873,11 @@ impl From<&NewGameOptions> for NewGameOptionsDto {
             techniques,
             seed: seed.clone(),
             max_attempts: *max_attempts,
+            anti_knight: *anti_knight,
+            anti_king: *anti_king,
+            jigsaw: *jigsaw,
+            symmetry: symmetry.to_string(),
+            mode: mode.label().to_string(),
         }
     }
 }
@@ -349,17 +896,17 @@ impl From<NewGameOptionsDto> for NewGameOptions {
             seed: value.seed,
             techniques: BTreeMap::new(),
             max_attempts: value.max_attempts,
+            anti_knight: value.anti_knight,
+            anti_king: value.anti_king,
+            jigsaw: value.jigsaw,
+            symmetry: value.symmetry.parse().unwrap_or_default(),
+            mode: GameMode::parse(&value.mode).unwrap_or_default(),
         };
         let enabled = value
             .techniques
             .iter()
-            .filter_map(|(id, enabled)| {
-                if *enabled {
-                    technique::find_technique_by_id(id).map(|technique| technique.id())
-                } else {
-                    None
-                }
-            })
+            .filter(|(_id, enabled)| **enabled)
+            .filter_map(|(id, _enabled)| id.parse::<TechniqueId>().ok())
             .collect::<Vec<_>>();
         options.set_enabled_techniques(enabled);
         options.apply_preset(difficulty);
@@ -367,10 +914,44 @@ impl From<NewGameOptionsDto> for NewGameOptions {
     }
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub(crate) enum PuzzleDifficultyDto {
+    Rated(String),
+    BeyondTechniques,
+}
+
+impl From<PuzzleDifficulty> for PuzzleDifficultyDto {
+    fn from(value: PuzzleDifficulty) -> Self {
+        match value {
+            PuzzleDifficulty::Rated(preset) => Self::Rated(preset.label().to_string()),
+            PuzzleDifficulty::BeyondTechniques => Self::BeyondTechniques,
+        }
+    }
+}
+
+impl PuzzleDifficultyDto {
+    /// Returns the state value, or `None` if the preset label is unknown.
+    fn into_state(self) -> Option<PuzzleDifficulty> {
+        match self {
+            Self::Rated(label) => DifficultyPreset::parse(&label).map(PuzzleDifficulty::Rated),
+            Self::BeyondTechniques => Some(PuzzleDifficulty::BeyondTechniques),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(default)]
 pub(crate) struct SettingsDto {
+    language: LanguageDto,
     assist: AssistSettingsDto,
+    assist_profiles: AssistProfilesDto,
+    input: InputSettingsDto,
+    theme: ThemeSettingsDto,
+    display: DisplaySettingsDto,
+    grid_style: GridStyleDto,
+    motion: MotionPreferenceDto,
+    autosave: AutosaveSettingsDto,
+    solver: SolverSettingsDto,
 }
 
 impl Default for SettingsDto {
@@ -382,7 +963,16 @@ impl Default for SettingsDto {
 impl From<&Settings> for SettingsDto {
     fn from(value: &Settings) -> Self {
         Self {
+            language: value.language.into(),
             assist: AssistSettingsDto::from(&value.assist),
+            assist_profiles: AssistProfilesDto::from(&value.assist_profiles),
+            input: InputSettingsDto::from(&value.input),
+            theme: ThemeSettingsDto::from(&value.theme),
+            display: DisplaySettingsDto::from(&value.display),
+            grid_style: value.grid_style.into(),
+            motion: value.motion.into(),
+            autosave: value.autosave.into(),
+            solver: SolverSettingsDto::from(&value.solver),
         }
     }
 }
@@ -396,17 +986,33 @@ impl From<Settings> for SettingsDto {
 impl From<SettingsDto> for Settings {
     fn from(value: SettingsDto) -> Self {
         Self {
+            language: value.language.into(),
             assist: value.assist.into(),
+            assist_profiles: value.assist_profiles.into(),
+            input: value.input.into(),
+            theme: value.theme.into(),
+            display: value.display.into(),
+            grid_style: value.grid_style.into(),
+            motion: value.motion.into(),
+            autosave: value.autosave.into(),
+            solver: value.solver.into(),
         }
     }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(default)]
+#[expect(clippy::struct_excessive_bools)]
 pub(crate) struct AssistSettingsDto {
     pub(crate) block_rule_violations: bool,
+    pub(crate) validation_mode: ValidationModeDto,
     pub(crate) highlight: HighlightSettingsDto,
     pub(crate) notes: NotesSettingsDto,
+    pub(crate) keypad_candidate_counts: bool,
+    pub(crate) lock_completed_digits: bool,
+    pub(crate) candidate_heatmap: bool,
+    pub(crate) deduction_depth_overlay: bool,
+    pub(crate) hint_detail: HintDetailDto,
 }
 
 impl Default for AssistSettingsDto {
@@ -419,8 +1025,14 @@ impl From<&AssistSettings> for AssistSettingsDto {
     fn from(value: &AssistSettings) -> Self {
         Self {
             block_rule_violations: value.block_rule_violations,
+            validation_mode: value.validation_mode.into(),
             highlight: HighlightSettingsDto::from(&value.highlight),
             notes: NotesSettingsDto::from(&value.notes),
+            keypad_candidate_counts: value.keypad_candidate_counts,
+            lock_completed_digits: value.lock_completed_digits,
+            candidate_heatmap: value.candidate_heatmap,
+            deduction_depth_overlay: value.deduction_depth_overlay,
+            hint_detail: value.hint_detail.into(),
         }
     }
 }
@@ -435,8 +1047,65 @@ impl From<AssistSettingsDto> for AssistSettings {
     fn from(value: AssistSettingsDto) -> Self {
         Self {
             block_rule_violations: value.block_rule_violations,
+            validation_mode: value.validation_mode.into(),
             highlight: value.highlight.into(),
             notes: value.notes.into(),
+            keypad_candidate_counts: value.keypad_candidate_counts,
+            lock_completed_digits: value.lock_completed_digits,
+            candidate_heatmap: value.candidate_heatmap,
+            deduction_depth_overlay: value.deduction_depth_overlay,
+            hint_detail: value.hint_detail.into(),
+        }
+    }
+}
+
+/// Assist profiles keyed by difficulty label.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub(crate) struct AssistProfilesDto {
+    pub(crate) enabled: bool,
+    pub(crate) active: Option<String>,
+    pub(crate) saved: BTreeMap<String, AssistSettingsDto>,
+}
+
+impl Default for AssistProfilesDto {
+    fn default() -> Self {
+        Self::from(&AssistProfiles::default())
+    }
+}
+
+impl From<&AssistProfiles> for AssistProfilesDto {
+    fn from(value: &AssistProfiles) -> Self {
+        Self {
+            enabled: value.enabled,
+            active: value.active.map(|preset| preset.label().to_owned()),
+            saved: value
+                .saved
+                .iter()
+                .map(|(preset, assist)| (preset.label().to_owned(), assist.into()))
+                .collect(),
+        }
+    }
+}
+
+impl From<AssistProfiles> for AssistProfilesDto {
+    fn from(value: AssistProfiles) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl From<AssistProfilesDto> for AssistProfiles {
+    fn from(value: AssistProfilesDto) -> Self {
+        Self {
+            enabled: value.enabled,
+            active: value.active.as_deref().and_then(DifficultyPreset::parse),
+            saved: value
+                .saved
+                .into_iter()
+                .filter_map(|(label, assist)| {
+                    Some((DifficultyPreset::parse(&label)?, assist.into()))
+                })
+                .collect(),
         }
     }
 }
@@ -445,10 +1114,16 @@ impl From<AssistSettingsDto> for AssistSettings {
 #[serde(default)]
 #[expect(clippy::struct_excessive_bools)]
 pub(crate) struct HighlightSettingsDto {
+    pub(crate) selected_cell: bool,
     pub(crate) selected_digit: bool,
+    pub(crate) selected_digit_note: bool,
     pub(crate) selected_cell_peer: bool,
     pub(crate) selected_digit_peer: bool,
     pub(crate) conflict: bool,
+    pub(crate) selected_digit_candidate: bool,
+    pub(crate) bivalue_cell: bool,
+    pub(crate) bivalue_source: BivalueSourceDto,
+    pub(crate) intensity: HighlightIntensityDto,
 }
 
 impl Default for HighlightSettingsDto {
@@ -460,10 +1135,16 @@ impl Default for HighlightSettingsDto {
 impl From<&HighlightSettings> for HighlightSettingsDto {
     fn from(value: &HighlightSettings) -> Self {
         Self {
+            selected_cell: value.selected_cell,
             selected_digit: value.selected_digit,
+            selected_digit_note: value.selected_digit_note,
             selected_cell_peer: value.selected_cell_peer,
             selected_digit_peer: value.selected_digit_peer,
             conflict: value.conflict,
+            selected_digit_candidate: value.selected_digit_candidate,
+            bivalue_cell: value.bivalue_cell,
+            bivalue_source: value.bivalue_source.into(),
+            intensity: value.intensity.into(),
         }
     }
 }
@@ -477,10 +1158,82 @@ impl From<HighlightSettings> for HighlightSettingsDto {
 impl From<HighlightSettingsDto> for HighlightSettings {
     fn from(value: HighlightSettingsDto) -> Self {
         Self {
+            selected_cell: value.selected_cell,
             selected_digit: value.selected_digit,
+            selected_digit_note: value.selected_digit_note,
             selected_cell_peer: value.selected_cell_peer,
             selected_digit_peer: value.selected_digit_peer,
             conflict: value.conflict,
+            selected_digit_candidate: value.selected_digit_candidate,
+            bivalue_cell: value.bivalue_cell,
+            bivalue_source: value.bivalue_source.into(),
+            intensity: value.intensity.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[serde(default)]
+pub(crate) struct HighlightIntensityDto {
+    pub(crate) selection: f32,
+    pub(crate) house: f32,
+    pub(crate) same_digit: f32,
+    pub(crate) notes: f32,
+    pub(crate) conflict: f32,
+}
+
+impl Default for HighlightIntensityDto {
+    fn default() -> Self {
+        HighlightIntensity::default().into()
+    }
+}
+
+impl From<HighlightIntensity> for HighlightIntensityDto {
+    fn from(value: HighlightIntensity) -> Self {
+        Self {
+            selection: value.selection,
+            house: value.house,
+            same_digit: value.same_digit,
+            notes: value.notes,
+            conflict: value.conflict,
+        }
+    }
+}
+
+impl From<HighlightIntensityDto> for HighlightIntensity {
+    fn from(value: HighlightIntensityDto) -> Self {
+        HighlightIntensity {
+            selection: value.selection,
+            house: value.house,
+            same_digit: value.same_digit,
+            notes: value.notes,
+            conflict: value.conflict,
+        }
+        .clamped()
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub(crate) enum BivalueSourceDto {
+    #[default]
+    Candidates,
+    Notes,
+}
+
+impl From<BivalueSource> for BivalueSourceDto {
+    fn from(value: BivalueSource) -> Self {
+        match value {
+            BivalueSource::Candidates => Self::Candidates,
+            BivalueSource::Notes => Self::Notes,
+        }
+    }
+}
+
+impl From<BivalueSourceDto> for BivalueSource {
+    fn from(value: BivalueSourceDto) -> Self {
+        match value {
+            BivalueSourceDto::Candidates => Self::Candidates,
+            BivalueSourceDto::Notes => Self::Notes,
         }
     }
 }
@@ -521,3 +1274,604 @@ impl From<NotesSettings> for NotesSettingsDto {
         Self::from(&value)
     }
 }
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub(crate) struct InputSettingsDto {
+    pub(crate) order: InputOrderDto,
+    pub(crate) key_bindings: KeyBindingsDto,
+}
+
+impl Default for InputSettingsDto {
+    fn default() -> Self {
+        InputSettings::default().into()
+    }
+}
+
+impl From<&InputSettings> for InputSettingsDto {
+    fn from(value: &InputSettings) -> Self {
+        Self {
+            order: value.order.into(),
+            key_bindings: KeyBindingsDto::from(&value.key_bindings),
+        }
+    }
+}
+
+impl From<InputSettings> for InputSettingsDto {
+    fn from(value: InputSettings) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl From<InputSettingsDto> for InputSettings {
+    fn from(value: InputSettingsDto) -> Self {
+        Self {
+            order: value.order.into(),
+            key_bindings: value.key_bindings.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub(crate) struct KeyBindingsDto {
+    pub(crate) corner_note: NoteChordDto,
+    pub(crate) center_note: NoteChordDto,
+    /// Rebound commands by [`CommandId::name`]; `None` unbinds the command.
+    pub(crate) commands: BTreeMap<String, Option<KeyTriggerDto>>,
+}
+
+impl Default for KeyBindingsDto {
+    fn default() -> Self {
+        Self::from(&KeyBindings::default())
+    }
+}
+
+impl From<&KeyBindings> for KeyBindingsDto {
+    fn from(value: &KeyBindings) -> Self {
+        Self {
+            corner_note: value.corner_note.into(),
+            center_note: value.center_note.into(),
+            commands: value
+                .commands
+                .iter()
+                .map(|(id, trigger)| (id.name().to_owned(), trigger.map(KeyTriggerDto::from)))
+                .collect(),
+        }
+    }
+}
+
+impl From<KeyBindingsDto> for KeyBindings {
+    fn from(value: KeyBindingsDto) -> Self {
+        let mut bindings = KeyBindings {
+            commands: value
+                .commands
+                .into_iter()
+                .filter_map(|(name, trigger)| {
+                    // Commands and keys this build does not know keep their defaults.
+                    let id = CommandId::from_name(&name)?;
+                    let trigger = match trigger {
+                        Some(trigger) => Some(trigger.into_trigger()?),
+                        None => None,
+                    };
+                    Some((id, trigger))
+                })
+                .collect(),
+            ..KeyBindings::default()
+        };
+        if value.corner_note != value.center_note {
+            bindings.corner_note = value.corner_note.into();
+            bindings.center_note = value.center_note.into();
+        }
+        bindings
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub(crate) enum NoteChordDto {
+    Shift,
+    Command,
+    CommandShift,
+}
+
+impl From<NoteChord> for NoteChordDto {
+    fn from(value: NoteChord) -> Self {
+        match value {
+            NoteChord::Shift => Self::Shift,
+            NoteChord::Command => Self::Command,
+            NoteChord::CommandShift => Self::CommandShift,
+        }
+    }
+}
+
+impl From<NoteChordDto> for NoteChord {
+    fn from(value: NoteChordDto) -> Self {
+        match value {
+            NoteChordDto::Shift => Self::Shift,
+            NoteChordDto::Command => Self::Command,
+            NoteChordDto::CommandShift => Self::CommandShift,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub(crate) struct KeyTriggerDto {
+    /// The key, named as egui names it.
+    key: String,
+    #[serde(default)]
+    command: bool,
+    #[serde(default)]
+    shift: bool,
+}
+
+impl From<KeyTrigger> for KeyTriggerDto {
+    fn from(value: KeyTrigger) -> Self {
+        Self {
+            key: value.key.name().to_owned(),
+            command: value.command,
+            shift: value.shift,
+        }
+    }
+}
+
+impl KeyTriggerDto {
+    fn into_trigger(self) -> Option<KeyTrigger> {
+        let key = Key::from_name(&self.key)?;
+        Some(KeyTrigger::new(key, self.command, self.shift))
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub(crate) struct SolverSettingsDto {
+    /// Technique IDs in preferred order.
+    pub(crate) order: Vec<String>,
+    /// IDs of disabled techniques.
+    pub(crate) disabled: Vec<String>,
+    /// Whether techniques that assume a unique solution are used.
+    pub(crate) assume_unique_solution: bool,
+}
+
+impl From<&SolverSettings> for SolverSettingsDto {
+    fn from(value: &SolverSettings) -> Self {
+        Self {
+            order: value
+                .priority
+                .order()
+                .iter()
+                .map(|id| id.as_str().to_owned())
+                .collect(),
+            disabled: value
+                .priority
+                .disabled()
+                .map(|id| id.as_str().to_owned())
+                .collect(),
+            assume_unique_solution: value.priority.unique_solution_assumed(),
+        }
+    }
+}
+
+impl From<SolverSettingsDto> for SolverSettings {
+    fn from(value: SolverSettingsDto) -> Self {
+        let parse = |ids: Vec<String>| {
+            ids.into_iter()
+                .filter_map(|id| id.parse::<TechniqueId>().ok())
+                .collect::<Vec<_>>()
+        };
+        let mut settings = Self {
+            priority: TechniquePriority::new()
+                .with_order(with_new_techniques(parse(value.order)))
+                .with_unique_solution_assumed(value.assume_unique_solution),
+        };
+        for id in parse(value.disabled) {
+            settings.set_technique_enabled(id, false);
+        }
+        settings
+    }
+}
+
+/// Inserts built-in techniques missing from a saved order before the next
+/// technique that follows them by default, so that techniques added after the
+/// order was saved do not end up last.
+fn with_new_techniques(mut order: Vec<TechniqueId>) -> Vec<TechniqueId> {
+    if order.is_empty() {
+        return order;
+    }
+    for (index, id) in TechniqueId::ALL.into_iter().enumerate().rev() {
+        if order.contains(&id) || technique::by_id(id).is_none() {
+            continue;
+        }
+        let at = TechniqueId::ALL[index + 1..]
+            .iter()
+            .find_map(|next| order.iter().position(|saved| saved == next))
+            .unwrap_or(order.len());
+        order.insert(at, id);
+    }
+    order
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub(crate) enum LanguageDto {
+    #[default]
+    English,
+    Japanese,
+}
+
+impl From<Language> for LanguageDto {
+    fn from(value: Language) -> Self {
+        match value {
+            Language::English => Self::English,
+            Language::Japanese => Self::Japanese,
+        }
+    }
+}
+
+impl From<LanguageDto> for Language {
+    fn from(value: LanguageDto) -> Self {
+        match value {
+            LanguageDto::English => Self::English,
+            LanguageDto::Japanese => Self::Japanese,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub(crate) enum ValidationModeDto {
+    #[default]
+    Rules,
+    Solution,
+}
+
+impl From<ValidationMode> for ValidationModeDto {
+    fn from(value: ValidationMode) -> Self {
+        match value {
+            ValidationMode::Rules => Self::Rules,
+            ValidationMode::Solution => Self::Solution,
+        }
+    }
+}
+
+impl From<ValidationModeDto> for ValidationMode {
+    fn from(value: ValidationModeDto) -> Self {
+        match value {
+            ValidationModeDto::Rules => Self::Rules,
+            ValidationModeDto::Solution => Self::Solution,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub(crate) enum HintDetailDto {
+    #[default]
+    ConditionCells,
+    FocusArea,
+}
+
+impl From<HintDetail> for HintDetailDto {
+    fn from(value: HintDetail) -> Self {
+        match value {
+            HintDetail::ConditionCells => Self::ConditionCells,
+            HintDetail::FocusArea => Self::FocusArea,
+        }
+    }
+}
+
+impl From<HintDetailDto> for HintDetail {
+    fn from(value: HintDetailDto) -> Self {
+        match value {
+            HintDetailDto::ConditionCells => Self::ConditionCells,
+            HintDetailDto::FocusArea => Self::FocusArea,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub(crate) enum InputOrderDto {
+    #[default]
+    CellFirst,
+    DigitFirst,
+}
+
+impl From<InputOrder> for InputOrderDto {
+    fn from(value: InputOrder) -> Self {
+        match value {
+            InputOrder::CellFirst => Self::CellFirst,
+            InputOrder::DigitFirst => Self::DigitFirst,
+        }
+    }
+}
+
+impl From<InputOrderDto> for InputOrder {
+    fn from(value: InputOrderDto) -> Self {
+        match value {
+            InputOrderDto::CellFirst => Self::CellFirst,
+            InputOrderDto::DigitFirst => Self::DigitFirst,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub(crate) enum MotionPreferenceDto {
+    #[default]
+    System,
+    Full,
+    Reduced,
+}
+
+impl From<MotionPreference> for MotionPreferenceDto {
+    fn from(value: MotionPreference) -> Self {
+        match value {
+            MotionPreference::System => Self::System,
+            MotionPreference::Full => Self::Full,
+            MotionPreference::Reduced => Self::Reduced,
+        }
+    }
+}
+
+impl From<MotionPreferenceDto> for MotionPreference {
+    fn from(value: MotionPreferenceDto) -> Self {
+        match value {
+            MotionPreferenceDto::System => Self::System,
+            MotionPreferenceDto::Full => Self::Full,
+            MotionPreferenceDto::Reduced => Self::Reduced,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub(crate) struct DisplaySettingsDto {
+    pub(crate) ui_zoom: f32,
+    pub(crate) digit_size: f32,
+    pub(crate) note_size: f32,
+}
+
+impl Default for DisplaySettingsDto {
+    fn default() -> Self {
+        DisplaySettings::default().into()
+    }
+}
+
+impl From<&DisplaySettings> for DisplaySettingsDto {
+    fn from(value: &DisplaySettings) -> Self {
+        Self {
+            ui_zoom: value.ui_zoom,
+            digit_size: value.digit_size,
+            note_size: value.note_size,
+        }
+    }
+}
+
+impl From<DisplaySettings> for DisplaySettingsDto {
+    fn from(value: DisplaySettings) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl From<DisplaySettingsDto> for DisplaySettings {
+    fn from(value: DisplaySettingsDto) -> Self {
+        DisplaySettings {
+            ui_zoom: value.ui_zoom,
+            digit_size: value.digit_size,
+            note_size: value.note_size,
+        }
+        .clamped()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[serde(default)]
+pub(crate) struct GridStyleDto {
+    pub(crate) notes: NoteLayoutDto,
+    pub(crate) reserve_note_space: bool,
+    pub(crate) givens: GivenStyleDto,
+    pub(crate) coordinates: bool,
+}
+
+impl Default for GridStyleDto {
+    fn default() -> Self {
+        GridStyle::default().into()
+    }
+}
+
+impl From<GridStyle> for GridStyleDto {
+    fn from(value: GridStyle) -> Self {
+        Self {
+            notes: value.notes.into(),
+            reserve_note_space: value.reserve_note_space,
+            givens: value.givens.into(),
+            coordinates: value.coordinates,
+        }
+    }
+}
+
+impl From<GridStyleDto> for GridStyle {
+    fn from(value: GridStyleDto) -> Self {
+        Self {
+            notes: value.notes.into(),
+            reserve_note_space: value.reserve_note_space,
+            givens: value.givens.into(),
+            coordinates: value.coordinates,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub(crate) enum NoteLayoutDto {
+    #[default]
+    MiniGrid,
+    Linear,
+}
+
+impl From<NoteLayout> for NoteLayoutDto {
+    fn from(value: NoteLayout) -> Self {
+        match value {
+            NoteLayout::MiniGrid => Self::MiniGrid,
+            NoteLayout::Linear => Self::Linear,
+        }
+    }
+}
+
+impl From<NoteLayoutDto> for NoteLayout {
+    fn from(value: NoteLayoutDto) -> Self {
+        match value {
+            NoteLayoutDto::MiniGrid => Self::MiniGrid,
+            NoteLayoutDto::Linear => Self::Linear,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub(crate) enum GivenStyleDto {
+    #[default]
+    Plain,
+    Bold,
+    Boxed,
+}
+
+impl From<GivenStyle> for GivenStyleDto {
+    fn from(value: GivenStyle) -> Self {
+        match value {
+            GivenStyle::Plain => Self::Plain,
+            GivenStyle::Bold => Self::Bold,
+            GivenStyle::Boxed => Self::Boxed,
+        }
+    }
+}
+
+impl From<GivenStyleDto> for GivenStyle {
+    fn from(value: GivenStyleDto) -> Self {
+        match value {
+            GivenStyleDto::Plain => Self::Plain,
+            GivenStyleDto::Bold => Self::Bold,
+            GivenStyleDto::Boxed => Self::Boxed,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub(crate) struct AutosaveSettingsDto {
+    pub(crate) interval_secs: u32,
+}
+
+impl Default for AutosaveSettingsDto {
+    fn default() -> Self {
+        AutosaveSettings::default().into()
+    }
+}
+
+impl From<AutosaveSettings> for AutosaveSettingsDto {
+    fn from(value: AutosaveSettings) -> Self {
+        Self {
+            interval_secs: value.interval_secs,
+        }
+    }
+}
+
+impl From<AutosaveSettingsDto> for AutosaveSettings {
+    fn from(value: AutosaveSettingsDto) -> Self {
+        AutosaveSettings {
+            interval_secs: value.interval_secs,
+        }
+        .clamped()
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub(crate) struct ThemeSettingsDto {
+    pub(crate) preset: String,
+    pub(crate) custom: ThemeColorsDto,
+}
+
+impl Default for ThemeSettingsDto {
+    fn default() -> Self {
+        ThemeSettings::default().into()
+    }
+}
+
+impl From<&ThemeSettings> for ThemeSettingsDto {
+    fn from(value: &ThemeSettings) -> Self {
+        Self {
+            preset: value.preset.label().to_string(),
+            custom: ThemeColorsDto::from(&value.custom),
+        }
+    }
+}
+
+impl From<ThemeSettings> for ThemeSettingsDto {
+    fn from(value: ThemeSettings) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl From<ThemeSettingsDto> for ThemeSettings {
+    fn from(value: ThemeSettingsDto) -> Self {
+        Self {
+            preset: ThemePreset::parse(&value.preset).unwrap_or_default(),
+            custom: value.custom.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub(crate) struct ThemeColorsDto {
+    pub(crate) background: [u8; 3],
+    pub(crate) border: [u8; 3],
+    pub(crate) given: [u8; 3],
+    pub(crate) filled: [u8; 3],
+    pub(crate) note: [u8; 3],
+    pub(crate) selected_cell: [u8; 3],
+    pub(crate) selected_digit: [u8; 3],
+    pub(crate) house: [u8; 3],
+    pub(crate) conflict: [u8; 3],
+    pub(crate) hint: [u8; 3],
+}
+
+impl Default for ThemeColorsDto {
+    fn default() -> Self {
+        ThemeSettings::default().custom.into()
+    }
+}
+
+impl From<&ThemeColors> for ThemeColorsDto {
+    fn from(value: &ThemeColors) -> Self {
+        Self {
+            background: value.background,
+            border: value.border,
+            given: value.given,
+            filled: value.filled,
+            note: value.note,
+            selected_cell: value.selected_cell,
+            selected_digit: value.selected_digit,
+            house: value.house,
+            conflict: value.conflict,
+            hint: value.hint,
+        }
+    }
+}
+
+impl From<ThemeColors> for ThemeColorsDto {
+    fn from(value: ThemeColors) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl From<ThemeColorsDto> for ThemeColors {
+    fn from(value: ThemeColorsDto) -> Self {
+        Self {
+            background: value.background,
+            border: value.border,
+            given: value.given,
+            filled: value.filled,
+            note: value.note,
+            selected_cell: value.selected_cell,
+            selected_digit: value.selected_digit,
+            house: value.house,
+            conflict: value.conflict,
+            hint: value.hint,
+        }
+    }
+}
@@ -0,0 +1,184 @@
+//! Journal of unsaved progress for recovering after a crash.
+//!
+//! eframe writes its storage to disk only on the autosave interval and on
+//! shutdown, so a crash loses every change made since the last autosave. The
+//! journal records the full persisted state, including the undo history, on
+//! every change and is cleared once eframe has saved. A journal left over at
+//! startup therefore holds progress that never reached the storage.
+//!
+//! Web builds write each change to local storage right away, so there is
+//! nothing to recover and the journal is disabled there.
+
+use crate::{
+    persistence::dto::{AppStateConversionError, PersistedState},
+    state::AppState,
+};
+
+#[derive(Debug, derive_more::Display, derive_more::Error, derive_more::From)]
+pub(crate) enum JournalError {
+    #[display("invalid journal: {_0}")]
+    Json(serde_json::Error),
+    #[display("failed to restore the journaled state: {_0}")]
+    Conversion(AppStateConversionError),
+}
+
+#[must_use]
+pub(crate) fn encode_journal(state: &AppState) -> String {
+    serde_json::to_string(&PersistedState::from(state)).expect("state DTOs always serialize")
+}
+
+pub(crate) fn decode_journal(contents: &[u8]) -> Result<AppState, JournalError> {
+    let state: PersistedState = serde_json::from_slice(contents)?;
+    Ok(state.try_into()?)
+}
+
+/// Returns the progress left over by a session that ended without saving.
+pub(crate) fn load() -> Option<AppState> {
+    let contents = platform::read()?;
+    decode_journal(&contents)
+        .inspect_err(|err| log::warn!("failed to recover unsaved progress: {err}"))
+        .ok()
+}
+
+/// Records `state` as the latest unsaved progress.
+pub(crate) fn write(state: &AppState) {
+    if platform::ENABLED {
+        platform::write(encode_journal(state).as_bytes());
+    }
+}
+
+/// Discards the journal once its progress has been saved or declined.
+pub(crate) fn clear() {
+    platform::remove();
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod platform {
+    use std::{fs, io, path::PathBuf};
+
+    pub(super) const ENABLED: bool = true;
+
+    const FILE_NAME: &str = "journal.json";
+
+    fn path() -> Option<PathBuf> {
+        eframe::storage_dir(crate::APP_ID).map(|dir| dir.join(FILE_NAME))
+    }
+
+    pub(super) fn read() -> Option<Vec<u8>> {
+        let path = path()?;
+        fs::read(&path)
+            .inspect_err(|err| {
+                if err.kind() != io::ErrorKind::NotFound {
+                    log::warn!("failed to read {}: {err}", path.display());
+                }
+            })
+            .ok()
+    }
+
+    /// Writes through a temporary file, so a crash mid-write keeps the previous journal.
+    pub(super) fn write(contents: &[u8]) {
+        let Some(path) = path() else {
+            return;
+        };
+        let temp = path.with_extension("json.tmp");
+        let result = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|()| fs::write(&temp, contents))
+            .and_then(|()| fs::rename(&temp, &path));
+        if let Err(err) = result {
+            log::warn!("failed to write {}: {err}", path.display());
+        }
+    }
+
+    pub(super) fn remove() {
+        let Some(path) = path() else {
+            return;
+        };
+        if let Err(err) = fs::remove_file(&path)
+            && err.kind() != io::ErrorKind::NotFound
+        {
+            log::warn!("failed to remove {}: {err}", path.display());
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod platform {
+    pub(super) const ENABLED: bool = false;
+
+    pub(super) fn read() -> Option<Vec<u8>> {
+        None
+    }
+
+    pub(super) fn write(_contents: &[u8]) {}
+
+    pub(super) fn remove() {}
+}
+
+#[cfg(test)]
+mod tests {
+    use numelace_core::{Digit, DigitGrid, Position};
+    use numelace_game::{Game, InputDigitOptions};
+
+    use super::{JournalError, decode_journal, encode_journal};
+    use crate::state::AppState;
+
+    fn game() -> Game {
+        let solution: DigitGrid =
+            "185362947793148526246795183564239871931874265827516394318427659672951438459683712"
+                .parse()
+                .unwrap();
+        let mut problem = solution.clone();
+        problem.set(Position::new(0, 1), None);
+        let empty = DigitGrid::new();
+        Game::from_problem_filled_notes(&problem, &solution, &empty, &[[0; 9]; 9]).unwrap()
+    }
+
+    #[test]
+    fn journal_keeps_undo_history() {
+        let mut state = AppState::new(game());
+        let pos = Position::new(0, 1);
+        state.set_selected_cell(pos);
+        state
+            .game
+            .set_digit(pos, Digit::D8, &InputDigitOptions::default())
+            .unwrap();
+        state.push_history();
+
+        let mut restored = decode_journal(encode_journal(&state).as_bytes()).unwrap();
+
+        assert_eq!(restored.game.cell(pos).as_digit(), Some(Digit::D8));
+        assert!(restored.undo());
+        assert_eq!(restored.game.cell(pos).as_digit(), None);
+    }
+
+    #[test]
+    fn journal_keeps_the_review() {
+        let mut state = AppState::new(game());
+        state
+            .game
+            .set_digit(
+                Position::new(0, 1),
+                Digit::D8,
+                &InputDigitOptions::default(),
+            )
+            .unwrap();
+        assert!(state.game.is_solved());
+        state.reviewing = true;
+        state.solved_recorded = true;
+
+        let restored = decode_journal(encode_journal(&state).as_bytes()).unwrap();
+
+        assert!(restored.reviewing);
+        assert!(restored.solved_recorded);
+    }
+
+    #[test]
+    fn rejects_corrupt_journal() {
+        assert!(matches!(
+            decode_journal(br#"{"game":"#),
+            Err(JournalError::Json(_))
+        ));
+    }
+}
@@ -1,2 +1,5 @@
 pub(crate) mod dto;
+pub(crate) mod journal;
+pub(crate) mod settings_file;
 pub(crate) mod storage;
+pub(crate) mod sync;
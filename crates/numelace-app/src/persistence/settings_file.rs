@@ -0,0 +1,221 @@
+//! Settings files for moving configuration between machines.
+//!
+//! A file wraps the same [`SettingsDto`] used for app storage in an envelope
+//! carrying a format marker and a version. Files from older versions are
+//! upgraded step by step before deserialization; files from newer versions
+//! are rejected rather than silently dropping settings this build does not
+//! know.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{persistence::dto::SettingsDto, state::Settings};
+
+/// File name suggested when exporting settings.
+pub(crate) const SETTINGS_FILE_NAME: &str = "numelace-settings.json";
+
+const FORMAT: &str = "numelace-settings";
+const CURRENT_VERSION: u32 = 1;
+
+/// Upgrade steps for the `settings` object; entry `i` turns version `i + 1`
+/// into version `i + 2`.
+///
+/// Additive changes need no step, since missing fields fall back to their
+/// defaults. Add a step when a field is renamed or its meaning changes, and
+/// bump [`CURRENT_VERSION`].
+const MIGRATIONS: &[fn(&mut Value)] = &[];
+
+const _: () = assert!(MIGRATIONS.len() == CURRENT_VERSION as usize - 1);
+
+#[derive(Debug, Serialize, Deserialize)]
+struct SettingsFile<S> {
+    format: String,
+    version: u32,
+    settings: S,
+}
+
+#[derive(Debug, derive_more::Display, derive_more::Error, derive_more::From)]
+pub(crate) enum SettingsFileError {
+    #[display("invalid settings file: {_0}")]
+    Json(serde_json::Error),
+    #[display("not a Numelace settings file")]
+    UnknownFormat,
+    #[display("settings file version {version} is newer than this app supports")]
+    #[from(skip)]
+    UnsupportedVersion {
+        #[error(not(source))]
+        version: u32,
+    },
+}
+
+/// Serializes `settings` as a pretty-printed settings file.
+#[must_use]
+pub(crate) fn encode_settings(settings: &Settings) -> String {
+    let file = SettingsFile {
+        format: FORMAT.to_owned(),
+        version: CURRENT_VERSION,
+        settings: SettingsDto::from(settings),
+    };
+    serde_json::to_string_pretty(&file).expect("settings DTOs always serialize")
+}
+
+/// Parses a settings file, upgrading it from older versions.
+pub(crate) fn decode_settings(contents: &[u8]) -> Result<Settings, SettingsFileError> {
+    let file: SettingsFile<Value> = serde_json::from_slice(contents)?;
+    if file.format != FORMAT || file.version == 0 {
+        return Err(SettingsFileError::UnknownFormat);
+    }
+    if file.version > CURRENT_VERSION {
+        return Err(SettingsFileError::UnsupportedVersion {
+            version: file.version,
+        });
+    }
+
+    let mut settings = file.settings;
+    for migrate in &MIGRATIONS[file.version as usize - 1..] {
+        migrate(&mut settings);
+    }
+    let settings: SettingsDto = serde_json::from_value(settings)?;
+    Ok(settings.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use eframe::egui::Key;
+    use numelace_solver::technique::TechniqueId;
+
+    use super::{SettingsFileError, decode_settings, encode_settings};
+    use crate::{
+        action::NoteKind,
+        state::{
+            CommandId, GivenStyle, GridStyle, HighlightIntensity, HintDetail, KeyTrigger, Language,
+            NoteChord, NoteLayout, Settings, ThemePreset, ValidationMode,
+        },
+    };
+
+    #[test]
+    fn settings_round_trip() {
+        let mut settings = Settings {
+            language: Language::Japanese,
+            ..Settings::default()
+        };
+        settings.theme.preset = ThemePreset::Dark;
+        settings.assist.candidate_heatmap = true;
+        settings.assist.hint_detail = HintDetail::FocusArea;
+        settings.assist.validation_mode = ValidationMode::Solution;
+        settings.solver.priority.set_unique_solution_assumed(true);
+        settings.grid_style = GridStyle {
+            notes: NoteLayout::Linear,
+            reserve_note_space: true,
+            givens: GivenStyle::Boxed,
+            coordinates: true,
+        };
+        settings.assist.highlight.selected_digit_note = false;
+        settings.assist.highlight.intensity.house = 0.5;
+
+        let decoded = decode_settings(encode_settings(&settings).as_bytes()).unwrap();
+
+        assert_eq!(decoded.language, Language::Japanese);
+        assert_eq!(decoded.theme, settings.theme);
+        assert!(decoded.assist.candidate_heatmap);
+        assert_eq!(decoded.assist.hint_detail, HintDetail::FocusArea);
+        assert_eq!(decoded.assist.validation_mode, ValidationMode::Solution);
+        assert_eq!(decoded.solver, settings.solver);
+        assert_eq!(decoded.grid_style, settings.grid_style);
+        assert!(!decoded.assist.highlight.selected_digit_note);
+        assert_eq!(
+            decoded.assist.highlight.intensity,
+            settings.assist.highlight.intensity
+        );
+    }
+
+    #[test]
+    fn key_bindings_round_trip() {
+        let mut settings = Settings::default();
+        let bindings = &mut settings.input.key_bindings;
+        bindings.set_note_chord(NoteKind::Center, NoteChord::CommandShift);
+        bindings
+            .commands
+            .insert(CommandId::Undo, Some(KeyTrigger::command(Key::U)));
+        bindings.commands.insert(CommandId::Redo, None);
+
+        let decoded = decode_settings(encode_settings(&settings).as_bytes()).unwrap();
+
+        assert_eq!(decoded.input.key_bindings, settings.input.key_bindings);
+    }
+
+    #[test]
+    fn unknown_key_bindings_keep_their_defaults() {
+        let contents = br#"{"format":"numelace-settings","version":1,"settings":{"input":{"key_bindings":{"corner_note":"Command","center_note":"Command","commands":{"undo":{"key":"NoSuchKey"},"no_such_command":null,"redo":null}}}}}"#;
+
+        let bindings = decode_settings(contents).unwrap().input.key_bindings;
+
+        assert_eq!(bindings.corner_note, NoteChord::Shift);
+        assert_eq!(bindings.center_note, NoteChord::Command);
+        assert_eq!(bindings.commands.len(), 1);
+        assert_eq!(bindings.commands[&CommandId::Redo], None);
+    }
+
+    #[test]
+    fn highlight_intensity_is_clamped_on_load() {
+        let contents = br#"{"format":"numelace-settings","version":1,"settings":{"assist":{"highlight":{"intensity":{"house":0.0,"conflict":3.0}}}}}"#;
+
+        let intensity = decode_settings(contents)
+            .unwrap()
+            .assist
+            .highlight
+            .intensity;
+
+        assert_eq!(intensity.house, *HighlightIntensity::RANGE.start());
+        assert_eq!(intensity.conflict, *HighlightIntensity::RANGE.end());
+        assert_eq!(intensity.selection, 1.0);
+    }
+
+    #[test]
+    fn missing_fields_fall_back_to_defaults() {
+        let contents =
+            br#"{"format":"numelace-settings","version":1,"settings":{"language":"Japanese"}}"#;
+
+        let decoded = decode_settings(contents).unwrap();
+
+        assert_eq!(decoded.language, Language::Japanese);
+        assert_eq!(decoded.theme, Settings::default().theme);
+    }
+
+    #[test]
+    fn saved_technique_order_keeps_new_techniques_in_place() {
+        let contents = br#"{"format":"numelace-settings","version":1,"settings":{"solver":{"order":["hidden_single","naked_single","locked_candidates"]}}}"#;
+
+        let decoded = decode_settings(contents).unwrap();
+
+        assert_eq!(
+            decoded.solver.technique_order()[..4],
+            [
+                TechniqueId::HiddenSingle,
+                TechniqueId::FullHouse,
+                TechniqueId::NakedSingle,
+                TechniqueId::LockedCandidates,
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_foreign_and_future_files() {
+        let foreign = br#"{"format":"other","version":1,"settings":{}}"#;
+        assert!(matches!(
+            decode_settings(foreign),
+            Err(SettingsFileError::UnknownFormat)
+        ));
+
+        let future = br#"{"format":"numelace-settings","version":99,"settings":{}}"#;
+        assert!(matches!(
+            decode_settings(future),
+            Err(SettingsFileError::UnsupportedVersion { version: 99 })
+        ));
+
+        assert!(matches!(
+            decode_settings(b"not json"),
+            Err(SettingsFileError::Json(_))
+        ));
+    }
+}
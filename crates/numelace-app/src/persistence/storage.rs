@@ -6,7 +6,10 @@ const STATE_KEY: &str = "numelace.state";
 
 pub(crate) fn load_state(storage: &dyn Storage) -> Option<AppState> {
     let state = eframe::get_value::<PersistedState>(storage, STATE_KEY)?;
-    state.try_into().ok()
+    state
+        .try_into()
+        .inspect_err(|err| log::warn!("failed to restore the saved state: {err}"))
+        .ok()
 }
 
 pub(crate) fn save_state(storage: &mut dyn Storage, state: &AppState) {
@@ -0,0 +1,112 @@
+//! Reference backend storing blobs as files in a directory.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use super::{PutOutcome, SyncBackend, SyncBlob, SyncError, SyncVersion};
+
+/// Stores each blob in `<dir>/<key>.sync`, with its version on the first line.
+///
+/// Versions count writes, so they are compared against what the file holds
+/// right before writing. The directory can be shared by a file sync service;
+/// two devices writing within the same sync interval are not detected.
+#[derive(Debug)]
+pub(crate) struct FileSyncBackend {
+    dir: PathBuf,
+}
+
+impl FileSyncBackend {
+    #[must_use]
+    pub(crate) fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{key}.sync"))
+    }
+}
+
+impl SyncBackend for FileSyncBackend {
+    fn get(&self, key: &str) -> Result<Option<SyncBlob>, SyncError> {
+        let contents = match fs::read(self.path(key)) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+        let Some(newline) = contents.iter().position(|&byte| byte == b'\n') else {
+            return Err(SyncError::Corrupt(format!(
+                "{} has no version line",
+                self.path(key).display()
+            )));
+        };
+        let version = String::from_utf8_lossy(&contents[..newline]).into_owned();
+        Ok(Some(SyncBlob {
+            version: SyncVersion(version),
+            contents: contents[newline + 1..].to_vec(),
+        }))
+    }
+
+    fn put(
+        &self,
+        key: &str,
+        contents: &[u8],
+        expected: Option<&SyncVersion>,
+    ) -> Result<PutOutcome, SyncError> {
+        // A removed blob has nothing to lose, so it never conflicts.
+        let current = self.get(key)?;
+        if let Some(current) = &current
+            && Some(&current.version) != expected
+        {
+            return Ok(PutOutcome::Conflict(current.clone()));
+        }
+        let count = current.map_or(0, |blob| blob.version.0.parse::<u64>().unwrap_or(0));
+        let version = SyncVersion((count + 1).to_string());
+        let mut file = format!("{}\n", version.0).into_bytes();
+        file.extend_from_slice(contents);
+        write_atomically(&self.path(key), &file)?;
+        Ok(PutOutcome::Stored(version))
+    }
+}
+
+/// Writes through a temporary file, so readers never see a partial blob.
+fn write_atomically(path: &Path, contents: &[u8]) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let temp = path.with_extension("sync.tmp");
+    fs::write(&temp, contents)?;
+    fs::rename(&temp, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, process};
+
+    use super::FileSyncBackend;
+    use crate::persistence::sync::{PutOutcome, SyncBackend};
+
+    #[test]
+    fn writes_are_conditional_on_the_stored_version() {
+        let dir = env::temp_dir().join(format!("numelace-sync-test-{}", process::id()));
+        let backend = FileSyncBackend::new(dir.clone());
+        assert!(backend.get("state").unwrap().is_none());
+
+        let PutOutcome::Stored(first) = backend.put("state", b"one", None).unwrap() else {
+            panic!("the first write has nothing to conflict with");
+        };
+        let PutOutcome::Stored(second) = backend.put("state", b"two", Some(&first)).unwrap() else {
+            panic!("the write builds on the stored version");
+        };
+        let PutOutcome::Conflict(remote) = backend.put("state", b"three", Some(&first)).unwrap()
+        else {
+            panic!("the write builds on an outdated version");
+        };
+        assert_eq!(remote.version, second);
+        assert_eq!(remote.contents, b"two");
+        assert_eq!(backend.get("state").unwrap().unwrap().contents, b"two");
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
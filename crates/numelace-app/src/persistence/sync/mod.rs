@@ -0,0 +1,531 @@
+//! Syncing the saved state across devices.
+//!
+//! A [`SyncBackend`] stores versioned blobs by key. Writes are conditional on
+//! the version last seen, so two devices saving at once cannot overwrite each
+//! other unnoticed: the second write fails with the other device's blob, and
+//! a conflict resolution hook merges the two before retrying.
+//!
+//! The app syncs the same [`PersistedState`] it keeps in eframe storage, so
+//! the board, settings and statistics all follow the player. When two states
+//! meet, on a pull or a conflicting write, the board saved last wins and the
+//! progress of both sides is merged with [`PersistedState::merge_progress`].
+//! The pull at startup runs on the sync thread, and its result is merged into
+//! the running app once it arrives.
+//!
+//! Sync is configured through environment variables and only works in native
+//! builds:
+//!
+//! - `NUMELACE_SYNC_DIR=<dir>` syncs through a directory, e.g. one shared by
+//!   a file sync service.
+//! - `NUMELACE_SYNC_URL=<url>` syncs to a `WebDAV` collection, with an optional
+//!   `Authorization` header value in `NUMELACE_SYNC_AUTH`. This needs the
+//!   `webdav` feature.
+
+use std::{
+    env, io,
+    path::PathBuf,
+    sync::mpsc::{self, Receiver, Sender},
+    task::Waker,
+    thread::{self, JoinHandle},
+};
+
+use crate::{
+    persistence::dto::{AppStateConversionError, PersistedState},
+    state::AppState,
+};
+
+pub(crate) use self::file::FileSyncBackend;
+#[cfg(all(feature = "webdav", not(target_arch = "wasm32")))]
+pub(crate) use self::webdav::WebDavSyncBackend;
+
+mod file;
+#[cfg(all(feature = "webdav", not(target_arch = "wasm32")))]
+mod webdav;
+
+/// Key of the synced app state.
+const STATE_KEY: &str = "state";
+
+/// Conflicts resolved in a row before giving up on a write.
+const MAX_CONFLICT_RETRIES: usize = 3;
+
+/// Opaque version of a stored blob, changed by every write.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SyncVersion(pub(crate) String);
+
+#[derive(Debug, Clone)]
+pub(crate) struct SyncBlob {
+    pub(crate) version: SyncVersion,
+    pub(crate) contents: Vec<u8>,
+}
+
+/// Result of a conditional write.
+#[derive(Debug)]
+pub(crate) enum PutOutcome {
+    Stored(SyncVersion),
+    /// The stored blob changed since the expected version; nothing was written.
+    Conflict(SyncBlob),
+}
+
+#[derive(Debug, derive_more::Display, derive_more::Error, derive_more::From)]
+pub(crate) enum SyncError {
+    #[display("sync I/O error: {_0}")]
+    Io(io::Error),
+    #[cfg(all(feature = "webdav", not(target_arch = "wasm32")))]
+    #[display("sync request failed: {_0}")]
+    #[from(skip)]
+    Request(#[error(not(source))] String),
+    #[display("invalid synced data: {_0}")]
+    Json(serde_json::Error),
+    #[display("corrupt synced data: {_0}")]
+    #[from(skip)]
+    Corrupt(#[error(not(source))] String),
+    #[display("sync kept conflicting after {MAX_CONFLICT_RETRIES} retries")]
+    TooManyConflicts,
+}
+
+/// Storage for versioned blobs shared between devices.
+pub(crate) trait SyncBackend: Send {
+    /// Returns the blob stored under `key`, if any.
+    fn get(&self, key: &str) -> Result<Option<SyncBlob>, SyncError>;
+
+    /// Stores `contents` under `key` if the stored version is still
+    /// `expected`, where `None` expects no blob at all.
+    fn put(
+        &self,
+        key: &str,
+        contents: &[u8],
+        expected: Option<&SyncVersion>,
+    ) -> Result<PutOutcome, SyncError>;
+}
+
+/// Writes `contents` under `key`, calling `resolve(local, remote)` to merge
+/// with a blob written by another device since `base`.
+///
+/// Returns the version written.
+pub(crate) fn put_resolving(
+    backend: &dyn SyncBackend,
+    key: &str,
+    contents: Vec<u8>,
+    base: Option<&SyncVersion>,
+    mut resolve: impl FnMut(&[u8], &[u8]) -> Result<Vec<u8>, SyncError>,
+) -> Result<SyncVersion, SyncError> {
+    let mut contents = contents;
+    let mut expected = base.cloned();
+    for _ in 0..=MAX_CONFLICT_RETRIES {
+        match backend.put(key, &contents, expected.as_ref())? {
+            PutOutcome::Stored(version) => return Ok(version),
+            PutOutcome::Conflict(remote) => {
+                contents = resolve(&contents, &remote.contents)?;
+                expected = Some(remote.version);
+            }
+        }
+    }
+    Err(SyncError::TooManyConflicts)
+}
+
+/// Resolves a conflict between two synced app states.
+fn merge_states(local: &[u8], remote: &[u8]) -> Result<Vec<u8>, SyncError> {
+    let merged = resolve_states(
+        serde_json::from_slice(local)?,
+        serde_json::from_slice(remote)?,
+    );
+    Ok(serde_json::to_vec(&merged)?)
+}
+
+/// Keeps the board of the state saved last, `local` on a tie, and merges the
+/// progress of the other into it.
+fn resolve_states(local: PersistedState, remote: PersistedState) -> PersistedState {
+    let (mut newer, older) = if remote.saved_at() > local.saved_at() {
+        (remote, local)
+    } else {
+        (local, remote)
+    };
+    newer.merge_progress(older);
+    newer
+}
+
+/// The app state after merging in a pulled state.
+#[derive(Debug)]
+pub(crate) enum Pulled {
+    /// The synced board was saved later and replaces the local one.
+    Remote(Box<AppState>),
+    /// The local board was saved later and stays; only the progress of this
+    /// state is new.
+    Local(Box<AppState>),
+}
+
+/// Merges the pulled `remote` state with the running `local` one.
+pub(crate) fn resolve_pulled(
+    local: &AppState,
+    remote: PersistedState,
+) -> Result<Pulled, AppStateConversionError> {
+    let remote_is_newer = remote.saved_at() > local.saved_at;
+    let state = AppState::try_from(resolve_states(PersistedState::from(local), remote))?;
+    Ok(if remote_is_newer {
+        Pulled::Remote(Box::new(state))
+    } else {
+        Pulled::Local(Box::new(state))
+    })
+}
+
+/// Where to sync, read from the environment.
+#[derive(Debug, Default)]
+pub(crate) enum SyncOptions {
+    #[default]
+    Disabled,
+    Directory(PathBuf),
+    #[cfg(all(feature = "webdav", not(target_arch = "wasm32")))]
+    WebDav {
+        url: String,
+        authorization: Option<String>,
+    },
+}
+
+impl SyncOptions {
+    #[must_use]
+    pub(crate) fn from_env() -> Self {
+        if let Some(dir) = env::var_os("NUMELACE_SYNC_DIR") {
+            return Self::Directory(dir.into());
+        }
+        if let Ok(url) = env::var("NUMELACE_SYNC_URL") {
+            #[cfg(all(feature = "webdav", not(target_arch = "wasm32")))]
+            return Self::WebDav {
+                url,
+                authorization: env::var("NUMELACE_SYNC_AUTH").ok(),
+            };
+            #[cfg(not(all(feature = "webdav", not(target_arch = "wasm32"))))]
+            log::error!("syncing to {url} needs a build with the `webdav` feature");
+        }
+        Self::Disabled
+    }
+
+    fn backend(self) -> Option<Box<dyn SyncBackend>> {
+        match self {
+            Self::Disabled => None,
+            Self::Directory(dir) => Some(Box::new(FileSyncBackend::new(dir))),
+            #[cfg(all(feature = "webdav", not(target_arch = "wasm32")))]
+            Self::WebDav { url, authorization } => {
+                Some(Box::new(WebDavSyncBackend::new(&url, authorization)))
+            }
+        }
+    }
+}
+
+/// Sync of the app state through a backend.
+pub(crate) struct StateSync {
+    backend: Box<dyn SyncBackend>,
+    /// Version of the last blob read or written, the base of the next write.
+    version: Option<SyncVersion>,
+}
+
+impl StateSync {
+    #[must_use]
+    pub(crate) fn new(options: SyncOptions) -> Option<Self> {
+        Some(Self {
+            backend: options.backend()?,
+            version: None,
+        })
+    }
+
+    /// Fetches the synced state, which the next push builds on.
+    pub(crate) fn pull(&mut self) -> Result<Option<PersistedState>, SyncError> {
+        let Some(blob) = self.backend.get(STATE_KEY)? else {
+            return Ok(None);
+        };
+        self.version = Some(blob.version);
+        Ok(Some(serde_json::from_slice(&blob.contents)?))
+    }
+
+    pub(crate) fn push(&mut self, state: &PersistedState) -> Result<(), SyncError> {
+        let contents = serde_json::to_vec(state)?;
+        let version = put_resolving(
+            self.backend.as_ref(),
+            STATE_KEY,
+            contents,
+            self.version.as_ref(),
+            merge_states,
+        )?;
+        self.version = Some(version);
+        Ok(())
+    }
+
+    /// Moves the sync to a background thread, so slow backends stall neither
+    /// startup nor saving.
+    ///
+    /// The thread first pulls the synced state, which
+    /// [`SyncWorker::take_pulled`] hands over, waking `waker` once it arrives.
+    /// States queued meanwhile are pushed after the pull.
+    #[must_use]
+    pub(crate) fn spawn(mut self, waker: Waker) -> SyncWorker {
+        let (sender, receiver) = mpsc::channel::<PersistedState>();
+        let (pulled_sender, pulled) = mpsc::channel();
+        let thread = thread::spawn(move || {
+            match self.pull() {
+                Ok(Some(state)) => {
+                    let _ = pulled_sender.send(state);
+                    waker.wake();
+                }
+                Ok(None) => {}
+                Err(err) => log::warn!("failed to fetch the synced state: {err}"),
+            }
+            drop(pulled_sender);
+            while let Ok(mut state) = receiver.recv() {
+                // Only the latest of the states queued meanwhile matters.
+                while let Ok(newer) = receiver.try_recv() {
+                    state = newer;
+                }
+                if let Err(err) = self.push(&state) {
+                    log::warn!("failed to sync the saved state: {err}");
+                }
+            }
+        });
+        SyncWorker {
+            sender: Some(sender),
+            pulled: Some(pulled),
+            thread: Some(thread),
+        }
+    }
+}
+
+/// Handle to the background thread pushing saved states.
+#[derive(Debug)]
+pub(crate) struct SyncWorker {
+    sender: Option<Sender<PersistedState>>,
+    /// Receives the state pulled at startup, until it is taken.
+    pulled: Option<Receiver<PersistedState>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl SyncWorker {
+    /// Returns the state pulled at startup, once, when it has arrived.
+    pub(crate) fn take_pulled(&mut self) -> Option<PersistedState> {
+        let pulled = self.pulled.as_ref()?;
+        match pulled.try_recv() {
+            Ok(state) => {
+                self.pulled = None;
+                Some(state)
+            }
+            Err(mpsc::TryRecvError::Empty) => None,
+            Err(mpsc::TryRecvError::Disconnected) => {
+                self.pulled = None;
+                None
+            }
+        }
+    }
+
+    pub(crate) fn queue(&self, state: PersistedState) {
+        if let Some(sender) = &self.sender {
+            // The thread only stops once the sender is dropped.
+            let _ = sender.send(state);
+        }
+    }
+
+    /// Waits for the queued states to be pushed, so the last save before
+    /// shutdown is not lost.
+    pub(crate) fn finish(&mut self) {
+        self.sender = None;
+        if let Some(thread) = self.thread.take()
+            && thread.join().is_err()
+        {
+            log::error!("the sync thread panicked");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, sync::Mutex, time::Duration};
+
+    use numelace_core::{DigitPositions, Position, PuzzleFingerprint};
+    use numelace_game::Game;
+
+    use super::{
+        Pulled, PutOutcome, SyncBackend, SyncBlob, SyncError, SyncVersion, merge_states,
+        put_resolving, resolve_pulled,
+    };
+    use crate::{
+        persistence::dto::PersistedState,
+        state::{AppState, PuzzleSource},
+    };
+
+    /// In-memory backend counting versions up from 1.
+    #[derive(Default)]
+    struct MemoryBackend {
+        blobs: Mutex<BTreeMap<String, (u64, Vec<u8>)>>,
+    }
+
+    impl SyncBackend for MemoryBackend {
+        fn get(&self, key: &str) -> Result<Option<SyncBlob>, SyncError> {
+            Ok(self
+                .blobs
+                .lock()
+                .unwrap()
+                .get(key)
+                .map(|(version, contents)| SyncBlob {
+                    version: SyncVersion(version.to_string()),
+                    contents: contents.clone(),
+                }))
+        }
+
+        fn put(
+            &self,
+            key: &str,
+            contents: &[u8],
+            expected: Option<&SyncVersion>,
+        ) -> Result<PutOutcome, SyncError> {
+            let current = self.get(key)?;
+            if current.as_ref().map(|blob| &blob.version) != expected {
+                return Ok(PutOutcome::Conflict(current.unwrap()));
+            }
+            let version = current.map_or(1, |blob| blob.version.0.parse::<u64>().unwrap() + 1);
+            self.blobs
+                .lock()
+                .unwrap()
+                .insert(key.to_owned(), (version, contents.to_vec()));
+            Ok(PutOutcome::Stored(SyncVersion(version.to_string())))
+        }
+    }
+
+    #[test]
+    fn conflicting_writes_are_merged() {
+        let backend = MemoryBackend::default();
+        let base =
+            put_resolving(&backend, "k", b"a".to_vec(), None, |_, _| unreachable!()).unwrap();
+        // Another device writes on top of the same base.
+        put_resolving(
+            &backend,
+            "k",
+            b"b".to_vec(),
+            Some(&base),
+            |_, _| unreachable!(),
+        )
+        .unwrap();
+
+        let version = put_resolving(
+            &backend,
+            "k",
+            b"c".to_vec(),
+            Some(&base),
+            |local, remote| Ok([remote, local].concat()),
+        )
+        .unwrap();
+
+        let stored = backend.get("k").unwrap().unwrap();
+        assert_eq!(stored.version, version);
+        assert_eq!(stored.contents, b"bc");
+    }
+
+    /// Two states saved at the given times, each with its own selected cell
+    /// and a hint only it recorded.
+    fn diverged_states(local_saved_at: u64, remote_saved_at: u64) -> (AppState, AppState) {
+        let mut local = AppState::new(Game::new_empty());
+        local.set_selected_cell(Position::new(0, 0));
+        local.hint_usage.record("naked_single");
+        local.saved_at = local_saved_at;
+        let mut remote = AppState::new(Game::new_empty());
+        remote.set_selected_cell(Position::new(8, 8));
+        remote.hint_usage.record("x_wing");
+        remote.saved_at = remote_saved_at;
+        (local, remote)
+    }
+
+    #[test]
+    fn pull_keeps_a_newer_local_board() {
+        let (local, remote) = diverged_states(2_000, 1_000);
+
+        let Pulled::Local(state) = resolve_pulled(&local, (&remote).into()).unwrap() else {
+            panic!("expected the local board to stay");
+        };
+
+        assert_eq!(state.selected_cell(), Some(Position::new(0, 0)));
+        assert_eq!(state.saved_at, 2_000);
+        assert_eq!(state.hint_usage.counts()["naked_single"], 1);
+        assert_eq!(state.hint_usage.counts()["x_wing"], 1);
+    }
+
+    #[test]
+    fn pull_takes_a_newer_remote_board() {
+        let (local, remote) = diverged_states(1_000, 2_000);
+
+        let Pulled::Remote(state) = resolve_pulled(&local, (&remote).into()).unwrap() else {
+            panic!("expected the synced board to win");
+        };
+
+        assert_eq!(state.selected_cell(), Some(Position::new(8, 8)));
+        assert_eq!(state.saved_at, 2_000);
+        assert_eq!(state.hint_usage.counts()["naked_single"], 1);
+        assert_eq!(state.hint_usage.counts()["x_wing"], 1);
+    }
+
+    #[test]
+    fn conflicting_states_keep_the_newer_board() {
+        let (local, remote) = diverged_states(1_000, 2_000);
+        let encode = |state: &AppState| serde_json::to_vec(&PersistedState::from(state)).unwrap();
+
+        let merged = merge_states(&encode(&local), &encode(&remote)).unwrap();
+        let merged: AppState = serde_json::from_slice::<PersistedState>(&merged)
+            .unwrap()
+            .try_into()
+            .unwrap();
+
+        assert_eq!(merged.selected_cell(), Some(Position::new(8, 8)));
+    }
+
+    #[test]
+    fn merged_states_keep_the_local_board_and_all_progress() {
+        let mut local = AppState::new(Game::new_empty());
+        local.set_selected_cell(Position::new(4, 4));
+        local.hint_usage.record("naked_single");
+        local.hint_usage.record("naked_single");
+        let mut remote = AppState::new(Game::new_empty());
+        remote.hint_usage.record("naked_single");
+        remote.hint_usage.record("x_wing");
+        remote.hint_usage.record_reveal();
+        remote.record_hinted_cells(DigitPositions::from_elem(Position::new(1, 2)));
+        let encode = |state: &AppState| serde_json::to_vec(&PersistedState::from(state)).unwrap();
+
+        let merged = merge_states(&encode(&local), &encode(&remote)).unwrap();
+        let merged: AppState = serde_json::from_slice::<PersistedState>(&merged)
+            .unwrap()
+            .try_into()
+            .unwrap();
+
+        assert_eq!(merged.selected_cell(), Some(Position::new(4, 4)));
+        assert_eq!(merged.hint_usage.counts()["naked_single"], 2);
+        assert_eq!(merged.hint_usage.counts()["x_wing"], 1);
+        assert_eq!(merged.hint_usage.reveals(), 1);
+        assert_eq!(merged.hint_usage.houses().rows[1], 1);
+        // The heat map belongs to the local board.
+        assert!(merged.hint_heat_map.is_empty());
+    }
+
+    #[test]
+    fn legacy_solved_puzzles_merge_into_puzzle_metadata() {
+        let fingerprint = PuzzleFingerprint::from(0xabc);
+        let mut local = AppState::new(Game::new_empty());
+        local.puzzle_metadata.record_solved(
+            fingerprint,
+            PuzzleSource::Shared,
+            None,
+            "2026-10-15",
+            Some(Duration::from_secs(90)),
+        );
+        // A device that still saves only the solved dates.
+        let mut remote =
+            serde_json::to_value(PersistedState::from(&AppState::new(Game::new_empty()))).unwrap();
+        remote["solved_puzzles"] = serde_json::json!({ fingerprint.to_string(): "2026-10-01" });
+        let encode = |state: &AppState| serde_json::to_vec(&PersistedState::from(state)).unwrap();
+
+        let merged = merge_states(&encode(&local), &serde_json::to_vec(&remote).unwrap()).unwrap();
+        let merged: AppState = serde_json::from_slice::<PersistedState>(&merged)
+            .unwrap()
+            .try_into()
+            .unwrap();
+
+        let entry = merged.puzzle_metadata.get(fingerprint).unwrap();
+        assert_eq!(entry.source, Some(PuzzleSource::Shared));
+        assert_eq!(entry.added_on, "2026-10-01");
+        assert_eq!(entry.solved_on.as_deref(), Some("2026-10-01"));
+        assert_eq!(entry.best_time, Some(Duration::from_secs(90)));
+    }
+}
@@ -0,0 +1,107 @@
+//! Backend storing blobs on a `WebDAV` server.
+
+use std::fmt::Display;
+
+use ureq::{Agent, http::StatusCode};
+
+use super::{PutOutcome, SyncBackend, SyncBlob, SyncError, SyncVersion};
+
+/// Stores each blob as `<url>/<key>.json`, using `ETag`s as versions.
+///
+/// Writes carry `If-Match` (or `If-None-Match: *` for a new blob), so the
+/// server rejects them with 412 Precondition Failed once another device has
+/// written.
+#[derive(Debug)]
+pub(crate) struct WebDavSyncBackend {
+    agent: Agent,
+    url: String,
+    authorization: Option<String>,
+}
+
+impl WebDavSyncBackend {
+    #[must_use]
+    pub(crate) fn new(url: &str, authorization: Option<String>) -> Self {
+        let agent = Agent::config_builder()
+            .http_status_as_error(false)
+            .build()
+            .into();
+        Self {
+            agent,
+            url: url.trim_end_matches('/').to_owned(),
+            authorization,
+        }
+    }
+
+    fn blob_url(&self, key: &str) -> String {
+        format!("{}/{key}.json", self.url)
+    }
+}
+
+fn request_error(err: impl Display) -> SyncError {
+    SyncError::Request(err.to_string())
+}
+
+fn etag<B>(response: &ureq::http::Response<B>) -> Result<SyncVersion, SyncError> {
+    response
+        .headers()
+        .get("ETag")
+        .and_then(|etag| etag.to_str().ok())
+        .map(|etag| SyncVersion(etag.to_owned()))
+        .ok_or_else(|| SyncError::Request("the server sent no ETag".to_owned()))
+}
+
+impl SyncBackend for WebDavSyncBackend {
+    fn get(&self, key: &str) -> Result<Option<SyncBlob>, SyncError> {
+        let mut request = self.agent.get(&self.blob_url(key));
+        if let Some(authorization) = &self.authorization {
+            request = request.header("Authorization", authorization);
+        }
+        let mut response = request.call().map_err(request_error)?;
+        match response.status() {
+            StatusCode::NOT_FOUND => Ok(None),
+            status if status.is_success() => Ok(Some(SyncBlob {
+                version: etag(&response)?,
+                contents: response.body_mut().read_to_vec().map_err(request_error)?,
+            })),
+            status => Err(request_error(format!("GET returned {status}"))),
+        }
+    }
+
+    fn put(
+        &self,
+        key: &str,
+        contents: &[u8],
+        expected: Option<&SyncVersion>,
+    ) -> Result<PutOutcome, SyncError> {
+        let mut request = self.agent.put(&self.blob_url(key));
+        if let Some(authorization) = &self.authorization {
+            request = request.header("Authorization", authorization);
+        }
+        request = match expected {
+            Some(version) => request.header("If-Match", &version.0),
+            None => request.header("If-None-Match", "*"),
+        };
+        let response = request.send(contents).map_err(request_error)?;
+        match response.status() {
+            StatusCode::PRECONDITION_FAILED => {
+                let remote = self
+                    .get(key)?
+                    .ok_or_else(|| request_error("the conflicting blob was removed"))?;
+                Ok(PutOutcome::Conflict(remote))
+            }
+            status if status.is_success() => {
+                // Not every server returns the new ETag, so ask for it.
+                let version = match etag(&response) {
+                    Ok(version) => version,
+                    Err(_) => {
+                        self.get(key)?
+                            .ok_or_else(|| request_error("the written blob was removed"))?
+                            .version
+                    }
+                };
+                Ok(PutOutcome::Stored(version))
+            }
+            status => Err(request_error(format!("PUT returned {status}"))),
+        }
+    }
+}
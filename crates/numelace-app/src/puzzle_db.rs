@@ -0,0 +1,380 @@
+//! Pre-graded puzzles embedded in the binary for instant new games.
+//!
+//! Generating a hard puzzle can take a long time, and on the web there may be
+//! no worker to take that work off the UI thread. The new game flow falls
+//! back to this database when generation is unavailable or too slow.
+//!
+//! `assets/puzzles.bin` starts with a header: the magic `NLPD`, a format
+//! version byte, and one little-endian `u16` count per preset from
+//! [`DB_PRESETS`]. Fixed-size records follow, grouped by preset in the same
+//! order, so any puzzle is found by offset without scanning.
+//!
+//! Puzzles are stored with their digits relabelled so the first solution row
+//! reads `1..=9`. A record packs the remaining solution rows as 19-bit
+//! permutation ranks, leaving out the last row since it follows from the
+//! others, and then an 81-bit mask of the givens. Sampling relabels the
+//! digits at random, which turns each record into many distinct puzzles of
+//! the same difficulty.
+
+use numelace_core::{Digit, DigitGrid, Position, RegionLayout};
+use numelace_generator::{GeneratedPuzzle, PuzzleSeed};
+
+use crate::state::DifficultyPreset;
+
+/// Presets with bundled puzzles, in storage order.
+pub(crate) const DB_PRESETS: [DifficultyPreset; 5] = [
+    DifficultyPreset::Basic,
+    DifficultyPreset::Intermediate,
+    DifficultyPreset::UpperIntermediate,
+    DifficultyPreset::Advanced,
+    DifficultyPreset::Expert,
+];
+
+const DATA: &[u8] = include_bytes!("../../../assets/puzzles.bin");
+
+const MAGIC: &[u8; 4] = b"NLPD";
+const VERSION: u8 = 1;
+const HEADER_LEN: usize = MAGIC.len() + 1 + 2 * DB_PRESETS.len();
+
+/// Bits of a permutation rank; `9! < 2^19`.
+const RANK_BITS: usize = 19;
+/// Solution rows stored per record: all but the first and the last.
+const STORED_ROWS: u8 = 7;
+const RECORD_BITS: usize = STORED_ROWS as usize * RANK_BITS + 81;
+const RECORD_LEN: usize = RECORD_BITS.div_ceil(8);
+
+const FACTORIALS: [u32; 9] = [1, 1, 2, 6, 24, 120, 720, 5040, 40320];
+
+/// Returns how many puzzles are bundled for `preset`.
+#[must_use]
+pub(crate) fn count(preset: DifficultyPreset) -> usize {
+    preset_range(preset).map_or(0, |range| range.len())
+}
+
+/// Picks a random bundled puzzle for `preset` with its digits shuffled.
+#[must_use]
+pub(crate) fn sample(preset: DifficultyPreset) -> Option<GeneratedPuzzle> {
+    let count = count(preset);
+    if count == 0 {
+        return None;
+    }
+    let mut labels = Digit::ALL;
+    for i in (1..labels.len()).rev() {
+        labels.swap(i, rand::random_range(0..=i));
+    }
+    puzzle(preset, rand::random_range(0..count), labels)
+}
+
+/// Decodes a puzzle, writing `labels[d - 1]` wherever digit `d` is stored.
+fn puzzle(preset: DifficultyPreset, index: usize, labels: [Digit; 9]) -> Option<GeneratedPuzzle> {
+    let range = preset_range(preset)?;
+    if index >= range.len() {
+        return None;
+    }
+    let start = HEADER_LEN + (range.start + index) * RECORD_LEN;
+    let record: &[u8; RECORD_LEN] = DATA.get(start..start + RECORD_LEN)?.try_into().ok()?;
+    let (problem, solution) = decode_record(record)?;
+    let relabel = |grid: &DigitGrid| {
+        DigitGrid::from_array(
+            Position::ALL.map(|pos| grid[pos].map(|digit| labels[usize::from(digit.value() - 1)])),
+        )
+    };
+    let mut seed_bytes = record.to_vec();
+    seed_bytes.extend(labels.map(|digit| digit.value()));
+    Some(GeneratedPuzzle {
+        problem: relabel(&problem),
+        solution: relabel(&solution),
+        regions: RegionLayout::standard(),
+        seed: PuzzleSeed::from_arbitrary_bytes(&seed_bytes),
+    })
+}
+
+/// Returns the record indices of `preset`, or `None` if it has no puzzles.
+fn preset_range(preset: DifficultyPreset) -> Option<std::ops::Range<usize>> {
+    let slot = DB_PRESETS.iter().position(|p| *p == preset)?;
+    let header = DATA.get(..HEADER_LEN)?;
+    if &header[..MAGIC.len()] != MAGIC || header[MAGIC.len()] != VERSION {
+        return None;
+    }
+    let counts = header[MAGIC.len() + 1..]
+        .as_chunks::<2>()
+        .0
+        .iter()
+        .map(|bytes| usize::from(u16::from_le_bytes(*bytes)));
+    let start = counts.clone().take(slot).sum();
+    let len = counts.clone().nth(slot)?;
+    Some(start..start + len)
+}
+
+/// Encodes a solved puzzle as a record; `solution` must be complete.
+#[cfg(test)]
+fn encode_record(problem: &DigitGrid, solution: &DigitGrid) -> [u8; RECORD_LEN] {
+    // Map the first row to 1..=9.
+    let mut canonical = [0_u8; 9];
+    for col in 0..9 {
+        let digit = solution[Position::new(0, col)].expect("solution is complete");
+        canonical[usize::from(digit.value() - 1)] = col;
+    }
+
+    let mut bits = BitWriter::default();
+    for row in 1..=STORED_ROWS {
+        let perm = [0, 1, 2, 3, 4, 5, 6, 7, 8].map(|col| {
+            let digit = solution[Position::new(row, col)].expect("solution is complete");
+            canonical[usize::from(digit.value() - 1)]
+        });
+        bits.write(permutation_rank(perm), RANK_BITS);
+    }
+    for pos in Position::ALL {
+        bits.write(u32::from(problem[pos].is_some()), 1);
+    }
+    bits.finish()
+}
+
+fn decode_record(record: &[u8; RECORD_LEN]) -> Option<(DigitGrid, DigitGrid)> {
+    let mut bits = BitReader::new(record);
+    let mut solution = DigitGrid::new();
+    for (col, digit) in (0..9).zip(Digit::ALL) {
+        solution.set(Position::new(0, col), Some(digit));
+    }
+    for row in 1..=STORED_ROWS {
+        let perm = permutation_from_rank(bits.read(RANK_BITS))?;
+        for (col, value) in (0..9).zip(perm) {
+            solution.set(
+                Position::new(row, col),
+                Some(Digit::ALL[usize::from(value)]),
+            );
+        }
+    }
+    // Each column of the last row holds the one digit missing above it.
+    for col in 0..9 {
+        let seen = (0..8)
+            .filter_map(|row| solution[Position::new(row, col)])
+            .fold(0_u16, |seen, digit| seen | 1 << (digit.value() - 1));
+        if seen.count_ones() != 8 {
+            return None;
+        }
+        let missing = (!seen & 0x1ff).trailing_zeros();
+        solution.set(
+            Position::new(8, col),
+            Some(Digit::ALL[usize::try_from(missing).ok()?]),
+        );
+    }
+
+    let mut problem = DigitGrid::new();
+    for pos in Position::ALL {
+        if bits.read(1) == 1 {
+            problem.set(pos, solution[pos]);
+        }
+    }
+    Some((problem, solution))
+}
+
+/// Returns the lexicographic rank of a permutation of `0..9`.
+#[cfg(test)]
+fn permutation_rank(perm: [u8; 9]) -> u32 {
+    let mut rank = 0;
+    for (i, value) in perm.iter().enumerate() {
+        let smaller_later = perm[i + 1..].iter().filter(|v| *v < value).count();
+        rank += u32::try_from(smaller_later).unwrap() * FACTORIALS[8 - i];
+    }
+    rank
+}
+
+fn permutation_from_rank(mut rank: u32) -> Option<[u8; 9]> {
+    let mut remaining: Vec<u8> = (0..9).collect();
+    let mut perm = [0; 9];
+    for (i, value) in perm.iter_mut().enumerate() {
+        let factorial = FACTORIALS[8 - i];
+        let index = usize::try_from(rank / factorial).ok()?;
+        if index >= remaining.len() {
+            return None;
+        }
+        *value = remaining.remove(index);
+        rank %= factorial;
+    }
+    Some(perm)
+}
+
+/// Writes values least significant bit first.
+#[cfg(test)]
+#[derive(Default)]
+struct BitWriter {
+    bytes: [u8; RECORD_LEN],
+    len: usize,
+}
+
+#[cfg(test)]
+impl BitWriter {
+    fn write(&mut self, value: u32, bits: usize) {
+        for bit in 0..bits {
+            if value >> bit & 1 == 1 {
+                self.bytes[self.len / 8] |= 1 << (self.len % 8);
+            }
+            self.len += 1;
+        }
+    }
+
+    fn finish(self) -> [u8; RECORD_LEN] {
+        self.bytes
+    }
+}
+
+struct BitReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> BitReader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, pos: 0 }
+    }
+
+    fn read(&mut self, bits: usize) -> u32 {
+        let mut value = 0;
+        for bit in 0..bits {
+            let byte = self.bytes[self.pos / 8];
+            value |= u32::from(byte >> (self.pos % 8) & 1) << bit;
+            self.pos += 1;
+        }
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, sync::Mutex, thread};
+
+    use numelace_core::{Digit, DigitGrid, Position};
+    use numelace_game::Game;
+    use numelace_generator::{GeneratedPuzzle, PuzzleSeed};
+    use numelace_solver::TechniquePriority;
+
+    use super::{
+        DB_PRESETS, HEADER_LEN, MAGIC, VERSION, count, decode_record, encode_record, puzzle, sample,
+    };
+    use crate::{
+        state::{NewGameOptions, PuzzleDifficulty},
+        worker::tasks::{
+            GeneratePuzzleRequestDto, GradePuzzleRequestDto, generate_puzzle, grade_puzzle,
+        },
+    };
+
+    /// Puzzles kept per preset when regenerating the database.
+    const PUZZLES_PER_PRESET: usize = 1000;
+
+    fn grade(puzzle: &GeneratedPuzzle) -> PuzzleDifficulty {
+        let game = Game::from_problem_filled_notes(
+            &puzzle.problem,
+            &puzzle.solution,
+            &DigitGrid::new(),
+            &[[0; 9]; 9],
+        )
+        .unwrap();
+        let request = GradePuzzleRequestDto::new(&game, &TechniquePriority::new()).unwrap();
+        PuzzleDifficulty::from(grade_puzzle(&request).unwrap())
+    }
+
+    #[test]
+    fn record_round_trips_up_to_relabelling() {
+        let problem: DigitGrid =
+            "..3.2.6..9..3.5..1..18.64....81.29..7.......8..67.82....26.95..8..2.3..9..5.1.3.."
+                .parse()
+                .unwrap();
+        let solution: DigitGrid =
+            "483921657967345821251876493548132976729564138136798245372689514814253769695417382"
+                .parse()
+                .unwrap();
+
+        let (decoded_problem, decoded_solution) =
+            decode_record(&encode_record(&problem, &solution)).unwrap();
+
+        let mut labels = [None; 9];
+        for pos in Position::ALL {
+            let stored = decoded_solution[pos].unwrap();
+            let label = labels[usize::from(stored.value() - 1)].get_or_insert(solution[pos]);
+            assert_eq!(*label, solution[pos], "{pos:?}");
+            assert_eq!(decoded_problem[pos].is_some(), problem[pos].is_some());
+            assert_eq!(
+                decoded_problem[pos].or(decoded_solution[pos]),
+                decoded_solution[pos]
+            );
+        }
+    }
+
+    #[test]
+    fn bundled_puzzles_match_their_preset() {
+        for preset in DB_PRESETS {
+            let count = count(preset);
+            assert_ne!(count, 0, "no puzzles for {preset:?}");
+            // Grading every puzzle is slow; spot-check across the range.
+            for index in (0..count).step_by(count.div_ceil(8)) {
+                let puzzle = puzzle(preset, index, Digit::ALL).unwrap();
+                assert_eq!(
+                    grade(&puzzle),
+                    PuzzleDifficulty::Rated(preset),
+                    "{preset:?} #{index}"
+                );
+            }
+            assert!(puzzle(preset, count, Digit::ALL).is_none());
+            assert_eq!(
+                grade(&sample(preset).unwrap()),
+                PuzzleDifficulty::Rated(preset)
+            );
+        }
+    }
+
+    /// Rebuilds `assets/puzzles.bin` from fixed seeds.
+    ///
+    /// Run with `cargo test -p numelace-app --release -- --ignored regenerate_puzzle_db`.
+    #[test]
+    #[ignore = "takes minutes; only needed to change the bundled puzzles"]
+    fn regenerate_puzzle_db() {
+        let mut header = MAGIC.to_vec();
+        header.push(VERSION);
+        let mut records = vec![];
+        for preset in DB_PRESETS {
+            let mut options = NewGameOptions::default();
+            options.apply_preset(preset);
+            let request = GeneratePuzzleRequestDto::new(options, &TechniquePriority::new());
+            let found = Mutex::new(vec![]);
+            let workers = thread::available_parallelism().map_or(1, usize::from);
+            thread::scope(|scope| {
+                for worker in 0..workers {
+                    let (request, found) = (&request, &found);
+                    scope.spawn(move || {
+                        for attempt in (worker..).step_by(workers) {
+                            if found.lock().unwrap().len() >= PUZZLES_PER_PRESET {
+                                break;
+                            }
+                            let seed = PuzzleSeed::from_arbitrary_bytes(
+                                format!("{preset:?}-{attempt}").as_bytes(),
+                            );
+                            let mut request = request.clone();
+                            request.seed = seed.to_string();
+                            let puzzle =
+                                GeneratedPuzzle::try_from(generate_puzzle(&request)).unwrap();
+                            if grade(&puzzle) == PuzzleDifficulty::Rated(preset) {
+                                found.lock().unwrap().push((attempt, puzzle));
+                            }
+                        }
+                    });
+                }
+            });
+            // Keep the lowest attempts so the result does not depend on thread timing.
+            let mut found = found.into_inner().unwrap();
+            found.sort_by_key(|(attempt, _)| *attempt);
+            found.truncate(PUZZLES_PER_PRESET);
+            header.extend(u16::try_from(found.len()).unwrap().to_le_bytes());
+            for (_, puzzle) in found {
+                records.extend(encode_record(&puzzle.problem, &puzzle.solution));
+            }
+        }
+        assert_eq!(header.len(), HEADER_LEN);
+        header.extend(records);
+        fs::write(
+            concat!(env!("CARGO_MANIFEST_DIR"), "/../../assets/puzzles.bin"),
+            header,
+        )
+        .unwrap();
+    }
+}
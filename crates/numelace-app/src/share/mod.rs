@@ -0,0 +1,43 @@
+//! Share links that carry a puzzle in the URL fragment.
+//!
+//! The puzzle itself is encoded by [`persistence::share`](crate::persistence::share);
+//! this module only builds links and, on the web build, reads a shared puzzle
+//! from the page URL at startup.
+
+mod platform;
+
+const FRAGMENT_PREFIX: &str = "#puzzle=";
+
+/// Returns a link that opens the puzzle encoded in `code`.
+#[must_use]
+pub(crate) fn share_url(code: &str) -> String {
+    format!("{}{FRAGMENT_PREFIX}{code}", platform::base_url())
+}
+
+/// Takes the share code from the page URL, if the app was opened from a share link.
+///
+/// The fragment is cleared so a reload does not offer the same puzzle again.
+#[must_use]
+pub(crate) fn take_shared_code() -> Option<String> {
+    platform::take_fragment().and_then(|fragment| parse_fragment(&fragment))
+}
+
+fn parse_fragment(fragment: &str) -> Option<String> {
+    fragment
+        .strip_prefix(FRAGMENT_PREFIX)
+        .filter(|code| !code.is_empty())
+        .map(str::to_owned)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_fragment;
+
+    #[test]
+    fn parse_fragment_extracts_code() {
+        assert_eq!(parse_fragment("#puzzle=QAbc-_"), Some("QAbc-_".to_owned()));
+        assert_eq!(parse_fragment("#puzzle="), None);
+        assert_eq!(parse_fragment("#other"), None);
+        assert_eq!(parse_fragment(""), None);
+    }
+}
@@ -0,0 +1,10 @@
+/// Native builds link to the hosted web version.
+const PUBLIC_URL: &str = "https://gifnksm.github.io/numelace/";
+
+pub(in crate::share) fn base_url() -> String {
+    PUBLIC_URL.to_owned()
+}
+
+pub(in crate::share) fn take_fragment() -> Option<String> {
+    None
+}
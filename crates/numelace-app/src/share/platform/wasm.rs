@@ -0,0 +1,27 @@
+use wasm_bindgen::JsValue;
+
+pub(in crate::share) fn base_url() -> String {
+    web_sys::window()
+        .and_then(|window| {
+            let location = window.location();
+            Some(format!(
+                "{}{}",
+                location.origin().ok()?,
+                location.pathname().ok()?
+            ))
+        })
+        .unwrap_or_default()
+}
+
+pub(in crate::share) fn take_fragment() -> Option<String> {
+    let window = web_sys::window()?;
+    let hash = window.location().hash().ok()?;
+    if hash.is_empty() {
+        return None;
+    }
+    // Drop the fragment without adding a history entry.
+    if let (Ok(history), url) = (window.history(), base_url()) {
+        let _ = history.replace_state_with_url(&JsValue::NULL, "", Some(&url));
+    }
+    Some(hash)
+}
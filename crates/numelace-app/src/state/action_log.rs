@@ -0,0 +1,78 @@
+use std::collections::VecDeque;
+
+use crate::action::{Action, AppAction, BoardMutationAction};
+
+/// Number of actions kept in the log.
+const CAPACITY: usize = 50;
+
+/// The most recent actions handled, for bug reports.
+///
+/// Entries are redacted: only board, selection and history actions keep their
+/// payload, which holds nothing but digits and positions. Every other action is
+/// logged by its variant names, so share codes, file paths and clipboard
+/// contents never end up in a report.
+#[derive(Debug, Default)]
+pub(crate) struct ActionLog {
+    entries: VecDeque<String>,
+}
+
+impl ActionLog {
+    pub(crate) fn record(&mut self, action: &Action) {
+        if self.entries.len() == CAPACITY {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(describe(action));
+    }
+
+    /// Returns the logged actions, oldest first.
+    pub(crate) fn entries(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(String::as_str)
+    }
+}
+
+fn describe(action: &Action) -> String {
+    match action {
+        Action::App(AppAction::BoardMutation(BoardMutationAction::ApplyTechniqueStep(_))) => {
+            variant_names(&format!("{action:?}"))
+        }
+        Action::App(AppAction::BoardMutation(action)) => format!("{action:?}"),
+        Action::App(AppAction::Selection(action)) => format!("{action:?}"),
+        Action::App(AppAction::History(action)) => format!("{action:?}"),
+        _ => variant_names(&format!("{action:?}")),
+    }
+}
+
+/// Keeps the leading chain of tuple variant names of a `Debug` string,
+/// e.g. `Flow::LoadSharedPuzzle` for `Flow(LoadSharedPuzzle("..."))`.
+fn variant_names(debug: &str) -> String {
+    let end = debug.find([' ', '{', '"', '[']).unwrap_or(debug.len());
+    debug[..end]
+        .split(['(', ')'])
+        .filter(|name| !name.is_empty())
+        .collect::<Vec<_>>()
+        .join("::")
+}
+
+#[cfg(test)]
+mod tests {
+    use numelace_core::Digit;
+
+    use super::*;
+    use crate::action::{FlowAction, SelectionAction};
+
+    #[test]
+    fn action_log_keeps_recent_redacted_entries() {
+        let mut log = ActionLog::default();
+        log.record(&FlowAction::LoadSharedPuzzle("secret".to_owned()).into());
+        for _ in 0..CAPACITY {
+            log.record(&SelectionAction::ToggleArmedDigit(Digit::D3).into());
+        }
+        assert_eq!(log.entries().count(), CAPACITY);
+        assert!(log.entries().all(|entry| entry == "ToggleArmedDigit(D3)"));
+
+        log.record(&FlowAction::LoadSharedPuzzle("secret".to_owned()).into());
+        log.record(&AppAction::CancelContextual.into());
+        let last: Vec<_> = log.entries().skip(CAPACITY - 2).collect();
+        assert_eq!(last, ["Flow::LoadSharedPuzzle", "App::CancelContextual"]);
+    }
+}
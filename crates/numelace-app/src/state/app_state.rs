@@ -1,19 +1,75 @@
-use numelace_core::{Digit, Position};
+use numelace_core::{Digit, DigitPositions, Position};
 use numelace_game::{Game, InputDigitOptions, NoteCleanupPolicy, RuleCheckPolicy};
-
-use crate::state::{History, HistorySource, HistoryTarget, NewGameOptions, Settings};
+use numelace_generator::PuzzleSeed;
+use web_time::{SystemTime, UNIX_EPOCH};
+
+use crate::state::{
+    CampaignProgress, CampaignPuzzle, HintHeatMap, HintUsage, History, HistorySource,
+    HistoryTarget, HotSeatRace, NewGameOptions, Player, PuzzleDifficulty, PuzzleMetadataStore,
+    PuzzleSource, RaceSummary, Settings, TimedGame, ValidationMode, fingerprint, par_time, today,
+};
+
+/// A clock stopped because the puzzle on the board was solved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum GameFinish {
+    /// A timed game was solved.
+    Timed,
+    /// A race player solved their copy while the other is still playing.
+    RaceTurn(Player),
+    /// The last race player solved their copy.
+    Race,
+}
 
 // AppState holds persisted state (game/session + settings + history). It is serialized for resume.
 #[derive(Debug)]
+#[expect(clippy::struct_excessive_bools)]
 pub(crate) struct AppState {
     pub(crate) game: Game,
     selected_cell: Option<Position>,
+    /// Every selected cell, the selected cell among them; empty when no cell
+    /// is selected.
+    selection: DigitPositions,
     selected_digit: Option<Digit>,
+    armed_digit: Option<Digit>,
     pub(crate) input_mode: InputMode,
     pub(crate) new_game_options: NewGameOptions,
     pub(crate) settings: Settings,
+    /// Rated difficulty of the current puzzle, when known.
+    pub(crate) difficulty: Option<PuzzleDifficulty>,
+    /// Seed the current puzzle was generated from; `None` for imported,
+    /// shared and campaign puzzles.
+    pub(crate) seed: Option<PuzzleSeed>,
+    /// Options the generator made the current puzzle with, its seed filled in,
+    /// so the puzzle can be generated again; `None` for puzzles that did not
+    /// come from the generator.
+    pub(crate) generated_with: Option<NewGameOptions>,
+    pub(crate) hint_usage: HintUsage,
+    /// Cells of the current game that hints and reveals pointed at.
+    pub(crate) hint_heat_map: HintHeatMap,
+    /// Clock of the current game, when it was started in timed mode.
+    pub(crate) timed: Option<TimedGame>,
+    /// Hot-seat race in progress, when the game was started in race mode.
+    pub(crate) race: Option<HotSeatRace>,
+    pub(crate) campaign: CampaignProgress,
+    /// Campaign puzzle being played, when the game was started from a pack.
+    pub(crate) campaign_puzzle: Option<CampaignPuzzle>,
+    /// Classic puzzles imported or solved so far, keyed by fingerprint.
+    pub(crate) puzzle_metadata: PuzzleMetadataStore,
+    /// Whether the tutorial has run, so it starts only on the first launch.
+    pub(crate) tutorial_seen: bool,
+    /// Whether the player chose to review the solved puzzle, which keeps the
+    /// board read-only until another puzzle is loaded.
+    pub(crate) reviewing: bool,
+    /// Whether solving the puzzle on the board was already recorded and
+    /// shown, so solving it again after an undo does neither twice.
+    pub(crate) solved_recorded: bool,
+    /// When a change was last saved, in milliseconds since the Unix epoch; 0
+    /// if never. Syncing keeps the board saved last.
+    pub(crate) saved_at: u64,
     history: History,
     dirty: bool,
+    /// Incremented on every mutable access; keys view-model caches.
+    revision: u64,
 }
 
 impl AppState {
@@ -22,12 +78,29 @@ impl AppState {
         let mut state = Self {
             game,
             selected_cell: None,
+            selection: DigitPositions::EMPTY,
             selected_digit: None,
+            armed_digit: None,
             input_mode: InputMode::Fill,
             new_game_options: NewGameOptions::default(),
             settings: Settings::default(),
+            difficulty: None,
+            seed: None,
+            generated_with: None,
+            reviewing: false,
+            solved_recorded: false,
+            hint_usage: HintUsage::default(),
+            hint_heat_map: HintHeatMap::default(),
+            timed: None,
+            race: None,
+            campaign: CampaignProgress::default(),
+            campaign_puzzle: None,
+            puzzle_metadata: PuzzleMetadataStore::default(),
+            tutorial_seen: false,
+            saved_at: 0,
             history: History::new(),
             dirty: false,
+            revision: 0,
         };
         state.reset_history();
         state
@@ -59,15 +132,179 @@ impl AppState {
         Self {
             game,
             selected_cell,
+            selection: selected_cell.into_iter().collect(),
             selected_digit,
+            armed_digit: None,
             input_mode,
             new_game_options,
             settings,
+            difficulty: None,
+            seed: None,
+            generated_with: None,
+            reviewing: false,
+            solved_recorded: false,
+            hint_usage: HintUsage::default(),
+            hint_heat_map: HintHeatMap::default(),
+            timed: None,
+            race: None,
+            campaign: CampaignProgress::default(),
+            campaign_puzzle: None,
+            puzzle_metadata: PuzzleMetadataStore::default(),
+            tutorial_seen: false,
+            saved_at: 0,
             history,
             dirty: false,
+            revision: 0,
+        }
+    }
+
+    /// Replaces the state with `restored`, e.g. progress recovered after a crash.
+    ///
+    /// The dirty flag and revision carry over, so the change is still saved
+    /// and view-model caches notice it.
+    pub(crate) fn restore(&mut self, restored: AppState) {
+        *self = AppState {
+            dirty: self.dirty,
+            revision: self.revision,
+            ..restored
+        };
+    }
+
+    /// Takes the progress that follows the player across devices from
+    /// `other`, keeping the board and settings.
+    pub(crate) fn adopt_progress(&mut self, other: AppState) {
+        self.hint_usage = other.hint_usage;
+        self.campaign = other.campaign;
+        self.puzzle_metadata = other.puzzle_metadata;
+        self.tutorial_seen = other.tutorial_seen;
+    }
+
+    /// Records that the state is being saved now.
+    pub(crate) fn stamp_saved_at(&mut self) {
+        self.saved_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| {
+                u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX)
+            });
+    }
+
+    /// Records the rated difficulty and derives the par time of the game clocks from it.
+    pub(crate) fn set_difficulty(&mut self, difficulty: Option<PuzzleDifficulty>) {
+        self.difficulty = difficulty;
+        let empty_cells = Position::ALL.len() - self.game.given_positions().len();
+        let par = difficulty.map(|difficulty| par_time(difficulty, empty_cells));
+        if let Some(timed) = &mut self.timed {
+            timed.par = par;
+        }
+        if let Some(race) = &mut self.race {
+            for player in Player::ALL {
+                race.clock_mut(player).par = par;
+            }
+        }
+    }
+
+    /// Starts a hot-seat race on the current puzzle, with player one at the board.
+    pub(crate) fn start_race(&mut self) {
+        let mut parked_history = History::with_capacity(self.history.capacity());
+        parked_history.reset(&HistorySource::new(&self.game, None));
+        self.race = Some(HotSeatRace::from_parts(
+            Player::One,
+            Default::default(),
+            self.game.clone(),
+            parked_history,
+        ));
+    }
+
+    /// Hands the board to the other race player.
+    ///
+    /// Returns false when there is no race or the other player has already finished.
+    pub(crate) fn pass_turn(&mut self) -> bool {
+        let Some(race) = &mut self.race else {
+            return false;
+        };
+        if race.clock(race.current().other()).finished {
+            return false;
+        }
+        race.swap_board(&mut self.game, &mut self.history);
+        self.clear_selected_cell_and_digit();
+        true
+    }
+
+    #[must_use]
+    pub(crate) fn race_summary(&self) -> Option<RaceSummary> {
+        self.race.as_ref().map(|race| race.summary(&self.game))
+    }
+
+    /// Returns the clock running for the board, if any.
+    #[must_use]
+    pub(crate) fn active_clock(&self) -> Option<TimedGame> {
+        match &self.race {
+            Some(race) => Some(race.clock(race.current())),
+            None => self.timed,
+        }
+    }
+
+    /// Returns the clock running for the board mutably, if any.
+    pub(crate) fn active_clock_mut(&mut self) -> Option<&mut TimedGame> {
+        match &mut self.race {
+            Some(race) => Some(race.clock_mut(race.current())),
+            None => self.timed.as_mut(),
+        }
+    }
+
+    /// Stops the running clock once the puzzle on the board is solved.
+    ///
+    /// Returns a value only for the change that stopped the clock.
+    pub(crate) fn finish_game(&mut self) -> Option<GameFinish> {
+        if !self.game.is_solved() {
+            return None;
+        }
+        let clock = self.active_clock_mut().filter(|clock| !clock.finished)?;
+        clock.finished = true;
+        match &self.race {
+            None => Some(GameFinish::Timed),
+            Some(race) if race.is_finished() => Some(GameFinish::Race),
+            Some(race) => Some(GameFinish::RaceTurn(race.current())),
         }
     }
 
+    /// Records the campaign puzzle on the board as solved once it is.
+    ///
+    /// Returns true only for the change that solved it for the first time.
+    pub(crate) fn complete_campaign_puzzle(&mut self) -> bool {
+        match self.campaign_puzzle {
+            Some(puzzle) if self.game.is_solved() => self.campaign.mark_solved(puzzle),
+            _ => false,
+        }
+    }
+
+    /// Records that a hint or reveal pointed at `cells`, for the heat map of
+    /// the game and the statistics per house.
+    pub(crate) fn record_hinted_cells(&mut self, cells: DigitPositions) {
+        self.hint_heat_map.record(cells);
+        self.hint_usage.record_cells(cells, self.game.regions());
+    }
+
+    /// Records the puzzle on the board as solved today, with its difficulty
+    /// and, for timed games, the play time.
+    ///
+    /// Call this only for the change that solved the puzzle, since
+    /// fingerprinting it searches every variant of the puzzle.
+    pub(crate) fn record_solved_puzzle(&mut self) {
+        let Some(fingerprint) = fingerprint(&self.game) else {
+            return;
+        };
+        let source = self
+            .campaign_puzzle
+            .map_or(PuzzleSource::Generated, PuzzleSource::Pack);
+        let time = self
+            .timed
+            .filter(|timed| timed.finished)
+            .map(|timed| timed.elapsed);
+        self.puzzle_metadata
+            .record_solved(fingerprint, source, self.difficulty, &today(), time);
+    }
+
     pub(crate) fn access(&mut self) -> AppStateAccess<'_> {
         AppStateAccess { app_state: self }
     }
@@ -81,6 +318,12 @@ impl AppState {
         self.dirty = false;
     }
 
+    /// Returns a counter that changes whenever the state may have changed.
+    #[must_use]
+    pub(crate) fn revision(&self) -> u64 {
+        self.revision
+    }
+
     pub(crate) fn apply_new_game_settings(&mut self) {
         if self.settings.assist.notes.auto_fill_notes_on_new_or_reset {
             self.game.auto_fill_notes_all_cells();
@@ -89,10 +332,13 @@ impl AppState {
 
     #[must_use]
     pub(crate) fn rule_check_policy(&self) -> RuleCheckPolicy {
-        if self.settings.assist.block_rule_violations {
-            RuleCheckPolicy::Strict
-        } else {
-            RuleCheckPolicy::Permissive
+        let assist = &self.settings.assist;
+        if !assist.block_rule_violations {
+            return RuleCheckPolicy::Permissive;
+        }
+        match assist.validation_mode {
+            ValidationMode::Rules => RuleCheckPolicy::Strict,
+            ValidationMode::Solution => RuleCheckPolicy::Solution,
         }
     }
 
@@ -117,12 +363,66 @@ impl AppState {
         self.selected_cell
     }
 
+    /// Returns every selected cell, including [`AppState::selected_cell`].
+    #[must_use]
+    pub(crate) fn selected_cells(&self) -> DigitPositions {
+        self.selection
+    }
+
     #[must_use]
     pub(crate) fn selected_digit(&self) -> Option<Digit> {
         self.selected_digit
     }
 
+    /// Returns the digit entered by tapping cells in digit-first input order.
+    #[must_use]
+    pub(crate) fn armed_digit(&self) -> Option<Digit> {
+        self.armed_digit
+    }
+
+    /// Arms `digit`, or disarms it if it is already armed.
+    ///
+    /// The armed digit also becomes the selected digit so its cells are highlighted.
+    pub(crate) fn toggle_armed_digit(&mut self, digit: Digit) {
+        if self.armed_digit == Some(digit) {
+            self.armed_digit = None;
+        } else {
+            self.armed_digit = Some(digit);
+            self.selected_digit = Some(digit);
+        }
+    }
+
+    pub(crate) fn disarm_digit(&mut self) {
+        self.armed_digit = None;
+    }
+
+    /// Selects the cell at `pos` alone.
     pub(crate) fn set_selected_cell(&mut self, pos: Position) {
+        self.selected_cell = Some(pos);
+        self.selection = DigitPositions::from_elem(pos);
+        self.update_selected_digit();
+    }
+
+    /// Adds the cell at `pos` to the selection and moves the selected cell
+    /// there, or removes it from the selection if it is already selected.
+    ///
+    /// Removing the selected cell moves it to another selected cell, if any.
+    pub(crate) fn toggle_cell_in_selection(&mut self, pos: Position) {
+        if self.selection.contains(pos) {
+            self.selection.remove(pos);
+            if self.selected_cell == Some(pos) {
+                self.selected_cell = self.selection.first();
+            }
+        } else {
+            self.selection.insert(pos);
+            self.selected_cell = Some(pos);
+        }
+        self.update_selected_digit();
+    }
+
+    /// Moves the selected cell to `pos`, keeping the cells selected so far.
+    pub(crate) fn extend_selection(&mut self, pos: Position) {
+        self.selection.insert(pos);
         self.selected_cell = Some(pos);
         self.update_selected_digit();
     }
@@ -137,11 +437,14 @@ impl AppState {
 
     pub(crate) fn clear_selected_cell(&mut self) {
         self.selected_cell = None;
+        self.selection = DigitPositions::EMPTY;
     }
 
     pub(crate) fn clear_selected_cell_and_digit(&mut self) {
         self.selected_cell = None;
+        self.selection = DigitPositions::EMPTY;
         self.selected_digit = None;
+        self.armed_digit = None;
     }
 
     #[must_use]
@@ -236,6 +539,7 @@ impl AppStateAccess<'_> {
 
     pub(crate) fn as_mut(&mut self) -> &mut AppState {
         self.app_state.dirty = true;
+        self.app_state.revision += 1;
         self.app_state
     }
 }
@@ -0,0 +1,98 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::campaign::PACKS;
+
+/// A puzzle of the bundled packs, by pack and puzzle index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct CampaignPuzzle {
+    pub(crate) pack: usize,
+    pub(crate) index: usize,
+}
+
+/// Puzzles solved in each pack, keyed by pack ID.
+///
+/// A pack unlocks once every puzzle of the previous pack is solved, and
+/// within a pack each puzzle unlocks once the one before it is solved.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct CampaignProgress {
+    solved: BTreeMap<String, BTreeSet<usize>>,
+}
+
+impl CampaignProgress {
+    #[must_use]
+    pub(crate) fn from_solved(solved: BTreeMap<String, BTreeSet<usize>>) -> Self {
+        Self { solved }
+    }
+
+    #[must_use]
+    pub(crate) fn solved(&self) -> &BTreeMap<String, BTreeSet<usize>> {
+        &self.solved
+    }
+
+    #[must_use]
+    pub(crate) fn is_solved(&self, puzzle: CampaignPuzzle) -> bool {
+        self.solved
+            .get(PACKS[puzzle.pack].id)
+            .is_some_and(|solved| solved.contains(&puzzle.index))
+    }
+
+    #[must_use]
+    pub(crate) fn solved_count(&self, pack: usize) -> usize {
+        self.solved.get(PACKS[pack].id).map_or(0, BTreeSet::len)
+    }
+
+    #[must_use]
+    pub(crate) fn is_pack_complete(&self, pack: usize) -> bool {
+        self.solved_count(pack) >= PACKS[pack].puzzle_count()
+    }
+
+    #[must_use]
+    pub(crate) fn is_pack_unlocked(&self, pack: usize) -> bool {
+        pack == 0 || self.is_pack_complete(pack - 1)
+    }
+
+    #[must_use]
+    pub(crate) fn is_unlocked(&self, puzzle: CampaignPuzzle) -> bool {
+        self.is_pack_unlocked(puzzle.pack)
+            && (puzzle.index == 0
+                || self.is_solved(CampaignPuzzle {
+                    index: puzzle.index - 1,
+                    ..puzzle
+                }))
+    }
+
+    /// Records `puzzle` as solved; returns false if it already was.
+    pub(crate) fn mark_solved(&mut self, puzzle: CampaignPuzzle) -> bool {
+        self.solved
+            .entry(PACKS[puzzle.pack].id.to_owned())
+            .or_default()
+            .insert(puzzle.index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CampaignProgress, CampaignPuzzle};
+    use crate::campaign::PACKS;
+
+    #[test]
+    fn puzzles_and_packs_unlock_in_order() {
+        let mut progress = CampaignProgress::default();
+        let first = CampaignPuzzle { pack: 0, index: 0 };
+        let second = CampaignPuzzle { pack: 0, index: 1 };
+        assert!(progress.is_unlocked(first));
+        assert!(!progress.is_unlocked(second));
+        assert!(!progress.is_pack_unlocked(1));
+
+        assert!(progress.mark_solved(first));
+        assert!(!progress.mark_solved(first));
+        assert!(progress.is_unlocked(second));
+
+        for index in 0..PACKS[0].puzzle_count() {
+            progress.mark_solved(CampaignPuzzle { pack: 0, index });
+        }
+        assert!(progress.is_pack_complete(0));
+        assert!(progress.is_unlocked(CampaignPuzzle { pack: 1, index: 0 }));
+        assert!(!progress.is_unlocked(CampaignPuzzle { pack: 1, index: 1 }));
+    }
+}
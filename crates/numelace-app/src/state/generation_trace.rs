@@ -0,0 +1,65 @@
+use numelace_core::{DigitGrid, PositionIndexedArray};
+use numelace_generator::{GenerationTrace, candidate_counts};
+
+/// What the cells of the generator trace board are shaded by.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TraceShading {
+    /// Candidates left by the givens, so open areas stand out.
+    #[default]
+    Candidates,
+    /// When each cell was removed, early removals darkest.
+    RemovalOrder,
+}
+
+/// A generator trace shown in developer mode, with the step being viewed.
+#[derive(Debug, Clone)]
+pub(crate) struct GenerationTraceView {
+    pub(crate) trace: GenerationTrace,
+    /// Number of carve steps applied to the board shown.
+    pub(crate) step: usize,
+    pub(crate) shading: TraceShading,
+    /// Kept removal number of each removed cell, counted from 1.
+    removal_numbers: PositionIndexedArray<Option<u16>>,
+}
+
+impl GenerationTraceView {
+    /// Shows `trace` at its last step, which is the generated problem.
+    #[must_use]
+    pub(crate) fn new(trace: GenerationTrace) -> Self {
+        let mut removal_numbers = PositionIndexedArray::default();
+        for (number, removed) in (1..).zip(trace.removal_order()) {
+            for pos in removed {
+                removal_numbers[pos] = Some(number);
+            }
+        }
+        Self {
+            step: trace.steps.len(),
+            trace,
+            shading: TraceShading::default(),
+            removal_numbers,
+        }
+    }
+
+    /// Returns the problem at the viewed step.
+    #[must_use]
+    pub(crate) fn problem(&self) -> DigitGrid {
+        self.trace.problem_after(self.step)
+    }
+
+    /// Returns the candidates of each cell at the viewed step.
+    #[must_use]
+    pub(crate) fn candidate_counts(&self) -> PositionIndexedArray<u8> {
+        candidate_counts(&self.trace.regions, &self.problem())
+    }
+
+    #[must_use]
+    pub(crate) fn removal_numbers(&self) -> &PositionIndexedArray<Option<u16>> {
+        &self.removal_numbers
+    }
+
+    /// Returns the number of kept removals, the largest removal number.
+    #[must_use]
+    pub(crate) fn removal_count(&self) -> usize {
+        self.trace.kept_removals()
+    }
+}
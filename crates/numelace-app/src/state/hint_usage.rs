@@ -0,0 +1,181 @@
+use std::collections::BTreeMap;
+
+use numelace_core::{DigitPositions, Position, PositionIndexedArray, RegionLayout};
+
+/// Local count of the techniques shown in requested hints and of the
+/// revealed answers.
+///
+/// Counts are keyed by technique step key and never leave the device, except
+/// through a sync backend the player configured.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct HintUsage {
+    counts: BTreeMap<String, u32>,
+    reveals: u32,
+    houses: HouseHints,
+}
+
+impl HintUsage {
+    #[must_use]
+    pub(crate) fn from_counts(
+        counts: BTreeMap<String, u32>,
+        reveals: u32,
+        houses: HouseHints,
+    ) -> Self {
+        Self {
+            counts,
+            reveals,
+            houses,
+        }
+    }
+
+    #[must_use]
+    pub(crate) fn counts(&self) -> &BTreeMap<String, u32> {
+        &self.counts
+    }
+
+    pub(crate) fn record(&mut self, technique_key: &str) {
+        let count = self.counts.entry(technique_key.to_owned()).or_default();
+        *count = count.saturating_add(1);
+    }
+
+    /// Counts a reveal of the selected cell or of the whole solution.
+    pub(crate) fn record_reveal(&mut self) {
+        self.reveals = self.reveals.saturating_add(1);
+    }
+
+    #[must_use]
+    pub(crate) fn reveals(&self) -> u32 {
+        self.reveals
+    }
+
+    /// Counts a hint or reveal once for every house holding one of `cells`.
+    pub(crate) fn record_cells(&mut self, cells: DigitPositions, regions: &RegionLayout) {
+        self.houses.record(cells, regions);
+    }
+
+    #[must_use]
+    pub(crate) fn houses(&self) -> &HouseHints {
+        &self.houses
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.counts.clear();
+        self.reveals = 0;
+        self.houses = HouseHints::default();
+    }
+
+    #[must_use]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.counts.is_empty() && self.reveals == 0 && self.houses.is_empty()
+    }
+
+    #[must_use]
+    pub(crate) fn total(&self) -> u32 {
+        self.counts.values().sum()
+    }
+
+    /// Returns the techniques ordered from the most to the least hinted.
+    #[must_use]
+    pub(crate) fn most_hinted(&self) -> Vec<(&str, u32)> {
+        let mut entries = self
+            .counts
+            .iter()
+            .map(|(key, count)| (key.as_str(), *count))
+            .collect::<Vec<_>>();
+        entries.sort_by(|(_, a), (_, b)| b.cmp(a));
+        entries
+    }
+}
+
+/// Hints and reveals counted per house.
+///
+/// Boxes follow the region layout of the puzzle, so jigsaw regions count as
+/// boxes.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct HouseHints {
+    pub(crate) rows: [u32; 9],
+    pub(crate) cols: [u32; 9],
+    pub(crate) boxes: [u32; 9],
+}
+
+impl HouseHints {
+    fn record(&mut self, cells: DigitPositions, regions: &RegionLayout) {
+        let mut rows = [false; 9];
+        let mut cols = [false; 9];
+        let mut boxes = [false; 9];
+        for pos in cells {
+            rows[usize::from(pos.row())] = true;
+            cols[usize::from(pos.col())] = true;
+            boxes[usize::from(regions.region_index(pos))] = true;
+        }
+        for (counts, touched) in [
+            (&mut self.rows, rows),
+            (&mut self.cols, cols),
+            (&mut self.boxes, boxes),
+        ] {
+            for (count, touched) in counts.iter_mut().zip(touched) {
+                if touched {
+                    *count = count.saturating_add(1);
+                }
+            }
+        }
+    }
+
+    /// Keeps the larger count of every house.
+    #[must_use]
+    pub(crate) fn max(self, other: Self) -> Self {
+        let max = |a: [u32; 9], b: [u32; 9]| std::array::from_fn(|i| a[i].max(b[i]));
+        Self {
+            rows: max(self.rows, other.rows),
+            cols: max(self.cols, other.cols),
+            boxes: max(self.boxes, other.boxes),
+        }
+    }
+
+    #[must_use]
+    pub(crate) fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// How often each cell of the current game was the target of a hint or a
+/// reveal, shown over the board once the game is solved.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct HintHeatMap {
+    counts: PositionIndexedArray<u8>,
+}
+
+impl HintHeatMap {
+    /// Restores a heat map from counts in position order; missing counts are
+    /// zero.
+    #[must_use]
+    pub(crate) fn from_counts(counts: &[u8]) -> Self {
+        Self {
+            counts: PositionIndexedArray::from_fn(|pos| {
+                counts.get(usize::from(pos.index())).copied().unwrap_or(0)
+            }),
+        }
+    }
+
+    /// Returns the counts in position order.
+    #[must_use]
+    pub(crate) fn counts(&self) -> Vec<u8> {
+        self.counts.iter().copied().collect()
+    }
+
+    pub(crate) fn record(&mut self, cells: DigitPositions) {
+        for pos in cells {
+            self.counts[pos] = self.counts[pos].saturating_add(1);
+        }
+    }
+
+    #[must_use]
+    pub(crate) fn count(&self, pos: Position) -> u8 {
+        self.counts[pos]
+    }
+
+    #[must_use]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.counts.iter().all(|&count| count == 0)
+    }
+}
@@ -85,18 +85,10 @@ impl History {
     }
 
     pub(crate) fn build_undo_games(&self, game: &Game) -> Vec<Game> {
-        let (problem, solution) = base_problem_and_solution(game);
         self.stack
             .iter_from_current()
-            .map(|snapshot| {
-                Game::from_problem_filled_notes(
-                    &problem,
-                    &solution,
-                    &snapshot.filled,
-                    &snapshot.notes,
-                )
-            })
-            .collect::<Result<Vec<_>, _>>()
+            .map(|snapshot| snapshot.to_game(game))
+            .collect::<Option<Vec<_>>>()
             .unwrap_or_default()
     }
 
@@ -165,25 +157,27 @@ impl History {
 pub(crate) struct HistorySnapshot {
     pub(crate) filled: DigitGrid,
     pub(crate) notes: [[u16; 9]; 9],
+    pub(crate) center_notes: [[u16; 9]; 9],
     pub(crate) selected_at_change: Option<Position>,
 }
 
 fn base_problem_and_solution(game: &Game) -> (DigitGrid, DigitGrid) {
     let mut problem = DigitGrid::new();
-    for pos in Position::ALL {
-        if let CellState::Given(digit) = game.cell(pos) {
-            problem.set(pos, Some(*digit));
-        }
+    for pos in game.given_positions() {
+        problem.set(pos, game.cell(pos).as_given());
     }
     (problem, game.solution().clone())
 }
 
 impl HistorySnapshot {
-    fn new(source: &HistorySource<'_>) -> Self {
+    pub(crate) fn new(source: &HistorySource<'_>) -> Self {
         let mut filled = DigitGrid::new();
         let mut notes = [[0u16; 9]; 9];
-        for pos in Position::ALL {
-            match source.game.cell(pos) {
+        let mut center_notes = [[0u16; 9]; 9];
+        for (pos, cell) in source.game.iter_cells() {
+            center_notes[usize::from(pos.row())][usize::from(pos.col())] =
+                source.game.center_notes(pos).bits();
+            match cell {
                 CellState::Filled(digit) => {
                     filled.set(pos, Some(*digit));
                 }
@@ -196,19 +190,42 @@ impl HistorySnapshot {
         Self {
             filled,
             notes,
+            center_notes,
             selected_at_change: source.selected_cell,
         }
     }
 
+    /// Returns whether both snapshots hold the same digits and notes,
+    /// wherever the selection was.
+    #[must_use]
+    pub(crate) fn same_board(&self, other: &Self) -> bool {
+        self.filled == other.filled
+            && self.notes == other.notes
+            && self.center_notes == other.center_notes
+    }
+
+    /// Rebuilds the snapshot on the puzzle of `base`, keeping its givens and
+    /// variant constraints.
+    #[must_use]
+    pub(crate) fn to_game(&self, base: &Game) -> Option<Game> {
+        let (problem, solution) = base_problem_and_solution(base);
+        let game = Game::from_problem_filled_notes(&problem, &solution, &self.filled, &self.notes)
+            .and_then(|game| game.with_center_notes(&self.center_notes))
+            .ok()?;
+        Some(
+            game.with_cages(base.cages().clone())
+                .with_regions(*base.regions())
+                .with_edge_constraints(base.edge_constraints().clone())
+                .with_chess_rules(base.chess_rules()),
+        )
+    }
+
     fn apply(&self, target: &mut HistoryTarget<'_>) -> bool {
-        let (problem, solution) = base_problem_and_solution(target.game);
-        match Game::from_problem_filled_notes(&problem, &solution, &self.filled, &self.notes) {
-            Ok(new_game) => {
-                *target.game = new_game;
-                *target.selected_cell = self.selected_at_change;
-                true
-            }
-            Err(_) => false,
-        }
+        let Some(game) = self.to_game(target.game) else {
+            return false;
+        };
+        *target.game = game;
+        *target.selected_cell = self.selected_at_change;
+        true
     }
 }
@@ -0,0 +1,261 @@
+//! Keyboard bindings, which the player can change from the defaults.
+//!
+//! The default shortcuts live with the commands in
+//! [`COMMANDS`](crate::ui::command::COMMANDS); [`KeyBindings`] only holds the
+//! player's changes, so new defaults reach everyone who did not rebind them.
+
+use std::collections::BTreeMap;
+
+use eframe::egui::Key;
+
+use crate::action::NoteKind;
+
+/// Identifies a command; the order matches
+/// [`COMMANDS`](crate::ui::command::COMMANDS).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) enum CommandId {
+    Undo,
+    Redo,
+    ToggleInputMode,
+    ClearCell,
+    ClearScratchpad,
+    AutoFillAllCells,
+    AutoFillEmptyCells,
+    AutoFillSelectedCell,
+    CheckSolvability,
+    CheckProgress,
+    Hint,
+    AuditNotes,
+    ClearMistakes,
+    RevealCell,
+    RevealAll,
+    ToggleCandidateHeatmap,
+    ToggleInfoPanel,
+    NewGame,
+    RegeneratePuzzle,
+    ResetInputs,
+    Settings,
+    Statistics,
+    RaceStandings,
+    Campaign,
+    Learn,
+    BugReport,
+    ExportPuzzle,
+    ExportProgress,
+    CopyPuzzleLink,
+    CopyProgressLink,
+    CopyBoardImage,
+}
+
+impl CommandId {
+    /// Every command, in [`COMMANDS`](crate::ui::command::COMMANDS) order.
+    pub(crate) const ALL: [Self; 31] = [
+        Self::Undo,
+        Self::Redo,
+        Self::ToggleInputMode,
+        Self::ClearCell,
+        Self::ClearScratchpad,
+        Self::AutoFillAllCells,
+        Self::AutoFillEmptyCells,
+        Self::AutoFillSelectedCell,
+        Self::CheckSolvability,
+        Self::CheckProgress,
+        Self::Hint,
+        Self::AuditNotes,
+        Self::ClearMistakes,
+        Self::RevealCell,
+        Self::RevealAll,
+        Self::ToggleCandidateHeatmap,
+        Self::ToggleInfoPanel,
+        Self::NewGame,
+        Self::RegeneratePuzzle,
+        Self::ResetInputs,
+        Self::Settings,
+        Self::Statistics,
+        Self::RaceStandings,
+        Self::Campaign,
+        Self::Learn,
+        Self::BugReport,
+        Self::ExportPuzzle,
+        Self::ExportProgress,
+        Self::CopyPuzzleLink,
+        Self::CopyProgressLink,
+        Self::CopyBoardImage,
+    ];
+
+    /// Returns the name the command is saved under.
+    #[must_use]
+    pub(crate) const fn name(self) -> &'static str {
+        match self {
+            Self::Undo => "undo",
+            Self::Redo => "redo",
+            Self::ToggleInputMode => "toggle_input_mode",
+            Self::ClearCell => "clear_cell",
+            Self::ClearScratchpad => "clear_scratchpad",
+            Self::AutoFillAllCells => "auto_fill_all_cells",
+            Self::AutoFillEmptyCells => "auto_fill_empty_cells",
+            Self::AutoFillSelectedCell => "auto_fill_selected_cell",
+            Self::CheckSolvability => "check_solvability",
+            Self::CheckProgress => "check_progress",
+            Self::Hint => "hint",
+            Self::AuditNotes => "audit_notes",
+            Self::ClearMistakes => "clear_mistakes",
+            Self::RevealCell => "reveal_cell",
+            Self::RevealAll => "reveal_all",
+            Self::ToggleCandidateHeatmap => "toggle_candidate_heatmap",
+            Self::ToggleInfoPanel => "toggle_info_panel",
+            Self::NewGame => "new_game",
+            Self::RegeneratePuzzle => "regenerate_puzzle",
+            Self::ResetInputs => "reset_inputs",
+            Self::Settings => "settings",
+            Self::Statistics => "statistics",
+            Self::RaceStandings => "race_standings",
+            Self::Campaign => "campaign",
+            Self::Learn => "learn",
+            Self::BugReport => "bug_report",
+            Self::ExportPuzzle => "export_puzzle",
+            Self::ExportProgress => "export_progress",
+            Self::CopyPuzzleLink => "copy_puzzle_link",
+            Self::CopyProgressLink => "copy_progress_link",
+            Self::CopyBoardImage => "copy_board_image",
+        }
+    }
+
+    /// Returns the command saved under `name`.
+    #[must_use]
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|id| id.name() == name)
+    }
+}
+
+/// A key with the exact modifiers that trigger it.
+///
+/// `command` is Ctrl on Windows and Linux and Cmd on Mac.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct KeyTrigger {
+    pub(crate) key: Key,
+    pub(crate) command: bool,
+    pub(crate) shift: bool,
+}
+
+impl KeyTrigger {
+    pub(crate) const fn new(key: Key, command: bool, shift: bool) -> Self {
+        Self {
+            key,
+            command,
+            shift,
+        }
+    }
+
+    pub(crate) const fn plain(key: Key) -> Self {
+        Self::new(key, false, false)
+    }
+
+    pub(crate) const fn command(key: Key) -> Self {
+        Self::new(key, true, false)
+    }
+
+    pub(crate) const fn shift(key: Key) -> Self {
+        Self::new(key, false, true)
+    }
+
+    pub(crate) const fn command_shift(key: Key) -> Self {
+        Self::new(key, true, true)
+    }
+}
+
+/// Modifiers that turn the digit keys into note chords.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum NoteChord {
+    Shift,
+    /// Ctrl on Windows and Linux and Cmd on Mac.
+    Command,
+    CommandShift,
+}
+
+impl NoteChord {
+    #[must_use]
+    pub(crate) const fn all() -> [Self; 3] {
+        [Self::Shift, Self::Command, Self::CommandShift]
+    }
+
+    /// Returns the trigger of the chord on `key`.
+    #[must_use]
+    pub(crate) const fn trigger(self, key: Key) -> KeyTrigger {
+        match self {
+            Self::Shift => KeyTrigger::shift(key),
+            Self::Command => KeyTrigger::command(key),
+            Self::CommandShift => KeyTrigger::command_shift(key),
+        }
+    }
+}
+
+/// The player's changes to the default key bindings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct KeyBindings {
+    /// Chord that toggles a corner note in the selected cells.
+    pub(crate) corner_note: NoteChord,
+    /// Chord that toggles a center note in the selected cells.
+    pub(crate) center_note: NoteChord,
+    /// Shortcuts of the commands the player rebound, replacing their
+    /// defaults; `None` leaves the command without a shortcut.
+    pub(crate) commands: BTreeMap<CommandId, Option<KeyTrigger>>,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            corner_note: NoteChord::Shift,
+            center_note: NoteChord::Command,
+            commands: BTreeMap::new(),
+        }
+    }
+}
+
+impl KeyBindings {
+    /// Returns the chord of the notes of `kind`.
+    #[must_use]
+    pub(crate) fn note_chord(&self, kind: NoteKind) -> NoteChord {
+        match kind {
+            NoteKind::Corner => self.corner_note,
+            NoteKind::Center => self.center_note,
+        }
+    }
+
+    /// Binds the notes of `kind` to `chord`, handing the chord they had to
+    /// the other kind of notes if it used `chord`.
+    pub(crate) fn set_note_chord(&mut self, kind: NoteKind, chord: NoteChord) {
+        let (this, other) = match kind {
+            NoteKind::Corner => (&mut self.corner_note, &mut self.center_note),
+            NoteKind::Center => (&mut self.center_note, &mut self.corner_note),
+        };
+        if *other == chord {
+            *other = *this;
+        }
+        *this = chord;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn note_chords_swap_instead_of_clashing() {
+        let mut bindings = KeyBindings::default();
+        bindings.set_note_chord(NoteKind::Corner, NoteChord::Command);
+        assert_eq!(bindings.corner_note, NoteChord::Command);
+        assert_eq!(bindings.center_note, NoteChord::Shift);
+
+        bindings.set_note_chord(NoteKind::Center, NoteChord::CommandShift);
+        assert_eq!(bindings.corner_note, NoteChord::Command);
+        assert_eq!(bindings.center_note, NoteChord::CommandShift);
+    }
+
+    #[test]
+    fn command_names_round_trip() {
+        for id in CommandId::ALL {
+            assert_eq!(CommandId::from_name(id.name()), Some(id));
+        }
+    }
+}
@@ -1,17 +1,35 @@
-pub(crate) use self::{app_state::*, history::*, new_game_options::*, settings::*, ui_state::*};
+pub(crate) use self::{
+    action_log::*, app_state::*, campaign::*, generation_trace::*, hint_usage::*, history::*,
+    key_bindings::*, new_game_options::*, puzzle_metadata::*, race::*, scratchpad::*, settings::*,
+    solve_log::*, timed_game::*, ui_state::*,
+};
 
+mod action_log;
 mod app_state;
+mod campaign;
+mod generation_trace;
+mod hint_usage;
 mod history;
+mod key_bindings;
 mod new_game_options;
+mod puzzle_metadata;
+mod race;
+mod scratchpad;
 mod settings;
+mod solve_log;
+mod timed_game;
 mod ui_state;
 
 #[cfg(test)]
 mod tests {
-    use numelace_core::{Digit, DigitGrid, Position};
+    use numelace_core::{Digit, DigitGrid, DigitPositions, Position};
     use numelace_game::{CellState, Game, InputDigitOptions};
+    use numelace_solver::technique::TechniqueId;
 
-    use super::AppState;
+    use super::{
+        AnimationPolicy, AppState, DifficultyPreset, HintUsage, MotionPreference, Settings,
+        SolverSettings,
+    };
 
     fn fixed_game() -> Game {
         let problem: DigitGrid = "\
@@ -28,7 +46,7 @@ mod tests {
         .parse()
         .unwrap();
         let solution: DigitGrid =
-            "185362947793148526246795183564239871931874265827516394318427659672951438459683712"
+            "815362947793841526246795813564239178938174265127586394381427659672958431459613782"
                 .parse()
                 .unwrap();
         let filled: DigitGrid = "\
@@ -94,4 +112,122 @@ mod tests {
         ));
         assert_eq!(app_state.selected_cell(), Some(Position::new(0, 2)));
     }
+
+    #[test]
+    fn solver_settings_keep_fundamentals_enabled_and_reorder() {
+        let mut solver = SolverSettings::default();
+        solver.set_technique_enabled(TechniqueId::NakedSingle, false);
+        solver.set_technique_enabled(TechniqueId::XWing, false);
+        assert!(solver.priority.is_enabled(TechniqueId::NakedSingle));
+        assert!(!solver.priority.is_enabled(TechniqueId::XWing));
+
+        solver.move_technique_down(1);
+        assert_eq!(
+            solver.technique_order()[..3],
+            [
+                TechniqueId::FullHouse,
+                TechniqueId::HiddenSingle,
+                TechniqueId::NakedSingle
+            ]
+        );
+        assert!(
+            solver
+                .priority
+                .techniques()
+                .iter()
+                .all(|tech| tech.technique_id() != Some(TechniqueId::XWing))
+        );
+    }
+
+    #[test]
+    fn assist_profiles_switch_with_difficulty() {
+        let mut settings = Settings::default();
+        settings.switch_assist_profile(DifficultyPreset::Expert);
+        assert_eq!(settings.assist_profiles.active, None);
+
+        settings.assist_profiles.enabled = true;
+        settings.switch_assist_profile(DifficultyPreset::Expert);
+        settings.assist.block_rule_violations = false;
+
+        settings.switch_assist_profile(DifficultyPreset::Basic);
+        assert!(settings.assist.block_rule_violations);
+        settings.assist.keypad_candidate_counts = true;
+
+        settings.switch_assist_profile(DifficultyPreset::Expert);
+        assert!(!settings.assist.block_rule_violations);
+        assert!(!settings.assist.keypad_candidate_counts);
+        settings.switch_assist_profile(DifficultyPreset::Basic);
+        assert!(settings.assist.keypad_candidate_counts);
+    }
+
+    #[test]
+    fn assist_profiles_start_from_preset_defaults() {
+        let mut settings = Settings::default();
+        settings.assist_profiles.enabled = true;
+        settings.switch_assist_profile(DifficultyPreset::Basic);
+        settings.assist.candidate_heatmap = true;
+
+        // Unsaved difficulties start from their own defaults, not the
+        // settings of the previous one.
+        settings.switch_assist_profile(DifficultyPreset::Advanced);
+        assert!(!settings.assist.candidate_heatmap);
+        assert!(settings.assist.block_rule_violations);
+
+        settings.switch_assist_profile(DifficultyPreset::Expert);
+        assert!(!settings.assist.block_rule_violations);
+        assert!(!settings.assist.highlight.conflict);
+        assert!(!settings.assist.notes.auto_remove_peer_notes_on_fill);
+    }
+
+    #[test]
+    fn motion_preference_resolves_against_the_system() {
+        for system_prefers_reduced in [false, true] {
+            let resolve = |preference| AnimationPolicy::resolve(preference, system_prefers_reduced);
+            assert_eq!(
+                resolve(MotionPreference::System).is_reduced(),
+                system_prefers_reduced
+            );
+            assert!(resolve(MotionPreference::Full).is_full());
+            assert!(resolve(MotionPreference::Reduced).is_reduced());
+        }
+    }
+
+    #[test]
+    fn hint_usage_orders_techniques_by_count() {
+        let mut usage = HintUsage::default();
+        for key in [
+            "naked_single",
+            "x_wing",
+            "x_wing",
+            "hidden_single",
+            "x_wing",
+        ] {
+            usage.record(key);
+        }
+        assert_eq!(usage.total(), 5);
+        assert_eq!(usage.most_hinted()[0], ("x_wing", 3));
+
+        usage.clear();
+        assert_eq!(usage.total(), 0);
+    }
+
+    #[test]
+    fn hinted_cells_count_once_per_house() {
+        let mut app_state = AppState::new(fixed_game());
+        let mut cells = DigitPositions::new();
+        cells.insert(Position::new(0, 0));
+        cells.insert(Position::new(0, 5));
+        app_state.record_hinted_cells(cells);
+        app_state.record_hinted_cells(DigitPositions::from_elem(Position::new(0, 0)));
+
+        assert_eq!(app_state.hint_heat_map.count(Position::new(0, 0)), 2);
+        assert_eq!(app_state.hint_heat_map.count(Position::new(0, 5)), 1);
+        let houses = app_state.hint_usage.houses();
+        assert_eq!(houses.rows[0], 2);
+        assert_eq!((houses.cols[0], houses.cols[5]), (2, 1));
+        assert_eq!((houses.boxes[0], houses.boxes[1]), (2, 1));
+
+        app_state.hint_usage.clear();
+        assert!(app_state.hint_usage.houses().is_empty());
+    }
 }
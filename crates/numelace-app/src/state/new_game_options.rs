@@ -1,6 +1,11 @@
 use std::collections::BTreeMap;
 
-use numelace_solver::{TechniqueTier, technique};
+use numelace_core::ChessRules;
+use numelace_generator::ClueSymmetry;
+use numelace_solver::{
+    TechniqueTier,
+    technique::{self, TechniqueId},
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub(crate) enum DifficultyPreset {
@@ -70,12 +75,51 @@ impl DifficultyPreset {
     }
 }
 
+/// How a new game is played.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, derive_more::IsVariant)]
+pub(crate) enum GameMode {
+    #[default]
+    Classic,
+    /// Play against a par time estimated from the puzzle's difficulty.
+    Timed,
+    /// Two players take turns solving identical copies of the puzzle.
+    HotSeatRace,
+}
+
+impl GameMode {
+    #[must_use]
+    pub(crate) const fn label(self) -> &'static str {
+        match self {
+            Self::Classic => "Classic",
+            Self::Timed => "Timed",
+            Self::HotSeatRace => "Hot-Seat Race",
+        }
+    }
+
+    #[must_use]
+    pub(crate) const fn all() -> [GameMode; 3] {
+        [Self::Classic, Self::Timed, Self::HotSeatRace]
+    }
+
+    pub(crate) fn parse(label: &str) -> Option<Self> {
+        let normalized = label.trim();
+        Self::all()
+            .into_iter()
+            .find(|mode| normalized.eq_ignore_ascii_case(mode.label()))
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct NewGameOptions {
     pub(crate) difficulty: DifficultyPreset,
-    pub(crate) techniques: BTreeMap<&'static str, bool>,
+    pub(crate) techniques: BTreeMap<TechniqueId, bool>,
     pub(crate) seed: String,
     pub(crate) max_attempts: usize,
+    pub(crate) anti_knight: bool,
+    pub(crate) anti_king: bool,
+    pub(crate) jigsaw: bool,
+    pub(crate) symmetry: ClueSymmetry,
+    pub(crate) mode: GameMode,
 }
 
 impl Default for NewGameOptions {
@@ -85,6 +129,11 @@ impl Default for NewGameOptions {
             techniques: BTreeMap::new(),
             seed: String::new(),
             max_attempts: Self::default_max_attempts(),
+            anti_knight: false,
+            anti_king: false,
+            jigsaw: false,
+            symmetry: ClueSymmetry::None,
+            mode: GameMode::Classic,
         };
         options.apply_preset(DifficultyPreset::Basic);
         options
@@ -96,6 +145,13 @@ impl NewGameOptions {
         100
     }
 
+    #[must_use]
+    pub(crate) fn chess_rules(&self) -> ChessRules {
+        ChessRules::new()
+            .with_anti_knight(self.anti_knight)
+            .with_anti_king(self.anti_king)
+    }
+
     pub(crate) fn apply_preset(&mut self, preset: DifficultyPreset) {
         self.difficulty = preset;
         if preset == DifficultyPreset::Custom {
@@ -105,8 +161,8 @@ impl NewGameOptions {
         self.set_techniques_by_tier(tier);
     }
 
-    pub(crate) fn set_technique_enabled(&mut self, technique_id: &'static str, mut enabled: bool) {
-        let Some(technique) = technique::find_technique_by_id(technique_id) else {
+    pub(crate) fn set_technique_enabled(&mut self, technique_id: TechniqueId, mut enabled: bool) {
+        let Some(technique) = technique::by_id(technique_id) else {
             return;
         };
         if technique.tier().is_fundamental() {
@@ -117,34 +173,61 @@ impl NewGameOptions {
     }
 
     #[must_use]
-    pub(crate) fn is_technique_enabled(&self, technique_id: &'static str) -> bool {
+    pub(crate) fn is_technique_enabled(&self, technique_id: TechniqueId) -> bool {
         self.techniques.get(&technique_id).copied().unwrap_or(false)
     }
 
     pub(crate) fn set_techniques_by_tier(&mut self, tier: TechniqueTier) {
         self.techniques.clear();
         for technique in technique::all_techniques() {
-            self.techniques
-                .insert(technique.id(), technique.tier() <= tier);
+            let Some(id) = technique.technique_id() else {
+                continue;
+            };
+            self.techniques.insert(id, technique.tier() <= tier);
         }
     }
 
     pub(crate) fn set_enabled_techniques(
         &mut self,
-        enabled: impl IntoIterator<Item = &'static str>,
+        enabled: impl IntoIterator<Item = TechniqueId>,
     ) {
         self.techniques.clear();
         for technique in technique::all_techniques() {
-            let enabled = technique.tier().is_fundamental();
-            self.techniques.insert(technique.id(), enabled);
+            let Some(id) = technique.technique_id() else {
+                continue;
+            };
+            self.techniques
+                .insert(id, technique.tier().is_fundamental());
         }
-        for key in enabled {
-            self.techniques.insert(key, true);
+        for id in enabled {
+            self.techniques.insert(id, true);
         }
         self.difficulty = DifficultyPreset::Custom;
     }
 }
 
+/// Difficulty rated for the current puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PuzzleDifficulty {
+    /// The techniques solve the puzzle; the preset matches the hardest one needed.
+    Rated(DifficultyPreset),
+    /// The techniques get stuck, so solving requires guessing.
+    BeyondTechniques,
+}
+
+impl PuzzleDifficulty {
+    /// Returns the preset whose assist profile fits the puzzle.
+    ///
+    /// Puzzles beyond the techniques use the Expert profile.
+    #[must_use]
+    pub(crate) const fn profile_preset(self) -> DifficultyPreset {
+        match self {
+            Self::Rated(preset) => preset,
+            Self::BeyondTechniques => DifficultyPreset::Expert,
+        }
+    }
+}
+
 impl DifficultyPreset {
     #[must_use]
     pub(crate) const fn tier(self) -> TechniqueTier {
@@ -0,0 +1,317 @@
+use std::{collections::BTreeMap, fmt::Write as _, time::Duration};
+
+use numelace_core::{ChessRules, DigitGrid, PuzzleFingerprint};
+use numelace_game::Game;
+use web_time::{SystemTime, UNIX_EPOCH};
+
+use crate::state::{CampaignPuzzle, DifficultyPreset, PuzzleDifficulty};
+
+/// Where a puzzle in the metadata store came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PuzzleSource {
+    Generated,
+    /// Imported from a share link.
+    Shared,
+    /// One of the bundled campaign puzzles.
+    Pack(CampaignPuzzle),
+}
+
+/// What is known about one puzzle the player has played.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PuzzleMetadata {
+    /// `None` for puzzles recorded before sources were tracked.
+    pub(crate) source: Option<PuzzleSource>,
+    pub(crate) difficulty: Option<PuzzleDifficulty>,
+    /// Date the puzzle was first recorded (`YYYY-MM-DD`, UTC).
+    pub(crate) added_on: String,
+    /// Date the puzzle was first solved (`YYYY-MM-DD`, UTC).
+    pub(crate) solved_on: Option<String>,
+    /// Fastest solve in timed mode.
+    pub(crate) best_time: Option<Duration>,
+}
+
+impl PuzzleMetadata {
+    #[must_use]
+    pub(crate) fn new(source: Option<PuzzleSource>, added_on: String) -> Self {
+        Self {
+            source,
+            difficulty: None,
+            added_on,
+            solved_on: None,
+            best_time: None,
+        }
+    }
+}
+
+/// Solved puzzles and best time for one difficulty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct DifficultyRecord {
+    pub(crate) difficulty: PuzzleDifficulty,
+    pub(crate) solved: usize,
+    pub(crate) best_time: Option<Duration>,
+}
+
+/// Classic puzzles the player has imported or solved, keyed by fingerprint.
+///
+/// A fingerprint hashes the canonical form of the givens, so a puzzle is
+/// recognized again after it was rotated, mirrored or relabelled. Variant
+/// puzzles have no fingerprint, since their cages, regions and markers do not
+/// survive those transformations.
+///
+/// Puzzles are added when a share link is imported or when they are first
+/// solved. The store backs the import dialog, the statistics and the pack
+/// browser.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct PuzzleMetadataStore {
+    entries: BTreeMap<PuzzleFingerprint, PuzzleMetadata>,
+}
+
+impl PuzzleMetadataStore {
+    #[must_use]
+    pub(crate) fn from_entries(entries: BTreeMap<PuzzleFingerprint, PuzzleMetadata>) -> Self {
+        Self { entries }
+    }
+
+    #[must_use]
+    pub(crate) fn entries(&self) -> &BTreeMap<PuzzleFingerprint, PuzzleMetadata> {
+        &self.entries
+    }
+
+    #[must_use]
+    pub(crate) fn get(&self, fingerprint: PuzzleFingerprint) -> Option<&PuzzleMetadata> {
+        self.entries.get(&fingerprint)
+    }
+
+    /// Returns the entry of the campaign puzzle `puzzle`, if it was recorded.
+    ///
+    /// This looks the puzzle up by source, so the pack browser needs no
+    /// fingerprints.
+    #[must_use]
+    pub(crate) fn get_pack_puzzle(&self, puzzle: CampaignPuzzle) -> Option<&PuzzleMetadata> {
+        self.entries
+            .values()
+            .find(|entry| entry.source == Some(PuzzleSource::Pack(puzzle)))
+    }
+
+    /// Adds the puzzle with `fingerprint` on `date`, keeping an existing entry.
+    pub(crate) fn add(
+        &mut self,
+        fingerprint: PuzzleFingerprint,
+        source: PuzzleSource,
+        date: &str,
+    ) -> &mut PuzzleMetadata {
+        let entry = self
+            .entries
+            .entry(fingerprint)
+            .or_insert_with(|| PuzzleMetadata::new(Some(source), date.to_owned()));
+        entry.source.get_or_insert(source);
+        entry
+    }
+
+    /// Records the puzzle with `fingerprint` as solved on `date`, keeping an
+    /// earlier date and a faster time.
+    pub(crate) fn record_solved(
+        &mut self,
+        fingerprint: PuzzleFingerprint,
+        source: PuzzleSource,
+        difficulty: Option<PuzzleDifficulty>,
+        date: &str,
+        time: Option<Duration>,
+    ) {
+        let entry = self.add(fingerprint, source, date);
+        if difficulty.is_some() {
+            entry.difficulty = difficulty;
+        }
+        entry.solved_on.get_or_insert_with(|| date.to_owned());
+        entry.best_time = faster(entry.best_time, time);
+    }
+
+    /// Returns the number of puzzles solved so far.
+    #[must_use]
+    pub(crate) fn solved_count(&self) -> usize {
+        self.entries
+            .values()
+            .filter(|entry| entry.solved_on.is_some())
+            .count()
+    }
+
+    /// Returns the solved puzzles and best time of each rated difficulty,
+    /// easiest first, skipping difficulties with nothing solved.
+    #[must_use]
+    pub(crate) fn difficulty_records(&self) -> Vec<DifficultyRecord> {
+        DifficultyPreset::all()
+            .into_iter()
+            .map(PuzzleDifficulty::Rated)
+            .chain([PuzzleDifficulty::BeyondTechniques])
+            .filter_map(|difficulty| {
+                let solved = self.entries.values().filter(|entry| {
+                    entry.solved_on.is_some() && entry.difficulty == Some(difficulty)
+                });
+                let (count, best_time) = solved.fold((0, None), |(count, best), entry| {
+                    (count + 1, faster(best, entry.best_time))
+                });
+                (count > 0).then_some(DifficultyRecord {
+                    difficulty,
+                    solved: count,
+                    best_time,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Returns the shorter of two optional times.
+#[must_use]
+pub(crate) fn faster(a: Option<Duration>, b: Option<Duration>) -> Option<Duration> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+/// Hashes the canonical form of `game`'s givens, for classic puzzles only.
+///
+/// This searches every variant of the puzzle, so call it once per puzzle
+/// rather than per frame.
+#[must_use]
+pub(crate) fn fingerprint(game: &Game) -> Option<PuzzleFingerprint> {
+    if !game.is_initialized()
+        || !game.regions().is_standard()
+        || !game.cages().is_empty()
+        || !game.edge_constraints().is_empty()
+        || game.chess_rules() != ChessRules::default()
+    {
+        return None;
+    }
+    let mut givens = DigitGrid::new();
+    for pos in game.given_positions() {
+        givens.set(pos, game.cell(pos).as_given());
+    }
+    Some(givens.fingerprint())
+}
+
+/// Returns the current date in UTC as `YYYY-MM-DD`.
+#[must_use]
+pub(crate) fn today() -> String {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+    civil_date(secs / 86_400)
+}
+
+/// Formats the date `days` after 1970-01-01.
+fn civil_date(days: u64) -> String {
+    // Howard Hinnant's days-to-civil algorithm, with years starting in March.
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+    let mut out = String::new();
+    let _ = write!(out, "{year:04}-{month:02}-{day:02}");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use numelace_core::{DigitGrid, Position};
+    use numelace_game::Game;
+
+    use super::*;
+
+    const SOLUTION: &str =
+        "185362947793148526246795183564239871931874265827516394318427659672951438459683712";
+
+    fn game(solution: &str) -> Game {
+        let solution: DigitGrid = solution.parse().unwrap();
+        let mut problem = solution.clone();
+        for pos in Position::ALL.into_iter().filter(|pos| pos.index() % 3 == 0) {
+            problem.clear(pos);
+        }
+        Game::from_problem_filled_notes(&problem, &solution, &DigitGrid::new(), &[[0; 9]; 9])
+            .unwrap()
+    }
+
+    #[test]
+    fn fingerprints_recognize_relabelled_puzzles() {
+        let relabeled: String = SOLUTION
+            .chars()
+            .map(|ch| char::from(b'0' + 10 - (ch as u8 - b'0')))
+            .collect();
+        let original = fingerprint(&game(SOLUTION)).unwrap();
+        assert_eq!(fingerprint(&game(&relabeled)), Some(original));
+        assert_eq!(fingerprint(&Game::new_empty()), None);
+    }
+
+    #[test]
+    fn solves_keep_the_first_date_and_the_best_time() {
+        let mut store = PuzzleMetadataStore::default();
+        let fingerprint = PuzzleFingerprint::from(1);
+        let rated = PuzzleDifficulty::Rated(DifficultyPreset::Basic);
+        store.add(fingerprint, PuzzleSource::Shared, "2026-10-14");
+        store.record_solved(
+            fingerprint,
+            PuzzleSource::Generated,
+            Some(rated),
+            "2026-10-15",
+            Some(Duration::from_secs(300)),
+        );
+        store.record_solved(
+            fingerprint,
+            PuzzleSource::Generated,
+            None,
+            "2026-10-16",
+            Some(Duration::from_secs(200)),
+        );
+
+        let entry = store.get(fingerprint).unwrap();
+        assert_eq!(entry.source, Some(PuzzleSource::Shared));
+        assert_eq!(entry.added_on, "2026-10-14");
+        assert_eq!(entry.solved_on.as_deref(), Some("2026-10-15"));
+        assert_eq!(entry.best_time, Some(Duration::from_secs(200)));
+        assert_eq!(
+            store.difficulty_records(),
+            [DifficultyRecord {
+                difficulty: rated,
+                solved: 1,
+                best_time: Some(Duration::from_secs(200)),
+            }]
+        );
+    }
+
+    #[test]
+    fn pack_puzzles_are_found_by_source() {
+        let mut store = PuzzleMetadataStore::default();
+        let puzzle = CampaignPuzzle { pack: 0, index: 2 };
+        store.record_solved(
+            PuzzleFingerprint::from(7),
+            PuzzleSource::Pack(puzzle),
+            None,
+            "2026-10-15",
+            None,
+        );
+        assert!(store.get_pack_puzzle(puzzle).is_some());
+        assert!(
+            store
+                .get_pack_puzzle(CampaignPuzzle { pack: 0, index: 3 })
+                .is_none()
+        );
+        assert_eq!(store.solved_count(), 1);
+    }
+
+    #[test]
+    fn civil_dates_follow_the_calendar() {
+        assert_eq!(civil_date(0), "1970-01-01");
+        assert_eq!(civil_date(11_016), "2000-02-29");
+        assert_eq!(civil_date(20_741), "2026-10-15");
+    }
+}
@@ -0,0 +1,245 @@
+use std::{cmp::Ordering, mem};
+
+use numelace_game::Game;
+
+use crate::state::{History, TimedGame};
+
+/// Player taking part in a hot-seat race.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Player {
+    One,
+    Two,
+}
+
+impl Player {
+    pub(crate) const ALL: [Self; 2] = [Self::One, Self::Two];
+
+    #[must_use]
+    pub(crate) const fn other(self) -> Self {
+        match self {
+            Self::One => Self::Two,
+            Self::Two => Self::One,
+        }
+    }
+
+    const fn index(self) -> usize {
+        match self {
+            Self::One => 0,
+            Self::Two => 1,
+        }
+    }
+}
+
+/// How many of the cells to solve a player has filled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct RaceProgress {
+    pub(crate) filled: usize,
+    pub(crate) total: usize,
+}
+
+impl RaceProgress {
+    #[must_use]
+    pub(crate) fn of(game: &Game) -> Self {
+        let filled = game.filled_positions().len();
+        Self {
+            filled,
+            total: filled + game.empty_positions().len(),
+        }
+    }
+
+    #[must_use]
+    pub(crate) fn percent(self) -> usize {
+        (self.filled * 100).checked_div(self.total).unwrap_or(100)
+    }
+}
+
+/// Clock and progress of one race player.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct RaceStanding {
+    pub(crate) player: Player,
+    pub(crate) clock: TimedGame,
+    pub(crate) progress: RaceProgress,
+}
+
+/// Snapshot of a race for display.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct RaceSummary {
+    /// Player at the board.
+    pub(crate) current: Player,
+    pub(crate) standings: [RaceStanding; 2],
+}
+
+impl RaceSummary {
+    #[must_use]
+    pub(crate) fn is_finished(&self) -> bool {
+        self.standings
+            .iter()
+            .all(|standing| standing.clock.finished)
+    }
+
+    /// Returns the player with the faster time once both have finished, or
+    /// `None` for a tie or a race still in progress.
+    #[must_use]
+    pub(crate) fn winner(&self) -> Option<Player> {
+        if !self.is_finished() {
+            return None;
+        }
+        let [one, two] = self.standings.map(|standing| standing.clock.elapsed);
+        match one.cmp(&two) {
+            Ordering::Less => Some(Player::One),
+            Ordering::Greater => Some(Player::Two),
+            Ordering::Equal => None,
+        }
+    }
+}
+
+/// Two players taking turns at identical copies of one puzzle.
+///
+/// The player at the board plays on [`AppState::game`](crate::state::AppState)
+/// with its usual history, so every input, hint, and check works unchanged;
+/// the other player's board and history are parked here until their turn.
+/// Each player has their own clock, which only runs during their turns.
+#[derive(Debug)]
+pub(crate) struct HotSeatRace {
+    current: Player,
+    clocks: [TimedGame; 2],
+    parked_game: Game,
+    parked_history: History,
+}
+
+impl HotSeatRace {
+    #[must_use]
+    pub(crate) fn from_parts(
+        current: Player,
+        clocks: [TimedGame; 2],
+        parked_game: Game,
+        parked_history: History,
+    ) -> Self {
+        Self {
+            current,
+            clocks,
+            parked_game,
+            parked_history,
+        }
+    }
+
+    /// Returns the player at the board.
+    #[must_use]
+    pub(crate) fn current(&self) -> Player {
+        self.current
+    }
+
+    #[must_use]
+    pub(crate) fn clock(&self, player: Player) -> TimedGame {
+        self.clocks[player.index()]
+    }
+
+    pub(crate) fn clock_mut(&mut self, player: Player) -> &mut TimedGame {
+        &mut self.clocks[player.index()]
+    }
+
+    #[must_use]
+    pub(crate) fn parked_game(&self) -> &Game {
+        &self.parked_game
+    }
+
+    #[must_use]
+    pub(crate) fn parked_history(&self) -> &History {
+        &self.parked_history
+    }
+
+    /// Returns whether both players have solved their copy.
+    #[must_use]
+    pub(crate) fn is_finished(&self) -> bool {
+        self.clocks.iter().all(|clock| clock.finished)
+    }
+
+    /// Returns the clocks and progress of both players, given the board in play.
+    #[must_use]
+    pub(crate) fn summary(&self, board: &Game) -> RaceSummary {
+        RaceSummary {
+            current: self.current,
+            standings: Player::ALL.map(|player| RaceStanding {
+                player,
+                clock: self.clock(player),
+                progress: RaceProgress::of(if player == self.current {
+                    board
+                } else {
+                    &self.parked_game
+                }),
+            }),
+        }
+    }
+
+    /// Parks the board of the current player and hands the other one theirs.
+    pub(crate) fn swap_board(&mut self, game: &mut Game, history: &mut History) {
+        mem::swap(game, &mut self.parked_game);
+        mem::swap(history, &mut self.parked_history);
+        self.current = self.current.other();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use numelace_core::{Digit, DigitGrid, Position};
+    use numelace_game::{Game, InputDigitOptions};
+
+    use super::{HotSeatRace, Player, RaceProgress};
+    use crate::state::{History, HistorySource};
+
+    fn new_game(problem: &DigitGrid) -> Game {
+        Game::from_problem_filled_notes(problem, &DigitGrid::new(), &DigitGrid::new(), &[[0; 9]; 9])
+            .unwrap()
+    }
+
+    fn race_on(game: &Game) -> HotSeatRace {
+        let mut history = History::new();
+        history.reset(&HistorySource::new(game, None));
+        HotSeatRace::from_parts(Player::One, Default::default(), game.clone(), history)
+    }
+
+    #[test]
+    fn swap_board_keeps_each_players_progress() {
+        let problem: DigitGrid = format!("1{}", ".".repeat(80)).parse().unwrap();
+        let mut game = new_game(&problem);
+        let mut history = History::new();
+        let mut race = race_on(&game);
+        game.set_digit(
+            Position::new(0, 1),
+            Digit::D2,
+            &InputDigitOptions::default(),
+        )
+        .unwrap();
+
+        race.swap_board(&mut game, &mut history);
+        assert_eq!(race.current(), Player::Two);
+        let summary = race.summary(&game);
+        assert_eq!(summary.standings[0].progress.filled, 1);
+        assert_eq!(summary.standings[1].progress, RaceProgress::of(&game));
+        assert_eq!(summary.standings[1].progress.filled, 0);
+
+        race.swap_board(&mut game, &mut history);
+        assert_eq!(race.current(), Player::One);
+        assert_eq!(RaceProgress::of(&game).filled, 1);
+    }
+
+    #[test]
+    fn winner_needs_both_players_finished() {
+        let game = new_game(&DigitGrid::new());
+        let mut race = race_on(&game);
+        race.clock_mut(Player::Two).elapsed = Duration::from_secs(60);
+        race.clock_mut(Player::Two).finished = true;
+        assert_eq!(race.summary(&game).winner(), None);
+
+        race.clock_mut(Player::One).elapsed = Duration::from_secs(90);
+        race.clock_mut(Player::One).finished = true;
+        assert_eq!(race.summary(&game).winner(), Some(Player::Two));
+
+        race.clock_mut(Player::One).elapsed = Duration::from_secs(60);
+        let summary = race.summary(&game);
+        assert!(summary.is_finished());
+        assert_eq!(summary.winner(), None);
+    }
+}
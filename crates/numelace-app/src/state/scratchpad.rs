@@ -0,0 +1,107 @@
+use eframe::egui::Pos2;
+use numelace_core::Position;
+
+/// What dragging over the board draws while the scratchpad is active.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ScratchTool {
+    /// Freehand strokes following the pointer.
+    Pen,
+    /// Straight lines from the cell where the drag started to the cell where
+    /// it ended.
+    Line,
+}
+
+/// One mark on the scratchpad.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum ScratchStroke {
+    /// A freehand path in grid coordinates: (0, 0) is the top-left corner of
+    /// the grid and (1, 1) the bottom-right, so strokes follow the board when
+    /// the layout is resized.
+    Pen(Vec<Pos2>),
+    /// A line between the centers of two cells.
+    Line { from: Position, to: Position },
+}
+
+impl ScratchStroke {
+    /// Returns whether the stroke would draw nothing visible.
+    #[must_use]
+    pub(crate) fn is_degenerate(&self) -> bool {
+        match self {
+            Self::Pen(points) => points.len() < 2,
+            Self::Line { from, to } => from == to,
+        }
+    }
+}
+
+/// Sketches drawn over the board, such as chains traced by hand.
+///
+/// The scratchpad is not part of the game: undo, saving and sharing ignore
+/// it, and it is cleared when another puzzle is loaded.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Scratchpad {
+    /// Tool that drags over the board draw with; `None` while cells take
+    /// input as usual.
+    pub(crate) tool: Option<ScratchTool>,
+    strokes: Vec<ScratchStroke>,
+}
+
+impl Scratchpad {
+    #[must_use]
+    pub(crate) fn strokes(&self) -> &[ScratchStroke] {
+        &self.strokes
+    }
+
+    /// Selects `tool`, or returns the board to normal input if it was
+    /// already selected.
+    pub(crate) fn toggle_tool(&mut self, tool: ScratchTool) {
+        self.tool = (self.tool != Some(tool)).then_some(tool);
+    }
+
+    /// Adds `stroke`, unless it would draw nothing visible.
+    pub(crate) fn add(&mut self, stroke: ScratchStroke) {
+        if !stroke.is_degenerate() {
+            self.strokes.push(stroke);
+        }
+    }
+
+    /// Removes all strokes, keeping the selected tool.
+    pub(crate) fn clear(&mut self) {
+        self.strokes.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toggling_the_selected_tool_deselects_it() {
+        let mut scratchpad = Scratchpad::default();
+        scratchpad.toggle_tool(ScratchTool::Pen);
+        assert_eq!(scratchpad.tool, Some(ScratchTool::Pen));
+        scratchpad.toggle_tool(ScratchTool::Line);
+        assert_eq!(scratchpad.tool, Some(ScratchTool::Line));
+        scratchpad.toggle_tool(ScratchTool::Line);
+        assert_eq!(scratchpad.tool, None);
+    }
+
+    #[test]
+    fn degenerate_strokes_are_not_added() {
+        let mut scratchpad = Scratchpad::default();
+        let cell = Position::new(2, 3);
+        scratchpad.add(ScratchStroke::Pen(vec![Pos2::new(0.5, 0.5)]));
+        scratchpad.add(ScratchStroke::Line {
+            from: cell,
+            to: cell,
+        });
+        assert_eq!(scratchpad.strokes(), []);
+
+        scratchpad.add(ScratchStroke::Line {
+            from: cell,
+            to: Position::new(2, 7),
+        });
+        assert_eq!(scratchpad.strokes().len(), 1);
+        scratchpad.clear();
+        assert_eq!(scratchpad.strokes(), []);
+    }
+}
@@ -1,45 +1,303 @@
+use std::{collections::HashMap, ops::RangeInclusive, time::Duration};
+
+use numelace_solver::{
+    TechniquePriority,
+    technique::{self, TechniqueId},
+};
+
+use crate::state::{DifficultyPreset, HintStage, KeyBindings};
+
 #[derive(Debug, Default, Clone)]
 pub(crate) struct Settings {
+    pub(crate) language: Language,
     pub(crate) assist: AssistSettings,
+    pub(crate) assist_profiles: AssistProfiles,
+    pub(crate) input: InputSettings,
+    pub(crate) theme: ThemeSettings,
+    pub(crate) display: DisplaySettings,
+    pub(crate) grid_style: GridStyle,
+    pub(crate) motion: MotionPreference,
+    pub(crate) autosave: AutosaveSettings,
+    pub(crate) solver: SolverSettings,
+}
+
+/// Language used for user-facing strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub(crate) enum Language {
+    #[default]
+    English,
+    Japanese,
+}
+
+impl Language {
+    /// Returns the language name written in that language.
+    #[must_use]
+    pub(crate) const fn native_name(self) -> &'static str {
+        match self {
+            Self::English => "English",
+            Self::Japanese => "日本語",
+        }
+    }
+
+    #[must_use]
+    pub(crate) const fn all() -> [Language; 2] {
+        [Self::English, Self::Japanese]
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub(crate) struct InputSettings {
+    pub(crate) order: InputOrder,
+    pub(crate) key_bindings: KeyBindings,
+}
+
+/// Whether a cell or a digit is chosen first when entering digits with the pointer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, derive_more::IsVariant)]
+pub(crate) enum InputOrder {
+    /// Select a cell, then press a keypad digit to enter it.
+    #[default]
+    CellFirst,
+    /// Press a keypad digit to arm it, then tap cells to enter it.
+    DigitFirst,
 }
 
 #[derive(Debug, Clone)]
+#[expect(clippy::struct_excessive_bools)]
 pub(crate) struct AssistSettings {
     pub(crate) block_rule_violations: bool,
+    /// What blocked inputs are checked against; only takes effect together
+    /// with `block_rule_violations`.
+    pub(crate) validation_mode: ValidationMode,
     pub(crate) highlight: HighlightSettings,
     pub(crate) notes: NotesSettings,
+    /// Show on each keypad digit how many empty cells can still take it.
+    pub(crate) keypad_candidate_counts: bool,
+    /// Disable the keypad key of a digit once all nine of it are placed
+    /// correctly.
+    pub(crate) lock_completed_digits: bool,
+    /// Tint cells without a digit by how many candidates they have left.
+    pub(crate) candidate_heatmap: bool,
+    /// Tint cells without a digit by how deep a trial is needed to decide them.
+    pub(crate) deduction_depth_overlay: bool,
+    pub(crate) hint_detail: HintDetail,
 }
 
 impl Default for AssistSettings {
     fn default() -> Self {
         Self {
             block_rule_violations: true,
+            validation_mode: ValidationMode::default(),
             highlight: HighlightSettings::default(),
             notes: NotesSettings::default(),
+            keypad_candidate_counts: false,
+            lock_completed_digits: false,
+            candidate_heatmap: false,
+            deduction_depth_overlay: false,
+            hint_detail: HintDetail::default(),
+        }
+    }
+}
+
+impl AssistSettings {
+    /// Returns the assist settings a difficulty's profile starts from.
+    ///
+    /// Expert puzzles start without blocking or highlighting conflicts and
+    /// without automatic notes; the other difficulties start from the
+    /// defaults.
+    #[must_use]
+    pub(crate) fn for_difficulty(difficulty: DifficultyPreset) -> Self {
+        let defaults = Self::default();
+        if difficulty != DifficultyPreset::Expert {
+            return defaults;
+        }
+        Self {
+            block_rule_violations: false,
+            highlight: HighlightSettings {
+                conflict: false,
+                ..defaults.highlight
+            },
+            notes: NotesSettings {
+                auto_remove_peer_notes_on_fill: false,
+                auto_fill_notes_on_new_or_reset: false,
+            },
+            ..defaults
+        }
+    }
+}
+
+/// What inputs are checked against when rule violations are blocked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum ValidationMode {
+    /// Block digits and notes that conflict with placed digits.
+    #[default]
+    Rules,
+    /// Also block digits that differ from the stored solution, so no wrong
+    /// move can be made.
+    Solution,
+}
+
+/// How much the first request for a hint reveals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum HintDetail {
+    /// Highlight the cells the technique relies on.
+    #[default]
+    ConditionCells,
+    /// Only point at the house or cell to examine; the next request shows the
+    /// condition cells.
+    FocusArea,
+}
+
+impl HintDetail {
+    /// Returns the stage a new hint starts at.
+    #[must_use]
+    pub(crate) const fn first_stage(self) -> HintStage {
+        match self {
+            Self::ConditionCells => HintStage::Stage1,
+            Self::FocusArea => HintStage::Stage0,
+        }
+    }
+}
+
+/// Assist settings remembered separately for each difficulty.
+///
+/// When enabled, starting or loading a game saves the current assist settings
+/// to the profile of the previous game's difficulty and loads the profile of
+/// the new game's difficulty. A difficulty without a saved profile starts
+/// from [`AssistSettings::for_difficulty`].
+#[derive(Debug, Default, Clone)]
+pub(crate) struct AssistProfiles {
+    pub(crate) enabled: bool,
+    /// Difficulty whose profile the current assist settings belong to.
+    pub(crate) active: Option<DifficultyPreset>,
+    pub(crate) saved: HashMap<DifficultyPreset, AssistSettings>,
+}
+
+impl Settings {
+    /// Switches the assist settings to the profile for `difficulty`.
+    ///
+    /// Does nothing unless per-difficulty profiles are enabled. The first
+    /// switch after enabling them keeps the current assist settings as the
+    /// profile for `difficulty`; later switches start difficulties without a
+    /// saved profile from their defaults.
+    pub(crate) fn switch_assist_profile(&mut self, difficulty: DifficultyPreset) {
+        let profiles = &mut self.assist_profiles;
+        if !profiles.enabled {
+            return;
+        }
+        if let Some(active) = profiles.active {
+            profiles.saved.insert(active, self.assist.clone());
+            self.assist = profiles
+                .saved
+                .get(&difficulty)
+                .cloned()
+                .unwrap_or_else(|| AssistSettings::for_difficulty(difficulty));
         }
+        profiles.active = Some(difficulty);
     }
 }
 
 #[derive(Debug, Clone)]
 #[expect(clippy::struct_excessive_bools)]
 pub(crate) struct HighlightSettings {
+    /// Outline the selected cell.
+    pub(crate) selected_cell: bool,
     pub(crate) selected_digit: bool,
+    /// Shade notes of the selected digit.
+    pub(crate) selected_digit_note: bool,
     pub(crate) selected_cell_peer: bool,
     pub(crate) selected_digit_peer: bool,
     pub(crate) conflict: bool,
+    /// Shade cells without a digit where the selected digit is still a candidate.
+    pub(crate) selected_digit_candidate: bool,
+    /// Shade cells without a digit that have exactly two candidates left.
+    pub(crate) bivalue_cell: bool,
+    /// Where the candidates of bi-value cells come from; only takes effect
+    /// together with `bivalue_cell`.
+    pub(crate) bivalue_source: BivalueSource,
+    pub(crate) intensity: HighlightIntensity,
 }
 
 impl Default for HighlightSettings {
     fn default() -> Self {
         Self {
+            selected_cell: true,
             selected_digit: true,
+            selected_digit_note: true,
             selected_cell_peer: false,
             selected_digit_peer: true,
             conflict: true,
+            selected_digit_candidate: false,
+            bivalue_cell: false,
+            bivalue_source: BivalueSource::default(),
+            intensity: HighlightIntensity::default(),
+        }
+    }
+}
+
+/// How strongly each kind of highlight is drawn, from faint to full color.
+///
+/// A faded highlight is blended towards the cell background, or towards the
+/// normal digit color for conflicts, so it never disappears entirely; the
+/// toggles in [`HighlightSettings`] turn highlights off.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct HighlightIntensity {
+    /// Outline of the selected cell.
+    pub(crate) selection: f32,
+    /// Rows, columns and boxes of the selected cell and of the selected digit.
+    pub(crate) house: f32,
+    /// Cells holding the selected digit.
+    pub(crate) same_digit: f32,
+    /// Notes of the selected digit, and cells where it is still a candidate.
+    pub(crate) notes: f32,
+    /// Conflicting digits and notes.
+    pub(crate) conflict: f32,
+}
+
+impl Default for HighlightIntensity {
+    fn default() -> Self {
+        Self {
+            selection: 1.0,
+            house: 1.0,
+            same_digit: 1.0,
+            notes: 1.0,
+            conflict: 1.0,
+        }
+    }
+}
+
+impl HighlightIntensity {
+    pub(crate) const RANGE: RangeInclusive<f32> = 0.25..=1.0;
+
+    /// Returns the intensities clamped to [`Self::RANGE`].
+    #[must_use]
+    pub(crate) fn clamped(self) -> Self {
+        let clamp = |value: f32| {
+            if value.is_finite() {
+                value.clamp(*Self::RANGE.start(), *Self::RANGE.end())
+            } else {
+                1.0
+            }
+        };
+        Self {
+            selection: clamp(self.selection),
+            house: clamp(self.house),
+            same_digit: clamp(self.same_digit),
+            notes: clamp(self.notes),
+            conflict: clamp(self.conflict),
         }
     }
 }
 
+/// Which candidates decide whether a cell is bi-value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum BivalueSource {
+    /// The digits the rules still allow, whatever the notes say.
+    #[default]
+    Candidates,
+    /// The notes the player wrote, so cells without notes are never marked.
+    Notes,
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct NotesSettings {
     pub(crate) auto_remove_peer_notes_on_fill: bool,
@@ -54,3 +312,330 @@ impl Default for NotesSettings {
         }
     }
 }
+
+/// Technique order and enablement used for hints and puzzle grading.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct SolverSettings {
+    pub(crate) priority: TechniquePriority,
+}
+
+impl SolverSettings {
+    /// Returns the IDs of all techniques in application order, including disabled ones
+    /// and those left out until a unique solution is assumed.
+    #[must_use]
+    pub(crate) fn technique_order(&self) -> Vec<TechniqueId> {
+        let mut techniques = technique::all_techniques_assuming_unique_solution();
+        self.priority.sort(&mut techniques);
+        techniques
+            .iter()
+            .filter_map(|tech| tech.technique_id())
+            .collect()
+    }
+
+    /// Enables or disables a technique. Fundamental techniques stay enabled.
+    pub(crate) fn set_technique_enabled(&mut self, technique_id: TechniqueId, enabled: bool) {
+        let Some(technique) = technique::by_id(technique_id) else {
+            return;
+        };
+        self.priority
+            .set_enabled(technique_id, enabled || technique.tier().is_fundamental());
+    }
+
+    /// Swaps the technique at `index` in [`Self::technique_order`] with the next one.
+    pub(crate) fn move_technique_down(&mut self, index: usize) {
+        let mut order = self.technique_order();
+        if index + 1 < order.len() {
+            order.swap(index, index + 1);
+            self.priority.set_order(order);
+        }
+    }
+}
+
+/// Scaling factors for the UI and the digits drawn on the board.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct DisplaySettings {
+    /// Zoom factor applied to the whole UI, including modals and menus.
+    pub(crate) ui_zoom: f32,
+    /// Size of digits in cells and on the keypad, relative to the default.
+    pub(crate) digit_size: f32,
+    /// Size of notes, relative to the default.
+    pub(crate) note_size: f32,
+}
+
+impl Default for DisplaySettings {
+    fn default() -> Self {
+        Self::NORMAL
+    }
+}
+
+impl DisplaySettings {
+    pub(crate) const UI_ZOOM_RANGE: RangeInclusive<f32> = 0.75..=2.0;
+    pub(crate) const DIGIT_SIZE_RANGE: RangeInclusive<f32> = 0.75..=1.2;
+    pub(crate) const NOTE_SIZE_RANGE: RangeInclusive<f32> = 0.75..=1.4;
+
+    pub(crate) const NORMAL: Self = Self {
+        ui_zoom: 1.0,
+        digit_size: 1.0,
+        note_size: 1.0,
+    };
+
+    pub(crate) const LARGE_PRINT: Self = Self {
+        ui_zoom: 1.5,
+        digit_size: 1.15,
+        note_size: 1.4,
+    };
+
+    /// Returns the settings with every factor clamped to its valid range.
+    #[must_use]
+    pub(crate) fn clamped(self) -> Self {
+        let clamp = |value: f32, range: RangeInclusive<f32>| {
+            if value.is_finite() {
+                value.clamp(*range.start(), *range.end())
+            } else {
+                1.0
+            }
+        };
+        Self {
+            ui_zoom: clamp(self.ui_zoom, Self::UI_ZOOM_RANGE),
+            digit_size: clamp(self.digit_size, Self::DIGIT_SIZE_RANGE),
+            note_size: clamp(self.note_size, Self::NOTE_SIZE_RANGE),
+        }
+    }
+}
+
+/// How the board draws notes, givens and coordinates.
+///
+/// Players are used to different conventions depending on where they learned
+/// to solve, so these only change the drawing, never what the cells hold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) struct GridStyle {
+    pub(crate) notes: NoteLayout,
+    /// Whether notes are placed in the space all nine would take, so they
+    /// stay put as notes are added or removed. Only [`NoteLayout::Linear`]
+    /// moves notes otherwise.
+    pub(crate) reserve_note_space: bool,
+    pub(crate) givens: GivenStyle,
+    /// Whether row and column numbers are shown around the board.
+    pub(crate) coordinates: bool,
+}
+
+/// Where each note is placed in its cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum NoteLayout {
+    /// Every digit has its own spot in a 3x3 mini-grid, like a keypad.
+    #[default]
+    MiniGrid,
+    /// Notes are written one after another in ascending order.
+    Linear,
+}
+
+/// How given digits stand out from the digits the player filled in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum GivenStyle {
+    /// Givens differ from filled digits by color only.
+    #[default]
+    Plain,
+    /// Givens are drawn in a heavier weight.
+    Bold,
+    /// Givens are drawn inside a box.
+    Boxed,
+}
+
+/// Whether the UI animates.
+///
+/// Resolved to an [`AnimationPolicy`] every frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum MotionPreference {
+    /// Follow the system's reduced-motion preference where the platform
+    /// reports one, and animate otherwise.
+    #[default]
+    System,
+    /// Always animate.
+    Full,
+    /// Never animate, and repaint less often while waiting for background work.
+    Reduced,
+}
+
+/// Whether the UI animates in the current frame.
+///
+/// Resolved from the [`MotionPreference`] setting and the system's
+/// reduced-motion preference, and passed to the UI modules that animate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, derive_more::IsVariant)]
+pub(crate) enum AnimationPolicy {
+    /// Widgets animate and spinners turn, repainting every frame.
+    #[default]
+    Full,
+    /// Widgets switch state instantly, progress is shown without motion, and
+    /// waiting repaints only a few times per second.
+    Reduced,
+}
+
+impl AnimationPolicy {
+    #[must_use]
+    pub(crate) fn resolve(preference: MotionPreference, system_prefers_reduced: bool) -> Self {
+        match preference {
+            MotionPreference::System if system_prefers_reduced => Self::Reduced,
+            MotionPreference::System | MotionPreference::Full => Self::Full,
+            MotionPreference::Reduced => Self::Reduced,
+        }
+    }
+}
+
+/// How often progress is written to durable storage.
+///
+/// Changes in between are kept in the crash recovery journal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct AutosaveSettings {
+    pub(crate) interval_secs: u32,
+}
+
+impl Default for AutosaveSettings {
+    fn default() -> Self {
+        Self { interval_secs: 30 }
+    }
+}
+
+impl AutosaveSettings {
+    pub(crate) const INTERVAL_SECS_RANGE: RangeInclusive<u32> = 5..=300;
+
+    /// Returns the settings with the interval clamped to its valid range.
+    #[must_use]
+    pub(crate) fn clamped(self) -> Self {
+        let range = Self::INTERVAL_SECS_RANGE;
+        Self {
+            interval_secs: self.interval_secs.clamp(*range.start(), *range.end()),
+        }
+    }
+
+    #[must_use]
+    pub(crate) fn interval(self) -> Duration {
+        Duration::from_secs(self.interval_secs.into())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub(crate) enum ThemePreset {
+    /// Derive colors from the egui light/dark visuals.
+    #[default]
+    System,
+    Light,
+    Dark,
+    HighContrast,
+    Custom,
+}
+
+impl ThemePreset {
+    #[must_use]
+    pub(crate) const fn label(self) -> &'static str {
+        match self {
+            Self::System => "System",
+            Self::Light => "Light",
+            Self::Dark => "Dark",
+            Self::HighContrast => "High Contrast",
+            Self::Custom => "Custom",
+        }
+    }
+
+    #[must_use]
+    pub(crate) const fn all() -> [ThemePreset; 5] {
+        [
+            Self::System,
+            Self::Light,
+            Self::Dark,
+            Self::HighContrast,
+            Self::Custom,
+        ]
+    }
+
+    pub(crate) fn parse(label: &str) -> Option<Self> {
+        let normalized = label.trim();
+        Self::all()
+            .into_iter()
+            .find(|preset| normalized.eq_ignore_ascii_case(preset.label()))
+    }
+}
+
+/// sRGB colors used by the grid and keypad renderers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ThemeColors {
+    pub(crate) background: [u8; 3],
+    pub(crate) border: [u8; 3],
+    pub(crate) given: [u8; 3],
+    pub(crate) filled: [u8; 3],
+    pub(crate) note: [u8; 3],
+    pub(crate) selected_cell: [u8; 3],
+    pub(crate) selected_digit: [u8; 3],
+    pub(crate) house: [u8; 3],
+    pub(crate) conflict: [u8; 3],
+    pub(crate) hint: [u8; 3],
+}
+
+impl ThemeColors {
+    pub(crate) const LIGHT: Self = Self {
+        background: [255, 255, 255],
+        border: [60, 60, 60],
+        given: [0, 0, 0],
+        filled: [80, 80, 80],
+        note: [80, 80, 80],
+        selected_cell: [255, 0, 0],
+        selected_digit: [144, 209, 255],
+        house: [220, 220, 220],
+        conflict: [255, 0, 0],
+        hint: [255, 110, 0],
+    };
+
+    pub(crate) const DARK: Self = Self {
+        background: [10, 10, 10],
+        border: [180, 180, 180],
+        given: [255, 255, 255],
+        filled: [140, 140, 140],
+        note: [140, 140, 140],
+        selected_cell: [255, 0, 0],
+        selected_digit: [0, 92, 128],
+        house: [70, 70, 70],
+        conflict: [255, 0, 0],
+        hint: [255, 165, 0],
+    };
+
+    pub(crate) const HIGH_CONTRAST: Self = Self {
+        background: [255, 255, 255],
+        border: [0, 0, 0],
+        given: [0, 0, 0],
+        filled: [0, 0, 190],
+        note: [0, 0, 0],
+        selected_cell: [220, 0, 0],
+        selected_digit: [255, 220, 0],
+        house: [200, 200, 200],
+        conflict: [220, 0, 0],
+        hint: [230, 90, 0],
+    };
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ThemeSettings {
+    pub(crate) preset: ThemePreset,
+    pub(crate) custom: ThemeColors,
+}
+
+impl Default for ThemeSettings {
+    fn default() -> Self {
+        Self {
+            preset: ThemePreset::System,
+            custom: ThemeColors::LIGHT,
+        }
+    }
+}
+
+impl ThemeSettings {
+    /// Returns the colors of the selected preset, or `None` for [`ThemePreset::System`].
+    #[must_use]
+    pub(crate) fn colors(&self) -> Option<&ThemeColors> {
+        match self.preset {
+            ThemePreset::System => None,
+            ThemePreset::Light => Some(&ThemeColors::LIGHT),
+            ThemePreset::Dark => Some(&ThemeColors::DARK),
+            ThemePreset::HighContrast => Some(&ThemeColors::HIGH_CONTRAST),
+            ThemePreset::Custom => Some(&self.custom),
+        }
+    }
+}
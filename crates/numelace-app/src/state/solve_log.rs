@@ -0,0 +1,241 @@
+use std::time::Duration;
+
+use numelace_core::{DigitPositions, Position};
+use numelace_game::Game;
+use web_time::Instant;
+
+use crate::state::{HistorySnapshot, HistorySource};
+
+/// What a step of the solve log did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SolveStepKind {
+    /// Digits or notes entered by the player.
+    Move,
+    /// A digit that does not match the solution.
+    Mistake,
+    /// A hint that was shown or applied, or a revealed cell.
+    Hint,
+    Undo,
+    Redo,
+}
+
+/// One step of the solve log, with the board it left behind.
+#[derive(Debug, Clone)]
+pub(crate) struct SolveStep {
+    pub(crate) kind: SolveStepKind,
+    /// Time since the puzzle was loaded.
+    pub(crate) at: Duration,
+    /// Cells whose digit or notes changed.
+    pub(crate) changed: DigitPositions,
+    snapshot: HistorySnapshot,
+}
+
+/// Every step of the current puzzle with its timing, for the post-solve
+/// review.
+///
+/// Unlike the undo history, the log keeps undone moves and the undo itself,
+/// so a review shows the solve as it happened. It is not saved: a puzzle
+/// resumed after a restart is logged from the first move on.
+#[derive(Debug, Default)]
+pub(crate) struct SolveLog {
+    start: Option<(Instant, HistorySnapshot)>,
+    steps: Vec<SolveStep>,
+    /// Number of steps shown on the board while reviewing; `None` shows the
+    /// current game.
+    reviewed: Option<usize>,
+}
+
+impl SolveLog {
+    /// Starts a new log from `game`.
+    pub(crate) fn reset(&mut self, game: &Game, now: Instant) {
+        let snapshot = HistorySnapshot::new(&HistorySource::new(game, None));
+        *self = Self {
+            start: Some((now, snapshot)),
+            ..Self::default()
+        };
+    }
+
+    #[must_use]
+    pub(crate) fn steps(&self) -> &[SolveStep] {
+        &self.steps
+    }
+
+    /// Returns the time since the log started, or up to the last step once
+    /// the puzzle is `finished`.
+    #[must_use]
+    pub(crate) fn elapsed(&self, now: Instant, finished: bool) -> Duration {
+        match (&self.start, self.steps.last()) {
+            (_, Some(last)) if finished => last.at,
+            (Some((started, _)), _) => now.saturating_duration_since(*started),
+            (None, _) => Duration::ZERO,
+        }
+    }
+
+    /// Returns the number of hints shown or applied and cells revealed.
+    #[must_use]
+    pub(crate) fn hint_count(&self) -> usize {
+        self.steps
+            .iter()
+            .filter(|step| step.kind == SolveStepKind::Hint)
+            .count()
+    }
+
+    /// Logs the change from `before` to `game`.
+    ///
+    /// Steps that leave the board as it was are dropped, except hints, which
+    /// count even when they were only shown.
+    pub(crate) fn record(
+        &mut self,
+        kind: SolveStepKind,
+        before: &HistorySnapshot,
+        game: &Game,
+        selected_cell: Option<Position>,
+        now: Instant,
+    ) {
+        let after = HistorySnapshot::new(&HistorySource::new(game, selected_cell));
+        if kind != SolveStepKind::Hint && after.same_board(before) {
+            return;
+        }
+        let (started, _) = self.start.get_or_insert_with(|| (now, before.clone()));
+        let at = now.saturating_duration_since(*started);
+        let changed = changed_cells(before, &after);
+        let is_mistake = changed.iter().any(|pos| {
+            after
+                .filled
+                .get(pos)
+                .zip(game.solution().get(pos))
+                .is_some_and(|(digit, answer)| digit != answer)
+        });
+        let kind = if kind == SolveStepKind::Move && is_mistake {
+            SolveStepKind::Mistake
+        } else {
+            kind
+        };
+        self.steps.push(SolveStep {
+            kind,
+            at,
+            changed,
+            snapshot: after,
+        });
+    }
+
+    /// Rebuilds the board after the first `step` steps on the puzzle of
+    /// `base`; step 0 is the board the log started from.
+    #[must_use]
+    pub(crate) fn game_at(&self, step: usize, base: &Game) -> Option<Game> {
+        let snapshot = match step.checked_sub(1) {
+            None => &self.start.as_ref()?.1,
+            Some(index) => &self.steps.get(index)?.snapshot,
+        };
+        snapshot.to_game(base)
+    }
+
+    /// Returns the number of steps shown while reviewing, or `None` when the
+    /// current game is shown.
+    #[must_use]
+    pub(crate) fn reviewed(&self) -> Option<usize> {
+        self.reviewed
+    }
+
+    /// Shows the board after the first `step` steps; the last step shows the
+    /// current game again.
+    pub(crate) fn review(&mut self, step: usize) {
+        self.reviewed = (step < self.steps.len()).then_some(step);
+    }
+}
+
+fn changed_cells(before: &HistorySnapshot, after: &HistorySnapshot) -> DigitPositions {
+    Position::ALL
+        .into_iter()
+        .filter(|&pos| {
+            let (row, col) = (usize::from(pos.row()), usize::from(pos.col()));
+            before.filled.get(pos) != after.filled.get(pos)
+                || before.notes[row][col] != after.notes[row][col]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use numelace_core::{Digit, DigitGrid};
+    use numelace_game::InputDigitOptions;
+
+    use super::*;
+
+    fn game() -> Game {
+        let solution: DigitGrid =
+            "185362947793148526246795183564239871931874265827516394318427659672951438459683712"
+                .parse()
+                .unwrap();
+        let mut problem = solution.clone();
+        for pos in [Position::new(0, 0), Position::new(0, 1)] {
+            problem.set(pos, None);
+        }
+        Game::from_problem_filled_notes(&problem, &solution, &DigitGrid::new(), &[[0; 9]; 9])
+            .unwrap()
+    }
+
+    fn enter(log: &mut SolveLog, game: &mut Game, pos: Position, digit: Digit) {
+        let before = HistorySnapshot::new(&HistorySource::new(game, None));
+        game.set_digit(pos, digit, &InputDigitOptions::default())
+            .unwrap();
+        log.record(
+            SolveStepKind::Move,
+            &before,
+            game,
+            Some(pos),
+            Instant::now(),
+        );
+    }
+
+    #[test]
+    fn log_classifies_mistakes_and_keeps_every_board() {
+        let mut game = game();
+        let mut log = SolveLog::default();
+        log.reset(&game, Instant::now());
+
+        enter(&mut log, &mut game, Position::new(0, 0), Digit::D2);
+        enter(&mut log, &mut game, Position::new(0, 0), Digit::D1);
+        let before = HistorySnapshot::new(&HistorySource::new(&game, None));
+        log.record(SolveStepKind::Hint, &before, &game, None, Instant::now());
+        log.record(SolveStepKind::Undo, &before, &game, None, Instant::now());
+
+        let kinds: Vec<_> = log.steps().iter().map(|step| step.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                SolveStepKind::Mistake,
+                SolveStepKind::Move,
+                SolveStepKind::Hint
+            ]
+        );
+        assert_eq!(log.hint_count(), 1);
+        assert_eq!(
+            log.steps()[0].changed,
+            DigitPositions::from_elem(Position::new(0, 0))
+        );
+
+        let start = log.game_at(0, &game).unwrap();
+        assert_eq!(start.cell(Position::new(0, 0)).as_digit(), None);
+        let mistake = log.game_at(1, &game).unwrap();
+        assert_eq!(
+            mistake.cell(Position::new(0, 0)).as_digit(),
+            Some(Digit::D2)
+        );
+        assert!(log.game_at(4, &game).is_none());
+    }
+
+    #[test]
+    fn reviewing_the_last_step_shows_the_current_game() {
+        let mut game = game();
+        let mut log = SolveLog::default();
+        log.reset(&game, Instant::now());
+        enter(&mut log, &mut game, Position::new(0, 0), Digit::D1);
+        enter(&mut log, &mut game, Position::new(0, 1), Digit::D8);
+
+        log.review(1);
+        assert_eq!(log.reviewed(), Some(1));
+        log.review(2);
+        assert_eq!(log.reviewed(), None);
+    }
+}
@@ -0,0 +1,109 @@
+use std::time::Duration;
+
+use crate::state::{DifficultyPreset, PuzzleDifficulty};
+
+/// Clock of a game started in timed mode, compared against par once solved.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct TimedGame {
+    /// Play time so far, excluding time the window was in the background.
+    pub(crate) elapsed: Duration,
+    /// Estimated solving time, known once the puzzle has been graded.
+    pub(crate) par: Option<Duration>,
+    /// Whether the puzzle has been solved, which stops the clock for good.
+    pub(crate) finished: bool,
+}
+
+impl TimedGame {
+    pub(crate) fn tick(&mut self, delta: Duration) {
+        if !self.finished {
+            self.elapsed += delta;
+        }
+    }
+
+    /// Returns how the final time compares with par, once both are known.
+    #[must_use]
+    pub(crate) fn rating(&self) -> Option<ParRating> {
+        let par = self.par.filter(|_| self.finished)?;
+        Some(ParRating::new(self.elapsed, par))
+    }
+}
+
+/// Estimated solving time of a graded puzzle.
+///
+/// Every empty cell is allowed more time the harder the techniques the puzzle
+/// needs; puzzles beyond the techniques get the most generous allowance.
+#[must_use]
+pub(crate) fn par_time(difficulty: PuzzleDifficulty, empty_cells: usize) -> Duration {
+    let secs_per_cell: u64 = match difficulty {
+        PuzzleDifficulty::Rated(DifficultyPreset::Basic) => 8,
+        PuzzleDifficulty::Rated(DifficultyPreset::Intermediate) => 12,
+        PuzzleDifficulty::Rated(DifficultyPreset::UpperIntermediate) => 16,
+        PuzzleDifficulty::Rated(DifficultyPreset::Advanced) => 22,
+        PuzzleDifficulty::Rated(DifficultyPreset::Expert | DifficultyPreset::Custom) => 30,
+        PuzzleDifficulty::BeyondTechniques => 40,
+    };
+    Duration::from_secs(secs_per_cell * empty_cells as u64)
+}
+
+/// Result of a timed game relative to its par time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum ParRating {
+    /// At most three quarters of par.
+    Outstanding,
+    UnderPar,
+    /// At most one and a half times par.
+    OverPar,
+    WellOverPar,
+}
+
+impl ParRating {
+    #[must_use]
+    pub(crate) fn new(elapsed: Duration, par: Duration) -> Self {
+        if elapsed <= par * 3 / 4 {
+            Self::Outstanding
+        } else if elapsed <= par {
+            Self::UnderPar
+        } else if elapsed <= par * 3 / 2 {
+            Self::OverPar
+        } else {
+            Self::WellOverPar
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{ParRating, TimedGame, par_time};
+    use crate::state::{DifficultyPreset, PuzzleDifficulty};
+
+    #[test]
+    fn par_grows_with_difficulty_and_empty_cells() {
+        let basic = PuzzleDifficulty::Rated(DifficultyPreset::Basic);
+        let expert = PuzzleDifficulty::Rated(DifficultyPreset::Expert);
+        assert!(par_time(basic, 50) < par_time(expert, 50));
+        assert!(par_time(basic, 40) < par_time(basic, 50));
+        assert!(par_time(expert, 50) < par_time(PuzzleDifficulty::BeyondTechniques, 50));
+    }
+
+    #[test]
+    fn rating_compares_final_time_with_par() {
+        let par = Duration::from_secs(400);
+        let mut timed = TimedGame {
+            par: Some(par),
+            ..TimedGame::default()
+        };
+        timed.tick(Duration::from_secs(300));
+        assert_eq!(timed.rating(), None);
+
+        timed.finished = true;
+        timed.tick(Duration::from_secs(300));
+        assert_eq!(timed.elapsed, Duration::from_secs(300));
+        assert_eq!(timed.rating(), Some(ParRating::Outstanding));
+
+        assert_eq!(ParRating::new(par, par), ParRating::UnderPar);
+        assert_eq!(ParRating::new(par * 3 / 2, par), ParRating::OverPar);
+        assert_eq!(ParRating::new(par * 2, par), ParRating::WellOverPar);
+    }
+}
@@ -1,38 +1,185 @@
-use numelace_core::{Digit, Position};
-use numelace_solver::BoxedTechniqueStep;
+use std::time::Duration;
+
+use eframe::egui::{ColorImage, Vec2};
+use numelace_core::{
+    CandidateGrid, Digit, DigitPositions, DigitSet, House, Position, PositionIndexedArray,
+    RegionLayout,
+};
+use numelace_solver::{
+    BoxedTechniqueStep,
+    lesson::{self, Lesson},
+    technique::TechniqueId,
+};
+use web_time::Instant;
 
 use crate::{
-    action::{ModalRequest, SpinnerId, SpinnerKind},
-    flow::FlowExecutor,
+    action::{
+        ModalRequest, Notification, NotificationId, NotificationLevel, ScriptPrompt, SpinnerId,
+        SpinnerKind,
+        recording::{ActionRecorder, Replay},
+    },
+    flow::{FlowExecutor, FlowId},
+    state::{ActionLog, AnimationPolicy, AppState, GenerationTraceView, Scratchpad, SolveLog},
+    worker::Readiness,
 };
 
+/// How long a notification stays on screen unless dismissed.
+fn notification_lifetime(level: NotificationLevel) -> Duration {
+    match level {
+        NotificationLevel::Info => Duration::from_secs(3),
+        NotificationLevel::Error => Duration::from_secs(8),
+    }
+}
+
+/// Content queued for the system clipboard.
+#[derive(Debug)]
+pub(crate) enum ClipboardContent {
+    Text(String),
+    Image(ColorImage),
+}
+
+/// Top-level screen filling the central panel.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Screen {
+    #[default]
+    Game,
+    Campaign,
+    Learn,
+}
+
+/// Why the game screen shows the board without accepting moves.
+///
+/// While the game is read-only, the action handler drops everything that
+/// would change the board or its history; selection, highlighting and the
+/// rest of the UI keep working.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ReadOnlyReason {
+    /// A developer recording is being replayed; only its own actions move.
+    Replay,
+    /// The player chose to review the solved puzzle; see
+    /// [`AppState::reviewing`].
+    Review,
+}
+
 // UiState holds ephemeral UI-only state (modals, spinners, ghosts). It is not persisted.
 #[derive(Debug)]
+#[expect(clippy::struct_excessive_bools)]
 pub(crate) struct UiState {
+    pub(crate) screen: Screen,
     pub(crate) active_modal: Option<ModalRequest>,
+    /// Prompt of the running scripted flow, e.g. the tutorial.
+    pub(crate) script_prompt: Option<ScriptPrompt>,
     pub(crate) conflict_ghost: Option<(Position, GhostType)>,
     pub(crate) hint_state: Option<HintState>,
+    pub(crate) learn: LearnState,
+    /// Notes flagged by the notes audit, highlighted while its dialog is open.
+    pub(crate) note_audit: Option<Vec<(Position, DigitSet)>>,
+    /// Trial depths of the hardness overlay, which lag behind the board while
+    /// the worker analyzes its latest candidates.
+    pub(crate) deduction_depths: Option<PositionIndexedArray<u8>>,
+    /// Candidates of the last analysis started for the overlay, and its flow.
+    pub(crate) deduction_depths_request: Option<(CandidateGrid, FlowId)>,
     pub(crate) executor: FlowExecutor,
     pub(crate) spinner_state: SpinnerState,
+    pub(crate) notifications: NotificationState,
+    /// Whether progress was written since the last autosave notification.
+    pub(crate) saved_since_autosave: bool,
+    /// Whether the journal holds recovered progress the player has not
+    /// restored or declined yet, so saving must not discard it.
+    pub(crate) keep_journal: bool,
     pub(crate) requested_initial_new_game: bool,
+    /// Whether the background worker finished warming up; New Game waits for it.
+    pub(crate) worker_readiness: Readiness,
+    pub(crate) applied_ui_zoom: Option<f32>,
+    pub(crate) applied_min_window_size: Option<Vec2>,
+    /// Whether the system asks for reduced motion; read once at startup.
+    pub(crate) system_prefers_reduced_motion: bool,
+    /// Animation policy last applied to the egui styles.
+    pub(crate) applied_animation: Option<AnimationPolicy>,
+    /// When the clock of a timed game last advanced; `None` while it is paused.
+    pub(crate) last_clock_tick: Option<Instant>,
+    /// Share code from the page URL, offered for loading on the first frame.
+    pub(crate) shared_code: Option<String>,
+    /// Progress recovered from the journal, offered for restoring on the first frame.
+    pub(crate) recovered_state: Option<Box<AppState>>,
+    /// Content to copy to the system clipboard on the next frame.
+    pub(crate) pending_clipboard: Option<ClipboardContent>,
+    /// Incremented whenever an action is handled; keys view-model caches.
+    pub(crate) revision: u64,
+    /// Recent actions, included in bug reports.
+    pub(crate) action_log: ActionLog,
+    /// Records app actions in developer mode.
+    pub(crate) recorder: Option<ActionRecorder>,
+    /// Recording being replayed in developer mode.
+    pub(crate) replay: Option<Replay>,
+    /// Whether new puzzles come with a generator trace, in developer mode.
+    pub(crate) trace_generator: bool,
+    /// Generator trace of the last new puzzle, shown in developer mode.
+    pub(crate) generation_trace: Option<GenerationTraceView>,
+    pub(crate) scratchpad: Scratchpad,
+    /// Whether the game info sidebar is shown next to the board.
+    pub(crate) show_info_panel: bool,
+    /// Set while the board is only shown, not played, for reasons that do not
+    /// outlive the session; see [`UiState::read_only_reason`].
+    pub(crate) read_only: Option<ReadOnlyReason>,
+    /// Steps of the current puzzle, replayed by the post-solve review.
+    pub(crate) solve_log: SolveLog,
 }
 
 impl UiState {
+    /// Returns why the board does not accept moves from the player, if it
+    /// does not.
+    #[must_use]
+    pub(crate) fn read_only_reason(&self, app_state: &AppState) -> Option<ReadOnlyReason> {
+        self.read_only
+            .or(app_state.reviewing.then_some(ReadOnlyReason::Review))
+    }
+
     #[must_use]
     pub(crate) fn new() -> Self {
         Self {
+            screen: Screen::Game,
             active_modal: None,
+            script_prompt: None,
             conflict_ghost: None,
             hint_state: None,
+            learn: LearnState::new(),
+            note_audit: None,
+            deduction_depths: None,
+            deduction_depths_request: None,
             executor: FlowExecutor::new(),
             spinner_state: SpinnerState::default(),
+            notifications: NotificationState::default(),
+            saved_since_autosave: false,
+            keep_journal: false,
             requested_initial_new_game: false,
+            worker_readiness: Readiness::WarmingUp,
+            applied_ui_zoom: None,
+            applied_min_window_size: None,
+            system_prefers_reduced_motion: false,
+            applied_animation: None,
+            last_clock_tick: None,
+            shared_code: None,
+            recovered_state: None,
+            pending_clipboard: None,
+            revision: 0,
+            action_log: ActionLog::default(),
+            recorder: None,
+            replay: None,
+            trace_generator: false,
+            generation_trace: None,
+            scratchpad: Scratchpad::default(),
+            show_info_panel: false,
+            read_only: None,
+            solve_log: SolveLog::default(),
         }
     }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub(crate) enum HintStage {
+    /// Only the area to examine is shown; see [`HintState::focus_area`].
+    Stage0,
     Stage1,
     Stage2,
     Stage3Preview,
@@ -45,6 +192,72 @@ pub(crate) struct HintState {
     pub(crate) step: BoxedTechniqueStep,
 }
 
+impl HintState {
+    /// Returns the cells highlighted in [`HintStage::Stage0`].
+    ///
+    /// This is the only condition cell when there is one, and otherwise the
+    /// house holding the most condition cells (rows before columns before
+    /// boxes on a tie), which points the player at the right place without
+    /// giving away the pattern. Boxes are taken from `regions`.
+    #[must_use]
+    pub(crate) fn focus_area(&self, regions: &RegionLayout) -> DigitPositions {
+        let conditions = self.step.condition_positions();
+        if conditions.len() <= 1 {
+            return conditions;
+        }
+        House::ALL
+            .into_iter()
+            .map(|house| house.positions(regions))
+            .rev()
+            .max_by_key(|positions| (*positions & conditions).len())
+            .unwrap_or(conditions)
+    }
+}
+
+/// Lesson and example shown on the Learn screen.
+#[derive(Debug, Clone)]
+pub(crate) struct LearnState {
+    pub(crate) lesson: Lesson,
+    pub(crate) example: usize,
+    /// How much of the example's step is revealed; never [`HintStage::Stage3Apply`].
+    pub(crate) stage: HintStage,
+}
+
+impl LearnState {
+    #[must_use]
+    fn new() -> Self {
+        let lesson =
+            lesson::lesson(TechniqueId::NakedSingle).expect("built-in techniques have lessons");
+        Self {
+            lesson,
+            example: 0,
+            stage: HintStage::Stage0,
+        }
+    }
+
+    /// Switches to the lesson for `id`, keeping the current one for variant
+    /// techniques, which have none.
+    pub(crate) fn select(&mut self, id: TechniqueId) {
+        if let Some(lesson) = lesson::lesson(id) {
+            *self = Self {
+                lesson,
+                example: 0,
+                stage: HintStage::Stage0,
+            };
+        }
+    }
+
+    /// Returns the example shown as a hint, if the lesson has any.
+    #[must_use]
+    pub(crate) fn hint_state(&self) -> Option<HintState> {
+        let example = self.lesson.examples.get(self.example)?;
+        Some(HintState {
+            stage: self.stage,
+            step: example.step.clone(),
+        })
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum GhostType {
     Digit(Digit),
@@ -83,3 +296,91 @@ pub(crate) struct SpinnerEntry {
     pub(crate) id: SpinnerId,
     pub(crate) kind: SpinnerKind,
 }
+
+/// Queue of notifications (toasts), oldest first.
+#[derive(Debug, Default)]
+pub(crate) struct NotificationState {
+    last_id: u64,
+    active: Vec<NotificationEntry>,
+}
+
+impl NotificationState {
+    pub(crate) fn push(&mut self, notification: Notification, now: Instant) -> NotificationId {
+        self.last_id += 1;
+        let id = NotificationId::new(self.last_id);
+        let expires_at = now + notification_lifetime(notification.kind.level());
+        self.active.push(NotificationEntry {
+            id,
+            notification,
+            expires_at,
+        });
+        id
+    }
+
+    pub(crate) fn dismiss(&mut self, id: NotificationId) {
+        self.active.retain(|entry| entry.id != id);
+    }
+
+    /// Drops expired notifications and returns the time until the next one expires.
+    pub(crate) fn expire(&mut self, now: Instant) -> Option<Duration> {
+        self.active.retain(|entry| entry.expires_at > now);
+        self.active.iter().map(|entry| entry.expires_at - now).min()
+    }
+
+    #[must_use]
+    pub(crate) fn active(&self) -> &[NotificationEntry] {
+        &self.active
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct NotificationEntry {
+    pub(crate) id: NotificationId,
+    pub(crate) notification: Notification,
+    expires_at: Instant,
+}
+
+#[cfg(test)]
+mod tests {
+    use web_time::Instant;
+
+    use super::{NotificationState, notification_lifetime};
+    use crate::action::{Notification, NotificationKind, NotificationLevel};
+
+    fn notification() -> Notification {
+        Notification::new(NotificationKind::NewGameFailed).with_detail("worker disconnected")
+    }
+
+    #[test]
+    fn notifications_expire_or_get_dismissed() {
+        let lifetime = notification_lifetime(NotificationLevel::Error);
+        let now = Instant::now();
+        let mut state = NotificationState::default();
+        let first = state.push(notification(), now);
+        let later = now + lifetime / 2;
+        state.push(notification(), later);
+
+        assert_eq!(state.expire(later), Some(lifetime / 2));
+        assert_eq!(state.expire(now + lifetime), Some(lifetime / 2));
+        assert_eq!(state.active().len(), 1);
+        assert_ne!(state.active()[0].id, first);
+
+        state.dismiss(state.active()[0].id);
+        assert_eq!(state.expire(later), None);
+    }
+
+    #[test]
+    fn info_notifications_expire_before_errors() {
+        let now = Instant::now();
+        let mut state = NotificationState::default();
+        state.push(Notification::new(NotificationKind::ShareLinkCopied), now);
+        state.push(notification(), now);
+
+        state.expire(now + notification_lifetime(NotificationLevel::Info));
+        assert_eq!(state.active().len(), 1);
+        assert_eq!(
+            state.active()[0].notification.kind,
+            NotificationKind::NewGameFailed
+        );
+    }
+}
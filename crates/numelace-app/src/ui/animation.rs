@@ -0,0 +1,61 @@
+use std::time::Duration;
+
+use eframe::egui::{Context, Style, style::ScrollAnimation};
+
+use crate::state::AnimationPolicy;
+
+/// How often the UI repaints under [`AnimationPolicy::Reduced`] while a
+/// spinner waits for background work.
+///
+/// Flows only advance when a frame polls them, so the wait must still repaint,
+/// just not on every frame.
+const REDUCED_BUSY_REPAINT_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Applies `animation` to egui's own animations: collapsing headers, window
+/// fades and smooth scrolling.
+pub(crate) fn apply_to_styles(ctx: &Context, animation: AnimationPolicy) {
+    let default = Style::default();
+    let (animation_time, scroll_animation) = match animation {
+        AnimationPolicy::Full => (default.animation_time, default.scroll_animation),
+        AnimationPolicy::Reduced => (0.0, ScrollAnimation::none()),
+    };
+    ctx.all_styles_mut(|style| {
+        style.animation_time = animation_time;
+        style.scroll_animation = scroll_animation;
+    });
+}
+
+/// Requests the next frame while the UI waits for background work.
+pub(crate) fn request_busy_repaint(ctx: &Context, animation: AnimationPolicy) {
+    match animation {
+        AnimationPolicy::Full => ctx.request_repaint(),
+        AnimationPolicy::Reduced => ctx.request_repaint_after(REDUCED_BUSY_REPAINT_INTERVAL),
+    }
+}
+
+/// Returns whether the system asks applications to minimize motion.
+///
+/// Only browsers report the preference, through the
+/// `prefers-reduced-motion` media query.
+#[cfg(target_arch = "wasm32")]
+#[must_use]
+pub(crate) fn system_prefers_reduced_motion() -> bool {
+    web_sys::window()
+        .and_then(|window| {
+            window
+                .match_media("(prefers-reduced-motion: reduce)")
+                .ok()
+                .flatten()
+        })
+        .is_some_and(|query| query.matches())
+}
+
+/// Returns whether the system asks applications to minimize motion.
+///
+/// Only browsers report the preference, through the
+/// `prefers-reduced-motion` media query.
+#[cfg(not(target_arch = "wasm32"))]
+#[must_use]
+pub(crate) fn system_prefers_reduced_motion() -> bool {
+    false
+}
@@ -0,0 +1,161 @@
+use eframe::egui::{Button, ProgressBar, RichText, ScrollArea, Ui};
+
+use super::notification;
+use crate::{
+    action::{ActionRequestQueue, FlowAction, UiAction},
+    campaign::{PACKS, PuzzlePack},
+    i18n::{self, Text, tr, tr_args},
+    state::{CampaignProgress, CampaignPuzzle, NotificationEntry, PuzzleMetadataStore, Screen},
+    ui::{icon, status_line},
+};
+
+/// Width of a puzzle button in the pack listing.
+const PUZZLE_BUTTON_WIDTH: f32 = 48.0;
+
+#[derive(Debug, Clone)]
+pub(crate) struct CampaignScreenViewModel<'a> {
+    progress: &'a CampaignProgress,
+    puzzle_metadata: &'a PuzzleMetadataStore,
+    current: Option<CampaignPuzzle>,
+    notifications: &'a [NotificationEntry],
+}
+
+impl<'a> CampaignScreenViewModel<'a> {
+    #[must_use]
+    pub(crate) fn new(
+        progress: &'a CampaignProgress,
+        puzzle_metadata: &'a PuzzleMetadataStore,
+        current: Option<CampaignPuzzle>,
+        notifications: &'a [NotificationEntry],
+    ) -> Self {
+        Self {
+            progress,
+            puzzle_metadata,
+            current,
+            notifications,
+        }
+    }
+}
+
+pub(crate) fn show(
+    ui: &mut Ui,
+    vm: &CampaignScreenViewModel,
+    action_queue: &mut ActionRequestQueue,
+) {
+    ui.horizontal(|ui| {
+        if ui
+            .button(format!("{} {}", icon::ARROW_LEFT, tr(Text::BackToGame)))
+            .clicked()
+        {
+            action_queue.request(UiAction::ShowScreen(Screen::Game).into());
+        }
+        ui.heading(tr(Text::Campaign));
+    });
+    ui.label(tr(Text::CampaignDescription));
+    ui.separator();
+
+    ScrollArea::vertical().show(ui, |ui| {
+        for (pack_index, pack) in PACKS.iter().enumerate() {
+            show_pack(ui, vm, pack_index, pack, action_queue);
+            ui.add_space(ui.spacing().item_spacing.y * 2.0);
+        }
+    });
+
+    notification::show(ui.ctx(), vm.notifications, action_queue);
+}
+
+fn show_pack(
+    ui: &mut Ui,
+    vm: &CampaignScreenViewModel,
+    pack_index: usize,
+    pack: &PuzzlePack,
+    action_queue: &mut ActionRequestQueue,
+) {
+    let unlocked = vm.progress.is_pack_unlocked(pack_index);
+    let solved = vm.progress.solved_count(pack_index);
+    let total = pack.puzzle_count();
+    ui.group(|ui| {
+        ui.horizontal(|ui| {
+            let title = RichText::new(tr(pack.title)).strong();
+            if unlocked {
+                ui.label(title);
+            } else {
+                ui.label(RichText::new(icon::LOCK).weak());
+                ui.label(title.weak());
+            }
+            ui.weak(i18n::difficulty(pack.difficulty));
+        });
+        #[expect(clippy::cast_precision_loss)]
+        let fraction = solved as f32 / total.max(1) as f32;
+        ui.add(ProgressBar::new(fraction).text(format!("{solved}/{total}")));
+        if !unlocked {
+            ui.weak(tr(Text::PackLocked));
+            return;
+        }
+        ui.horizontal_wrapped(|ui| {
+            for index in 0..total {
+                let puzzle = CampaignPuzzle {
+                    pack: pack_index,
+                    index,
+                };
+                show_puzzle_button(ui, vm, puzzle, action_queue);
+            }
+        });
+    });
+}
+
+fn show_puzzle_button(
+    ui: &mut Ui,
+    vm: &CampaignScreenViewModel,
+    puzzle: CampaignPuzzle,
+    action_queue: &mut ActionRequestQueue,
+) {
+    let number = puzzle.index + 1;
+    let is_current = vm.current == Some(puzzle);
+    let label = if vm.progress.is_solved(puzzle) {
+        format!("{} {number}", icon::CHECK)
+    } else if is_current {
+        format!("{} {number}", icon::PLAY)
+    } else {
+        number.to_string()
+    };
+    let unlocked = vm.progress.is_unlocked(puzzle);
+    let button = Button::new(label)
+        .selected(is_current)
+        .min_size([PUZZLE_BUTTON_WIDTH, 0.0].into());
+    let mut response = ui.add_enabled(unlocked, button);
+    response = if !unlocked {
+        response.on_disabled_hover_text(tr(Text::PuzzleLocked))
+    } else if is_current {
+        response.on_hover_text(tr(Text::NowPlaying))
+    } else {
+        response.on_hover_text(puzzle_hover_text(vm, puzzle))
+    };
+    if response.clicked() {
+        // Picking the puzzle being played just returns to it.
+        if is_current {
+            action_queue.request(UiAction::ShowScreen(Screen::Game).into());
+        } else {
+            action_queue.request(FlowAction::StartCampaignPuzzle(puzzle).into());
+        }
+    }
+}
+
+/// Names the puzzle, with the player's best time once it was solved timed.
+fn puzzle_hover_text(vm: &CampaignScreenViewModel, puzzle: CampaignPuzzle) -> String {
+    let name = tr_args(
+        Text::CampaignPuzzleNumber,
+        &[("count", &(puzzle.index + 1))],
+    );
+    let best_time = vm
+        .puzzle_metadata
+        .get_pack_puzzle(puzzle)
+        .and_then(|entry| entry.best_time);
+    match best_time {
+        Some(time) => {
+            let time = status_line::clock_text(time);
+            format!("{name}\n{}", tr_args(Text::BestTime, &[("time", &time)]))
+        }
+        None => name,
+    }
+}
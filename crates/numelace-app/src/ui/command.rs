@@ -0,0 +1,576 @@
+//! Registry of the commands offered by the toolbar, the keypad and keyboard
+//! shortcuts.
+//!
+//! Each command is described once, with its label, icon, category, default
+//! shortcuts and when it is available, so every place that offers it shows
+//! the same text and shortcut.
+
+use eframe::egui::{Context, InputState, Key, KeyboardShortcut, Modifiers};
+
+use crate::{
+    action::{
+        Action, BoardMutationAction, FlowAction, HistoryAction, InputModeAction, ModalRequest,
+        NotesFillScope, StateQueryAction, UiAction, UpdateStateAction,
+    },
+    export::ExportContent,
+    i18n::{Text, tr},
+    state::{CommandId, KeyBindings, KeyTrigger, Screen},
+    ui::{icon, input},
+};
+
+/// Group a command is listed under.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CommandCategory {
+    Edit,
+    Notes,
+    Assist,
+    Game,
+    Share,
+}
+
+impl CommandCategory {
+    #[must_use]
+    pub(crate) const fn all() -> [Self; 5] {
+        [
+            Self::Edit,
+            Self::Notes,
+            Self::Assist,
+            Self::Game,
+            Self::Share,
+        ]
+    }
+
+    #[must_use]
+    pub(crate) fn label(self) -> &'static str {
+        tr(match self {
+            Self::Edit => Text::CommandCategoryEdit,
+            Self::Notes => Text::Notes,
+            Self::Assist => Text::Assist,
+            Self::Game => Text::CommandCategoryGame,
+            Self::Share => Text::Share,
+        })
+    }
+}
+
+impl KeyTrigger {
+    /// Returns whether the key was pressed this frame with exactly these
+    /// modifiers.
+    #[must_use]
+    pub(crate) fn pressed(self, i: &InputState) -> bool {
+        i.key_pressed(self.key)
+            && i.modifiers.command == self.command
+            && i.modifiers.shift == self.shift
+    }
+
+    /// Formats the trigger the way the platform writes shortcuts, such as
+    /// `Ctrl+Z` or `⌘Z`.
+    #[must_use]
+    pub(crate) fn format(self, ctx: &Context) -> String {
+        let mut modifiers = Modifiers::NONE;
+        if self.command {
+            modifiers |= Modifiers::COMMAND;
+        }
+        if self.shift {
+            modifiers |= Modifiers::SHIFT;
+        }
+        ctx.format_shortcut(&KeyboardShortcut::new(modifiers, self.key))
+    }
+}
+
+/// State the availability of commands depends on.
+#[derive(Debug, Clone, Copy, Default)]
+#[expect(clippy::struct_excessive_bools)]
+pub(crate) struct CommandContext {
+    pub(crate) can_undo: bool,
+    pub(crate) can_redo: bool,
+    /// Whether the selected cell holds a digit or notes the player entered.
+    pub(crate) has_removable_input: bool,
+    pub(crate) can_auto_fill_selected_cell: bool,
+    pub(crate) can_reveal_selected_cell: bool,
+    pub(crate) has_scratch_strokes: bool,
+    /// Whether the background worker can generate a new puzzle.
+    pub(crate) worker_ready: bool,
+    /// Whether the puzzle on the board can be generated again.
+    pub(crate) can_regenerate: bool,
+}
+
+/// Describes a command.
+#[derive(Debug)]
+pub(crate) struct Command {
+    pub(crate) id: CommandId,
+    pub(crate) icon: Option<&'static str>,
+    label: Text,
+    /// Longer explanation shown on hover; the label is shown when `None`.
+    tooltip: Option<Text>,
+    pub(crate) category: CommandCategory,
+    default_shortcuts: &'static [KeyTrigger],
+    available: fn(CommandContext) -> bool,
+}
+
+impl Command {
+    #[must_use]
+    pub(crate) fn label(&self) -> &'static str {
+        tr(self.label)
+    }
+
+    /// Returns the label with the icon in front, for menu items.
+    #[must_use]
+    pub(crate) fn menu_label(&self) -> String {
+        match self.icon {
+            Some(icon) => format!("{icon} {}", self.label()),
+            None => self.label().to_owned(),
+        }
+    }
+
+    /// Returns the shortcuts of the command under `bindings`; the first one
+    /// is shown in tooltips.
+    #[must_use]
+    pub(crate) fn shortcuts<'a>(&'a self, bindings: &'a KeyBindings) -> &'a [KeyTrigger] {
+        match bindings.commands.get(&self.id) {
+            Some(trigger) => trigger.as_slice(),
+            None => self.default_shortcuts,
+        }
+    }
+
+    /// Returns whether the command's shortcuts under `bindings` differ from
+    /// its defaults.
+    #[must_use]
+    pub(crate) fn is_rebound(&self, bindings: &KeyBindings) -> bool {
+        self.shortcuts(bindings) != self.default_shortcuts
+    }
+
+    /// Returns the tooltip, followed by the first shortcut if there is one.
+    #[must_use]
+    pub(crate) fn hover_text(&self, ctx: &Context, bindings: &KeyBindings) -> String {
+        let text = tr(self.tooltip.unwrap_or(self.label));
+        match self.shortcuts(bindings).first() {
+            Some(shortcut) => format!("{text} ({})", shortcut.format(ctx)),
+            None => text.to_owned(),
+        }
+    }
+
+    #[must_use]
+    pub(crate) fn is_available(&self, context: CommandContext) -> bool {
+        (self.available)(context)
+    }
+}
+
+impl CommandId {
+    #[must_use]
+    pub(crate) fn command(self) -> &'static Command {
+        &COMMANDS[self as usize]
+    }
+
+    /// Returns the action the command requests.
+    #[must_use]
+    pub(crate) fn action(self) -> Action {
+        match self {
+            Self::Undo => HistoryAction::Undo.into(),
+            Self::Redo => HistoryAction::Redo.into(),
+            Self::ToggleInputMode => InputModeAction::ToggleInputMode.into(),
+            Self::ClearCell => BoardMutationAction::ClearCell.into(),
+            Self::ClearScratchpad => UiAction::ClearScratchpad.into(),
+            Self::AutoFillAllCells => auto_fill_notes(NotesFillScope::AllCells),
+            Self::AutoFillEmptyCells => auto_fill_notes(NotesFillScope::EmptyCells),
+            Self::AutoFillSelectedCell => auto_fill_notes(NotesFillScope::SelectedCell),
+            Self::CheckSolvability => FlowAction::CheckSolvability.into(),
+            Self::CheckProgress => StateQueryAction::CheckProgress.into(),
+            Self::Hint => FlowAction::Hint.into(),
+            Self::AuditNotes => FlowAction::AuditNotes.into(),
+            Self::ClearMistakes => BoardMutationAction::ClearMistakes.into(),
+            Self::RevealCell => FlowAction::RevealSelectedCell.into(),
+            Self::RevealAll => FlowAction::RevealAll.into(),
+            Self::ToggleCandidateHeatmap => UpdateStateAction::ToggleCandidateHeatmap.into(),
+            Self::ToggleInfoPanel => UiAction::ToggleInfoPanel.into(),
+            Self::NewGame => FlowAction::StartNewGame.into(),
+            Self::RegeneratePuzzle => FlowAction::RegeneratePuzzle.into(),
+            Self::ResetInputs => FlowAction::ResetInputs.into(),
+            Self::Settings => UiAction::OpenModal(ModalRequest::Settings).into(),
+            Self::Statistics => UiAction::OpenModal(ModalRequest::Statistics).into(),
+            Self::RaceStandings => UiAction::OpenModal(ModalRequest::RaceResult).into(),
+            Self::Campaign => UiAction::ShowScreen(Screen::Campaign).into(),
+            Self::Learn => UiAction::ShowScreen(Screen::Learn).into(),
+            Self::BugReport => FlowAction::ShowBugReport.into(),
+            Self::ExportPuzzle => FlowAction::Export(ExportContent::Givens).into(),
+            Self::ExportProgress => FlowAction::Export(ExportContent::Progress).into(),
+            Self::CopyPuzzleLink => FlowAction::CopyShareLink {
+                include_progress: false,
+            }
+            .into(),
+            Self::CopyProgressLink => FlowAction::CopyShareLink {
+                include_progress: true,
+            }
+            .into(),
+            Self::CopyBoardImage => FlowAction::ShareBoardImage.into(),
+        }
+    }
+}
+
+/// Binds `trigger` to the command `id` alone, taking it from any other
+/// command that had it.
+///
+/// Returns `false`, changing nothing, if `trigger` is kept for digit entry or
+/// moving the selection; see [`input::is_reserved`].
+pub(crate) fn rebind(bindings: &mut KeyBindings, id: CommandId, trigger: KeyTrigger) -> bool {
+    if input::is_reserved(trigger) {
+        return false;
+    }
+    release(bindings, id, &[trigger]);
+    bindings.commands.insert(id, Some(trigger));
+    true
+}
+
+/// Gives the command `id` its default shortcuts back, taking them from any
+/// other command they were bound to since.
+pub(crate) fn reset_binding(bindings: &mut KeyBindings, id: CommandId) {
+    release(bindings, id, id.command().default_shortcuts);
+    bindings.commands.remove(&id);
+}
+
+/// Removes `triggers` from the shortcuts of every command but `id`.
+fn release(bindings: &mut KeyBindings, id: CommandId, triggers: &[KeyTrigger]) {
+    for command in COMMANDS.iter().filter(|command| command.id != id) {
+        let shortcuts = command.shortcuts(bindings);
+        if shortcuts.iter().any(|trigger| triggers.contains(trigger)) {
+            let kept = shortcuts
+                .iter()
+                .copied()
+                .find(|trigger| !triggers.contains(trigger));
+            bindings.commands.insert(command.id, kept);
+        }
+    }
+}
+
+fn auto_fill_notes(scope: NotesFillScope) -> Action {
+    BoardMutationAction::AutoFillNotes { scope }.into()
+}
+
+fn always(_: CommandContext) -> bool {
+    true
+}
+
+impl Command {
+    /// Starts a command that has no shortcut and is always available.
+    const fn new(
+        id: CommandId,
+        icon: Option<&'static str>,
+        label: Text,
+        tooltip: Option<Text>,
+        category: CommandCategory,
+    ) -> Self {
+        Self {
+            id,
+            icon,
+            label,
+            tooltip,
+            category,
+            default_shortcuts: &[],
+            available: always,
+        }
+    }
+
+    const fn with_shortcuts(mut self, shortcuts: &'static [KeyTrigger]) -> Self {
+        self.default_shortcuts = shortcuts;
+        self
+    }
+
+    const fn with_available(mut self, available: fn(CommandContext) -> bool) -> Self {
+        self.available = available;
+        self
+    }
+}
+
+/// Every command, in [`CommandId`] order.
+pub(crate) static COMMANDS: [Command; 31] = {
+    use CommandCategory::{Assist, Edit, Game, Notes, Share};
+    use CommandId as Id;
+
+    [
+        Command::new(Id::Undo, Some(icon::ARROW_UNDO), Text::Undo, None, Edit)
+            .with_shortcuts(&[KeyTrigger::command(Key::Z)])
+            .with_available(|context| context.can_undo),
+        Command::new(Id::Redo, Some(icon::ARROW_REDO), Text::Redo, None, Edit)
+            .with_shortcuts(&[KeyTrigger::command(Key::Y)])
+            .with_available(|context| context.can_redo),
+        Command::new(
+            Id::ToggleInputMode,
+            Some(icon::PENCIL),
+            Text::KeyToggleInputMode,
+            None,
+            Edit,
+        )
+        .with_shortcuts(&[KeyTrigger::plain(Key::S)]),
+        Command::new(
+            Id::ClearCell,
+            Some(icon::GARBAGE_CAN),
+            Text::KeyClearCell,
+            None,
+            Edit,
+        )
+        .with_shortcuts(&[
+            KeyTrigger::plain(Key::Delete),
+            KeyTrigger::plain(Key::Backspace),
+        ])
+        .with_available(|context| context.has_removable_input),
+        Command::new(
+            Id::ClearScratchpad,
+            Some(icon::GARBAGE_CAN),
+            Text::ClearScratchpad,
+            Some(Text::ClearScratchpadTooltip),
+            Edit,
+        )
+        .with_available(|context| context.has_scratch_strokes),
+        Command::new(
+            Id::AutoFillAllCells,
+            Some(icon::LETTER_UPPER_A),
+            Text::AutoFillAllCells,
+            Some(Text::AutoFillAllCellsTooltip),
+            Notes,
+        )
+        .with_shortcuts(&[KeyTrigger::shift(Key::A)]),
+        Command::new(
+            Id::AutoFillEmptyCells,
+            Some(icon::LETTER_UPPER_A),
+            Text::AutoFillEmptyCells,
+            Some(Text::AutoFillEmptyCellsTooltip),
+            Notes,
+        ),
+        Command::new(
+            Id::AutoFillSelectedCell,
+            Some(icon::LETTER_A),
+            Text::AutoFillSelectedCell,
+            Some(Text::AutoFillSelectedCellTooltip),
+            Notes,
+        )
+        .with_shortcuts(&[KeyTrigger::plain(Key::A)])
+        .with_available(|context| context.can_auto_fill_selected_cell),
+        Command::new(
+            Id::CheckSolvability,
+            Some(icon::SEARCH_RIGHT),
+            Text::CheckSolvability,
+            Some(Text::CheckSolvabilityTooltip),
+            Assist,
+        )
+        .with_shortcuts(&[KeyTrigger::command(Key::K)]),
+        Command::new(
+            Id::CheckProgress,
+            Some(icon::CHECK),
+            Text::CheckProgress,
+            Some(Text::CheckProgressTooltip),
+            Assist,
+        )
+        .with_shortcuts(&[KeyTrigger::command_shift(Key::K)]),
+        Command::new(
+            Id::Hint,
+            Some(icon::LIGHTBULB),
+            Text::Hint,
+            Some(Text::HintTooltip),
+            Assist,
+        )
+        .with_shortcuts(&[KeyTrigger::command(Key::J)]),
+        Command::new(
+            Id::AuditNotes,
+            Some(icon::EYEGLASSES),
+            Text::AuditNotes,
+            Some(Text::AuditNotesTooltip),
+            Assist,
+        ),
+        Command::new(
+            Id::ClearMistakes,
+            Some(icon::CANCEL),
+            Text::ClearMistakes,
+            Some(Text::ClearMistakesTooltip),
+            Assist,
+        ),
+        Command::new(
+            Id::RevealCell,
+            Some(icon::EYE),
+            Text::RevealCell,
+            Some(Text::RevealCellTooltip),
+            Assist,
+        )
+        .with_available(|context| context.can_reveal_selected_cell),
+        Command::new(
+            Id::RevealAll,
+            Some(icon::EYE),
+            Text::RevealAll,
+            Some(Text::RevealAllTooltip),
+            Assist,
+        ),
+        Command::new(
+            Id::ToggleCandidateHeatmap,
+            Some(icon::CONTRAST),
+            Text::CandidateHeatmap,
+            Some(Text::CandidateHeatmapTooltip),
+            Assist,
+        ),
+        Command::new(
+            Id::ToggleInfoPanel,
+            Some(icon::NOTEPAD),
+            Text::GameInfo,
+            Some(Text::GameInfoTooltip),
+            Game,
+        )
+        .with_shortcuts(&[KeyTrigger::command(Key::I)]),
+        Command::new(Id::NewGame, Some(icon::PLUS), Text::NewGame, None, Game)
+            .with_shortcuts(&[KeyTrigger::command(Key::N)])
+            .with_available(|context| context.worker_ready),
+        Command::new(
+            Id::RegeneratePuzzle,
+            Some(icon::LOOP),
+            Text::RegeneratePuzzle,
+            Some(Text::RegeneratePuzzleTooltip),
+            Game,
+        )
+        .with_shortcuts(&[KeyTrigger::command_shift(Key::N)])
+        .with_available(|context| context.worker_ready && context.can_regenerate),
+        Command::new(
+            Id::ResetInputs,
+            Some(icon::ROTATE_CCW),
+            Text::ResetInputs,
+            None,
+            Game,
+        )
+        .with_shortcuts(&[KeyTrigger::command_shift(Key::Backspace)]),
+        Command::new(
+            Id::Settings,
+            Some(icon::GEAR_NO_HUB),
+            Text::Settings,
+            None,
+            Game,
+        )
+        .with_shortcuts(&[KeyTrigger::command(Key::Comma)]),
+        Command::new(
+            Id::Statistics,
+            Some(icon::CHART),
+            Text::Statistics,
+            Some(Text::StatisticsTooltip),
+            Game,
+        ),
+        Command::new(
+            Id::RaceStandings,
+            Some(icon::FLAG_CHECKERED),
+            Text::RaceStandings,
+            Some(Text::RaceStatusTooltip),
+            Game,
+        ),
+        Command::new(
+            Id::Campaign,
+            Some(icon::WORLD_MAP),
+            Text::Campaign,
+            Some(Text::CampaignTooltip),
+            Game,
+        ),
+        Command::new(
+            Id::Learn,
+            Some(icon::GRADUATION),
+            Text::Learn,
+            Some(Text::LearnTooltip),
+            Game,
+        ),
+        Command::new(
+            Id::BugReport,
+            Some(icon::ANIMAL_BUG),
+            Text::BugReport,
+            Some(Text::BugReportTooltip),
+            Game,
+        ),
+        Command::new(
+            Id::ExportPuzzle,
+            None,
+            Text::ExportPuzzle,
+            Some(Text::ExportPuzzleTooltip),
+            Share,
+        ),
+        Command::new(
+            Id::ExportProgress,
+            None,
+            Text::ExportProgress,
+            Some(Text::ExportProgressTooltip),
+            Share,
+        ),
+        Command::new(
+            Id::CopyPuzzleLink,
+            None,
+            Text::CopyPuzzleLink,
+            Some(Text::CopyPuzzleLinkTooltip),
+            Share,
+        ),
+        Command::new(
+            Id::CopyProgressLink,
+            None,
+            Text::CopyProgressLink,
+            Some(Text::CopyProgressLinkTooltip),
+            Share,
+        ),
+        Command::new(
+            Id::CopyBoardImage,
+            None,
+            Text::CopyBoardImage,
+            Some(Text::CopyBoardImageTooltip),
+            Share,
+        ),
+    ]
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn commands_are_registered_in_id_order() {
+        for (index, command) in COMMANDS.iter().enumerate() {
+            assert_eq!(command.id as usize, index, "{:?}", command.id);
+        }
+    }
+
+    #[test]
+    fn shortcuts_are_not_shared() {
+        let triggers: Vec<_> = COMMANDS
+            .iter()
+            .flat_map(|command| command.default_shortcuts)
+            .collect();
+        for (index, trigger) in triggers.iter().enumerate() {
+            assert!(!triggers[..index].contains(trigger), "{trigger:?}");
+        }
+    }
+
+    #[test]
+    fn availability_follows_the_context() {
+        let context = CommandContext {
+            can_undo: true,
+            ..CommandContext::default()
+        };
+        assert!(CommandId::Undo.command().is_available(context));
+        assert!(!CommandId::Redo.command().is_available(context));
+        assert!(!CommandId::NewGame.command().is_available(context));
+        assert!(CommandId::Hint.command().is_available(context));
+    }
+
+    #[test]
+    fn rebinding_takes_the_trigger_from_its_old_command() {
+        let mut bindings = KeyBindings::default();
+        let undo = KeyTrigger::command(Key::Z);
+
+        assert!(rebind(&mut bindings, CommandId::Redo, undo));
+        assert_eq!(CommandId::Redo.command().shortcuts(&bindings), [undo]);
+        assert_eq!(CommandId::Undo.command().shortcuts(&bindings), []);
+
+        reset_binding(&mut bindings, CommandId::Undo);
+        assert_eq!(CommandId::Undo.command().shortcuts(&bindings), [undo]);
+        assert_eq!(CommandId::Redo.command().shortcuts(&bindings), []);
+        assert!(CommandId::Redo.command().is_rebound(&bindings));
+    }
+
+    #[test]
+    fn movement_and_digit_keys_cannot_be_rebound() {
+        let mut bindings = KeyBindings::default();
+        for trigger in [
+            KeyTrigger::plain(Key::ArrowUp),
+            KeyTrigger::command(Key::Num3),
+        ] {
+            assert!(!rebind(&mut bindings, CommandId::Undo, trigger));
+        }
+        assert_eq!(bindings, KeyBindings::default());
+    }
+}
@@ -0,0 +1,53 @@
+//! Font setup.
+//!
+//! egui's bundled fonts do not cover Japanese, so on native builds a CJK font
+//! installed on the system is appended as a fallback when one is found.
+//! Web builds keep the bundled fonts only.
+
+use eframe::egui::Context;
+
+#[cfg(not(target_arch = "wasm32"))]
+const CJK_FONT_PATHS: &[&str] = &[
+    "/usr/share/fonts/opentype/noto/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/noto-cjk/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/google-noto-cjk/NotoSansCJK-Regular.ttc",
+    "/System/Library/Fonts/ヒラギノ角ゴシック W3.ttc",
+    "/System/Library/Fonts/Hiragino Sans GB.ttc",
+    "C:\\Windows\\Fonts\\YuGothM.ttc",
+    "C:\\Windows\\Fonts\\meiryo.ttc",
+    "C:\\Windows\\Fonts\\msgothic.ttc",
+];
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn install(ctx: &Context) {
+    use std::sync::Arc;
+
+    use eframe::egui::{FontData, FontDefinitions, FontFamily};
+
+    const CJK_FONT_NAME: &str = "cjk-fallback";
+
+    let Some(data) = CJK_FONT_PATHS
+        .iter()
+        .find_map(|path| std::fs::read(path).ok())
+    else {
+        log::info!("No CJK font found; Japanese text may not render");
+        return;
+    };
+
+    let mut fonts = FontDefinitions::default();
+    fonts.font_data.insert(
+        CJK_FONT_NAME.to_owned(),
+        Arc::new(FontData::from_owned(data)),
+    );
+    for family in [FontFamily::Proportional, FontFamily::Monospace] {
+        fonts
+            .families
+            .entry(family)
+            .or_default()
+            .push(CJK_FONT_NAME.to_owned());
+    }
+    ctx.set_fonts(fonts);
+}
+
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn install(_ctx: &Context) {}
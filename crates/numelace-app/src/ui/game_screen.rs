@@ -4,13 +4,16 @@ use eframe::{
 };
 use egui_extras::{Size, StripBuilder};
 
-use super::{grid, keypad, toolbar};
+use super::{grid, info_panel, keypad, notification, review, toolbar};
 use crate::{
     action::ActionRequestQueue,
+    state::{DisplaySettings, NotificationEntry},
     ui::{
         grid::GridViewModel,
+        info_panel::InfoPanelViewModel,
         keypad::KeypadViewModel,
-        layout::LayoutScale,
+        layout::{ComponentUnits, LayoutScale},
+        review::ReviewViewModel,
         status_line::{self, StatusLineViewModel},
         toolbar::ToolbarViewModel,
     },
@@ -22,6 +25,13 @@ pub(crate) struct GameScreenViewModel<'a> {
     pub(crate) status_line: StatusLineViewModel<'a>,
     pub(crate) grid: GridViewModel<'a>,
     pub(crate) keypad: KeypadViewModel<'a>,
+    pub(crate) display: DisplaySettings,
+    pub(crate) notifications: &'a [NotificationEntry],
+    /// Timeline shown in place of the keypad while a solved puzzle is
+    /// reviewed.
+    pub(crate) review: Option<ReviewViewModel<'a>>,
+    /// Game info shown in a sidebar, when the player opened it.
+    pub(crate) info_panel: Option<InfoPanelViewModel>,
 }
 
 impl<'a> GameScreenViewModel<'a> {
@@ -31,40 +41,71 @@ impl<'a> GameScreenViewModel<'a> {
         status_line: StatusLineViewModel<'a>,
         grid: GridViewModel<'a>,
         keypad: KeypadViewModel<'a>,
+        display: DisplaySettings,
+        notifications: &'a [NotificationEntry],
     ) -> Self {
         Self {
             toolbar,
             status_line,
             grid,
             keypad,
+            display,
+            notifications,
+            review: None,
+            info_panel: None,
         }
     }
+
+    #[must_use]
+    pub(crate) fn with_review(mut self, review: Option<ReviewViewModel<'a>>) -> Self {
+        self.review = review;
+        self
+    }
+
+    #[must_use]
+    pub(crate) fn with_info_panel(mut self, info_panel: Option<InfoPanelViewModel>) -> Self {
+        self.info_panel = info_panel;
+        self
+    }
 }
 
 pub(crate) fn show(ui: &mut Ui, vm: &GameScreenViewModel, action_queue: &mut ActionRequestQueue) {
+    // The sidebar takes its width first, so the board fits the rest.
+    if let Some(info_panel) = &vm.info_panel {
+        info_panel::show(ui, info_panel, action_queue);
+    }
     let avail = ui.available_size();
     let toolbar_units = toolbar::required_units();
-    let grid_units = grid::required_units();
+    let grid_units = grid::required_units(vm.grid.shows_coordinates());
     let status_units = status_line::required_units();
     let keypad_units = keypad::required_units();
 
-    let width_units = toolbar_units
-        .width
-        .max(grid_units.width)
-        .max(status_units.width)
-        .max(keypad_units.width);
-    let height_units =
-        toolbar_units.height + grid_units.height + status_units.height + keypad_units.height;
+    let ComponentUnits {
+        width: width_units,
+        height: height_units,
+    } = required_units(vm.grid.shows_coordinates());
     let cell_size_width = avail.x / width_units;
     let cell_size_height = avail.y / height_units;
     let cell_size = round_cell_size(cell_size_width.min(cell_size_height).max(0.0));
 
-    let scale = LayoutScale::new(cell_size);
+    let mut scale = LayoutScale::new(cell_size, &vm.display);
     let toolbar_height = toolbar_units.height * cell_size;
     let status_height = status_units.height * cell_size;
     let grid_height = grid_units.height * cell_size;
     let grid_width = grid_height;
-    let keypad_height = keypad_units.height * cell_size;
+    let mut keypad_height = keypad_units.height * cell_size;
+
+    // On portrait viewports (phones), the grid is limited by the width and the
+    // leftover height is given to a larger keypad.
+    if avail.x < avail.y * PORTRAIT_ASPECT_RATIO && cell_size > 0.0 {
+        let spare_height = avail.y - height_units * cell_size;
+        let key_scale = ((keypad_height + spare_height) / keypad_height)
+            .min(grid_units.width / keypad_units.width)
+            .clamp(1.0, MAX_TOUCH_KEY_SCALE);
+        let key_size = round_cell_size(cell_size * key_scale);
+        keypad_height = keypad_units.height * key_size;
+        scale = scale.with_touch_layout(key_size);
+    }
 
     ui.scope(|ui| {
         ui.spacing_mut().item_spacing = Vec2::ZERO;
@@ -92,7 +133,11 @@ pub(crate) fn show(ui: &mut Ui, vm: &GameScreenViewModel, action_queue: &mut Act
                                     grid::show(ui, &vm.grid, &scale, action_queue);
                                 });
                                 strip.cell(|ui| {
-                                    keypad::show(ui, &vm.keypad, &scale, action_queue);
+                                    if let Some(review) = &vm.review {
+                                        review::show(ui, review, &scale, action_queue);
+                                    } else {
+                                        keypad::show(ui, &vm.keypad, &scale, action_queue);
+                                    }
                                 });
                                 strip.empty();
                             });
@@ -100,8 +145,45 @@ pub(crate) fn show(ui: &mut Ui, vm: &GameScreenViewModel, action_queue: &mut Act
                 });
         });
     });
+
+    notification::show(ui.ctx(), vm.notifications, action_queue);
 }
 
+/// Returns the size of the whole game screen in cells.
+fn required_units(coordinates: bool) -> ComponentUnits {
+    let toolbar_units = toolbar::required_units();
+    let grid_units = grid::required_units(coordinates);
+    let status_units = status_line::required_units();
+    let keypad_units = keypad::required_units();
+    ComponentUnits::new(
+        toolbar_units
+            .width
+            .max(grid_units.width)
+            .max(status_units.width)
+            .max(keypad_units.width),
+        toolbar_units.height + grid_units.height + status_units.height + keypad_units.height,
+    )
+}
+
+/// Returns the smallest window size for the game screen.
+///
+/// Cells are kept at [`MIN_CELL_SIZE`] scaled by the digit size, so larger
+/// digits keep room around them in a small window. The size never goes below
+/// [`crate::MIN_WINDOW_SIZE`], which leaves room for the toolbar.
+#[must_use]
+pub(crate) fn min_window_size(display: &DisplaySettings, coordinates: bool) -> Vec2 {
+    let units = required_units(coordinates);
+    let cell_size = MIN_CELL_SIZE * display.digit_size;
+    (Vec2::new(units.width, units.height) * cell_size).max(Vec2::from(crate::MIN_WINDOW_SIZE))
+}
+
+/// Smallest cell size at the default digit size, in points.
+const MIN_CELL_SIZE: f32 = 22.0;
+/// Viewports narrower than this width/height ratio use the touch layout.
+const PORTRAIT_ASPECT_RATIO: f32 = 0.8;
+/// Upper bound of the keypad button size relative to the cell size in the touch layout.
+const MAX_TOUCH_KEY_SCALE: f32 = 1.4;
+
 /// Snap the computed cell size to the GUI rounding grid so layout and rendering
 /// agree on pixel boundaries and avoid cumulative rounding drift.
 /// The `0.01` factor forces a 1/100-cell quantization first, then snaps that
@@ -122,3 +204,21 @@ fn with_horizontal_center(ui: &mut Ui, width: f32, add: impl FnOnce(&mut Ui)) {
             strip.empty();
         });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn min_window_size_grows_with_the_digit_size() {
+        let normal = min_window_size(&DisplaySettings::NORMAL, false);
+        assert_eq!(normal, Vec2::from(crate::MIN_WINDOW_SIZE));
+
+        let large = DisplaySettings {
+            digit_size: *DisplaySettings::DIGIT_SIZE_RANGE.end(),
+            ..DisplaySettings::NORMAL
+        };
+        assert!(min_window_size(&large, false).y > normal.y);
+        assert!(min_window_size(&large, true).y > min_window_size(&large, false).y);
+    }
+}
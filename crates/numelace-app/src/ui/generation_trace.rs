@@ -0,0 +1,211 @@
+use eframe::egui::{
+    Align2, Context, FontId, Pos2, Rect, Sense, Slider, Stroke, StrokeKind, Ui, Vec2, Window, vec2,
+};
+use numelace_core::Position;
+
+use crate::{
+    action::{ActionRequestQueue, UiAction},
+    i18n::{Text, tr, tr_args},
+    state::{GenerationTraceView, TraceShading},
+};
+
+const CELL_SIZE: f32 = 22.0;
+const CHART_SIZE: Vec2 = vec2(9.0 * CELL_SIZE, 80.0);
+
+/// Shows the generator trace of the last new puzzle in a developer window.
+///
+/// The window is not modal, so the trace can be compared with the board.
+pub(crate) fn show(
+    ctx: &Context,
+    view: &mut GenerationTraceView,
+    action_queue: &mut ActionRequestQueue,
+) {
+    let mut open = true;
+    Window::new(tr(Text::GenerationTrace))
+        .open(&mut open)
+        .resizable(false)
+        .show(ctx, |ui| {
+            show_summary(ui, view);
+            ui.separator();
+            show_entropy_chart(ui, view);
+            let step_count = view.trace.steps.len();
+            ui.add(Slider::new(&mut view.step, 0..=step_count).text(tr(Text::TraceStep)));
+            show_step(ui, view);
+            ui.horizontal(|ui| {
+                ui.label(tr(Text::TraceShading));
+                ui.radio_value(
+                    &mut view.shading,
+                    TraceShading::Candidates,
+                    tr(Text::TraceShadingCandidates),
+                );
+                ui.radio_value(
+                    &mut view.shading,
+                    TraceShading::RemovalOrder,
+                    tr(Text::TraceShadingRemovalOrder),
+                );
+            });
+            show_board(ui, view);
+        });
+    if !open {
+        action_queue.request(UiAction::CloseGenerationTrace.into());
+    }
+}
+
+fn show_summary(ui: &mut Ui, view: &GenerationTraceView) {
+    let trace = &view.trace;
+    ui.label(tr_args(
+        Text::TraceStrategies,
+        &[
+            ("fill", &trace.fill_strategy),
+            ("carve", &trace.carve_strategy),
+        ],
+    ));
+    ui.label(tr_args(
+        Text::TraceUniquenessChecks,
+        &[
+            ("count", &trace.uniqueness_checks()),
+            ("kept", &trace.kept_removals()),
+        ],
+    ));
+    ui.label(tr_args(
+        Text::TraceSolverRuns,
+        &[("count", &trace.solver_runs)],
+    ));
+}
+
+/// Plots the entropy of every checked problem: kept steps joined by a line,
+/// rejected ones as faint dots, and the viewed step as a vertical rule.
+fn show_entropy_chart(ui: &mut Ui, view: &GenerationTraceView) {
+    let (rect, _) = ui.allocate_exact_size(CHART_SIZE, Sense::hover());
+    let painter = ui.painter_at(rect);
+    let visuals = ui.visuals();
+    painter.rect_filled(rect, 0.0, visuals.extreme_bg_color);
+    let steps = &view.trace.steps;
+    let max_entropy = steps.iter().map(|step| step.entropy).fold(1.0, f64::max);
+    #[expect(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+    let point = |index: usize, entropy: f64| -> Pos2 {
+        let x = (index + 1) as f32 / steps.len().max(1) as f32;
+        let y = (entropy / max_entropy) as f32;
+        rect.lerp_inside(vec2(x, 1.0 - y))
+    };
+    let kept: Vec<Pos2> = steps
+        .iter()
+        .enumerate()
+        .filter(|(_, step)| step.kept)
+        .map(|(index, step)| point(index, step.entropy))
+        .collect();
+    let accent = visuals.selection.bg_fill;
+    painter.line(kept, Stroke::new(1.5, accent));
+    let rejected = visuals.error_fg_color.gamma_multiply(0.5);
+    for (index, step) in steps.iter().enumerate().filter(|(_, step)| !step.kept) {
+        painter.circle_filled(point(index, step.entropy), 1.5, rejected);
+    }
+    if view.step > 0 {
+        let x = point(view.step - 1, 0.0).x;
+        painter.vline(x, rect.y_range(), Stroke::new(1.0, visuals.text_color()));
+    }
+}
+
+fn show_step(ui: &mut Ui, view: &GenerationTraceView) {
+    let Some(step) = view
+        .step
+        .checked_sub(1)
+        .map(|index| &view.trace.steps[index])
+    else {
+        ui.weak(tr(Text::TraceSolution));
+        return;
+    };
+    let cells = step
+        .removed
+        .into_iter()
+        .map(|pos| pos.to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let text = if step.kept {
+        Text::TraceStepKept
+    } else {
+        Text::TraceStepRejected
+    };
+    ui.label(tr_args(
+        text,
+        &[("cells", &cells), ("bits", &format!("{:.1}", step.entropy))],
+    ));
+}
+
+/// Draws the problem at the viewed step, shading empty cells and outlining
+/// the cells the step tried to remove.
+fn show_board(ui: &mut Ui, view: &GenerationTraceView) {
+    let (rect, _) = ui.allocate_exact_size(Vec2::splat(9.0 * CELL_SIZE), Sense::hover());
+    let painter = ui.painter_at(rect);
+    let visuals = ui.visuals();
+    let problem = view.problem();
+    let counts = view.candidate_counts();
+    let tried = view
+        .step
+        .checked_sub(1)
+        .map(|index| view.trace.steps[index]);
+    let font = FontId::proportional(CELL_SIZE * 0.6);
+    let small_font = FontId::proportional(CELL_SIZE * 0.4);
+    for pos in Position::ALL {
+        let min = rect.min + vec2(f32::from(pos.col()), f32::from(pos.row())) * CELL_SIZE;
+        let cell = Rect::from_min_size(min, Vec2::splat(CELL_SIZE));
+        if let Some(digit) = problem.get(pos) {
+            painter.text(
+                cell.center(),
+                Align2::CENTER_CENTER,
+                digit.value().to_string(),
+                font.clone(),
+                visuals.text_color(),
+            );
+        } else {
+            let (fraction, label) = match view.shading {
+                TraceShading::Candidates => {
+                    let count = counts[pos];
+                    (f32::from(count) / 9.0, count.to_string())
+                }
+                TraceShading::RemovalOrder => {
+                    let number = view.removal_numbers()[pos].unwrap_or_default();
+                    #[expect(clippy::cast_precision_loss)]
+                    let fraction = 1.0 - f32::from(number) / view.removal_count().max(1) as f32;
+                    (fraction, number.to_string())
+                }
+            };
+            let fill = visuals
+                .selection
+                .bg_fill
+                .gamma_multiply(fraction.clamp(0.0, 1.0));
+            painter.rect_filled(cell, 0.0, fill);
+            painter.text(
+                cell.center(),
+                Align2::CENTER_CENTER,
+                label,
+                small_font.clone(),
+                visuals.weak_text_color(),
+            );
+        }
+        if let Some(step) = tried.filter(|step| step.removed.contains(pos)) {
+            let color = if step.kept {
+                visuals.selection.stroke.color
+            } else {
+                visuals.error_fg_color
+            };
+            painter.rect_stroke(
+                cell.shrink(1.0),
+                0.0,
+                Stroke::new(2.0, color),
+                StrokeKind::Inside,
+            );
+        }
+    }
+    let line = Stroke::new(1.0, visuals.weak_text_color());
+    for index in 0..=9_u8 {
+        let offset = f32::from(index) * CELL_SIZE;
+        let stroke = if index % 3 == 0 {
+            Stroke::new(2.0, visuals.text_color())
+        } else {
+            line
+        };
+        painter.vline(rect.left() + offset, rect.y_range(), stroke);
+        painter.hline(rect.x_range(), rect.top() + offset, stroke);
+    }
+}
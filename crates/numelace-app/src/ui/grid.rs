@@ -1,14 +1,22 @@
 use std::sync::Arc;
 
 use eframe::egui::{
-    Align2, Color32, FontId, Painter, Pos2, Rect, Sense, Stroke, StrokeKind, Ui, Vec2,
+    Align2, Color32, FontId, Painter, Pos2, Rect, Response, Sense, Shape, Stroke, StrokeKind, Ui,
+    Vec2, WidgetInfo, WidgetType,
+};
+use numelace_core::{
+    CageSet, Digit, DigitSet, EdgeConstraintSet, EdgeKind, Position, PositionIndexedArray,
+    RegionLayout,
 };
-use numelace_core::{Digit, DigitSet, Position, PositionIndexedArray};
 use numelace_game::CellState;
+use numelace_solver::{ChainNode, LinkKind, TechniqueLink};
 
 use crate::{
-    action::{ActionRequestQueue, BoardMutationAction, SelectionAction},
-    state::HighlightSettings,
+    action::{ActionRequestQueue, BoardMutationAction, MoveDirection, SelectionAction, UiAction},
+    state::{
+        GivenStyle, GridStyle, HighlightIntensity, HighlightSettings, NoteLayout, ScratchStroke,
+        ScratchTool, Scratchpad, ThemeSettings,
+    },
     ui::{
         grid_theme::{GridPalette, GridTheme},
         input::InputContext,
@@ -31,6 +39,9 @@ bitflags::bitflags! {
         const HINT_APPLICATION_PLACEMENT = 0x0200;
         const HINT_APPLICATION_ELIMINATION = 0x0400;
         const HINT_APPLICATION_TEMPORARY = 0x0800;
+        const SELECTED_DIGIT_CANDIDATE = 0x1000;
+        const NOTE_CLEANUP = 0x2000;
+        const BIVALUE = 0x4000;
     }
 }
 
@@ -39,6 +50,18 @@ pub(crate) struct GridCell {
     pub(crate) content: CellState,
     pub(crate) visual_state: GridVisualState,
     pub(crate) note_visual_state: NoteVisualState,
+    /// Center notes of a cell without a digit.
+    pub(crate) center_notes: DigitSet,
+    /// Candidate count of a cell without a digit, when the heatmap is shown.
+    pub(crate) candidate_count: Option<u8>,
+    /// Hints and reveals that pointed at the cell, when the hint heat map of a
+    /// solved game is shown.
+    pub(crate) hint_count: Option<u8>,
+    /// Trial depth needed to decide a cell without a digit, when the hardness
+    /// overlay is shown; cells beyond the analysis limit get one more than it.
+    pub(crate) deduction_depth: Option<u8>,
+    /// Description of the cell announced by screen readers.
+    pub(crate) accessible_label: String,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Eq)]
@@ -50,6 +73,8 @@ pub(crate) struct NoteVisualState {
     pub(crate) hint_condition_temporary: DigitSet,
     pub(crate) hint_application_elimination: DigitSet,
     pub(crate) hint_application_temporary: DigitSet,
+    /// Notes that note cleanup removes when the previewed hint is applied.
+    pub(crate) note_cleanup: DigitSet,
 }
 
 impl NoteVisualState {
@@ -63,6 +88,7 @@ impl NoteVisualState {
             hint_condition_temporary,
             hint_application_elimination,
             hint_application_temporary,
+            note_cleanup,
         } = self;
         let mut vs = GridVisualState::empty();
         if selected_digit.contains(digit) {
@@ -86,37 +112,67 @@ impl NoteVisualState {
         if hint_application_temporary.contains(digit) {
             vs |= GridVisualState::HINT_APPLICATION_TEMPORARY;
         }
+        if note_cleanup.contains(digit) {
+            vs |= GridVisualState::NOTE_CLEANUP;
+        }
         vs
     }
 }
 
 #[derive(Debug, Clone)]
 pub(crate) struct GridViewModel<'a> {
-    grid: PositionIndexedArray<GridCell>,
+    grid: &'a PositionIndexedArray<GridCell>,
+    cages: CageSet,
+    edges: EdgeConstraintSet,
+    regions: RegionLayout,
     enabled_highlights: GridVisualState,
+    /// Highlights drawn on notes; like `enabled_highlights`, but the selected
+    /// digit has its own toggle for notes.
+    enabled_note_highlights: GridVisualState,
+    intensity: HighlightIntensity,
+    theme: ThemeSettings,
     input_context: &'a InputContext,
+    /// Links between candidates drawn as arrows for the current hint.
+    links: Vec<TechniqueLink>,
+    /// Scratchpad tool that drags over the grid draw with.
+    scratch_tool: Option<ScratchTool>,
+    scratch_strokes: &'a [ScratchStroke],
+    style: GridStyle,
 }
 
 impl<'a> GridViewModel<'a> {
     #[must_use]
     pub(crate) fn new(
-        grid: PositionIndexedArray<GridCell>,
+        grid: &'a PositionIndexedArray<GridCell>,
+        cages: CageSet,
+        edges: EdgeConstraintSet,
+        regions: RegionLayout,
         highlight_settings: &HighlightSettings,
+        theme: ThemeSettings,
         input_context: &'a InputContext,
     ) -> Self {
-        let mut enabled_highlights = GridVisualState::SELECTED_CELL
-            | GridVisualState::HINT_CONDITION_CELL
+        let mut enabled_highlights = GridVisualState::HINT_CONDITION_CELL
             | GridVisualState::HINT_CONDITION_DIGIT
             | GridVisualState::HINT_CONDITION_TEMPORARY
             | GridVisualState::HINT_APPLICATION_PLACEMENT
             | GridVisualState::HINT_APPLICATION_ELIMINATION
-            | GridVisualState::HINT_APPLICATION_TEMPORARY;
+            | GridVisualState::HINT_APPLICATION_TEMPORARY
+            | GridVisualState::NOTE_CLEANUP;
         let HighlightSettings {
+            selected_cell,
             selected_digit,
+            selected_digit_note,
             selected_cell_peer,
             selected_digit_peer,
             conflict,
+            selected_digit_candidate,
+            bivalue_cell,
+            bivalue_source: _,
+            intensity,
         } = highlight_settings;
+        if *selected_cell {
+            enabled_highlights |= GridVisualState::SELECTED_CELL;
+        }
         if *selected_digit_peer {
             enabled_highlights |= GridVisualState::SELECTED_DIGIT_PEER;
         }
@@ -129,13 +185,64 @@ impl<'a> GridViewModel<'a> {
         if *conflict {
             enabled_highlights |= GridVisualState::CONFLICT;
         }
+        if *selected_digit_candidate {
+            enabled_highlights |= GridVisualState::SELECTED_DIGIT_CANDIDATE;
+        }
+        if *bivalue_cell {
+            enabled_highlights |= GridVisualState::BIVALUE;
+        }
+        let mut enabled_note_highlights = enabled_highlights - GridVisualState::SELECTED_DIGIT;
+        if *selected_digit_note {
+            enabled_note_highlights |= GridVisualState::SELECTED_DIGIT;
+        }
         Self {
             grid,
+            cages,
+            edges,
+            regions,
             enabled_highlights,
+            enabled_note_highlights,
+            intensity: *intensity,
+            theme,
             input_context,
+            links: Vec::new(),
+            scratch_tool: None,
+            scratch_strokes: &[],
+            style: GridStyle::default(),
         }
     }
 
+    /// Returns the view model with `links` drawn as arrows over the cells.
+    #[must_use]
+    pub(crate) fn with_links(mut self, links: Vec<TechniqueLink>) -> Self {
+        self.links = links;
+        self
+    }
+
+    /// Returns the view model drawing notes, givens and coordinates in `style`.
+    #[must_use]
+    pub(crate) fn with_style(mut self, style: GridStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Returns whether row and column numbers are drawn around the board.
+    #[must_use]
+    pub(crate) fn shows_coordinates(&self) -> bool {
+        self.style.coordinates
+    }
+
+    /// Returns the view model with the scratchpad drawn over the cells.
+    ///
+    /// While a scratchpad tool is selected, drags over the grid draw instead of
+    /// selecting cells.
+    #[must_use]
+    pub(crate) fn with_scratchpad(mut self, scratchpad: &'a Scratchpad) -> Self {
+        self.scratch_tool = scratchpad.tool;
+        self.scratch_strokes = scratchpad.strokes();
+        self
+    }
+
     fn grid_thick_border(palette: &GridPalette, cell_size: f32) -> Stroke {
         let base_width = f32::max(cell_size * CELL_BORDER_WIDTH_BASE_RATIO, 1.0);
         Stroke::new(
@@ -147,6 +254,10 @@ impl<'a> GridViewModel<'a> {
     fn effective_visual_state(&self, state: GridVisualState) -> EffectiveGridVisualState {
         EffectiveGridVisualState(self.enabled_highlights & state)
     }
+
+    fn effective_note_visual_state(&self, state: GridVisualState) -> EffectiveGridVisualState {
+        EffectiveGridVisualState(self.enabled_note_highlights & state)
+    }
 }
 
 pub(crate) const GRID_CELLS: f32 = 9.0;
@@ -157,9 +268,14 @@ pub(crate) fn grid_side_with_border(cell_size: f32) -> f32 {
     GRID_CELLS * cell_size + thick_border * 4.0
 }
 
+/// Returns the size of the grid in cells, including the gutter for row and
+/// column numbers when `coordinates` is set.
 #[must_use]
-pub(crate) const fn required_units() -> ComponentUnits {
-    let len = GRID_CELLS + CELL_BORDER_WIDTH_BASE_RATIO * (THICK_BORDER_WIDTH_RATIO * 4.0);
+pub(crate) const fn required_units(coordinates: bool) -> ComponentUnits {
+    let mut len = GRID_CELLS + CELL_BORDER_WIDTH_BASE_RATIO * (THICK_BORDER_WIDTH_RATIO * 4.0);
+    if coordinates {
+        len += COORDINATE_GUTTER_RATIO * 2.0;
+    }
     ComponentUnits::new(len, len)
 }
 
@@ -175,6 +291,21 @@ const SELECTED_CELL_BORDER_WIDTH_RATIO: f32 = 3.0;
 const SELECTED_DIGIT_BORDER_WIDTH_RATIO: f32 = 1.0;
 const SELECTED_CELL_PEER_BORDER_WIDTH_RATIO: f32 = 0.5;
 const HINT_CORNER_WIDTH_RATIO: f32 = 3.0;
+const CAGE_INSET_RATIO: f32 = 0.08;
+const CAGE_DASH_RATIO: f32 = 0.08;
+const EDGE_MARKER_RATIO: f32 = 0.14;
+const LINK_BEND_RATIO: f32 = 0.2;
+const LINK_ARROW_RATIO: f32 = 0.12;
+const LINK_CURVE_SEGMENTS: u8 = 16;
+const SCRATCH_WIDTH_RATIO: f32 = 2.0;
+/// Width of the row and column number gutter, relative to the cell size.
+///
+/// The gutter is kept on all four sides so the board stays centered, and
+/// numbers are drawn on the left and top.
+const COORDINATE_GUTTER_RATIO: f32 = 0.4;
+const GIVEN_BOX_INSET_RATIO: f32 = 0.1;
+/// Notes per line in [`NoteLayout::Linear`].
+const LINEAR_NOTE_COLUMNS: usize = 5;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 struct EffectiveGridVisualState(GridVisualState);
@@ -191,13 +322,29 @@ impl EffectiveGridVisualState {
         }
     }
 
+    fn note_text_color(self, palette: &GridPalette) -> Color32 {
+        if self.0.intersects(GridVisualState::CONFLICT) {
+            return palette.text_conflict;
+        }
+        if self.0.intersects(GridVisualState::NOTE_CLEANUP) {
+            return palette.text_note_cleanup;
+        }
+        palette.text_note
+    }
+
     fn cell_fill_color(self, palette: &GridPalette) -> Color32 {
         if self.0.intersects(GridVisualState::SELECTED_DIGIT) {
             return palette.cell_bg_selected_digit;
         }
+        if self.0.intersects(GridVisualState::SELECTED_DIGIT_CANDIDATE) {
+            return palette.cell_bg_selected_digit_candidate;
+        }
         if self.0.intersects(GridVisualState::SELECTED_DIGIT_PEER) {
             return palette.cell_bg_selected_digit_peer;
         }
+        if self.0.intersects(GridVisualState::BIVALUE) {
+            return palette.cell_bg_bivalue;
+        }
         palette.cell_bg_default
     }
 
@@ -316,6 +463,13 @@ impl EffectiveGridVisualState {
         }
         None
     }
+
+    fn note_cleanup_stroke(self, rect: Rect, palette: &GridPalette) -> Option<Stroke> {
+        if self.0.intersects(GridVisualState::NOTE_CLEANUP) {
+            return Some(Stroke::new(rect.height() * 0.1, palette.text_note_cleanup));
+        }
+        None
+    }
 }
 
 pub(crate) fn show(
@@ -327,16 +481,41 @@ pub(crate) fn show(
     let cell_size = scale.cell_size;
     let style = Arc::clone(ui.style());
     let visuals = &style.visuals;
-    let grid_theme = GridTheme::from_visuals(visuals);
-    let palette = grid_theme.palette_for(visuals);
-    let grid_side = grid_side_with_border(cell_size);
+    let grid_theme = GridTheme::from_settings(&vm.theme, visuals);
+    let palette = &grid_theme
+        .palette_for(visuals)
+        .with_intensity(&vm.intensity);
+    let gutter = if vm.style.coordinates {
+        cell_size * COORDINATE_GUTTER_RATIO
+    } else {
+        0.0
+    };
+    let grid_side = grid_side_with_border(cell_size) + gutter * 2.0;
 
-    let (rect, _response) = ui.allocate_exact_size(Vec2::splat(grid_side), Sense::hover());
+    // In the touch layout the whole grid senses drags for swipe navigation;
+    // cells added later still receive taps. The scratchpad takes over drags in
+    // every layout.
+    let sense = if scale.touch || vm.scratch_tool.is_some() {
+        Sense::drag()
+    } else {
+        Sense::hover()
+    };
+    let (outer_rect, response) = ui.allocate_exact_size(Vec2::splat(grid_side), sense);
+    let rect = outer_rect.shrink(gutter);
 
     let thick_border = GridViewModel::grid_thick_border(palette, cell_size);
     let base_border = f32::max(cell_size * CELL_BORDER_WIDTH_BASE_RATIO, 1.0);
     let inner_rect = rect.shrink(thick_border.width);
 
+    let scratch = ScratchGeometry {
+        rect,
+        inner_rect,
+        cell_size,
+        thick_border_width: thick_border.width,
+        stroke: Stroke::new(base_border * SCRATCH_WIDTH_RATIO, palette.scratch),
+    };
+    let scratch_preview = handle_grid_drag(ui, vm, &response, &scratch, action_queue);
+
     let painter = ui.painter();
     draw_outer_border(painter, rect, thick_border);
 
@@ -346,17 +525,18 @@ pub(crate) fn show(
             let cell = &vm.grid[pos];
             let vs = vm.effective_visual_state(cell.visual_state);
 
-            let col_f = f32::from(col);
-            let row_f = f32::from(row);
-            let cell_min = inner_rect.min
-                + Vec2::new(
-                    cell_size * col_f + (col_f / 3.0).floor() * thick_border.width,
-                    cell_size * row_f + (row_f / 3.0).floor() * thick_border.width,
-                );
-            let cell_max = cell_min + Vec2::splat(cell_size);
-            let cell_rect = Rect::from_min_max(cell_min, cell_max);
+            let cell_rect = cell_rect(inner_rect, cell_size, thick_border.width, pos);
 
             draw_cell_fill(painter, cell_rect, vs.cell_fill_color(palette));
+            if let Some(count) = cell.candidate_count {
+                draw_cell_fill(painter, cell_rect, heatmap_color(count, palette));
+            }
+            if let Some(count) = cell.hint_count {
+                draw_cell_fill(painter, cell_rect, hint_heat_color(count, palette));
+            }
+            if let Some(depth) = cell.deduction_depth.filter(|&depth| depth > 0) {
+                draw_cell_fill(painter, cell_rect, deduction_depth_color(depth, palette));
+            }
             draw_cell_border(painter, cell_rect, vs.cell_base_border(palette, cell_size));
             if let Some(stroke) = vs.cell_overlay_border(palette, cell_size) {
                 draw_cell_border(painter, cell_rect, stroke);
@@ -366,61 +546,687 @@ pub(crate) fn show(
                 draw_corners(painter, cell_rect, stroke);
             }
 
-            if let Some(digits) = cell.content.as_notes() {
-                let notes_rect = cell_rect.shrink(base_border * SELECTED_CELL_BORDER_WIDTH_RATIO);
-                draw_notes(
-                    painter,
-                    vm,
-                    notes_rect,
-                    digits,
-                    &cell.note_visual_state,
-                    palette,
-                );
-            } else if let Some(digit) = cell.content.as_digit() {
-                if let Some(color) = vs.hint_digit_pill_color(palette) {
-                    draw_digit_pill(painter, cell_rect.center(), cell_size, color);
+            draw_cell_content(painter, vm, pos, cell_rect, base_border, scale, palette);
+
+            // The scratchpad takes the pointer while one of its tools is selected.
+            if vm.scratch_tool.is_none() {
+                interact_cell(ui, vm, pos, cell_rect, action_queue);
+            }
+        }
+    }
+
+    draw_variant_overlays(
+        painter,
+        vm,
+        inner_rect,
+        cell_size,
+        thick_border,
+        base_border,
+        palette,
+    );
+    if vm.style.coordinates {
+        draw_coordinates(painter, inner_rect, cell_size, thick_border.width, palette);
+    }
+    for stroke in vm.scratch_strokes.iter().chain(&scratch_preview) {
+        scratch.draw(painter, stroke);
+    }
+}
+
+/// Draws the notes or the digit of the cell at `pos`.
+fn draw_cell_content(
+    painter: &Painter,
+    vm: &GridViewModel,
+    pos: Position,
+    cell_rect: Rect,
+    base_border: f32,
+    scale: &LayoutScale,
+    palette: &GridPalette,
+) {
+    let cell = &vm.grid[pos];
+    let vs = vm.effective_visual_state(cell.visual_state);
+    if let Some(digits) = cell.content.as_notes() {
+        let notes_rect = cell_rect.shrink(base_border * SELECTED_CELL_BORDER_WIDTH_RATIO);
+        draw_notes(
+            painter,
+            vm,
+            notes_rect,
+            scale.note_font_scale,
+            digits,
+            &cell.note_visual_state,
+            palette,
+        );
+    }
+    if !cell.center_notes.is_empty() {
+        draw_center_notes(
+            painter,
+            cell_rect.shrink(base_border * SELECTED_CELL_BORDER_WIDTH_RATIO),
+            scale.note_font_scale,
+            cell.center_notes,
+            palette,
+        );
+    }
+    if let Some(digit) = cell.content.as_digit() {
+        let is_given = cell.content.is_given();
+        let color = vs.text_color(is_given, palette);
+        let given_style = if is_given {
+            vm.style.givens
+        } else {
+            GivenStyle::Plain
+        };
+        if given_style == GivenStyle::Boxed {
+            painter.rect_stroke(
+                cell_rect.shrink(scale.cell_size * GIVEN_BOX_INSET_RATIO),
+                scale.cell_size * GIVEN_BOX_INSET_RATIO,
+                Stroke::new(base_border, color),
+                StrokeKind::Inside,
+            );
+        }
+        if let Some(color) = vs.hint_digit_pill_color(palette) {
+            draw_digit_pill(painter, cell_rect.center(), scale.cell_size, color);
+        }
+        draw_cell_digit(
+            painter,
+            cell_rect.center(),
+            scale.digit_font_size,
+            digit,
+            color,
+            given_style == GivenStyle::Bold,
+        );
+        let digit_rect = cell_rect.shrink(base_border);
+        if let Some(stroke) = vs.cell_underline_stroke(digit_rect, palette) {
+            let offset = digit_rect.height() * 0.15;
+            let y = digit_rect.bottom() - stroke.width;
+            let start = Pos2::new(digit_rect.left() + offset, y);
+            let end = Pos2::new(digit_rect.right() - offset, y);
+            painter.line_segment([start, end], stroke);
+        }
+    }
+}
+
+/// Numbers the rows to the left of the board and the columns above it, in
+/// the gutter [`required_units`] reserves.
+fn draw_coordinates(
+    painter: &Painter,
+    inner_rect: Rect,
+    cell_size: f32,
+    thick_border_width: f32,
+    palette: &GridPalette,
+) {
+    let font = FontId::proportional(cell_size * COORDINATE_GUTTER_RATIO * 0.7);
+    // Centered in the gutter, which starts past the outer border.
+    let offset = thick_border_width + cell_size * COORDINATE_GUTTER_RATIO * 0.5;
+    for index in 0..9 {
+        let label = (index + 1).to_string();
+        let row = cell_rect(
+            inner_rect,
+            cell_size,
+            thick_border_width,
+            Position::new(index, 0),
+        );
+        painter.text(
+            Pos2::new(inner_rect.left() - offset, row.center().y),
+            Align2::CENTER_CENTER,
+            &label,
+            font.clone(),
+            palette.text_note,
+        );
+        let col = cell_rect(
+            inner_rect,
+            cell_size,
+            thick_border_width,
+            Position::new(0, index),
+        );
+        painter.text(
+            Pos2::new(col.center().x, inner_rect.top() - offset),
+            Align2::CENTER_CENTER,
+            label,
+            font.clone(),
+            palette.text_note,
+        );
+    }
+}
+
+/// Makes the cell at `pos` clickable and describes it to assistive technology.
+fn interact_cell(
+    ui: &Ui,
+    vm: &GridViewModel,
+    pos: Position,
+    cell_rect: Rect,
+    action_queue: &mut ActionRequestQueue,
+) {
+    let cell = &vm.grid[pos];
+    let response = ui.interact(
+        cell_rect,
+        ui.id().with((pos.col(), pos.row())),
+        Sense::click(),
+    );
+    request_cell_action(vm, pos, &response, action_queue);
+    response.widget_info(|| {
+        WidgetInfo::selected(
+            WidgetType::Button,
+            true,
+            cell.visual_state.contains(GridVisualState::SELECTED_CELL),
+            &cell.accessible_label,
+        )
+    });
+}
+
+/// Requests the action for a click (or long-press) on the cell at `pos`.
+fn request_cell_action(
+    vm: &GridViewModel,
+    pos: Position,
+    response: &Response,
+    action_queue: &mut ActionRequestQueue,
+) {
+    let swap_input_mode = vm.input_context.swap_input_mode;
+    if let Some(digit) = vm.input_context.armed_digit {
+        // Digit-first: tapping enters the armed digit, long-press in the other mode.
+        if response.clicked() || response.secondary_clicked() {
+            action_queue.request(
+                BoardMutationAction::RequestDigit {
+                    digit: Some(digit),
+                    swap_input_mode: swap_input_mode != response.secondary_clicked(),
+                    position: Some(pos),
                 }
-                draw_cell_digit(
-                    painter,
-                    cell_rect.center(),
-                    cell_size,
-                    digit,
-                    vs.text_color(cell.content.is_given(), palette),
-                );
-                let digit_rect = cell_rect.shrink(base_border);
-                if let Some(stroke) = vs.cell_underline_stroke(digit_rect, palette) {
-                    let offset = digit_rect.height() * 0.15;
-                    let y = digit_rect.bottom() - stroke.width;
-                    let start = Pos2::new(digit_rect.left() + offset, y);
-                    let end = Pos2::new(digit_rect.right() - offset, y);
-                    painter.line_segment([start, end], stroke);
+                .into(),
+            );
+        }
+    } else if response.secondary_clicked() {
+        action_queue.request(
+            BoardMutationAction::RequestDigit {
+                digit: None,
+                swap_input_mode,
+                position: Some(pos),
+            }
+            .into(),
+        );
+    } else if response.double_clicked() {
+        action_queue.request(
+            BoardMutationAction::AdvanceCell {
+                position: Some(pos),
+            }
+            .into(),
+        );
+    } else if response.clicked() && vm.input_context.toggle_selection {
+        action_queue.request(SelectionAction::ToggleCellInSelection(pos).into());
+    } else if response.clicked() {
+        action_queue.request(SelectionAction::SelectOrClearCell(pos).into());
+    }
+}
+
+/// Draws cages, region borders, edge markers, and hint links on top of the cells.
+fn draw_variant_overlays(
+    painter: &Painter,
+    vm: &GridViewModel,
+    inner_rect: Rect,
+    cell_size: f32,
+    thick_border: Stroke,
+    base_border: f32,
+    palette: &GridPalette,
+) {
+    draw_cages(
+        painter,
+        &vm.cages,
+        inner_rect,
+        cell_size,
+        thick_border.width,
+        base_border,
+        palette,
+    );
+    if vm.regions.is_standard() {
+        draw_box_borders(painter, inner_rect, cell_size, thick_border);
+    } else {
+        // Keep the box gaps for a stable layout, but paint them as plain spacing
+        // and draw the irregular region outlines on top.
+        let gap = Stroke::new(thick_border.width, palette.cell_bg_default);
+        draw_box_borders(painter, inner_rect, cell_size, gap);
+        draw_region_borders(painter, &vm.regions, inner_rect, cell_size, thick_border);
+    }
+    draw_edge_markers(
+        painter,
+        &vm.edges,
+        inner_rect,
+        cell_size,
+        thick_border.width,
+        base_border,
+        palette,
+    );
+    draw_links(
+        painter,
+        vm,
+        inner_rect,
+        cell_size,
+        thick_border.width,
+        base_border,
+        palette,
+    );
+}
+
+fn cell_rect(inner_rect: Rect, cell_size: f32, thick_border_width: f32, pos: Position) -> Rect {
+    let col_f = f32::from(pos.col());
+    let row_f = f32::from(pos.row());
+    let cell_min = inner_rect.min
+        + Vec2::new(
+            cell_size * col_f + (col_f / 3.0).floor() * thick_border_width,
+            cell_size * row_f + (row_f / 3.0).floor() * thick_border_width,
+        );
+    Rect::from_min_size(cell_min, Vec2::splat(cell_size))
+}
+
+fn draw_region_borders(
+    painter: &Painter,
+    regions: &RegionLayout,
+    inner_rect: Rect,
+    cell_size: f32,
+    stroke: Stroke,
+) {
+    let half = stroke.width.max(1.0) * 0.5;
+    for pos in Position::ALL {
+        let region = regions.region_index(pos);
+        let rect = cell_rect(inner_rect, cell_size, stroke.width, pos);
+        if let Some(right) = pos.right()
+            && regions.region_index(right) != region
+        {
+            let right_rect = cell_rect(inner_rect, cell_size, stroke.width, right);
+            let x = f32::midpoint(rect.right(), right_rect.left());
+            let edge = Rect::from_min_max(
+                Pos2::new(x - half, rect.top() - half),
+                Pos2::new(x + half, rect.bottom() + half),
+            );
+            painter.rect_filled(edge, 0.0, stroke.color);
+        }
+        if let Some(down) = pos.down()
+            && regions.region_index(down) != region
+        {
+            let down_rect = cell_rect(inner_rect, cell_size, stroke.width, down);
+            let y = f32::midpoint(rect.bottom(), down_rect.top());
+            let edge = Rect::from_min_max(
+                Pos2::new(rect.left() - half, y - half),
+                Pos2::new(rect.right() + half, y + half),
+            );
+            painter.rect_filled(edge, 0.0, stroke.color);
+        }
+    }
+}
+
+fn draw_cages(
+    painter: &Painter,
+    cages: &CageSet,
+    inner_rect: Rect,
+    cell_size: f32,
+    thick_border_width: f32,
+    base_border: f32,
+    palette: &GridPalette,
+) {
+    let inset = cell_size * CAGE_INSET_RATIO;
+    let dash = cell_size * CAGE_DASH_RATIO;
+    let stroke = Stroke::new(base_border, palette.border_cage);
+    let sum_font = FontId::proportional(cell_size * 0.22);
+
+    for cage in cages {
+        let positions = cage.positions();
+        let in_cage = |neighbor: Option<Position>| neighbor.is_some_and(|p| positions.contains(p));
+        for pos in positions {
+            let rect = cell_rect(inner_rect, cell_size, thick_border_width, pos).shrink(inset);
+            let edges = [
+                (pos.up(), [rect.left_top(), rect.right_top()]),
+                (pos.down(), [rect.left_bottom(), rect.right_bottom()]),
+                (pos.left(), [rect.left_top(), rect.left_bottom()]),
+                (pos.right(), [rect.right_top(), rect.right_bottom()]),
+            ];
+            for (neighbor, points) in edges {
+                if !in_cage(neighbor) {
+                    painter.extend(Shape::dashed_line(&points, stroke, dash, dash));
                 }
             }
+        }
 
-            let response = ui.interact(cell_rect, ui.id().with((col, row)), Sense::click());
-            if response.secondary_clicked() {
-                action_queue.request(
-                    BoardMutationAction::RequestDigit {
-                        digit: None,
-                        swap_input_mode: vm.input_context.swap_input_mode,
-                        position: Some(pos),
-                    }
-                    .into(),
-                );
-            } else if response.double_clicked() {
-                action_queue.request(
-                    BoardMutationAction::AdvanceCell {
-                        position: Some(pos),
-                    }
-                    .into(),
+        let anchor = cell_rect(inner_rect, cell_size, thick_border_width, cage.anchor());
+        let label_pos = anchor.min + Vec2::splat(inset * 0.5);
+        let galley = painter.layout_no_wrap(
+            cage.sum().to_string(),
+            sum_font.clone(),
+            palette.text_cage_sum,
+        );
+        let label_rect = Rect::from_min_size(label_pos, galley.size());
+        painter.rect_filled(label_rect, 0.0, palette.cell_bg_default);
+        painter.galley(label_pos, galley, palette.text_cage_sum);
+    }
+}
+
+fn draw_edge_markers(
+    painter: &Painter,
+    edges: &EdgeConstraintSet,
+    inner_rect: Rect,
+    cell_size: f32,
+    thick_border_width: f32,
+    base_border: f32,
+    palette: &GridPalette,
+) {
+    let size = cell_size * EDGE_MARKER_RATIO;
+    let stroke = Stroke::new(base_border * 1.5, palette.edge_marker);
+
+    for edge in edges {
+        let first = cell_rect(inner_rect, cell_size, thick_border_width, edge.first());
+        let second = cell_rect(inner_rect, cell_size, thick_border_width, edge.second());
+        let center = Pos2::new(
+            f32::midpoint(first.center().x, second.center().x),
+            f32::midpoint(first.center().y, second.center().y),
+        );
+        // Unit vector from the first cell towards the second one.
+        let along = if edge.is_vertical() { Vec2::X } else { Vec2::Y };
+        let across = along.rot90();
+        match edge.kind() {
+            EdgeKind::Consecutive => {
+                painter.circle(center, size * 0.5, palette.cell_bg_default, stroke);
+            }
+            EdgeKind::LessThan | EdgeKind::GreaterThan => {
+                // The chevron opens towards the larger digit.
+                let towards_larger = if edge.kind() == EdgeKind::LessThan {
+                    along
+                } else {
+                    -along
+                };
+                let tip = center - towards_larger * size * 0.5;
+                let back = center + towards_larger * size * 0.5;
+                painter.line(
+                    vec![back + across * size, tip, back - across * size],
+                    stroke,
                 );
-            } else if response.clicked() {
-                action_queue.request(SelectionAction::SelectOrClearCell(pos).into());
             }
         }
     }
+}
+
+/// Draws the hint links as curved arrows between candidates: solid for strong
+/// links and dashed for weak ones.
+///
+/// Links bend to one side so that links along a row or column do not run
+/// over the candidates between their ends, and two candidates of one cell
+/// still get a visible arc.
+fn draw_links(
+    painter: &Painter,
+    vm: &GridViewModel,
+    inner_rect: Rect,
+    cell_size: f32,
+    thick_border_width: f32,
+    base_border: f32,
+    palette: &GridPalette,
+) {
+    let stroke = Stroke::new(base_border * 1.5, palette.hint_link);
+    let dash = cell_size * CAGE_DASH_RATIO;
+    let note_radius = cell_size / 9.0;
+    let arrow = cell_size * LINK_ARROW_RATIO;
+    let anchor = |node: ChainNode| {
+        let rect = cell_rect(inner_rect, cell_size, thick_border_width, node.position);
+        if let Some(notes) = vm.grid[node.position].content.as_notes() {
+            let notes_rect = rect.shrink(base_border * SELECTED_CELL_BORDER_WIDTH_RATIO);
+            note_rect(notes_rect, vm.style, notes, node.digit).center()
+        } else {
+            rect.center()
+        }
+    };
+
+    for link in &vm.links {
+        let (from, to) = (anchor(link.from), anchor(link.to));
+        let along = to - from;
+        if along.length() < f32::EPSILON {
+            continue;
+        }
+        let bend = f32::max(along.length() * LINK_BEND_RATIO, cell_size * 0.15);
+        let control = from + along * 0.5 + along.normalized().rot90() * bend;
+        // Stop short of the candidates so their digits stay readable.
+        let start = from + (control - from).normalized() * note_radius;
+        let end = to + (control - to).normalized() * note_radius;
+        let points: Vec<_> = (0..=LINK_CURVE_SEGMENTS)
+            .map(|i| {
+                let t = f32::from(i) / f32::from(LINK_CURVE_SEGMENTS);
+                let u = 1.0 - t;
+                (start.to_vec2() * (u * u)
+                    + control.to_vec2() * (2.0 * u * t)
+                    + end.to_vec2() * (t * t))
+                    .to_pos2()
+            })
+            .collect();
+        match link.kind {
+            LinkKind::Strong => {
+                painter.add(Shape::line(points, stroke));
+            }
+            LinkKind::Weak => {
+                painter.extend(Shape::dashed_line(&points, stroke, dash, dash));
+            }
+        }
+        let direction = (end - control).normalized();
+        let back = end - direction * arrow;
+        let side = direction.rot90() * arrow * 0.5;
+        painter.add(Shape::convex_polygon(
+            vec![end, back + side, back - side],
+            palette.hint_link,
+            Stroke::NONE,
+        ));
+    }
+}
+
+/// Returns the spot of the note for `digit` in a cell's notes area `rect`,
+/// where the cell holds `notes`.
+///
+/// In [`NoteLayout::Linear`], notes fill lines of [`LINEAR_NOTE_COLUMNS`]
+/// from the left, and the lines are centered vertically. With
+/// [`GridStyle::reserve_note_space`], the lines all nine notes would take are
+/// centered instead, so notes do not move as lines are added or removed.
+/// Digits missing from `notes`, such as candidates a hint links to, keep their
+/// mini-grid spot.
+fn note_rect(rect: Rect, style: GridStyle, notes: DigitSet, digit: Digit) -> Rect {
+    let (index, columns, rows) = match style.notes {
+        NoteLayout::Linear if notes.contains(digit) => {
+            let index = notes.into_iter().take_while(|&note| note != digit).count();
+            let shown = if style.reserve_note_space {
+                DigitSet::FULL.len()
+            } else {
+                notes.len()
+            };
+            (
+                index,
+                LINEAR_NOTE_COLUMNS,
+                shown.div_ceil(LINEAR_NOTE_COLUMNS),
+            )
+        }
+        NoteLayout::MiniGrid | NoteLayout::Linear => (usize::from(digit.value() - 1), 3, 3),
+    };
+    #[expect(clippy::cast_precision_loss)]
+    let (x, y, columns, rows) = (
+        (index % columns) as f32,
+        (index / columns) as f32,
+        columns as f32,
+        rows as f32,
+    );
+    let size = Vec2::new(rect.width() / columns, rect.height() / 3.0);
+    let top = rect.center().y - size.y * rows / 2.0;
+    Rect::from_min_size(Pos2::new(rect.left() + x * size.x, top + y * size.y), size)
+}
+
+/// Tracks a drag over the grid and returns its direction once it ends.
+///
+/// Drags shorter than half a cell are ignored.
+fn swipe_direction(ui: &Ui, response: &Response, cell_size: f32) -> Option<MoveDirection> {
+    let id = response.id.with("swipe_delta");
+    if let Some(delta) = response.total_drag_delta() {
+        ui.data_mut(|data| data.insert_temp(id, delta));
+        return None;
+    }
+    if !response.drag_stopped() {
+        return None;
+    }
+    let delta: Vec2 = ui.data_mut(|data| data.remove_temp(id))?;
+    if delta.length() < cell_size * 0.5 {
+        return None;
+    }
+    let direction = if delta.x.abs() > delta.y.abs() {
+        if delta.x > 0.0 {
+            MoveDirection::Right
+        } else {
+            MoveDirection::Left
+        }
+    } else if delta.y > 0.0 {
+        MoveDirection::Down
+    } else {
+        MoveDirection::Up
+    };
+    Some(direction)
+}
+
+/// Handles a drag over the whole grid: a scratchpad stroke while a tool is
+/// selected, swipe navigation otherwise.
+///
+/// Returns the scratchpad stroke in progress, if any.
+fn handle_grid_drag(
+    ui: &Ui,
+    vm: &GridViewModel,
+    response: &Response,
+    scratch: &ScratchGeometry,
+    action_queue: &mut ActionRequestQueue,
+) -> Option<ScratchStroke> {
+    if let Some(tool) = vm.scratch_tool {
+        return track_scratch_stroke(ui, response, tool, scratch, action_queue);
+    }
+    if let Some(direction) = swipe_direction(ui, response, scratch.cell_size) {
+        action_queue.request(SelectionAction::MoveSelection(direction).into());
+    }
+    None
+}
+
+/// Tracks a drag over the grid as a scratchpad stroke drawn with `tool`.
+///
+/// Returns the stroke in progress so it can be previewed; once the drag stops,
+/// the finished stroke is requested to be added to the scratchpad instead.
+fn track_scratch_stroke(
+    ui: &Ui,
+    response: &Response,
+    tool: ScratchTool,
+    scratch: &ScratchGeometry,
+    action_queue: &mut ActionRequestQueue,
+) -> Option<ScratchStroke> {
+    let id = response.id.with("scratch_stroke");
+    if response.drag_stopped() {
+        let stroke = ui.data_mut(|data| {
+            let stroke = data.get_temp::<ScratchStroke>(id);
+            data.remove::<ScratchStroke>(id);
+            stroke
+        });
+        if let Some(stroke) = stroke {
+            action_queue.request(UiAction::AddScratchStroke(stroke).into());
+        }
+        return None;
+    }
+    if !response.dragged() {
+        return None;
+    }
+    let pointer = response.interact_pointer_pos()?;
+    let point = scratch.normalize(pointer);
+    ui.data_mut(|data| {
+        let stroke = match (tool, data.get_temp::<ScratchStroke>(id)) {
+            (ScratchTool::Pen, Some(ScratchStroke::Pen(mut points))) => {
+                if points.last() != Some(&point) {
+                    points.push(point);
+                }
+                ScratchStroke::Pen(points)
+            }
+            (ScratchTool::Pen, _) => ScratchStroke::Pen(vec![point]),
+            (ScratchTool::Line, Some(ScratchStroke::Line { from, .. })) => ScratchStroke::Line {
+                from,
+                to: scratch.nearest_cell(pointer),
+            },
+            (ScratchTool::Line, _) => {
+                let pos = scratch.nearest_cell(pointer);
+                ScratchStroke::Line { from: pos, to: pos }
+            }
+        };
+        data.insert_temp(id, stroke.clone());
+        Some(stroke)
+    })
+}
+
+/// Where scratchpad strokes land on the grid, and how they are painted.
+struct ScratchGeometry {
+    rect: Rect,
+    inner_rect: Rect,
+    cell_size: f32,
+    thick_border_width: f32,
+    stroke: Stroke,
+}
+
+impl ScratchGeometry {
+    fn cell_center(&self, pos: Position) -> Pos2 {
+        cell_rect(
+            self.inner_rect,
+            self.cell_size,
+            self.thick_border_width,
+            pos,
+        )
+        .center()
+    }
+
+    /// Returns the cell whose center is closest to `point`.
+    fn nearest_cell(&self, point: Pos2) -> Position {
+        Position::ALL
+            .into_iter()
+            .min_by(|&a, &b| {
+                let distance = |pos| self.cell_center(pos).distance_sq(point);
+                distance(a).total_cmp(&distance(b))
+            })
+            .unwrap()
+    }
+
+    /// Converts a screen point to grid coordinates, clamped to the grid.
+    fn normalize(&self, point: Pos2) -> Pos2 {
+        ((point - self.rect.min) / self.rect.size())
+            .clamp(Vec2::ZERO, Vec2::splat(1.0))
+            .to_pos2()
+    }
+
+    fn draw(&self, painter: &Painter, stroke: &ScratchStroke) {
+        match stroke {
+            ScratchStroke::Pen(points) => {
+                let points = points
+                    .iter()
+                    .map(|point| self.rect.min + point.to_vec2() * self.rect.size())
+                    .collect();
+                painter.line(points, self.stroke);
+            }
+            ScratchStroke::Line { from, to } => {
+                let (from, to) = (self.cell_center(*from), self.cell_center(*to));
+                painter.line_segment([from, to], self.stroke);
+                for end in [from, to] {
+                    painter.circle_filled(end, self.stroke.width * 1.5, self.stroke.color);
+                }
+            }
+        }
+    }
+}
 
-    draw_box_borders(painter, inner_rect, cell_size, thick_border);
+/// Returns the heatmap tint for a cell; fewer candidates give a stronger tint.
+fn heatmap_color(candidate_count: u8, palette: &GridPalette) -> Color32 {
+    let remaining = 9 - candidate_count.clamp(1, 9);
+    palette
+        .heatmap
+        .gamma_multiply(0.05 + f32::from(remaining) / 8.0 * 0.45)
+}
+
+/// Returns the hint heat map tint for a cell; more hints give a stronger tint.
+fn hint_heat_color(hint_count: u8, palette: &GridPalette) -> Color32 {
+    let hints = hint_count.clamp(1, 4);
+    palette
+        .heatmap
+        .gamma_multiply(0.1 + f32::from(hints) / 4.0 * 0.5)
+}
+
+/// Returns the hardness overlay tint for a cell; deeper trials give a stronger
+/// tint.
+fn deduction_depth_color(depth: u8, palette: &GridPalette) -> Color32 {
+    let depth = depth.clamp(1, 3);
+    palette
+        .heatmap
+        .gamma_multiply(0.1 + f32::from(depth) / 3.0 * 0.5)
 }
 
 fn draw_cell_fill(painter: &Painter, rect: Rect, color: Color32) {
@@ -431,14 +1237,29 @@ fn draw_cell_border(painter: &Painter, rect: Rect, stroke: Stroke) {
     painter.rect_stroke(rect, 0.0, stroke, StrokeKind::Inside);
 }
 
-fn draw_cell_digit(painter: &Painter, center: Pos2, cell_size: f32, digit: Digit, color: Color32) {
-    painter.text(
-        center,
-        Align2::CENTER_CENTER,
-        digit.as_str(),
-        FontId::proportional(cell_size * 0.8),
-        color,
-    );
+/// Draws `digit` centered at `center`.
+///
+/// The bundled fonts have no bold face, so `bold` digits are drawn a few
+/// times with a slight horizontal offset instead.
+fn draw_cell_digit(
+    painter: &Painter,
+    center: Pos2,
+    font_size: f32,
+    digit: Digit,
+    color: Color32,
+    bold: bool,
+) {
+    let offsets: &[f32] = if bold { &[-0.5, 0.0, 0.5] } else { &[0.0] };
+    let weight = f32::max(font_size * 0.04, 0.5);
+    for offset in offsets {
+        painter.text(
+            center + Vec2::new(offset * weight, 0.0),
+            Align2::CENTER_CENTER,
+            digit.as_str(),
+            FontId::proportional(font_size),
+            color,
+        );
+    }
 }
 
 fn draw_outer_border(painter: &Painter, rect: Rect, stroke: Stroke) {
@@ -536,31 +1357,45 @@ fn draw_corners(painter: &Painter, rect: Rect, stroke: Stroke) {
     painter.rect_filled(bottom_right_v, 0.0, stroke.color);
 }
 
+/// Writes the center notes in one line across the middle of the cell,
+/// shrinking the text so that all of them fit.
+fn draw_center_notes(
+    painter: &Painter,
+    rect: Rect,
+    font_scale: f32,
+    digits: DigitSet,
+    palette: &GridPalette,
+) {
+    #[expect(clippy::cast_precision_loss)]
+    let count = digits.len() as f32;
+    let font_size = f32::min(rect.height() / 2.5, rect.width() / (count * 0.6)) * font_scale;
+    let text: String = digits.iter().map(|digit| digit.as_str()).collect();
+    painter.text(
+        rect.center(),
+        Align2::CENTER_CENTER,
+        text,
+        FontId::proportional(font_size),
+        palette.text_note,
+    );
+}
+
 fn draw_notes(
     painter: &Painter,
     vm: &GridViewModel,
     rect: Rect,
+    font_scale: f32,
     digits: DigitSet,
     note_visual_state: &NoteVisualState,
     palette: &GridPalette,
 ) {
-    let note_font = FontId::proportional(rect.height() / 3.0);
+    let note_font = FontId::proportional(rect.height() / 3.0 * font_scale);
 
-    let cell_w = rect.width() / 3.0;
-    let cell_h = rect.height() / 3.0;
-
-    for digit in Digit::ALL {
-        if !digits.contains(digit) {
-            continue;
-        }
-        let idx = digit.value() - 1;
-        let y = f32::from(idx / 3);
-        let x = f32::from(idx % 3);
-
-        let center = rect.min + Vec2::new((x + 0.5) * cell_w, (y + 0.5) * cell_h);
-        let vs = vm.effective_visual_state(note_visual_state.digit_highlight(digit));
-        let text_color = vs.text_color(false, palette);
-        let fill_rect = Rect::from_center_size(center, Vec2::splat(f32::min(cell_w, cell_h)) * 0.9);
+    for digit in digits {
+        let spot = note_rect(rect, vm.style, digits, digit);
+        let (center, side) = (spot.center(), spot.width().min(spot.height()));
+        let vs = vm.effective_note_visual_state(note_visual_state.digit_highlight(digit));
+        let text_color = vs.note_text_color(palette);
+        let fill_rect = Rect::from_center_size(center, Vec2::splat(side) * 0.9);
         if let Some(fill_color) = vs.note_fill_color(palette) {
             painter.rect_filled(fill_rect, 0.0, fill_color);
         }
@@ -571,7 +1406,7 @@ fn draw_notes(
             painter.line_segment([start, end], stroke);
         }
         if let Some(pill_color) = vs.hint_digit_pill_color(palette) {
-            let pill_radius = f32::min(cell_w, cell_h) * 0.8 * 0.5;
+            let pill_radius = side * 0.8 * 0.5;
             painter.circle_filled(center, pill_radius, pill_color);
         }
         painter.text(
@@ -587,5 +1422,11 @@ fn draw_notes(
             let end = Pos2::new(fill_rect.right() - offset, fill_rect.bottom() - offset);
             painter.line_segment([start, end], stroke);
         }
+        if let Some(stroke) = vs.note_cleanup_stroke(fill_rect, palette) {
+            let offset = fill_rect.width() * 0.15;
+            let start = Pos2::new(fill_rect.left() + offset, center.y);
+            let end = Pos2::new(fill_rect.right() - offset, center.y);
+            painter.line_segment([start, end], stroke);
+        }
     }
 }
@@ -0,0 +1,140 @@
+//! Deterministic text dumps of grid view-model cells for snapshot tests.
+//!
+//! A dump starts with a 9x9 map of the cell contents, followed by one line for
+//! every cell that carries a visual flag, a highlighted note, or a candidate
+//! count. Snapshots live in `snapshots/grid/` of this crate; run the tests with
+//! `UPDATE_SNAPSHOTS=1` to write new or changed ones, then review the diff.
+
+use std::{env, fmt::Write as _, fs, path::PathBuf};
+
+use numelace_core::{DigitSet, Position, PositionIndexedArray};
+use numelace_game::CellState;
+
+use crate::ui::grid::{GridCell, GridVisualState, NoteVisualState};
+
+/// Renders the cells as text.
+///
+/// Only the flags in `enabled` are listed, so a dump can show what the grid
+/// actually draws under the player's highlight settings.
+pub(crate) fn dump_grid(grid: &PositionIndexedArray<GridCell>, enabled: GridVisualState) -> String {
+    let mut out = String::new();
+    for row in 0..9 {
+        for col in 0..9 {
+            out.push(content_char(grid[Position::new(row, col)].content));
+        }
+        out.push('\n');
+    }
+
+    for pos in Position::ALL {
+        let cell = &grid[pos];
+        let flags = cell.visual_state & enabled;
+        let notes = note_details(&cell.note_visual_state);
+        if flags.is_empty() && notes.is_empty() && cell.candidate_count.is_none() {
+            continue;
+        }
+        let _ = write!(
+            out,
+            "\nr{}c{} {}",
+            pos.row() + 1,
+            pos.col() + 1,
+            content_label(cell.content)
+        );
+        if !flags.is_empty() {
+            out.push_str(" [");
+            let _ = bitflags::parser::to_writer(&flags, &mut out);
+            out.push(']');
+        }
+        for (name, digits) in notes {
+            let _ = write!(out, " {name}={}", digits_text(digits));
+        }
+        if let Some(count) = cell.candidate_count {
+            let _ = write!(out, " candidates={count}");
+        }
+    }
+    out.push('\n');
+    out
+}
+
+/// Compares `actual` with the stored snapshot `name`.
+///
+/// With `UPDATE_SNAPSHOTS` set, the snapshot is written instead.
+///
+/// # Panics
+///
+/// Panics if the snapshot is missing or differs from `actual`.
+#[track_caller]
+pub(crate) fn assert_grid_snapshot(name: &str, actual: &str) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("snapshots/grid")
+        .join(format!("{name}.snap"));
+    if env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, actual).unwrap();
+        return;
+    }
+    let Ok(expected) = fs::read_to_string(&path) else {
+        panic!(
+            "missing snapshot {}; rerun with UPDATE_SNAPSHOTS=1 to create it",
+            path.display()
+        );
+    };
+    assert_eq!(
+        expected,
+        actual,
+        "snapshot {} changed; rerun with UPDATE_SNAPSHOTS=1 to accept it",
+        path.display()
+    );
+}
+
+fn content_char(content: CellState) -> char {
+    match content {
+        CellState::Given(digit) | CellState::Filled(digit) => char::from(b'0' + digit.value()),
+        CellState::Notes(_) => 'n',
+        CellState::Empty => '.',
+    }
+}
+
+fn content_label(content: CellState) -> String {
+    match content {
+        CellState::Given(digit) => format!("given {digit}"),
+        CellState::Filled(digit) => format!("filled {digit}"),
+        CellState::Notes(notes) => format!("notes {}", digits_text(notes)),
+        CellState::Empty => "empty".to_owned(),
+    }
+}
+
+fn note_details(state: &NoteVisualState) -> Vec<(&'static str, DigitSet)> {
+    let NoteVisualState {
+        selected_digit,
+        conflict,
+        ghost,
+        hint_condition_digit,
+        hint_condition_temporary,
+        hint_application_elimination,
+        hint_application_temporary,
+        note_cleanup,
+    } = state.clone();
+    [
+        ("note_selected_digit", selected_digit),
+        ("note_conflict", conflict),
+        ("note_ghost", ghost),
+        ("note_hint_condition_digit", hint_condition_digit),
+        ("note_hint_condition_temporary", hint_condition_temporary),
+        (
+            "note_hint_application_elimination",
+            hint_application_elimination,
+        ),
+        (
+            "note_hint_application_temporary",
+            hint_application_temporary,
+        ),
+        ("note_cleanup", note_cleanup),
+    ]
+    .into_iter()
+    .filter(|(_, digits)| !digits.is_empty())
+    .collect()
+}
+
+fn digits_text(digits: DigitSet) -> String {
+    digits.into_iter().map(|digit| digit.to_string()).collect()
+}
@@ -1,5 +1,7 @@
 use eframe::egui::{Color32, Visuals};
 
+use crate::state::{HighlightIntensity, ThemeColors, ThemeSettings};
+
 /// Color palette for Sudoku grid rendering.
 ///
 /// This is intentionally independent from `egui::Visuals` so grid-specific
@@ -10,25 +12,42 @@ pub(crate) struct GridPalette {
     pub(crate) cell_bg_default: Color32,
     pub(crate) cell_bg_selected_digit: Color32,
     pub(crate) cell_bg_selected_digit_peer: Color32,
+    pub(crate) cell_bg_selected_digit_candidate: Color32,
+    pub(crate) cell_bg_bivalue: Color32,
 
     pub(crate) note_bg_selected_digit: Color32,
 
     pub(crate) pill_hint: Color32,
 
+    pub(crate) heatmap: Color32,
+
     pub(crate) border_inactive: Color32,
     pub(crate) border_selected_cell: Color32,
     pub(crate) border_selected_cell_peer: Color32,
     pub(crate) border_selected_digit: Color32,
     pub(crate) border_hint_condition: Color32,
+    pub(crate) border_cage: Color32,
+    pub(crate) edge_marker: Color32,
 
     pub(crate) underline_hint_condition: Color32,
     pub(crate) underline_hint_application: Color32,
 
+    pub(crate) hint_link: Color32,
+
+    pub(crate) scratch: Color32,
+
     pub(crate) elimination_stroke: Color32,
 
     pub(crate) text_normal: Color32,
+    pub(crate) text_note: Color32,
+    pub(crate) text_note_cleanup: Color32,
     pub(crate) text_given: Color32,
     pub(crate) text_conflict: Color32,
+    pub(crate) text_cage_sum: Color32,
+
+    pub(crate) key_bg: Color32,
+    pub(crate) key_text: Color32,
+    pub(crate) key_text_conflict: Color32,
 }
 
 impl GridPalette {
@@ -51,6 +70,9 @@ impl GridPalette {
             // widgets.hovered.bg_fill = (220, 220, 220) (light)
             Color32::from_gray(220)
         };
+        let cell_bg_default = visuals.text_edit_bg_color(); // dark=(10, 10, 10) light=(255, 255, 255)
+        let cell_bg_selected_digit_candidate =
+            cell_bg_selected_digit.lerp_to_gamma(cell_bg_default, 0.6);
         let hint_accent = if visuals.dark_mode {
             Color32::from_rgb(255, 165, 0)
         } else {
@@ -68,28 +90,139 @@ impl GridPalette {
         let border_selected_digit = visuals.selection.stroke.color; // dark=(192, 222, 255) light=(0, 83, 125)
 
         Self {
-            cell_bg_default: visuals.text_edit_bg_color(), // dark=(10, 10, 10) light=(255, 255, 255)
+            cell_bg_default,
             cell_bg_selected_digit,
             cell_bg_selected_digit_peer,
+            cell_bg_selected_digit_candidate,
+            cell_bg_bivalue: hint_accent_soft.lerp_to_gamma(cell_bg_default, 0.7),
 
             note_bg_selected_digit: cell_bg_selected_digit,
 
             pill_hint: hint_accent,
 
+            heatmap: hint_accent,
+
             border_inactive,
             border_selected_cell,
             border_selected_cell_peer,
             border_selected_digit,
             border_hint_condition: hint_accent,
+            border_cage: border_inactive,
+            edge_marker: visuals.strong_text_color(), // dark=(255, 255, 255) light=(0, 0, 0)
 
             underline_hint_condition: hint_accent_soft,
             underline_hint_application: hint_accent_soft,
 
+            hint_link: hint_accent,
+
+            scratch: border_selected_digit,
+
             elimination_stroke: visuals.error_fg_color, // dark/light=(255, 0, 0)
 
             text_normal: visuals.text_color(), // dark=(140, 140, 140) light=(80, 80, 80)
+            text_note: visuals.text_color(),   // dark=(140, 140, 140) light=(80, 80, 80)
+            text_note_cleanup: visuals.weak_text_color(),
             text_given: visuals.strong_text_color(), // dark=(255, 255, 255) light=(0, 0, 0)
-            text_conflict: visuals.error_fg_color, // dark/light=(255, 0, 0)
+            text_conflict: visuals.error_fg_color,   // dark/light=(255, 0, 0)
+            text_cage_sum: visuals.strong_text_color(), // dark=(255, 255, 255) light=(0, 0, 0)
+
+            key_bg: visuals.widgets.inactive.weak_bg_fill, // dark=(60, 60, 60) light=(220, 220, 220)
+            key_text: visuals.text_color(), // dark=(140, 140, 140) light=(80, 80, 80)
+            key_text_conflict: visuals.warn_fg_color, // dark=(255, 143, 0) light=(255, 100, 0)
+        }
+    }
+
+    /// Build the palette from user-selected theme colors.
+    ///
+    /// Secondary colors (borders of selected digits, hint underlines, ...) are
+    /// derived from the primary ones so a theme only has to pick a handful of colors.
+    #[must_use]
+    pub(crate) fn from_colors(colors: &ThemeColors) -> Self {
+        let rgb = |[r, g, b]: [u8; 3]| Color32::from_rgb(r, g, b);
+        let background = rgb(colors.background);
+        let border = rgb(colors.border);
+        let given = rgb(colors.given);
+        let selected_cell = rgb(colors.selected_cell);
+        let selected_digit = rgb(colors.selected_digit);
+        let conflict = rgb(colors.conflict);
+        let hint = rgb(colors.hint);
+        let hint_soft = hint.lerp_to_gamma(background, 0.4);
+
+        Self {
+            cell_bg_default: background,
+            cell_bg_selected_digit: selected_digit,
+            cell_bg_selected_digit_peer: rgb(colors.house),
+            cell_bg_selected_digit_candidate: selected_digit.lerp_to_gamma(background, 0.6),
+            cell_bg_bivalue: hint_soft.lerp_to_gamma(background, 0.6),
+
+            note_bg_selected_digit: selected_digit,
+
+            pill_hint: hint,
+
+            heatmap: hint,
+
+            border_inactive: border,
+            border_selected_cell: selected_cell,
+            border_selected_cell_peer: selected_cell,
+            border_selected_digit: given,
+            border_hint_condition: hint,
+            border_cage: border,
+            edge_marker: given,
+
+            underline_hint_condition: hint_soft,
+            underline_hint_application: hint_soft,
+
+            hint_link: hint,
+
+            scratch: given.lerp_to_gamma(selected_digit, 0.5),
+
+            elimination_stroke: conflict,
+
+            text_normal: rgb(colors.filled),
+            text_note: rgb(colors.note),
+            text_note_cleanup: rgb(colors.note).lerp_to_gamma(background, 0.5),
+            text_given: given,
+            text_conflict: conflict,
+            text_cage_sum: given,
+
+            key_bg: background,
+            key_text: given,
+            key_text_conflict: conflict,
+        }
+    }
+
+    /// Returns the palette with each kind of highlight faded by `intensity`.
+    ///
+    /// Highlights fade towards the cell background, and conflicts towards the
+    /// normal digit color so that they stay readable.
+    #[must_use]
+    pub(crate) fn with_intensity(&self, intensity: &HighlightIntensity) -> Self {
+        let fade = |color: Color32, amount: f32| self.cell_bg_default.lerp_to_gamma(color, amount);
+        Self {
+            border_selected_cell: fade(self.border_selected_cell, intensity.selection),
+            cell_bg_selected_digit_peer: fade(self.cell_bg_selected_digit_peer, intensity.house),
+            border_selected_cell_peer: fade(self.border_selected_cell_peer, intensity.house),
+            cell_bg_selected_digit: fade(self.cell_bg_selected_digit, intensity.same_digit),
+            border_selected_digit: fade(self.border_selected_digit, intensity.same_digit),
+            note_bg_selected_digit: fade(self.note_bg_selected_digit, intensity.notes),
+            cell_bg_selected_digit_candidate: fade(
+                self.cell_bg_selected_digit_candidate,
+                intensity.notes,
+            ),
+            text_conflict: self
+                .text_normal
+                .lerp_to_gamma(self.text_conflict, intensity.conflict),
+            ..self.clone()
+        }
+    }
+
+    /// Build the palette for the theme settings, falling back to the current
+    /// visuals for [`ThemePreset::System`](crate::state::ThemePreset::System).
+    #[must_use]
+    pub(crate) fn from_settings(theme: &ThemeSettings, visuals: &Visuals) -> Self {
+        match theme.colors() {
+            Some(colors) => Self::from_colors(colors),
+            None => Self::from_visuals(visuals),
         }
     }
 }
@@ -102,12 +235,11 @@ pub(crate) struct GridTheme {
 }
 
 impl GridTheme {
-    /// Create a theme using the current visuals for both palettes.
-    ///
-    /// This preserves existing colors today while allowing later divergence.
+    /// Create a theme from the theme settings, using the same palette for
+    /// both light and dark visuals.
     #[must_use]
-    pub(crate) fn from_visuals(visuals: &Visuals) -> Self {
-        let palette = GridPalette::from_visuals(visuals);
+    pub(crate) fn from_settings(theme: &ThemeSettings, visuals: &Visuals) -> Self {
+        let palette = GridPalette::from_settings(theme, visuals);
         Self {
             light: palette.clone(),
             dark: palette,
@@ -0,0 +1,136 @@
+use std::time::Duration;
+
+use eframe::egui::{Grid, Id, Label, Panel, ProgressBar, RichText, Sense, Ui};
+use numelace_core::Position;
+
+use crate::{
+    action::{ActionRequestQueue, UiAction},
+    i18n::{self, Text, tr},
+    state::{ClipboardContent, PuzzleDifficulty},
+    ui::{icon, status_line},
+};
+
+/// Default width of the sidebar, in points.
+const PANEL_WIDTH: f32 = 200.0;
+/// Number of seed characters shown; the full seed is shown on hover.
+const SEED_PREFIX_LEN: usize = 12;
+/// Shown for facts the puzzle does not have, such as the seed of an
+/// imported puzzle.
+const NOT_AVAILABLE: &str = "—";
+
+/// Facts about the puzzle on the board, shown in a sidebar next to it.
+#[derive(Debug, Clone)]
+pub(crate) struct InfoPanelViewModel {
+    pub(crate) difficulty: Option<PuzzleDifficulty>,
+    pub(crate) clues: usize,
+    /// Generator seed as hexadecimal, for generated puzzles.
+    pub(crate) seed: Option<String>,
+    pub(crate) placed: usize,
+    pub(crate) elapsed: Duration,
+    /// Whether the elapsed time is still counting, so the panel redraws.
+    pub(crate) clock_running: bool,
+    pub(crate) hints_used: usize,
+}
+
+impl InfoPanelViewModel {
+    #[must_use]
+    pub(crate) fn new(
+        difficulty: Option<PuzzleDifficulty>,
+        clues: usize,
+        seed: Option<String>,
+        placed: usize,
+    ) -> Self {
+        Self {
+            difficulty,
+            clues,
+            seed,
+            placed,
+            elapsed: Duration::ZERO,
+            clock_running: false,
+            hints_used: 0,
+        }
+    }
+
+    #[must_use]
+    pub(crate) fn with_elapsed(mut self, elapsed: Duration, clock_running: bool) -> Self {
+        self.elapsed = elapsed;
+        self.clock_running = clock_running;
+        self
+    }
+
+    #[must_use]
+    pub(crate) fn with_hints_used(mut self, hints_used: usize) -> Self {
+        self.hints_used = hints_used;
+        self
+    }
+
+    /// Returns the share of cells holding a digit, from 0.0 to 1.0.
+    #[must_use]
+    pub(crate) fn progress(&self) -> f32 {
+        #[expect(clippy::cast_precision_loss)]
+        let fraction = self.placed as f32 / Position::ALL.len() as f32;
+        fraction
+    }
+}
+
+pub(crate) fn show(ui: &mut Ui, vm: &InfoPanelViewModel, action_queue: &mut ActionRequestQueue) {
+    Panel::right(Id::new("info_panel"))
+        .resizable(false)
+        .default_size(PANEL_WIDTH)
+        .show(ui, |ui| {
+            ui.heading(tr(Text::GameInfo));
+            ui.separator();
+            Grid::new("info_panel_grid")
+                .num_columns(2)
+                .striped(true)
+                .show(ui, |ui| {
+                    show_rows(ui, vm, action_queue);
+                });
+            ui.add_space(ui.spacing().item_spacing.y * 2.0);
+            ui.label(tr(Text::InfoPlacedDigits));
+            let total = Position::ALL.len();
+            ui.add(ProgressBar::new(vm.progress()).text(format!("{}/{total}", vm.placed)));
+        });
+    if vm.clock_running {
+        ui.ctx().request_repaint_after(Duration::from_secs(1));
+    }
+}
+
+fn show_rows(ui: &mut Ui, vm: &InfoPanelViewModel, action_queue: &mut ActionRequestQueue) {
+    ui.label(tr(Text::Difficulty));
+    ui.label(vm.difficulty.map_or(NOT_AVAILABLE, i18n::puzzle_difficulty));
+    ui.end_row();
+
+    ui.label(tr(Text::InfoClues));
+    ui.label(vm.clues.to_string());
+    ui.end_row();
+
+    ui.label(tr(Text::InfoSeed));
+    match &vm.seed {
+        Some(seed) => {
+            let prefix = seed.get(..SEED_PREFIX_LEN).unwrap_or(seed);
+            let response = ui
+                .add(
+                    Label::new(RichText::new(format!("{prefix}…")).monospace())
+                        .sense(Sense::click()),
+                )
+                .on_hover_text(format!("{seed}\n{} {}", icon::COPY, tr(Text::InfoSeedCopy)));
+            if response.clicked() {
+                let content = ClipboardContent::Text(seed.clone());
+                action_queue.request(UiAction::CopyToClipboard(content).into());
+            }
+        }
+        None => {
+            ui.label(NOT_AVAILABLE);
+        }
+    }
+    ui.end_row();
+
+    ui.label(tr(Text::InfoElapsed));
+    ui.label(status_line::clock_text(vm.elapsed));
+    ui.end_row();
+
+    ui.label(tr(Text::InfoHintsUsed));
+    ui.label(vm.hints_used.to_string());
+    ui.end_row();
+}
@@ -3,87 +3,77 @@ use numelace_core::Digit;
 
 use crate::{
     action::{
-        Action, ActionRequestQueue, AppAction, BoardMutationAction, FlowAction, HistoryAction,
-        InputModeAction, ModalRequest, MoveDirection, NotesFillScope, SelectionAction, UiAction,
+        Action, ActionRequestQueue, AppAction, BoardMutationAction, MoveDirection, NoteKind,
+        SelectionAction,
     },
-    state::InputMode,
+    state::{InputMode, InputOrder, KeyBindings, KeyTrigger},
+    ui::command::COMMANDS,
 };
 
 #[derive(Debug, Clone)]
 pub(crate) struct InputContext {
     pub(crate) allow_input: bool,
     pub(crate) swap_input_mode: bool,
+    /// Whether clicking a cell adds it to the selection or removes it.
+    pub(crate) toggle_selection: bool,
     pub(crate) base_input_mode: InputMode,
     pub(crate) effective_input_mode: InputMode,
+    pub(crate) input_order: InputOrder,
+    pub(crate) armed_digit: Option<Digit>,
+    pub(crate) key_bindings: KeyBindings,
 }
 
 pub(crate) fn build_input_context(
     i: &InputState,
     allow_input: bool,
     base_input_mode: InputMode,
+    input_order: InputOrder,
+    armed_digit: Option<Digit>,
+    key_bindings: KeyBindings,
 ) -> InputContext {
     let swap_input_mode = allow_input && i.modifiers.command;
     let effective_input_mode = base_input_mode.swapped(swap_input_mode);
     InputContext {
         allow_input,
         swap_input_mode,
+        toggle_selection: allow_input && i.modifiers.command,
         base_input_mode,
         effective_input_mode,
+        input_order,
+        armed_digit,
+        key_bindings,
     }
 }
 
-struct Trigger {
-    key: Key,
-    command: bool,
-    shift: bool,
-}
-
-impl Trigger {
-    const fn new(key: Key, command: bool, shift: bool) -> Self {
-        Self {
-            key,
-            command,
-            shift,
-        }
-    }
-}
-
+/// A key binding that is not a command, such as digit entry and moving the
+/// selection. Commands bring their own shortcuts from [`COMMANDS`], and the
+/// note chords come from the [`KeyBindings`]; both take precedence.
 struct Shortcut {
-    trigger: Trigger,
+    trigger: KeyTrigger,
     action: Action,
 }
 
 impl Shortcut {
-    const fn new(trigger: Trigger, action: Action) -> Self {
+    const fn new(trigger: KeyTrigger, action: Action) -> Self {
         Self { trigger, action }
     }
 
     const fn command(key: Key, action: Action) -> Self {
-        Self::new(Trigger::new(key, true, false), action)
-    }
-
-    const fn command_shift(key: Key, action: Action) -> Self {
-        Self::new(Trigger::new(key, true, true), action)
-    }
-
-    const fn shift(key: Key, action: Action) -> Self {
-        Self::new(Trigger::new(key, false, true), action)
+        Self::new(KeyTrigger::command(key), action)
     }
 
     const fn plain(key: Key, action: Action) -> Self {
-        Self::new(Trigger::new(key, false, false), action)
+        Self::new(KeyTrigger::plain(key), action)
     }
 
     const fn digit(key: Key, digit: Digit, command: bool) -> Self {
         Self::new(
-            Trigger::new(key, command, false),
-            Action::App(AppAction::BoardMutation(
-                BoardMutationAction::RequestDigit {
-                    digit: Some(digit),
-                    swap_input_mode: command,
-                    position: None,
-                },
-            )),
+            KeyTrigger::new(key, command, false),
+            board_mutation_action(BoardMutationAction::RequestDigit {
+                digit: Some(digit),
+                swap_input_mode: command,
+                position: None,
+            }),
         )
     }
 }
@@ -92,59 +82,53 @@ const fn board_mutation_action(action: BoardMutationAction) -> Action {
     Action::App(AppAction::BoardMutation(action))
 }
 
-const fn history_action(action: HistoryAction) -> Action {
-    Action::App(AppAction::History(action))
-}
-
-const fn selection_action(action: SelectionAction) -> Action {
-    Action::App(AppAction::Selection(action))
-}
-
 const fn move_selection_action(direction: MoveDirection) -> Action {
-    selection_action(SelectionAction::MoveSelection(direction))
+    Action::App(AppAction::Selection(SelectionAction::MoveSelection(
+        direction,
+    )))
 }
 
-const fn input_mode_action(action: InputModeAction) -> Action {
-    Action::App(AppAction::InputMode(action))
+const fn extend_selection_action(direction: MoveDirection) -> Action {
+    Action::App(AppAction::Selection(SelectionAction::ExtendSelection(
+        direction,
+    )))
 }
 
-const SHORTCUTS: [Shortcut; 38] = [
-    Shortcut::command(Key::N, Action::Flow(FlowAction::StartNewGame)),
-    Shortcut::command(
-        Key::Comma,
-        Action::Ui(UiAction::OpenModal(ModalRequest::Settings)),
-    ),
-    Shortcut::command_shift(Key::Backspace, Action::Flow(FlowAction::ResetInputs)),
-    Shortcut::command(Key::K, Action::Flow(FlowAction::CheckSolvability)),
-    Shortcut::command(Key::J, Action::Flow(FlowAction::Hint)),
-    Shortcut::command(Key::Z, history_action(HistoryAction::Undo)),
-    Shortcut::command(Key::Y, history_action(HistoryAction::Redo)),
+/// Keys of the digits, in [`Digit::ALL`] order.
+const DIGIT_KEYS: [Key; 9] = [
+    Key::Num1,
+    Key::Num2,
+    Key::Num3,
+    Key::Num4,
+    Key::Num5,
+    Key::Num6,
+    Key::Num7,
+    Key::Num8,
+    Key::Num9,
+];
+
+const SHORTCUTS: [Shortcut; 30] = [
     Shortcut::plain(Key::ArrowUp, move_selection_action(MoveDirection::Up)),
     Shortcut::plain(Key::ArrowDown, move_selection_action(MoveDirection::Down)),
     Shortcut::plain(Key::ArrowLeft, move_selection_action(MoveDirection::Left)),
     Shortcut::plain(Key::ArrowRight, move_selection_action(MoveDirection::Right)),
-    Shortcut::plain(Key::Escape, Action::App(AppAction::CancelContextual)),
-    Shortcut::plain(Key::S, input_mode_action(InputModeAction::ToggleInputMode)),
-    Shortcut::plain(
-        Key::A,
-        board_mutation_action(BoardMutationAction::AutoFillNotes {
-            scope: NotesFillScope::SelectedCell,
-        }),
+    Shortcut::new(
+        KeyTrigger::shift(Key::ArrowUp),
+        extend_selection_action(MoveDirection::Up),
     ),
-    Shortcut::shift(
-        Key::A,
-        board_mutation_action(BoardMutationAction::AutoFillNotes {
-            scope: NotesFillScope::AllCells,
-        }),
+    Shortcut::new(
+        KeyTrigger::shift(Key::ArrowDown),
+        extend_selection_action(MoveDirection::Down),
     ),
-    Shortcut::plain(
-        Key::Delete,
-        board_mutation_action(BoardMutationAction::ClearCell),
+    Shortcut::new(
+        KeyTrigger::shift(Key::ArrowLeft),
+        extend_selection_action(MoveDirection::Left),
     ),
-    Shortcut::plain(
-        Key::Backspace,
-        board_mutation_action(BoardMutationAction::ClearCell),
+    Shortcut::new(
+        KeyTrigger::shift(Key::ArrowRight),
+        extend_selection_action(MoveDirection::Right),
     ),
+    Shortcut::plain(Key::Escape, Action::App(AppAction::CancelContextual)),
     Shortcut::digit(Key::Num1, Digit::D1, true),
     Shortcut::digit(Key::Num1, Digit::D1, false),
     Shortcut::digit(Key::Num2, Digit::D2, true),
@@ -185,6 +169,18 @@ const SHORTCUTS: [Shortcut; 38] = [
     ),
 ];
 
+/// Returns whether `trigger` is kept for the bindings of [`SHORTCUTS`] and the
+/// note chords, so commands cannot be bound to it.
+///
+/// Digit, arrow, Escape, Space and Enter keys are kept with any modifiers.
+#[must_use]
+pub(crate) fn is_reserved(trigger: KeyTrigger) -> bool {
+    DIGIT_KEYS.contains(&trigger.key)
+        || SHORTCUTS
+            .iter()
+            .any(|shortcut| shortcut.trigger.key == trigger.key)
+}
+
 pub(crate) fn handle_input(
     i: &InputState,
     context: &InputContext,
@@ -194,13 +190,30 @@ pub(crate) fn handle_input(
         return;
     }
 
-    // `i.modifiers.command` is true when Ctrl (Windows/Linux) or Cmd (Mac) is pressed
+    let bindings = &context.key_bindings;
+    for command in &COMMANDS {
+        if command
+            .shortcuts(bindings)
+            .iter()
+            .any(|trigger| trigger.pressed(i))
+        {
+            action_queue.request(command.id.action());
+            return;
+        }
+    }
+    for (digit, key) in Digit::ALL.into_iter().zip(DIGIT_KEYS) {
+        for kind in [NoteKind::Corner, NoteKind::Center] {
+            if bindings.note_chord(kind).trigger(key).pressed(i) {
+                action_queue.request(board_mutation_action(BoardMutationAction::ToggleNote {
+                    digit,
+                    kind,
+                }));
+                return;
+            }
+        }
+    }
     for shortcut in SHORTCUTS {
-        let triggered = i.key_pressed(shortcut.trigger.key)
-            && i.modifiers.command == shortcut.trigger.command
-            && i.modifiers.shift == shortcut.trigger.shift;
-
-        if triggered {
+        if shortcut.trigger.pressed(i) {
             action_queue.request(shortcut.action);
             return;
         }
@@ -1,15 +1,16 @@
-use std::sync::Arc;
-
 use eframe::egui::{
-    self, Align2, Button, Color32, FontId, Grid, RichText, Ui, UiBuilder, Vec2, Visuals,
+    self, Align2, Button, Color32, FontId, Grid, Response, RichText, Ui, UiBuilder, Vec2,
 };
 use numelace_core::{Digit, DigitIndexedArray};
 use numelace_game::{InputBlockReason, InputOperation};
 
 use crate::{
-    action::{ActionRequestQueue, BoardMutationAction, InputModeAction, NotesFillScope},
-    state::InputMode,
+    action::{ActionRequestQueue, BoardMutationAction, SelectionAction},
+    i18n::{Text, tr, tr_args},
+    state::{CommandId, InputMode, ThemeSettings},
     ui::{
+        command::CommandContext,
+        grid_theme::GridPalette,
         icon,
         input::InputContext,
         layout::{ComponentUnits, LayoutScale},
@@ -19,9 +20,9 @@ use crate::{
 #[derive(Debug, Clone)]
 pub(crate) struct KeypadViewModel<'a> {
     digit_states: DigitIndexedArray<DigitKeyState>,
-    has_removable_input: bool,
+    commands: CommandContext,
     input_context: &'a InputContext,
-    auto_fill_capability: Option<Result<InputOperation, InputBlockReason>>,
+    theme: ThemeSettings,
 }
 
 #[derive(Debug, Clone)]
@@ -29,6 +30,9 @@ pub(crate) struct DigitKeyState {
     set_digit: Option<Result<InputOperation, InputBlockReason>>,
     toggle_note: Option<Result<InputOperation, InputBlockReason>>,
     decided_count: usize,
+    armed: Option<bool>,
+    candidate_count: Option<usize>,
+    locked: bool,
 }
 
 impl DigitKeyState {
@@ -42,32 +46,54 @@ impl DigitKeyState {
             set_digit: digit,
             toggle_note: note,
             decided_count,
+            armed: None,
+            candidate_count: None,
+            locked: false,
         }
     }
+
+    /// Marks the key as an arming key for digit-first input.
+    #[must_use]
+    pub(crate) fn with_armed(mut self, armed: bool) -> Self {
+        self.armed = Some(armed);
+        self
+    }
+
+    /// Shows how many cells without a digit can still take this digit.
+    #[must_use]
+    pub(crate) fn with_candidate_count(mut self, count: usize) -> Self {
+        self.candidate_count = Some(count);
+        self
+    }
+
+    /// Disables the key because all nine of the digit are placed correctly.
+    #[must_use]
+    pub(crate) fn with_locked(mut self, locked: bool) -> Self {
+        self.locked = locked;
+        self
+    }
 }
 
 impl<'a> KeypadViewModel<'a> {
     #[must_use]
     pub(crate) fn new(
         digit_states: DigitIndexedArray<DigitKeyState>,
-        has_removable_input: bool,
+        commands: CommandContext,
         input_context: &'a InputContext,
-        auto_fill_capability: Option<Result<InputOperation, InputBlockReason>>,
+        theme: ThemeSettings,
     ) -> Self {
         Self {
             digit_states,
-            has_removable_input,
+            commands,
             input_context,
-            auto_fill_capability,
+            theme,
         }
     }
 }
 
 enum ButtonType {
     Digit(Digit),
-    ClearCell,
-    AutoFillNotes,
-    ToggleInputMode,
+    Command(CommandId),
 }
 
 const BUTTON_LAYOUT: [[Option<ButtonType>; 6]; 2] = {
@@ -76,23 +102,29 @@ const BUTTON_LAYOUT: [[Option<ButtonType>; 6]; 2] = {
         Some(ButtonType::Digit(d))
     }
     #[expect(clippy::unnecessary_wraps)]
-    const fn c() -> Option<ButtonType> {
-        Some(ButtonType::ClearCell)
-    }
-    #[expect(clippy::unnecessary_wraps)]
-    const fn a() -> Option<ButtonType> {
-        Some(ButtonType::AutoFillNotes)
-    }
-    #[expect(clippy::unnecessary_wraps)]
-    const fn t() -> Option<ButtonType> {
-        Some(ButtonType::ToggleInputMode)
+    const fn c(id: CommandId) -> Option<ButtonType> {
+        Some(ButtonType::Command(id))
     }
 
     #[allow(clippy::enum_glob_use)]
     use Digit::*;
     [
-        [d(D1), d(D2), d(D3), d(D4), d(D5), t()],
-        [d(D6), d(D7), d(D8), d(D9), c(), a()],
+        [
+            d(D1),
+            d(D2),
+            d(D3),
+            d(D4),
+            d(D5),
+            c(CommandId::ToggleInputMode),
+        ],
+        [
+            d(D6),
+            d(D7),
+            d(D8),
+            d(D9),
+            c(CommandId::ClearCell),
+            c(CommandId::AutoFillSelectedCell),
+        ],
     ]
 };
 
@@ -118,14 +150,13 @@ pub(crate) fn show(
     scale: &LayoutScale,
     action_queue: &mut ActionRequestQueue,
 ) {
-    let style = Arc::clone(ui.style());
-    let visuals = &style.visuals;
+    let palette = GridPalette::from_settings(&vm.theme, ui.visuals());
 
     let padding = Vec2::new(0.0, scale.padding.y);
     let avail = ui.available_size() - padding;
     let x_buttons = BUTTON_LAYOUT[0].len() as f32;
     let y_buttons = BUTTON_LAYOUT.len() as f32;
-    let button_size = scale.cell_size;
+    let button_size = scale.key_size;
     let x_spacing = if x_buttons > 1.0 {
         ((avail.x - button_size * x_buttons) / (x_buttons - 1.0)).max(0.0)
     } else {
@@ -153,14 +184,30 @@ pub(crate) fn show(
                     for button_type in row {
                         match button_type {
                             Some(ButtonType::Digit(digit)) => {
-                                if show_digit_button(
+                                let response = show_digit_button(
                                     ui,
                                     digit,
                                     button_size,
+                                    scale.key_font_size,
                                     effective_input_mode,
                                     &vm.digit_states[digit],
-                                    visuals,
-                                ) {
+                                    &palette,
+                                );
+                                if vm.input_context.input_order.is_digit_first() {
+                                    if response.clicked() || response.secondary_clicked() {
+                                        action_queue.request(
+                                            SelectionAction::ToggleArmedDigit(digit).into(),
+                                        );
+                                    }
+                                    continue;
+                                }
+                                // Long-press (or right-click) enters the digit in the other mode.
+                                let swap_input_mode = if response.secondary_clicked() {
+                                    Some(!swap_input_mode)
+                                } else {
+                                    response.clicked().then_some(swap_input_mode)
+                                };
+                                if let Some(swap_input_mode) = swap_input_mode {
                                     action_queue.request(
                                         BoardMutationAction::RequestDigit {
                                             digit: Some(digit),
@@ -171,29 +218,8 @@ pub(crate) fn show(
                                     );
                                 }
                             }
-                            Some(ButtonType::ClearCell) => {
-                                if show_clear_button(ui, button_size, vm.has_removable_input) {
-                                    action_queue.request(BoardMutationAction::ClearCell.into());
-                                }
-                            }
-                            Some(ButtonType::AutoFillNotes) => {
-                                if show_auto_fill_button(ui, button_size, vm.auto_fill_capability) {
-                                    action_queue.request(
-                                        BoardMutationAction::AutoFillNotes {
-                                            scope: NotesFillScope::SelectedCell,
-                                        }
-                                        .into(),
-                                    );
-                                }
-                            }
-                            Some(ButtonType::ToggleInputMode) => {
-                                if show_toggle_input_mode_button(
-                                    ui,
-                                    button_size,
-                                    vm.input_context.base_input_mode,
-                                ) {
-                                    action_queue.request(InputModeAction::ToggleInputMode.into());
-                                }
+                            Some(ButtonType::Command(id)) => {
+                                show_command_button(ui, vm, id, button_size, action_queue);
                             }
                             None => {
                                 ui.allocate_space(Vec2::splat(button_size));
@@ -209,6 +235,8 @@ pub(crate) fn show(
 struct DigitButtonProps {
     effective_input_mode: InputMode,
     capability: Option<Result<InputOperation, InputBlockReason>>,
+    armed: Option<bool>,
+    locked: bool,
     digit: Digit,
 }
 
@@ -221,63 +249,66 @@ impl DigitButtonProps {
         Self {
             effective_input_mode,
             capability,
+            armed: state.armed,
+            locked: state.locked,
             digit,
         }
     }
 
     fn tooltip(&self) -> String {
-        let d = self.digit;
-        match self.effective_input_mode {
-            InputMode::Fill => match self.capability {
-                Some(Ok(InputOperation::Set)) => format!("Set digit {d}"),
-                Some(Ok(InputOperation::Removed)) => {
-                    format!("Set digit {d} (unexpected state)")
-                }
-                Some(Ok(InputOperation::NoOp)) => {
-                    format!("Set digit {d} (already set)")
-                }
-                Some(Err(InputBlockReason::Conflict)) => {
-                    format!("Set digit {d} (blocked by rule violation)")
-                }
-                Some(Err(InputBlockReason::GivenCell)) => {
-                    format!("Set digit {d} (blocked by pre-filled cell)")
-                }
-                Some(Err(InputBlockReason::FilledCell)) => {
-                    format!("Set digit {d} (blocked by unexpected state)")
-                }
-                None => format!("Set digit {d} (blocked by no cell selected)"),
+        if self.locked {
+            return tr_args(Text::KeyDigitCompleted, &[("digit", &self.digit)]);
+        }
+        let text = match (self.armed, self.effective_input_mode) {
+            (Some(true), _) => Text::KeyDisarm,
+            (Some(false), _) => Text::KeyArm,
+            (None, InputMode::Fill) => match self.capability {
+                Some(Ok(InputOperation::Set)) => Text::KeySetDigit,
+                Some(Ok(InputOperation::Removed)) => Text::KeySetDigitUnexpected,
+                Some(Ok(InputOperation::NoOp)) => Text::KeySetDigitAlreadySet,
+                Some(Err(InputBlockReason::Conflict)) => Text::KeySetDigitBlockedByConflict,
+                Some(Err(InputBlockReason::WrongDigit)) => Text::KeySetDigitBlockedByWrongDigit,
+                Some(Err(InputBlockReason::GivenCell)) => Text::KeySetDigitBlockedByGiven,
+                Some(Err(InputBlockReason::FilledCell)) => Text::KeySetDigitBlockedByUnexpected,
+                None => Text::KeySetDigitNoCell,
             },
-            InputMode::Notes => match self.capability {
-                Some(Ok(InputOperation::Set)) => format!("Add note {d}"),
-                Some(Ok(InputOperation::Removed)) => format!("Remove note {d}"),
-                Some(Ok(InputOperation::NoOp)) => {
-                    format!("Toggle note {d} (blocked by unexpected state)")
-                }
-                Some(Err(InputBlockReason::Conflict)) => {
-                    format!("Add note {d} (blocked by rule violation)")
+            (None, InputMode::Notes) => match self.capability {
+                Some(Ok(InputOperation::Set)) => Text::KeyAddNote,
+                Some(Ok(InputOperation::Removed)) => Text::KeyRemoveNote,
+                Some(Ok(InputOperation::NoOp)) => Text::KeyToggleNoteUnexpected,
+                Some(Err(InputBlockReason::Conflict | InputBlockReason::WrongDigit)) => {
+                    Text::KeyAddNoteBlockedByConflict
                 }
                 Some(Err(InputBlockReason::GivenCell | InputBlockReason::FilledCell)) => {
-                    format!("Add note {d} (blocked by filled cell)")
-                }
-                None => {
-                    format!("Toggle note {d} (blocked by no cell selected)")
+                    Text::KeyAddNoteBlockedByFilled
                 }
+                None => Text::KeyToggleNoteNoCell,
             },
-        }
+        };
+        tr_args(text, &[("digit", &self.digit)])
     }
 
-    fn text_color(&self, visuals: &Visuals) -> Color32 {
+    fn text_color(&self, palette: &GridPalette) -> Color32 {
         match self.capability {
-            Some(Err(InputBlockReason::Conflict)) => visuals.warn_fg_color,
+            Some(Err(InputBlockReason::Conflict | InputBlockReason::WrongDigit)) => {
+                palette.key_text_conflict
+            }
             Some(Ok(_) | Err(InputBlockReason::GivenCell | InputBlockReason::FilledCell))
-            | None => visuals.text_color(),
+            | None => palette.key_text,
         }
     }
 
     fn enabled(&self) -> bool {
+        if self.locked {
+            return false;
+        }
+        if self.armed.is_some() {
+            return true;
+        }
         match self.capability {
             Some(
-                Ok(InputOperation::Set | InputOperation::Removed) | Err(InputBlockReason::Conflict),
+                Ok(InputOperation::Set | InputOperation::Removed)
+                | Err(InputBlockReason::Conflict | InputBlockReason::WrongDigit),
             ) => true,
             Some(
                 Ok(InputOperation::NoOp)
@@ -299,6 +330,7 @@ impl DigitButtonProps {
                 Ok(InputOperation::NoOp)
                 | Err(
                     InputBlockReason::Conflict
+                    | InputBlockReason::WrongDigit
                     | InputBlockReason::GivenCell
                     | InputBlockReason::FilledCell,
                 ),
@@ -312,25 +344,53 @@ fn show_digit_button(
     ui: &mut Ui,
     digit: Digit,
     button_size: f32,
+    font_size: f32,
     effective_input_mode: InputMode,
     state: &DigitKeyState,
-    visuals: &Visuals,
-) -> bool {
-    let digit_count_color = visuals.text_color();
-    let op_icon_color = visuals.text_color();
-
+    palette: &GridPalette,
+) -> Response {
     let props = DigitButtonProps::new(state, digit, effective_input_mode);
 
-    let tooltip = props.tooltip();
+    // A locked key is dimmed along with its counts.
+    let dim = |color: Color32| {
+        if props.locked {
+            color.gamma_multiply(0.4)
+        } else {
+            color
+        }
+    };
+    let digit_count_color = dim(palette.key_text);
+    let op_icon_color = palette.key_text;
+    let candidate_count_color = dim(palette.border_selected_digit);
+
+    let tooltip = match state.candidate_count {
+        Some(count) => format!(
+            "{}\n{}",
+            props.tooltip(),
+            tr_args(
+                Text::KeyCandidateCount,
+                &[("count", &count), ("digit", &digit)]
+            )
+        ),
+        None => props.tooltip(),
+    };
     let text = RichText::new(digit.as_str())
-        .color(props.text_color(visuals))
-        .size(button_size * 0.8);
-    let button = Button::new(text).min_size(Vec2::splat(button_size));
+        .color(dim(props.text_color(palette)))
+        .size(font_size);
+    let armed = props.armed == Some(true);
+    let fill = if armed {
+        palette.cell_bg_selected_digit
+    } else {
+        palette.key_bg
+    };
+    let button = Button::new(text)
+        .selected(armed)
+        .fill(fill)
+        .min_size(Vec2::splat(button_size));
     let button = ui
         .add_enabled(props.enabled(), button)
         .on_hover_text(&tooltip)
         .on_disabled_hover_text(&tooltip);
-    let clicked = button.clicked();
 
     ui.painter().text(
         button.rect.right_top() + egui::vec2(-4.0, 2.0),
@@ -339,6 +399,15 @@ fn show_digit_button(
         FontId::proportional(button_size * 0.25),
         digit_count_color,
     );
+    if let Some(count) = state.candidate_count {
+        ui.painter().text(
+            button.rect.left_top() + egui::vec2(4.0, 2.0),
+            Align2::LEFT_TOP,
+            count.to_string(),
+            FontId::proportional(button_size * 0.25),
+            candidate_count_color,
+        );
+    }
 
     if let Some(op_icon) = props.op_icon() {
         ui.painter().text(
@@ -349,38 +418,35 @@ fn show_digit_button(
             op_icon_color,
         );
     }
-    clicked
+    button
 }
 
-fn show_clear_button(ui: &mut Ui, button_size: f32, has_removable_input: bool) -> bool {
-    let text = RichText::new(icon::GARBAGE_CAN).size(button_size * 0.8);
-    let button = Button::new(text).min_size(Vec2::splat(button_size));
-    let button = ui
-        .add_enabled(has_removable_input, button)
-        .on_hover_text("Clear cell (digit and notes)")
-        .on_disabled_hover_text("Clear cell (no removable cell selected)");
-    button.clicked()
-}
-
-fn show_auto_fill_button(
+/// Shows the keypad button of a command and requests its action when
+/// clicked.
+fn show_command_button(
     ui: &mut Ui,
+    vm: &KeypadViewModel,
+    id: CommandId,
     button_size: f32,
-    auto_fill_capability: Option<Result<InputOperation, InputBlockReason>>,
-) -> bool {
-    let enabled = auto_fill_capability.is_some_and(|res| res.is_ok_and(|op| op.is_set()));
-    let text = RichText::new(icon::LETTER_A).size(button_size * 0.8);
-    let button = Button::new(text).min_size(Vec2::splat(button_size));
-    let button = ui
-        .add_enabled(enabled, button)
-        .on_hover_text("Auto-fill notes (selected cell)");
-    button.clicked()
-}
-
-fn show_toggle_input_mode_button(ui: &mut Ui, button_size: f32, input_mode: InputMode) -> bool {
-    let text = RichText::new(icon::PENCIL).size(button_size * 0.8);
+    action_queue: &mut ActionRequestQueue,
+) {
+    let command = id.command();
+    // The input mode toggle stays pressed while notes are entered.
+    let selected = id == CommandId::ToggleInputMode && vm.input_context.base_input_mode.is_notes();
+    let text = RichText::new(command.icon.unwrap_or_default()).size(button_size * 0.8);
     let button = Button::new(text)
-        .selected(input_mode.is_notes())
+        .selected(selected)
         .min_size(Vec2::splat(button_size));
-    let button = ui.add(button).on_hover_text("Toggle Fill/Notes mode");
-    button.clicked()
+    let hover_text = command.hover_text(ui.ctx(), &vm.input_context.key_bindings);
+    let disabled_hover_text = match id {
+        CommandId::ClearCell => tr(Text::KeyClearCellDisabled),
+        _ => &hover_text,
+    };
+    let response = ui
+        .add_enabled(command.is_available(vm.commands), button)
+        .on_hover_text(&hover_text)
+        .on_disabled_hover_text(disabled_hover_text);
+    if response.clicked() {
+        action_queue.request(id.action());
+    }
 }
@@ -1,10 +1,19 @@
 use eframe::egui::Vec2;
 
+use crate::state::DisplaySettings;
+
 #[derive(Debug, Clone, Copy)]
 pub(crate) struct LayoutScale {
     pub(crate) cell_size: f32,
     pub(crate) spacing: Vec2,
     pub(crate) padding: Vec2,
+    pub(crate) digit_font_size: f32,
+    pub(crate) note_font_scale: f32,
+    /// Side length of keypad buttons; larger than `cell_size` in the touch layout.
+    pub(crate) key_size: f32,
+    pub(crate) key_font_size: f32,
+    /// Whether the touch layout (larger keypad, swipe navigation) is active.
+    pub(crate) touch: bool,
 }
 
 impl LayoutScale {
@@ -12,14 +21,31 @@ impl LayoutScale {
     pub(crate) const PADDING_FACTOR: Vec2 = Vec2::new(0.20, 0.30);
 
     #[must_use]
-    pub(crate) fn new(cell_size: f32) -> Self {
+    pub(crate) fn new(cell_size: f32, display: &DisplaySettings) -> Self {
         let spacing = Vec2::splat(cell_size) * Self::SPACING_FACTOR;
         let padding = Vec2::splat(cell_size) * Self::PADDING_FACTOR;
+        let digit_font_size = cell_size * 0.8 * display.digit_size;
         Self {
             cell_size,
             spacing,
             padding,
+            digit_font_size,
+            note_font_scale: display.note_size,
+            key_size: cell_size,
+            key_font_size: digit_font_size,
+            touch: false,
+        }
+    }
+
+    /// Switches to the touch layout with keypad buttons of the given size.
+    #[must_use]
+    pub(crate) fn with_touch_layout(mut self, key_size: f32) -> Self {
+        if self.cell_size > 0.0 {
+            self.key_font_size = self.digit_font_size * key_size / self.cell_size;
         }
+        self.key_size = key_size;
+        self.touch = true;
+        self
     }
 }
 
@@ -0,0 +1,185 @@
+use eframe::egui::{Button, RichText, ScrollArea, Ui};
+use numelace_solver::technique::TechniqueId;
+
+use super::{grid, notification};
+use crate::{
+    action::{ActionRequestQueue, UiAction},
+    i18n::{self, Text, tr, tr_args},
+    state::{DisplaySettings, HintStage, LearnState, NotificationEntry, Screen},
+    ui::{grid::GridViewModel, icon, layout::LayoutScale},
+};
+
+/// Width of the technique list.
+const TECHNIQUE_LIST_WIDTH: f32 = 200.0;
+/// Largest cell size of the example board.
+const MAX_CELL_SIZE: f32 = 40.0;
+
+/// Stages an example steps through, from where to look to the result.
+const STAGES: [HintStage; 4] = [
+    HintStage::Stage0,
+    HintStage::Stage1,
+    HintStage::Stage2,
+    HintStage::Stage3Preview,
+];
+
+#[derive(Debug, Clone)]
+pub(crate) struct LearnScreenViewModel<'a> {
+    /// Techniques with a lesson and their English names, easiest first.
+    techniques: Vec<(TechniqueId, &'static str)>,
+    learn: &'a LearnState,
+    grid: GridViewModel<'a>,
+    display: DisplaySettings,
+    notifications: &'a [NotificationEntry],
+}
+
+impl<'a> LearnScreenViewModel<'a> {
+    #[must_use]
+    pub(crate) fn new(
+        techniques: Vec<(TechniqueId, &'static str)>,
+        learn: &'a LearnState,
+        grid: GridViewModel<'a>,
+        display: DisplaySettings,
+        notifications: &'a [NotificationEntry],
+    ) -> Self {
+        Self {
+            techniques,
+            learn,
+            grid,
+            display,
+            notifications,
+        }
+    }
+}
+
+pub(crate) fn show(ui: &mut Ui, vm: &LearnScreenViewModel, action_queue: &mut ActionRequestQueue) {
+    ui.horizontal(|ui| {
+        if ui
+            .button(format!("{} {}", icon::ARROW_LEFT, tr(Text::BackToGame)))
+            .clicked()
+        {
+            action_queue.request(UiAction::ShowScreen(Screen::Game).into());
+        }
+        ui.heading(tr(Text::Learn));
+    });
+    ui.label(tr(Text::LearnDescription));
+    ui.separator();
+
+    ui.horizontal_top(|ui| {
+        ui.vertical(|ui| {
+            ui.set_width(TECHNIQUE_LIST_WIDTH);
+            ScrollArea::vertical()
+                .id_salt("learn_techniques")
+                .show(ui, |ui| {
+                    show_technique_list(ui, vm, action_queue);
+                });
+        });
+        ui.separator();
+        ui.vertical(|ui| {
+            ScrollArea::vertical()
+                .id_salt("learn_lesson")
+                .show(ui, |ui| {
+                    show_lesson(ui, vm, action_queue);
+                });
+        });
+    });
+
+    notification::show(ui.ctx(), vm.notifications, action_queue);
+}
+
+fn show_technique_list(
+    ui: &mut Ui,
+    vm: &LearnScreenViewModel,
+    action_queue: &mut ActionRequestQueue,
+) {
+    for &(id, name) in &vm.techniques {
+        let selected = vm.learn.lesson.id == id;
+        let name = i18n::technique_name(id.as_str(), name);
+        if ui.selectable_label(selected, name).clicked() && !selected {
+            action_queue.request(UiAction::SelectLesson(id).into());
+        }
+    }
+}
+
+fn show_lesson(ui: &mut Ui, vm: &LearnScreenViewModel, action_queue: &mut ActionRequestQueue) {
+    let lesson = &vm.learn.lesson;
+    let (summary, explanation) = i18n::lesson_text(lesson);
+    ui.heading(i18n::technique_name(lesson.id.as_str(), lesson.name));
+    ui.label(RichText::new(summary).strong());
+    ui.label(explanation);
+    ui.add_space(ui.spacing().item_spacing.y * 2.0);
+
+    if lesson.examples.is_empty() {
+        ui.weak(tr(Text::LessonNoExamples));
+        return;
+    }
+
+    ui.horizontal(|ui| {
+        for index in 0..lesson.examples.len() {
+            let label = tr_args(Text::LessonExample, &[("index", &(index + 1))]);
+            if ui
+                .selectable_label(vm.learn.example == index, label)
+                .clicked()
+            {
+                action_queue.request(
+                    UiAction::ShowLessonExample {
+                        example: index,
+                        stage: HintStage::Stage0,
+                    }
+                    .into(),
+                );
+            }
+        }
+    });
+
+    let stage_index = STAGES
+        .iter()
+        .position(|&stage| stage == vm.learn.stage)
+        .unwrap_or(0);
+    ui.horizontal(|ui| {
+        let back = Button::new(format!(
+            "{} {}",
+            icon::ARROW_LEFT,
+            tr(Text::LessonPreviousStage)
+        ));
+        if ui.add_enabled(stage_index > 0, back).clicked() {
+            request_stage(vm, STAGES[stage_index - 1], action_queue);
+        }
+        let next = Button::new(format!(
+            "{} {}",
+            tr(Text::LessonNextStage),
+            icon::ARROW_RIGHT
+        ));
+        if ui
+            .add_enabled(stage_index + 1 < STAGES.len(), next)
+            .clicked()
+        {
+            request_stage(vm, STAGES[stage_index + 1], action_queue);
+        }
+        ui.label(tr(match vm.learn.stage {
+            HintStage::Stage0 => Text::LessonStageFocus,
+            HintStage::Stage1 => Text::LessonStageCells,
+            HintStage::Stage2 => Text::LessonStageDigits,
+            HintStage::Stage3Preview | HintStage::Stage3Apply => Text::LessonStagePreview,
+        }));
+    });
+
+    let units = grid::required_units(vm.grid.shows_coordinates());
+    let cell_size = (ui.available_width() / units.width).clamp(0.0, MAX_CELL_SIZE);
+    let scale = LayoutScale::new(cell_size.floor(), &vm.display);
+    // The example board is read-only, so clicks on it are dropped.
+    grid::show(ui, &vm.grid, &scale, &mut ActionRequestQueue::default());
+}
+
+fn request_stage(
+    vm: &LearnScreenViewModel,
+    stage: HintStage,
+    action_queue: &mut ActionRequestQueue,
+) {
+    action_queue.request(
+        UiAction::ShowLessonExample {
+            example: vm.learn.example,
+            stage,
+        }
+        .into(),
+    );
+}
@@ -1,11 +1,23 @@
+pub(crate) mod animation;
+pub(crate) mod campaign_screen;
+pub(crate) mod command;
+pub(crate) mod fonts;
 pub(crate) mod game_screen;
+pub(crate) mod generation_trace;
 pub(crate) mod grid;
+#[cfg(test)]
+pub(crate) mod grid_snapshot;
 pub(crate) mod grid_theme;
 pub(crate) mod icon;
+pub(crate) mod info_panel;
 pub(crate) mod input;
 pub(crate) mod keypad;
 pub(crate) mod layout;
+pub(crate) mod learn_screen;
 pub(crate) mod modal;
+pub(crate) mod notification;
+pub(crate) mod review;
+pub(crate) mod script_prompt;
 pub(crate) mod spinner;
 pub(crate) mod status_line;
 pub(crate) mod toolbar;
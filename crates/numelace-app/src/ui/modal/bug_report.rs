@@ -0,0 +1,47 @@
+use eframe::egui::{Context, Id, Modal, ScrollArea, Sides, TextEdit};
+
+use crate::{
+    action::{ActionRequestQueue, UiAction},
+    i18n::{Text, tr},
+    state::ClipboardContent,
+    ui::icon,
+};
+
+pub(crate) fn show(ctx: &Context, report: &str, action_queue: &mut ActionRequestQueue) {
+    let modal = Modal::new(Id::new("bug_report_modal")).show(ctx, |ui| {
+        ui.heading(tr(Text::BugReport));
+        ui.label(tr(Text::BugReportLabel));
+        ScrollArea::vertical().max_height(320.0).show(ui, |ui| {
+            let mut text = report;
+            ui.add(
+                TextEdit::multiline(&mut text)
+                    .code_editor()
+                    .desired_width(480.0),
+            );
+        });
+
+        Sides::new().show(
+            ui,
+            |ui| {
+                if ui
+                    .button(format!("{} {}", icon::CLIPBOARD, tr(Text::CopyReport)))
+                    .clicked()
+                {
+                    let content = ClipboardContent::Text(report.to_owned());
+                    action_queue.request(UiAction::CopyToClipboard(content).into());
+                }
+            },
+            |ui| {
+                if ui
+                    .button(format!("{} {}", icon::CHECK, tr(Text::Close)))
+                    .clicked()
+                {
+                    ui.close();
+                }
+            },
+        );
+    });
+    if modal.should_close() {
+        action_queue.request(UiAction::CloseModal.into());
+    }
+}
@@ -1,14 +1,16 @@
 use std::borrow::Cow;
 
 use eframe::egui::{CollapsingHeader, Context, Id, Modal, Response, RichText, Sides, Ui};
+use numelace_core::Position;
 
 use crate::{
     action::{
         AlertKind, AlertResponder, AlertResult, ConfirmKind, ConfirmResponder, ConfirmResult,
         Responder,
     },
+    i18n::{self, Text, tr, tr_args},
     ui::icon,
-    worker::tasks::SolvabilityStatsDto,
+    worker::tasks::{ContradictionWitnessDto, SolvabilityStatsDto},
 };
 
 struct DialogResult {
@@ -66,66 +68,142 @@ struct ConfirmDialogSpec {
     id: Id,
     heading: &'static str,
     label: &'static str,
+    /// Extra lines shown below the label.
+    details: Vec<String>,
     confirm_label: &'static str,
     confirm_icon: &'static str,
 }
 
 impl ConfirmKind {
-    fn spec(self) -> ConfirmDialogSpec {
+    fn spec(&self) -> ConfirmDialogSpec {
         match self {
             ConfirmKind::NewGame => ConfirmDialogSpec {
                 id: Id::new("new_game_confirm"),
-                heading: "New Game?",
-                label: "Start a new game? Current progress will be lost.",
-                confirm_label: "New Game",
+                heading: tr(Text::NewGameConfirmHeading),
+                label: tr(Text::NewGameConfirmLabel),
+                details: vec![],
+                confirm_label: tr(Text::NewGame),
                 confirm_icon: icon::CHECK,
             },
             ConfirmKind::ResetInputs => ConfirmDialogSpec {
                 id: Id::new("reset_inputs_confirm"),
-                heading: "Reset Inputs?",
-                label: "Clear all your inputs and return to the initial puzzle state?",
-                confirm_label: "Reset Inputs",
+                heading: tr(Text::ResetInputsConfirmHeading),
+                label: tr(Text::ResetInputsConfirmLabel),
+                details: vec![],
+                confirm_label: tr(Text::ResetInputs),
                 confirm_icon: icon::CHECK,
             },
-            ConfirmKind::SolvabilityInconsistent => ConfirmDialogSpec {
+            ConfirmKind::IllegalNotesFound { count } => ConfirmDialogSpec {
+                id: Id::new("illegal_notes_confirm"),
+                heading: tr(Text::IllegalNotesFound),
+                label: tr(Text::IllegalNotesFoundLabel),
+                details: vec![tr_args(Text::IllegalNotesCount, &[("count", count)])],
+                confirm_label: tr(Text::RemoveIllegalNotes),
+                confirm_icon: icon::GARBAGE_CAN,
+            },
+            ConfirmKind::RevealCell => ConfirmDialogSpec {
+                id: Id::new("reveal_cell_confirm"),
+                heading: tr(Text::RevealCellConfirmHeading),
+                label: tr(Text::RevealCellConfirmLabel),
+                details: vec![],
+                confirm_label: tr(Text::RevealCell),
+                confirm_icon: icon::EYE,
+            },
+            ConfirmKind::RevealAll => ConfirmDialogSpec {
+                id: Id::new("reveal_all_confirm"),
+                heading: tr(Text::RevealAllConfirmHeading),
+                label: tr(Text::RevealAllConfirmLabel),
+                details: vec![],
+                confirm_label: tr(Text::RevealAll),
+                confirm_icon: icon::EYE,
+            },
+            ConfirmKind::SolvabilityInconsistent { witness } => ConfirmDialogSpec {
                 id: Id::new("solvability_result"),
-                heading: "Board Inconsistent",
-                label: "A conflict or a no-candidate cell was detected. We recommend undoing to the last consistent state.",
-                confirm_label: "Undo",
+                heading: tr(Text::BoardInconsistent),
+                label: tr(Text::BoardInconsistentLabel),
+                details: witness_details(witness),
+                confirm_label: tr(Text::Undo),
                 confirm_icon: icon::ARROW_UNDO,
             },
-            ConfirmKind::SolvabilityNoSolution => ConfirmDialogSpec {
+            ConfirmKind::SolvabilityNoSolution { witness } => ConfirmDialogSpec {
                 id: Id::new("solvability_result"),
-                heading: "No Solution Found",
-                label: "No solution exists from the current state. We recommend undoing to the last solvable state.",
-                confirm_label: "Undo",
+                heading: tr(Text::NoSolutionFound),
+                label: tr(Text::NoSolutionFoundLabel),
+                details: witness_details(witness),
+                confirm_label: tr(Text::Undo),
                 confirm_icon: icon::ARROW_UNDO,
             },
             ConfirmKind::SolvabilityNotesMaybeIncorrect => ConfirmDialogSpec {
                 id: Id::new("solvability_result"),
-                heading: "Notes May Be Incorrect",
-                label: "A solution exists when ignoring notes. Rebuild candidates now?",
-                confirm_label: "Rebuild",
+                heading: tr(Text::NotesMayBeIncorrect),
+                label: tr(Text::SolvableIgnoringNotesLabel),
+                details: vec![],
+                confirm_label: tr(Text::Rebuild),
                 confirm_icon: icon::CHECK,
             },
-            ConfirmKind::HintInconsistent => ConfirmDialogSpec {
+            ConfirmKind::HintInconsistent { witness } => ConfirmDialogSpec {
                 id: Id::new("hint_inconsistent"),
-                heading: "Board Inconsistent",
-                label: "A conflict or a no-candidate cell was detected. We recommend undoing to the last consistent state.",
-                confirm_label: "Undo",
+                heading: tr(Text::BoardInconsistent),
+                label: tr(Text::BoardInconsistentLabel),
+                details: witness_details(witness),
+                confirm_label: tr(Text::Undo),
                 confirm_icon: icon::ARROW_UNDO,
             },
             ConfirmKind::HintNotesMaybeIncorrect => ConfirmDialogSpec {
                 id: Id::new("hint_notes_maybe_incorrect"),
-                heading: "Notes May Be Incorrect",
-                label: "A hint exists when ignoring notes. Rebuild candidates now?",
-                confirm_label: "Rebuild",
+                heading: tr(Text::NotesMayBeIncorrect),
+                label: tr(Text::HintIgnoringNotesLabel),
+                details: vec![],
+                confirm_label: tr(Text::Rebuild),
+                confirm_icon: icon::CHECK,
+            },
+            ConfirmKind::LoadSharedPuzzle { solved_on } => ConfirmDialogSpec {
+                id: Id::new("load_shared_puzzle_confirm"),
+                heading: tr(Text::LoadSharedPuzzleHeading),
+                label: tr(Text::LoadSharedPuzzleLabel),
+                details: solved_on_details(solved_on.as_deref()),
+                confirm_label: tr(Text::LoadSharedPuzzle),
+                confirm_icon: icon::CHECK,
+            },
+            ConfirmKind::RecoverProgress => ConfirmDialogSpec {
+                id: Id::new("recover_progress_confirm"),
+                heading: tr(Text::RecoverProgressHeading),
+                label: tr(Text::RecoverProgressLabel),
+                details: vec![],
+                confirm_label: tr(Text::RecoverProgress),
                 confirm_icon: icon::CHECK,
             },
         }
     }
 }
 
+/// Mentions the date the player already solved a loaded puzzle on.
+fn solved_on_details(solved_on: Option<&str>) -> Vec<String> {
+    solved_on
+        .map(|date| tr_args(Text::AlreadySolvedOn, &[("date", &date)]))
+        .into_iter()
+        .collect()
+}
+
+/// Lists the cells of `witness`, one line per kind.
+fn witness_details(witness: &ContradictionWitnessDto) -> Vec<String> {
+    let cells = |indices: &[u8]| {
+        indices
+            .iter()
+            .map(|&index| Position::from_index(index).to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    [
+        (Text::InvalidCells, &witness.invalid_cells),
+        (Text::ConflictingEntries, &witness.conflicting_entries),
+    ]
+    .into_iter()
+    .filter(|(_, indices)| !indices.is_empty())
+    .map(|(text, indices)| tr_args(text, &[("cells", &cells(indices))]))
+    .collect()
+}
+
 #[derive(Debug)]
 enum AlertBody<'a> {
     Text(Cow<'a, str>),
@@ -143,76 +221,110 @@ struct AlertDialogSpec<'a> {
 }
 
 impl AlertKind {
+    #[expect(clippy::too_many_lines)]
     fn spec(&self) -> AlertDialogSpec<'_> {
         match self {
             AlertKind::SolvabilitySolvable { stats } => AlertDialogSpec {
                 id: Id::new("solvability_result"),
-                heading: "Solvable",
+                heading: tr(Text::Solvable),
                 body: AlertBody::SolvabilityStats {
-                    summary: Cow::Borrowed("A solution is still possible from the current state."),
+                    summary: Cow::Borrowed(tr(Text::SolvableLabel)),
                     stats,
                 },
-                ok_label: "OK",
+                ok_label: tr(Text::Ok),
             },
             AlertKind::SolvabilityUndoNotice { steps } => AlertDialogSpec {
                 id: Id::new("solvability_undo_notice"),
-                heading: "Undo Complete",
-                body: AlertBody::Text(Cow::Owned(format!(
-                    "Undid {steps} step(s) to return to a solvable state."
+                heading: tr(Text::UndoComplete),
+                body: AlertBody::Text(Cow::Owned(tr_args(
+                    Text::UndidToSolvable,
+                    &[("steps", steps)],
                 ))),
-                ok_label: "OK",
+                ok_label: tr(Text::Ok),
             },
             AlertKind::SolvabilityUndoNotFound => AlertDialogSpec {
                 id: Id::new("solvability_undo_not_found"),
-                heading: "No Solution Found",
-                body: AlertBody::Text(Cow::Borrowed("Undo did not find a solvable state.")),
-                ok_label: "OK",
+                heading: tr(Text::NoSolutionFound),
+                body: AlertBody::Text(Cow::Borrowed(tr(Text::UndoNoSolvableState))),
+                ok_label: tr(Text::Ok),
             },
             AlertKind::SolvabilityAlreadySolved => AlertDialogSpec {
                 id: Id::new("solvability_already_solved"),
-                heading: "Already Solved",
-                body: AlertBody::Text(Cow::Borrowed(
-                    "The puzzle is already solved, so no solvability check is needed.",
-                )),
-                ok_label: "OK",
+                heading: tr(Text::AlreadySolved),
+                body: AlertBody::Text(Cow::Borrowed(tr(Text::AlreadySolvedNoCheck))),
+                ok_label: tr(Text::Ok),
+            },
+            AlertKind::SolvabilityUndetermined => AlertDialogSpec {
+                id: Id::new("solvability_undetermined"),
+                heading: tr(Text::CheckUnavailable),
+                body: AlertBody::Text(Cow::Borrowed(tr(Text::CheckUndeterminedLabel))),
+                ok_label: tr(Text::Ok),
             },
             AlertKind::HintUndoNotice { steps } => AlertDialogSpec {
                 id: Id::new("hint_undo_notice"),
-                heading: "Undo Complete",
-                body: AlertBody::Text(Cow::Owned(format!(
-                    "Undid {steps} step(s) to return to a consistent state."
+                heading: tr(Text::UndoComplete),
+                body: AlertBody::Text(Cow::Owned(tr_args(
+                    Text::UndidToConsistent,
+                    &[("steps", steps)],
                 ))),
-                ok_label: "OK",
+                ok_label: tr(Text::Ok),
             },
             AlertKind::HintStuckNoStep => AlertDialogSpec {
                 id: Id::new("hint_stuck_no_step"),
-                heading: "No Hint Found",
-                body: AlertBody::Text(Cow::Borrowed(
-                    "No applicable techniques found from the current state.",
-                )),
-                ok_label: "OK",
+                heading: tr(Text::NoHintFound),
+                body: AlertBody::Text(Cow::Borrowed(tr(Text::NoApplicableTechniques))),
+                ok_label: tr(Text::Ok),
+            },
+            AlertKind::HintUndetermined => AlertDialogSpec {
+                id: Id::new("hint_undetermined"),
+                heading: tr(Text::NoHintFound),
+                body: AlertBody::Text(Cow::Borrowed(tr(Text::HintUndeterminedLabel))),
+                ok_label: tr(Text::Ok),
             },
             AlertKind::HintStuckAfterRollback => AlertDialogSpec {
                 id: Id::new("hint_stuck_after_rollback"),
-                heading: "No Hint Found",
-                body: AlertBody::Text(Cow::Borrowed(
-                    "The conflict was resolved, but no next step is available.",
-                )),
-                ok_label: "OK",
+                heading: tr(Text::NoHintFound),
+                body: AlertBody::Text(Cow::Borrowed(tr(Text::ConflictResolvedNoStep))),
+                ok_label: tr(Text::Ok),
             },
             AlertKind::HintInconsistentAfterRollback => AlertDialogSpec {
                 id: Id::new("hint_inconsistent_after_rollback"),
-                heading: "No Hint Found",
-                body: AlertBody::Text(Cow::Borrowed("Undo did not find a consistent state.")),
-                ok_label: "OK",
+                heading: tr(Text::NoHintFound),
+                body: AlertBody::Text(Cow::Borrowed(tr(Text::UndoNoConsistentState))),
+                ok_label: tr(Text::Ok),
             },
             AlertKind::HintAlreadySolved => AlertDialogSpec {
                 id: Id::new("hint_already_solved"),
-                heading: "Already Solved",
-                body: AlertBody::Text(Cow::Borrowed(
-                    "The puzzle is already solved, so no hint is available.",
-                )),
-                ok_label: "OK",
+                heading: tr(Text::AlreadySolved),
+                body: AlertBody::Text(Cow::Borrowed(tr(Text::AlreadySolvedNoHint))),
+                ok_label: tr(Text::Ok),
+            },
+            AlertKind::NoIllegalNotes => AlertDialogSpec {
+                id: Id::new("no_illegal_notes"),
+                heading: tr(Text::NotesLookFine),
+                body: AlertBody::Text(Cow::Borrowed(tr(Text::NoIllegalNotesLabel))),
+                ok_label: tr(Text::Ok),
+            },
+            AlertKind::ExportSaved { path } => AlertDialogSpec {
+                id: Id::new("export_saved"),
+                heading: tr(Text::ExportSaved),
+                body: AlertBody::Text(Cow::Owned(tr_args(
+                    Text::ExportSavedLabel,
+                    &[("path", path)],
+                ))),
+                ok_label: tr(Text::Ok),
+            },
+            AlertKind::ExportFailed { message } => AlertDialogSpec {
+                id: Id::new("export_failed"),
+                heading: tr(Text::ExportFailed),
+                body: AlertBody::Text(Cow::Borrowed(message)),
+                ok_label: tr(Text::Ok),
+            },
+            AlertKind::SharedPuzzleInvalid { message } => AlertDialogSpec {
+                id: Id::new("shared_puzzle_invalid"),
+                heading: tr(Text::SharedPuzzleInvalid),
+                body: AlertBody::Text(Cow::Borrowed(message)),
+                ok_label: tr(Text::Ok),
             },
         }
     }
@@ -220,7 +332,7 @@ impl AlertKind {
 
 pub(crate) fn show_confirm(
     ctx: &Context,
-    kind: ConfirmKind,
+    kind: &ConfirmKind,
     responder: &mut Option<ConfirmResponder>,
 ) {
     let spec = kind.spec();
@@ -230,6 +342,9 @@ pub(crate) fn show_confirm(
         spec.heading,
         |ui: &mut Ui| {
             ui.label(spec.label);
+            for detail in &spec.details {
+                ui.label(detail);
+            }
         },
         |ui: &mut Ui| {
             let confirm = primary_button(
@@ -242,7 +357,7 @@ pub(crate) fn show_confirm(
                 ui.close();
             }
 
-            let cancel = ui.button(format!("{} Cancel", icon::CANCEL));
+            let cancel = ui.button(format!("{} {}", icon::CANCEL, tr(Text::Cancel)));
             if cancel.clicked() {
                 send_response(responder, ConfirmResult::Cancelled);
                 ui.close();
@@ -267,10 +382,10 @@ pub(crate) fn show_alert(ctx: &Context, kind: &AlertKind, responder: &mut Option
             }
             AlertBody::SolvabilityStats { summary, stats } => {
                 ui.label(summary.as_ref());
-                CollapsingHeader::new("Solving details")
+                CollapsingHeader::new(tr(Text::SolvingDetails))
                     .default_open(false)
                     .show(ui, |ui| {
-                        ui.label(format!("Total steps: {}", stats.total_steps));
+                        ui.label(tr_args(Text::TotalSteps, &[("steps", &stats.total_steps)]));
                         let mut shown = false;
                         for entry in stats
                             .technique_counts
@@ -278,10 +393,14 @@ pub(crate) fn show_alert(ctx: &Context, kind: &AlertKind, responder: &mut Option
                             .filter(|entry| entry.count > 0)
                         {
                             shown = true;
-                            ui.label(format!("{}: {}", entry.name, entry.count));
+                            ui.label(format!(
+                                "{}: {}",
+                                i18n::technique_name(&entry.id, &entry.name),
+                                entry.count
+                            ));
                         }
                         if !shown {
-                            ui.label("No techniques applied.");
+                            ui.label(tr(Text::NoTechniquesApplied));
                         }
                     });
             }
@@ -0,0 +1,185 @@
+//! A small board fragment showing the highlight settings while they are edited.
+
+use eframe::egui::{
+    Align2, Color32, FontId, Painter, Rect, Sense, Stroke, StrokeKind, Ui, Vec2, vec2,
+};
+
+use crate::{
+    state::{HighlightSettings, ThemeSettings},
+    ui::grid_theme::GridPalette,
+};
+
+const CELL_SIZE: f32 = 24.0;
+const ROWS: u8 = 3;
+const COLS: u8 = 5;
+
+/// What a preview cell holds.
+#[derive(Clone, Copy)]
+enum Content {
+    Empty,
+    Given(u8),
+    Filled(u8),
+    Notes(&'static [u8]),
+}
+
+/// The selected cell holds a 5, another 5 sits in a different row and column,
+/// two 7s conflict, and one cell has a note of 5.
+const SELECTED: (u8, u8) = (1, 1);
+const SELECTED_DIGIT: u8 = 5;
+const CELLS: [[Content; COLS as usize]; ROWS as usize] = [
+    [
+        Content::Empty,
+        Content::Given(3),
+        Content::Empty,
+        Content::Given(5),
+        Content::Empty,
+    ],
+    [
+        Content::Given(8),
+        Content::Filled(5),
+        Content::Empty,
+        Content::Empty,
+        Content::Filled(1),
+    ],
+    [
+        Content::Given(7),
+        Content::Empty,
+        Content::Filled(7),
+        Content::Empty,
+        Content::Notes(&[2, 5, 8]),
+    ],
+];
+
+/// Draws the preview with the colors of `theme` and the current highlights.
+pub(super) fn show(ui: &mut Ui, highlight: &HighlightSettings, theme: &ThemeSettings) {
+    let size = vec2(f32::from(COLS), f32::from(ROWS)) * CELL_SIZE;
+    let (rect, _) = ui.allocate_exact_size(size, Sense::hover());
+    let painter = ui.painter_at(rect);
+    let preview = Preview {
+        highlight,
+        palette: GridPalette::from_settings(theme, ui.visuals())
+            .with_intensity(&highlight.intensity),
+    };
+    for row in 0..ROWS {
+        for col in 0..COLS {
+            let min = rect.min + vec2(f32::from(col), f32::from(row)) * CELL_SIZE;
+            let cell = Rect::from_min_size(min, Vec2::splat(CELL_SIZE));
+            painter.rect_filled(cell, 0.0, preview.fill(row, col));
+            painter.rect_stroke(
+                cell,
+                0.0,
+                Stroke::new(1.0, preview.palette.border_inactive),
+                StrokeKind::Inside,
+            );
+            if let Some(border) = preview.border(row, col) {
+                painter.rect_stroke(cell, 0.0, border, StrokeKind::Inside);
+            }
+            preview.draw_content(&painter, cell, row, col);
+        }
+    }
+}
+
+struct Preview<'a> {
+    highlight: &'a HighlightSettings,
+    palette: GridPalette,
+}
+
+impl Preview<'_> {
+    fn content(row: u8, col: u8) -> Content {
+        CELLS[usize::from(row)][usize::from(col)]
+    }
+
+    fn holds_selected_digit(row: u8, col: u8) -> bool {
+        matches!(
+            Self::content(row, col),
+            Content::Given(SELECTED_DIGIT) | Content::Filled(SELECTED_DIGIT)
+        )
+    }
+
+    fn sees_selected_digit(row: u8, col: u8) -> bool {
+        (0..ROWS).any(|r| Self::holds_selected_digit(r, col))
+            || (0..COLS).any(|c| Self::holds_selected_digit(row, c))
+    }
+
+    fn conflicts(row: u8, digit: u8) -> bool {
+        CELLS[usize::from(row)]
+            .iter()
+            .filter(
+                |content| matches!(content, Content::Given(d) | Content::Filled(d) if *d == digit),
+            )
+            .count()
+            > 1
+    }
+
+    fn fill(&self, row: u8, col: u8) -> Color32 {
+        let is_candidate = matches!(
+            Self::content(row, col),
+            Content::Notes(notes) if notes.contains(&SELECTED_DIGIT)
+        );
+        if self.highlight.selected_digit && Self::holds_selected_digit(row, col) {
+            self.palette.cell_bg_selected_digit
+        } else if self.highlight.selected_digit_candidate && is_candidate {
+            self.palette.cell_bg_selected_digit_candidate
+        } else if self.highlight.selected_digit_peer && Self::sees_selected_digit(row, col) {
+            self.palette.cell_bg_selected_digit_peer
+        } else {
+            self.palette.cell_bg_default
+        }
+    }
+
+    fn border(&self, row: u8, col: u8) -> Option<Stroke> {
+        let is_selected = (row, col) == SELECTED;
+        if is_selected && self.highlight.selected_cell {
+            Some(Stroke::new(3.0, self.palette.border_selected_cell))
+        } else if self.highlight.selected_digit && Self::holds_selected_digit(row, col) {
+            Some(Stroke::new(1.5, self.palette.border_selected_digit))
+        } else if self.highlight.selected_cell_peer
+            && !is_selected
+            && (row == SELECTED.0 || col == SELECTED.1)
+        {
+            Some(Stroke::new(1.0, self.palette.border_selected_cell_peer))
+        } else {
+            None
+        }
+    }
+
+    fn draw_content(&self, painter: &Painter, cell: Rect, row: u8, col: u8) {
+        match Self::content(row, col) {
+            Content::Empty => {}
+            content @ (Content::Given(digit) | Content::Filled(digit)) => {
+                let color = if self.highlight.conflict && Self::conflicts(row, digit) {
+                    self.palette.text_conflict
+                } else if matches!(content, Content::Given(_)) {
+                    self.palette.text_given
+                } else {
+                    self.palette.text_normal
+                };
+                painter.text(
+                    cell.center(),
+                    Align2::CENTER_CENTER,
+                    digit.to_string(),
+                    FontId::proportional(CELL_SIZE * 0.7),
+                    color,
+                );
+            }
+            Content::Notes(notes) => {
+                let spot_size = CELL_SIZE / 3.0;
+                for &digit in notes {
+                    let index = digit - 1;
+                    let offset = vec2(f32::from(index % 3), f32::from(index / 3)) * spot_size;
+                    let spot = Rect::from_min_size(cell.min + offset, Vec2::splat(spot_size));
+                    if self.highlight.selected_digit_note && digit == SELECTED_DIGIT {
+                        painter.rect_filled(spot, 0.0, self.palette.note_bg_selected_digit);
+                    }
+                    painter.text(
+                        spot.center(),
+                        Align2::CENTER_CENTER,
+                        digit.to_string(),
+                        FontId::proportional(CELL_SIZE * 0.3),
+                        self.palette.text_note,
+                    );
+                }
+            }
+        }
+    }
+}
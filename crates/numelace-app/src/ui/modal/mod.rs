@@ -1,22 +1,40 @@
 use eframe::egui::Context;
 
-pub(crate) use self::{new_game_options::NewGameOptionsViewModel, settings::SettingsViewModel};
+pub(crate) use self::{
+    new_game_options::NewGameOptionsViewModel, race_result::RaceResultViewModel,
+    settings::SettingsViewModel, solved_result::SolvedResultViewModel,
+    statistics::StatisticsViewModel,
+};
 use crate::action::{ActionRequestQueue, ModalRequest};
 
+mod bug_report;
 mod dialogs;
+mod highlight_preview;
 mod new_game_options;
+mod race_result;
 mod settings;
+mod solved_result;
+mod statistics;
+
+/// View models of every modal, one of which is shown at a time.
+#[derive(Debug, Clone)]
+pub(crate) struct ModalViewModels<'a> {
+    pub(crate) new_game_options: NewGameOptionsViewModel<'a>,
+    pub(crate) settings: SettingsViewModel<'a>,
+    pub(crate) statistics: StatisticsViewModel<'a>,
+    pub(crate) solved_result: SolvedResultViewModel,
+    pub(crate) race_result: RaceResultViewModel,
+}
 
 pub(crate) fn show(
     ctx: &Context,
     action_queue: &mut ActionRequestQueue,
     modal_request: &mut ModalRequest,
-    new_game_options_vm: &NewGameOptionsViewModel,
-    settings_vm: &SettingsViewModel,
+    vms: &ModalViewModels,
 ) {
     match modal_request {
         ModalRequest::Confirm { kind, responder } => {
-            dialogs::show_confirm(ctx, *kind, responder);
+            dialogs::show_confirm(ctx, kind, responder);
         }
         ModalRequest::Alert { kind, responder } => {
             dialogs::show_alert(ctx, kind, responder);
@@ -27,14 +45,26 @@ pub(crate) fn show(
         } => {
             new_game_options::show(
                 ctx,
-                new_game_options_vm,
+                &vms.new_game_options,
                 action_queue,
                 *can_cancel,
                 responder,
             );
         }
         ModalRequest::Settings => {
-            settings::show(ctx, settings_vm, action_queue);
+            settings::show(ctx, &vms.settings, action_queue);
+        }
+        ModalRequest::Statistics => {
+            statistics::show(ctx, &vms.statistics, action_queue);
+        }
+        ModalRequest::SolvedResult => {
+            solved_result::show(ctx, &vms.solved_result, action_queue);
+        }
+        ModalRequest::RaceResult => {
+            race_result::show(ctx, &vms.race_result, action_queue);
+        }
+        ModalRequest::BugReport { report } => {
+            bug_report::show(ctx, report, action_queue);
         }
     }
 }
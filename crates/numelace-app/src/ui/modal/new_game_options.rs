@@ -1,28 +1,66 @@
 use eframe::egui::{
-    Checkbox, CollapsingHeader, Context, DragValue, Id, Modal, Response, Sides, TextEdit, Ui,
+    Button, Checkbox, CollapsingHeader, Context, DragValue, Id, Modal, Response, Sides, TextEdit,
+    Ui,
 };
+use numelace_generator::ClueSymmetry;
 use numelace_solver::technique;
 
 use crate::{
     action::{ActionRequestQueue, NewGameOptionsResponder, UpdateStateAction},
-    state::{DifficultyPreset, NewGameOptions},
+    i18n::{self, Text, tr},
+    state::{DifficultyPreset, GameMode, NewGameOptions},
     ui::icon,
+    worker::Readiness,
 };
 
 #[derive(Debug, Clone)]
 pub(crate) struct NewGameOptionsViewModel<'a> {
     new_game_options: &'a NewGameOptions,
+    worker_readiness: Readiness,
 }
 
 impl<'a> NewGameOptionsViewModel<'a> {
     #[must_use]
-    pub(crate) fn new(settings: &'a NewGameOptions) -> Self {
+    pub(crate) fn new(settings: &'a NewGameOptions, worker_readiness: Readiness) -> Self {
         Self {
             new_game_options: settings,
+            worker_readiness,
         }
     }
 }
 
+fn show_techniques(ui: &mut Ui, draft: &mut NewGameOptions) -> bool {
+    let mut changed = false;
+    CollapsingHeader::new(tr(Text::Techniques))
+        .default_open(false)
+        .show(ui, |ui| {
+            for technique in technique::all_techniques() {
+                let Some(id) = technique.technique_id() else {
+                    continue;
+                };
+                let mut enabled = draft.is_technique_enabled(id);
+                let can_toggle = !technique.tier().is_fundamental();
+                let text = i18n::technique(technique.id());
+                let label = format!(
+                    "{} ({})",
+                    text.map_or(technique.name(), |text| text.name),
+                    i18n::difficulty(DifficultyPreset::from(technique.tier()))
+                );
+                let mut response = ui.add_enabled(can_toggle, Checkbox::new(&mut enabled, label));
+                if let Some(text) = text {
+                    response = response
+                        .on_hover_text(text.description)
+                        .on_disabled_hover_text(text.description);
+                }
+                if response.changed() {
+                    changed = true;
+                    draft.set_technique_enabled(id, enabled);
+                }
+            }
+        });
+    changed
+}
+
 pub(crate) fn show(
     ctx: &Context,
     vm: &NewGameOptionsViewModel,
@@ -32,50 +70,69 @@ pub(crate) fn show(
 ) {
     let mut draft = vm.new_game_options.clone();
     let modal = Modal::new(Id::new("new_game_options_modal")).show(ctx, |ui| {
-        ui.heading("New Game");
-        ui.label("Choose difficulty and techniques to generate a new puzzle.");
+        ui.heading(tr(Text::NewGame));
+        ui.label(tr(Text::NewGameDescription));
 
         let mut changed = false;
 
         ui.separator();
-        ui.label("Difficulty");
+        ui.label(tr(Text::Difficulty));
         for preset in DifficultyPreset::all() {
-            let response = ui.radio_value(&mut draft.difficulty, preset, preset.label());
+            let response = ui.radio_value(&mut draft.difficulty, preset, i18n::difficulty(preset));
             if response.clicked() {
                 changed = true;
                 draft.apply_preset(preset);
             }
         }
 
-        CollapsingHeader::new("Techniques")
-            .default_open(false)
-            .show(ui, |ui| {
-                for technique in technique::all_techniques() {
-                    let mut enabled = draft.is_technique_enabled(technique.id());
-                    let can_toggle = !technique.tier().is_fundamental();
-                    let label = format!(
-                        "{} ({})",
-                        technique.name(),
-                        DifficultyPreset::from(technique.tier()).label()
-                    );
-                    if ui
-                        .add_enabled(can_toggle, Checkbox::new(&mut enabled, label))
-                        .changed()
-                    {
-                        changed = true;
-                        draft.set_technique_enabled(technique.id(), enabled);
-                    }
-                }
-            });
+        changed |= show_techniques(ui, &mut draft);
+
+        ui.separator();
+        ui.label(tr(Text::ExtraRules));
+        changed |= ui
+            .checkbox(&mut draft.anti_knight, tr(Text::AntiKnight))
+            .on_hover_text(tr(Text::AntiKnightTooltip))
+            .changed();
+        changed |= ui
+            .checkbox(&mut draft.anti_king, tr(Text::AntiKing))
+            .on_hover_text(tr(Text::AntiKingTooltip))
+            .changed();
+        changed |= ui
+            .checkbox(&mut draft.jigsaw, tr(Text::Jigsaw))
+            .on_hover_text(tr(Text::JigsawTooltip))
+            .changed();
+
+        ui.separator();
+        ui.label(tr(Text::Symmetry));
+        for symmetry in ClueSymmetry::ALL {
+            changed |= ui
+                .radio_value(&mut draft.symmetry, symmetry, i18n::symmetry(symmetry))
+                .changed();
+        }
+
+        ui.separator();
+        ui.label(tr(Text::GameMode));
+        for mode in GameMode::all() {
+            let (glyph, tooltip) = match mode {
+                GameMode::Classic => (icon::PENCIL, Text::ModeClassicTooltip),
+                GameMode::Timed => (icon::STOPWATCH, Text::TimedModeTooltip),
+                GameMode::HotSeatRace => (icon::USERS, Text::HotSeatRaceTooltip),
+            };
+            let label = format!("{glyph} {}", i18n::game_mode(mode));
+            changed |= ui
+                .radio_value(&mut draft.mode, mode, label)
+                .on_hover_text(tr(tooltip))
+                .changed();
+        }
 
         ui.separator();
-        ui.label("Seed (optional)");
+        ui.label(tr(Text::SeedOptional));
         changed |= ui
-            .add(TextEdit::singleline(&mut draft.seed).hint_text("Leave blank for random"))
+            .add(TextEdit::singleline(&mut draft.seed).hint_text(tr(Text::SeedHint)))
             .changed();
 
         ui.separator();
-        ui.label("Generation attempts");
+        ui.label(tr(Text::GenerationAttempts));
         changed |= ui
             .add(
                 DragValue::new(&mut draft.max_attempts)
@@ -88,14 +145,23 @@ pub(crate) fn show(
             ui,
             |_ui| {},
             |ui| {
-                let response = ui.button(format!("{} Generate", icon::CHECK));
+                let response = ui
+                    .add_enabled(
+                        vm.worker_readiness.is_ready(),
+                        Button::new(format!("{} {}", icon::CHECK, tr(Text::Generate))),
+                    )
+                    .on_disabled_hover_text(tr(Text::PreparingGenerator));
                 request_focus_if_none(ui, &response);
                 if response.clicked() {
                     let mut response = draft.clone();
                     response.seed = response.seed.trim().to_string();
                     send_response(responder, Some(response));
                 }
-                if can_cancel && ui.button(format!("{} Cancel", icon::CANCEL)).clicked() {
+                if can_cancel
+                    && ui
+                        .button(format!("{} {}", icon::CANCEL, tr(Text::Cancel)))
+                        .clicked()
+                {
                     send_response(responder, None);
                 }
             },
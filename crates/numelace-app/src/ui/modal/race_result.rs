@@ -0,0 +1,103 @@
+use eframe::egui::{Context, Grid, Id, Modal, RichText, Sides};
+
+use crate::{
+    action::{ActionRequestQueue, FlowAction, UiAction},
+    i18n::{self, Text, tr, tr_args},
+    state::RaceSummary,
+    ui::{icon, status_line::clock_text},
+};
+
+#[derive(Debug, Clone)]
+pub(crate) struct RaceResultViewModel {
+    race: Option<RaceSummary>,
+}
+
+impl RaceResultViewModel {
+    #[must_use]
+    pub(crate) fn new(race: Option<RaceSummary>) -> Self {
+        Self { race }
+    }
+}
+
+fn outcome_text(race: &RaceSummary) -> String {
+    if !race.is_finished() {
+        return tr(Text::RaceInProgress).to_owned();
+    }
+    match race.winner() {
+        Some(winner) => tr_args(Text::RaceWinner, &[("player", &i18n::player(winner))]),
+        None => tr(Text::RaceTie).to_owned(),
+    }
+}
+
+pub(crate) fn show(ctx: &Context, vm: &RaceResultViewModel, action_queue: &mut ActionRequestQueue) {
+    let modal = Modal::new(Id::new("race_result_modal")).show(ctx, |ui| {
+        ui.heading(format!(
+            "{} {}",
+            icon::FLAG_CHECKERED,
+            tr(Text::RaceResultHeading)
+        ));
+        let Some(race) = &vm.race else {
+            return;
+        };
+        let winner = race.winner();
+        // One column per player, so the two results line up side by side.
+        Grid::new("race_result").num_columns(3).show(ui, |ui| {
+            ui.label("");
+            for standing in &race.standings {
+                let name = i18n::player(standing.player);
+                if winner == Some(standing.player) {
+                    ui.strong(format!("{} {name}", icon::TROPHY));
+                } else {
+                    ui.strong(name);
+                }
+            }
+            ui.end_row();
+            ui.label(tr(Text::RaceTime));
+            for standing in &race.standings {
+                let time = clock_text(standing.clock.elapsed);
+                if standing.clock.finished {
+                    ui.strong(time);
+                } else {
+                    ui.label(format!("{} {time}", icon::HOURGLASS));
+                }
+            }
+            ui.end_row();
+            ui.label(tr(Text::RaceProgress));
+            for standing in &race.standings {
+                ui.label(format!(
+                    "{}/{} ({}%)",
+                    standing.progress.filled,
+                    standing.progress.total,
+                    standing.progress.percent(),
+                ));
+            }
+            ui.end_row();
+        });
+        let color = if race.is_finished() {
+            ui.visuals().warn_fg_color
+        } else {
+            ui.visuals().text_color()
+        };
+        ui.label(RichText::new(outcome_text(race)).heading().color(color));
+
+        Sides::new().show(
+            ui,
+            |_ui| {},
+            |ui| {
+                if ui
+                    .button(format!("{} {}", icon::CHECK, tr(Text::Close)))
+                    .clicked()
+                {
+                    ui.close();
+                }
+                if race.is_finished() && ui.button(tr(Text::NewGame)).clicked() {
+                    action_queue.request(UiAction::CloseModal.into());
+                    action_queue.request(FlowAction::StartNewGame.into());
+                }
+            },
+        );
+    });
+    if modal.should_close() {
+        action_queue.request(UiAction::CloseModal.into());
+    }
+}
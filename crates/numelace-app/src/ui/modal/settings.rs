@@ -1,9 +1,24 @@
-use eframe::egui::{CollapsingHeader, Context, Id, Modal, ScrollArea, Sides, widgets};
+use eframe::egui::{
+    Button, Checkbox, CollapsingHeader, ComboBox, Context, Event, Grid, Id, Key, Modal, RichText,
+    ScrollArea, Sides, Slider, Ui, widgets,
+};
+use numelace_solver::technique;
 
 use crate::{
-    action::{ActionRequestQueue, UiAction, UpdateStateAction},
-    state::{AssistSettings, HighlightSettings, NotesSettings, Settings},
-    ui::icon,
+    action::{ActionRequestQueue, FlowAction, NoteKind, UiAction, UpdateStateAction},
+    i18n::{self, Text, tr},
+    state::{
+        AssistProfiles, AssistSettings, AutosaveSettings, BivalueSource, CommandId,
+        DisplaySettings, GivenStyle, GridStyle, HighlightIntensity, HighlightSettings, HintDetail,
+        InputOrder, InputSettings, KeyBindings, KeyTrigger, Language, MotionPreference, NoteChord,
+        NoteLayout, NotesSettings, Settings, SolverSettings, ThemeColors, ThemePreset,
+        ThemeSettings, ValidationMode,
+    },
+    ui::{
+        command::{COMMANDS, CommandCategory, rebind, reset_binding},
+        icon,
+        modal::highlight_preview,
+    },
 };
 
 #[derive(Debug, Clone)]
@@ -18,87 +33,748 @@ impl<'a> SettingsViewModel<'a> {
     }
 }
 
+fn show_validation_mode(
+    ui: &mut Ui,
+    block_rule_violations: bool,
+    validation_mode: &mut ValidationMode,
+) -> bool {
+    let mut changed = false;
+    ui.add_enabled_ui(block_rule_violations, |ui| {
+        ui.indent("validation_mode", |ui| {
+            changed |= ui
+                .radio_value(
+                    validation_mode,
+                    ValidationMode::Rules,
+                    tr(Text::ValidationModeRules),
+                )
+                .changed();
+            changed |= ui
+                .radio_value(
+                    validation_mode,
+                    ValidationMode::Solution,
+                    tr(Text::ValidationModeSolution),
+                )
+                .on_hover_text(tr(Text::ValidationModeSolutionTooltip))
+                .changed();
+        });
+    });
+    changed
+}
+
+fn show_bivalue_source(ui: &mut Ui, bivalue_cell: bool, source: &mut BivalueSource) -> bool {
+    let mut changed = false;
+    ui.add_enabled_ui(bivalue_cell, |ui| {
+        ui.indent("bivalue_source", |ui| {
+            changed |= ui
+                .radio_value(
+                    source,
+                    BivalueSource::Candidates,
+                    tr(Text::BivalueSourceCandidates),
+                )
+                .changed();
+            changed |= ui
+                .radio_value(source, BivalueSource::Notes, tr(Text::BivalueSourceNotes))
+                .changed();
+        });
+    });
+    changed
+}
+
+fn show_hint_detail(ui: &mut Ui, hint_detail: &mut HintDetail) -> bool {
+    let mut changed = false;
+    ui.label(format!("{} {}", icon::LIGHTBULB, tr(Text::HintDetail)));
+    ui.indent("hint_detail", |ui| {
+        changed |= ui
+            .radio_value(
+                hint_detail,
+                HintDetail::ConditionCells,
+                tr(Text::HintDetailConditionCells),
+            )
+            .changed();
+        changed |= ui
+            .radio_value(
+                hint_detail,
+                HintDetail::FocusArea,
+                tr(Text::HintDetailFocusArea),
+            )
+            .on_hover_text(tr(Text::HintDetailFocusAreaTooltip))
+            .changed();
+    });
+    changed
+}
+
+fn show_assist_settings(
+    ui: &mut Ui,
+    assist: &mut AssistSettings,
+    profiles: &mut AssistProfiles,
+    theme: &ThemeSettings,
+) -> bool {
+    let mut changed = false;
+    let AssistSettings {
+        block_rule_violations,
+        validation_mode,
+        highlight,
+        notes,
+        keypad_candidate_counts,
+        lock_completed_digits,
+        candidate_heatmap,
+        deduction_depth_overlay,
+        hint_detail,
+    } = assist;
+    CollapsingHeader::new(format!("{} {}", icon::BOLT, tr(Text::Assist)))
+        .default_open(true)
+        .show(ui, |ui| {
+            changed |= ui
+                .checkbox(block_rule_violations, tr(Text::BlockRuleViolations))
+                .changed();
+            changed |= show_validation_mode(ui, *block_rule_violations, validation_mode);
+
+            ui.label(format!("{} {}", icon::BRIGHTNESS, tr(Text::Highlight)));
+            ui.indent("highlight", |ui| {
+                changed |= show_highlight_settings(ui, highlight, theme);
+            });
+
+            ui.label(format!("{} {}", icon::PENCIL, tr(Text::Notes)));
+            ui.indent("notes", |ui| {
+                let NotesSettings {
+                    auto_remove_peer_notes_on_fill,
+                    auto_fill_notes_on_new_or_reset,
+                } = notes;
+                changed |= ui
+                    .checkbox(
+                        auto_remove_peer_notes_on_fill,
+                        tr(Text::AutoRemovePeerNotes),
+                    )
+                    .changed();
+                changed |= ui
+                    .checkbox(
+                        auto_fill_notes_on_new_or_reset,
+                        tr(Text::AutoFillNotesOnNewOrReset),
+                    )
+                    .changed();
+            });
+
+            changed |= ui
+                .checkbox(keypad_candidate_counts, tr(Text::KeypadCandidateCounts))
+                .changed();
+            changed |= ui
+                .checkbox(lock_completed_digits, tr(Text::LockCompletedDigits))
+                .on_hover_text(tr(Text::LockCompletedDigitsTooltip))
+                .changed();
+            changed |= ui
+                .checkbox(candidate_heatmap, tr(Text::CandidateHeatmap))
+                .on_hover_text(tr(Text::CandidateHeatmapTooltip))
+                .changed();
+            changed |= ui
+                .checkbox(deduction_depth_overlay, tr(Text::DeductionDepthOverlay))
+                .on_hover_text(tr(Text::DeductionDepthOverlayTooltip))
+                .changed();
+
+            changed |= show_hint_detail(ui, hint_detail);
+
+            ui.separator();
+            changed |= ui
+                .checkbox(&mut profiles.enabled, tr(Text::AssistProfiles))
+                .on_hover_text(tr(Text::AssistProfilesTooltip))
+                .changed();
+            if profiles.enabled
+                && let Some(active) = profiles.active
+            {
+                ui.weak(i18n::tr_args(
+                    Text::AssistProfileActive,
+                    &[("difficulty", &i18n::difficulty(active))],
+                ));
+            }
+        });
+    changed
+}
+
+fn show_highlight_settings(
+    ui: &mut Ui,
+    highlight: &mut HighlightSettings,
+    theme: &ThemeSettings,
+) -> bool {
+    let mut changed = false;
+    let HighlightSettings {
+        selected_cell,
+        selected_digit,
+        selected_digit_note,
+        selected_cell_peer,
+        selected_digit_peer,
+        conflict,
+        selected_digit_candidate,
+        bivalue_cell,
+        bivalue_source,
+        intensity,
+    } = highlight;
+    changed |= ui
+        .checkbox(selected_cell, tr(Text::HighlightSelectedCell))
+        .changed();
+    changed |= ui
+        .checkbox(selected_digit, tr(Text::HighlightSelectedDigit))
+        .changed();
+    changed |= ui
+        .checkbox(selected_digit_note, tr(Text::HighlightSelectedDigitNote))
+        .changed();
+    changed |= ui
+        .checkbox(selected_cell_peer, tr(Text::HighlightSelectedCellPeer))
+        .changed();
+    changed |= ui
+        .checkbox(selected_digit_peer, tr(Text::HighlightSelectedDigitPeer))
+        .changed();
+    changed |= ui.checkbox(conflict, tr(Text::HighlightConflict)).changed();
+    changed |= ui
+        .checkbox(
+            selected_digit_candidate,
+            tr(Text::HighlightSelectedDigitCandidate),
+        )
+        .changed();
+    changed |= ui
+        .checkbox(bivalue_cell, tr(Text::HighlightBivalueCell))
+        .on_hover_text(tr(Text::HighlightBivalueCellTooltip))
+        .changed();
+    changed |= show_bivalue_source(ui, *bivalue_cell, bivalue_source);
+
+    let HighlightIntensity {
+        selection,
+        house,
+        same_digit,
+        notes,
+        conflict: conflict_intensity,
+    } = intensity;
+    CollapsingHeader::new(tr(Text::HighlightIntensity))
+        .default_open(false)
+        .show(ui, |ui| {
+            for (label, value) in [
+                (Text::ColorSelectedCell, selection),
+                (Text::ColorHouse, house),
+                (Text::ColorSelectedDigit, same_digit),
+                (Text::Notes, notes),
+                (Text::ColorConflicts, conflict_intensity),
+            ] {
+                changed |= ui
+                    .add(Slider::new(value, HighlightIntensity::RANGE).text(tr(label)))
+                    .changed();
+            }
+        });
+    highlight_preview::show(ui, highlight, theme);
+    changed
+}
+
+fn show_display_settings(ui: &mut Ui, display: &mut DisplaySettings) -> bool {
+    let mut changed = false;
+    ui.label(format!("{} {}", icon::FONT_SIZE, tr(Text::FontSize)));
+    ui.indent("display", |ui| {
+        ui.horizontal(|ui| {
+            for (label, preset) in [
+                (Text::FontSizeNormal, DisplaySettings::NORMAL),
+                (Text::FontSizeLargePrint, DisplaySettings::LARGE_PRINT),
+            ] {
+                if ui.selectable_label(*display == preset, tr(label)).clicked() {
+                    *display = preset;
+                    changed = true;
+                }
+            }
+        });
+        let DisplaySettings {
+            ui_zoom,
+            digit_size,
+            note_size,
+        } = display;
+        for (label, value, range) in [
+            (Text::UiScale, ui_zoom, DisplaySettings::UI_ZOOM_RANGE),
+            (Text::Digits, digit_size, DisplaySettings::DIGIT_SIZE_RANGE),
+            (Text::Notes, note_size, DisplaySettings::NOTE_SIZE_RANGE),
+        ] {
+            changed |= ui
+                .add(Slider::new(value, range).text(tr(label)).step_by(0.05))
+                .changed();
+        }
+    });
+    changed
+}
+
+fn show_grid_style(ui: &mut Ui, grid_style: &mut GridStyle) -> bool {
+    let mut changed = false;
+    let GridStyle {
+        notes,
+        reserve_note_space,
+        givens,
+        coordinates,
+    } = grid_style;
+    ui.horizontal(|ui| {
+        ui.label(tr(Text::Notes));
+        changed |= ui
+            .radio_value(notes, NoteLayout::MiniGrid, tr(Text::NoteLayoutMiniGrid))
+            .changed();
+        changed |= ui
+            .radio_value(notes, NoteLayout::Linear, tr(Text::NoteLayoutLinear))
+            .on_hover_text(tr(Text::NoteLayoutLinearTooltip))
+            .changed();
+    });
+    ui.add_enabled_ui(*notes == NoteLayout::Linear, |ui| {
+        changed |= ui
+            .checkbox(reserve_note_space, tr(Text::ReserveNoteSpace))
+            .on_hover_text(tr(Text::ReserveNoteSpaceTooltip))
+            .changed();
+    });
+    ui.horizontal(|ui| {
+        ui.label(tr(Text::ColorGivenDigits));
+        for (style, label) in [
+            (GivenStyle::Plain, Text::GivenStylePlain),
+            (GivenStyle::Bold, Text::GivenStyleBold),
+            (GivenStyle::Boxed, Text::GivenStyleBoxed),
+        ] {
+            changed |= ui.radio_value(givens, style, tr(label)).changed();
+        }
+    });
+    changed |= ui
+        .checkbox(coordinates, tr(Text::BoardCoordinates))
+        .on_hover_text(tr(Text::BoardCoordinatesTooltip))
+        .changed();
+    changed
+}
+
+fn show_motion_preference(ui: &mut Ui, motion: &mut MotionPreference) -> bool {
+    let mut changed = false;
+    ui.horizontal(|ui| {
+        ui.label(tr(Text::Motion));
+        for (preference, label, tooltip) in [
+            (
+                MotionPreference::System,
+                Text::ThemeSystem,
+                Some(Text::MotionSystemTooltip),
+            ),
+            (MotionPreference::Full, Text::MotionFull, None),
+            (
+                MotionPreference::Reduced,
+                Text::MotionReduced,
+                Some(Text::MotionReducedTooltip),
+            ),
+        ] {
+            let response = ui.radio_value(motion, preference, tr(label));
+            let response = match tooltip {
+                Some(tooltip) => response.on_hover_text(tr(tooltip)),
+                None => response,
+            };
+            changed |= response.changed();
+        }
+    });
+    changed
+}
+
+fn show_theme_settings(ui: &mut Ui, theme: &mut ThemeSettings) -> bool {
+    let mut changed = false;
+    let previous = *theme;
+    ComboBox::from_label(tr(Text::BoardColors))
+        .selected_text(i18n::theme_preset(theme.preset))
+        .show_ui(ui, |ui| {
+            for preset in ThemePreset::all() {
+                changed |= ui
+                    .selectable_value(&mut theme.preset, preset, i18n::theme_preset(preset))
+                    .changed();
+            }
+        });
+    if changed && theme.preset == ThemePreset::Custom {
+        // Start customizing from the colors that were shown so far.
+        theme.custom = match previous.colors() {
+            Some(colors) => *colors,
+            None if ui.visuals().dark_mode => ThemeColors::DARK,
+            None => ThemeColors::LIGHT,
+        };
+    }
+
+    if theme.preset == ThemePreset::Custom {
+        let ThemeColors {
+            background,
+            border,
+            given,
+            filled,
+            note,
+            selected_cell,
+            selected_digit,
+            house,
+            conflict,
+            hint,
+        } = &mut theme.custom;
+        Grid::new("custom_theme_colors").show(ui, |ui| {
+            for (label, color) in [
+                (Text::ColorBackground, background),
+                (Text::ColorGridLines, border),
+                (Text::ColorGivenDigits, given),
+                (Text::ColorFilledDigits, filled),
+                (Text::Notes, note),
+                (Text::ColorSelectedCell, selected_cell),
+                (Text::ColorSelectedDigit, selected_digit),
+                (Text::ColorHouse, house),
+                (Text::ColorConflicts, conflict),
+                (Text::ColorHints, hint),
+            ] {
+                ui.label(tr(label));
+                changed |= ui.color_edit_button_srgb(color).changed();
+                ui.end_row();
+            }
+        });
+    }
+    changed
+}
+
+/// The command whose shortcut is being recorded, and whether the last key
+/// pressed for it was rejected.
+#[derive(Debug, Clone, Copy)]
+struct Capture {
+    id: CommandId,
+    rejected: bool,
+}
+
+/// Lists the shortcut of every command by category and lets the player
+/// rebind them and the note chords.
+fn show_keyboard_shortcuts(ui: &mut Ui, key_bindings: &mut KeyBindings) -> bool {
+    let mut changed = false;
+    CollapsingHeader::new(format!("{} {}", icon::KEY, tr(Text::KeyboardShortcuts)))
+        .default_open(false)
+        .show(ui, |ui| {
+            changed |= show_note_chords(ui, key_bindings);
+
+            let capture_id = Id::new("shortcut_capture");
+            let mut capture = ui.data(|data| data.get_temp::<Capture>(capture_id));
+            if let Some(current) = capture
+                && let Some(trigger) = take_pressed_key(ui)
+            {
+                capture = if trigger.key == Key::Escape {
+                    None
+                } else if rebind(key_bindings, current.id, trigger) {
+                    changed = true;
+                    None
+                } else {
+                    Some(Capture {
+                        rejected: true,
+                        ..current
+                    })
+                };
+            }
+
+            Grid::new("keyboard_shortcuts")
+                .striped(true)
+                .show(ui, |ui| {
+                    for category in CommandCategory::all() {
+                        let commands = COMMANDS
+                            .iter()
+                            .filter(|command| command.category == category);
+                        for (index, command) in commands.enumerate() {
+                            ui.weak(if index == 0 { category.label() } else { "" });
+                            ui.label(command.menu_label());
+                            let capturing = capture.filter(|capture| capture.id == command.id);
+                            let text = if capturing.is_some() {
+                                tr(Text::PressAKey).to_owned()
+                            } else {
+                                let shortcuts: Vec<_> = command
+                                    .shortcuts(key_bindings)
+                                    .iter()
+                                    .map(|shortcut| shortcut.format(ui.ctx()))
+                                    .collect();
+                                if shortcuts.is_empty() {
+                                    tr(Text::Unbound).to_owned()
+                                } else {
+                                    shortcuts.join(" / ")
+                                }
+                            };
+                            let mut response = ui.add(
+                                Button::new(RichText::new(text).monospace())
+                                    .selected(capturing.is_some()),
+                            );
+                            if capturing.is_some_and(|capture| capture.rejected) {
+                                response = response.on_hover_text(tr(Text::KeyReserved));
+                            }
+                            if response.clicked() {
+                                capture = Some(Capture {
+                                    id: command.id,
+                                    rejected: false,
+                                });
+                            }
+                            if ui
+                                .add_enabled(
+                                    command.is_rebound(key_bindings),
+                                    Button::new(icon::ARROW_UNDO),
+                                )
+                                .on_hover_text(tr(Text::ResetShortcut))
+                                .clicked()
+                            {
+                                reset_binding(key_bindings, command.id);
+                                changed = true;
+                            }
+                            ui.end_row();
+                        }
+                    }
+                });
+            if ui
+                .add_enabled(
+                    *key_bindings != KeyBindings::default(),
+                    Button::new(tr(Text::ResetToDefaults)),
+                )
+                .clicked()
+            {
+                *key_bindings = KeyBindings::default();
+                capture = None;
+                changed = true;
+            }
+
+            ui.data_mut(|data| match capture {
+                Some(capture) => {
+                    data.insert_temp(capture_id, capture);
+                }
+                None => data.remove::<Capture>(capture_id),
+            });
+        });
+    changed
+}
+
+/// Lets the player pick the modifiers that turn digit keys into corner and
+/// center note input.
+fn show_note_chords(ui: &mut Ui, key_bindings: &mut KeyBindings) -> bool {
+    let mut changed = false;
+    Grid::new("note_chords").show(ui, |ui| {
+        for (kind, label) in [
+            (NoteKind::Corner, Text::CornerNoteKeys),
+            (NoteKind::Center, Text::CenterNoteKeys),
+        ] {
+            ui.label(tr(label));
+            let mut chord = key_bindings.note_chord(kind);
+            ComboBox::from_id_salt(("note_chord", kind))
+                .selected_text(chord_label(ui.ctx(), chord))
+                .show_ui(ui, |ui| {
+                    for option in NoteChord::all() {
+                        ui.selectable_value(&mut chord, option, chord_label(ui.ctx(), option));
+                    }
+                });
+            if chord != key_bindings.note_chord(kind) {
+                key_bindings.set_note_chord(kind, chord);
+                changed = true;
+            }
+            ui.end_row();
+        }
+    });
+    changed
+}
+
+/// Names a note chord by how it is written with the digit 1.
+fn chord_label(ctx: &Context, chord: NoteChord) -> String {
+    chord.trigger(Key::Num1).format(ctx)
+}
+
+/// Takes the first key pressed this frame, so that it neither closes the
+/// modal nor reaches anything else.
+fn take_pressed_key(ui: &Ui) -> Option<KeyTrigger> {
+    ui.input_mut(|i| {
+        let index = i.events.iter().position(|event| {
+            matches!(
+                event,
+                Event::Key {
+                    pressed: true,
+                    repeat: false,
+                    ..
+                }
+            )
+        })?;
+        let Event::Key { key, modifiers, .. } = i.events.remove(index) else {
+            unreachable!();
+        };
+        Some(KeyTrigger::new(key, modifiers.command, modifiers.shift))
+    })
+}
+
+fn show_solver_settings(ui: &mut Ui, solver: &mut SolverSettings) -> bool {
+    let mut changed = false;
+    CollapsingHeader::new(format!("{} {}", icon::LIGHTBULB, tr(Text::Solver)))
+        .default_open(false)
+        .show(ui, |ui| {
+            ui.label(tr(Text::SolverDescription));
+            let mut assumed = solver.priority.unique_solution_assumed();
+            if ui
+                .checkbox(&mut assumed, tr(Text::AssumeUniqueSolution))
+                .on_hover_text(tr(Text::AssumeUniqueSolutionTooltip))
+                .changed()
+            {
+                solver.priority.set_unique_solution_assumed(assumed);
+                changed = true;
+            }
+            let order = solver.technique_order();
+            let last = order.len() - 1;
+            Grid::new("solver_techniques").show(ui, |ui| {
+                for (index, id) in order.into_iter().enumerate() {
+                    let Some(technique) = technique::by_id(id) else {
+                        continue;
+                    };
+                    let text = i18n::technique(id.as_str());
+                    let available = !id.assumes_unique_solution() || assumed;
+                    let mut enabled = available && solver.priority.is_enabled(id);
+                    let can_toggle = available && !technique.tier().is_fundamental();
+                    let label = text.map_or(technique.name(), |text| text.name);
+                    let mut response =
+                        ui.add_enabled(can_toggle, Checkbox::new(&mut enabled, label));
+                    if let Some(text) = text {
+                        response = response
+                            .on_hover_text(text.description)
+                            .on_disabled_hover_text(text.description);
+                    }
+                    if response.changed() {
+                        solver.set_technique_enabled(id, enabled);
+                        changed = true;
+                    }
+                    ui.horizontal(|ui| {
+                        if ui
+                            .add_enabled(index > 0, Button::new(icon::CARET_UP))
+                            .on_hover_text(tr(Text::MoveTechniqueUp))
+                            .clicked()
+                        {
+                            solver.move_technique_down(index - 1);
+                            changed = true;
+                        }
+                        if ui
+                            .add_enabled(index < last, Button::new(icon::CARET_DOWN))
+                            .on_hover_text(tr(Text::MoveTechniqueDown))
+                            .clicked()
+                        {
+                            solver.move_technique_down(index);
+                            changed = true;
+                        }
+                    });
+                    ui.end_row();
+                }
+            });
+            if ui
+                .add_enabled(
+                    *solver != SolverSettings::default(),
+                    Button::new(tr(Text::ResetToDefaults)),
+                )
+                .clicked()
+            {
+                *solver = SolverSettings::default();
+                changed = true;
+            }
+        });
+    changed
+}
+
 pub(crate) fn show(ctx: &Context, vm: &SettingsViewModel, action_queue: &mut ActionRequestQueue) {
     let modal = Modal::new(Id::new("settings_modal")).show(ctx, |ui| {
-        ui.heading("Settings");
+        ui.heading(tr(Text::Settings));
         let mut changed = false;
         let mut settings = vm.settings.clone();
-        let Settings { assist } = &mut settings;
+        let Settings {
+            language,
+            assist,
+            assist_profiles,
+            input,
+            theme,
+            display,
+            grid_style,
+            motion,
+            autosave,
+            solver,
+        } = &mut settings;
         ScrollArea::vertical().show(ui, |ui| {
-            let AssistSettings {
-                block_rule_violations,
-                highlight,
-                notes,
-            } = assist;
-            CollapsingHeader::new(format!("{} Assist", icon::BOLT))
+            ComboBox::from_label(format!("{} {}", icon::GLOBE_MERIDIANS, tr(Text::Language)))
+                .selected_text(language.native_name())
+                .show_ui(ui, |ui| {
+                    for option in Language::all() {
+                        changed |= ui
+                            .selectable_value(language, option, option.native_name())
+                            .changed();
+                    }
+                });
+
+            changed |= show_assist_settings(ui, assist, assist_profiles, theme);
+
+            CollapsingHeader::new(format!("{} {}", icon::POINT_RIGHT, tr(Text::Input)))
                 .default_open(true)
                 .show(ui, |ui| {
+                    let InputSettings {
+                        order,
+                        key_bindings,
+                    } = input;
                     changed |= ui
-                        .checkbox(block_rule_violations, "Block rule violations")
+                        .radio_value(order, InputOrder::CellFirst, tr(Text::CellFirst))
+                        .on_hover_text(tr(Text::CellFirstTooltip))
                         .changed();
-
-                    ui.label(format!("{} Highlight", icon::BRIGHTNESS));
-                    ui.indent("highlight", |ui| {
-                        let HighlightSettings {
-                            selected_digit,
-                            selected_cell_peer,
-                            selected_digit_peer,
-                            conflict,
-                        } = highlight;
-                        changed |= ui
-                            .checkbox(selected_digit, "Selected digit cells/notes")
-                            .changed();
-                        changed |= ui
-                            .checkbox(selected_cell_peer, "Selected cell's row/col/box")
-                            .changed();
-                        changed |= ui
-                            .checkbox(selected_digit_peer, "Selected digit cells' row/col/box")
-                            .changed();
-                        changed |= ui.checkbox(conflict, "Conflicting cells/notes").changed();
-                    });
-
-                    ui.label(format!("{} Notes", icon::PENCIL));
-                    ui.indent("notes", |ui| {
-                        let NotesSettings {
-                            auto_remove_peer_notes_on_fill,
-                            auto_fill_notes_on_new_or_reset,
-                        } = notes;
-                        changed |= ui
-                            .checkbox(
-                                auto_remove_peer_notes_on_fill,
-                                "Auto-remove row/col/box notes on fill",
-                            )
-                            .changed();
-                        changed |= ui
-                            .checkbox(
-                                auto_fill_notes_on_new_or_reset,
-                                "Auto-fill notes on new game/reset",
-                            )
-                            .changed();
-                    });
+                    changed |= ui
+                        .radio_value(order, InputOrder::DigitFirst, tr(Text::DigitFirst))
+                        .on_hover_text(tr(Text::DigitFirstTooltip))
+                        .changed();
+                    changed |= show_keyboard_shortcuts(ui, key_bindings);
                 });
 
-            CollapsingHeader::new(format!("{} Appearance", icon::PALETTE))
+            CollapsingHeader::new(format!("{} {}", icon::PALETTE, tr(Text::Appearance)))
                 .default_open(true)
                 .show(ui, |ui| {
                     widgets::global_theme_preference_buttons(ui);
+                    changed |= show_theme_settings(ui, theme);
+                    changed |= show_display_settings(ui, display);
+                    changed |= show_grid_style(ui, grid_style);
+                    changed |= show_motion_preference(ui, motion);
                 });
+
+            CollapsingHeader::new(format!("{} {}", icon::FLOPPY, tr(Text::Saving)))
+                .default_open(false)
+                .show(ui, |ui| {
+                    let AutosaveSettings { interval_secs } = autosave;
+                    changed |= ui
+                        .add(
+                            Slider::new(interval_secs, AutosaveSettings::INTERVAL_SECS_RANGE)
+                                .text(tr(Text::AutosaveInterval))
+                                .suffix(tr(Text::SecondsSuffix)),
+                        )
+                        .on_hover_text(tr(Text::AutosaveIntervalTooltip))
+                        .changed();
+                });
+
+            changed |= show_solver_settings(ui, solver);
         });
 
         Sides::new().show(
             ui,
-            |_ui| {},
+            |ui| show_file_buttons(ui, action_queue),
             |ui| {
-                if ui.button(format!("{} Close", icon::CHECK)).clicked() {
+                if ui
+                    .button(format!("{} {}", icon::CHECK, tr(Text::Close)))
+                    .clicked()
+                {
                     ui.close();
                 }
             },
         );
         if changed {
-            action_queue.request(UpdateStateAction::UpdateSettings(settings).into());
+            action_queue.request(UpdateStateAction::UpdateSettings(Box::new(settings)).into());
         }
     });
     if modal.should_close() {
         action_queue.request(UiAction::CloseModal.into());
     }
 }
+
+/// Shows the settings export and import buttons and the tutorial replay
+/// button.
+fn show_file_buttons(ui: &mut Ui, action_queue: &mut ActionRequestQueue) {
+    if ui
+        .button(format!("{} {}", icon::OUTBOX, tr(Text::ExportSettings)))
+        .on_hover_text(tr(Text::ExportSettingsTooltip))
+        .clicked()
+    {
+        action_queue.request(FlowAction::ExportSettings.into());
+    }
+    if ui
+        .button(format!("{} {}", icon::INBOX, tr(Text::ImportSettings)))
+        .on_hover_text(tr(Text::ImportSettingsTooltip))
+        .clicked()
+    {
+        action_queue.request(FlowAction::ImportSettings.into());
+    }
+    if ui
+        .button(format!("{} {}", icon::GRADUATION, tr(Text::ReplayTutorial)))
+        .on_hover_text(tr(Text::ReplayTutorialTooltip))
+        .clicked()
+    {
+        action_queue.request(UiAction::CloseModal.into());
+        action_queue.request(FlowAction::StartTutorial.into());
+    }
+}
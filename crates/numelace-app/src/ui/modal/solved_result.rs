@@ -0,0 +1,104 @@
+use eframe::egui::{Context, Grid, Id, Modal, RichText, Sides};
+
+use crate::{
+    action::{ActionRequestQueue, FlowAction, UiAction, UpdateStateAction},
+    i18n::{self, Text, tr},
+    state::{ParRating, PuzzleDifficulty, TimedGame},
+    ui::{icon, status_line::clock_text},
+};
+
+#[derive(Debug, Clone)]
+pub(crate) struct SolvedResultViewModel {
+    timed: Option<TimedGame>,
+    difficulty: Option<PuzzleDifficulty>,
+}
+
+impl SolvedResultViewModel {
+    #[must_use]
+    pub(crate) fn new(timed: Option<TimedGame>, difficulty: Option<PuzzleDifficulty>) -> Self {
+        Self { timed, difficulty }
+    }
+}
+
+fn rating_text(rating: ParRating) -> &'static str {
+    tr(match rating {
+        ParRating::Outstanding => Text::RatingOutstanding,
+        ParRating::UnderPar => Text::RatingUnderPar,
+        ParRating::OverPar => Text::RatingOverPar,
+        ParRating::WellOverPar => Text::RatingWellOverPar,
+    })
+}
+
+/// Shows the result of the solved puzzle: its difficulty, and for timed games
+/// the play time against par.
+pub(crate) fn show(
+    ctx: &Context,
+    vm: &SolvedResultViewModel,
+    action_queue: &mut ActionRequestQueue,
+) {
+    let modal = Modal::new(Id::new("solved_result_modal")).show(ctx, |ui| {
+        ui.heading(format!(
+            "{} {}",
+            icon::TROPHY,
+            tr(Text::SolvedResultHeading)
+        ));
+        Grid::new("solved_result").num_columns(2).show(ui, |ui| {
+            if let Some(difficulty) = vm.difficulty {
+                ui.label(tr(Text::Difficulty));
+                ui.label(match difficulty {
+                    PuzzleDifficulty::Rated(preset) => i18n::difficulty(preset),
+                    PuzzleDifficulty::BeyondTechniques => tr(Text::DifficultyBeyondTechniques),
+                });
+                ui.end_row();
+            }
+            if let Some(timed) = vm.timed {
+                ui.label(tr(Text::YourTime));
+                ui.strong(clock_text(timed.elapsed));
+                ui.end_row();
+                ui.label(tr(Text::ParTime));
+                ui.label(
+                    timed
+                        .par
+                        .map_or_else(|| tr(Text::ParTimeUnknown).to_owned(), clock_text),
+                );
+                ui.end_row();
+            }
+        });
+        if let Some(rating) = vm.timed.and_then(|timed| timed.rating()) {
+            let color = if rating <= ParRating::UnderPar {
+                ui.visuals().warn_fg_color
+            } else {
+                ui.visuals().text_color()
+            };
+            ui.label(RichText::new(rating_text(rating)).heading().color(color));
+        }
+        ui.weak(tr(Text::SolvedResultReviewHint));
+
+        Sides::new().show(
+            ui,
+            |_ui| {},
+            |ui| {
+                if ui
+                    .button(format!("{} {}", icon::CHECK, tr(Text::Close)))
+                    .clicked()
+                {
+                    ui.close();
+                }
+                if ui
+                    .button(format!("{} {}", icon::EYE, tr(Text::SolvedResultReview)))
+                    .clicked()
+                {
+                    action_queue.request(UiAction::CloseModal.into());
+                    action_queue.request(UpdateStateAction::StartReview.into());
+                }
+                if ui.button(tr(Text::NewGame)).clicked() {
+                    action_queue.request(UiAction::CloseModal.into());
+                    action_queue.request(FlowAction::StartNewGame.into());
+                }
+            },
+        );
+    });
+    if modal.should_close() {
+        action_queue.request(UiAction::CloseModal.into());
+    }
+}
@@ -0,0 +1,151 @@
+use eframe::egui::{Button, Context, Grid, Id, Modal, Sides, Ui};
+
+use crate::{
+    action::{ActionRequestQueue, UiAction, UpdateStateAction},
+    i18n::{self, Text, tr, tr_args},
+    state::{HintUsage, HouseHints, PuzzleMetadataStore},
+    ui::{icon, status_line},
+};
+
+#[derive(Debug, Clone)]
+pub(crate) struct StatisticsViewModel<'a> {
+    hint_usage: &'a HintUsage,
+    puzzle_metadata: &'a PuzzleMetadataStore,
+}
+
+impl<'a> StatisticsViewModel<'a> {
+    #[must_use]
+    pub(crate) fn new(hint_usage: &'a HintUsage, puzzle_metadata: &'a PuzzleMetadataStore) -> Self {
+        Self {
+            hint_usage,
+            puzzle_metadata,
+        }
+    }
+}
+
+pub(crate) fn show(ctx: &Context, vm: &StatisticsViewModel, action_queue: &mut ActionRequestQueue) {
+    let modal = Modal::new(Id::new("statistics_modal")).show(ctx, |ui| {
+        ui.heading(tr(Text::Statistics));
+        ui.label(tr_args(
+            Text::HintsRequested,
+            &[("count", &vm.hint_usage.total())],
+        ));
+        ui.label(tr_args(
+            Text::RevealsUsed,
+            &[("count", &vm.hint_usage.reveals())],
+        ));
+
+        ui.separator();
+        show_solved_puzzles(ui, vm.puzzle_metadata);
+
+        ui.separator();
+        ui.label(format!("{} {}", icon::CHART, tr(Text::HintedTechniques)));
+        let entries = vm.hint_usage.most_hinted();
+        if entries.is_empty() {
+            ui.weak(tr(Text::NoHintsYet));
+        } else {
+            Grid::new("hinted_techniques")
+                .num_columns(2)
+                .striped(true)
+                .show(ui, |ui| {
+                    for (key, count) in entries {
+                        ui.label(i18n::technique_name(key, key));
+                        ui.label(count.to_string());
+                        ui.end_row();
+                    }
+                });
+        }
+
+        ui.separator();
+        ui.label(format!("{} {}", icon::CHART, tr(Text::HintsByHouse)));
+        show_house_hints(ui, vm.hint_usage.houses());
+
+        Sides::new().show(
+            ui,
+            |ui| {
+                if ui
+                    .add_enabled(
+                        !vm.hint_usage.is_empty(),
+                        Button::new(format!(
+                            "{} {}",
+                            icon::GARBAGE_CAN,
+                            tr(Text::ClearStatistics)
+                        )),
+                    )
+                    .clicked()
+                {
+                    action_queue.request(UpdateStateAction::ClearHintUsage.into());
+                }
+            },
+            |ui| {
+                if ui
+                    .button(format!("{} {}", icon::CHECK, tr(Text::Close)))
+                    .clicked()
+                {
+                    ui.close();
+                }
+            },
+        );
+    });
+    if modal.should_close() {
+        action_queue.request(UiAction::CloseModal.into());
+    }
+}
+
+/// Shows the number of solved puzzles and the best time of each difficulty.
+fn show_solved_puzzles(ui: &mut Ui, puzzle_metadata: &PuzzleMetadataStore) {
+    ui.label(tr_args(
+        Text::PuzzlesSolved,
+        &[("count", &puzzle_metadata.solved_count())],
+    ));
+    let records = puzzle_metadata.difficulty_records();
+    if records.is_empty() {
+        ui.weak(tr(Text::NoSolvedPuzzlesYet));
+        return;
+    }
+    ui.label(format!("{} {}", icon::CHART, tr(Text::BestTimes)));
+    Grid::new("best_times")
+        .num_columns(3)
+        .striped(true)
+        .show(ui, |ui| {
+            for record in records {
+                ui.label(i18n::puzzle_difficulty(record.difficulty));
+                ui.label(record.solved.to_string());
+                ui.label(
+                    record
+                        .best_time
+                        .map_or_else(|| "-".to_owned(), status_line::clock_text),
+                );
+                ui.end_row();
+            }
+        });
+}
+
+/// Shows the hint counts of every row, column and box, numbered from 1.
+fn show_house_hints(ui: &mut Ui, houses: &HouseHints) {
+    if houses.is_empty() {
+        ui.weak(tr(Text::NoHintsYet));
+        return;
+    }
+    Grid::new("house_hints")
+        .num_columns(10)
+        .striped(true)
+        .show(ui, |ui| {
+            ui.label("");
+            for index in 1..=9 {
+                ui.strong(index.to_string());
+            }
+            ui.end_row();
+            for (text, counts) in [
+                (Text::HouseRows, &houses.rows),
+                (Text::HouseColumns, &houses.cols),
+                (Text::HouseBoxes, &houses.boxes),
+            ] {
+                ui.label(tr(text));
+                for count in counts {
+                    ui.label(count.to_string());
+                }
+                ui.end_row();
+            }
+        });
+}
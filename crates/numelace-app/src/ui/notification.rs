@@ -0,0 +1,70 @@
+use eframe::egui::{Align2, Area, Context, Frame, Id, Order, RichText, Ui, vec2};
+
+use crate::{
+    action::{ActionRequestQueue, NotificationKind, NotificationLevel, UiAction},
+    i18n::{Text, tr},
+    state::NotificationEntry,
+    ui::icon,
+};
+
+const MARGIN: f32 = 12.0;
+const MAX_WIDTH: f32 = 320.0;
+
+/// Shows active notifications as toasts stacked in the bottom-right corner, oldest on top.
+pub(crate) fn show(
+    ctx: &Context,
+    entries: &[NotificationEntry],
+    action_queue: &mut ActionRequestQueue,
+) {
+    if entries.is_empty() {
+        return;
+    }
+    Area::new(Id::new("notifications"))
+        .order(Order::Foreground)
+        .anchor(Align2::RIGHT_BOTTOM, vec2(-MARGIN, -MARGIN))
+        .show(ctx, |ui| {
+            ui.set_max_width(MAX_WIDTH);
+            for entry in entries {
+                show_entry(ui, entry, action_queue);
+            }
+        });
+}
+
+fn show_entry(ui: &mut Ui, entry: &NotificationEntry, action_queue: &mut ActionRequestQueue) {
+    let kind = entry.notification.kind;
+    let heading = match kind {
+        NotificationKind::ShareLinkCopied => tr(Text::ShareLinkCopied),
+        NotificationKind::BoardImageCopied => tr(Text::BoardImageCopied),
+        NotificationKind::Autosaved => tr(Text::Autosaved),
+        NotificationKind::SettingsExported => tr(Text::SettingsExported),
+        NotificationKind::SettingsImported => tr(Text::SettingsImported),
+        NotificationKind::RaceTurnFinished => tr(Text::RaceTurnFinished),
+        NotificationKind::CampaignPuzzleSolved => tr(Text::CampaignPuzzleSolved),
+        NotificationKind::CampaignPuzzleLoadFailed => tr(Text::CampaignPuzzleLoadFailed),
+        NotificationKind::SettingsExportFailed => tr(Text::SettingsExportFailed),
+        NotificationKind::SettingsImportFailed => tr(Text::SettingsImportFailed),
+        NotificationKind::NewGameFailed => tr(Text::NewGameFailed),
+        NotificationKind::SolvabilityCheckFailed => tr(Text::SolvabilityCheckFailed),
+        NotificationKind::ProgressOnTrack => tr(Text::ProgressOnTrack),
+        NotificationKind::ProgressOffTrack => tr(Text::ProgressOffTrack),
+        NotificationKind::ProgressCheckUnavailable => tr(Text::ProgressCheckUnavailable),
+    };
+    let visuals = ui.visuals();
+    let (glyph, color) = match kind.level() {
+        NotificationLevel::Info => (icon::CHECK, visuals.text_color()),
+        NotificationLevel::Error => (icon::WARNING, visuals.error_fg_color),
+    };
+    Frame::popup(ui.style()).show(ui, |ui| {
+        ui.horizontal(|ui| {
+            ui.label(RichText::new(glyph).color(color));
+            ui.strong(heading);
+            let dismiss = ui.small_button(icon::CLOSE).on_hover_text(tr(Text::Close));
+            if dismiss.clicked() {
+                action_queue.request(UiAction::DismissNotification { id: entry.id }.into());
+            }
+        });
+        if let Some(detail) = &entry.notification.detail {
+            ui.small(detail);
+        }
+    });
+}
@@ -0,0 +1,160 @@
+//! Timeline of the solve log, shown in place of the keypad while a solved
+//! puzzle is reviewed.
+
+use eframe::egui::{Button, RichText, Sense, Stroke, Ui, Vec2, emath::remap_clamp, pos2, vec2};
+
+use crate::{
+    action::{ActionRequestQueue, UiAction},
+    i18n::{Text, tr, tr_args},
+    state::{SolveLog, SolveStep, SolveStepKind},
+    ui::{icon, layout::LayoutScale, status_line::clock_text},
+};
+
+#[derive(Debug, Clone)]
+pub(crate) struct ReviewViewModel<'a> {
+    steps: &'a [SolveStep],
+    /// Number of steps shown on the board.
+    shown: usize,
+}
+
+impl<'a> ReviewViewModel<'a> {
+    #[must_use]
+    pub(crate) fn new(log: &'a SolveLog) -> Self {
+        let steps = log.steps();
+        Self {
+            steps,
+            shown: log.reviewed().unwrap_or(steps.len()),
+        }
+    }
+}
+
+pub(crate) fn show(
+    ui: &mut Ui,
+    vm: &ReviewViewModel,
+    scale: &LayoutScale,
+    action_queue: &mut ActionRequestQueue,
+) {
+    let cell_size = scale.cell_size;
+    ui.spacing_mut().item_spacing = scale.spacing;
+    ui.add_space(cell_size * 0.2);
+    show_controls(ui, vm, cell_size, action_queue);
+    show_timeline(ui, vm, cell_size, action_queue);
+    ui.label(
+        RichText::new(step_text(vm))
+            .color(ui.visuals().weak_text_color())
+            .size(cell_size * 0.35),
+    );
+}
+
+fn show_controls(
+    ui: &mut Ui,
+    vm: &ReviewViewModel,
+    cell_size: f32,
+    action_queue: &mut ActionRequestQueue,
+) {
+    let last = vm.steps.len();
+    let previous = vm.shown.saturating_sub(1);
+    let next = (vm.shown + 1).min(last);
+    ui.horizontal(|ui| {
+        for (label, tooltip, step) in [
+            (icon::FIRST, Text::ReviewFirstTooltip, 0),
+            (icon::BACKWARD, Text::ReviewPreviousTooltip, previous),
+            (icon::FORWARD, Text::ReviewNextTooltip, next),
+            (icon::LAST, Text::ReviewLastTooltip, last),
+        ] {
+            let button = Button::new(RichText::new(label).size(cell_size * 0.5))
+                .min_size(Vec2::splat(cell_size));
+            if ui
+                .add_enabled(step != vm.shown, button)
+                .on_hover_text(tr(tooltip))
+                .clicked()
+            {
+                action_queue.request(UiAction::ReviewSolveStep(step).into());
+            }
+        }
+    });
+}
+
+/// Draws one tick per step, red for mistakes and yellow for hints, with a
+/// handle at the shown step. Clicking or dragging picks the nearest step.
+fn show_timeline(
+    ui: &mut Ui,
+    vm: &ReviewViewModel,
+    cell_size: f32,
+    action_queue: &mut ActionRequestQueue,
+) {
+    let size = vec2(ui.available_width(), cell_size * 0.8);
+    let (rect, response) = ui.allocate_exact_size(size, Sense::click_and_drag());
+    let response = response.on_hover_text(tr(Text::ReviewTimelineTooltip));
+    let track = rect.shrink2(vec2(cell_size * 0.25, cell_size * 0.25));
+    let last = vm.steps.len();
+    #[expect(clippy::cast_precision_loss)]
+    let x_of = |step: usize| remap_clamp(step as f32, 0.0..=last.max(1) as f32, track.x_range());
+
+    let visuals = ui.visuals();
+    let painter = ui.painter_at(rect);
+    painter.hline(
+        track.x_range(),
+        track.center().y,
+        Stroke::new(cell_size * 0.06, visuals.widgets.inactive.bg_fill),
+    );
+    for (index, step) in vm.steps.iter().enumerate() {
+        let color = match step.kind {
+            SolveStepKind::Mistake => visuals.error_fg_color,
+            SolveStepKind::Hint => visuals.warn_fg_color,
+            SolveStepKind::Move | SolveStepKind::Undo | SolveStepKind::Redo => continue,
+        };
+        painter.vline(
+            x_of(index + 1),
+            track.y_range(),
+            Stroke::new(cell_size * 0.05, color),
+        );
+    }
+    let handle_color = if response.dragged() || response.hovered() {
+        visuals.widgets.hovered.fg_stroke.color
+    } else {
+        visuals.selection.bg_fill
+    };
+    painter.circle_filled(
+        pos2(x_of(vm.shown), track.center().y),
+        cell_size * 0.15,
+        handle_color,
+    );
+
+    if (response.clicked() || response.dragged())
+        && let Some(pointer) = response.interact_pointer_pos()
+    {
+        #[expect(
+            clippy::cast_precision_loss,
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss
+        )]
+        let step = remap_clamp(pointer.x, track.x_range(), 0.0..=last as f32).round() as usize;
+        if step != vm.shown {
+            action_queue.request(UiAction::ReviewSolveStep(step).into());
+        }
+    }
+}
+
+fn step_text(vm: &ReviewViewModel) -> String {
+    let position = tr_args(
+        Text::ReviewStep,
+        &[("step", &vm.shown), ("total", &vm.steps.len())],
+    );
+    let Some(step) = vm.shown.checked_sub(1).map(|index| &vm.steps[index]) else {
+        return position;
+    };
+    let kind = match step.kind {
+        SolveStepKind::Move => None,
+        SolveStepKind::Mistake => Some(Text::ReviewMistake),
+        SolveStepKind::Hint => Some(Text::ReviewHint),
+        SolveStepKind::Undo => Some(Text::Undo),
+        SolveStepKind::Redo => Some(Text::Redo),
+    };
+    let mut text = format!("{position} · {}", clock_text(step.at));
+    if let Some(kind) = kind {
+        text.push_str(" · ");
+        text.push_str(tr(kind));
+    }
+    text
+}
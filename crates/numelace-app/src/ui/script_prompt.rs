@@ -0,0 +1,52 @@
+use eframe::egui::{Align2, Area, Context, Frame, Id, Order, vec2};
+
+use crate::{
+    action::{ActionRequestQueue, ScriptPrompt, ScriptResult, UiAction},
+    i18n::{Text, tr, tr_args},
+    ui::icon,
+};
+
+const MARGIN: f32 = 12.0;
+const MAX_WIDTH: f32 = 360.0;
+
+/// Shows the prompt of a scripted flow at the top of the window.
+///
+/// The prompt is a non-modal overlay, so the board and toolbar below stay
+/// usable for the goal it asks for.
+pub(crate) fn show(ctx: &Context, prompt: &ScriptPrompt, action_queue: &mut ActionRequestQueue) {
+    Area::new(Id::new("script_prompt"))
+        .order(Order::Foreground)
+        .anchor(Align2::CENTER_TOP, vec2(0.0, MARGIN))
+        .show(ctx, |ui| {
+            ui.set_max_width(MAX_WIDTH);
+            Frame::popup(ui.style()).show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(icon::GRADUATION);
+                    ui.weak(tr_args(
+                        Text::ScriptStepCount,
+                        &[("index", &(prompt.step + 1)), ("count", &prompt.step_count)],
+                    ));
+                });
+                ui.label(tr(prompt.text));
+                ui.horizontal(|ui| {
+                    if prompt.goal.is_none()
+                        && ui
+                            .button(format!(
+                                "{} {}",
+                                tr(Text::ScriptContinue),
+                                icon::ARROW_RIGHT
+                            ))
+                            .clicked()
+                    {
+                        action_queue
+                            .request(UiAction::RespondScriptPrompt(ScriptResult::Completed).into());
+                    }
+                    let last = prompt.step + 1 == prompt.step_count;
+                    if !last && ui.button(tr(Text::ScriptSkip)).clicked() {
+                        action_queue
+                            .request(UiAction::RespondScriptPrompt(ScriptResult::Skipped).into());
+                    }
+                });
+            });
+        });
+}
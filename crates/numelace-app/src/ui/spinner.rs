@@ -1,24 +1,41 @@
-use eframe::egui::{Context, Id, Modal, Spinner};
+use eframe::egui::{Context, Id, Modal, Spinner, Ui};
 
-use crate::action::SpinnerKind;
+use crate::{
+    action::SpinnerKind,
+    i18n::{Text, tr},
+    state::AnimationPolicy,
+    ui::{animation, icon},
+};
 
-pub(crate) fn show(ctx: &Context, spinner: SpinnerKind) {
-    ctx.request_repaint();
+pub(crate) fn show(ctx: &Context, spinner: SpinnerKind, policy: AnimationPolicy) {
+    animation::request_busy_repaint(ctx, policy);
     match spinner {
         SpinnerKind::NewGame => {
             Modal::new(Id::new("generating_new_game")).show(ctx, |ui| {
-                ui.heading("Generating...");
-                ui.add(Spinner::new());
-                ui.label("Generating new game...");
+                ui.heading(tr(Text::Generating));
+                show_indicator(ui, policy);
+                ui.label(tr(Text::GeneratingNewGame));
             });
         }
         SpinnerKind::CheckSolvability => {
             Modal::new(Id::new("checking_solvability")).show(ctx, |ui| {
-                ui.heading("Checking Solvability...");
-                ui.add(Spinner::new());
-                ui.label("Please wait while we analyze the current board.");
-                ui.label("This may take a few seconds.");
+                ui.heading(tr(Text::CheckingSolvability));
+                show_indicator(ui, policy);
+                ui.label(tr(Text::CheckingSolvabilityWait));
+                ui.label(tr(Text::CheckingSolvabilitySlow));
             });
         }
     }
 }
+
+/// Shows a turning spinner, or a still hourglass when motion is reduced.
+fn show_indicator(ui: &mut Ui, policy: AnimationPolicy) {
+    match policy {
+        AnimationPolicy::Full => {
+            ui.add(Spinner::new());
+        }
+        AnimationPolicy::Reduced => {
+            ui.label(icon::HOURGLASS);
+        }
+    }
+}
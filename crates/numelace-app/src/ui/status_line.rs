@@ -1,7 +1,14 @@
-use eframe::egui::{Align, Label, RichText, Ui, Vec2, Widget as _};
+use std::time::Duration;
+
+use eframe::egui::{Align, Label, Response, RichText, Ui, Vec2, Widget as _, accesskit::Live};
+use numelace_core::ChessRules;
 
 use crate::{
-    state::{HintStage, HintState},
+    i18n::{self, Text, tr, tr_args},
+    state::{
+        HintStage, HintState, PuzzleDifficulty, RaceStanding, RaceSummary, ReadOnlyReason,
+        TimedGame,
+    },
     ui::{
         icon,
         layout::{ComponentUnits, LayoutScale},
@@ -18,15 +25,120 @@ pub(crate) enum GameStatus<'a> {
 #[derive(Debug, Clone)]
 pub(crate) struct StatusLineViewModel<'a> {
     status: GameStatus<'a>,
+    chess: ChessRules,
+    has_conflict: bool,
+    difficulty: Option<PuzzleDifficulty>,
+    timed: Option<TimedGame>,
+    race: Option<RaceSummary>,
+    read_only: Option<ReadOnlyReason>,
 }
 
 impl<'a> StatusLineViewModel<'a> {
     #[must_use]
-    pub(crate) fn new(status: GameStatus<'a>) -> Self {
-        Self { status }
+    pub(crate) fn new(
+        status: GameStatus<'a>,
+        chess: ChessRules,
+        has_conflict: bool,
+        difficulty: Option<PuzzleDifficulty>,
+        timed: Option<TimedGame>,
+        race: Option<RaceSummary>,
+    ) -> Self {
+        Self {
+            status,
+            chess,
+            has_conflict,
+            difficulty,
+            timed,
+            race,
+            read_only: None,
+        }
+    }
+
+    /// Marks the game as read-only for `reason`.
+    #[must_use]
+    pub(crate) fn with_read_only(mut self, reason: Option<ReadOnlyReason>) -> Self {
+        self.read_only = reason;
+        self
     }
 }
 
+fn difficulty_text(difficulty: PuzzleDifficulty) -> String {
+    format!("{} {}", icon::CHART_UP, i18n::puzzle_difficulty(difficulty))
+}
+
+/// Formats a play time as `m:ss`, or `h:mm:ss` from one hour on.
+#[must_use]
+pub(crate) fn clock_text(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    let (hours, minutes, secs) = (secs / 3600, secs / 60 % 60, secs % 60);
+    if hours > 0 {
+        format!("{hours}:{minutes:02}:{secs:02}")
+    } else {
+        format!("{minutes}:{secs:02}")
+    }
+}
+
+fn timer_text(timed: TimedGame) -> String {
+    let elapsed = clock_text(timed.elapsed);
+    match timed.par {
+        Some(par) => format!("{} {elapsed} / {}", icon::STOPWATCH, clock_text(par)),
+        None => format!("{} {elapsed}", icon::STOPWATCH),
+    }
+}
+
+fn race_standing_text(standing: &RaceStanding) -> String {
+    format!(
+        "{}: {} ({}%)",
+        i18n::player(standing.player),
+        clock_text(standing.clock.elapsed),
+        standing.progress.percent(),
+    )
+}
+
+fn rules_text(chess: ChessRules) -> Option<String> {
+    let rules = [
+        chess.anti_knight().then(|| tr(Text::AntiKnight)),
+        chess.anti_king().then(|| tr(Text::AntiKing)),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>();
+    (!rules.is_empty()).then(|| rules.join(" / "))
+}
+
+/// Marks `response` as a live region so screen readers announce its changes.
+fn announce_changes(ui: &Ui, response: &Response) {
+    ui.ctx()
+        .accesskit_node_builder(response.id, |node| node.set_live(Live::Polite));
+}
+
+fn show_race_standings(ui: &mut Ui, race: &RaceSummary, cell_size: f32) {
+    for standing in &race.standings {
+        let mut text = RichText::new(race_standing_text(standing)).size(cell_size * 0.4);
+        text = if standing.player == race.current {
+            text.strong()
+        } else {
+            text.color(ui.visuals().weak_text_color())
+        };
+        Label::new(text)
+            .ui(ui)
+            .on_hover_text(tr(Text::RaceStatusTooltip));
+    }
+}
+
+fn show_read_only(ui: &mut Ui, reason: ReadOnlyReason, cell_size: f32) {
+    Label::new(
+        RichText::new(format!("{} {}", icon::EYE, tr(Text::ReadOnly)))
+            .color(ui.visuals().weak_text_color())
+            .size(cell_size * 0.4),
+    )
+    .ui(ui)
+    .on_hover_text(tr(match reason {
+        ReadOnlyReason::Replay => Text::ReadOnlyReplayTooltip,
+        ReadOnlyReason::Review => Text::ReadOnlyReviewTooltip,
+    }));
+}
+
 #[must_use]
 pub(crate) fn required_units() -> ComponentUnits {
     ComponentUnits::new(0.0, 0.5)
@@ -36,58 +148,114 @@ pub(crate) fn show(ui: &mut Ui, vm: &StatusLineViewModel, scale: &LayoutScale) {
     let cell_size = scale.cell_size;
     ui.spacing_mut().item_spacing = Vec2::new(scale.spacing.x, 0.0);
     ui.horizontal(|ui| {
+        let mut description = None;
         let (status_text, status_color) = match vm.status {
             GameStatus::InProgress => (
-                format!("{} Game in progress...", icon::HOURGLASS),
+                format!("{} {}", icon::HOURGLASS, tr(Text::StatusInProgress)),
                 ui.visuals().text_color(),
             ),
             GameStatus::Solved => (
-                format!("{} Solved! Congratulations!", icon::TROPHY),
+                format!("{} {}", icon::TROPHY, tr(Text::StatusSolved)),
                 ui.visuals().warn_fg_color,
             ),
             GameStatus::Hint(hint) => {
-                let technique = hint.step.technique_name();
+                let technique =
+                    i18n::technique_name(hint.step.technique_key(), hint.step.technique_name());
+                // Naming the technique is what stage 2 reveals, so keep earlier stages vague.
+                if hint.stage >= HintStage::Stage2 {
+                    description =
+                        i18n::technique(hint.step.technique_key()).map(|text| text.description);
+                }
+                let (stage_icon, stage_text) = match hint.stage {
+                    HintStage::Stage0 => (icon::SEARCH_RIGHT, tr(Text::HintLookHere).to_owned()),
+                    HintStage::Stage1 => (icon::FOUR_CORNERS, tr(Text::HintFocusArea).to_owned()),
+                    HintStage::Stage2 => (
+                        icon::EXCLAMATION_MARK,
+                        tr_args(Text::HintApplicable, &[("technique", &technique)]),
+                    ),
+                    HintStage::Stage3Preview => (
+                        icon::EYE,
+                        tr_args(Text::HintPreviewing, &[("technique", &technique)]),
+                    ),
+                    HintStage::Stage3Apply => (
+                        icon::CHECK,
+                        tr_args(Text::HintApplied, &[("technique", &technique)]),
+                    ),
+                };
                 (
-                    match hint.stage {
-                        HintStage::Stage1 => {
-                            format!(
-                                "{} Hint: {} Focus on the highlighted area",
-                                icon::LIGHTBULB,
-                                icon::FOUR_CORNERS,
-                            )
-                        }
-                        HintStage::Stage2 => {
-                            format!(
-                                "{} Hint: {} {technique} is applicable here",
-                                icon::LIGHTBULB,
-                                icon::EXCLAMATION_MARK,
-                            )
-                        }
-                        HintStage::Stage3Preview => {
-                            format!(
-                                "{} Hint: {} Previewing {technique} changes",
-                                icon::LIGHTBULB,
-                                icon::EYE,
-                            )
-                        }
-                        HintStage::Stage3Apply => {
-                            format!(
-                                "{} Hint: {} Applied {technique}",
-                                icon::LIGHTBULB,
-                                icon::CHECK,
-                            )
-                        }
-                    },
+                    format!(
+                        "{} {} {stage_icon} {stage_text}",
+                        icon::LIGHTBULB,
+                        tr(Text::StatusHint),
+                    ),
                     ui.visuals().warn_fg_color,
                 )
             }
         };
-        Label::new(
+        if let Some(difficulty) = vm.difficulty {
+            Label::new(
+                RichText::new(difficulty_text(difficulty))
+                    .color(ui.visuals().weak_text_color())
+                    .size(cell_size * 0.4),
+            )
+            .ui(ui)
+            .on_hover_text(tr(Text::Difficulty));
+        }
+        if let Some(timed) = vm.timed {
+            Label::new(
+                RichText::new(timer_text(timed))
+                    .color(ui.visuals().weak_text_color())
+                    .size(cell_size * 0.4),
+            )
+            .ui(ui)
+            .on_hover_text(tr(Text::PlayTimeTooltip));
+        }
+        if let Some(race) = &vm.race {
+            show_race_standings(ui, race, cell_size);
+        }
+        if let Some(rules) = rules_text(vm.chess) {
+            Label::new(
+                RichText::new(rules)
+                    .color(ui.visuals().weak_text_color())
+                    .size(cell_size * 0.4),
+            )
+            .ui(ui);
+        }
+        if let Some(reason) = vm.read_only {
+            show_read_only(ui, reason, cell_size);
+        }
+        if vm.has_conflict && !matches!(vm.status, GameStatus::Solved) {
+            let response = Label::new(
+                RichText::new(format!("{} {}", icon::WARNING, tr(Text::StatusConflict)))
+                    .color(ui.visuals().error_fg_color)
+                    .size(cell_size * 0.4),
+            )
+            .ui(ui);
+            announce_changes(ui, &response);
+        }
+        let response = Label::new(
             RichText::new(status_text)
                 .color(status_color)
                 .size(cell_size * 0.4),
         )
         .halign(Align::Max)
         .ui(ui);
+        announce_changes(ui, &response);
+        if let Some(description) = description {
+            response.on_hover_text(description);
+        }
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::clock_text;
+
+    #[test]
+    fn clock_text_shows_hours_only_when_needed() {
+        assert_eq!(clock_text(Duration::from_millis(65_900)), "1:05");
+        assert_eq!(clock_text(Duration::from_secs(3_725)), "1:02:05");
+    }
+}
@@ -1,12 +1,11 @@
 use eframe::egui::{Button, Id, Popup, Response, RichText, ThemePreference, Ui, Vec2};
-use numelace_game::{InputBlockReason, InputOperation};
 
 use crate::{
-    action::{
-        ActionRequestQueue, BoardMutationAction, FlowAction, HistoryAction, ModalRequest,
-        NotesFillScope, UiAction,
-    },
+    action::{ActionRequestQueue, AppAction, UiAction},
+    i18n::{self, Text, tr, tr_args},
+    state::{CommandId, KeyBindings, RaceSummary, ScratchTool},
     ui::{
+        command::CommandContext,
         icon,
         layout::{ComponentUnits, LayoutScale},
     },
@@ -14,24 +13,39 @@ use crate::{
 
 #[derive(Debug, Clone)]
 pub(crate) struct ToolbarViewModel {
-    can_undo: bool,
-    can_redo: bool,
-    selected_cell_auto_fill_capability: Option<Result<InputOperation, InputBlockReason>>,
+    commands: CommandContext,
+    candidate_heatmap: bool,
+    race: Option<RaceSummary>,
+    scratch_tool: Option<ScratchTool>,
+    info_panel: bool,
+    key_bindings: KeyBindings,
 }
 
 impl ToolbarViewModel {
     #[must_use]
     pub(crate) fn new(
-        can_undo: bool,
-        can_redo: bool,
-        selected_cell_auto_fill_capability: Option<Result<InputOperation, InputBlockReason>>,
+        commands: CommandContext,
+        candidate_heatmap: bool,
+        race: Option<RaceSummary>,
+        scratch_tool: Option<ScratchTool>,
+        key_bindings: KeyBindings,
     ) -> Self {
         Self {
-            can_undo,
-            can_redo,
-            selected_cell_auto_fill_capability,
+            commands,
+            candidate_heatmap,
+            race,
+            scratch_tool,
+            info_panel: false,
+            key_bindings,
         }
     }
+
+    /// Marks the game info sidebar as shown.
+    #[must_use]
+    pub(crate) fn with_info_panel(mut self, info_panel: bool) -> Self {
+        self.info_panel = info_panel;
+        self
+    }
 }
 
 #[must_use]
@@ -48,55 +62,37 @@ pub(crate) fn show(
     let cell_size = scale.cell_size;
     ui.spacing_mut().item_spacing = Vec2::new(scale.spacing.x, 0.0);
     ui.horizontal(|ui| {
-        if button(ui, icon::ARROW_UNDO, "Undo", vm.can_undo, cell_size).clicked() {
-            action_queue.request(HistoryAction::Undo.into());
+        for id in [
+            CommandId::Undo,
+            CommandId::Redo,
+            CommandId::CheckSolvability,
+            CommandId::Hint,
+        ] {
+            command_button(ui, vm, id, cell_size, action_queue);
         }
 
-        if button(ui, icon::ARROW_REDO, "Redo", vm.can_redo, cell_size).clicked() {
-            action_queue.request(HistoryAction::Redo.into());
-        }
-
-        if button(
-            ui,
-            icon::SEARCH_RIGHT,
-            "Check whether the current board still has a solution.",
-            true,
-            cell_size,
-        )
-        .clicked()
-        {
-            action_queue.request(FlowAction::CheckSolvability.into());
-        }
-
-        if button(
-            ui,
-            icon::LIGHTBULB,
-            "Get a hint (stage 1).",
-            true,
-            cell_size,
-        )
-        .clicked()
-        {
-            action_queue.request(FlowAction::Hint.into());
+        if let Some(race) = vm.race {
+            let next = race.current.other();
+            let tooltip = tr_args(Text::PassTurnTooltip, &[("player", &i18n::player(next))]);
+            let can_pass = !race
+                .standings
+                .iter()
+                .any(|standing| standing.player == next && standing.clock.finished);
+            if button(ui, icon::USERS, &tooltip, can_pass, cell_size).clicked() {
+                action_queue.request(AppAction::PassTurn.into());
+            }
         }
 
         ui.separator();
 
-        if button(ui, icon::PLUS, "New Game", true, cell_size).clicked() {
-            action_queue.request(FlowAction::StartNewGame.into());
-        }
-
-        if button(ui, icon::ROTATE_CCW, "Reset Inputs", true, cell_size).clicked() {
-            action_queue.request(FlowAction::ResetInputs.into());
-        }
-
-        if button(ui, icon::GEAR_NO_HUB, "Settings", true, cell_size).clicked() {
-            action_queue.request(UiAction::OpenModal(ModalRequest::Settings).into());
-        }
+        command_button(ui, vm, CommandId::NewGame, cell_size, action_queue)
+            .on_disabled_hover_text(tr(Text::PreparingGenerator));
+        command_button(ui, vm, CommandId::ResetInputs, cell_size, action_queue);
+        command_button(ui, vm, CommandId::Settings, cell_size, action_queue);
 
         ui.separator();
 
-        let response = button(ui, icon::MENU, "More", true, cell_size);
+        let response = button(ui, icon::MENU, tr(Text::More), true, cell_size);
         Popup::menu(&response)
             .id(Id::new("toolbar_more_menu"))
             .show(|ui| show_menu(ui, vm, cell_size, action_queue));
@@ -109,85 +105,210 @@ fn show_menu(
     cell_size: f32,
     action_queue: &mut ActionRequestQueue,
 ) {
-    if menu_button(
-        ui,
-        &format!("{} Auto-fill notes (all cells)", icon::LETTER_UPPER_A),
-        "Automatically fill in notes for all cells based on the current board state.",
-        true,
-        cell_size,
-    )
-    .clicked()
+    for id in [
+        CommandId::AutoFillAllCells,
+        CommandId::AutoFillEmptyCells,
+        CommandId::AutoFillSelectedCell,
+        CommandId::ClearMistakes,
+        CommandId::CheckProgress,
+        CommandId::AuditNotes,
+        CommandId::RevealCell,
+        CommandId::RevealAll,
+    ] {
+        command_menu_item(ui, vm, id, cell_size, action_queue);
+    }
+    let heatmap = CommandId::ToggleCandidateHeatmap.command();
+    let mut candidate_heatmap = vm.candidate_heatmap;
+    if ui
+        .toggle_value(
+            &mut candidate_heatmap,
+            menu_text(&heatmap.menu_label(), cell_size),
+        )
+        .on_hover_text(heatmap.hover_text(ui.ctx(), &vm.key_bindings))
+        .clicked()
     {
-        action_queue.request(
-            BoardMutationAction::AutoFillNotes {
-                scope: NotesFillScope::AllCells,
-            }
-            .into(),
-        );
+        action_queue.request(heatmap.id.action());
     }
-    if menu_button(
-        ui,
-        &format!("{} Auto-fill notes (empty cells)", icon::LETTER_UPPER_A),
-        "Automatically fill in notes for empty cells based on the current board state",
-        true,
-        cell_size,
-    )
-    .clicked()
+
+    ui.separator();
+
+    // Looking back on play: game info, statistics, race standings, and the
+    // campaign.
+    let info_panel = CommandId::ToggleInfoPanel.command();
+    let mut shown = vm.info_panel;
+    if ui
+        .toggle_value(&mut shown, menu_text(&info_panel.menu_label(), cell_size))
+        .on_hover_text(info_panel.hover_text(ui.ctx(), &vm.key_bindings))
+        .clicked()
     {
-        action_queue.request(
-            BoardMutationAction::AutoFillNotes {
-                scope: NotesFillScope::EmptyCells,
-            }
-            .into(),
-        );
+        action_queue.request(info_panel.id.action());
     }
-    if menu_button(
+    command_menu_item(ui, vm, CommandId::Statistics, cell_size, action_queue);
+    if vm.race.is_some() {
+        command_menu_item(ui, vm, CommandId::RaceStandings, cell_size, action_queue);
+    }
+    command_menu_item(ui, vm, CommandId::Campaign, cell_size, action_queue);
+    command_menu_item(ui, vm, CommandId::Learn, cell_size, action_queue);
+    show_scratchpad_menu(ui, vm, cell_size, action_queue);
+    show_submenu(
         ui,
-        &format!("{} Auto-fill notes (selected cell)", icon::LETTER_A),
-        "Automatically fill in notes for selected cell based on the current board state",
-        vm.selected_cell_auto_fill_capability
-            .is_some_and(|res| res.is_ok_and(|op| op.is_set())),
+        vm,
+        &format!("{} {}", icon::PRINTER, tr(Text::Export)),
+        &[CommandId::ExportPuzzle, CommandId::ExportProgress],
         cell_size,
-    )
-    .clicked()
-    {
-        action_queue.request(
-            BoardMutationAction::AutoFillNotes {
-                scope: NotesFillScope::SelectedCell,
-            }
-            .into(),
-        );
-    }
+        action_queue,
+    );
+    show_submenu(
+        ui,
+        vm,
+        &format!("{} {}", icon::LINK, tr(Text::Share)),
+        &[
+            CommandId::CopyPuzzleLink,
+            CommandId::CopyProgressLink,
+            CommandId::CopyBoardImage,
+        ],
+        cell_size,
+        action_queue,
+    );
+    show_appearance_menu(ui, cell_size);
 
     ui.separator();
 
+    // Reproducing a puzzle: generating it again from its seed, and reporting it.
+    command_menu_item(ui, vm, CommandId::RegeneratePuzzle, cell_size, action_queue);
+    command_menu_item(ui, vm, CommandId::BugReport, cell_size, action_queue);
+}
+
+fn show_appearance_menu(ui: &mut Ui, cell_size: f32) {
     ui.menu_button(
-        menu_text(&format!("{} Appearance", icon::PALETTE), cell_size),
+        menu_text(
+            &format!("{} {}", icon::PALETTE, tr(Text::Appearance)),
+            cell_size,
+        ),
         |ui| {
             let mut theme_preference = ui.ctx().options(|opt| opt.theme_preference);
             ui.radio_value(
                 &mut theme_preference,
                 ThemePreference::System,
-                menu_text(&format!("{} System", icon::LAPTOP), cell_size),
+                menu_text(
+                    &format!("{} {}", icon::LAPTOP, tr(Text::ThemeSystem)),
+                    cell_size,
+                ),
             )
-            .on_hover_text("Follow the system theme preference.");
+            .on_hover_text(tr(Text::ThemeSystemTooltip));
             ui.radio_value(
                 &mut theme_preference,
                 ThemePreference::Dark,
-                menu_text("🌙 Dark", cell_size),
+                menu_text(&format!("🌙 {}", tr(Text::ThemeDark)), cell_size),
             )
-            .on_hover_text("Use dark mode theme");
+            .on_hover_text(tr(Text::ThemeDarkTooltip));
             ui.radio_value(
                 &mut theme_preference,
                 ThemePreference::Light,
-                menu_text(&format!("{} Light", icon::SUN), cell_size),
+                menu_text(
+                    &format!("{} {}", icon::SUN, tr(Text::ThemeLight)),
+                    cell_size,
+                ),
             )
-            .on_hover_text("Use light mode theme");
+            .on_hover_text(tr(Text::ThemeLightTooltip));
             ui.ctx().set_theme(theme_preference);
         },
     );
 }
 
+fn show_scratchpad_menu(
+    ui: &mut Ui,
+    vm: &ToolbarViewModel,
+    cell_size: f32,
+    action_queue: &mut ActionRequestQueue,
+) {
+    ui.menu_button(
+        menu_text(
+            &format!("{} {}", icon::PENCIL, tr(Text::Scratchpad)),
+            cell_size,
+        ),
+        |ui| {
+            for (tool, label, tooltip) in [
+                (ScratchTool::Pen, Text::ScratchPen, Text::ScratchPenTooltip),
+                (
+                    ScratchTool::Line,
+                    Text::ScratchLine,
+                    Text::ScratchLineTooltip,
+                ),
+            ] {
+                let mut selected = vm.scratch_tool == Some(tool);
+                if ui
+                    .toggle_value(&mut selected, menu_text(tr(label), cell_size))
+                    .on_hover_text(tr(tooltip))
+                    .clicked()
+                {
+                    action_queue.request(UiAction::ToggleScratchTool(tool).into());
+                }
+            }
+            command_menu_item(ui, vm, CommandId::ClearScratchpad, cell_size, action_queue);
+        },
+    );
+}
+
+fn show_submenu(
+    ui: &mut Ui,
+    vm: &ToolbarViewModel,
+    label: &str,
+    ids: &[CommandId],
+    cell_size: f32,
+    action_queue: &mut ActionRequestQueue,
+) {
+    ui.menu_button(menu_text(label, cell_size), |ui| {
+        for &id in ids {
+            command_menu_item(ui, vm, id, cell_size, action_queue);
+        }
+    });
+}
+
+/// Shows the icon button of a command and requests its action when clicked.
+fn command_button(
+    ui: &mut Ui,
+    vm: &ToolbarViewModel,
+    id: CommandId,
+    cell_size: f32,
+    action_queue: &mut ActionRequestQueue,
+) -> Response {
+    let command = id.command();
+    let response = button(
+        ui,
+        command.icon.unwrap_or_default(),
+        &command.hover_text(ui.ctx(), &vm.key_bindings),
+        command.is_available(vm.commands),
+        cell_size,
+    );
+    if response.clicked() {
+        action_queue.request(id.action());
+    }
+    response
+}
+
+/// Shows the menu item of a command and requests its action when clicked.
+fn command_menu_item(
+    ui: &mut Ui,
+    vm: &ToolbarViewModel,
+    id: CommandId,
+    cell_size: f32,
+    action_queue: &mut ActionRequestQueue,
+) {
+    let command = id.command();
+    if menu_button(
+        ui,
+        &command.menu_label(),
+        &command.hover_text(ui.ctx(), &vm.key_bindings),
+        command.is_available(vm.commands),
+        cell_size,
+    )
+    .clicked()
+    {
+        action_queue.request(id.action());
+    }
+}
+
 fn button(ui: &mut Ui, label: &str, hover_text: &str, enabled: bool, cell_size: f32) -> Response {
     let text_size = cell_size * 0.8;
     ui.add_enabled(
@@ -96,7 +96,6 @@ impl<T> UndoRedoStack<T> {
         self.stack.get(self.cursor)
     }
 
-    #[must_use]
     pub(crate) fn iter_from_current(&self) -> impl DoubleEndedIterator<Item = &T> {
         self.stack.iter().take(self.cursor + 1).rev()
     }
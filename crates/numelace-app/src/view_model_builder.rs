@@ -1,38 +1,86 @@
 use numelace_core::{
-    Digit, DigitIndexedArray, DigitPositions, DigitSet, Position, PositionIndexedArray,
+    CageSet, CageViolation, ChessRules, Digit, DigitIndexedArray, DigitPositions, DigitSet,
+    EdgeConstraintSet, Position, PositionIndexedArray, RegionLayout,
 };
-use numelace_game::CellState;
-use numelace_solver::TechniqueApplication;
+use numelace_game::{CellState, Game, InputBlockReason, InputOperation};
+use numelace_solver::{TechniqueApplication, TechniqueLink, technique};
+use web_time::Instant;
 
 use crate::{
-    state::{AppState, GhostType, HintStage, HintState, Settings, UiState},
+    i18n::{Text, tr, tr_args},
+    state::{
+        AppState, BivalueSource, GhostType, HintHeatMap, HintStage, HintState, LearnState,
+        Settings, SolveStep, SolveStepKind, UiState,
+    },
     ui::{
+        campaign_screen::CampaignScreenViewModel,
+        command::CommandContext,
         game_screen::GameScreenViewModel,
         grid::{GridCell, GridViewModel, GridVisualState, NoteVisualState},
+        info_panel::InfoPanelViewModel,
         input::InputContext,
         keypad::{DigitKeyState, KeypadViewModel},
-        modal::{NewGameOptionsViewModel, SettingsViewModel},
+        learn_screen::LearnScreenViewModel,
+        modal::{
+            ModalViewModels, NewGameOptionsViewModel, RaceResultViewModel, SettingsViewModel,
+            SolvedResultViewModel, StatisticsViewModel,
+        },
+        review::ReviewViewModel,
         status_line::{GameStatus, StatusLineViewModel},
         toolbar::ToolbarViewModel,
     },
+    worker::Readiness,
 };
 
 #[must_use]
-pub(crate) fn build_toolbar_vm(app_state: &AppState, _ui_state: &UiState) -> ToolbarViewModel {
-    let auto_fill_capability = app_state
-        .selected_cell()
-        .map(|pos| app_state.game.auto_fill_cell_notes_capability(pos));
+/// Collects the state that decides which commands are available.
+pub(crate) fn build_command_context(app_state: &AppState, ui_state: &UiState) -> CommandContext {
+    let game = &app_state.game;
+    let selected_cell = app_state.selected_cell();
+    let is_set = |capability: Result<InputOperation, InputBlockReason>| {
+        capability.is_ok_and(|op| op.is_set())
+    };
+    let selected_cells = app_state.selected_cells();
+    CommandContext {
+        can_undo: app_state.can_undo(),
+        can_redo: app_state.can_redo(),
+        has_removable_input: selected_cells
+            .into_iter()
+            .any(|pos| game.has_removable_input(pos)),
+        can_auto_fill_selected_cell: selected_cells
+            .into_iter()
+            .any(|pos| is_set(game.auto_fill_cell_notes_capability(pos))),
+        can_reveal_selected_cell: selected_cell
+            .is_some_and(|pos| is_set(game.reveal_cell_capability(pos))),
+        has_scratch_strokes: !ui_state.scratchpad.strokes().is_empty(),
+        worker_ready: ui_state.worker_readiness.is_ready(),
+        can_regenerate: app_state.generated_with.is_some(),
+    }
+}
+
+pub(crate) fn build_toolbar_vm(
+    app_state: &AppState,
+    ui_state: &UiState,
+    commands: CommandContext,
+) -> ToolbarViewModel {
     ToolbarViewModel::new(
-        app_state.can_undo(),
-        app_state.can_redo(),
-        auto_fill_capability,
+        commands,
+        app_state.settings.assist.candidate_heatmap,
+        app_state.race_summary(),
+        ui_state.scratchpad.tool,
+        app_state.settings.input.key_bindings.clone(),
     )
+    .with_info_panel(ui_state.show_info_panel)
 }
 
-fn fill_notes_for_empty_cell(grid: &mut PositionIndexedArray<GridCell>, pos: Position) -> DigitSet {
+fn fill_notes_for_empty_cell(
+    grid: &mut PositionIndexedArray<GridCell>,
+    regions: &RegionLayout,
+    pos: Position,
+) -> DigitSet {
     assert!(grid[pos].content.is_empty());
     let mut notes = DigitSet::FULL;
-    for peer_pos in pos.house_peers() {
+    for peer_pos in regions.house_peers(pos) {
         if let Some(digit) = grid[peer_pos].content.as_digit() {
             notes.remove(digit);
         }
@@ -62,8 +110,8 @@ fn apply_conflict_ghost(
 
 fn effective_hint_applications(
     grid: &PositionIndexedArray<GridCell>,
+    regions: &RegionLayout,
     hint_state: &HintState,
-    settings: &Settings,
 ) -> Vec<TechniqueApplication> {
     let mut apps = Vec::new();
 
@@ -73,18 +121,6 @@ fn effective_hint_applications(
                 if grid[position].content.as_digit() != Some(digit) {
                     apps.push(TechniqueApplication::Placement { position, digit });
                 }
-                if settings.assist.notes.auto_remove_peer_notes_on_fill {
-                    for peer_pos in position.house_peers() {
-                        if let Some(notes) = grid[peer_pos].content.as_notes()
-                            && notes.contains(digit)
-                        {
-                            apps.push(TechniqueApplication::CandidateElimination {
-                                positions: DigitPositions::from_elem(peer_pos),
-                                digits: DigitSet::from_elem(digit),
-                            });
-                        }
-                    }
-                }
             }
             TechniqueApplication::CandidateElimination { positions, digits } => {
                 let mut by_digits: DigitIndexedArray<DigitPositions> =
@@ -92,7 +128,7 @@ fn effective_hint_applications(
                 for pos in positions {
                     let notes = if grid[pos].content.is_empty() {
                         let mut notes = DigitSet::FULL;
-                        for peer_pos in pos.house_peers() {
+                        for peer_pos in regions.house_peers(pos) {
                             if let Some(digit) = grid[peer_pos].content.as_digit() {
                                 notes.remove(digit);
                             }
@@ -124,25 +160,81 @@ fn effective_hint_applications(
     apps
 }
 
+/// Returns the user notes that note cleanup removes when the hint's placements
+/// are applied.
+///
+/// Must run before [`apply_hint_ghost`], which shows the placed digits and so
+/// hides whether a placement changes the cell.
+fn hint_note_cleanup(
+    grid: &PositionIndexedArray<GridCell>,
+    regions: &RegionLayout,
+    hint_state: &HintState,
+    settings: &Settings,
+) -> Vec<(Position, Digit)> {
+    let mut removals = Vec::new();
+    if hint_state.stage < HintStage::Stage3Preview
+        || hint_state.stage >= HintStage::Stage3Apply
+        || !settings.assist.notes.auto_remove_peer_notes_on_fill
+    {
+        return removals;
+    }
+
+    for app in hint_state.step.application() {
+        let TechniqueApplication::Placement { position, digit } = app else {
+            continue;
+        };
+        if grid[position].content.as_digit() == Some(digit) {
+            continue;
+        }
+        for peer_pos in regions.house_peers(position) {
+            if grid[peer_pos]
+                .content
+                .as_notes()
+                .is_some_and(|notes| notes.contains(digit))
+            {
+                removals.push((peer_pos, digit));
+            }
+        }
+    }
+    removals
+}
+
+/// Marks the notes removed by note cleanup, unless the hint itself already
+/// eliminates them.
+fn apply_note_cleanup_visuals(
+    grid: &mut PositionIndexedArray<GridCell>,
+    removals: &[(Position, Digit)],
+) {
+    for &(pos, digit) in removals {
+        let note_visual_state = &mut grid[pos].note_visual_state;
+        if !note_visual_state
+            .hint_application_elimination
+            .contains(digit)
+        {
+            note_visual_state.note_cleanup.insert(digit);
+        }
+    }
+}
+
 fn apply_hint_ghost(
     grid: &mut PositionIndexedArray<GridCell>,
+    regions: &RegionLayout,
     hint_state: &HintState,
-    settings: &Settings,
 ) {
-    if hint_state.stage >= HintStage::Stage3Apply {
+    if hint_state.stage == HintStage::Stage0 || hint_state.stage >= HintStage::Stage3Apply {
         return;
     }
 
     for pos in hint_state.step.condition_positions() {
         if grid[pos].content.is_empty() {
-            let notes = fill_notes_for_empty_cell(grid, pos);
+            let notes = fill_notes_for_empty_cell(grid, regions, pos);
             grid[pos].note_visual_state.ghost |= notes;
             grid[pos].note_visual_state.hint_condition_temporary |= notes;
         }
     }
 
     if hint_state.stage >= HintStage::Stage3Preview {
-        for app in effective_hint_applications(grid, hint_state, settings) {
+        for app in effective_hint_applications(grid, regions, hint_state) {
             match app {
                 TechniqueApplication::Placement { position, digit } => {
                     if grid[position].content.as_digit() != Some(digit) {
@@ -154,7 +246,7 @@ fn apply_hint_ghost(
                 TechniqueApplication::CandidateElimination { positions, digits } => {
                     for pos in positions {
                         if grid[pos].content.is_empty() {
-                            let notes = fill_notes_for_empty_cell(grid, pos);
+                            let notes = fill_notes_for_empty_cell(grid, regions, pos);
                             grid[pos].note_visual_state.ghost |= notes;
                             grid[pos].note_visual_state.hint_application_temporary |= notes;
                         }
@@ -180,13 +272,20 @@ fn apply_hint_ghost(
 
 fn apply_hint_visuals(
     grid: &mut PositionIndexedArray<GridCell>,
+    regions: &RegionLayout,
     hint_state: &HintState,
-    settings: &Settings,
 ) {
     if hint_state.stage >= HintStage::Stage3Apply {
         return;
     }
 
+    if hint_state.stage == HintStage::Stage0 {
+        for pos in hint_state.focus_area(regions) {
+            grid[pos].visual_state |= GridVisualState::HINT_CONDITION_CELL;
+        }
+        return;
+    }
+
     for pos in hint_state.step.condition_positions() {
         grid[pos].visual_state |= GridVisualState::HINT_CONDITION_CELL;
     }
@@ -214,7 +313,7 @@ fn apply_hint_visuals(
     }
 
     if hint_state.stage >= HintStage::Stage3Preview {
-        for app in effective_hint_applications(grid, hint_state, settings) {
+        for app in effective_hint_applications(grid, regions, hint_state) {
             match app {
                 TechniqueApplication::Placement { position, digit: _ } => {
                     grid[position].visual_state |= GridVisualState::HINT_APPLICATION_PLACEMENT;
@@ -235,44 +334,202 @@ fn apply_hint_visuals(
     }
 }
 
-fn apply_selection_highlights(grid: &mut PositionIndexedArray<GridCell>, pos: Position) {
+/// Returns the links of the hint to draw as arrows, from the stage that shows
+/// the candidates it relies on until it is applied.
+fn hint_links(hint_state: Option<&HintState>) -> Vec<TechniqueLink> {
+    hint_state
+        .filter(|hint_state| {
+            (HintStage::Stage2..HintStage::Stage3Apply).contains(&hint_state.stage)
+        })
+        .map(|hint_state| hint_state.step.links())
+        .unwrap_or_default()
+}
+
+/// Records the candidate count of every cell shown without a digit.
+fn apply_candidate_heatmap(grid: &mut PositionIndexedArray<GridCell>, game: &Game) {
+    for pos in Position::ALL {
+        if grid[pos].content.as_digit().is_none() {
+            let count = game.legal_digits_at(pos).len();
+            grid[pos].candidate_count = Some(u8::try_from(count).unwrap_or(u8::MAX));
+        }
+    }
+}
+
+/// Records the trial depth of every cell shown without a digit.
+///
+/// The depths come from the worker and may be of an earlier position while
+/// it analyzes the current one.
+fn apply_deduction_depths(
+    grid: &mut PositionIndexedArray<GridCell>,
+    depths: &PositionIndexedArray<u8>,
+) {
+    for pos in Position::ALL {
+        if grid[pos].content.as_digit().is_none() {
+            grid[pos].deduction_depth = Some(depths[pos]);
+        }
+    }
+}
+
+/// Marks cells without a digit that have exactly two candidates, taken from
+/// the rules or from the notes as `source` says.
+fn apply_bivalue_highlights(
+    grid: &mut PositionIndexedArray<GridCell>,
+    game: &Game,
+    source: BivalueSource,
+) {
+    for pos in Position::ALL {
+        let content = grid[pos].content;
+        if content.as_digit().is_some() {
+            continue;
+        }
+        let count = match source {
+            BivalueSource::Candidates => game.legal_digits_at(pos).len(),
+            BivalueSource::Notes => content.as_notes().map_or(0, DigitSet::len),
+        };
+        if count == 2 {
+            grid[pos].visual_state |= GridVisualState::BIVALUE;
+        }
+    }
+}
+
+/// Records the hint count of every cell a hint or reveal pointed at.
+fn apply_hint_heat_map(grid: &mut PositionIndexedArray<GridCell>, heat_map: &HintHeatMap) {
+    for pos in Position::ALL {
+        let count = heat_map.count(pos);
+        if count > 0 {
+            grid[pos].hint_count = Some(count);
+        }
+    }
+}
+
+fn apply_selection_highlights(
+    grid: &mut PositionIndexedArray<GridCell>,
+    regions: &RegionLayout,
+    pos: Position,
+) {
     grid[pos].visual_state |= GridVisualState::SELECTED_CELL;
-    for house_pos in pos.house_positions() {
+    for house_pos in regions.house_positions(pos) {
         grid[house_pos].visual_state |= GridVisualState::SELECTED_CELL_PEER;
     }
 }
 
-fn apply_conflict_highlights(grid: &mut PositionIndexedArray<GridCell>) {
+/// Returns the cells holding each digit as shown on the grid: the game's
+/// digits, with ghost digits taking the place of whatever the cell holds.
+fn shown_digit_positions(
+    grid: &PositionIndexedArray<GridCell>,
+    game: &Game,
+) -> DigitIndexedArray<DigitPositions> {
+    let mut positions = DigitIndexedArray::from_fn(|digit| game.digit_positions(digit));
     for pos in Position::ALL {
+        if !grid[pos].visual_state.contains(GridVisualState::GHOST) {
+            continue;
+        }
         let Some(digit) = grid[pos].content.as_digit() else {
             continue;
         };
+        for digit_positions in &mut positions {
+            digit_positions.remove(pos);
+        }
+        positions[digit].insert(pos);
+    }
+    positions
+}
 
-        for peer_pos in pos.house_peers() {
-            if grid[peer_pos].content.as_digit() == Some(digit) {
-                grid[peer_pos].visual_state |= GridVisualState::CONFLICT;
-            }
+fn apply_conflict_highlights(
+    grid: &mut PositionIndexedArray<GridCell>,
+    regions: &RegionLayout,
+    digit_positions: &DigitIndexedArray<DigitPositions>,
+) {
+    for &positions in digit_positions {
+        for pos in regions.duplicated_positions(positions) {
+            grid[pos].visual_state |= GridVisualState::CONFLICT;
+        }
+    }
 
-            if grid[peer_pos]
-                .content
-                .as_notes()
-                .is_some_and(|notes| notes.contains(digit))
-                && !grid[peer_pos]
+    for pos in Position::ALL {
+        let Some(notes) = grid[pos].content.as_notes() else {
+            continue;
+        };
+        let peers = regions.house_peers(pos);
+        for digit in notes {
+            if !(peers & digit_positions[digit]).is_empty()
+                && !grid[pos]
                     .note_visual_state
                     .hint_application_elimination
                     .contains(digit)
             {
-                grid[peer_pos].note_visual_state.conflict.insert(digit);
+                grid[pos].note_visual_state.conflict.insert(digit);
+            }
+        }
+    }
+}
+
+fn apply_note_audit_highlights(
+    grid: &mut PositionIndexedArray<GridCell>,
+    note_audit: &[(Position, DigitSet)],
+) {
+    for &(pos, digits) in note_audit {
+        if let Some(notes) = grid[pos].content.as_notes() {
+            grid[pos].note_visual_state.conflict |= notes & digits;
+        }
+    }
+}
+
+fn apply_edge_conflict_highlights(
+    grid: &mut PositionIndexedArray<GridCell>,
+    edges: &EdgeConstraintSet,
+) {
+    for edge in edges {
+        if edge.check_with(|pos| grid[pos].content.as_digit()).is_err() {
+            grid[edge.first()].visual_state |= GridVisualState::CONFLICT;
+            grid[edge.second()].visual_state |= GridVisualState::CONFLICT;
+        }
+    }
+}
+
+fn apply_chess_conflict_highlights(
+    grid: &mut PositionIndexedArray<GridCell>,
+    chess: ChessRules,
+    digit_positions: &DigitIndexedArray<DigitPositions>,
+) {
+    for &positions in digit_positions {
+        for pos in positions {
+            if !(chess.peers(pos) & positions).is_empty() {
+                grid[pos].visual_state |= GridVisualState::CONFLICT;
             }
         }
     }
 }
 
-fn apply_selected_digit_highlights(grid: &mut PositionIndexedArray<GridCell>, digit: Digit) {
+fn apply_cage_conflict_highlights(grid: &mut PositionIndexedArray<GridCell>, cages: &CageSet) {
+    for cage in cages {
+        let Err(violation) = cage.check_with(|pos| grid[pos].content.as_digit()) else {
+            continue;
+        };
+        for pos in cage.positions() {
+            let Some(digit) = grid[pos].content.as_digit() else {
+                continue;
+            };
+            let conflicting = match violation {
+                CageViolation::DuplicatedDigit { digit: duplicated } => digit == duplicated,
+                CageViolation::SumExceeded { .. } | CageViolation::SumMismatch { .. } => true,
+            };
+            if conflicting {
+                grid[pos].visual_state |= GridVisualState::CONFLICT;
+            }
+        }
+    }
+}
+
+fn apply_selected_digit_highlights(
+    grid: &mut PositionIndexedArray<GridCell>,
+    regions: &RegionLayout,
+    digit: Digit,
+) {
     for pos in Position::ALL {
         if grid[pos].content.as_digit() == Some(digit) {
             grid[pos].visual_state |= GridVisualState::SELECTED_DIGIT;
-            for house_pos in pos.house_positions() {
+            for house_pos in regions.house_positions(pos) {
                 grid[house_pos].visual_state |= GridVisualState::SELECTED_DIGIT_PEER;
             }
         }
@@ -287,39 +544,202 @@ fn apply_selected_digit_highlights(grid: &mut PositionIndexedArray<GridCell>, di
     }
 }
 
+fn apply_selected_digit_candidate_highlights(
+    grid: &mut PositionIndexedArray<GridCell>,
+    candidates: DigitPositions,
+) {
+    for pos in candidates {
+        if grid[pos].content.as_digit().is_none() {
+            grid[pos].visual_state |= GridVisualState::SELECTED_DIGIT_CANDIDATE;
+        }
+    }
+}
+
+/// Describes a cell for screen readers, e.g. "row 4 column 7, given 5".
+///
+/// Uses the actual cell content rather than any ghost shown in its place.
+fn cell_accessible_label(pos: Position, content: CellState, conflict: bool) -> String {
+    let mut parts = vec![tr_args(
+        Text::CellPosition,
+        &[("row", &(pos.row() + 1)), ("col", &(pos.col() + 1))],
+    )];
+    if let Some(digit) = content.as_digit() {
+        let text = if content.is_given() {
+            Text::CellGiven
+        } else {
+            Text::CellFilled
+        };
+        parts.push(tr_args(text, &[("digit", &digit)]));
+    } else {
+        parts.push(tr(Text::CellEmpty).to_owned());
+        if let Some(notes) = content.as_notes() {
+            let digits = notes
+                .iter()
+                .map(|digit| digit.to_string())
+                .collect::<Vec<_>>()
+                .join(" ");
+            parts.push(tr_args(Text::CellCandidates, &[("digits", &digits)]));
+        }
+    }
+    if conflict {
+        parts.push(tr(Text::CellConflict).to_owned());
+    }
+    parts.join(", ")
+}
+
 fn build_grid(app_state: &AppState, ui_state: &UiState) -> PositionIndexedArray<GridCell> {
+    let reviewed = reviewed_board(app_state, ui_state);
+    let game = reviewed.as_ref().map_or(&app_state.game, |(game, _)| game);
     let mut grid = PositionIndexedArray::from_fn(|pos| GridCell {
-        content: *app_state.game.cell(pos),
+        content: *game.cell(pos),
         visual_state: GridVisualState::empty(),
         note_visual_state: NoteVisualState::default(),
+        center_notes: game.center_notes(pos),
+        candidate_count: None,
+        hint_count: None,
+        deduction_depth: None,
+        accessible_label: String::new(),
     });
 
     if let Some((pos, ghost)) = ui_state.conflict_ghost {
         apply_conflict_ghost(&mut grid, pos, ghost);
     }
 
+    let regions = game.regions();
     if let Some(hint_state) = &ui_state.hint_state {
-        apply_hint_ghost(&mut grid, hint_state, &app_state.settings);
-        apply_hint_visuals(&mut grid, hint_state, &app_state.settings);
+        let cleanup = hint_note_cleanup(&grid, regions, hint_state, &app_state.settings);
+        apply_hint_ghost(&mut grid, regions, hint_state);
+        apply_hint_visuals(&mut grid, regions, hint_state);
+        apply_note_cleanup_visuals(&mut grid, &cleanup);
+    }
+
+    let digit_positions = shown_digit_positions(&grid, game);
+    apply_conflict_highlights(&mut grid, regions, &digit_positions);
+    if let Some(note_audit) = &ui_state.note_audit {
+        apply_note_audit_highlights(&mut grid, note_audit);
+    }
+    apply_cage_conflict_highlights(&mut grid, game.cages());
+    apply_edge_conflict_highlights(&mut grid, game.edge_constraints());
+    apply_chess_conflict_highlights(&mut grid, game.chess_rules(), &digit_positions);
+    if let Some((_, Some(step))) = &reviewed {
+        apply_review_highlights(&mut grid, game, step);
     }
 
-    apply_conflict_highlights(&mut grid);
+    if app_state.settings.assist.candidate_heatmap {
+        apply_candidate_heatmap(&mut grid, game);
+    }
+    if app_state.settings.assist.deduction_depth_overlay
+        && let Some(depths) = &ui_state.deduction_depths
+    {
+        apply_deduction_depths(&mut grid, depths);
+    }
+    let highlight = &app_state.settings.assist.highlight;
+    if highlight.bivalue_cell {
+        apply_bivalue_highlights(&mut grid, game, highlight.bivalue_source);
+    }
+    if game.is_solved() {
+        apply_hint_heat_map(&mut grid, &app_state.hint_heat_map);
+    }
 
     if let Some(pos) = app_state.selected_cell() {
-        apply_selection_highlights(&mut grid, pos);
+        apply_selection_highlights(&mut grid, regions, pos);
+    }
+    for pos in app_state.selected_cells() {
+        grid[pos].visual_state |= GridVisualState::SELECTED_CELL;
     }
     if let Some(digit) = app_state.selected_digit() {
-        apply_selected_digit_highlights(&mut grid, digit);
+        apply_selected_digit_highlights(&mut grid, regions, digit);
+        let candidates = game.to_candidate_grid().digit_positions(digit);
+        apply_selected_digit_candidate_highlights(&mut grid, candidates);
+    }
+
+    for pos in Position::ALL {
+        let conflict = grid[pos].visual_state.contains(GridVisualState::CONFLICT);
+        let mut label = cell_accessible_label(pos, *game.cell(pos), conflict);
+        if let Some(count) = grid[pos].hint_count {
+            label.push_str(", ");
+            label.push_str(&tr_args(Text::CellHinted, &[("count", &count)]));
+        }
+        grid[pos].accessible_label = label;
     }
 
     grid
 }
 
+/// Board shown instead of the game while the post-solve review steps through
+/// the solve log, with the step that led to it.
+fn reviewed_board<'a>(
+    app_state: &AppState,
+    ui_state: &'a UiState,
+) -> Option<(Game, Option<&'a SolveStep>)> {
+    let log = &ui_state.solve_log;
+    let step = log.reviewed()?;
+    let game = log.game_at(step, &app_state.game)?;
+    Some((game, step.checked_sub(1).map(|index| &log.steps()[index])))
+}
+
+/// Points at the cells the reviewed step changed, and marks a mistaken digit
+/// as a conflict.
+fn apply_review_highlights(
+    grid: &mut PositionIndexedArray<GridCell>,
+    game: &Game,
+    step: &SolveStep,
+) {
+    for pos in step.changed {
+        grid[pos].visual_state |= GridVisualState::HINT_CONDITION_CELL;
+        let filled = grid[pos].content.as_filled();
+        if step.kind == SolveStepKind::Mistake
+            && filled.is_some()
+            && filled != game.solution().get(pos)
+        {
+            grid[pos].visual_state |= GridVisualState::CONFLICT;
+        }
+    }
+}
+
+/// Grid cells kept across frames, rebuilt only when the state revisions change.
+///
+/// Building the grid checks conflicts and highlights for every cell, which
+/// dominates the per-frame cost while the app is idle.
+#[derive(Debug, Default)]
+pub(crate) struct GridCache {
+    entry: Option<CachedGrid>,
+}
+
+#[derive(Debug)]
+struct CachedGrid {
+    revisions: (u64, u64),
+    grid: PositionIndexedArray<GridCell>,
+    has_conflict: bool,
+}
+
+impl GridCache {
+    fn get_or_build(&mut self, app_state: &AppState, ui_state: &UiState) -> &CachedGrid {
+        let revisions = (app_state.revision(), ui_state.revision);
+        let entry = match self.entry.take() {
+            Some(entry) if entry.revisions == revisions => entry,
+            _ => {
+                let grid = build_grid(app_state, ui_state);
+                let has_conflict = grid
+                    .iter()
+                    .any(|cell| cell.visual_state.contains(GridVisualState::CONFLICT));
+                CachedGrid {
+                    revisions,
+                    grid,
+                    has_conflict,
+                }
+            }
+        };
+        self.entry.insert(entry)
+    }
+}
+
 #[must_use]
 pub(crate) fn build_game_screen_view_model<'a>(
     app_state: &AppState,
     ui_state: &'a UiState,
     input_context: &'a InputContext,
+    grid_cache: &'a mut GridCache,
 ) -> GameScreenViewModel<'a> {
     let game = &app_state.game;
     let selected_cell = app_state.selected_cell();
@@ -332,37 +752,191 @@ pub(crate) fn build_game_screen_view_model<'a>(
     } else {
         GameStatus::InProgress
     };
-    let status_line_vm = StatusLineViewModel::new(status);
-    let toolbar_vm = build_toolbar_vm(app_state, ui_state);
+    let commands = build_command_context(app_state, ui_state);
+    let toolbar_vm = build_toolbar_vm(app_state, ui_state, commands);
 
-    let grid = build_grid(app_state, ui_state);
-    let grid_vm = GridViewModel::new(grid, &settings.assist.highlight, input_context);
+    let cached = grid_cache.get_or_build(app_state, ui_state);
+    let status_line_vm = StatusLineViewModel::new(
+        status,
+        game.chess_rules(),
+        cached.has_conflict,
+        app_state.difficulty,
+        app_state.timed,
+        app_state.race_summary(),
+    )
+    .with_read_only(ui_state.read_only_reason(app_state));
+    let grid_vm = GridViewModel::new(
+        &cached.grid,
+        game.cages().clone(),
+        game.edge_constraints().clone(),
+        *game.regions(),
+        &settings.assist.highlight,
+        settings.theme,
+        input_context,
+    )
+    .with_links(hint_links(ui_state.hint_state.as_ref()))
+    .with_style(settings.grid_style)
+    .with_scratchpad(&ui_state.scratchpad);
 
-    let policy = app_state.rule_check_policy();
+    // The keypad must not give away the answer by disabling wrong digits.
+    let policy = app_state.rule_check_policy().rules_only();
     let decided_digit_count = game.decided_digit_count();
+    let completed_digits = if settings.assist.lock_completed_digits {
+        game.completed_digits()
+    } else {
+        DigitSet::EMPTY
+    };
+    let candidate_cell_count = settings
+        .assist
+        .keypad_candidate_counts
+        .then(|| game.candidate_cell_count());
     let digit_capabilities = DigitIndexedArray::from_fn(|digit| {
         let set_digit = selected_cell.map(|pos| game.set_digit_capability(pos, digit, policy));
         let toggle_note = selected_cell.map(|pos| game.toggle_note_capability(pos, digit, policy));
-        DigitKeyState::new(set_digit, toggle_note, decided_digit_count[digit])
+        let mut state = DigitKeyState::new(set_digit, toggle_note, decided_digit_count[digit])
+            .with_locked(completed_digits.contains(digit));
+        if let Some(counts) = &candidate_cell_count {
+            state = state.with_candidate_count(counts[digit]);
+        }
+        if settings.input.order.is_digit_first() {
+            state.with_armed(app_state.armed_digit() == Some(digit))
+        } else {
+            state
+        }
     });
-    let has_removable_input = selected_cell.is_some_and(|pos| game.has_removable_input(pos));
-    let auto_fill_capability = selected_cell.map(|pos| game.auto_fill_cell_notes_capability(pos));
-    let keypad_vm = KeypadViewModel::new(
-        digit_capabilities,
-        has_removable_input,
-        input_context,
-        auto_fill_capability,
-    );
+    let keypad_vm =
+        KeypadViewModel::new(digit_capabilities, commands, input_context, settings.theme);
+
+    GameScreenViewModel::new(
+        toolbar_vm,
+        status_line_vm,
+        grid_vm,
+        keypad_vm,
+        settings.display,
+        ui_state.notifications.active(),
+    )
+    .with_review(build_review_vm(app_state, ui_state))
+    .with_info_panel(
+        ui_state
+            .show_info_panel
+            .then(|| build_info_panel_vm(app_state, ui_state, Instant::now())),
+    )
+}
+
+/// Describes the puzzle on the board for the info sidebar.
+///
+/// The elapsed time is the game clock of timed games and races, and
+/// otherwise the time since the puzzle was loaded in this session.
+#[must_use]
+pub(crate) fn build_info_panel_vm(
+    app_state: &AppState,
+    ui_state: &UiState,
+    now: Instant,
+) -> InfoPanelViewModel {
+    let game = &app_state.game;
+    let placed = Position::ALL.len() - game.empty_positions().len();
+    let (elapsed, clock_running) = if let Some(clock) = app_state.active_clock() {
+        (clock.elapsed, !clock.finished)
+    } else {
+        let solved = game.is_solved();
+        (ui_state.solve_log.elapsed(now, solved), !solved)
+    };
+    InfoPanelViewModel::new(
+        app_state.difficulty,
+        game.given_positions().len(),
+        app_state.seed.map(|seed| seed.to_string()),
+        placed,
+    )
+    .with_elapsed(elapsed, clock_running)
+    .with_hints_used(ui_state.solve_log.hint_count())
+}
+
+/// Offers the solve log for review once the puzzle is solved.
+fn build_review_vm<'a>(app_state: &AppState, ui_state: &'a UiState) -> Option<ReviewViewModel<'a>> {
+    (app_state.reviewing && !ui_state.solve_log.steps().is_empty())
+        .then(|| ReviewViewModel::new(&ui_state.solve_log))
+}
+
+#[must_use]
+pub(crate) fn build_campaign_screen_view_model<'a>(
+    app_state: &'a AppState,
+    ui_state: &'a UiState,
+) -> CampaignScreenViewModel<'a> {
+    CampaignScreenViewModel::new(
+        &app_state.campaign,
+        &app_state.puzzle_metadata,
+        app_state.campaign_puzzle,
+        ui_state.notifications.active(),
+    )
+}
 
-    GameScreenViewModel::new(toolbar_vm, status_line_vm, grid_vm, keypad_vm)
+/// Builds the example board of the Learn screen, showing every candidate of
+/// the example as a note.
+#[must_use]
+pub(crate) fn build_learn_grid(learn: &LearnState) -> PositionIndexedArray<GridCell> {
+    let example = learn.lesson.examples.get(learn.example);
+    let mut grid = PositionIndexedArray::from_fn(|pos| {
+        let content = example.map_or(CellState::Empty, |example| {
+            CellState::Notes(example.grid.candidates_at(pos))
+        });
+        GridCell {
+            content,
+            visual_state: GridVisualState::empty(),
+            note_visual_state: NoteVisualState::default(),
+            center_notes: DigitSet::EMPTY,
+            candidate_count: None,
+            hint_count: None,
+            deduction_depth: None,
+            accessible_label: cell_accessible_label(pos, content, false),
+        }
+    });
+    if let Some(hint_state) = learn.hint_state() {
+        let regions = RegionLayout::standard();
+        apply_hint_ghost(&mut grid, &regions, &hint_state);
+        apply_hint_visuals(&mut grid, &regions, &hint_state);
+    }
+    grid
+}
+
+#[must_use]
+pub(crate) fn build_learn_screen_view_model<'a>(
+    app_state: &AppState,
+    ui_state: &'a UiState,
+    grid: &'a PositionIndexedArray<GridCell>,
+    input_context: &'a InputContext,
+) -> LearnScreenViewModel<'a> {
+    let settings = &app_state.settings;
+    let techniques = technique::all_techniques()
+        .iter()
+        .filter_map(|technique| Some((technique.technique_id()?, technique.name())))
+        .collect();
+    let grid_vm = GridViewModel::new(
+        grid,
+        CageSet::default(),
+        EdgeConstraintSet::default(),
+        RegionLayout::standard(),
+        &settings.assist.highlight,
+        settings.theme,
+        input_context,
+    )
+    .with_links(hint_links(ui_state.learn.hint_state().as_ref()))
+    .with_style(settings.grid_style);
+    LearnScreenViewModel::new(
+        techniques,
+        &ui_state.learn,
+        grid_vm,
+        settings.display,
+        ui_state.notifications.active(),
+    )
 }
 
 #[must_use]
 pub(crate) fn build_new_game_options_view_model(
     app_state: &AppState,
+    worker_readiness: Readiness,
 ) -> NewGameOptionsViewModel<'_> {
     let new_game_options = &app_state.new_game_options;
-    NewGameOptionsViewModel::new(new_game_options)
+    NewGameOptionsViewModel::new(new_game_options, worker_readiness)
 }
 
 #[must_use]
@@ -371,16 +945,58 @@ pub(crate) fn build_settings_view_model(app_state: &AppState) -> SettingsViewMod
     SettingsViewModel::new(settings)
 }
 
+#[must_use]
+pub(crate) fn build_statistics_view_model(app_state: &AppState) -> StatisticsViewModel<'_> {
+    StatisticsViewModel::new(&app_state.hint_usage, &app_state.puzzle_metadata)
+}
+
+#[must_use]
+pub(crate) fn build_solved_result_view_model(app_state: &AppState) -> SolvedResultViewModel {
+    SolvedResultViewModel::new(app_state.timed, app_state.difficulty)
+}
+
+#[must_use]
+pub(crate) fn build_race_result_view_model(app_state: &AppState) -> RaceResultViewModel {
+    RaceResultViewModel::new(app_state.race_summary())
+}
+
+#[must_use]
+pub(crate) fn build_modal_view_models(
+    app_state: &AppState,
+    worker_readiness: Readiness,
+) -> ModalViewModels<'_> {
+    ModalViewModels {
+        new_game_options: build_new_game_options_view_model(app_state, worker_readiness),
+        settings: build_settings_view_model(app_state),
+        statistics: build_statistics_view_model(app_state),
+        solved_result: build_solved_result_view_model(app_state),
+        race_result: build_race_result_view_model(app_state),
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use numelace_core::{Digit, DigitGrid, DigitPositions, DigitSet, Position};
-    use numelace_game::{CellState, Game};
-    use numelace_solver::{BoxedTechniqueStep, TechniqueApplication, TechniqueStep};
+    use numelace_core::{
+        Cage, CageSet, ChessRules, Digit, DigitGrid, DigitPositions, DigitSet, EdgeConstraint,
+        EdgeConstraintSet, EdgeKind, Position, PositionIndexedArray,
+    };
+    use numelace_game::{CellState, Game, RuleCheckPolicy};
+    use numelace_solver::{
+        BoxedTechniqueStep, Technique, TechniqueApplication, TechniqueStep, corpus, technique,
+    };
+
+    use std::time::Duration;
+
+    use numelace_generator::PuzzleSeed;
+    use web_time::Instant;
 
-    use super::build_grid;
+    use super::{GridCache, build_grid, build_info_panel_vm, hint_links};
     use crate::{
-        state::{AppState, GhostType, HintStage, HintState, UiState},
-        ui::grid::GridVisualState,
+        state::{AppState, BivalueSource, GhostType, HintStage, HintState, TimedGame, UiState},
+        ui::{
+            grid::GridVisualState,
+            grid_snapshot::{assert_grid_snapshot, dump_grid},
+        },
     };
 
     fn blank_grid() -> DigitGrid {
@@ -435,6 +1051,10 @@ mod tests {
             "HintTest"
         }
 
+        fn technique_key(&self) -> &'static str {
+            "hint_test"
+        }
+
         fn clone_box(&self) -> BoxedTechniqueStep {
             Box::new(self.clone())
         }
@@ -487,6 +1107,245 @@ mod tests {
         );
     }
 
+    #[test]
+    fn build_grid_highlights_cage_conflicts() {
+        let cage = Cage::new(
+            [Position::new(0, 0), Position::new(4, 4)]
+                .into_iter()
+                .collect(),
+            3,
+        )
+        .unwrap();
+        let cages = CageSet::from_cages([cage]).unwrap();
+        let filled: DigitGrid = format!("1{}1{}", ".".repeat(39), ".".repeat(40))
+            .parse()
+            .unwrap();
+        let game = game_from_filled(&filled).with_cages(cages);
+        let app_state = AppState::new(game);
+
+        let grid = build_grid(&app_state, &UiState::new());
+
+        for pos in [Position::new(0, 0), Position::new(4, 4)] {
+            assert!(grid[pos].visual_state.contains(GridVisualState::CONFLICT));
+        }
+    }
+
+    #[test]
+    fn build_grid_highlights_edge_conflicts() {
+        let edge =
+            EdgeConstraint::new(Position::new(0, 0), Position::new(0, 1), EdgeKind::LessThan)
+                .unwrap();
+        let edges = EdgeConstraintSet::from_constraints([edge]).unwrap();
+        let filled: DigitGrid = format!("21{}", ".".repeat(79)).parse().unwrap();
+        let game = game_from_filled(&filled).with_edge_constraints(edges);
+        let app_state = AppState::new(game);
+
+        let grid = build_grid(&app_state, &UiState::new());
+
+        for pos in [Position::new(0, 0), Position::new(0, 1)] {
+            assert!(grid[pos].visual_state.contains(GridVisualState::CONFLICT));
+        }
+    }
+
+    #[test]
+    fn build_grid_highlights_chess_conflicts() {
+        // (0, 0) and (1, 2) are a knight's move apart.
+        let filled: DigitGrid = format!("1{}1{}", ".".repeat(10), ".".repeat(69))
+            .parse()
+            .unwrap();
+        let game =
+            game_from_filled(&filled).with_chess_rules(ChessRules::new().with_anti_knight(true));
+        let app_state = AppState::new(game);
+
+        let grid = build_grid(&app_state, &UiState::new());
+
+        for pos in [Position::new(0, 0), Position::new(1, 2)] {
+            assert!(grid[pos].visual_state.contains(GridVisualState::CONFLICT));
+        }
+    }
+
+    #[test]
+    fn build_grid_highlights_selected_digit_candidates() {
+        let filled: DigitGrid = format!("1{}", ".".repeat(80)).parse().unwrap();
+        let mut app_state = AppState::new(game_from_filled(&filled));
+        app_state.set_selected_cell(Position::new(0, 0));
+
+        let grid = build_grid(&app_state, &UiState::new());
+
+        for pos in [
+            Position::new(0, 0),
+            Position::new(0, 5),
+            Position::new(2, 2),
+        ] {
+            assert!(
+                !grid[pos]
+                    .visual_state
+                    .contains(GridVisualState::SELECTED_DIGIT_CANDIDATE)
+            );
+        }
+        assert!(
+            grid[Position::new(4, 4)]
+                .visual_state
+                .contains(GridVisualState::SELECTED_DIGIT_CANDIDATE)
+        );
+    }
+
+    #[test]
+    fn grid_shows_deduction_depths_with_overlay() {
+        let problem: DigitGrid = format!("1{}", ".".repeat(80)).parse().unwrap();
+        let notes = [[0u16; 9]; 9];
+        let game =
+            Game::from_problem_filled_notes(&problem, &DigitGrid::new(), &DigitGrid::new(), &notes)
+                .unwrap();
+        let mut app_state = AppState::new(game);
+        let mut ui_state = UiState::new();
+        let given = Position::new(0, 0);
+        let empty = Position::new(0, 1);
+        ui_state.deduction_depths = Some(PositionIndexedArray::from_fn(|_| 1));
+
+        assert_eq!(
+            build_grid(&app_state, &ui_state)[empty].deduction_depth,
+            None
+        );
+
+        app_state.settings.assist.deduction_depth_overlay = true;
+        let grid = build_grid(&app_state, &ui_state);
+        assert_eq!(grid[given].deduction_depth, None);
+        assert_eq!(grid[empty].deduction_depth, Some(1));
+    }
+
+    #[test]
+    fn build_grid_highlights_bivalue_cells_from_chosen_source() {
+        let filled: DigitGrid = format!("1234567{}", ".".repeat(74)).parse().unwrap();
+        let solution: DigitGrid =
+            "185362947793148526246795183564239871931874265827516394318427659672951438459683712"
+                .parse()
+                .unwrap();
+        let mut notes = [[0u16; 9]; 9];
+        notes[4][4] = 0b0_0000_0110;
+        let game =
+            Game::from_problem_filled_notes(&blank_grid(), &solution, &filled, &notes).unwrap();
+        let mut app_state = AppState::new(game);
+        app_state.settings.assist.highlight.bivalue_cell = true;
+        let bivalue = |app_state: &AppState, pos| {
+            build_grid(app_state, &UiState::new())[pos]
+                .visual_state
+                .contains(GridVisualState::BIVALUE)
+        };
+
+        assert!(bivalue(&app_state, Position::new(0, 8)));
+        assert!(!bivalue(&app_state, Position::new(0, 0)));
+        assert!(!bivalue(&app_state, Position::new(4, 4)));
+
+        app_state.settings.assist.highlight.bivalue_source = BivalueSource::Notes;
+        assert!(!bivalue(&app_state, Position::new(0, 8)));
+        assert!(bivalue(&app_state, Position::new(4, 4)));
+    }
+
+    #[test]
+    fn build_grid_highlights_audited_notes() {
+        // (0, 2) and (2, 3) are a knight's move apart in different boxes.
+        let filled: DigitGrid = format!("..1{}", ".".repeat(78)).parse().unwrap();
+        let mut notes = [[0u16; 9]; 9];
+        notes[2][3] = 0b0_0000_0011;
+        let game =
+            Game::from_problem_filled_notes(&DigitGrid::new(), &DigitGrid::new(), &filled, &notes)
+                .unwrap()
+                .with_chess_rules(ChessRules::new().with_anti_knight(true));
+        let mut ui_state = UiState::new();
+        ui_state.note_audit = Some(game.illegal_notes());
+        let app_state = AppState::new(game);
+
+        let pos = Position::new(2, 3);
+        assert!(
+            build_grid(&app_state, &UiState::new())[pos]
+                .note_visual_state
+                .conflict
+                .is_empty()
+        );
+        let grid = build_grid(&app_state, &ui_state);
+        assert_eq!(
+            grid[pos].note_visual_state.conflict,
+            DigitSet::from_iter([Digit::D1])
+        );
+    }
+
+    #[test]
+    fn build_grid_describes_cells_for_screen_readers() {
+        let problem: DigitGrid = format!("1{}", ".".repeat(80)).parse().unwrap();
+        let solution: DigitGrid =
+            "185362947793148526246795183564239871931874265827516394318427659672951438459683712"
+                .parse()
+                .unwrap();
+        let filled: DigitGrid = format!(".1{}", ".".repeat(79)).parse().unwrap();
+        let mut notes = [[0u16; 9]; 9];
+        notes[4][4] = 0b0_1000_0110;
+        let game = Game::from_problem_filled_notes(&problem, &solution, &filled, &notes).unwrap();
+        let app_state = AppState::new(game);
+
+        let grid = build_grid(&app_state, &UiState::new());
+
+        assert_eq!(
+            grid[Position::new(0, 0)].accessible_label,
+            "row 1 column 1, given 1, conflict"
+        );
+        assert_eq!(
+            grid[Position::new(0, 1)].accessible_label,
+            "row 1 column 2, filled 1, conflict"
+        );
+        assert_eq!(
+            grid[Position::new(4, 4)].accessible_label,
+            "row 5 column 5, empty, candidates 2 3 8"
+        );
+        assert_eq!(
+            grid[Position::new(8, 8)].accessible_label,
+            "row 9 column 9, empty"
+        );
+    }
+
+    #[test]
+    fn build_grid_counts_candidates_for_heatmap() {
+        let mut app_state = AppState::new(game_from_filled(&filled_with_conflict()));
+        let ui_state = UiState::new();
+        assert_eq!(
+            build_grid(&app_state, &ui_state)[Position::new(0, 2)].candidate_count,
+            None
+        );
+
+        app_state.settings.assist.candidate_heatmap = true;
+        let grid = build_grid(&app_state, &ui_state);
+
+        assert_eq!(grid[Position::new(0, 0)].candidate_count, None);
+        assert_eq!(grid[Position::new(0, 2)].candidate_count, Some(8));
+        assert_eq!(grid[Position::new(8, 8)].candidate_count, Some(9));
+    }
+
+    #[test]
+    fn build_grid_shows_hint_heat_map_once_solved() {
+        let solution: DigitGrid =
+            "185362947793148526246795183564239871931874265827516394318427659672951438459683712"
+                .parse()
+                .unwrap();
+        let mut app_state = AppState::new(game_from_filled(&blank_grid()));
+        app_state.record_hinted_cells(DigitPositions::from_elem(Position::new(2, 3)));
+        let ui_state = UiState::new();
+        assert_eq!(
+            build_grid(&app_state, &ui_state)[Position::new(2, 3)].hint_count,
+            None
+        );
+
+        app_state.game = game_from_filled(&solution);
+        let grid = build_grid(&app_state, &ui_state);
+
+        assert_eq!(grid[Position::new(2, 3)].hint_count, Some(1));
+        assert_eq!(grid[Position::new(0, 0)].hint_count, None);
+        assert!(
+            grid[Position::new(2, 3)]
+                .accessible_label
+                .ends_with("hinted 1 times")
+        );
+    }
+
     #[test]
     fn build_grid_applies_digit_ghost() {
         let app_state = AppState::new(game_from_filled(&blank_grid()));
@@ -530,4 +1389,228 @@ mod tests {
                 .contains(GridVisualState::HINT_CONDITION_CELL)
         );
     }
+
+    #[test]
+    fn hint_links_are_shown_with_condition_digits() {
+        let grid = corpus::example(technique::TechniqueId::Aic).unwrap();
+        let step = technique::Aic::new().find_step(&grid).unwrap().unwrap();
+        let links_at = |stage| {
+            hint_links(Some(&HintState {
+                stage,
+                step: step.clone(),
+            }))
+        };
+
+        assert_eq!(links_at(HintStage::Stage1), []);
+        assert_eq!(links_at(HintStage::Stage2), step.links());
+        assert_eq!(links_at(HintStage::Stage3Preview), step.links());
+        assert_eq!(links_at(HintStage::Stage3Apply), []);
+        assert_eq!(hint_links(None), []);
+    }
+
+    #[test]
+    fn build_grid_highlights_only_focus_area_in_stage0() {
+        let app_state = AppState::new(game_from_filled(&blank_grid()));
+        let mut ui_state = UiState::new();
+        // Two cells of row 5 and one of column 8 outside it.
+        let positions = DigitPositions::from_iter([
+            Position::new(4, 1),
+            Position::new(4, 7),
+            Position::new(0, 8),
+        ]);
+        let step: BoxedTechniqueStep = Box::new(HintTestStep { positions });
+        ui_state.hint_state = Some(HintState {
+            stage: HintStage::Stage0,
+            step,
+        });
+
+        let grid = build_grid(&app_state, &ui_state);
+
+        for pos in Position::ALL {
+            let highlighted = grid[pos]
+                .visual_state
+                .contains(GridVisualState::HINT_CONDITION_CELL);
+            assert_eq!(highlighted, pos.row() == 4, "{pos:?}");
+            assert!(grid[pos].note_visual_state.ghost.is_empty(), "{pos:?}");
+        }
+    }
+
+    #[derive(Debug, Clone)]
+    struct PlacementTestStep {
+        position: Position,
+        digit: Digit,
+    }
+
+    impl TechniqueStep for PlacementTestStep {
+        fn technique_name(&self) -> &'static str {
+            "PlacementTest"
+        }
+
+        fn technique_key(&self) -> &'static str {
+            "placement_test"
+        }
+
+        fn clone_box(&self) -> BoxedTechniqueStep {
+            Box::new(self.clone())
+        }
+
+        fn condition_positions(&self) -> DigitPositions {
+            DigitPositions::EMPTY
+        }
+
+        fn condition_digit_positions(&self) -> Vec<(DigitPositions, DigitSet)> {
+            Vec::new()
+        }
+
+        fn application(&self) -> Vec<TechniqueApplication> {
+            vec![TechniqueApplication::Placement {
+                position: self.position,
+                digit: self.digit,
+            }]
+        }
+    }
+
+    #[test]
+    fn build_grid_previews_note_cleanup_of_hint_placement() {
+        let mut app_state = AppState::new(game_from_filled(&blank_grid()));
+        for (pos, digit) in [
+            (Position::new(0, 5), Digit::D1),
+            (Position::new(0, 5), Digit::D2),
+            (Position::new(5, 5), Digit::D1),
+        ] {
+            app_state
+                .game
+                .toggle_note(pos, digit, RuleCheckPolicy::Permissive)
+                .unwrap();
+        }
+        let mut ui_state = UiState::new();
+        let step = PlacementTestStep {
+            position: Position::new(0, 0),
+            digit: Digit::D1,
+        };
+        ui_state.hint_state = Some(HintState {
+            stage: HintStage::Stage3Preview,
+            step: Box::new(step),
+        });
+
+        let grid = build_grid(&app_state, &ui_state);
+
+        let cleanup = grid[Position::new(0, 5)].note_visual_state.note_cleanup;
+        assert_eq!(cleanup, DigitSet::from_elem(Digit::D1));
+        assert!(
+            grid[Position::new(5, 5)]
+                .note_visual_state
+                .note_cleanup
+                .is_empty()
+        );
+
+        app_state
+            .settings
+            .assist
+            .notes
+            .auto_remove_peer_notes_on_fill = false;
+        let grid = build_grid(&app_state, &ui_state);
+        assert!(
+            grid[Position::new(0, 5)]
+                .note_visual_state
+                .note_cleanup
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn snapshot_selection_layers_over_conflicts() {
+        let mut app_state = AppState::new(game_from_filled(&filled_with_conflict()));
+        app_state.set_selected_cell(Position::new(0, 0));
+
+        let grid = build_grid(&app_state, &UiState::new());
+
+        assert_grid_snapshot(
+            "selection_layers_over_conflicts",
+            &dump_grid(&grid, GridVisualState::all()),
+        );
+    }
+
+    #[test]
+    fn snapshot_hint_preview_with_ghost_and_note_cleanup() {
+        let mut app_state = AppState::new(game_from_filled(&blank_grid()));
+        for (pos, digit) in [
+            (Position::new(0, 5), Digit::D1),
+            (Position::new(0, 5), Digit::D2),
+            (Position::new(5, 5), Digit::D1),
+        ] {
+            app_state
+                .game
+                .toggle_note(pos, digit, RuleCheckPolicy::Permissive)
+                .unwrap();
+        }
+        let mut ui_state = UiState::new();
+        ui_state.conflict_ghost = Some((Position::new(8, 8), GhostType::Digit(Digit::D9)));
+        let step = PlacementTestStep {
+            position: Position::new(0, 0),
+            digit: Digit::D1,
+        };
+        ui_state.hint_state = Some(HintState {
+            stage: HintStage::Stage3Preview,
+            step: Box::new(step),
+        });
+
+        let grid = build_grid(&app_state, &ui_state);
+
+        assert_grid_snapshot(
+            "hint_preview_with_ghost_and_note_cleanup",
+            &dump_grid(&grid, GridVisualState::all()),
+        );
+    }
+
+    #[test]
+    fn info_panel_describes_the_puzzle_on_the_board() {
+        let mut app_state = AppState::new(game_from_filled(&filled_with_conflict()));
+        let mut ui_state = UiState::new();
+        let start = Instant::now();
+        ui_state.solve_log.reset(&app_state.game, start);
+
+        let info = build_info_panel_vm(&app_state, &ui_state, start + Duration::from_secs(5));
+        assert_eq!(info.clues, 0);
+        assert_eq!(info.placed, 2);
+        assert_eq!(info.seed, None);
+        assert_eq!(info.elapsed, Duration::from_secs(5));
+        assert!(info.clock_running);
+        assert_eq!(info.hints_used, 0);
+
+        app_state.seed = Some(PuzzleSeed::from([1; 32]));
+        app_state.timed = Some(TimedGame {
+            elapsed: Duration::from_secs(90),
+            ..TimedGame::default()
+        });
+        let info = build_info_panel_vm(&app_state, &ui_state, start);
+        assert_eq!(info.seed.as_deref(), Some("01".repeat(32).as_str()));
+        assert_eq!(info.elapsed, Duration::from_secs(90));
+    }
+
+    #[test]
+    fn grid_cache_rebuilds_only_after_revision_changes() {
+        let mut app_state = AppState::new(game_from_filled(&blank_grid()));
+        let mut ui_state = UiState::new();
+        let mut cache = GridCache::default();
+        let pos = Position::new(0, 0);
+        let is_selected = |cache: &mut GridCache, app_state: &AppState, ui_state: &UiState| {
+            cache.get_or_build(app_state, ui_state).grid[pos]
+                .visual_state
+                .contains(GridVisualState::SELECTED_CELL)
+        };
+
+        assert!(!is_selected(&mut cache, &app_state, &ui_state));
+
+        // Mutating without bumping the revision keeps serving the cached grid.
+        app_state.set_selected_cell(pos);
+        assert!(!is_selected(&mut cache, &app_state, &ui_state));
+
+        let _ = app_state.access().as_mut();
+        assert!(is_selected(&mut cache, &app_state, &ui_state));
+
+        app_state.clear_selected_cell_and_digit();
+        ui_state.revision += 1;
+        assert!(!is_selected(&mut cache, &app_state, &ui_state));
+    }
 }
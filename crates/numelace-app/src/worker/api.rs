@@ -1,6 +1,12 @@
+use std::{error::Error, ops::ControlFlow};
+
+use numelace_core::CandidateGrid;
 use serde::{Deserialize, Serialize};
 
-use super::{WorkError, WorkRequest as InnerWorkRequest, WorkResponse as InnerWorkResponse};
+use super::{
+    WorkError, WorkFrame as InnerWorkFrame, WorkRequest as InnerWorkRequest,
+    WorkResponse as InnerWorkResponse, codec, tasks::CandidateGridDto,
+};
 
 #[derive(Deserialize, Serialize)]
 #[serde(transparent)]
@@ -8,18 +14,50 @@ pub struct WorkRequest(InnerWorkRequest);
 
 #[derive(Deserialize, Serialize)]
 #[serde(transparent)]
-pub struct WorkResponse(InnerWorkResponse);
+pub struct WorkFrame(InnerWorkFrame);
 
 impl WorkRequest {
-    #[must_use]
-    pub fn handle(self) -> WorkResponse {
-        WorkResponse(self.0.handle())
+    /// Decodes a request sent by the app.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, impl Error> {
+        codec::decode(bytes).map(Self)
+    }
+
+    /// Handles the request, passing each response frame to `emit` in order.
+    pub fn handle(self, mut emit: impl FnMut(WorkFrame) -> ControlFlow<()>) {
+        self.0.handle_frames(|frame| emit(WorkFrame(frame)));
     }
 }
 
-impl WorkResponse {
+impl WorkFrame {
+    /// Encodes the frame for sending back to the app.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, impl Error> {
+        codec::encode(&self.0)
+    }
+
     #[must_use]
     pub fn deserialization_error() -> Self {
-        Self(InnerWorkResponse::Error(WorkError::DeserializationFailed))
+        Self(InnerWorkFrame::Done(InnerWorkResponse::Error(
+            WorkError::DeserializationFailed,
+        )))
     }
+
+    /// Returns the frame to send instead when this one cannot be encoded.
+    ///
+    /// The replacement reports a serialization error for the same
+    /// request or batch item, so the app does not wait for it in vain.
+    #[must_use]
+    pub fn into_serialization_error(self) -> Self {
+        Self(self.0.into_error(WorkError::SerializationFailed))
+    }
+}
+
+/// Converts per-cell candidate bitmasks into a [`CandidateGrid`] exactly as
+/// worker requests do.
+///
+/// Exposed for the fuzz targets, which cannot reach the internal DTOs.
+pub fn candidate_grid_from_bits(candidates: Vec<u16>) -> Result<CandidateGrid, impl Error> {
+    CandidateGrid::try_from(CandidateGridDto {
+        candidates,
+        regions: None,
+    })
 }
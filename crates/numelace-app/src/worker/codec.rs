@@ -0,0 +1,86 @@
+//! Binary encoding of worker messages.
+//!
+//! Requests and response frames cross the Web Worker boundary as CBOR bytes.
+//! A byte buffer can be transferred to the other side instead of being
+//! structured-cloned, and avoids building a JS object graph for every grid.
+
+use serde::{Serialize, de::DeserializeOwned};
+
+use super::WorkError;
+
+/// Encodes a worker message.
+///
+/// # Errors
+///
+/// Returns [`WorkError::SerializationFailed`] if `value` cannot be encoded.
+pub(crate) fn encode<T>(value: &T) -> Result<Vec<u8>, WorkError>
+where
+    T: Serialize,
+{
+    let mut bytes = vec![];
+    ciborium::into_writer(value, &mut bytes).map_err(|_| WorkError::SerializationFailed)?;
+    Ok(bytes)
+}
+
+/// Decodes a worker message.
+///
+/// # Errors
+///
+/// Returns [`WorkError::DeserializationFailed`] if `bytes` do not hold a `T`.
+pub(crate) fn decode<T>(bytes: &[u8]) -> Result<T, WorkError>
+where
+    T: DeserializeOwned,
+{
+    ciborium::from_reader(bytes).map_err(|_| WorkError::DeserializationFailed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode, encode};
+    use crate::worker::{
+        WorkError, WorkFrame, WorkRequest, WorkResponse,
+        tasks::{CandidateGridPairsDto, SolvabilityResultDto, SolvabilityUndoScanResultDto},
+    };
+
+    #[test]
+    fn messages_round_trip() {
+        let request = WorkRequest::Batch(vec![WorkRequest::CheckSolvabilityUndoScan(
+            CandidateGridPairsDto::from(vec![numelace_game::Game::new_empty()]),
+        )]);
+        let bytes = encode(&request).unwrap();
+        let WorkRequest::Batch(items) = decode(&bytes).unwrap() else {
+            panic!("expected a batch");
+        };
+        let [WorkRequest::CheckSolvabilityUndoScan(scan)] = items.as_slice() else {
+            panic!("expected one undo scan, got {items:?}");
+        };
+        assert_eq!(scan.grids.len(), 1);
+        assert_eq!(scan.grids[0].with_user_notes.candidates, vec![0x1ff; 81]);
+
+        let frame = WorkFrame::Partial {
+            index: 3,
+            response: WorkResponse::SolvabilityUndoScanReady(
+                SolvabilityUndoScanResultDto::not_found(),
+            ),
+        };
+        let bytes = encode(&frame).unwrap();
+        assert!(matches!(
+            decode(&bytes).unwrap(),
+            WorkFrame::Partial {
+                index: 3,
+                response: WorkResponse::SolvabilityUndoScanReady(SolvabilityUndoScanResultDto {
+                    index: None,
+                    state: SolvabilityResultDto::NoSolution { .. },
+                }),
+            }
+        ));
+    }
+
+    #[test]
+    fn garbage_is_rejected() {
+        assert!(matches!(
+            decode::<WorkFrame>(&[0xff, 0x00]),
+            Err(WorkError::DeserializationFailed)
+        ));
+    }
+}
@@ -6,24 +6,38 @@
 //! `wasm` module uses a Web Worker with message passing.
 
 use std::{
-    future::Future,
+    cell::{Cell, RefCell},
+    future::{self, Future},
+    ops::ControlFlow,
     pin::Pin,
-    task::{Context, Poll},
+    task::{Context, Poll, Waker},
+    time::Duration,
 };
 
 use platform::WorkHandle;
-pub(crate) use platform::warm_up;
+use web_time::Instant;
 
 use self::tasks::{
-    CandidateGridPairDto, CandidateGridPairsDto, GeneratedPuzzleDto, SolvabilityResultDto,
+    CandidateGridPairsDto, DeductionDepthsDto, DeductionDepthsRequestDto, GeneratedPuzzleDto,
+    GradePuzzleRequestDto, PuzzleGradeDto, SolvabilityRequestDto, SolvabilityResultDto,
     SolvabilityUndoScanResultDto,
 };
 use crate::worker::tasks::GeneratePuzzleRequestDto;
 
 pub(crate) mod api;
+mod codec;
 mod platform;
 pub(crate) mod tasks;
 
+/// Number of history states checked by one item of an undo scan batch.
+const UNDO_SCAN_CHUNK_LEN: usize = 8;
+
+/// Number of times a request is sent again after its worker died.
+const MAX_RETRIES: u32 = 3;
+
+/// Delay before the first retry of a request.
+const RETRY_BACKOFF: Duration = Duration::from_millis(100);
+
 /// A request that can be offloaded to a background worker.
 ///
 /// Internal: prefer typed helpers like `request_generate_puzzle` and `request_solvability`.
@@ -32,9 +46,19 @@ enum WorkRequest {
     /// Generate a Sudoku puzzle.
     GeneratePuzzle(GeneratePuzzleRequestDto),
     /// Check solvability for a given puzzle state.
-    CheckSolvability(CandidateGridPairDto),
+    CheckSolvability(SolvabilityRequestDto),
     /// Scan undo history for a solvable state.
     CheckSolvabilityUndoScan(CandidateGridPairsDto),
+    /// Rate the difficulty of a puzzle.
+    GradePuzzle(GradePuzzleRequestDto),
+    /// Analyze the trial depths of a board for the hardness overlay.
+    AnalyzeDeductionDepths(DeductionDepthsRequestDto),
+    /// Run a cheap generation so later requests start fast.
+    WarmUp,
+    /// Handle several requests back to back, answering each as it finishes.
+    ///
+    /// Batches cannot be nested.
+    Batch(Vec<WorkRequest>),
 }
 
 /// A response produced by background work.
@@ -46,10 +70,49 @@ enum WorkResponse {
     SolvabilityReady(SolvabilityResultDto),
     /// Undo scan result ready for display.
     SolvabilityUndoScanReady(SolvabilityUndoScanResultDto),
+    /// Difficulty grade ready for display.
+    PuzzleGraded(PuzzleGradeDto),
+    /// Trial depths ready for the overlay, or `None` if the analysis gave up.
+    DeductionDepthsReady(Option<DeductionDepthsDto>),
+    /// The worker finished warming up.
+    WarmedUp,
+    /// Every item of a batch has been answered.
+    BatchDone,
     /// An error occurred while performing background work.
     Error(WorkError),
 }
 
+/// One message of the response stream to a request.
+///
+/// Every request is answered by a single `Done` frame. A batch first sends a
+/// `Partial` frame for each item, in order, and ends with `Done(BatchDone)`.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+enum WorkFrame {
+    /// Response to the batch item at `index`.
+    Partial {
+        index: usize,
+        response: WorkResponse,
+    },
+    /// Final response; no more frames follow for the request.
+    Done(WorkResponse),
+}
+
+impl WorkFrame {
+    /// Replaces the response with `err`, keeping the batch index.
+    ///
+    /// A batch item that failed still needs its `Partial` frame, or the app
+    /// would wait for it until the request times out.
+    fn into_error(self, err: WorkError) -> Self {
+        match self {
+            Self::Partial { index, .. } => Self::Partial {
+                index,
+                response: WorkResponse::Error(err),
+            },
+            Self::Done(_) => Self::Done(WorkResponse::Error(err)),
+        }
+    }
+}
+
 /// Errors that can occur while scheduling or receiving background work.
 #[derive(
     Debug, Clone, serde::Serialize, serde::Deserialize, derive_more::Display, derive_more::Error,
@@ -73,9 +136,47 @@ pub(crate) enum WorkError {
     /// Received a response that does not match the request.
     #[display("unexpected worker response")]
     UnexpectedResponse,
+    /// The worker did not respond within the flow's time limit.
+    #[display("worker did not respond in time")]
+    TimedOut,
+    /// A batch contained another batch.
+    #[display("worker batches cannot be nested")]
+    NestedBatch,
+}
+
+impl WorkError {
+    /// Returns whether the work could not run in time, rather than failing on its input.
+    #[must_use]
+    pub(crate) fn is_unavailable(&self) -> bool {
+        matches!(
+            self,
+            Self::WorkerUrlMissing
+                | Self::WorkerInitFailed
+                | Self::WorkerDisconnected
+                | Self::TimedOut
+        )
+    }
 }
 
 impl WorkRequest {
+    /// Handle a request and pass its response frames to `emit`.
+    ///
+    /// A batch stops early once `emit` breaks, e.g. because nobody waits for
+    /// the rest of it anymore.
+    fn handle_frames(self, mut emit: impl FnMut(WorkFrame) -> ControlFlow<()>) {
+        let WorkRequest::Batch(requests) = self else {
+            let _ = emit(WorkFrame::Done(self.handle()));
+            return;
+        };
+        for (index, request) in requests.into_iter().enumerate() {
+            let response = request.handle();
+            if emit(WorkFrame::Partial { index, response }).is_break() {
+                return;
+            }
+        }
+        let _ = emit(WorkFrame::Done(WorkResponse::BatchDone));
+    }
+
     /// Handle a request and produce the corresponding response.
     ///
     /// This keeps the request-to-response mapping centralized across backends.
@@ -97,56 +198,265 @@ impl WorkRequest {
                     Err(_) => WorkResponse::Error(WorkError::DeserializationFailed),
                 }
             }
+            WorkRequest::GradePuzzle(request) => match tasks::grade_puzzle(&request) {
+                Ok(grade) => WorkResponse::PuzzleGraded(grade),
+                Err(_) => WorkResponse::Error(WorkError::DeserializationFailed),
+            },
+            WorkRequest::AnalyzeDeductionDepths(request) => {
+                match tasks::analyze_deduction_depths(request) {
+                    Ok(depths) => WorkResponse::DeductionDepthsReady(depths),
+                    Err(_) => WorkResponse::Error(WorkError::DeserializationFailed),
+                }
+            }
+            WorkRequest::WarmUp => {
+                tasks::warm_up();
+                WorkResponse::WarmedUp
+            }
+            WorkRequest::Batch(_) => WorkResponse::Error(WorkError::NestedBatch),
         }
     }
 }
 
-/// Future that resolves to a background work response.
-struct WorkResponseFuture {
+/// Frames received for one request.
+///
+/// If the worker dies before sending the first frame, the request is sent
+/// again after a [`retry_delay`], since the platform replaces dead workers on
+/// the next request. Requests that already received frames are not retried,
+/// as that would replay the batch items answered so far.
+struct WorkFrames {
+    request: WorkRequest,
     handle: Option<WorkHandle>,
-    response: Option<WorkResponse>,
+    error: Option<WorkError>,
+    retries: u32,
+    retry_at: Option<Instant>,
+    received: bool,
 }
 
-impl WorkResponseFuture {
+impl WorkFrames {
     #[must_use]
-    fn new(result: Result<WorkHandle, WorkError>) -> Self {
-        match result {
-            Ok(handle) => Self {
-                handle: Some(handle),
-                response: None,
-            },
-            Err(err) => Self {
-                handle: None,
-                response: Some(WorkResponse::Error(err)),
-            },
+    fn send(request: WorkRequest) -> Self {
+        PENDING_REQUESTS.set(PENDING_REQUESTS.get() + 1);
+        let (handle, error) = match platform::enqueue(request.clone()) {
+            Ok(handle) => (Some(handle), None),
+            Err(err) => (None, Some(err)),
+        };
+        Self {
+            request,
+            handle,
+            error,
+            retries: 0,
+            retry_at: None,
+            received: false,
+        }
+    }
+
+    /// Polls for the next frame, or `None` once the request is answered.
+    ///
+    /// The platform wakes `waker` when a frame arrives. Nothing wakes it when
+    /// a retry falls due, which [`has_pending_requests`] leaves to the caller.
+    fn poll_frame(&mut self, waker: &Waker) -> Poll<Option<Result<WorkFrame, WorkError>>> {
+        if let Some(retry_at) = self.retry_at {
+            if Instant::now() < retry_at {
+                return Poll::Pending;
+            }
+            self.retry_at = None;
+            match platform::enqueue(self.request.clone()) {
+                Ok(handle) => self.handle = Some(handle),
+                Err(err) => self.error = Some(err),
+            }
+        }
+        if let Some(err) = self.error.take() {
+            return self.fail(err);
+        }
+        let Some(handle) = self.handle.as_mut() else {
+            return Poll::Ready(None);
+        };
+
+        match handle.poll(waker) {
+            Ok(Some(frame)) => {
+                self.received = true;
+                if matches!(frame, WorkFrame::Done(_)) {
+                    self.handle = None;
+                }
+                Poll::Ready(Some(Ok(frame)))
+            }
+            Ok(None) => Poll::Pending,
+            Err(err) => {
+                self.handle = None;
+                self.fail(err)
+            }
+        }
+    }
+
+    /// Schedules a retry if `err` allows one, and reports `err` otherwise.
+    fn fail(&mut self, err: WorkError) -> Poll<Option<Result<WorkFrame, WorkError>>> {
+        let delay = retry_delay(self.retries);
+        match delay {
+            Some(delay) if matches!(err, WorkError::WorkerDisconnected) && !self.received => {
+                self.retries += 1;
+                log::warn!("{err}; retrying in {delay:?} (attempt {})", self.retries);
+                self.retry_at = Some(Instant::now() + delay);
+                Poll::Pending
+            }
+            _ => Poll::Ready(Some(Err(err))),
         }
     }
 }
 
+impl Drop for WorkFrames {
+    fn drop(&mut self) {
+        PENDING_REQUESTS.set(PENDING_REQUESTS.get() - 1);
+    }
+}
+
+thread_local! {
+    /// Number of requests whose [`WorkFrames`] are still alive.
+    static PENDING_REQUESTS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Returns whether any request still waits for the worker.
+///
+/// Frames wake the task that polls the request, but deadlines do not: retry
+/// backoffs and flow timeouts only pass when something polls again. Callers
+/// should poll now and then while this returns `true`.
+#[must_use]
+pub(crate) fn has_pending_requests() -> bool {
+    PENDING_REQUESTS.get() > 0
+}
+
+/// Returns how long to wait before retry number `retries + 1`, or `None` once
+/// [`MAX_RETRIES`] is used up.
+///
+/// The delay starts at [`RETRY_BACKOFF`] and doubles with every retry.
+#[must_use]
+fn retry_delay(retries: u32) -> Option<Duration> {
+    (retries < MAX_RETRIES).then(|| RETRY_BACKOFF * 2_u32.pow(retries))
+}
+
+/// Future that resolves to a background work response.
+struct WorkResponseFuture {
+    frames: WorkFrames,
+}
+
 impl Future for WorkResponseFuture {
     type Output = WorkResponse;
 
-    fn poll(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Self::Output> {
-        if let Some(response) = self.response.take() {
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        loop {
+            let response = match self.frames.poll_frame(cx.waker()) {
+                Poll::Ready(Some(Ok(WorkFrame::Done(response)))) => response,
+                Poll::Ready(Some(Ok(WorkFrame::Partial { .. }))) => continue,
+                Poll::Ready(Some(Err(err))) => WorkResponse::Error(err),
+                Poll::Ready(None) => WorkResponse::Error(WorkError::WorkerDisconnected),
+                Poll::Pending => return Poll::Pending,
+            };
             return Poll::Ready(response);
         }
+    }
+}
 
-        let Some(handle) = self.handle.as_mut() else {
-            return Poll::Ready(WorkResponse::Error(WorkError::WorkerDisconnected));
-        };
+/// Responses to the items of a batch, received as each one finishes.
+///
+/// Dropping it stops waiting; the native worker then skips the rest of the
+/// batch.
+struct BatchResponses {
+    frames: WorkFrames,
+}
 
-        match handle.poll() {
-            Ok(Some(response)) => Poll::Ready(response),
-            Ok(None) => Poll::Pending,
-            Err(err) => Poll::Ready(WorkResponse::Error(err)),
-        }
+impl BatchResponses {
+    /// Waits for the next item's response, or `None` once all are answered.
+    async fn next(&mut self) -> Option<Result<(usize, WorkResponse), WorkError>> {
+        future::poll_fn(|cx| {
+            self.frames
+                .poll_frame(cx.waker())
+                .map(|frame| match frame? {
+                    Ok(WorkFrame::Partial { index, response }) => Some(Ok((index, response))),
+                    Ok(WorkFrame::Done(WorkResponse::BatchDone)) => None,
+                    Ok(WorkFrame::Done(WorkResponse::Error(err))) | Err(err) => Some(Err(err)),
+                    Ok(WorkFrame::Done(_)) => Some(Err(WorkError::UnexpectedResponse)),
+                })
+        })
+        .await
     }
 }
 
 /// Enqueue background work and return a future for the response.
 #[must_use]
 fn send_request(request: WorkRequest) -> WorkResponseFuture {
-    WorkResponseFuture::new(platform::enqueue(request))
+    WorkResponseFuture {
+        frames: WorkFrames::send(request),
+    }
+}
+
+/// Enqueue a batch of background work and return its responses as they arrive.
+#[must_use]
+fn send_batch(requests: Vec<WorkRequest>) -> BatchResponses {
+    BatchResponses {
+        frames: WorkFrames::send(WorkRequest::Batch(requests)),
+    }
+}
+
+/// Whether the worker can take requests without a first-use delay.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum Readiness {
+    #[default]
+    WarmingUp,
+    Ready,
+}
+
+impl Readiness {
+    #[must_use]
+    pub(crate) fn is_ready(self) -> bool {
+        self == Self::Ready
+    }
+}
+
+/// Progress of the warm-up request started by [`warm_up`].
+enum WarmUp {
+    NotStarted,
+    Running(Box<WorkResponseFuture>),
+    Done,
+}
+
+thread_local! {
+    static WARM_UP: RefCell<WarmUp> = const { RefCell::new(WarmUp::NotStarted) };
+}
+
+/// Starts the worker and warms it up, unless that already happened.
+///
+/// Poll [`readiness`] to learn when the worker can take requests without a
+/// first-use delay.
+pub(crate) fn warm_up() {
+    WARM_UP.with_borrow_mut(|warm_up| {
+        if matches!(warm_up, WarmUp::NotStarted) {
+            *warm_up = WarmUp::Running(Box::new(send_request(WorkRequest::WarmUp)));
+        }
+    });
+}
+
+/// Returns whether the worker finished warming up, starting it if needed.
+///
+/// A failed warm-up still counts as ready: requests then report the failure
+/// themselves instead of waiting forever.
+#[must_use]
+pub(crate) fn readiness() -> Readiness {
+    warm_up();
+    WARM_UP.with_borrow_mut(|warm_up| {
+        let WarmUp::Running(future) = warm_up else {
+            return Readiness::Ready;
+        };
+        let Poll::Ready(response) = Pin::new(future).poll(&mut Context::from_waker(Waker::noop()))
+        else {
+            return Readiness::WarmingUp;
+        };
+        match response {
+            WorkResponse::WarmedUp => log::info!("worker is ready"),
+            WorkResponse::Error(err) => log::warn!("worker warm-up failed: {err}"),
+            response => log::warn!("unexpected warm-up response: {response:?}"),
+        }
+        *warm_up = WarmUp::Done;
+        Readiness::Ready
+    })
 }
 
 /// Enqueue background work for a generated puzzle and return the DTO.
@@ -162,21 +472,265 @@ pub(crate) async fn request_generate_puzzle(
 
 /// Enqueue background work for solvability check and return the state.
 pub(crate) async fn request_solvability(
-    grid: CandidateGridPairDto,
+    request: SolvabilityRequestDto,
 ) -> Result<SolvabilityResultDto, WorkError> {
-    match send_request(WorkRequest::CheckSolvability(grid)).await {
+    match send_request(WorkRequest::CheckSolvability(request)).await {
         WorkResponse::SolvabilityReady(state) => Ok(state),
         WorkResponse::Error(err) => Err(err),
         _ => Err(WorkError::UnexpectedResponse),
     }
 }
 
+/// Enqueue background work scanning undo history for a solvable state.
+///
+/// The history is checked in chunks of [`UNDO_SCAN_CHUNK_LEN`] states sent as
+/// one batch, newest first. The batch answers its chunks in order, so the
+/// first chunk that finds a solvable state holds the answer and the rest is not
+/// awaited.
 pub(crate) async fn request_solvability_undo_scan(
     undo_grids: CandidateGridPairsDto,
 ) -> Result<SolvabilityUndoScanResultDto, WorkError> {
-    match send_request(WorkRequest::CheckSolvabilityUndoScan(undo_grids)).await {
-        WorkResponse::SolvabilityUndoScanReady(result) => Ok(result),
+    let chunks = undo_grids
+        .grids
+        .chunks(UNDO_SCAN_CHUNK_LEN)
+        .map(|grids| {
+            WorkRequest::CheckSolvabilityUndoScan(CandidateGridPairsDto {
+                grids: grids.to_vec(),
+                monotone: undo_grids.monotone,
+                cages: undo_grids.cages.clone(),
+                edges: undo_grids.edges.clone(),
+                anti_knight: undo_grids.anti_knight,
+                anti_king: undo_grids.anti_king,
+            })
+        })
+        .collect();
+    let mut responses = send_batch(chunks);
+    while let Some(response) = responses.next().await {
+        match response? {
+            (
+                chunk,
+                WorkResponse::SolvabilityUndoScanReady(SolvabilityUndoScanResultDto {
+                    index: Some(index),
+                    state,
+                }),
+            ) => {
+                return Ok(SolvabilityUndoScanResultDto {
+                    index: Some(chunk * UNDO_SCAN_CHUNK_LEN + index),
+                    state,
+                });
+            }
+            (_, WorkResponse::SolvabilityUndoScanReady(_)) => {}
+            (_, WorkResponse::Error(err)) => return Err(err),
+            _ => return Err(WorkError::UnexpectedResponse),
+        }
+    }
+    Ok(SolvabilityUndoScanResultDto::not_found())
+}
+
+/// Enqueue background work for grading a puzzle and return the grade.
+pub(crate) async fn request_grade_puzzle(
+    request: GradePuzzleRequestDto,
+) -> Result<PuzzleGradeDto, WorkError> {
+    match send_request(WorkRequest::GradePuzzle(request)).await {
+        WorkResponse::PuzzleGraded(grade) => Ok(grade),
+        WorkResponse::Error(err) => Err(err),
+        _ => Err(WorkError::UnexpectedResponse),
+    }
+}
+
+/// Enqueue background work for the hardness overlay and return the depths.
+pub(crate) async fn request_deduction_depths(
+    request: DeductionDepthsRequestDto,
+) -> Result<Option<DeductionDepthsDto>, WorkError> {
+    match send_request(WorkRequest::AnalyzeDeductionDepths(request)).await {
+        WorkResponse::DeductionDepthsReady(depths) => Ok(depths),
         WorkResponse::Error(err) => Err(err),
         _ => Err(WorkError::UnexpectedResponse),
     }
 }
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use std::{
+        ops::ControlFlow,
+        pin::pin,
+        sync::{
+            Arc,
+            atomic::{AtomicBool, Ordering},
+        },
+        task::{Context, Poll, Wake, Waker},
+        thread,
+        time::{Duration, Instant},
+    };
+
+    use numelace_core::DigitGrid;
+    use numelace_game::Game;
+
+    use super::{
+        RETRY_BACKOFF, WorkError, WorkFrame, WorkRequest, WorkResponse, has_pending_requests,
+        readiness, request_solvability_undo_scan, retry_delay, send_request,
+    };
+    use crate::worker::tasks::{CandidateGridPairsDto, SolvabilityResultDto};
+
+    const SOLUTION: &str =
+        "185362947793148526246795183564239871931874265827516394318427659672951438459683712";
+
+    fn game_with_first_row(filled_row: &str) -> Game {
+        let problem: DigitGrid = format!("{}{}", ".".repeat(9), &SOLUTION[9..])
+            .parse()
+            .unwrap();
+        let solution: DigitGrid = SOLUTION.parse().unwrap();
+        let filled: DigitGrid = format!("{filled_row}{}", ".".repeat(72)).parse().unwrap();
+        let notes = [[0u16; 9]; 9];
+        Game::from_problem_filled_notes(&problem, &solution, &filled, &notes).unwrap()
+    }
+
+    #[test]
+    fn batch_answers_each_item_then_finishes() {
+        let empty_scan =
+            || WorkRequest::CheckSolvabilityUndoScan(CandidateGridPairsDto::from(vec![]));
+        let batch =
+            WorkRequest::Batch(vec![empty_scan(), WorkRequest::Batch(vec![]), empty_scan()]);
+
+        let mut frames = vec![];
+        batch.handle_frames(|frame| {
+            frames.push(frame);
+            ControlFlow::Continue(())
+        });
+
+        assert_eq!(frames.len(), 4);
+        assert!(matches!(
+            frames[0],
+            WorkFrame::Partial {
+                index: 0,
+                response: WorkResponse::SolvabilityUndoScanReady(_),
+            }
+        ));
+        assert!(matches!(
+            frames[1],
+            WorkFrame::Partial {
+                index: 1,
+                response: WorkResponse::Error(WorkError::NestedBatch),
+            }
+        ));
+        assert!(matches!(frames[2], WorkFrame::Partial { index: 2, .. }));
+        assert!(matches!(
+            frames[3],
+            WorkFrame::Done(WorkResponse::BatchDone)
+        ));
+    }
+
+    #[test]
+    fn error_frames_keep_the_batch_index() {
+        let partial = WorkFrame::Partial {
+            index: 3,
+            response: WorkResponse::WarmedUp,
+        };
+        assert!(matches!(
+            partial.into_error(WorkError::SerializationFailed),
+            WorkFrame::Partial {
+                index: 3,
+                response: WorkResponse::Error(WorkError::SerializationFailed),
+            }
+        ));
+        assert!(matches!(
+            WorkFrame::Done(WorkResponse::BatchDone).into_error(WorkError::SerializationFailed),
+            WorkFrame::Done(WorkResponse::Error(WorkError::SerializationFailed))
+        ));
+    }
+
+    #[test]
+    fn batch_stops_when_emit_breaks() {
+        let scan = || WorkRequest::CheckSolvabilityUndoScan(CandidateGridPairsDto::from(vec![]));
+        let batch = WorkRequest::Batch(vec![scan(), scan(), scan()]);
+
+        let mut count = 0;
+        batch.handle_frames(|_frame| {
+            count += 1;
+            ControlFlow::Break(())
+        });
+
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn warm_up_generates_a_puzzle_and_reports_readiness() {
+        assert!(matches!(
+            WorkRequest::WarmUp.handle(),
+            WorkResponse::WarmedUp
+        ));
+
+        let start = Instant::now();
+        while !readiness().is_ready() {
+            assert!(
+                start.elapsed() < Duration::from_secs(60),
+                "warm-up timed out"
+            );
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    struct FlagWaker(AtomicBool);
+
+    impl Wake for FlagWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn responses_wake_the_polling_task() {
+        let flag = Arc::new(FlagWaker(AtomicBool::new(false)));
+        let waker = Waker::from(Arc::clone(&flag));
+        let mut cx = Context::from_waker(&waker);
+
+        let mut future = pin!(send_request(WorkRequest::CheckSolvabilityUndoScan(
+            CandidateGridPairsDto::from(vec![])
+        )));
+        assert!(has_pending_requests());
+        let start = Instant::now();
+        let response = loop {
+            if let Poll::Ready(response) = future.as_mut().poll(&mut cx) {
+                break response;
+            }
+            while !flag.0.swap(false, Ordering::SeqCst) {
+                assert!(start.elapsed() < Duration::from_secs(60), "never woken");
+                thread::sleep(Duration::from_millis(1));
+            }
+        };
+        assert!(matches!(
+            response,
+            WorkResponse::SolvabilityUndoScanReady(_)
+        ));
+    }
+
+    #[test]
+    fn retry_delays_double_until_retries_run_out() {
+        assert_eq!(retry_delay(0), Some(RETRY_BACKOFF));
+        assert_eq!(retry_delay(1), Some(RETRY_BACKOFF * 2));
+        assert_eq!(retry_delay(2), Some(RETRY_BACKOFF * 4));
+        assert_eq!(retry_delay(3), None);
+    }
+
+    #[test]
+    fn undo_scan_finds_minimal_undo_steps_across_chunks() {
+        // Newest first: the ten latest states contradict the given 8 at r6c1.
+        let mut history = vec![game_with_first_row("8........"); 10];
+        history.extend(vec![game_with_first_row("........."); 3]);
+
+        let mut cx = Context::from_waker(Waker::noop());
+        let mut scan = pin!(request_solvability_undo_scan(history.into()));
+        let result = loop {
+            if let Poll::Ready(result) = scan.as_mut().poll(&mut cx) {
+                break result.unwrap();
+            }
+            thread::sleep(Duration::from_millis(1));
+        };
+
+        assert_eq!(result.index, Some(10));
+        assert!(matches!(
+            result.state,
+            SolvabilityResultDto::Solvable { .. }
+        ));
+    }
+}
@@ -1,19 +1,36 @@
 //! Native async work backend using a background thread and channel.
-use std::sync::{OnceLock, mpsc};
+use std::{
+    ops::ControlFlow,
+    sync::{Arc, Mutex, MutexGuard, PoisonError, mpsc},
+    task::Waker,
+};
 
-use super::super::{WorkError, WorkRequest, WorkResponse};
+use super::super::{WorkError, WorkFrame, WorkRequest};
 
 struct WorkRequestEnvelope {
     request: WorkRequest,
-    response_tx: mpsc::Sender<WorkResponse>,
+    frame_tx: mpsc::Sender<WorkFrame>,
+    waker: WakerSlot,
 }
 
-// Shared worker thread sender reused across requests.
-static WORKER_SENDER: OnceLock<mpsc::Sender<WorkRequestEnvelope>> = OnceLock::new();
+/// Waker of the task polling a request, woken by the worker thread after
+/// each frame.
+type WakerSlot = Arc<Mutex<Option<Waker>>>;
+
+fn wake(slot: &WakerSlot) {
+    let waker = slot.lock().unwrap_or_else(PoisonError::into_inner).take();
+    if let Some(waker) = waker {
+        waker.wake();
+    }
+}
+
+// Shared worker thread sender reused across requests. Replaced if the worker thread dies.
+static WORKER_SENDER: Mutex<Option<mpsc::Sender<WorkRequestEnvelope>>> = Mutex::new(None);
 
 /// A handle for polling background work completion.
 pub(crate) struct WorkHandle {
-    receiver: mpsc::Receiver<WorkResponse>,
+    receiver: mpsc::Receiver<WorkFrame>,
+    waker: WakerSlot,
 }
 
 impl std::fmt::Debug for WorkHandle {
@@ -23,56 +40,85 @@ impl std::fmt::Debug for WorkHandle {
 }
 
 impl WorkHandle {
-    /// Attempts to poll for a completed response.
-    pub(crate) fn poll(&mut self) -> Result<Option<WorkResponse>, WorkError> {
+    /// Attempts to poll for the next response frame.
+    ///
+    /// `waker` is woken when the next frame arrives or the worker thread
+    /// stops.
+    pub(crate) fn poll(&mut self, waker: &Waker) -> Result<Option<WorkFrame>, WorkError> {
         use mpsc::TryRecvError;
 
+        // Registered before receiving, so a frame sent in between still wakes.
+        *self.waker.lock().unwrap_or_else(PoisonError::into_inner) = Some(waker.clone());
         match self.receiver.try_recv() {
-            Ok(response) => Ok(Some(response)),
+            Ok(frame) => Ok(Some(frame)),
             Err(TryRecvError::Empty) => Ok(None),
             Err(TryRecvError::Disconnected) => Err(WorkError::WorkerDisconnected),
         }
     }
 }
 
-/// Starts the shared worker thread without sending a request.
-#[expect(clippy::unnecessary_wraps)]
-pub(crate) fn warm_up() -> Result<(), WorkError> {
-    let _ = WORKER_SENDER.get_or_init(|| {
-        let (tx, rx) = mpsc::channel::<WorkRequestEnvelope>();
-        std::thread::spawn(move || {
-            while let Ok(envelope) = rx.recv() {
-                let response = envelope.request.handle();
-                let _ = envelope.response_tx.send(response);
-            }
-        });
-        tx
-    });
-    Ok(())
-}
-
 /// Enqueues a background task on the shared worker thread and returns a handle for polling completion.
+///
+/// If the worker thread has died (e.g. a task panicked), a new one is started
+/// so that retrying the request can succeed.
 pub(crate) fn enqueue(request: WorkRequest) -> Result<WorkHandle, WorkError> {
-    let worker_tx = WORKER_SENDER.get_or_init(|| {
-        let (tx, rx) = mpsc::channel::<WorkRequestEnvelope>();
-        std::thread::spawn(move || {
-            while let Ok(envelope) = rx.recv() {
-                let response = envelope.request.handle();
-                let _ = envelope.response_tx.send(response);
-            }
-        });
-        tx
-    });
+    let (frame_tx, frame_rx) = mpsc::channel();
+    let waker = WakerSlot::default();
+    let envelope = WorkRequestEnvelope {
+        request,
+        frame_tx,
+        waker: Arc::clone(&waker),
+    };
 
-    let (response_tx, response_rx) = mpsc::channel();
-    worker_tx
-        .send(WorkRequestEnvelope {
-            request,
-            response_tx,
-        })
-        .map_err(|_| WorkError::WorkerDisconnected)?;
+    let mut sender = lock_sender();
+    if let Err(mpsc::SendError(envelope)) = sender.get_or_insert_with(spawn_worker).send(envelope) {
+        log::warn!("worker thread stopped; starting a new one");
+        sender
+            .insert(spawn_worker())
+            .send(envelope)
+            .map_err(|_| WorkError::WorkerDisconnected)?;
+    }
 
     Ok(WorkHandle {
-        receiver: response_rx,
+        receiver: frame_rx,
+        waker,
     })
 }
+
+/// Wakes a [`WakerSlot`] when dropped, including during unwinding.
+struct WakeOnDrop(WakerSlot);
+
+impl Drop for WakeOnDrop {
+    fn drop(&mut self) {
+        wake(&self.0);
+    }
+}
+
+fn lock_sender() -> MutexGuard<'static, Option<mpsc::Sender<WorkRequestEnvelope>>> {
+    WORKER_SENDER.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn spawn_worker() -> mpsc::Sender<WorkRequestEnvelope> {
+    let (tx, rx) = mpsc::channel::<WorkRequestEnvelope>();
+    std::thread::spawn(move || {
+        while let Ok(envelope) = rx.recv() {
+            // Declared first so it is dropped after the sender: a task that
+            // panics still wakes the handle, which then sees the disconnection.
+            let _wake_on_exit = WakeOnDrop(Arc::clone(&envelope.waker));
+            let WorkRequestEnvelope {
+                request,
+                frame_tx,
+                waker,
+            } = envelope;
+            request.handle_frames(|frame| match frame_tx.send(frame) {
+                Ok(()) => {
+                    wake(&waker);
+                    ControlFlow::Continue(())
+                }
+                // The handle was dropped; nobody waits for the rest.
+                Err(_) => ControlFlow::Break(()),
+            });
+        }
+    });
+    tx
+}
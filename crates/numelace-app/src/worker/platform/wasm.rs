@@ -1,21 +1,34 @@
 //! WASM async work backend.
 //!
 //! This module owns the web worker integration and keeps the main thread responsive
-//! during background puzzle generation. Failures are treated as internal errors and
-//! trigger a panic via the worker error handler.
+//! during background puzzle generation. A worker that reports an error is
+//! considered dead: its pending requests fail with
+//! [`WorkError::WorkerDisconnected`] and it is replaced on the next request.
+//! A version mismatch between app and worker is an internal error and panics.
+//!
+//! Requests go to a small pool of workers, spawned on demand: an idle worker
+//! if there is one, otherwise a new one up to the pool size, otherwise the
+//! least busy one. Messages are CBOR bytes whose buffers are transferred.
 
-use std::{cell::RefCell, collections::VecDeque, rc::Rc};
+use std::{
+    cell::{Cell, RefCell},
+    collections::VecDeque,
+    rc::Rc,
+    task::Waker,
+};
 
+use js_sys::{Array, Uint8Array};
 use wasm_bindgen::{JsCast, closure::Closure};
 use web_sys::{Event, MessageEvent, Url, Worker};
 
-use super::super::{WorkError, WorkRequest, WorkResponse};
+use super::super::{WorkError, WorkFrame, WorkRequest, codec};
 use crate::version;
 
 /// A handle for polling background work completion.
 pub(crate) struct WorkHandle {
-    response: Rc<RefCell<Option<WorkResponse>>>,
+    frames: Rc<RefCell<VecDeque<WorkFrame>>>,
     error: Rc<RefCell<Option<WorkError>>>,
+    waker: Rc<RefCell<Option<Waker>>>,
 }
 
 impl std::fmt::Debug for WorkHandle {
@@ -25,19 +38,48 @@ impl std::fmt::Debug for WorkHandle {
 }
 
 impl WorkHandle {
-    /// Attempts to poll for a completed response.
-    pub(crate) fn poll(&mut self) -> Result<Option<WorkResponse>, WorkError> {
+    /// Attempts to poll for the next response frame.
+    ///
+    /// `waker` is woken when the next frame or an error arrives.
+    pub(crate) fn poll(&mut self, waker: &Waker) -> Result<Option<WorkFrame>, WorkError> {
+        self.waker.replace(Some(waker.clone()));
+        if let Some(frame) = self.frames.borrow_mut().pop_front() {
+            return Ok(Some(frame));
+        }
         if let Some(err) = self.error.borrow_mut().take() {
             return Err(err);
         }
-
-        Ok(self.response.borrow_mut().take())
+        Ok(None)
     }
 }
 
+/// Receiving end of a request whose `Done` frame has not arrived yet.
+///
+/// The worker answers requests in order, so frames always belong to the
+/// oldest slot.
 struct PendingSlot {
-    response: Rc<RefCell<Option<WorkResponse>>>,
+    frames: Rc<RefCell<VecDeque<WorkFrame>>>,
     error: Rc<RefCell<Option<WorkError>>>,
+    waker: Rc<RefCell<Option<Waker>>>,
+}
+
+impl PendingSlot {
+    fn push(&self, frame: WorkFrame) {
+        self.frames.borrow_mut().push_back(frame);
+        self.wake();
+    }
+
+    fn fail(&self, err: WorkError) {
+        *self.error.borrow_mut() = Some(err);
+        self.wake();
+    }
+
+    /// Wakes the task waiting on this request, if it has polled it.
+    fn wake(&self) {
+        if let Some(waker) = self.waker.take() {
+            waker.wake();
+        }
+    }
 }
 
 struct SharedWorker {
@@ -45,6 +87,7 @@ struct SharedWorker {
     pending: Rc<RefCell<VecDeque<PendingSlot>>>,
     ready: Rc<RefCell<bool>>,
     pending_requests: Rc<RefCell<VecDeque<WorkRequest>>>,
+    alive: Rc<Cell<bool>>,
     _onmessage: Closure<dyn FnMut(MessageEvent)>,
     _onerror: Closure<dyn FnMut(Event)>,
 }
@@ -70,7 +113,7 @@ impl SharedWorker {
                 let Ok(worker_version) = serde_wasm_bindgen::from_value::<String>(event.data())
                 else {
                     for slot in pending_for_error_for_message.borrow().iter() {
-                        *slot.error.borrow_mut() = Some(WorkError::DeserializationFailed);
+                        slot.fail(WorkError::DeserializationFailed);
                     }
                     return;
                 };
@@ -85,15 +128,9 @@ impl SharedWorker {
                 );
 
                 while let Some(request) = pending_requests_for_message.borrow_mut().pop_front() {
-                    let Ok(payload) = serde_wasm_bindgen::to_value(&request) else {
-                        for slot in pending_for_error_for_message.borrow().iter() {
-                            *slot.error.borrow_mut() = Some(WorkError::SerializationFailed);
-                        }
-                        return;
-                    };
-                    if worker_for_message.post_message(&payload).is_err() {
+                    if let Err(err) = post_request(&worker_for_message, &request) {
                         for slot in pending_for_error_for_message.borrow().iter() {
-                            *slot.error.borrow_mut() = Some(WorkError::WorkerDisconnected);
+                            slot.fail(err.clone());
                         }
                         return;
                     }
@@ -101,24 +138,37 @@ impl SharedWorker {
                 return;
             }
 
-            let Some(slot) = pending_for_message.borrow_mut().pop_front() else {
+            let mut pending = pending_for_message.borrow_mut();
+            let Some(slot) = pending.front() else {
                 return;
             };
 
-            let value = event.data();
-            match serde_wasm_bindgen::from_value::<WorkResponse>(value) {
-                Ok(resp) => {
-                    *slot.response.borrow_mut() = Some(resp);
-                }
-                Err(_) => {
-                    *slot.error.borrow_mut() = Some(WorkError::DeserializationFailed);
-                }
+            let frame = event
+                .data()
+                .dyn_into::<Uint8Array>()
+                .map_err(|_| WorkError::DeserializationFailed)
+                .and_then(|bytes| codec::decode::<WorkFrame>(&bytes.to_vec()));
+            let Ok(frame) = frame else {
+                // Without the frame we cannot tell whether more follow, so
+                // give up on the request.
+                slot.fail(WorkError::DeserializationFailed);
+                pending.pop_front();
+                return;
+            };
+            let done = matches!(frame, WorkFrame::Done(_));
+            slot.push(frame);
+            if done {
+                pending.pop_front();
             }
         }) as Box<dyn FnMut(MessageEvent)>);
 
+        let alive = Rc::new(Cell::new(true));
+        let alive_for_error = Rc::clone(&alive);
         let onerror = Closure::wrap(Box::new(move |_event: Event| -> () {
-            for slot in pending_for_error.borrow().iter() {
-                *slot.error.borrow_mut() = Some(WorkError::WorkerDisconnected);
+            log::warn!("worker failed; it will be replaced");
+            alive_for_error.set(false);
+            for slot in pending_for_error.borrow_mut().drain(..) {
+                slot.fail(WorkError::WorkerDisconnected);
             }
         }) as Box<dyn FnMut(Event)>);
 
@@ -130,51 +180,87 @@ impl SharedWorker {
             pending,
             ready,
             pending_requests,
+            alive,
             _onmessage: onmessage,
             _onerror: onerror,
         })
     }
 
     fn send(&mut self, request: &WorkRequest) -> Result<(), WorkError> {
-        let payload =
-            serde_wasm_bindgen::to_value(&request).map_err(|_| WorkError::SerializationFailed)?;
-        self.worker
-            .post_message(&payload)
-            .map_err(|_| WorkError::SerializationFailed)?;
-        Ok(())
+        post_request(&self.worker, request)
     }
+
+    /// Returns whether the worker has no unanswered requests.
+    fn is_idle(&self) -> bool {
+        self.pending.borrow().is_empty()
+    }
+
+    /// Returns whether the worker has not reported an error.
+    fn is_alive(&self) -> bool {
+        self.alive.get()
+    }
+}
+
+impl Drop for SharedWorker {
+    fn drop(&mut self) {
+        self.worker.terminate();
+    }
+}
+
+/// Encodes `request` and transfers its bytes to `worker`.
+fn post_request(worker: &Worker, request: &WorkRequest) -> Result<(), WorkError> {
+    let bytes = Uint8Array::from(codec::encode(request)?.as_slice());
+    // A byte buffer always transfers, so a failure means the worker is gone.
+    worker
+        .post_message_with_transfer(&bytes, &Array::of1(&bytes.buffer()))
+        .map_err(|_| WorkError::WorkerDisconnected)
+}
+
+/// Upper bound on the number of workers.
+const MAX_WORKERS: usize = 4;
+
+/// Returns the number of workers to run, leaving a core for the UI thread.
+#[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn pool_size() -> usize {
+    let cores = web_sys::window().map_or(1.0, |window| window.navigator().hardware_concurrency());
+    (cores as usize).saturating_sub(1).clamp(1, MAX_WORKERS)
 }
 
 thread_local! {
-    static SHARED_WORKER: RefCell<Option<SharedWorker>> = const { RefCell::new(None) };
+    static WORKER_POOL: RefCell<Vec<SharedWorker>> = const { RefCell::new(Vec::new()) };
 }
 
+/// Runs `f` on the worker that should take the next request.
 fn with_worker<F, R>(f: F) -> Result<R, WorkError>
 where
     F: FnOnce(&mut SharedWorker) -> Result<R, WorkError>,
 {
-    SHARED_WORKER.with(|cell| {
-        let mut guard = cell.borrow_mut();
-        if guard.is_none() {
-            *guard = Some(SharedWorker::new()?);
-        }
-        let worker = guard.as_mut().expect("worker should be initialized");
-        f(worker)
+    WORKER_POOL.with(|cell| {
+        let mut workers = cell.borrow_mut();
+        workers.retain(SharedWorker::is_alive);
+        let index = match workers.iter().position(SharedWorker::is_idle) {
+            Some(index) => index,
+            None if workers.len() < pool_size() => {
+                workers.push(SharedWorker::new()?);
+                workers.len() - 1
+            }
+            None => (0..workers.len())
+                .min_by_key(|&index| workers[index].pending.borrow().len())
+                .expect("pool should not be empty"),
+        };
+        f(&mut workers[index])
     })
 }
 
-/// Starts the shared worker without sending a request.
-pub(crate) fn warm_up() -> Result<(), WorkError> {
-    with_worker(|_worker| Ok(()))
-}
-
 /// Enqueues a background task and returns a handle for polling completion.
 pub(crate) fn enqueue(request: WorkRequest) -> Result<WorkHandle, WorkError> {
-    let response = Rc::new(RefCell::new(None));
+    let frames = Rc::new(RefCell::new(VecDeque::new()));
     let error = Rc::new(RefCell::new(None));
+    let waker = Rc::new(RefCell::new(None));
     let slot = PendingSlot {
-        response: Rc::clone(&response),
+        frames: Rc::clone(&frames),
         error: Rc::clone(&error),
+        waker: Rc::clone(&waker),
     };
 
     with_worker(|worker| {
@@ -185,7 +271,11 @@ pub(crate) fn enqueue(request: WorkRequest) -> Result<WorkHandle, WorkError> {
             worker.pending_requests.borrow_mut().push_back(request);
         }
 
-        Ok(WorkHandle { response, error })
+        Ok(WorkHandle {
+            frames,
+            error,
+            waker,
+        })
     })
 }
 
@@ -0,0 +1,173 @@
+//! Trial depth analysis for the hardness overlay.
+
+use numelace_core::{
+    Cage, CageSet, CandidateGrid, ChessRules, EdgeConstraint, EdgeConstraintSet, Position,
+    PositionIndexedArray,
+};
+use numelace_game::Game;
+use numelace_solver::{DeductionDepthAnalyzer, SolverBudget, TechniqueGrid};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    persistence::dto::{CageDto, EdgeConstraintDto},
+    worker::tasks::{CandidateGridDto, CandidateGridDtoError},
+};
+
+/// Trial limit of each analysis.
+///
+/// Depth 2 takes a few thousand trials on the hardest known puzzles; a board
+/// that needs more is left without an overlay.
+const DEDUCTION_DEPTH_BUDGET: SolverBudget = SolverBudget::new().with_max_steps(20_000);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct DeductionDepthsRequestDto {
+    /// Candidates of the board, without the player's notes.
+    pub(crate) grid: CandidateGridDto,
+    /// Killer cages the trials have to satisfy.
+    pub(crate) cages: Vec<CageDto>,
+    /// Greater-than and consecutive markers the trials have to satisfy.
+    pub(crate) edges: Vec<EdgeConstraintDto>,
+    /// Whether cells a knight's move apart must differ.
+    pub(crate) anti_knight: bool,
+    /// Whether cells a king's move apart must differ.
+    pub(crate) anti_king: bool,
+}
+
+impl From<&Game> for DeductionDepthsRequestDto {
+    fn from(game: &Game) -> Self {
+        Self {
+            grid: game.to_candidate_grid().into(),
+            cages: game.cages().iter().map(CageDto::from).collect(),
+            edges: game
+                .edge_constraints()
+                .iter()
+                .map(EdgeConstraintDto::from)
+                .collect(),
+            anti_knight: game.chess_rules().anti_knight(),
+            anti_king: game.chess_rules().anti_king(),
+        }
+    }
+}
+
+/// Overlay value of every cell, ordered by `Position::ALL`.
+///
+/// Each value is the depth at which the cell was decided, `0` for cells that
+/// already had a single candidate, and one more than the depth limit for
+/// cells the analysis left undecided.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct DeductionDepthsDto {
+    pub(crate) depths: Vec<u8>,
+}
+
+impl TryFrom<DeductionDepthsDto> for PositionIndexedArray<u8> {
+    type Error = CandidateGridDtoError;
+
+    fn try_from(dto: DeductionDepthsDto) -> Result<Self, Self::Error> {
+        let len = dto.depths.len();
+        let depths = <[u8; 81]>::try_from(dto.depths)
+            .map_err(|_| CandidateGridDtoError::InvalidCandidateLength { len })?;
+        Ok(Self::from_array(depths))
+    }
+}
+
+/// Analyzes how deep a trial each cell of the request needs.
+///
+/// Returns `Ok(None)` for boards whose entries contradict each other and when
+/// the analysis runs out of budget.
+///
+/// # Errors
+///
+/// Returns an error if the request cannot be decoded.
+pub(crate) fn analyze_deduction_depths(
+    request: DeductionDepthsRequestDto,
+) -> Result<Option<DeductionDepthsDto>, CandidateGridDtoError> {
+    let grid = CandidateGrid::try_from(request.grid)?;
+    let cages = request
+        .cages
+        .into_iter()
+        .map(Cage::try_from)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| CandidateGridDtoError::InvalidCages)?;
+    let cages = CageSet::from_cages(cages).map_err(|_| CandidateGridDtoError::InvalidCages)?;
+    let edges = request
+        .edges
+        .into_iter()
+        .map(EdgeConstraint::try_from)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| CandidateGridDtoError::InvalidEdgeConstraints)?;
+    let edges = EdgeConstraintSet::from_constraints(edges)
+        .map_err(|_| CandidateGridDtoError::InvalidEdgeConstraints)?;
+    let chess = ChessRules::new()
+        .with_anti_knight(request.anti_knight)
+        .with_anti_king(request.anti_king);
+
+    let analyzer = DeductionDepthAnalyzer::new()
+        .with_budget(DEDUCTION_DEPTH_BUDGET)
+        .with_constraint(cages)
+        .with_constraint(edges)
+        .with_constraint(chess);
+    let Ok(depths) = analyzer.analyze(&TechniqueGrid::from(grid)) else {
+        return Ok(None);
+    };
+    let beyond = analyzer.max_depth() + 1;
+    let depths = Position::ALL
+        .into_iter()
+        .map(|pos| {
+            let depth = if depths.undecided().contains(pos) {
+                beyond
+            } else {
+                depths.depth(pos).unwrap_or(0)
+            };
+            u8::try_from(depth).unwrap_or(u8::MAX)
+        })
+        .collect();
+    Ok(Some(DeductionDepthsDto { depths }))
+}
+
+#[cfg(test)]
+mod tests {
+    use numelace_core::DigitGrid;
+
+    use super::*;
+
+    fn depths(problem: &str, chess: ChessRules) -> Option<PositionIndexedArray<u8>> {
+        let problem: DigitGrid = problem.parse().unwrap();
+        let notes = [[0u16; 9]; 9];
+        let game =
+            Game::from_problem_filled_notes(&problem, &DigitGrid::new(), &DigitGrid::new(), &notes)
+                .unwrap()
+                .with_chess_rules(chess);
+        analyze_deduction_depths(DeductionDepthsRequestDto::from(&game))
+            .unwrap()
+            .map(|dto| dto.try_into().unwrap())
+    }
+
+    #[test]
+    fn depths_count_the_trials_each_cell_needs() {
+        // A 17-clue puzzle that singles cannot start but one trial settles.
+        let depths = depths(
+            "4.....8.5.3..........7......2.....6.....8.4......1.......6.3.7.5..2.....1.4......",
+            ChessRules::new(),
+        )
+        .unwrap();
+
+        assert_eq!(depths[Position::new(0, 0)], 0);
+        assert_eq!(depths[Position::new(0, 1)], 1);
+    }
+
+    #[test]
+    fn depths_respect_chess_rules() {
+        // An anti-knight solution with a unique rectangle cleared: only the
+        // knight's moves rule out swapping its digits.
+        let problem =
+            "839246571512..9648764518932345961827281..5469976482315157693284628154793493827156";
+        let corner = Position::new(1, 3);
+        let beyond = u8::try_from(DeductionDepthAnalyzer::DEFAULT_MAX_DEPTH + 1).unwrap();
+
+        let plain = depths(problem, ChessRules::new()).unwrap();
+        assert_eq!(plain[corner], beyond);
+
+        let chess = depths(problem, ChessRules::new().with_anti_knight(true)).unwrap();
+        assert_eq!(chess[corner], 0);
+    }
+}
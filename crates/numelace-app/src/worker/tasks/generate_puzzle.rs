@@ -1,20 +1,28 @@
 use std::iter;
 
-use numelace_core::DigitGrid;
-use numelace_generator::{GeneratedPuzzle, PuzzleGenerator, PuzzleSeed};
-use numelace_solver::{TechniqueGrid, TechniqueSolver, TechniqueTier, technique};
+use numelace_core::{ChessRules, DigitGrid, DigitPositions, Position, RegionLayout};
+use numelace_generator::{
+    CarveStep, ClueSymmetry, GeneratedPuzzle, GenerationTrace, PuzzleGenerator, PuzzleSeed,
+};
+use numelace_solver::{
+    SolverError, TechniqueGrid, TechniqueSolver, TechniqueSolverStats, TechniqueTier, technique,
+};
 use serde::{Deserialize, Serialize};
 
 use crate::worker::tasks::GeneratePuzzleRequestDto;
 
 /// DTO for communicating newly generated Sudoku puzzles over worker boundaries.
 ///
-/// Uses compact 81-char string formats ('.' for empty, '1'..'9' for digits).
+/// Uses compact 81-char string formats ('.' for empty, '1'..'9' for digits;
+/// region numbers '1'..'9' for the layout).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) struct GeneratedPuzzleDto {
     pub(crate) seed: String,
     pub(crate) problem: String,
     pub(crate) solution: String,
+    pub(crate) regions: String,
+    /// Generator trace, when the request asked for one.
+    pub(crate) trace: Option<GenerationTraceDto>,
 }
 
 impl From<GeneratedPuzzle> for GeneratedPuzzleDto {
@@ -23,10 +31,91 @@ impl From<GeneratedPuzzle> for GeneratedPuzzleDto {
             seed: puzzle.seed.to_string(),
             problem: puzzle.problem.to_string(),
             solution: puzzle.solution.to_string(),
+            regions: puzzle.regions.to_string(),
+            trace: None,
         }
     }
 }
 
+/// DTO for a [`GenerationTrace`], with cells as position indices.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct GenerationTraceDto {
+    fill_strategy: String,
+    carve_strategy: String,
+    solution: String,
+    regions: String,
+    steps: Vec<CarveStepDto>,
+    solver_runs: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CarveStepDto {
+    removed: Vec<u8>,
+    kept: bool,
+    entropy: f64,
+}
+
+impl From<GenerationTrace> for GenerationTraceDto {
+    fn from(trace: GenerationTrace) -> Self {
+        let steps = trace
+            .steps
+            .iter()
+            .map(|step| CarveStepDto {
+                removed: step.removed.into_iter().map(Position::index).collect(),
+                kept: step.kept,
+                entropy: step.entropy,
+            })
+            .collect();
+        Self {
+            fill_strategy: trace.fill_strategy,
+            carve_strategy: trace.carve_strategy,
+            solution: trace.solution.to_string(),
+            regions: trace.regions.to_string(),
+            steps,
+            solver_runs: trace.solver_runs,
+        }
+    }
+}
+
+impl TryFrom<GenerationTraceDto> for GenerationTrace {
+    type Error = String;
+
+    fn try_from(value: GenerationTraceDto) -> Result<Self, Self::Error> {
+        let solution = value
+            .solution
+            .parse::<DigitGrid>()
+            .map_err(|e| e.to_string())?;
+        let regions = value
+            .regions
+            .parse::<RegionLayout>()
+            .map_err(|e| e.to_string())?;
+        let steps = value
+            .steps
+            .into_iter()
+            .map(|step| {
+                let removed = step
+                    .removed
+                    .into_iter()
+                    .map(|index| Position::try_from_index(index).map_err(|e| e.to_string()))
+                    .collect::<Result<DigitPositions, _>>()?;
+                Ok(CarveStep {
+                    removed,
+                    kept: step.kept,
+                    entropy: step.entropy,
+                })
+            })
+            .collect::<Result<_, String>>()?;
+        Ok(GenerationTrace {
+            fill_strategy: value.fill_strategy,
+            carve_strategy: value.carve_strategy,
+            solution,
+            regions,
+            steps,
+            solver_runs: value.solver_runs,
+        })
+    }
+}
+
 impl TryFrom<GeneratedPuzzleDto> for GeneratedPuzzle {
     type Error = String;
 
@@ -40,10 +129,15 @@ impl TryFrom<GeneratedPuzzleDto> for GeneratedPuzzle {
             .solution
             .parse::<DigitGrid>()
             .map_err(|e| e.to_string())?;
+        let regions = value
+            .regions
+            .parse::<RegionLayout>()
+            .map_err(|e| e.to_string())?;
         Ok(GeneratedPuzzle {
             seed,
             problem,
             solution,
+            regions,
         })
     }
 }
@@ -56,16 +150,82 @@ pub(crate) fn generate_puzzle(request: &GeneratePuzzleRequestDto) -> GeneratedPu
         }
     }
     let technique_solver = TechniqueSolver::new(techniques);
+    let chess = ChessRules::new()
+        .with_anti_knight(request.anti_knight)
+        .with_anti_king(request.anti_king);
+    let symmetry = request.symmetry.parse().unwrap_or_default();
+    let generator = new_generator(&technique_solver, chess, symmetry, request.jigsaw);
     let puzzle = if request.seed.is_empty() {
-        generate_random_puzzle(&technique_solver, request.max_attempts)
+        generate_random_puzzle(&generator, &technique_solver, chess, request.max_attempts)
     } else {
-        generate_seeded_puzzle(&request.seed, &technique_solver)
+        generator.generate_with_seed(parse_seed(&request.seed))
     };
-    puzzle.into()
+    // The seed reproduces the chosen puzzle, so tracing it again costs one
+    // more generation instead of a trace for every attempt.
+    let trace = request
+        .trace
+        .then(|| generator.generate_with_trace(puzzle.seed).1.into());
+    GeneratedPuzzleDto {
+        trace,
+        ..puzzle.into()
+    }
+}
+
+fn new_generator(
+    technique_solver: &TechniqueSolver,
+    chess: ChessRules,
+    symmetry: ClueSymmetry,
+    jigsaw: bool,
+) -> PuzzleGenerator<'_> {
+    let mut generator = PuzzleGenerator::new(technique_solver).with_symmetry(symmetry);
+    if jigsaw {
+        generator = generator.with_jigsaw_regions();
+    }
+    if chess.is_empty() {
+        generator
+    } else {
+        generator.with_constraint(chess)
+    }
+}
+
+/// Solves `grid` with the techniques, re-applying the chess rules whenever
+/// the techniques get stuck.
+pub(super) fn solve_with_rules(
+    technique_solver: &TechniqueSolver,
+    chess: ChessRules,
+    grid: &mut TechniqueGrid,
+) -> Result<(bool, TechniqueSolverStats), SolverError> {
+    let mut stats = technique_solver.new_stats();
+    loop {
+        let solved = technique_solver.solve_with_step_stats(grid, &mut stats)?;
+        if solved || !grid.prune_with(&chess)? {
+            return Ok((solved, stats));
+        }
+    }
+}
+
+/// Returns the index of the hardest technique applied at least once.
+///
+/// Techniques may be reordered by priority, so this compares tiers rather than
+/// picking the last technique in solver order. Ties go to the later technique.
+pub(super) fn hardest_application(
+    technique_solver: &TechniqueSolver,
+    stats: &TechniqueSolverStats,
+) -> Option<usize> {
+    iter::zip(
+        technique_solver.techniques().iter().map(|t| t.tier()),
+        stats.applications(),
+    )
+    .enumerate()
+    .filter(|(_index, (_tier, app))| **app > 0)
+    .max_by_key(|(_index, (tier, _app))| *tier)
+    .map(|(index, _)| index)
 }
 
 fn generate_random_puzzle(
+    generator: &PuzzleGenerator<'_>,
     technique_solver: &TechniqueSolver,
+    chess: ChessRules,
     max_attempts: usize,
 ) -> GeneratedPuzzle {
     let max_tier = technique_solver
@@ -76,18 +236,23 @@ fn generate_random_puzzle(
         .unwrap();
     let mut best: Option<(TechniqueTier, usize, GeneratedPuzzle)> = None;
     for _ in 0..max_attempts.max(1) {
-        let puzzle = PuzzleGenerator::new(technique_solver).generate();
-        let Ok((true, stats)) =
-            technique_solver.solve_with_step(&mut TechniqueGrid::from_digit_grid(&puzzle.problem))
-        else {
+        let puzzle = generator.generate();
+        let Ok((true, stats)) = solve_with_rules(
+            technique_solver,
+            chess,
+            &mut TechniqueGrid::from_digit_grid_with_regions(&puzzle.problem, puzzle.regions),
+        ) else {
             continue;
         };
-        let (tier, app) = iter::zip(
-            technique_solver.techniques().iter().map(|t| t.tier()),
-            stats.applications().iter().copied(),
-        )
-        .rfind(|(_tech, app)| *app > 0)
-        .unwrap_or((TechniqueTier::Fundamental, 0));
+        let (tier, app) = hardest_application(technique_solver, &stats).map_or(
+            (TechniqueTier::Fundamental, 0),
+            |index| {
+                (
+                    technique_solver.techniques()[index].tier(),
+                    stats.applications()[index],
+                )
+            },
+        );
         if tier >= max_tier {
             return puzzle;
         }
@@ -101,14 +266,72 @@ fn generate_random_puzzle(
     best.unwrap().2
 }
 
-fn generate_seeded_puzzle(seed: &str, technique_solver: &TechniqueSolver) -> GeneratedPuzzle {
-    let seed = parse_seed(seed);
-    PuzzleGenerator::new(technique_solver).generate_with_seed(seed)
-}
-
 fn parse_seed(seed: &str) -> PuzzleSeed {
     if let Ok(seed) = seed.parse() {
         return seed;
     }
     PuzzleSeed::from_arbitrary_bytes(seed.as_bytes())
 }
+
+#[cfg(test)]
+mod tests {
+    use numelace_solver::TechniquePriority;
+
+    use super::*;
+    use crate::state::NewGameOptions;
+
+    #[test]
+    fn traces_survive_the_worker_boundary() {
+        let options = NewGameOptions {
+            seed: "trace".to_owned(),
+            ..NewGameOptions::default()
+        };
+        let request = GeneratePuzzleRequestDto {
+            trace: true,
+            ..GeneratePuzzleRequestDto::new(options, &TechniquePriority::new())
+        };
+
+        let mut dto = generate_puzzle(&request);
+        let trace = GenerationTrace::try_from(dto.trace.take().unwrap()).unwrap();
+        let puzzle = GeneratedPuzzle::try_from(dto).unwrap();
+
+        assert_eq!(trace.solution, puzzle.solution);
+        assert_eq!(trace.problem_after(trace.steps.len()), puzzle.problem);
+        assert!(trace.uniqueness_checks() > 0);
+    }
+
+    #[test]
+    fn jigsaw_layouts_survive_the_worker_boundary() {
+        let options = NewGameOptions {
+            seed: "jigsaw".to_owned(),
+            jigsaw: true,
+            ..NewGameOptions::default()
+        };
+        let request = GeneratePuzzleRequestDto::new(options, &TechniquePriority::new());
+
+        let puzzle = GeneratedPuzzle::try_from(generate_puzzle(&request)).unwrap();
+
+        assert!(!puzzle.regions.is_standard());
+        assert!(puzzle.regions.is_solved(&puzzle.solution));
+    }
+
+    #[test]
+    fn seed_of_a_random_puzzle_generates_it_again() {
+        let options = NewGameOptions {
+            max_attempts: 3,
+            ..NewGameOptions::default()
+        };
+        let priority = TechniquePriority::new();
+        let original = generate_puzzle(&GeneratePuzzleRequestDto::new(options.clone(), &priority));
+
+        let generated_with = NewGameOptions {
+            seed: original.seed.clone(),
+            ..options
+        };
+        let again = generate_puzzle(&GeneratePuzzleRequestDto::new(generated_with, &priority));
+
+        assert_eq!(again.seed, original.seed);
+        assert_eq!(again.problem, original.problem);
+        assert_eq!(again.solution, original.solution);
+    }
+}
@@ -0,0 +1,154 @@
+//! Difficulty grading for puzzles that were not generated locally.
+
+use numelace_core::{ChessRules, DigitGrid, Position, RegionLayout};
+use numelace_game::{CellState, Game};
+use numelace_solver::{
+    TechniqueGrid, TechniquePriority, TechniqueSolver, TechniqueTier, technique,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    state::{DifficultyPreset, PuzzleDifficulty},
+    worker::tasks::generate_puzzle::{hardest_application, solve_with_rules},
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct GradePuzzleRequestDto {
+    /// Givens in the compact 81-char format.
+    pub(crate) problem: String,
+    /// Region layout in the compact 81-char format.
+    pub(crate) regions: String,
+    /// Technique IDs in application order.
+    pub(crate) techniques: Vec<String>,
+    pub(crate) anti_knight: bool,
+    pub(crate) anti_king: bool,
+}
+
+impl GradePuzzleRequestDto {
+    /// Builds a request for grading the givens of `game`.
+    ///
+    /// Returns `None` for puzzles the techniques cannot grade: killer cages
+    /// and edge constraints.
+    #[must_use]
+    pub(crate) fn new(game: &Game, priority: &TechniquePriority) -> Option<Self> {
+        if !game.cages().is_empty() || !game.edge_constraints().is_empty() {
+            return None;
+        }
+        let problem = Position::ALL
+            .into_iter()
+            .map(|pos| match game.cell(pos) {
+                CellState::Given(digit) => char::from(b'0' + digit.value()),
+                CellState::Filled(_) | CellState::Notes(_) | CellState::Empty => '.',
+            })
+            .collect();
+        let chess = game.chess_rules();
+        Some(Self {
+            problem,
+            regions: game.regions().to_string(),
+            techniques: priority
+                .techniques()
+                .iter()
+                .map(|technique| technique.id().to_owned())
+                .collect(),
+            anti_knight: chess.anti_knight(),
+            anti_king: chess.anti_king(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) enum PuzzleGradeDto {
+    /// The techniques solve the puzzle; `hardest_technique` is `None` when no
+    /// technique had to be applied.
+    Solved { hardest_technique: Option<String> },
+    /// The techniques get stuck, so solving requires guessing.
+    BeyondTechniques,
+}
+
+impl From<PuzzleGradeDto> for PuzzleDifficulty {
+    fn from(value: PuzzleGradeDto) -> Self {
+        match value {
+            PuzzleGradeDto::Solved { hardest_technique } => {
+                let tier = hardest_technique
+                    .and_then(|id| technique::find_technique_by_id(&id))
+                    .map_or(TechniqueTier::Fundamental, |technique| technique.tier());
+                Self::Rated(DifficultyPreset::from(tier))
+            }
+            PuzzleGradeDto::BeyondTechniques => Self::BeyondTechniques,
+        }
+    }
+}
+
+pub(crate) fn grade_puzzle(request: &GradePuzzleRequestDto) -> Result<PuzzleGradeDto, String> {
+    let problem = request
+        .problem
+        .parse::<DigitGrid>()
+        .map_err(|e| e.to_string())?;
+    let regions = request
+        .regions
+        .parse::<RegionLayout>()
+        .map_err(|e| e.to_string())?;
+    let techniques = request
+        .techniques
+        .iter()
+        .filter_map(|id| technique::find_technique_by_id(id))
+        .collect();
+    let technique_solver = TechniqueSolver::new(techniques);
+    let chess = ChessRules::new()
+        .with_anti_knight(request.anti_knight)
+        .with_anti_king(request.anti_king);
+
+    let mut grid = TechniqueGrid::from_digit_grid_with_regions(&problem, regions);
+    let grade = match solve_with_rules(&technique_solver, chess, &mut grid) {
+        Ok((true, stats)) => PuzzleGradeDto::Solved {
+            hardest_technique: hardest_application(&technique_solver, &stats)
+                .map(|index| technique_solver.techniques()[index].id().to_owned()),
+        },
+        Ok((false, _)) | Err(_) => PuzzleGradeDto::BeyondTechniques,
+    };
+    Ok(grade)
+}
+
+#[cfg(test)]
+mod tests {
+    use numelace_core::DigitGrid;
+    use numelace_game::Game;
+    use numelace_solver::{TechniquePriority, technique::TechniqueId};
+
+    use super::*;
+
+    const SOLUTION: &str =
+        "185362947793148526246795183564239871931874265827516394318427659672951438459683712";
+
+    fn game(problem: &str) -> Game {
+        let problem: DigitGrid = problem.parse().unwrap();
+        let solution: DigitGrid = SOLUTION.parse().unwrap();
+        let filled: DigitGrid = ".".repeat(81).parse().unwrap();
+        Game::from_problem_filled_notes(&problem, &solution, &filled, &[[0; 9]; 9]).unwrap()
+    }
+
+    fn grade(game: &Game, priority: &TechniquePriority) -> PuzzleGradeDto {
+        let request = GradePuzzleRequestDto::new(game, priority).unwrap();
+        grade_puzzle(&request).unwrap()
+    }
+
+    #[test]
+    fn single_missing_digit_needs_only_singles() {
+        let problem = format!(".{}", &SOLUTION[1..]);
+        let grade = grade(&game(&problem), &TechniquePriority::new());
+        assert!(matches!(
+            grade,
+            PuzzleGradeDto::Solved {
+                hardest_technique: Some(id)
+            } if id == TechniqueId::FullHouse.as_str()
+                || id == TechniqueId::NakedSingle.as_str()
+                || id == TechniqueId::HiddenSingle.as_str()
+        ));
+    }
+
+    #[test]
+    fn empty_board_is_beyond_techniques() {
+        let grade = grade(&game(&".".repeat(81)), &TechniquePriority::new());
+        assert_eq!(grade, PuzzleGradeDto::BeyondTechniques);
+    }
+}
@@ -1,14 +1,23 @@
+pub(crate) mod deduction_depth;
 pub(crate) mod generate_puzzle;
+pub(crate) mod grade_puzzle;
 pub(crate) mod solvability;
+pub(crate) mod warm_up;
 
+pub(crate) use deduction_depth::*;
 pub(crate) use generate_puzzle::*;
-use numelace_core::{CandidateGrid, Digit, DigitSet, Position};
+pub(crate) use grade_puzzle::*;
+use numelace_core::{CandidateGrid, Digit, DigitSet, Position, RegionLayout};
 use numelace_game::Game;
-use numelace_solver::technique;
+use numelace_solver::TechniquePriority;
 use serde::{Deserialize, Serialize};
 pub(crate) use solvability::*;
+pub(crate) use warm_up::*;
 
-use crate::state::NewGameOptions;
+use crate::{
+    persistence::dto::{CageDto, EdgeConstraintDto},
+    state::NewGameOptions,
+};
 
 /// Compact candidate grid DTO.
 ///
@@ -16,6 +25,10 @@ use crate::state::NewGameOptions;
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) struct CandidateGridDto {
     pub(crate) candidates: Vec<u16>,
+    /// Region layout in the compact 81-char format, or `None` for the
+    /// standard boxes.
+    #[serde(default)]
+    pub(crate) regions: Option<String>,
 }
 
 impl From<CandidateGrid> for CandidateGridDto {
@@ -25,7 +38,11 @@ impl From<CandidateGrid> for CandidateGridDto {
             let set = grid.candidates_at(pos);
             candidates.push(set.bits());
         }
-        Self { candidates }
+        let regions = (!grid.regions().is_standard()).then(|| grid.regions().to_string());
+        Self {
+            candidates,
+            regions,
+        }
     }
 }
 
@@ -36,6 +53,14 @@ pub(crate) enum CandidateGridDtoError {
     InvalidCandidateLength { len: usize },
     #[display("invalid candidate bits for {pos:?}: {bits:#05x}")]
     InvalidCandidateBits { pos: Position, bits: u16 },
+    #[display("user digit out of range at index {index}: {digit}")]
+    UserDigitOutOfRange { index: u8, digit: u8 },
+    #[display("invalid killer cages")]
+    InvalidCages,
+    #[display("invalid edge constraints")]
+    InvalidEdgeConstraints,
+    #[display("invalid region layout")]
+    InvalidRegions,
 }
 
 /// Converts a [`CandidateGridDto`] into a [`CandidateGrid`].
@@ -47,6 +72,9 @@ pub(crate) enum CandidateGridDtoError {
 ///
 /// Returns [`CandidateGridDtoError::InvalidCandidateBits`] if any cell contains
 /// invalid bits outside the 9-bit candidate range.
+///
+/// Returns [`CandidateGridDtoError::InvalidRegions`] if the region layout
+/// cannot be parsed.
 impl TryFrom<CandidateGridDto> for CandidateGrid {
     type Error = CandidateGridDtoError;
 
@@ -57,7 +85,13 @@ impl TryFrom<CandidateGridDto> for CandidateGrid {
             });
         }
 
-        let mut grid = CandidateGrid::new();
+        let regions = match dto.regions {
+            Some(regions) => regions
+                .parse::<RegionLayout>()
+                .map_err(|_| CandidateGridDtoError::InvalidRegions)?,
+            None => RegionLayout::standard(),
+        };
+        let mut grid = CandidateGrid::new().with_regions(regions);
         for (idx, pos) in Position::ALL.into_iter().enumerate() {
             let bits = dto.candidates[idx];
             let candidates = DigitSet::try_from_bits(bits)
@@ -97,43 +131,162 @@ impl From<Game> for CandidateGridPairDto {
     }
 }
 
+/// DTO for checking the solvability of the current game.
+///
+/// Besides the candidate grids, it carries the givens and the player's digits
+/// separately so the task can explain a contradiction in terms of the player's
+/// entries.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct SolvabilityRequestDto {
+    pub(crate) grids: CandidateGridPairDto,
+    /// Candidate grid with only the givens placed.
+    pub(crate) givens: CandidateGridDto,
+    /// Digits entered by the player as `(Position::index, digit value)` pairs.
+    pub(crate) user_digits: Vec<(u8, u8)>,
+    /// Killer cages the solutions have to satisfy.
+    pub(crate) cages: Vec<CageDto>,
+    /// Greater-than and consecutive markers the solutions have to satisfy.
+    pub(crate) edges: Vec<EdgeConstraintDto>,
+    /// Whether cells a knight's move apart must differ.
+    pub(crate) anti_knight: bool,
+    /// Whether cells a king's move apart must differ.
+    pub(crate) anti_king: bool,
+}
+
+impl From<&Game> for SolvabilityRequestDto {
+    fn from(game: &Game) -> Self {
+        let mut givens = CandidateGrid::new().with_regions(*game.regions());
+        let mut user_digits = vec![];
+        for pos in Position::ALL {
+            let cell = game.cell(pos);
+            match cell.as_digit() {
+                Some(digit) if cell.is_given() => {
+                    givens.place(pos, digit);
+                }
+                Some(digit) => user_digits.push((pos.index(), digit.value())),
+                None => {}
+            }
+        }
+        Self {
+            grids: game.into(),
+            givens: givens.into(),
+            user_digits,
+            cages: game.cages().iter().map(CageDto::from).collect(),
+            edges: game
+                .edge_constraints()
+                .iter()
+                .map(EdgeConstraintDto::from)
+                .collect(),
+            anti_knight: game.chess_rules().anti_knight(),
+            anti_king: game.chess_rules().anti_king(),
+        }
+    }
+}
+
+/// DTO for scanning undo history, ordered from the current state backwards.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) struct CandidateGridPairsDto {
     pub(crate) grids: Vec<CandidateGridPairDto>,
+    /// Whether every state only adds digits to the one before it.
+    ///
+    /// Only then do the solvable states form a suffix of the history that a
+    /// binary search can find; clearing or overwriting a digit can make an
+    /// older state unsolvable again.
+    #[serde(default)]
+    pub(crate) monotone: bool,
+    /// Killer cages of the puzzle, shared by every state of the history.
+    #[serde(default)]
+    pub(crate) cages: Vec<CageDto>,
+    /// Edge markers of the puzzle, shared by every state of the history.
+    #[serde(default)]
+    pub(crate) edges: Vec<EdgeConstraintDto>,
+    /// Whether the puzzle forbids equal digits a knight's move apart.
+    #[serde(default)]
+    pub(crate) anti_knight: bool,
+    /// Whether the puzzle forbids equal digits a king's move apart.
+    #[serde(default)]
+    pub(crate) anti_king: bool,
 }
 
 impl From<Vec<Game>> for CandidateGridPairsDto {
     fn from(games: Vec<Game>) -> Self {
+        let monotone = games
+            .windows(2)
+            .all(|pair| only_adds_digits(&pair[1], &pair[0]));
+        let (cages, edges, chess) = games
+            .first()
+            .map(|game| {
+                (
+                    game.cages().iter().map(CageDto::from).collect(),
+                    game.edge_constraints()
+                        .iter()
+                        .map(EdgeConstraintDto::from)
+                        .collect(),
+                    game.chess_rules(),
+                )
+            })
+            .unwrap_or_default();
         let grids = games.into_iter().map(CandidateGridPairDto::from).collect();
-        Self { grids }
+        Self {
+            grids,
+            monotone,
+            cages,
+            edges,
+            anti_knight: chess.anti_knight(),
+            anti_king: chess.anti_king(),
+        }
     }
 }
 
+/// Returns `true` if every digit in `older` is still in place in `newer`.
+fn only_adds_digits(older: &Game, newer: &Game) -> bool {
+    Position::ALL.into_iter().all(|pos| {
+        older
+            .cell(pos)
+            .as_digit()
+            .is_none_or(|digit| newer.cell(pos).as_digit() == Some(digit))
+    })
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[expect(clippy::struct_excessive_bools)]
 pub(crate) struct GeneratePuzzleRequestDto {
     pub(crate) techniques: Vec<String>,
     pub(crate) seed: String,
     pub(crate) max_attempts: usize,
+    pub(crate) anti_knight: bool,
+    pub(crate) anti_king: bool,
+    /// Whether every puzzle gets a random jigsaw layout.
+    pub(crate) jigsaw: bool,
+    pub(crate) symmetry: String,
+    /// Whether to return a generator trace with the puzzle, in developer mode.
+    pub(crate) trace: bool,
 }
 
-impl From<NewGameOptions> for GeneratePuzzleRequestDto {
-    fn from(value: NewGameOptions) -> Self {
-        let mut techniques = vec![];
-        for technique in technique::all_techniques() {
-            if value
-                .techniques
-                .get(&technique.id())
-                .copied()
-                .unwrap_or_default()
-            {
-                techniques.push(technique.id().to_string());
-            }
-        }
+impl GeneratePuzzleRequestDto {
+    /// Builds a request for the techniques enabled in `options`, ordered and
+    /// filtered by the solver `priority`.
+    pub(crate) fn new(options: NewGameOptions, priority: &TechniquePriority) -> Self {
+        let techniques = priority
+            .techniques()
+            .into_iter()
+            .filter(|technique| {
+                technique
+                    .technique_id()
+                    .is_some_and(|id| options.is_technique_enabled(id))
+            })
+            .map(|technique| technique.id().to_string())
+            .collect();
 
         Self {
             techniques,
-            seed: value.seed,
-            max_attempts: value.max_attempts,
+            seed: options.seed,
+            max_attempts: options.max_attempts,
+            anti_knight: options.anti_knight,
+            anti_king: options.anti_king,
+            jigsaw: options.jigsaw,
+            symmetry: options.symmetry.to_string(),
+            trace: false,
         }
     }
 }
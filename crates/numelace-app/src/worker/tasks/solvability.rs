@@ -4,14 +4,31 @@
 //! so they can be sent across worker boundaries. It also includes the solvability
 //! logic used by background tasks.
 
-use numelace_core::CandidateGrid;
-use numelace_solver::{BacktrackSolverStats, TechniqueGrid, technique};
+use std::time::Duration;
+
+use numelace_core::{
+    Cage, CageSet, CandidateGrid, ChessRules, Digit, EdgeConstraint, EdgeConstraintSet, Position,
+};
+use numelace_solver::{
+    BacktrackSolver, BacktrackSolverStats, SolverBudget, SolverError, TechniqueGrid, technique,
+};
 use serde::{Deserialize, Serialize};
 
-use crate::worker::tasks::{CandidateGridDtoError, CandidateGridPairDto, CandidateGridPairsDto};
+use crate::{
+    persistence::dto::{CageDto, EdgeConstraintDto},
+    worker::tasks::{CandidateGridDtoError, CandidateGridPairsDto, SolvabilityRequestDto},
+};
+
+/// Search limit of each grid check.
+///
+/// Finding one solution of a sound grid takes milliseconds, but a grid whose
+/// contradiction lies deep in the search can keep the worker busy for
+/// minutes; such checks are reported as undetermined instead.
+const SOLVER_BUDGET: SolverBudget = SolverBudget::new().with_max_duration(Duration::from_secs(5));
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) struct TechniqueCountDto {
+    pub(crate) id: String,
     pub(crate) name: String,
     pub(crate) count: usize,
 }
@@ -25,14 +42,32 @@ pub(crate) struct SolvabilityStatsDto {
     pub(crate) technique_counts: Vec<TechniqueCountDto>,
 }
 
+/// Cells explaining why a grid has no solution, stored as `Position::index`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct ContradictionWitnessDto {
+    /// Cells that break the rules directly: no candidates left, or a digit
+    /// repeated within a row, column, or box.
+    pub(crate) invalid_cells: Vec<u8>,
+    /// Player digits whose removal makes the puzzle solvable again.
+    ///
+    /// The set is minimal: putting back any one of them leaves no solution.
+    pub(crate) conflicting_entries: Vec<u8>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) enum SolvabilityResultDto {
-    Inconsistent,
-    NoSolution,
+    Inconsistent {
+        witness: ContradictionWitnessDto,
+    },
+    NoSolution {
+        witness: ContradictionWitnessDto,
+    },
     Solvable {
         with_user_notes: bool,
         stats: SolvabilityStatsDto,
     },
+    /// The check ran out of its search budget before reaching an answer.
+    Undetermined,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -41,12 +76,26 @@ pub(crate) struct SolvabilityUndoScanResultDto {
     pub(crate) state: SolvabilityResultDto,
 }
 
+impl SolvabilityUndoScanResultDto {
+    /// Result of a scan that found no solvable state.
+    #[must_use]
+    pub(crate) fn not_found() -> Self {
+        Self {
+            index: None,
+            state: SolvabilityResultDto::NoSolution {
+                witness: ContradictionWitnessDto::default(),
+            },
+        }
+    }
+}
+
 impl From<BacktrackSolverStats> for SolvabilityStatsDto {
     fn from(stats: BacktrackSolverStats) -> Self {
         let technique_counts = technique::all_techniques()
             .iter()
             .zip(stats.technique().applications().iter())
             .map(|(tech, count)| TechniqueCountDto {
+                id: tech.id().to_string(),
                 name: tech.name().to_string(),
                 count: *count,
             })
@@ -63,72 +112,453 @@ impl From<BacktrackSolverStats> for SolvabilityStatsDto {
 }
 
 /// Scan undo history grids to find the first solvable state.
+///
+/// Grids are ordered from the current state backwards. When the history is
+/// [`monotone`](CandidateGridPairsDto::monotone), undoing moves never turns a
+/// solvable state into an unsolvable one, so a binary search finds the minimal
+/// number of undo steps. Otherwise the states are checked one by one.
 pub(crate) fn handle_solvability_undo_scan(
     request: CandidateGridPairsDto,
 ) -> Result<SolvabilityUndoScanResultDto, CandidateGridDtoError> {
-    for (index, grids) in request.grids.into_iter().enumerate() {
-        let with_user_notes = TechniqueGrid::from(CandidateGrid::try_from(grids.with_user_notes)?);
-        let without_user_notes =
-            TechniqueGrid::from(CandidateGrid::try_from(grids.without_user_notes)?);
-
-        let with_state = check_grid_solvability(with_user_notes, true);
-        if matches!(with_state, SolvabilityResultDto::Solvable { .. }) {
-            return Ok(SolvabilityUndoScanResultDto {
-                index: Some(index),
-                state: with_state,
-            });
-        }
+    let grids = request
+        .grids
+        .into_iter()
+        .map(|grids| {
+            Ok((
+                CandidateGrid::try_from(grids.with_user_notes)?,
+                CandidateGrid::try_from(grids.without_user_notes)?,
+            ))
+        })
+        .collect::<Result<Vec<_>, CandidateGridDtoError>>()?;
+    let chess = ChessRules::new()
+        .with_anti_knight(request.anti_knight)
+        .with_anti_king(request.anti_king);
+    let solver = solver(request.cages, request.edges, chess)?;
 
-        let without_state = check_grid_solvability(without_user_notes, false);
-        if matches!(without_state, SolvabilityResultDto::Solvable { .. }) {
-            return Ok(SolvabilityUndoScanResultDto {
-                index: Some(index),
-                state: without_state,
-            });
+    let found = if request.monotone {
+        binary_search_solvable(&solver, &grids)
+    } else {
+        grids
+            .iter()
+            .enumerate()
+            .find_map(|(index, (with_user_notes, without_user_notes))| {
+                check_solvable_state(&solver, with_user_notes, without_user_notes)
+                    .map(|state| (index, state))
+            })
+    };
+
+    let result = match found {
+        Some((index, state)) => SolvabilityUndoScanResultDto {
+            index: Some(index),
+            state,
+        },
+        None => SolvabilityUndoScanResultDto::not_found(),
+    };
+    Ok(result)
+}
+
+/// Finds the first solvable state in a history whose solvable states form a
+/// suffix.
+fn binary_search_solvable(
+    solver: &BacktrackSolver,
+    grids: &[(CandidateGrid, CandidateGrid)],
+) -> Option<(usize, SolvabilityResultDto)> {
+    let mut found = None;
+    let (mut low, mut high) = (0, grids.len());
+    while low < high {
+        let mid = low + (high - low) / 2;
+        let (with_user_notes, without_user_notes) = &grids[mid];
+        if let Some(state) = check_solvable_state(solver, with_user_notes, without_user_notes) {
+            found = Some((mid, state));
+            high = mid;
+        } else {
+            low = mid + 1;
         }
     }
+    found
+}
 
-    Ok(SolvabilityUndoScanResultDto {
-        index: None,
-        state: SolvabilityResultDto::NoSolution,
-    })
+/// Returns the solvable state of a history entry, preferring the user notes.
+fn check_solvable_state(
+    solver: &BacktrackSolver,
+    with_user_notes: &CandidateGrid,
+    without_user_notes: &CandidateGrid,
+) -> Option<SolvabilityResultDto> {
+    [(with_user_notes, true), (without_user_notes, false)]
+        .into_iter()
+        .map(|(grid, with_user_notes)| {
+            check_grid_solvability(solver, TechniqueGrid::from(grid.clone()), with_user_notes)
+        })
+        .find(|state| matches!(state, SolvabilityResultDto::Solvable { .. }))
 }
 
 /// Runs solvability logic with fallback between user notes and raw candidates.
 ///
 /// If the `with_user_notes` grid is inconsistent or unsolvable, the task retries
-/// with a grid that keeps only univalue positions.
+/// with a grid that keeps only univalue positions. When neither has a solution,
+/// the result carries a [`ContradictionWitnessDto`] for the latter.
 pub(crate) fn handle_solvability_request(
-    request: CandidateGridPairDto,
+    request: SolvabilityRequestDto,
 ) -> Result<SolvabilityResultDto, CandidateGridDtoError> {
-    let with_user_notes = TechniqueGrid::from(CandidateGrid::try_from(request.with_user_notes)?);
-    let without_user_notes =
-        TechniqueGrid::from(CandidateGrid::try_from(request.without_user_notes)?);
+    let with_user_notes =
+        TechniqueGrid::from(CandidateGrid::try_from(request.grids.with_user_notes)?);
+    let without_user_notes = CandidateGrid::try_from(request.grids.without_user_notes)?;
+    let chess = ChessRules::new()
+        .with_anti_knight(request.anti_knight)
+        .with_anti_king(request.anti_king);
+    let solver = solver(request.cages, request.edges, chess)?;
 
-    let first_result = check_grid_solvability(with_user_notes, true);
-    let result = if matches!(
+    let first_result = check_grid_solvability(&solver, with_user_notes, true);
+    let mut result = if matches!(
         first_result,
-        SolvabilityResultDto::Inconsistent | SolvabilityResultDto::NoSolution
+        SolvabilityResultDto::Inconsistent { .. } | SolvabilityResultDto::NoSolution { .. }
     ) {
-        check_grid_solvability(without_user_notes, false)
+        check_grid_solvability(
+            &solver,
+            TechniqueGrid::from(without_user_notes.clone()),
+            false,
+        )
     } else {
         first_result
     };
 
+    if let SolvabilityResultDto::Inconsistent { witness }
+    | SolvabilityResultDto::NoSolution { witness } = &mut result
+    {
+        let givens = CandidateGrid::try_from(request.givens)?;
+        let user_digits = request
+            .user_digits
+            .into_iter()
+            .map(|(index, digit)| {
+                let pos = Position::try_from_index(index).ok();
+                let value = (1..=9).contains(&digit).then(|| Digit::from_value(digit));
+                pos.zip(value)
+                    .ok_or(CandidateGridDtoError::UserDigitOutOfRange { index, digit })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        *witness = find_contradiction_witness(&solver, &without_user_notes, givens, &user_digits);
+    }
+
     Ok(result)
 }
 
-fn check_grid_solvability(grid: TechniqueGrid, with_user_notes: bool) -> SolvabilityResultDto {
+/// Finds the cells that explain why `grid` has no solution.
+///
+/// Player digits are re-applied one by one on top of `givens`; a digit that
+/// makes the grid unsolvable is reported and left out. Since adding digits only
+/// narrows the solution space, every reported digit still conflicts with the
+/// final set of kept digits.
+fn find_contradiction_witness(
+    solver: &BacktrackSolver,
+    grid: &CandidateGrid,
+    givens: CandidateGrid,
+    user_digits: &[(Position, Digit)],
+) -> ContradictionWitnessDto {
+    let [empty] = grid.classify_positions();
+    let univalue = grid.univalue_positions();
+    let mut invalid = empty;
+    for pos in univalue {
+        let Some(digit) = grid.candidates_at(pos).as_single() else {
+            continue;
+        };
+        let duplicates = grid.digit_positions(digit) & univalue & grid.house_peers(pos);
+        if !duplicates.is_empty() {
+            invalid.insert(pos);
+        }
+    }
+
+    let mut conflicting_entries = vec![];
+    let mut kept = givens;
+    if is_solvable(solver, &kept) {
+        for &(pos, digit) in user_digits {
+            let mut trial = kept.clone();
+            trial.place(pos, digit);
+            if is_solvable(solver, &trial) {
+                kept = trial;
+            } else {
+                conflicting_entries.push(pos.index());
+            }
+        }
+    }
+
+    ContradictionWitnessDto {
+        invalid_cells: invalid.iter().map(Position::index).collect(),
+        conflicting_entries,
+    }
+}
+
+/// Returns `false` only for grids proven to have no solution, so that a check
+/// cut short by the budget never blames a player digit.
+fn is_solvable(solver: &BacktrackSolver, grid: &CandidateGrid) -> bool {
+    !matches!(
+        check_grid_solvability(solver, TechniqueGrid::from(grid.clone()), false),
+        SolvabilityResultDto::Inconsistent { .. } | SolvabilityResultDto::NoSolution { .. }
+    )
+}
+
+/// Returns the solver for the grids of a request, enforcing its killer cages,
+/// edge markers and chess rules.
+fn solver(
+    cages: Vec<CageDto>,
+    edges: Vec<EdgeConstraintDto>,
+    chess: ChessRules,
+) -> Result<BacktrackSolver, CandidateGridDtoError> {
+    let cages = cages
+        .into_iter()
+        .map(Cage::try_from)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| CandidateGridDtoError::InvalidCages)?;
+    let cages = CageSet::from_cages(cages).map_err(|_| CandidateGridDtoError::InvalidCages)?;
+    let edges = edges
+        .into_iter()
+        .map(EdgeConstraint::try_from)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| CandidateGridDtoError::InvalidEdgeConstraints)?;
+    let edges = EdgeConstraintSet::from_constraints(edges)
+        .map_err(|_| CandidateGridDtoError::InvalidEdgeConstraints)?;
+    Ok(BacktrackSolver::with_all_techniques()
+        .with_budget(SOLVER_BUDGET)
+        .with_cages(cages)
+        .with_edge_constraints(edges)
+        .with_constraint(chess))
+}
+
+fn check_grid_solvability(
+    solver: &BacktrackSolver,
+    grid: TechniqueGrid,
+    with_user_notes: bool,
+) -> SolvabilityResultDto {
     if grid.check_consistency().is_err() {
-        return SolvabilityResultDto::Inconsistent;
+        return SolvabilityResultDto::Inconsistent {
+            witness: ContradictionWitnessDto::default(),
+        };
     }
 
-    let solver = numelace_solver::BacktrackSolver::with_all_techniques();
-    match solver.solve_with_step(grid).map(|mut sol| sol.next()) {
+    match solver
+        .solve_with_step(grid)
+        .and_then(|mut solutions| solutions.try_next())
+    {
         Ok(Some((_grid, stats))) => SolvabilityResultDto::Solvable {
             with_user_notes,
             stats: stats.into(),
         },
-        Ok(None) | Err(_) => SolvabilityResultDto::NoSolution,
+        Err(SolverError::BudgetExceeded) => SolvabilityResultDto::Undetermined,
+        Ok(None) | Err(SolverError::Inconsistent(_)) => SolvabilityResultDto::NoSolution {
+            witness: ContradictionWitnessDto::default(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use numelace_core::{
+        Cage, CageSet, ChessRules, DigitGrid, DigitPositions, EdgeConstraint, EdgeConstraintSet,
+        EdgeKind, Position,
+    };
+    use numelace_game::Game;
+
+    use super::{SolvabilityResultDto, handle_solvability_request, handle_solvability_undo_scan};
+    use crate::worker::tasks::CandidateGridPairsDto;
+
+    const SOLUTION: &str =
+        "185362947793148526246795183564239871931874265827516394318427659672951438459683712";
+
+    fn game_with_first_row(filled_row: &str) -> Game {
+        let problem: DigitGrid = format!("{}{}", ".".repeat(9), &SOLUTION[9..])
+            .parse()
+            .unwrap();
+        let solution: DigitGrid = SOLUTION.parse().unwrap();
+        let filled: DigitGrid = format!("{filled_row}{}", ".".repeat(72)).parse().unwrap();
+        let notes = [[0u16; 9]; 9];
+        Game::from_problem_filled_notes(&problem, &solution, &filled, &notes).unwrap()
+    }
+
+    #[test]
+    fn witness_reports_wrong_entry_and_duplicate() {
+        // r1c1 = 8 repeats the given 8 at r6c1 and leaves r1c2 without
+        // candidates; r1c3 = 5 is correct.
+        let game = game_with_first_row("8.5......");
+
+        let result = handle_solvability_request((&game).into()).unwrap();
+
+        let SolvabilityResultDto::Inconsistent { witness } = result else {
+            panic!("expected an inconsistent result, got {result:?}");
+        };
+        let r1c1 = Position::new(0, 0).index();
+        let r1c2 = Position::new(0, 1).index();
+        let r6c1 = Position::new(5, 0).index();
+        assert_eq!(witness.invalid_cells, vec![r1c1, r1c2, r6c1]);
+        assert_eq!(witness.conflicting_entries, vec![r1c1]);
+    }
+
+    #[test]
+    fn solvable_game_has_no_witness() {
+        let game = game_with_first_row("1.5......");
+
+        let result = handle_solvability_request((&game).into()).unwrap();
+
+        assert!(matches!(result, SolvabilityResultDto::Solvable { .. }));
+    }
+
+    /// Returns a cage over r1c1 and r1c2 that the solution cannot fill: it
+    /// holds 1 and 8 there.
+    fn impossible_cage() -> CageSet {
+        let cells = DigitPositions::from_iter([Position::new(0, 0), Position::new(0, 1)]);
+        CageSet::from_cages([Cage::new(cells, 10).unwrap()]).unwrap()
+    }
+
+    #[test]
+    fn solvability_respects_cages() {
+        let game = game_with_first_row(".........").with_cages(impossible_cage());
+
+        let result = handle_solvability_request((&game).into()).unwrap();
+
+        assert!(matches!(result, SolvabilityResultDto::NoSolution { .. }));
+    }
+
+    #[test]
+    fn undo_scan_respects_cages() {
+        let history = vec![game_with_first_row(".........").with_cages(impossible_cage())];
+        let request = CandidateGridPairsDto::from(history);
+
+        let result = handle_solvability_undo_scan(request).unwrap();
+
+        assert_eq!(result.index, None);
+    }
+
+    /// Returns a marker between r1c1 and r1c2 that the solution breaks: it
+    /// holds 1 and 8 there.
+    fn impossible_edge() -> EdgeConstraintSet {
+        let edge = EdgeConstraint::new(
+            Position::new(0, 0),
+            Position::new(0, 1),
+            EdgeKind::GreaterThan,
+        )
+        .unwrap();
+        EdgeConstraintSet::from_constraints([edge]).unwrap()
+    }
+
+    #[test]
+    fn solvability_respects_edge_constraints() {
+        let game = game_with_first_row(".........").with_edge_constraints(impossible_edge());
+
+        let result = handle_solvability_request((&game).into()).unwrap();
+
+        assert!(matches!(result, SolvabilityResultDto::NoSolution { .. }));
+    }
+
+    #[test]
+    fn undo_scan_respects_edge_constraints() {
+        let history =
+            vec![game_with_first_row(".........").with_edge_constraints(impossible_edge())];
+        let request = CandidateGridPairsDto::from(history);
+
+        let result = handle_solvability_undo_scan(request).unwrap();
+
+        assert_eq!(result.index, None);
+    }
+
+    /// Solution of an anti-knight puzzle: equal digits are never a knight's
+    /// move apart.
+    const ANTI_KNIGHT_SOLUTION: &str =
+        "839246571512739648764518932345961827281375469976482315157693284628154793493827156";
+
+    /// Returns an anti-knight game with the top band empty and the player's
+    /// `filled` digits.
+    fn anti_knight_game(filled: &str) -> Game {
+        let problem: DigitGrid = format!("{}{}", ".".repeat(27), &ANTI_KNIGHT_SOLUTION[27..])
+            .parse()
+            .unwrap();
+        let solution: DigitGrid = ANTI_KNIGHT_SOLUTION.parse().unwrap();
+        let filled: DigitGrid = format!("{filled}{}", ".".repeat(81 - filled.len()))
+            .parse()
+            .unwrap();
+        Game::from_problem_filled_notes(&problem, &solution, &filled, &[[0; 9]; 9])
+            .unwrap()
+            .with_chess_rules(ChessRules::new().with_anti_knight(true))
+    }
+
+    /// Player digits with r3c1 = 8, a knight's move from the given 8 at r5c2
+    /// but in no house with it.
+    const KNIGHT_CONFLICT: &str = "..................8";
+
+    #[test]
+    fn solvability_respects_chess_rules() {
+        let game = anti_knight_game(KNIGHT_CONFLICT);
+
+        let result = handle_solvability_request((&game).into()).unwrap();
+
+        let SolvabilityResultDto::NoSolution { witness } = result else {
+            panic!("expected no solution, got {result:?}");
+        };
+        assert_eq!(
+            witness.conflicting_entries,
+            vec![Position::new(2, 0).index()]
+        );
+
+        // Swapping the first and third rows satisfies every house.
+        let game = game.with_chess_rules(ChessRules::new());
+        let result = handle_solvability_request((&game).into()).unwrap();
+        assert!(matches!(result, SolvabilityResultDto::Solvable { .. }));
+    }
+
+    #[test]
+    fn undo_scan_respects_chess_rules() {
+        let history = vec![anti_knight_game(KNIGHT_CONFLICT), anti_knight_game("")];
+        let request = CandidateGridPairsDto::from(history);
+
+        let result = handle_solvability_undo_scan(request).unwrap();
+
+        assert_eq!(result.index, Some(1));
+    }
+
+    #[test]
+    fn undo_scan_finds_minimal_undo_steps() {
+        // Newest first: the two latest moves break the puzzle.
+        let history = vec![
+            game_with_first_row("8.56....."),
+            game_with_first_row("8.5......"),
+            game_with_first_row("..5......"),
+            game_with_first_row("........."),
+        ];
+        let request = CandidateGridPairsDto::from(history);
+        assert!(request.monotone);
+
+        let result = handle_solvability_undo_scan(request).unwrap();
+
+        assert_eq!(result.index, Some(2));
+        assert!(matches!(
+            result.state,
+            SolvabilityResultDto::Solvable { .. }
+        ));
+    }
+
+    #[test]
+    fn undo_scan_handles_cleared_and_overwritten_digits() {
+        // Newest first: a bad digit, cleared, then a different bad digit. A
+        // binary search would probe the bad state at index 2 and miss the
+        // solvable state at index 1.
+        let history = vec![
+            game_with_first_row("6........"),
+            game_with_first_row("........."),
+            game_with_first_row("8........"),
+            game_with_first_row("........."),
+        ];
+        let request = CandidateGridPairsDto::from(history);
+        assert!(!request.monotone);
+
+        let result = handle_solvability_undo_scan(request).unwrap();
+
+        assert_eq!(result.index, Some(1));
+    }
+
+    #[test]
+    fn undo_scan_reports_missing_solvable_state() {
+        let history = vec![game_with_first_row("8........")];
+        let request = CandidateGridPairsDto::from(history);
+
+        let result = handle_solvability_undo_scan(request).unwrap();
+
+        assert_eq!(result.index, None);
     }
 }
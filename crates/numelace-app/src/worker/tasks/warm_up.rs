@@ -0,0 +1,16 @@
+//! Warm-up task run once when the app starts.
+//!
+//! The first generation in a fresh worker is noticeably slower than later
+//! ones, especially on wasm where the engine compiles code on first use.
+//! Generating one easy puzzle up front moves that cost out of the player's
+//! first New Game.
+
+use numelace_generator::{PuzzleGenerator, PuzzleSeed};
+use numelace_solver::{TechniqueSolver, technique};
+
+/// Generates one puzzle from a fixed seed, solvable with fundamental techniques.
+pub(crate) fn warm_up() {
+    let technique_solver = TechniqueSolver::new(technique::fundamental_techniques());
+    let seed = PuzzleSeed::from_arbitrary_bytes(b"warm-up");
+    let _ = PuzzleGenerator::new(&technique_solver).generate_with_seed(seed);
+}
@@ -1 +1 @@
-pub use crate::worker::api::{WorkRequest, WorkResponse};
+pub use crate::worker::api::{WorkFrame, WorkRequest, candidate_grid_from_bits};
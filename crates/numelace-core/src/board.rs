@@ -0,0 +1,163 @@
+//! Board dimension descriptors.
+
+/// Dimensions of a sudoku board: the side length and the shape of its boxes.
+///
+/// The grid containers in this crate ([`Position`](crate::Position),
+/// [`DigitPositions`](crate::DigitPositions), [`CandidateGrid`](crate::CandidateGrid), ...)
+/// are fixed to the standard 9×9 board. `BoardDims` describes the other common
+/// sizes (6×6 with 2×3 boxes, 16×16 with 4×4 boxes) so that size-dependent
+/// arithmetic and digit notation can be shared while the containers are
+/// generalized.
+///
+/// # Examples
+///
+/// ```
+/// use numelace_core::BoardDims;
+///
+/// let dims = BoardDims::SIXTEEN;
+/// assert_eq!(dims.side(), 16);
+/// assert_eq!(dims.cell_count(), 256);
+/// assert_eq!(dims.box_index(5, 9), 6);
+/// assert_eq!(dims.digit_char(10), Some('A'));
+/// assert_eq!(dims.parse_digit('g'), Some(16));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BoardDims {
+    box_height: u8,
+    box_width: u8,
+}
+
+impl Default for BoardDims {
+    fn default() -> Self {
+        Self::NINE
+    }
+}
+
+impl BoardDims {
+    /// 6×6 board with 2-row × 3-column boxes.
+    pub const SIX: Self = Self::new(2, 3);
+    /// Standard 9×9 board with 3×3 boxes.
+    pub const NINE: Self = Self::new(3, 3);
+    /// 16×16 board with 4×4 boxes.
+    pub const SIXTEEN: Self = Self::new(4, 4);
+
+    /// Creates board dimensions from the box shape.
+    ///
+    /// The board side is `box_height * box_width`, and each band holds
+    /// `box_width` boxes stacked `box_height` rows high.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either dimension is zero or the side length exceeds 16.
+    #[must_use]
+    pub const fn new(box_height: u8, box_width: u8) -> Self {
+        assert!(box_height > 0 && box_width > 0);
+        assert!(box_height as u16 * box_width as u16 <= 16);
+        Self {
+            box_height,
+            box_width,
+        }
+    }
+
+    /// Returns the number of rows in each box.
+    #[must_use]
+    pub const fn box_height(&self) -> u8 {
+        self.box_height
+    }
+
+    /// Returns the number of columns in each box.
+    #[must_use]
+    pub const fn box_width(&self) -> u8 {
+        self.box_width
+    }
+
+    /// Returns the number of rows (and columns, digits, and boxes) on the board.
+    #[must_use]
+    pub const fn side(&self) -> u8 {
+        self.box_height * self.box_width
+    }
+
+    /// Returns the total number of cells on the board.
+    #[must_use]
+    pub const fn cell_count(&self) -> usize {
+        let side = self.side() as usize;
+        side * side
+    }
+
+    /// Returns the box index of the cell at (`row`, `col`), in row-major box order.
+    #[must_use]
+    pub const fn box_index(&self, row: u8, col: u8) -> u8 {
+        // Boxes are `box_width` wide, so there are `box_height` boxes per band.
+        (row / self.box_height) * self.box_height + col / self.box_width
+    }
+
+    /// Returns the character used to display `value` (1-based).
+    ///
+    /// Boards up to 9×9 use `1`-`9`; larger boards continue with `A`, `B`, ...
+    /// Returns `None` if `value` is outside `1..=side`.
+    #[must_use]
+    pub fn digit_char(&self, value: u8) -> Option<char> {
+        match value {
+            1..=9 if value <= self.side() => Some(char::from(b'0' + value)),
+            10..=16 if value <= self.side() => Some(char::from(b'A' + value - 10)),
+            _ => None,
+        }
+    }
+
+    /// Parses a digit character produced by [`digit_char`](Self::digit_char).
+    ///
+    /// Letters are accepted in either case. Returns `None` if the character
+    /// does not denote a digit on this board.
+    #[must_use]
+    pub fn parse_digit(&self, ch: char) -> Option<u8> {
+        let value = match ch {
+            '1'..='9' => ch as u8 - b'0',
+            'A'..='G' => ch as u8 - b'A' + 10,
+            'a'..='g' => ch as u8 - b'a' + 10,
+            _ => return None,
+        };
+        (value <= self.side()).then_some(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Position, RegionLayout};
+
+    #[test]
+    fn test_nine_matches_position_boxes() {
+        let dims = BoardDims::NINE;
+        assert_eq!(dims.cell_count(), 81);
+        for pos in Position::ALL {
+            assert_eq!(
+                dims.box_index(pos.row(), pos.col()),
+                pos.box_index(&RegionLayout::standard())
+            );
+        }
+    }
+
+    #[test]
+    fn test_six_box_layout() {
+        let dims = BoardDims::SIX;
+        assert_eq!(dims.side(), 6);
+        assert_eq!(dims.box_index(0, 2), 0);
+        assert_eq!(dims.box_index(0, 3), 1);
+        assert_eq!(dims.box_index(2, 0), 2);
+        assert_eq!(dims.box_index(5, 5), 5);
+    }
+
+    #[test]
+    fn test_digit_notation() {
+        assert_eq!(BoardDims::SIX.digit_char(6), Some('6'));
+        assert_eq!(BoardDims::SIX.digit_char(7), None);
+        assert_eq!(BoardDims::SIX.parse_digit('7'), None);
+        assert_eq!(BoardDims::SIXTEEN.digit_char(16), Some('G'));
+        for value in 1..=16 {
+            let ch = BoardDims::SIXTEEN.digit_char(value).unwrap();
+            assert_eq!(BoardDims::SIXTEEN.parse_digit(ch), Some(value));
+        }
+        assert_eq!(BoardDims::SIXTEEN.digit_char(0), None);
+        assert_eq!(BoardDims::SIXTEEN.parse_digit('0'), None);
+    }
+}
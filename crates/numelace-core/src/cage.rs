@@ -0,0 +1,432 @@
+use crate::{Digit, DigitGrid, DigitPositions, DigitSet, Position};
+
+/// A killer cage: a group of cells whose digits must sum to a target.
+///
+/// Digits inside a cage never repeat, even when the cells do not share a house.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cage {
+    positions: DigitPositions,
+    sum: u8,
+}
+
+/// Errors that can occur when constructing a [`Cage`] or a [`CageSet`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, derive_more::Display, derive_more::Error)]
+pub enum CageError {
+    /// The cage contains no cells.
+    #[display("cage has no cells")]
+    Empty,
+    /// The cage contains more cells than distinct digits exist.
+    #[display("cage has too many cells: expected at most 9, got {_0}")]
+    TooManyCells(#[error(not(source))] usize),
+    /// No set of distinct digits of the cage size adds up to the target sum.
+    #[display("cage sum {sum} is unreachable with {len} cells")]
+    UnreachableSum {
+        /// Target sum of the cage.
+        sum: u8,
+        /// Number of cells in the cage.
+        len: usize,
+    },
+    /// A cell belongs to more than one cage.
+    #[display("cell {_0:?} belongs to more than one cage")]
+    Overlapping(#[error(not(source))] Position),
+}
+
+/// A rule violation detected in a (partially) filled cage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, derive_more::Display, derive_more::Error)]
+pub enum CageViolation {
+    /// The same digit is placed twice in the cage.
+    #[display("digit {digit} is duplicated in the cage")]
+    DuplicatedDigit {
+        /// The duplicated digit.
+        digit: Digit,
+    },
+    /// The placed digits already exceed the target sum.
+    #[display("placed digits exceed the cage sum {sum}")]
+    SumExceeded {
+        /// Target sum of the cage.
+        sum: u8,
+    },
+    /// The cage is complete but its digits do not add up to the target sum.
+    #[display("cage sum mismatch: expected {sum}, got {actual}")]
+    SumMismatch {
+        /// Target sum of the cage.
+        sum: u8,
+        /// Actual sum of the placed digits.
+        actual: u8,
+    },
+}
+
+fn digit_set_sum(digits: DigitSet) -> u8 {
+    digits.iter().map(|digit| digit.value()).sum()
+}
+
+impl Cage {
+    /// Creates a new cage covering `positions` with the given target `sum`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CageError`] if the cage is empty, has more than 9 cells, or
+    /// the sum cannot be reached with distinct digits.
+    pub fn new(positions: DigitPositions, sum: u8) -> Result<Self, CageError> {
+        let len = positions.len();
+        if len == 0 {
+            return Err(CageError::Empty);
+        }
+        if len > 9 {
+            return Err(CageError::TooManyCells(len));
+        }
+        // Smallest sum uses 1..=n, largest uses (10-n)..=9.
+        let min = len * (len + 1) / 2;
+        let max = len * (19 - len) / 2;
+        if !(min..=max).contains(&usize::from(sum)) {
+            return Err(CageError::UnreachableSum { sum, len });
+        }
+        Ok(Self { positions, sum })
+    }
+
+    /// Returns the cells covered by this cage.
+    #[must_use]
+    pub const fn positions(&self) -> DigitPositions {
+        self.positions
+    }
+
+    /// Returns the target sum of this cage.
+    #[must_use]
+    pub const fn sum(&self) -> u8 {
+        self.sum
+    }
+
+    /// Returns the number of cells in this cage.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Returns `true` if the cage has no cells.
+    ///
+    /// Cages built with [`Cage::new`] are never empty.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    /// Returns `true` if the cage covers `pos`.
+    #[must_use]
+    pub fn contains(&self, pos: Position) -> bool {
+        self.positions.contains(pos)
+    }
+
+    /// Returns the top-left cell of the cage, where its sum label is shown.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the cage is empty.
+    #[must_use]
+    pub fn anchor(&self) -> Position {
+        self.positions.first().expect("cage must not be empty")
+    }
+
+    /// Returns the digits that can still fill the open cells of the cage.
+    ///
+    /// `placed` holds the digits already decided inside the cage and
+    /// `available` the union of candidates of the open cells. A combination is
+    /// valid when it contains every placed digit, adds up to the target sum,
+    /// and its remaining digits are available. The result is the union of the
+    /// remaining digits over all valid combinations, or `None` if no
+    /// combination is valid.
+    #[must_use]
+    pub fn combination_digits(&self, placed: DigitSet, available: DigitSet) -> Option<DigitSet> {
+        let mut result = None;
+        for bits in 0..=DigitSet::FULL.bits() {
+            let combination = DigitSet::from_bits(bits);
+            if combination.len() != self.len()
+                || !placed.is_subset(combination)
+                || digit_set_sum(combination) != self.sum
+            {
+                continue;
+            }
+            let rest = combination.difference(placed);
+            if !rest.is_subset(available) {
+                continue;
+            }
+            *result.get_or_insert(DigitSet::EMPTY) |= rest;
+        }
+        result
+    }
+
+    /// Returns the digits that can still fill the open cells of the cage,
+    /// given the candidates of each cell.
+    ///
+    /// Cells with a single candidate count as decided. Returns `None` if a
+    /// decided digit repeats or no valid combination remains; see
+    /// [`combination_digits`](Self::combination_digits).
+    pub fn open_cell_digits<F>(&self, mut candidates_at: F) -> Option<DigitSet>
+    where
+        F: FnMut(Position) -> DigitSet,
+    {
+        let mut placed = DigitSet::EMPTY;
+        let mut available = DigitSet::EMPTY;
+        for pos in self.positions {
+            let candidates = candidates_at(pos);
+            if candidates.len() == 1 {
+                if !placed.is_disjoint(candidates) {
+                    return None;
+                }
+                placed |= candidates;
+            } else {
+                available |= candidates;
+            }
+        }
+        self.combination_digits(placed, available)
+    }
+
+    /// Checks the cage rules against the digits returned by `digit_at`.
+    ///
+    /// Empty cells are allowed; only duplicates, an exceeded sum, or a
+    /// completed cage with the wrong sum are reported.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first [`CageViolation`] found.
+    pub fn check_with<F>(&self, mut digit_at: F) -> Result<(), CageViolation>
+    where
+        F: FnMut(Position) -> Option<Digit>,
+    {
+        let mut placed = DigitSet::EMPTY;
+        let mut filled = 0;
+        let mut actual = 0;
+        for pos in self.positions {
+            let Some(digit) = digit_at(pos) else {
+                continue;
+            };
+            if !placed.insert(digit) {
+                return Err(CageViolation::DuplicatedDigit { digit });
+            }
+            filled += 1;
+            actual += digit.value();
+        }
+        if actual > self.sum {
+            return Err(CageViolation::SumExceeded { sum: self.sum });
+        }
+        if filled == self.len() && actual != self.sum {
+            return Err(CageViolation::SumMismatch {
+                sum: self.sum,
+                actual,
+            });
+        }
+        Ok(())
+    }
+
+    /// Checks the cage rules against a [`DigitGrid`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the first [`CageViolation`] found.
+    pub fn check(&self, grid: &DigitGrid) -> Result<(), CageViolation> {
+        self.check_with(|pos| grid.get(pos))
+    }
+}
+
+/// A set of non-overlapping killer cages.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CageSet {
+    cages: Vec<Cage>,
+    covered: DigitPositions,
+}
+
+impl CageSet {
+    /// Creates an empty cage set.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a cage set from the given cages.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CageError::Overlapping`] if two cages share a cell.
+    pub fn from_cages<I>(cages: I) -> Result<Self, CageError>
+    where
+        I: IntoIterator<Item = Cage>,
+    {
+        let mut set = Self::new();
+        for cage in cages {
+            set.insert(cage)?;
+        }
+        Ok(set)
+    }
+
+    /// Adds a cage to the set.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CageError::Overlapping`] if the cage shares a cell with an
+    /// existing cage.
+    pub fn insert(&mut self, cage: Cage) -> Result<(), CageError> {
+        if let Some(pos) = (self.covered & cage.positions).first() {
+            return Err(CageError::Overlapping(pos));
+        }
+        self.covered |= cage.positions;
+        self.cages.push(cage);
+        Ok(())
+    }
+
+    /// Returns `true` if the set contains no cages.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.cages.is_empty()
+    }
+
+    /// Returns the number of cages.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.cages.len()
+    }
+
+    /// Returns an iterator over the cages.
+    pub fn iter(&self) -> std::slice::Iter<'_, Cage> {
+        self.cages.iter()
+    }
+
+    /// Returns the cells covered by any cage.
+    #[must_use]
+    pub const fn covered_positions(&self) -> DigitPositions {
+        self.covered
+    }
+
+    /// Returns the cage covering `pos`, if any.
+    #[must_use]
+    pub fn cage_at(&self, pos: Position) -> Option<&Cage> {
+        if !self.covered.contains(pos) {
+            return None;
+        }
+        self.cages.iter().find(|cage| cage.contains(pos))
+    }
+
+    /// Checks every cage against a [`DigitGrid`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the first [`CageViolation`] found.
+    pub fn check(&self, grid: &DigitGrid) -> Result<(), CageViolation> {
+        self.cages.iter().try_for_each(|cage| cage.check(grid))
+    }
+}
+
+impl<'a> IntoIterator for &'a CageSet {
+    type Item = &'a Cage;
+    type IntoIter = std::slice::Iter<'a, Cage>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+
+    fn positions(cells: &[(u8, u8)]) -> DigitPositions {
+        cells
+            .iter()
+            .map(|&(row, col)| Position::new(row, col))
+            .collect()
+    }
+
+    #[test]
+    fn test_new_rejects_invalid_cages() {
+        assert_eq!(Cage::new(DigitPositions::EMPTY, 3), Err(CageError::Empty));
+        assert_eq!(
+            Cage::new(DigitPositions::ROW_POSITIONS[0] | positions(&[(1, 0)]), 45),
+            Err(CageError::TooManyCells(10))
+        );
+        assert_eq!(
+            Cage::new(positions(&[(0, 0), (0, 1)]), 2),
+            Err(CageError::UnreachableSum { sum: 2, len: 2 })
+        );
+        assert_eq!(
+            Cage::new(positions(&[(0, 0), (0, 1)]), 18),
+            Err(CageError::UnreachableSum { sum: 18, len: 2 })
+        );
+        assert!(Cage::new(positions(&[(0, 0), (0, 1)]), 17).is_ok());
+        assert!(Cage::new(DigitPositions::ROW_POSITIONS[0], 45).is_ok());
+    }
+
+    #[test]
+    fn test_combination_digits() {
+        let cage = Cage::new(positions(&[(0, 0), (0, 1)]), 3).unwrap();
+        assert_eq!(
+            cage.combination_digits(DigitSet::EMPTY, DigitSet::FULL),
+            Some(DigitSet::from_iter([Digit::D1, Digit::D2]))
+        );
+
+        let cage = Cage::new(positions(&[(0, 0), (0, 1), (1, 0)]), 10).unwrap();
+        // {1,2,7}, {1,3,6}, {1,4,5}, {2,3,5}
+        assert_eq!(
+            cage.combination_digits(DigitSet::from_elem(Digit::D1), DigitSet::FULL),
+            Some(DigitSet::from_iter([
+                Digit::D2,
+                Digit::D3,
+                Digit::D4,
+                Digit::D5,
+                Digit::D6,
+                Digit::D7
+            ]))
+        );
+        assert_eq!(
+            cage.combination_digits(
+                DigitSet::from_elem(Digit::D1),
+                DigitSet::from_iter([Digit::D4, Digit::D5])
+            ),
+            Some(DigitSet::from_iter([Digit::D4, Digit::D5]))
+        );
+        assert_eq!(
+            cage.combination_digits(DigitSet::from_elem(Digit::D9), DigitSet::FULL),
+            None
+        );
+    }
+
+    #[test]
+    fn test_check() {
+        let cage = Cage::new(positions(&[(0, 0), (0, 1)]), 5).unwrap();
+        let grid = |s: &str| DigitGrid::from_str(&format!("{s:.<81}")).unwrap();
+
+        assert_eq!(cage.check(&grid("")), Ok(()));
+        assert_eq!(cage.check(&grid("4")), Ok(()));
+        assert_eq!(cage.check(&grid("14")), Ok(()));
+        assert_eq!(
+            cage.check(&grid("6")),
+            Err(CageViolation::SumExceeded { sum: 5 })
+        );
+        assert_eq!(
+            cage.check(&grid("13")),
+            Err(CageViolation::SumMismatch { sum: 5, actual: 4 })
+        );
+
+        let cage = Cage::new(positions(&[(0, 0), (1, 1), (2, 2)]), 12).unwrap();
+        assert_eq!(
+            cage.check(&grid("3.........3")),
+            Err(CageViolation::DuplicatedDigit { digit: Digit::D3 })
+        );
+    }
+
+    #[test]
+    fn test_cage_set_rejects_overlap() {
+        let a = Cage::new(positions(&[(0, 0), (0, 1)]), 3).unwrap();
+        let b = Cage::new(positions(&[(0, 1), (0, 2)]), 3).unwrap();
+        assert_eq!(
+            CageSet::from_cages([a, b]),
+            Err(CageError::Overlapping(Position::new(0, 1)))
+        );
+
+        let c = Cage::new(positions(&[(1, 1), (1, 2)]), 4).unwrap();
+        let set = CageSet::from_cages([a, c]).unwrap();
+        assert_eq!(set.len(), 2);
+        assert_eq!(set.cage_at(Position::new(1, 2)), Some(&c));
+        assert_eq!(set.cage_at(Position::new(5, 5)), None);
+        assert_eq!(a.anchor(), Position::new(0, 0));
+    }
+}
@@ -3,7 +3,7 @@
 use std::iter;
 
 use crate::{
-    CellIndexIndexedArray, DigitGrid, DigitIndexedArray, House,
+    CellIndexIndexedArray, DigitGrid, DigitIndexedArray, House, RegionLayout,
     containers::{BitSet9, BitSet81},
     digit::Digit,
     index::{CellIndexSemantics, DigitSemantics, Index9, Index9Semantics, PositionSemantics},
@@ -18,6 +18,28 @@ use crate::{
 /// See the [crate-level documentation](crate#semantics-pattern-type-safe-indexing) for details.
 pub type DigitSet = BitSet9<DigitSemantics>;
 
+impl DigitSet {
+    /// Creates a set containing exactly one digit.
+    ///
+    /// This is the const counterpart of [`from_elem`](Self::from_elem), usable for
+    /// building compile-time tables.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use numelace_core::{Digit, DigitSet};
+    ///
+    /// const ODD_PAIR: DigitSet = DigitSet::from_digit(Digit::D1).union(DigitSet::from_digit(Digit::D3));
+    /// assert_eq!(ODD_PAIR.len(), 2);
+    /// assert!(ODD_PAIR.contains(Digit::D3));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn from_digit(digit: Digit) -> Self {
+        Self::from_bits(1 << (digit.value() - 1))
+    }
+}
+
 /// A set of grid positions.
 ///
 /// A specialized [`BitSet81`] using [`PositionSemantics`], providing type-safe set operations
@@ -96,7 +118,29 @@ impl DigitPositions {
         CellIndexIndexedArray::from_array(masks)
     };
 
-    /// Returns a bitmask of positions in the specified house.
+    /// Creates a set containing exactly one position.
+    ///
+    /// This is the const counterpart of [`from_elem`](Self::from_elem), usable for
+    /// building compile-time tables.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use numelace_core::{DigitPositions, Position};
+    ///
+    /// const CORNERS: DigitPositions = DigitPositions::from_position(Position::new(0, 0))
+    ///     .union(DigitPositions::from_position(Position::new(8, 8)));
+    /// assert_eq!(CORNERS.len(), 2);
+    /// assert!(CORNERS.contains(Position::new(8, 8)));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn from_position(pos: Position) -> Self {
+        Self::from_bits(1 << pos.index())
+    }
+
+    /// Returns a bitmask of positions in the specified house, taking boxes
+    /// from `regions`.
     ///
     /// This is a convenience wrapper around [`positions_in_row`], [`positions_in_col`], and [`positions_in_box`],
     /// depending on the [`House`] variant.
@@ -106,11 +150,11 @@ impl DigitPositions {
     /// [`positions_in_box`]: Self::positions_in_box
     #[must_use]
     #[inline]
-    pub fn positions_in_house(&self, house: House) -> HouseMask {
+    pub fn positions_in_house(&self, regions: &RegionLayout, house: House) -> HouseMask {
         match house {
             House::Row { row } => self.positions_in_row(row),
             House::Column { col } => self.positions_in_col(col),
-            House::Box { index } => self.positions_in_box(index),
+            House::Box { index } => self.positions_in_box(regions, index),
         }
     }
 
@@ -137,11 +181,7 @@ impl DigitPositions {
     #[must_use]
     #[inline]
     pub fn positions_in_row(&self, row: u8) -> HouseMask {
-        let mut mask = HouseMask::new();
-        for pos in *self & Self::ROW_POSITIONS[row] {
-            mask.insert(pos.col());
-        }
-        mask
+        self.row_mask(row)
     }
 
     /// Returns a bitmask of positions in the specified column.
@@ -167,41 +207,167 @@ impl DigitPositions {
     #[must_use]
     #[inline]
     pub fn positions_in_col(&self, col: u8) -> HouseMask {
-        let mut mask = HouseMask::new();
-        for pos in *self & Self::COL_POSITIONS[col] {
-            mask.insert(pos.row());
-        }
-        mask
+        self.col_mask(col)
     }
 
-    /// Returns a bitmask of positions in the specified 3×3 box.
+    /// Returns a bitmask of positions in the specified box (region) of `regions`.
     ///
     /// The returned mask contains the cell indices (0-8) within the box where positions exist.
-    /// Boxes are numbered 0-8 from left to right, top to bottom.
+    /// For the standard layout, boxes are numbered 0-8 from left to right, top to bottom.
     ///
     /// # Examples
     ///
     /// ```
-    /// use numelace_core::{DigitPositions, Position};
+    /// use numelace_core::{DigitPositions, Position, RegionLayout};
     ///
     /// let mut positions = DigitPositions::new();
     /// positions.insert(Position::new(0, 0)); // Top-left corner of box 0
     /// positions.insert(Position::new(1, 1)); // Center area of box 0
     /// positions.insert(Position::new(0, 3)); // Box 1
     ///
-    /// let positions_in_box = positions.positions_in_box(0);
+    /// let positions_in_box = positions.positions_in_box(&RegionLayout::standard(), 0);
     /// assert_eq!(positions_in_box.len(), 2); // Two positions in box 0
     /// ```
     #[must_use]
     #[inline]
-    pub fn positions_in_box(&self, box_index: u8) -> HouseMask {
+    pub fn positions_in_box(&self, regions: &RegionLayout, box_index: u8) -> HouseMask {
+        self.box_mask(regions, box_index)
+    }
+
+    /// Projects the positions in row `y` onto a [`HouseMask`] of column indices.
+    ///
+    /// Unlike iterating over the intersection with [`ROW_POSITIONS`](Self::ROW_POSITIONS),
+    /// this extracts the row directly from the underlying bits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `y` is not in the range 0-8.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use numelace_core::{DigitPositions, Position};
+    ///
+    /// let mut positions = DigitPositions::new();
+    /// positions.insert(Position::new(4, 1));
+    /// positions.insert(Position::new(4, 7));
+    /// positions.insert(Position::new(5, 7));
+    ///
+    /// let mask = positions.row_mask(4);
+    /// assert_eq!(mask.iter().collect::<Vec<_>>(), [1, 7]);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn row_mask(&self, y: u8) -> HouseMask {
+        assert!(y < 9);
+        let bits = ((self.bits() >> (u32::from(y) * 9)) & 0x1ff) as u16;
+        HouseMask::from_bits(bits)
+    }
+
+    /// Projects the positions in column `x` onto a [`HouseMask`] of row indices.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` is not in the range 0-8.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use numelace_core::{DigitPositions, Position};
+    ///
+    /// let mut positions = DigitPositions::new();
+    /// positions.insert(Position::new(0, 3));
+    /// positions.insert(Position::new(8, 3));
+    /// positions.insert(Position::new(8, 4));
+    ///
+    /// let mask = positions.col_mask(3);
+    /// assert_eq!(mask.iter().collect::<Vec<_>>(), [0, 8]);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn col_mask(&self, x: u8) -> HouseMask {
+        assert!(x < 9);
+        let bits = self.bits() >> x;
+        let mut mask = 0u16;
+        for y in 0..9 {
+            if bits & (1 << (y * 9)) != 0 {
+                mask |= 1 << y;
+            }
+        }
+        HouseMask::from_bits(mask)
+    }
+
+    /// Projects the positions in box (region) `b` of `regions` onto a
+    /// [`HouseMask`] of cell indices within the box.
+    ///
+    /// Cell indices follow [`Position::box_cell_index`], numbering the box's
+    /// cells 0-8 in row-major order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `b` is not in the range 0-8.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use numelace_core::{DigitPositions, Position, RegionLayout};
+    ///
+    /// let mut positions = DigitPositions::new();
+    /// positions.insert(Position::new(3, 3)); // Top-left of box 4
+    /// positions.insert(Position::new(5, 4)); // Bottom-middle of box 4
+    /// positions.insert(Position::new(0, 0)); // Box 0
+    ///
+    /// let mask = positions.box_mask(&RegionLayout::standard(), 4);
+    /// assert_eq!(mask.iter().collect::<Vec<_>>(), [0, 7]);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn box_mask(&self, regions: &RegionLayout, b: u8) -> HouseMask {
         let mut mask = HouseMask::new();
-        for pos in *self & Self::BOX_POSITIONS[box_index] {
-            mask.insert(pos.box_cell_index());
+        for (cell_index, pos) in (0..).zip(regions.region_positions(b)) {
+            if self.contains(pos) {
+                mask.insert(cell_index);
+            }
         }
         mask
     }
 
+    /// Lifts a [`HouseMask`] of cell indices in `house` back to absolute positions,
+    /// taking boxes from `regions`.
+    ///
+    /// This is the inverse of [`positions_in_house`](Self::positions_in_house) restricted
+    /// to `house`: `DigitPositions::from_house_mask(regions, house, p.positions_in_house(regions, house))`
+    /// equals `p & house.positions(regions)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use numelace_core::{DigitPositions, House, HouseMask, Position, RegionLayout};
+    ///
+    /// let mut mask = HouseMask::new();
+    /// mask.insert(2);
+    /// mask.insert(6);
+    ///
+    /// let regions = RegionLayout::standard();
+    /// let positions = DigitPositions::from_house_mask(&regions, House::Column { col: 5 }, mask);
+    /// assert_eq!(positions.len(), 2);
+    /// assert!(positions.contains(Position::new(2, 5)));
+    /// assert!(positions.contains(Position::new(6, 5)));
+    /// ```
+    #[must_use]
+    pub fn from_house_mask(regions: &RegionLayout, house: House, mask: HouseMask) -> Self {
+        match house {
+            House::Row { row } => Self::from_bits(u128::from(mask.bits()) << (u32::from(row) * 9)),
+            House::Column { .. } | House::Box { .. } => {
+                let mut positions = Self::new();
+                for i in mask {
+                    positions.insert(house.position_from_cell_index(regions, i));
+                }
+                positions
+            }
+        }
+    }
+
     /// Returns a bitmask of row indices (0-8) that contain at least one position.
     ///
     /// This is derived from the current set of positions.
@@ -228,15 +394,14 @@ impl DigitPositions {
         cols
     }
 
-    /// Returns a bitmask of box indices (0-8) that contain at least one position.
-    ///
-    /// Boxes are numbered 0-8 from left to right, top to bottom.
+    /// Returns a bitmask of the box (region) indices (0-8) of `regions` that
+    /// contain at least one position.
     #[must_use]
     #[inline]
-    pub fn boxes_set(&self) -> HouseMask {
+    pub fn boxes_set(&self, regions: &RegionLayout) -> HouseMask {
         let mut boxes = HouseMask::new();
         for index in 0..9 {
-            boxes.set(index, !(*self & Self::BOX_POSITIONS[index]).is_empty());
+            boxes.set(index, !(*self & regions.region_positions(index)).is_empty());
         }
         boxes
     }
@@ -284,6 +449,8 @@ pub type HouseMask = BitSet9<CellIndexSemantics>;
 pub struct CandidateGrid {
     /// `digit_positions[digit]` represents possible positions for that digit
     digit_positions: DigitIndexedArray<DigitPositions>,
+    /// Boxes the digits must not repeat in, besides rows and columns.
+    regions: RegionLayout,
 }
 
 impl CandidateGrid {
@@ -316,7 +483,17 @@ impl CandidateGrid {
     /// ```
     #[must_use]
     pub fn from_digit_grid(digit_grid: &DigitGrid) -> Self {
-        let mut grid = Self::new();
+        Self::from_digit_grid_with_regions(digit_grid, RegionLayout::standard())
+    }
+
+    /// Creates a candidate grid from a [`DigitGrid`] whose boxes are the
+    /// regions of `regions`.
+    ///
+    /// Like [`from_digit_grid`](Self::from_digit_grid), but each placed digit
+    /// is removed from the peers of its region rather than its 3×3 box.
+    #[must_use]
+    pub fn from_digit_grid_with_regions(digit_grid: &DigitGrid, regions: RegionLayout) -> Self {
+        let mut grid = Self::new().with_regions(regions);
         for pos in Position::ALL {
             if let Some(digit) = digit_grid.get(pos) {
                 grid.place(pos, digit);
@@ -367,20 +544,34 @@ impl Default for CandidateGrid {
 /// // check_consistency will detect this as NoCandidates error
 /// assert!(grid.check_consistency().is_err());
 /// ```
-#[derive(Debug, Clone, Copy, derive_more::Display, derive_more::Error)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, derive_more::Display, derive_more::Error)]
 pub enum ConsistencyError {
     /// A position has no remaining candidates.
     ///
     /// This occurs when candidate removal results in a position with no possible digits,
-    /// making the puzzle unsolvable.
-    #[display("candidate grid has positions with no candidates")]
-    NoCandidates,
+    /// making the puzzle unsolvable. `pos` is the first such position in row-major order.
+    #[display("no candidates remain at {pos}")]
+    NoCandidates {
+        /// The position without candidates.
+        #[error(not(source))]
+        pos: Position,
+    },
     /// Multiple positions in the same constraint region have the same univalue digit.
     ///
     /// This occurs when the same digit appears more than once in a row, column, or box,
-    /// violating Sudoku rules.
-    #[display("candidate grid has duplicated univalue digits")]
-    DuplicatedUnivalueDigits,
+    /// violating Sudoku rules. Only the first offending house is reported.
+    #[display("digit {digit} appears more than once in {house}")]
+    DuplicatedUnivalueDigits {
+        /// The duplicated digit.
+        #[error(not(source))]
+        digit: Digit,
+        /// The house containing the duplicates.
+        #[error(not(source))]
+        house: House,
+        /// The univalue positions of `digit` within `house`.
+        #[error(not(source))]
+        positions: DigitPositions,
+    },
     /// Candidate constraints violate Sudoku rules.
     ///
     /// This occurs when candidate distributions imply an impossible state without
@@ -389,15 +580,105 @@ pub enum ConsistencyError {
     CandidateConstraintViolation,
 }
 
+impl ConsistencyError {
+    /// Returns the positions involved in the inconsistency.
+    ///
+    /// The set is empty for [`CandidateConstraintViolation`](Self::CandidateConstraintViolation),
+    /// which is not attributed to specific cells.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use numelace_core::{CandidateGrid, Digit, Position};
+    ///
+    /// let mut grid = CandidateGrid::new();
+    /// grid.place(Position::new(0, 0), Digit::D5);
+    /// grid.place(Position::new(0, 4), Digit::D5);
+    ///
+    /// let err = grid.check_consistency().unwrap_err();
+    /// assert_eq!(err.positions().len(), 2);
+    /// assert!(err.positions().contains(Position::new(0, 4)));
+    /// ```
+    #[must_use]
+    pub fn positions(&self) -> DigitPositions {
+        match *self {
+            ConsistencyError::NoCandidates { pos } => DigitPositions::from_elem(pos),
+            ConsistencyError::DuplicatedUnivalueDigits { positions, .. } => positions,
+            ConsistencyError::CandidateConstraintViolation => DigitPositions::EMPTY,
+        }
+    }
+}
+
 impl CandidateGrid {
     /// Creates a new candidate grid with all positions available for all digits.
     #[must_use]
     pub fn new() -> Self {
         Self {
             digit_positions: DigitIndexedArray::from([DigitPositions::FULL; 9]),
+            regions: RegionLayout::standard(),
         }
     }
 
+    /// Sets the region layout whose regions act as the boxes of the grid.
+    ///
+    /// Only the houses change; candidates are kept as they are, so set the
+    /// layout before placing digits. Use
+    /// [`from_digit_grid_with_regions`](Self::from_digit_grid_with_regions) to
+    /// start from givens.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use numelace_core::{CandidateGrid, Digit, House, Position, RegionLayout};
+    ///
+    /// let regions: RegionLayout = "\
+    ///     111122333\
+    ///     111222333\
+    ///     112222333\
+    ///     444555666\
+    ///     444555666\
+    ///     444555666\
+    ///     777888999\
+    ///     777888999\
+    ///     777888999"
+    ///     .parse()
+    ///     .unwrap();
+    /// let mut grid = CandidateGrid::new().with_regions(regions);
+    /// grid.place(Position::new(0, 3), Digit::D1);
+    /// grid.place(Position::new(0, 0), Digit::D1);
+    ///
+    /// // (0, 3) belongs to the first region, so the two digits clash.
+    /// assert!(grid.check_consistency().is_err());
+    /// ```
+    #[must_use]
+    pub fn with_regions(mut self, regions: RegionLayout) -> Self {
+        self.regions = regions;
+        self
+    }
+
+    /// Returns the region layout whose regions act as the boxes of the grid.
+    #[must_use]
+    #[inline]
+    pub fn regions(&self) -> &RegionLayout {
+        &self.regions
+    }
+
+    /// Returns the positions of `house`, with boxes taken from the grid's
+    /// region layout.
+    #[must_use]
+    #[inline]
+    pub fn house_positions(&self, house: House) -> DigitPositions {
+        house.positions(&self.regions)
+    }
+
+    /// Returns all positions sharing a row, column, or box with `pos`,
+    /// excluding `pos`, with boxes taken from the grid's region layout.
+    #[must_use]
+    #[inline]
+    pub fn house_peers(&self, pos: Position) -> DigitPositions {
+        self.regions.house_peers(pos)
+    }
+
     /// Converts the candidate grid to a digit grid containing only univalue positions.
     ///
     /// A position is considered "univalue" when it has exactly one candidate remaining.
@@ -650,12 +931,10 @@ impl CandidateGrid {
     #[inline]
     pub fn check_consistency(&self) -> Result<(), ConsistencyError> {
         let [empty_positions, univalue_positions] = self.classify_positions();
-        if !empty_positions.is_empty() {
-            return Err(ConsistencyError::NoCandidates);
-        }
-        if !self.placed_digits_are_unique(univalue_positions) {
-            return Err(ConsistencyError::DuplicatedUnivalueDigits);
+        if let Some(pos) = empty_positions.first() {
+            return Err(ConsistencyError::NoCandidates { pos });
         }
+        self.check_placed_digits_are_unique(univalue_positions)?;
         Ok(())
     }
 
@@ -682,12 +961,10 @@ impl CandidateGrid {
     #[inline]
     pub fn is_solved(&self) -> Result<bool, ConsistencyError> {
         let [empty_positions, univalue_positions] = self.classify_positions();
-        if !empty_positions.is_empty() {
-            return Err(ConsistencyError::NoCandidates);
-        }
-        if !self.placed_digits_are_unique(univalue_positions) {
-            return Err(ConsistencyError::DuplicatedUnivalueDigits);
+        if let Some(pos) = empty_positions.first() {
+            return Err(ConsistencyError::NoCandidates { pos });
         }
+        self.check_placed_digits_are_unique(univalue_positions)?;
         Ok(univalue_positions.len() == 81)
     }
 
@@ -806,7 +1083,7 @@ impl CandidateGrid {
     /// Checks that definite digits have no duplicates in rows, columns, or boxes.
     ///
     /// For each position in `univalue_positions`, verifies that its digit appears
-    /// exactly once in its respective row, column, and 3×3 box.
+    /// exactly once in its respective row, column, and box of the region layout.
     ///
     /// # Arguments
     ///
@@ -816,22 +1093,32 @@ impl CandidateGrid {
     ///
     /// `true` if all definite digits satisfy sudoku uniqueness constraints,
     /// `false` if any digit appears multiple times in the same row, column, or box.
-    fn placed_digits_are_unique(&self, univalue_positions: DigitPositions) -> bool {
+    fn check_placed_digits_are_unique(
+        &self,
+        univalue_positions: DigitPositions,
+    ) -> Result<(), ConsistencyError> {
         for digit in Digit::ALL {
             let digit_positions = univalue_positions & self.digit_positions[digit];
             for pos in digit_positions {
-                if digit_positions.positions_in_row(pos.row()).len() != 1 {
-                    return false;
-                }
-                if digit_positions.positions_in_col(pos.col()).len() != 1 {
-                    return false;
-                }
-                if digit_positions.positions_in_box(pos.box_index()).len() != 1 {
-                    return false;
+                for house in [
+                    House::Row { row: pos.row() },
+                    House::Column { col: pos.col() },
+                    House::Box {
+                        index: pos.box_index(&self.regions),
+                    },
+                ] {
+                    let positions = digit_positions & house.positions(&self.regions);
+                    if positions.len() != 1 {
+                        return Err(ConsistencyError::DuplicatedUnivalueDigits {
+                            digit,
+                            house,
+                            positions,
+                        });
+                    }
                 }
             }
         }
-        true
+        Ok(())
     }
 }
 
@@ -1001,6 +1288,57 @@ mod tests {
         assert!(positions.contains(5));
     }
 
+    #[test]
+    fn test_house_mask_round_trip() {
+        let mut positions = DigitPositions::new();
+        for pos in [
+            Position::new(0, 0),
+            Position::new(0, 8),
+            Position::new(4, 4),
+            Position::new(5, 3),
+            Position::new(8, 0),
+            Position::new(8, 8),
+        ] {
+            positions.insert(pos);
+        }
+
+        let jigsaw: RegionLayout = "\
+            111122333\
+            111222333\
+            112222333\
+            444555666\
+            444555666\
+            444555666\
+            777888999\
+            777888999\
+            777888999"
+            .parse()
+            .unwrap();
+        for regions in [RegionLayout::standard(), jigsaw] {
+            for house in House::ALL {
+                let mask = positions.positions_in_house(&regions, house);
+                for i in 0..9 {
+                    assert_eq!(
+                        mask.contains(i),
+                        positions.contains(house.position_from_cell_index(&regions, i)),
+                        "{house:?} cell {i}"
+                    );
+                }
+                assert_eq!(
+                    DigitPositions::from_house_mask(&regions, house, mask),
+                    positions & house.positions(&regions)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_const_digit_set() {
+        for digit in Digit::ALL {
+            assert_eq!(DigitSet::from_digit(digit), DigitSet::from_elem(digit));
+        }
+    }
+
     #[test]
     fn test_house_sets() {
         let mut positions = DigitPositions::new();
@@ -1022,7 +1360,7 @@ mod tests {
         assert!(cols.contains(4));
         assert!(cols.contains(8));
 
-        let boxes = positions.boxes_set();
+        let boxes = positions.boxes_set(&RegionLayout::standard());
         assert_eq!(boxes.len(), 3);
         assert!(boxes.contains(0));
         assert!(boxes.contains(1));
@@ -1050,11 +1388,16 @@ mod tests {
 
         let mut grid = CandidateGrid::new();
         for pos in Position::BOXES[4] {
-            if pos.box_cell_index() != 4 {
+            if pos.box_cell_index(grid.regions()) != 4 {
                 grid.remove_candidate(pos, D9);
             }
         }
-        assert_eq!(grid.digit_positions(D9).positions_in_box(4).len(), 1);
+        assert_eq!(
+            grid.digit_positions(D9)
+                .positions_in_box(grid.regions(), 4)
+                .len(),
+            1
+        );
     }
 
     #[test]
@@ -1071,13 +1414,57 @@ mod tests {
         for digit in Digit::ALL {
             grid.remove_candidate(pos, digit);
         }
-        assert!(grid.check_consistency().is_err());
+        assert_eq!(
+            grid.check_consistency(),
+            Err(ConsistencyError::NoCandidates { pos })
+        );
 
         // Detects violation: duplicate digits in same house
         let mut grid = CandidateGrid::new();
         grid.place(Position::new(0, 0), D5);
         grid.place(Position::new(1, 0), D5);
-        assert!(grid.check_consistency().is_err());
+        let err = grid.check_consistency().unwrap_err();
+        let expected = DigitPositions::from_position(Position::new(0, 0))
+            | DigitPositions::from_position(Position::new(1, 0));
+        assert_eq!(
+            err,
+            ConsistencyError::DuplicatedUnivalueDigits {
+                digit: D5,
+                house: House::Column { col: 0 },
+                positions: expected,
+            }
+        );
+        assert_eq!(err.positions(), expected);
+        assert_eq!(
+            err.to_string(),
+            "digit 5 appears more than once in column 1"
+        );
+
+        // Boxes follow the region layout: (0, 3) joins the first region.
+        let regions: RegionLayout = "\
+            111122333\
+            111222333\
+            112222333\
+            444555666\
+            444555666\
+            444555666\
+            777888999\
+            777888999\
+            777888999"
+            .parse()
+            .unwrap();
+        let mut grid = CandidateGrid::new();
+        grid.place(Position::new(0, 3), D5);
+        grid.place(Position::new(1, 1), D5);
+        assert!(grid.check_consistency().is_ok());
+        let grid = grid.with_regions(regions);
+        assert!(matches!(
+            grid.check_consistency(),
+            Err(ConsistencyError::DuplicatedUnivalueDigits {
+                house: House::Box { index: 0 },
+                ..
+            })
+        ));
     }
 
     #[test]
@@ -0,0 +1,180 @@
+//! Canonical forms of digit grids.
+//!
+//! Two puzzles are essentially the same when one turns into the other by
+//! validity-preserving transformations: transposing the grid, reordering the
+//! bands or stacks, reordering the rows within a band or the columns within a
+//! stack, and relabelling the digits. The canonical form picks one fixed grid
+//! out of all those variants, so equal canonical forms mean equivalent puzzles.
+
+use crate::{Digit, DigitGrid, Position};
+
+/// Orders of three items.
+const PERMUTATIONS_3: [[u8; 3]; 6] = [
+    [0, 1, 2],
+    [0, 2, 1],
+    [1, 0, 2],
+    [1, 2, 0],
+    [2, 0, 1],
+    [2, 1, 0],
+];
+
+/// Number of line orders: the band order times the row order within each band.
+const LINE_ORDER_COUNT: usize = 6 * 6 * 6 * 6;
+
+impl DigitGrid {
+    /// Returns the canonical form of the grid.
+    ///
+    /// The canonical form is the variant of the grid, under transposition,
+    /// band, stack, row and column reordering and digit relabelling, whose
+    /// digits read smallest in row-major order. Empty cells read as 0 and digits
+    /// are relabelled in order of first appearance. Two grids have the same
+    /// canonical form exactly when one is a transformation of the other.
+    ///
+    /// This searches all 3,359,232 geometric variants, so it is meant for
+    /// one-off comparisons such as recognizing an imported puzzle, not for
+    /// inner loops.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use numelace_core::DigitGrid;
+    ///
+    /// let grid: DigitGrid = format!("9{}", ".".repeat(80)).parse().unwrap();
+    /// let moved: DigitGrid = format!("{}4", ".".repeat(80)).parse().unwrap();
+    /// assert_eq!(grid.canonical_form(), moved.canonical_form());
+    /// ```
+    #[must_use]
+    pub fn canonical_form(&self) -> Self {
+        let line_orders = line_orders();
+        let values: [u8; 81] =
+            Position::ALL.map(|pos| self.get(pos).map_or(0, |digit| digit.value()));
+        let transposed: [u8; 81] = Position::ALL
+            .map(|pos| values[usize::from(Position::new(pos.col(), pos.row()).index())]);
+
+        let mut best = [u8::MAX; 81];
+        let mut candidate = [0; 81];
+        for grid in [&values, &transposed] {
+            for rows in &line_orders {
+                for cols in &line_orders {
+                    if relabel_if_smaller(grid, rows, cols, &best, &mut candidate) {
+                        best = candidate;
+                    }
+                }
+            }
+        }
+        Self::from_array(best.map(|value| (value != 0).then(|| Digit::from_value(value))))
+    }
+}
+
+/// Writes the variant of `grid` with lines taken in the orders `rows` and
+/// `cols` into `candidate`, with digits relabelled by first appearance.
+///
+/// Returns true if the variant reads smaller than `best`. Stops early and
+/// returns false as soon as it reads larger.
+fn relabel_if_smaller(
+    grid: &[u8; 81],
+    rows: &[u8; 9],
+    cols: &[u8; 9],
+    best: &[u8; 81],
+    candidate: &mut [u8; 81],
+) -> bool {
+    let mut labels = [0_u8; 10];
+    let mut next_label = 1;
+    let mut smaller = false;
+    for (i, out) in candidate.iter_mut().enumerate() {
+        let value = grid[usize::from(rows[i / 9]) * 9 + usize::from(cols[i % 9])];
+        let label = if value == 0 {
+            0
+        } else {
+            let label = &mut labels[usize::from(value)];
+            if *label == 0 {
+                *label = next_label;
+                next_label += 1;
+            }
+            *label
+        };
+        if !smaller {
+            if label > best[i] {
+                return false;
+            }
+            smaller = label < best[i];
+        }
+        *out = label;
+    }
+    smaller
+}
+
+/// Lists the line orders that keep lines within their band or stack.
+fn line_orders() -> Vec<[u8; 9]> {
+    let mut orders = Vec::with_capacity(LINE_ORDER_COUNT);
+    for bands in PERMUTATIONS_3 {
+        for first in PERMUTATIONS_3 {
+            for second in PERMUTATIONS_3 {
+                for third in PERMUTATIONS_3 {
+                    let within = [first, second, third];
+                    let mut order = [0; 9];
+                    for (i, line) in order.iter_mut().enumerate() {
+                        *line = bands[i / 3] * 3 + within[i / 3][i % 3];
+                    }
+                    orders.push(order);
+                }
+            }
+        }
+    }
+    orders
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUZZLE: &str =
+        "530070000600195000098000060800060003400803001700020006060000280000419005000080079";
+
+    fn transform(grid: &DigitGrid, map: impl Fn(Position) -> Position) -> DigitGrid {
+        let mut out = DigitGrid::new();
+        for pos in Position::ALL {
+            out.set(map(pos), grid.get(pos));
+        }
+        out
+    }
+
+    #[test]
+    fn test_equivalent_grids_share_a_canonical_form() {
+        let grid: DigitGrid = PUZZLE.parse().unwrap();
+        let canonical = grid.canonical_form();
+
+        // Transpose, swap the first two bands, and swap two columns in a stack.
+        let moved = transform(&grid, |pos| {
+            let row = match pos.col() {
+                row @ 0..3 => row + 3,
+                row @ 3..6 => row - 3,
+                row => row,
+            };
+            let col = match pos.row() {
+                7 => 8,
+                8 => 7,
+                col => col,
+            };
+            Position::new(row, col)
+        });
+        // Relabel every digit.
+        let relabeled = DigitGrid::from_array(Position::ALL.map(|pos| {
+            moved
+                .get(pos)
+                .map(|digit| Digit::from_value(10 - digit.value()))
+        }));
+
+        assert_ne!(relabeled, grid);
+        assert_eq!(relabeled.canonical_form(), canonical);
+        assert_eq!(canonical.canonical_form(), canonical);
+    }
+
+    #[test]
+    fn test_different_grids_differ() {
+        let grid: DigitGrid = PUZZLE.parse().unwrap();
+        let mut other = grid.clone();
+        other.set(Position::new(0, 2), Some(Digit::D4));
+        assert_ne!(grid.canonical_form(), other.canonical_form());
+    }
+}
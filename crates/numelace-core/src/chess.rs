@@ -0,0 +1,232 @@
+use crate::{
+    BoxedConstraint, CandidateGrid, ConsistencyError, Constraint, ConstraintViolation, DigitGrid,
+    DigitPositions, Position,
+};
+
+const KNIGHT_OFFSETS: [(i8, i8); 8] = [
+    (-2, -1),
+    (-2, 1),
+    (-1, -2),
+    (-1, 2),
+    (1, -2),
+    (1, 2),
+    (2, -1),
+    (2, 1),
+];
+
+const KING_OFFSETS: [(i8, i8); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+const NAME: &str = "Anti-chess";
+
+/// Anti-knight and anti-king rules.
+///
+/// With anti-knight enabled, cells a chess knight's move apart cannot hold
+/// the same digit. With anti-king enabled, the same applies to cells a king's
+/// move apart (including diagonal neighbors).
+///
+/// # Examples
+///
+/// ```
+/// use numelace_core::{ChessRules, Position};
+///
+/// let rules = ChessRules::new().with_anti_knight(true);
+/// assert!(rules.peers(Position::new(0, 0)).contains(Position::new(1, 2)));
+/// assert!(!rules.peers(Position::new(0, 0)).contains(Position::new(1, 1)));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ChessRules {
+    anti_knight: bool,
+    anti_king: bool,
+}
+
+impl ChessRules {
+    /// Creates a rule set with both rules disabled.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            anti_knight: false,
+            anti_king: false,
+        }
+    }
+
+    /// Returns the rules with anti-knight enabled or disabled.
+    #[must_use]
+    pub const fn with_anti_knight(mut self, enabled: bool) -> Self {
+        self.anti_knight = enabled;
+        self
+    }
+
+    /// Returns the rules with anti-king enabled or disabled.
+    #[must_use]
+    pub const fn with_anti_king(mut self, enabled: bool) -> Self {
+        self.anti_king = enabled;
+        self
+    }
+
+    /// Returns `true` if the anti-knight rule is enabled.
+    #[must_use]
+    pub const fn anti_knight(self) -> bool {
+        self.anti_knight
+    }
+
+    /// Returns `true` if the anti-king rule is enabled.
+    #[must_use]
+    pub const fn anti_king(self) -> bool {
+        self.anti_king
+    }
+
+    /// Returns `true` if neither rule is enabled.
+    #[must_use]
+    pub const fn is_empty(self) -> bool {
+        !self.anti_knight && !self.anti_king
+    }
+
+    /// Returns the cells that must not share a digit with `pos` under these rules.
+    ///
+    /// Cells that already share a row, column, or box with `pos` are included
+    /// when a move reaches them.
+    #[must_use]
+    pub fn peers(self, pos: Position) -> DigitPositions {
+        let mut peers = DigitPositions::EMPTY;
+        let offsets = self
+            .anti_knight
+            .then_some(KNIGHT_OFFSETS)
+            .into_iter()
+            .chain(self.anti_king.then_some(KING_OFFSETS))
+            .flatten();
+        for (dr, dc) in offsets {
+            let (Some(row), Some(col)) = (
+                pos.row().checked_add_signed(dr),
+                pos.col().checked_add_signed(dc),
+            ) else {
+                continue;
+            };
+            if row < 9 && col < 9 {
+                peers.insert(Position::new(row, col));
+            }
+        }
+        peers
+    }
+
+    fn violation_at(self, grid: &DigitGrid, pos: Position) -> Result<(), ConstraintViolation> {
+        let Some(digit) = grid.get(pos) else {
+            return Ok(());
+        };
+        match self
+            .peers(pos)
+            .iter()
+            .find(|&peer| grid.get(peer) == Some(digit))
+        {
+            Some(peer) => Err(ConstraintViolation {
+                name: NAME,
+                positions: DigitPositions::from_iter([pos, peer]),
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Constraint for ChessRules {
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    fn clone_box(&self) -> BoxedConstraint {
+        Box::new(*self)
+    }
+
+    fn check(&self, grid: &DigitGrid) -> Result<(), ConstraintViolation> {
+        if self.is_empty() {
+            return Ok(());
+        }
+        Position::ALL
+            .into_iter()
+            .try_for_each(|pos| self.violation_at(grid, pos))
+    }
+
+    fn check_cell(&self, grid: &DigitGrid, pos: Position) -> Result<(), ConstraintViolation> {
+        self.violation_at(grid, pos)
+    }
+
+    fn prune(&self, candidates: &mut CandidateGrid) -> Result<bool, ConsistencyError> {
+        if self.is_empty() {
+            return Ok(false);
+        }
+        let mut changed = false;
+        for pos in candidates.univalue_positions() {
+            let digits = candidates.candidates_at(pos);
+            for peer in self.peers(pos) {
+                changed |= candidates.remove_candidate_set(peer, digits);
+                if candidates.candidates_at(peer).is_empty() {
+                    return Err(ConsistencyError::NoCandidates { pos: peer });
+                }
+            }
+        }
+        Ok(changed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+    use crate::{Digit, DigitSet};
+
+    #[test]
+    fn test_peers() {
+        let knight = ChessRules::new().with_anti_knight(true);
+        assert_eq!(knight.peers(Position::new(4, 4)).len(), 8);
+        assert_eq!(knight.peers(Position::new(0, 0)).len(), 2);
+
+        let king = ChessRules::new().with_anti_king(true);
+        assert_eq!(king.peers(Position::new(4, 4)).len(), 8);
+        assert_eq!(king.peers(Position::new(8, 8)).len(), 3);
+
+        let both = knight.with_anti_king(true);
+        assert_eq!(both.peers(Position::new(4, 4)).len(), 16);
+        assert!(ChessRules::new().peers(Position::new(4, 4)).is_empty());
+    }
+
+    #[test]
+    fn test_check() {
+        let grid = |s: &str| DigitGrid::from_str(&format!("{s:.<81}")).unwrap();
+        let knight = ChessRules::new().with_anti_knight(true);
+        // (0, 0) and (1, 2) are a knight's move apart.
+        let filled = grid("1..........1");
+        assert!(knight.check(&filled).is_err());
+        assert!(knight.check_cell(&filled, Position::new(1, 2)).is_err());
+        assert_eq!(knight.check_cell(&filled, Position::new(4, 4)), Ok(()));
+        assert_eq!(ChessRules::new().check(&filled), Ok(()));
+
+        let king = ChessRules::new().with_anti_king(true);
+        assert_eq!(king.check(&filled), Ok(()));
+        assert!(king.check(&grid("1.........1")).is_err());
+    }
+
+    #[test]
+    fn test_prune() {
+        let rules = ChessRules::new().with_anti_knight(true);
+        let mut candidates = CandidateGrid::new();
+        candidates.place(Position::new(0, 0), Digit::D1);
+        assert!(rules.prune(&mut candidates).unwrap());
+        assert!(
+            !candidates
+                .candidates_at(Position::new(2, 1))
+                .contains(Digit::D1)
+        );
+        assert!(!rules.prune(&mut candidates).unwrap());
+
+        candidates.set_candidate_at(Position::new(1, 2), DigitSet::from_elem(Digit::D1));
+        assert!(rules.prune(&mut candidates).is_err());
+    }
+}
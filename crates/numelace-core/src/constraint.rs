@@ -0,0 +1,238 @@
+use std::fmt::Debug;
+
+use crate::{
+    CageSet, CandidateGrid, ConsistencyError, DigitGrid, DigitPositions, EdgeConstraintSet,
+    Position,
+};
+
+/// A variant rule that applies on top of the classic row/column/box rules.
+///
+/// Implementations describe one kind of extra rule (killer cages, edge markers,
+/// ...). Game logic uses [`check`](Constraint::check) and
+/// [`check_cell`](Constraint::check_cell) to find rule violations, and solvers
+/// use [`prune`](Constraint::prune) to remove candidates that the rule rules out,
+/// so new variants can be supported without changes to either.
+pub trait Constraint: Debug + Send + Sync {
+    /// Returns a short, human-readable name of the rule.
+    fn name(&self) -> &'static str;
+
+    /// Returns a boxed clone of the constraint.
+    fn clone_box(&self) -> BoxedConstraint;
+
+    /// Checks the rule against a (partially) filled grid.
+    ///
+    /// Empty cells never cause a violation on their own.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first [`ConstraintViolation`] found.
+    fn check(&self, grid: &DigitGrid) -> Result<(), ConstraintViolation>;
+
+    /// Checks only the parts of the rule that involve `pos`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ConstraintViolation`] if a part of the rule involving `pos`
+    /// is broken.
+    fn check_cell(&self, grid: &DigitGrid, pos: Position) -> Result<(), ConstraintViolation>;
+
+    /// Removes candidates that cannot satisfy the rule.
+    ///
+    /// Returns `true` if any candidate was removed. Peer eliminations of the
+    /// classic rules are not performed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConsistencyError`] if the rule can no longer be satisfied.
+    fn prune(&self, candidates: &mut CandidateGrid) -> Result<bool, ConsistencyError>;
+}
+
+/// A boxed [`Constraint`].
+pub type BoxedConstraint = Box<dyn Constraint>;
+
+impl Clone for BoxedConstraint {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+/// A violation of a [`Constraint`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, derive_more::Display, derive_more::Error)]
+#[display("{name} rule is violated")]
+pub struct ConstraintViolation {
+    /// Name of the violated rule.
+    pub name: &'static str,
+    /// Cells involved in the violation.
+    #[error(not(source))]
+    pub positions: DigitPositions,
+}
+
+const CAGE_NAME: &str = "Killer cage";
+const EDGE_NAME: &str = "Edge marker";
+
+impl Constraint for CageSet {
+    fn name(&self) -> &'static str {
+        CAGE_NAME
+    }
+
+    fn clone_box(&self) -> BoxedConstraint {
+        Box::new(self.clone())
+    }
+
+    fn check(&self, grid: &DigitGrid) -> Result<(), ConstraintViolation> {
+        self.iter().try_for_each(|cage| {
+            cage.check(grid).map_err(|_| ConstraintViolation {
+                name: CAGE_NAME,
+                positions: cage.positions(),
+            })
+        })
+    }
+
+    fn check_cell(&self, grid: &DigitGrid, pos: Position) -> Result<(), ConstraintViolation> {
+        let Some(cage) = self.cage_at(pos) else {
+            return Ok(());
+        };
+        cage.check(grid).map_err(|_| ConstraintViolation {
+            name: CAGE_NAME,
+            positions: cage.positions(),
+        })
+    }
+
+    fn prune(&self, candidates: &mut CandidateGrid) -> Result<bool, ConsistencyError> {
+        let mut changed = false;
+        for cage in self {
+            let digits = cage
+                .open_cell_digits(|pos| candidates.candidates_at(pos))
+                .ok_or(ConsistencyError::CandidateConstraintViolation)?;
+            for pos in cage.positions() & !candidates.univalue_positions() {
+                changed |= candidates.remove_candidate_set(pos, !digits);
+            }
+        }
+        Ok(changed)
+    }
+}
+
+impl Constraint for EdgeConstraintSet {
+    fn name(&self) -> &'static str {
+        EDGE_NAME
+    }
+
+    fn clone_box(&self) -> BoxedConstraint {
+        Box::new(self.clone())
+    }
+
+    fn check(&self, grid: &DigitGrid) -> Result<(), ConstraintViolation> {
+        EdgeConstraintSet::check(self, grid).map_err(|violation| ConstraintViolation {
+            name: EDGE_NAME,
+            positions: DigitPositions::from_iter([
+                violation.constraint.first(),
+                violation.constraint.second(),
+            ]),
+        })
+    }
+
+    fn check_cell(&self, grid: &DigitGrid, pos: Position) -> Result<(), ConstraintViolation> {
+        self.touching(pos).try_for_each(|edge| {
+            edge.check(grid).map_err(|_| ConstraintViolation {
+                name: EDGE_NAME,
+                positions: DigitPositions::from_iter([edge.first(), edge.second()]),
+            })
+        })
+    }
+
+    fn prune(&self, candidates: &mut CandidateGrid) -> Result<bool, ConsistencyError> {
+        let mut changed = false;
+        for edge in self {
+            let (first, second) = (edge.first(), edge.second());
+            let (first_digits, second_digits) = edge.supported_digits(
+                candidates.candidates_at(first),
+                candidates.candidates_at(second),
+            );
+            if first_digits.is_empty() || second_digits.is_empty() {
+                return Err(ConsistencyError::CandidateConstraintViolation);
+            }
+            changed |= candidates.remove_candidate_set(first, !first_digits);
+            changed |= candidates.remove_candidate_set(second, !second_digits);
+        }
+        Ok(changed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+    use crate::{Cage, Digit, DigitSet, EdgeConstraint, EdgeKind};
+
+    fn boxed_constraints() -> Vec<BoxedConstraint> {
+        let cage = Cage::new(
+            DigitPositions::from_iter([Position::new(0, 0), Position::new(4, 4)]),
+            3,
+        )
+        .unwrap();
+        let edge = EdgeConstraint::new(
+            Position::new(8, 0),
+            Position::new(8, 1),
+            EdgeKind::Consecutive,
+        )
+        .unwrap();
+        vec![
+            Box::new(CageSet::from_cages([cage]).unwrap()),
+            Box::new(EdgeConstraintSet::from_constraints([edge]).unwrap()),
+        ]
+    }
+
+    #[test]
+    fn test_check_through_trait_objects() {
+        let constraints = boxed_constraints();
+        let grid = |s: &str| DigitGrid::from_str(&format!("{s:.<81}")).unwrap();
+
+        let mut filled = grid("1");
+        for constraint in &constraints {
+            assert_eq!(constraint.check(&filled), Ok(()));
+        }
+        filled.set(Position::new(4, 4), Some(Digit::D1));
+        filled.set(Position::new(8, 0), Some(Digit::D5));
+        filled.set(Position::new(8, 1), Some(Digit::D9));
+        let violation = constraints[0].check(&filled).unwrap_err();
+        assert!(violation.positions.contains(Position::new(4, 4)));
+        assert!(constraints[1].check(&filled).is_err());
+
+        // Only rules involving the cell are reported.
+        assert_eq!(
+            constraints[1].check_cell(&filled, Position::new(4, 4)),
+            Ok(())
+        );
+        assert!(
+            constraints[1]
+                .check_cell(&filled, Position::new(8, 1))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_prune() {
+        let constraints = boxed_constraints();
+        let mut candidates = CandidateGrid::new();
+        candidates.place(Position::new(8, 0), Digit::D1);
+        for constraint in &constraints {
+            assert!(constraint.prune(&mut candidates).unwrap());
+        }
+        assert_eq!(
+            candidates.candidates_at(Position::new(0, 0)),
+            DigitSet::from_iter([Digit::D1, Digit::D2])
+        );
+        assert_eq!(
+            candidates.candidates_at(Position::new(8, 1)),
+            DigitSet::from_elem(Digit::D2)
+        );
+        for constraint in &constraints {
+            assert!(!constraint.clone().prune(&mut candidates).unwrap());
+        }
+
+        let mut candidates = CandidateGrid::new();
+        candidates.place(Position::new(0, 0), Digit::D3);
+        assert!(constraints[0].prune(&mut candidates).is_err());
+    }
+}
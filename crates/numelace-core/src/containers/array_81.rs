@@ -184,6 +184,26 @@ where
         }))
     }
 
+    /// Returns a reference to the underlying raw array.
+    ///
+    /// This is a const function, so elements can be looked up by their raw index
+    /// in const contexts where the [`Index`](std::ops::Index) implementation is unavailable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use numelace_core::{containers::Array81, index::PositionSemantics};
+    ///
+    /// const MY_ARRAY: Array81<usize, PositionSemantics> = Array81::from_array([7; 81]);
+    /// const VALUE: usize = MY_ARRAY.as_array()[40];
+    /// assert_eq!(VALUE, 7);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn as_array(&self) -> &[T; 81] {
+        &self.array
+    }
+
     /// Returns an iterator over the array elements.
     ///
     /// # Examples
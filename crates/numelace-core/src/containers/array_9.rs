@@ -184,6 +184,26 @@ where
         }))
     }
 
+    /// Returns a reference to the underlying raw array.
+    ///
+    /// This is a const function, so elements can be looked up by their raw index
+    /// in const contexts where the [`Index`](std::ops::Index) implementation is unavailable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use numelace_core::{containers::Array9, index::CellIndexSemantics};
+    ///
+    /// const MY_ARRAY: Array9<usize, CellIndexSemantics> = Array9::from_array([7; 9]);
+    /// const VALUE: usize = MY_ARRAY.as_array()[3];
+    /// assert_eq!(VALUE, 7);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn as_array(&self) -> &[T; 9] {
+        &self.array
+    }
+
     /// Returns an iterator over the array elements.
     ///
     /// # Examples
@@ -149,7 +149,7 @@ where
 
     /// Returns the raw bits representing the set.
     #[must_use]
-    pub fn bits(self) -> u128 {
+    pub const fn bits(self) -> u128 {
         self.bits
     }
 
@@ -127,7 +127,7 @@ where
 
     /// Returns the raw bits representing the set.
     #[must_use]
-    pub fn bits(self) -> u16 {
+    pub const fn bits(self) -> u16 {
         self.bits
     }
 
@@ -0,0 +1,383 @@
+use crate::{Digit, DigitGrid, DigitSet, Position};
+
+/// Relation marked on the edge between two orthogonally adjacent cells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EdgeKind {
+    /// The first cell is less than the second (`<`).
+    LessThan,
+    /// The first cell is greater than the second (`>`).
+    GreaterThan,
+    /// The two cells hold consecutive digits (a dot on the edge).
+    Consecutive,
+}
+
+impl EdgeKind {
+    /// Returns the relation seen from the other cell.
+    #[must_use]
+    pub const fn reversed(self) -> Self {
+        match self {
+            Self::LessThan => Self::GreaterThan,
+            Self::GreaterThan => Self::LessThan,
+            Self::Consecutive => Self::Consecutive,
+        }
+    }
+
+    /// Returns `true` if `first` and `second` satisfy this relation.
+    #[must_use]
+    pub const fn is_satisfied_by(self, first: Digit, second: Digit) -> bool {
+        let (first, second) = (first.value(), second.value());
+        match self {
+            Self::LessThan => first < second,
+            Self::GreaterThan => first > second,
+            Self::Consecutive => first.abs_diff(second) == 1,
+        }
+    }
+}
+
+/// A constraint marker between two orthogonally adjacent cells.
+///
+/// The cells are stored in reading order: `first` is the upper or left cell
+/// and `second` is the lower or right cell. The [`EdgeKind`] relates the
+/// digit of `first` to the digit of `second`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EdgeConstraint {
+    first: Position,
+    second: Position,
+    kind: EdgeKind,
+}
+
+/// Errors that can occur when constructing an [`EdgeConstraint`] or an
+/// [`EdgeConstraintSet`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, derive_more::Display, derive_more::Error)]
+pub enum EdgeConstraintError {
+    /// The two cells are not orthogonally adjacent.
+    #[display("cells {first} and {second} are not adjacent")]
+    NotAdjacent {
+        /// One end of the edge.
+        first: Position,
+        /// The other end of the edge.
+        second: Position,
+    },
+    /// The edge between the two cells already carries a constraint.
+    #[display("edge between {first} and {second} already has a constraint")]
+    Duplicated {
+        /// Upper or left end of the edge.
+        first: Position,
+        /// Lower or right end of the edge.
+        second: Position,
+    },
+}
+
+/// A rule violation detected on a constrained edge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, derive_more::Display, derive_more::Error)]
+#[display("digits at {} and {} violate the {:?} constraint", constraint.first, constraint.second, constraint.kind)]
+pub struct EdgeViolation {
+    /// The violated constraint.
+    #[error(not(source))]
+    pub constraint: EdgeConstraint,
+}
+
+impl EdgeConstraint {
+    /// Creates a constraint relating the digit at `a` to the digit at `b`.
+    ///
+    /// The cells may be given in any order; the constraint is normalized so
+    /// that [`first`](Self::first) is the upper or left cell.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EdgeConstraintError::NotAdjacent`] if the cells do not share
+    /// an edge.
+    pub fn new(a: Position, b: Position, kind: EdgeKind) -> Result<Self, EdgeConstraintError> {
+        if a.right() == Some(b) || a.down() == Some(b) {
+            Ok(Self {
+                first: a,
+                second: b,
+                kind,
+            })
+        } else if b.right() == Some(a) || b.down() == Some(a) {
+            Ok(Self {
+                first: b,
+                second: a,
+                kind: kind.reversed(),
+            })
+        } else {
+            Err(EdgeConstraintError::NotAdjacent {
+                first: a,
+                second: b,
+            })
+        }
+    }
+
+    /// Returns the upper or left cell of the edge.
+    #[must_use]
+    pub const fn first(self) -> Position {
+        self.first
+    }
+
+    /// Returns the lower or right cell of the edge.
+    #[must_use]
+    pub const fn second(self) -> Position {
+        self.second
+    }
+
+    /// Returns the relation between the digit of `first` and the digit of `second`.
+    #[must_use]
+    pub const fn kind(self) -> EdgeKind {
+        self.kind
+    }
+
+    /// Returns `true` if the edge is vertical, i.e. `second` is right of `first`.
+    #[must_use]
+    pub fn is_vertical(self) -> bool {
+        self.first.row() == self.second.row()
+    }
+
+    /// Returns `true` if `pos` is one of the two cells of the edge.
+    #[must_use]
+    pub fn touches(self, pos: Position) -> bool {
+        self.first == pos || self.second == pos
+    }
+
+    /// Returns the candidates of each cell that have a partner on the other side.
+    ///
+    /// Given the candidates of `first` and `second`, returns the subsets that
+    /// can still satisfy the relation. An empty subset means the constraint
+    /// cannot be satisfied.
+    #[must_use]
+    pub fn supported_digits(self, first: DigitSet, second: DigitSet) -> (DigitSet, DigitSet) {
+        let mut first_supported = DigitSet::EMPTY;
+        let mut second_supported = DigitSet::EMPTY;
+        for a in first {
+            for b in second {
+                if self.kind.is_satisfied_by(a, b) {
+                    first_supported.insert(a);
+                    second_supported.insert(b);
+                }
+            }
+        }
+        (first_supported, second_supported)
+    }
+
+    /// Checks the constraint against the digits returned by `digit_at`.
+    ///
+    /// The constraint is only checked once both cells hold a digit.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EdgeViolation`] if both digits are placed and break the relation.
+    pub fn check_with<F>(self, mut digit_at: F) -> Result<(), EdgeViolation>
+    where
+        F: FnMut(Position) -> Option<Digit>,
+    {
+        if let (Some(first), Some(second)) = (digit_at(self.first), digit_at(self.second))
+            && !self.kind.is_satisfied_by(first, second)
+        {
+            return Err(EdgeViolation { constraint: self });
+        }
+        Ok(())
+    }
+
+    /// Checks the constraint against a [`DigitGrid`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EdgeViolation`] if both digits are placed and break the relation.
+    pub fn check(self, grid: &DigitGrid) -> Result<(), EdgeViolation> {
+        self.check_with(|pos| grid.get(pos))
+    }
+}
+
+/// A set of edge constraints with at most one constraint per edge.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct EdgeConstraintSet {
+    constraints: Vec<EdgeConstraint>,
+}
+
+impl EdgeConstraintSet {
+    /// Creates an empty constraint set.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a constraint set from the given constraints.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EdgeConstraintError::Duplicated`] if two constraints share an edge.
+    pub fn from_constraints<I>(constraints: I) -> Result<Self, EdgeConstraintError>
+    where
+        I: IntoIterator<Item = EdgeConstraint>,
+    {
+        let mut set = Self::new();
+        for constraint in constraints {
+            set.insert(constraint)?;
+        }
+        Ok(set)
+    }
+
+    /// Adds a constraint to the set.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EdgeConstraintError::Duplicated`] if the edge already has a constraint.
+    pub fn insert(&mut self, constraint: EdgeConstraint) -> Result<(), EdgeConstraintError> {
+        if self.between(constraint.first, constraint.second).is_some() {
+            return Err(EdgeConstraintError::Duplicated {
+                first: constraint.first,
+                second: constraint.second,
+            });
+        }
+        self.constraints.push(constraint);
+        Ok(())
+    }
+
+    /// Returns `true` if the set contains no constraints.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.constraints.is_empty()
+    }
+
+    /// Returns the number of constraints.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.constraints.len()
+    }
+
+    /// Returns an iterator over the constraints.
+    pub fn iter(&self) -> std::slice::Iter<'_, EdgeConstraint> {
+        self.constraints.iter()
+    }
+
+    /// Returns the constraint on the edge between `a` and `b`, if any.
+    #[must_use]
+    pub fn between(&self, a: Position, b: Position) -> Option<&EdgeConstraint> {
+        self.constraints
+            .iter()
+            .find(|constraint| constraint.touches(a) && constraint.touches(b))
+    }
+
+    /// Returns an iterator over the constraints touching `pos`.
+    pub fn touching(&self, pos: Position) -> impl Iterator<Item = &EdgeConstraint> {
+        self.constraints
+            .iter()
+            .filter(move |constraint| constraint.touches(pos))
+    }
+
+    /// Checks every constraint against a [`DigitGrid`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the first [`EdgeViolation`] found.
+    pub fn check(&self, grid: &DigitGrid) -> Result<(), EdgeViolation> {
+        self.constraints
+            .iter()
+            .try_for_each(|constraint| constraint.check(grid))
+    }
+}
+
+impl<'a> IntoIterator for &'a EdgeConstraintSet {
+    type Item = &'a EdgeConstraint;
+    type IntoIter = std::slice::Iter<'a, EdgeConstraint>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+
+    #[test]
+    fn test_new_normalizes_order() {
+        let a = Position::new(0, 1);
+        let b = Position::new(0, 0);
+        let constraint = EdgeConstraint::new(a, b, EdgeKind::LessThan).unwrap();
+        assert_eq!(constraint.first(), b);
+        assert_eq!(constraint.second(), a);
+        assert_eq!(constraint.kind(), EdgeKind::GreaterThan);
+        assert!(constraint.is_vertical());
+
+        assert_eq!(
+            EdgeConstraint::new(
+                Position::new(0, 0),
+                Position::new(1, 1),
+                EdgeKind::Consecutive
+            ),
+            Err(EdgeConstraintError::NotAdjacent {
+                first: Position::new(0, 0),
+                second: Position::new(1, 1),
+            })
+        );
+    }
+
+    #[test]
+    fn test_supported_digits() {
+        let less =
+            EdgeConstraint::new(Position::new(0, 0), Position::new(1, 0), EdgeKind::LessThan)
+                .unwrap();
+        let (first, second) = less.supported_digits(DigitSet::FULL, DigitSet::FULL);
+        assert!(!first.contains(Digit::D9));
+        assert!(!second.contains(Digit::D1));
+        assert_eq!(first.len(), 8);
+        assert_eq!(second.len(), 8);
+
+        let dot = EdgeConstraint::new(
+            Position::new(0, 0),
+            Position::new(0, 1),
+            EdgeKind::Consecutive,
+        )
+        .unwrap();
+        let (first, second) = dot.supported_digits(DigitSet::from_elem(Digit::D5), DigitSet::FULL);
+        assert_eq!(first, DigitSet::from_elem(Digit::D5));
+        assert_eq!(second, DigitSet::from_iter([Digit::D4, Digit::D6]));
+    }
+
+    #[test]
+    fn test_check() {
+        let grid = |s: &str| DigitGrid::from_str(&format!("{s:.<81}")).unwrap();
+        let set = EdgeConstraintSet::from_constraints([
+            EdgeConstraint::new(
+                Position::new(0, 0),
+                Position::new(0, 1),
+                EdgeKind::GreaterThan,
+            )
+            .unwrap(),
+            EdgeConstraint::new(
+                Position::new(0, 1),
+                Position::new(0, 2),
+                EdgeKind::Consecutive,
+            )
+            .unwrap(),
+        ])
+        .unwrap();
+
+        assert_eq!(set.check(&grid("5")), Ok(()));
+        assert_eq!(set.check(&grid("534")), Ok(()));
+        assert!(set.check(&grid("35")).is_err());
+        assert!(set.check(&grid("536")).is_err());
+        assert_eq!(set.touching(Position::new(0, 1)).count(), 2);
+    }
+
+    #[test]
+    fn test_set_rejects_duplicated_edge() {
+        let a = EdgeConstraint::new(Position::new(0, 0), Position::new(0, 1), EdgeKind::LessThan)
+            .unwrap();
+        let b = EdgeConstraint::new(
+            Position::new(0, 1),
+            Position::new(0, 0),
+            EdgeKind::Consecutive,
+        )
+        .unwrap();
+        assert_eq!(
+            EdgeConstraintSet::from_constraints([a, b]),
+            Err(EdgeConstraintError::Duplicated {
+                first: Position::new(0, 0),
+                second: Position::new(0, 1),
+            })
+        );
+    }
+}
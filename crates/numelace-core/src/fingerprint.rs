@@ -0,0 +1,136 @@
+//! Content-addressed puzzle fingerprints.
+
+use std::{fmt, str::FromStr};
+
+use crate::DigitGrid;
+
+/// A hash of a puzzle's canonical form.
+///
+/// Equivalent puzzles, which turn into one another by transposing, reordering
+/// lines or relabelling digits (see [`DigitGrid::canonical_form`]), share a
+/// fingerprint, so it identifies a puzzle however it was presented. The hash
+/// is 64-bit FNV-1a, which stays stable across platforms and releases, unlike
+/// the standard library's hasher, so fingerprints can be persisted.
+///
+/// Fingerprints format as 16 lowercase hexadecimal digits and parse back from
+/// that form.
+///
+/// # Examples
+///
+/// ```
+/// use numelace_core::{DigitGrid, PuzzleFingerprint};
+///
+/// let grid: DigitGrid = format!("9{}", ".".repeat(80)).parse().unwrap();
+/// let moved: DigitGrid = format!("{}4", ".".repeat(80)).parse().unwrap();
+/// assert_eq!(grid.fingerprint(), moved.fingerprint());
+///
+/// let text = grid.fingerprint().to_string();
+/// assert_eq!(text.parse::<PuzzleFingerprint>().unwrap(), grid.fingerprint());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PuzzleFingerprint(u64);
+
+impl PuzzleFingerprint {
+    /// Returns the hash value.
+    #[must_use]
+    pub const fn value(self) -> u64 {
+        self.0
+    }
+}
+
+impl From<u64> for PuzzleFingerprint {
+    fn from(value: u64) -> Self {
+        Self(value)
+    }
+}
+
+impl fmt::Display for PuzzleFingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:016x}", self.0)
+    }
+}
+
+/// Errors that can occur when parsing a [`PuzzleFingerprint`] from a string.
+#[derive(Debug, derive_more::Display, derive_more::Error)]
+pub enum PuzzleFingerprintParseError {
+    /// The input is not exactly 16 characters long.
+    #[display("invalid fingerprint length: expected 16, got {_0}")]
+    InvalidLength(#[error(not(source))] usize),
+    /// The input contains a character that is not a hexadecimal digit.
+    #[display("invalid fingerprint character '{_0}'")]
+    InvalidCharacter(#[error(not(source))] char),
+}
+
+impl FromStr for PuzzleFingerprint {
+    type Err = PuzzleFingerprintParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let len = s.chars().count();
+        if len != 16 {
+            return Err(PuzzleFingerprintParseError::InvalidLength(len));
+        }
+        s.chars()
+            .try_fold(0_u64, |value, ch| {
+                let digit = ch
+                    .to_digit(16)
+                    .ok_or(PuzzleFingerprintParseError::InvalidCharacter(ch))?;
+                Ok((value << 4) | u64::from(digit))
+            })
+            .map(Self)
+    }
+}
+
+impl DigitGrid {
+    /// Returns the fingerprint of the puzzle with this grid as its givens.
+    ///
+    /// This computes the [canonical form](Self::canonical_form), so it costs
+    /// as much as that search.
+    #[must_use]
+    pub fn fingerprint(&self) -> PuzzleFingerprint {
+        let hash = self
+            .canonical_form()
+            .iter()
+            .fold(0xcbf2_9ce4_8422_2325_u64, |hash, digit| {
+                (hash ^ u64::from(digit.map_or(0, |digit| digit.value())))
+                    .wrapping_mul(0x0000_0100_0000_01b3)
+            });
+        PuzzleFingerprint(hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fingerprint_round_trips_through_text() {
+        let fingerprint = PuzzleFingerprint::from(0x0123_4567_89ab_cdef);
+        assert_eq!(fingerprint.to_string(), "0123456789abcdef");
+        assert_eq!(
+            "0123456789ABCDEF".parse::<PuzzleFingerprint>().unwrap(),
+            fingerprint
+        );
+    }
+
+    #[test]
+    fn test_invalid_fingerprints_are_rejected() {
+        assert!(matches!(
+            "0123".parse::<PuzzleFingerprint>(),
+            Err(PuzzleFingerprintParseError::InvalidLength(4))
+        ));
+        assert!(matches!(
+            "0123456789abcdeg".parse::<PuzzleFingerprint>(),
+            Err(PuzzleFingerprintParseError::InvalidCharacter('g'))
+        ));
+    }
+
+    #[test]
+    fn test_empty_grid_fingerprint_is_stable() {
+        // 81 zero bytes through FNV-1a; saved fingerprints depend on this value.
+        let mut expected = 0xcbf2_9ce4_8422_2325_u64;
+        for _ in 0..81 {
+            expected = expected.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+        assert_eq!(DigitGrid::new().fingerprint().value(), expected);
+    }
+}
@@ -1,11 +1,14 @@
-use std::iter::FusedIterator;
+use std::{fmt, iter::FusedIterator};
 
 use crate::{
-    CellIndexIndexedArray, Digit, DigitPositions, Position,
+    CellIndexIndexedArray, Digit, DigitPositions, Position, RegionLayout,
     index::{DigitSemantics, Index9, Index9Semantics as _},
 };
 
-/// A Sudoku house (row, column, or 3×3 box).
+/// A Sudoku house (row, column, or box).
+///
+/// Boxes are the regions of a [`RegionLayout`]: the 3×3 boxes of the standard
+/// layout, or the irregular regions of a jigsaw puzzle.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum House {
     /// A row identified by its y coordinate (0-8).
@@ -18,13 +21,24 @@ pub enum House {
         /// Column index (0-8).
         col: u8,
     },
-    /// A 3×3 box identified by its index (0-8, left to right, top to bottom).
+    /// A box identified by its region index (0-8; for the standard layout,
+    /// left to right, top to bottom).
     Box {
         /// Box index (0-8).
         index: u8,
     },
 }
 
+impl fmt::Display for House {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            House::Row { row } => write!(f, "row {}", row + 1),
+            House::Column { col } => write!(f, "column {}", col + 1),
+            House::Box { index } => write!(f, "box {}", index + 1),
+        }
+    }
+}
+
 impl House {
     /// Array containing all rows (0-8).
     pub const ROWS: CellIndexIndexedArray<Self> = CellIndexIndexedArray::from_array([
@@ -79,29 +93,32 @@ impl House {
         all
     };
 
-    /// Converts a cell index within the house (0-8) into an absolute [`Position`].
+    /// Converts a cell index within the house (0-8) into an absolute [`Position`],
+    /// taking boxes from `regions`.
     ///
     /// # Panics
     ///
     /// Panics if `i` is not in the range 0-8.
     #[must_use]
     #[inline]
-    pub fn position_from_cell_index(self, i: u8) -> Position {
+    pub fn position_from_cell_index(self, regions: &RegionLayout, i: u8) -> Position {
         assert!(i < 9);
         match self {
             House::Row { row } => Position::new(row, i),
             House::Column { col } => Position::new(i, col),
-            House::Box { index } => Position::from_box(index, i),
+            House::Box { index } => Position::from_box(regions, index, i),
         }
     }
 
-    /// Returns all positions contained in this house.
+    /// Returns all positions contained in this house, taking boxes from
+    /// `regions`.
     #[must_use]
-    pub fn positions(self) -> DigitPositions {
+    #[inline]
+    pub fn positions(self, regions: &RegionLayout) -> DigitPositions {
         match self {
             House::Row { row } => DigitPositions::ROW_POSITIONS[row],
             House::Column { col } => DigitPositions::COL_POSITIONS[col],
-            House::Box { index } => DigitPositions::BOX_POSITIONS[index],
+            House::Box { index } => regions.region_positions(index),
         }
     }
 }
@@ -54,8 +54,21 @@ impl Index81 {
     /// assert_eq!(indices[80].index(), 80);
     /// ```
     pub fn all() -> impl Iterator<Item = Self> {
-        (0..81).map(Index81::new)
+        Self::ALL.into_iter()
     }
+
+    /// All 81 valid bit indices in ascending order.
+    ///
+    /// Unlike [`all`](Self::all), this can be used in const contexts.
+    pub const ALL: [Self; 81] = {
+        let mut indices = [Self { index: 0 }; 81];
+        let mut i = 0;
+        while i < 81 {
+            indices[i as usize] = Self::new(i);
+            i += 1;
+        }
+        indices
+    };
 }
 
 /// Defines the semantics for mapping values to indices in 81-element containers.
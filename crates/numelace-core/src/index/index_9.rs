@@ -55,8 +55,21 @@ impl Index9 {
     /// assert_eq!(indices[8].index(), 8);
     /// ```
     pub fn all() -> impl Iterator<Item = Self> {
-        (0..9).map(Index9::new)
+        Self::ALL.into_iter()
     }
+
+    /// All 9 valid bit indices in ascending order.
+    ///
+    /// Unlike [`all`](Self::all), this can be used in const contexts.
+    pub const ALL: [Self; 9] = {
+        let mut indices = [Self { index: 0 }; 9];
+        let mut i = 0;
+        while i < 9 {
+            indices[i as usize] = Self::new(i);
+            i += 1;
+        }
+        indices
+    };
 }
 
 /// Defines the semantics for mapping values to indices in 9-element containers.
@@ -10,6 +10,10 @@
 //!
 //! - **Basic Types**: [`Digit`] (1-9), [`Position`] (grid coordinates)
 //! - **Grid Types**: [`CandidateGrid`] (digit-centric), [`DigitGrid`] (cell-centric)
+//! - **Board Dimensions**: [`BoardDims`] (6×6, 9×9, 16×16 size descriptors)
+//! - **Variant Constraints**: [`Cage`], [`CageSet`] (killer cages), [`RegionLayout`] (jigsaw regions),
+//!   [`EdgeConstraintSet`] (greater-than / consecutive markers), [`ChessRules`]
+//!   (anti-knight / anti-king), all implementing [`Constraint`]
 //! - **Type Aliases**: [`DigitSet`], [`DigitPositions`], [`HouseMask`]
 //! - **Generic Infrastructure**: [`BitSet9`], [`BitSet81`], [`Array9`], [`Array81`]
 //!
@@ -221,15 +225,26 @@
 
 //! Each type provides the most natural interface for its access pattern.
 
+mod board;
+mod cage;
 mod candidate_grid;
+mod canonical;
+mod chess;
+mod constraint;
 pub mod containers;
 mod digit;
 mod digit_grid;
+mod edge;
+mod fingerprint;
 mod house;
 pub mod index;
+mod pencilmark;
 mod position;
+mod region;
 
 // Re-export commonly used types
 pub use self::{
-    candidate_grid::*, containers::aliases::*, digit::*, digit_grid::*, house::*, position::*,
+    board::*, cage::*, candidate_grid::*, chess::*, constraint::*, containers::aliases::*,
+    digit::*, digit_grid::*, edge::*, fingerprint::*, house::*, pencilmark::*, position::*,
+    region::*,
 };
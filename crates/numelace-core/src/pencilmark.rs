@@ -0,0 +1,221 @@
+//! Pencilmark text formats for candidate grids.
+//!
+//! Two formats are understood:
+//!
+//! - The 729-character format: 9 characters per cell in row-major order, where
+//!   the `k`-th character of a cell is the digit `k` if it is a candidate and
+//!   `.` or `0` otherwise.
+//! - The grid notation posted on forums: the candidates of each cell written as
+//!   one run of digits, with runs separated by whitespace and the box frame
+//!   drawn with `.`, `-`, `|`, `:`, `+` and `'` characters.
+
+use crate::{CandidateGrid, Digit, DigitSet, Position};
+
+/// Errors that can occur when parsing a [`CandidateGrid`] from pencilmarks.
+#[derive(Debug, derive_more::Display, derive_more::Error)]
+pub enum PencilmarkParseError {
+    /// The input contains a character that is neither a digit nor part of the format.
+    #[display("invalid character '{_0}'")]
+    InvalidCharacter(#[error(not(source))] char),
+    /// A 729-character input has a digit in the slot of another digit.
+    #[display("digit '{ch}' out of place in the candidates of {pos}")]
+    MisplacedDigit {
+        /// The cell whose candidates contain the digit.
+        #[error(not(source))]
+        pos: Position,
+        /// The misplaced character.
+        ch: char,
+    },
+    /// The input does not describe exactly 81 cells.
+    #[display("invalid cell count: expected 81, got {_0}")]
+    InvalidCellCount(#[error(not(source))] usize),
+}
+
+/// Characters drawing the box frame in the grid notation.
+const FRAME_CHARS: &[char] = &['.', '-', '|', ':', '+', '\''];
+
+impl CandidateGrid {
+    /// Parses a candidate grid from pencilmarks.
+    ///
+    /// Accepts both the 729-character format and the grid notation, see the
+    /// [module documentation](self). Whitespace is ignored in the 729-character
+    /// format, so it may be split into lines.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PencilmarkParseError`] if the input is in neither format.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use numelace_core::{CandidateGrid, Digit, DigitSet, Position};
+    ///
+    /// let text = "
+    /// .----------------.-----------------.----------------.
+    /// | 12  3    4     | 5   6    7      | 8   9    12    |
+    /// | 4   56   789   | 1   2    3      | 56  789  456   |
+    /// | 7   8    9     | 4   5    6      | 1   2    3     |
+    /// :----------------+-----------------+----------------:
+    /// | 3   1    2     | 6   4    5      | 9   7    8     |
+    /// | 6   4    5     | 9   7    8      | 3   1    2     |
+    /// | 9   7    8     | 3   1    2      | 6   4    5     |
+    /// :----------------+-----------------+----------------:
+    /// | 5   3    1     | 2   6    4      | 7   8    9     |
+    /// | 2   6    4     | 8   9    7      | 5   3    1     |
+    /// | 8   9    7     | 1   3    5      | 2   6    4     |
+    /// '----------------'-----------------'----------------'
+    /// ";
+    /// let grid = CandidateGrid::from_pencilmark_str(text).unwrap();
+    /// assert_eq!(
+    ///     grid.candidates_at(Position::new(0, 0)),
+    ///     DigitSet::from_iter([Digit::D1, Digit::D2])
+    /// );
+    ///
+    /// let compact = grid.to_pencilmark_string();
+    /// assert_eq!(compact.len(), 729);
+    /// assert_eq!(CandidateGrid::from_pencilmark_str(&compact).unwrap(), grid);
+    /// ```
+    pub fn from_pencilmark_str(s: &str) -> Result<Self, PencilmarkParseError> {
+        let compact: Vec<char> = s.chars().filter(|c| !c.is_whitespace()).collect();
+        if compact.len() == 729 && compact.iter().all(|&c| c == '.' || c.is_ascii_digit()) {
+            parse_fixed_width(&compact)
+        } else {
+            parse_grid_notation(s)
+        }
+    }
+
+    /// Formats the candidates in the 729-character format.
+    ///
+    /// Missing candidates are written as `.`.
+    #[must_use]
+    pub fn to_pencilmark_string(&self) -> String {
+        let mut out = String::with_capacity(729);
+        for pos in Position::ALL {
+            let candidates = self.candidates_at(pos);
+            for digit in Digit::ALL {
+                out.push(if candidates.contains(digit) {
+                    digit_char(digit)
+                } else {
+                    '.'
+                });
+            }
+        }
+        out
+    }
+}
+
+fn parse_fixed_width(chars: &[char]) -> Result<CandidateGrid, PencilmarkParseError> {
+    let mut grid = CandidateGrid::new();
+    for (pos, cell) in Position::ALL.into_iter().zip(chars.as_chunks::<9>().0) {
+        let mut candidates = DigitSet::EMPTY;
+        for (digit, &ch) in Digit::ALL.into_iter().zip(cell) {
+            match ch {
+                '.' | '0' => {}
+                _ if ch == digit_char(digit) => {
+                    candidates.insert(digit);
+                }
+                _ => return Err(PencilmarkParseError::MisplacedDigit { pos, ch }),
+            }
+        }
+        grid.set_candidate_at(pos, candidates);
+    }
+    Ok(grid)
+}
+
+fn parse_grid_notation(s: &str) -> Result<CandidateGrid, PencilmarkParseError> {
+    let mut cells = Vec::with_capacity(81);
+    for token in s.split(|c: char| c.is_whitespace() || FRAME_CHARS.contains(&c)) {
+        if token.is_empty() {
+            continue;
+        }
+        let mut candidates = DigitSet::EMPTY;
+        for ch in token.chars() {
+            let digit = ch
+                .to_digit(10)
+                .and_then(|value| u8::try_from(value).ok())
+                .filter(|&value| value != 0)
+                .ok_or(PencilmarkParseError::InvalidCharacter(ch))?;
+            candidates.insert(Digit::from_value(digit));
+        }
+        cells.push(candidates);
+    }
+    if cells.len() != 81 {
+        return Err(PencilmarkParseError::InvalidCellCount(cells.len()));
+    }
+
+    let mut grid = CandidateGrid::new();
+    for (pos, candidates) in Position::ALL.into_iter().zip(cells) {
+        grid.set_candidate_at(pos, candidates);
+    }
+    Ok(grid)
+}
+
+fn digit_char(digit: Digit) -> char {
+    char::from(b'0' + digit.value())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixed_width_round_trip() {
+        let mut grid = CandidateGrid::new();
+        grid.place(Position::new(0, 0), Digit::D5);
+        grid.remove_candidate(Position::new(4, 4), Digit::D9);
+        grid.set_candidate_at(Position::new(8, 8), DigitSet::EMPTY);
+
+        let text = grid.to_pencilmark_string();
+        assert_eq!(&text[..9], "....5....");
+        assert_eq!(&text[720..], ".........");
+        assert_eq!(CandidateGrid::from_pencilmark_str(&text).unwrap(), grid);
+
+        // `0` also marks a missing candidate, and lines may be wrapped.
+        let zeros = text.replace('.', "0");
+        let wrapped = zeros
+            .as_bytes()
+            .chunks(81)
+            .map(|line| std::str::from_utf8(line).unwrap())
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert_eq!(CandidateGrid::from_pencilmark_str(&wrapped).unwrap(), grid);
+    }
+
+    #[test]
+    fn test_grid_notation_without_frame() {
+        let cells: Vec<_> = (0..81).map(|i| (i % 9 + 1).to_string()).collect();
+        let mut text = cells.join(" ");
+        text.replace_range(0..1, "159");
+
+        let grid = CandidateGrid::from_pencilmark_str(&text).unwrap();
+        assert_eq!(
+            grid.candidates_at(Position::new(0, 0)),
+            DigitSet::from_iter([Digit::D1, Digit::D5, Digit::D9])
+        );
+        assert_eq!(
+            grid.candidates_at(Position::new(8, 8)),
+            DigitSet::from_elem(Digit::D9)
+        );
+    }
+
+    #[test]
+    fn test_rejects_malformed_input() {
+        let misplaced = format!("2{}", ".".repeat(728));
+        assert!(matches!(
+            CandidateGrid::from_pencilmark_str(&misplaced),
+            Err(PencilmarkParseError::MisplacedDigit { ch: '2', .. })
+        ));
+        assert!(matches!(
+            CandidateGrid::from_pencilmark_str("12 34 x"),
+            Err(PencilmarkParseError::InvalidCharacter('x'))
+        ));
+        assert!(matches!(
+            CandidateGrid::from_pencilmark_str("12 34 56"),
+            Err(PencilmarkParseError::InvalidCellCount(3))
+        ));
+        assert!(matches!(
+            CandidateGrid::from_pencilmark_str(&["10"; 81].join(" ")),
+            Err(PencilmarkParseError::InvalidCharacter('0'))
+        ));
+    }
+}
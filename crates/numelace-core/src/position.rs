@@ -2,7 +2,7 @@
 
 use std::fmt;
 
-use crate::{CellIndexIndexedArray, DigitPositions};
+use crate::{CellIndexIndexedArray, DigitPositions, RegionLayout};
 
 /// Board position (row, col) on the grid.
 ///
@@ -133,10 +133,12 @@ impl Position {
         CellIndexIndexedArray::from_array(columns)
     };
 
-    /// All positions in each 3×3 box, indexed by box number (0-8).
+    /// All positions in each 3×3 box of the standard layout, indexed by box
+    /// number (0-8).
     ///
     /// `BOXES[box_index]` contains all 9 positions in that box, ordered by
-    /// cell index within the box (same order as `Position::from_box`).
+    /// cell index within the box (same order as `Position::from_box` with
+    /// [`RegionLayout::standard`]).
     ///
     /// Box indices are arranged as:
     /// ```text
@@ -148,10 +150,11 @@ impl Position {
     /// # Example
     ///
     /// ```
-    /// # use numelace_core::Position;
+    /// # use numelace_core::{Position, RegionLayout};
     /// // Process all positions in box 4 (center box)
+    /// let regions = RegionLayout::standard();
     /// for pos in Position::BOXES[4] {
-    ///     assert_eq!(pos.box_index(), 4);
+    ///     assert_eq!(pos.box_index(&regions), 4);
     /// }
     /// ```
     pub const BOXES: CellIndexIndexedArray<[Position; 9]> = {
@@ -160,8 +163,9 @@ impl Position {
         while box_index < 9 {
             let mut cell_index = 0;
             while cell_index < 9 {
+                let origin = Position::box_origin(box_index);
                 boxes[box_index as usize][cell_index as usize] =
-                    Position::from_box(box_index, cell_index);
+                    Position::new(origin.row() + cell_index / 3, origin.col() + cell_index % 3);
                 cell_index += 1;
             }
             box_index += 1;
@@ -305,17 +309,27 @@ impl Position {
         }
     }
 
-    /// Creates a position from box index and cell index within that box.
+    /// Creates a position from a box (region) index of `regions` and the cell
+    /// index within that box.
+    ///
+    /// Cells of a box are numbered 0-8 in row-major order.
     ///
     /// # Panics
     ///
     /// Panics if `box_index` or `cell_index` is greater than or equal to 9.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use numelace_core::{Position, RegionLayout};
+    ///
+    /// let regions = RegionLayout::standard();
+    /// assert_eq!(Position::from_box(&regions, 4, 0), Position::new(3, 3));
+    /// ```
     #[must_use]
     #[inline]
-    pub const fn from_box(box_index: u8, cell_index: u8) -> Self {
-        assert!(box_index < 9 && cell_index < 9);
-        let origin = Self::box_origin(box_index);
-        Self::new(origin.row() + cell_index / 3, origin.col() + cell_index % 3)
+    pub fn from_box(regions: &RegionLayout, box_index: u8, cell_index: u8) -> Self {
+        regions.region_cell(box_index, cell_index)
     }
 
     /// Returns the index (0-80) of this position, where index = row * 9 + col.
@@ -339,21 +353,24 @@ impl Position {
         self.index % 9
     }
 
-    /// Returns the box index (0-8) that this position belongs to.
+    /// Returns the index (0-8) of the box (region) of `regions` that this
+    /// position belongs to.
     #[must_use]
     #[inline]
-    pub const fn box_index(&self) -> u8 {
-        (self.row() / 3) * 3 + (self.col() / 3)
+    pub fn box_index(self, regions: &RegionLayout) -> u8 {
+        regions.region_index(self)
     }
 
-    /// Returns the relative position (0-8) within the box.
+    /// Returns the relative position (0-8) within the box (region) of
+    /// `regions`, counting the box's cells in row-major order.
     #[must_use]
     #[inline]
-    pub const fn box_cell_index(&self) -> u8 {
-        (self.row() % 3) * 3 + (self.col() % 3)
+    pub fn box_cell_index(self, regions: &RegionLayout) -> u8 {
+        regions.cell_index_in_region(self)
     }
 
-    /// Returns the top-left position (origin) of the specified box.
+    /// Returns the top-left position (origin) of the specified 3×3 box of the
+    /// standard layout.
     ///
     /// # Panics
     ///
@@ -365,50 +382,48 @@ impl Position {
         Self::new((box_index / 3) * 3, (box_index % 3) * 3)
     }
 
-    /// Returns the union of row, column, and box positions for this cell.
+    /// Returns the union of row, column, and box positions for this cell,
+    /// with boxes taken from `regions`.
     ///
     /// The returned set includes this position itself.
     ///
     /// # Examples
     ///
     /// ```
-    /// use numelace_core::Position;
+    /// use numelace_core::{Position, RegionLayout};
     ///
     /// let pos = Position::new(4, 4);
-    /// let house = pos.house_positions();
+    /// let house = pos.house_positions(&RegionLayout::standard());
     /// assert!(house.contains(pos));
     /// assert!(house.contains(Position::new(0, 4)));
     /// assert!(house.contains(Position::new(4, 0)));
     /// ```
     #[inline]
     #[must_use]
-    pub fn house_positions(self) -> DigitPositions {
-        DigitPositions::ROW_POSITIONS[self.row()]
-            | DigitPositions::COL_POSITIONS[self.col()]
-            | DigitPositions::BOX_POSITIONS[self.box_index()]
+    pub fn house_positions(self, regions: &RegionLayout) -> DigitPositions {
+        regions.house_positions(self)
     }
 
-    /// Returns the positions that share a row, column, or box with this cell.
+    /// Returns the positions that share a row, column, or box with this cell,
+    /// with boxes taken from `regions`.
     ///
     /// The returned set excludes this position itself.
     ///
     /// # Examples
     ///
     /// ```
-    /// use numelace_core::Position;
+    /// use numelace_core::{Position, RegionLayout};
     ///
     /// let pos = Position::new(4, 4);
-    /// let peers = pos.house_peers();
+    /// let peers = pos.house_peers(&RegionLayout::standard());
     /// assert!(!peers.contains(pos));
     /// assert!(peers.contains(Position::new(0, 4)));
     /// assert!(peers.contains(Position::new(4, 0)));
     /// ```
     #[inline]
     #[must_use]
-    pub fn house_peers(self) -> DigitPositions {
-        let mut set = self.house_positions();
-        set.remove(self);
-        set
+    pub fn house_peers(self, regions: &RegionLayout) -> DigitPositions {
+        regions.house_peers(self)
     }
 }
 
@@ -426,13 +441,14 @@ mod tests {
         assert_eq!(pos.left(), Some(Position::new(5, 2)));
         assert_eq!(pos.right(), Some(Position::new(5, 4)));
 
-        assert_eq!(Position::new(0, 0).box_index(), 0);
-        assert_eq!(Position::new(4, 4).box_index(), 4);
-        assert_eq!(Position::new(8, 8).box_index(), 8);
+        let regions = RegionLayout::standard();
+        assert_eq!(Position::new(0, 0).box_index(&regions), 0);
+        assert_eq!(Position::new(4, 4).box_index(&regions), 4);
+        assert_eq!(Position::new(8, 8).box_index(&regions), 8);
 
-        assert_eq!(Position::new(0, 0).box_cell_index(), 0);
-        assert_eq!(Position::new(4, 4).box_cell_index(), 4);
-        assert_eq!(Position::new(8, 8).box_cell_index(), 8);
+        assert_eq!(Position::new(0, 0).box_cell_index(&regions), 0);
+        assert_eq!(Position::new(4, 4).box_cell_index(&regions), 4);
+        assert_eq!(Position::new(8, 8).box_cell_index(&regions), 8);
     }
 
     #[test]
@@ -491,10 +507,11 @@ mod tests {
 
     #[test]
     fn test_from_box() {
-        assert_eq!(Position::from_box(0, 0), Position::new(0, 0));
-        assert_eq!(Position::from_box(0, 8), Position::new(2, 2));
-        assert_eq!(Position::from_box(4, 4), Position::new(4, 4));
-        assert_eq!(Position::from_box(8, 8), Position::new(8, 8));
+        let regions = RegionLayout::standard();
+        assert_eq!(Position::from_box(&regions, 0, 0), Position::new(0, 0));
+        assert_eq!(Position::from_box(&regions, 0, 8), Position::new(2, 2));
+        assert_eq!(Position::from_box(&regions, 4, 4), Position::new(4, 4));
+        assert_eq!(Position::from_box(&regions, 8, 8), Position::new(8, 8));
 
         assert_eq!(Position::box_origin(0), Position::new(0, 0));
         assert_eq!(Position::box_origin(4), Position::new(3, 3));
@@ -504,13 +521,13 @@ mod tests {
     #[test]
     #[should_panic(expected = "assertion failed")]
     fn test_from_box_invalid_box_index() {
-        let _ = Position::from_box(9, 0);
+        let _ = Position::from_box(&RegionLayout::standard(), 9, 0);
     }
 
     #[test]
     #[should_panic(expected = "assertion failed")]
     fn test_from_box_invalid_cell_index() {
-        let _ = Position::from_box(0, 9);
+        let _ = Position::from_box(&RegionLayout::standard(), 0, 9);
     }
 
     #[test]
@@ -521,12 +538,26 @@ mod tests {
 
     #[test]
     fn test_from_box_roundtrip() {
-        // from_box and box_index/box_cell_index are inverses
-        for box_index in 0..9 {
-            for cell_index in 0..9 {
-                let pos = Position::from_box(box_index, cell_index);
-                assert_eq!(pos.box_index(), box_index);
-                assert_eq!(pos.box_cell_index(), cell_index);
+        // from_box and box_index/box_cell_index are inverses, for any layout
+        let jigsaw: RegionLayout = "\
+            111122333\
+            111222333\
+            112222333\
+            444555666\
+            444555666\
+            444555666\
+            777888999\
+            777888999\
+            777888999"
+            .parse()
+            .unwrap();
+        for regions in [RegionLayout::standard(), jigsaw] {
+            for box_index in 0..9 {
+                for cell_index in 0..9 {
+                    let pos = Position::from_box(&regions, box_index, cell_index);
+                    assert_eq!(pos.box_index(&regions), box_index);
+                    assert_eq!(pos.box_cell_index(&regions), cell_index);
+                }
             }
         }
     }
@@ -534,7 +565,7 @@ mod tests {
     #[test]
     fn test_house_positions() {
         let pos = Position::new(4, 4);
-        let house = pos.house_positions();
+        let house = pos.house_positions(&RegionLayout::standard());
 
         assert!(house.contains(pos));
         assert_eq!(house.len(), 21);
@@ -547,7 +578,7 @@ mod tests {
     #[test]
     fn test_house_peers() {
         let pos = Position::new(4, 4);
-        let peers = pos.house_peers();
+        let peers = pos.house_peers(&RegionLayout::standard());
 
         assert!(!peers.contains(pos));
         assert_eq!(peers.len(), 20);
@@ -608,7 +639,10 @@ mod tests {
                 9
             );
             for (cell_index, &pos) in (0u8..).zip(Position::BOXES[box_index].iter()) {
-                assert_eq!(pos, Position::from_box(box_index, cell_index));
+                assert_eq!(
+                    pos,
+                    Position::from_box(&RegionLayout::standard(), box_index, cell_index)
+                );
             }
         }
 
@@ -616,4 +650,14 @@ mod tests {
         assert_eq!(Position::ALL.len(), 81);
         assert_eq!(Position::ALL.iter().collect::<HashSet<_>>().len(), 81);
     }
+
+    #[test]
+    fn test_const_position_set() {
+        for pos in Position::ALL {
+            assert_eq!(
+                DigitPositions::from_position(pos),
+                DigitPositions::from_elem(pos)
+            );
+        }
+    }
 }
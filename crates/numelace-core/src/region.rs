@@ -0,0 +1,375 @@
+use std::{fmt, str::FromStr};
+
+use crate::{
+    CellIndexIndexedArray, DigitGrid, DigitPositions, DigitSet, Position, PositionIndexedArray,
+};
+
+/// Partition of the grid into the nine regions that play the role of boxes.
+///
+/// The standard layout uses the 3×3 boxes, while jigsaw (irregular) puzzles
+/// use arbitrary orthogonally connected regions of nine cells. Row and column
+/// rules are unaffected by the layout.
+///
+/// Every box-based API ([`Position::box_index`], [`House::Box`](crate::House::Box),
+/// [`CandidateGrid`](crate::CandidateGrid), ...) takes its boxes from a
+/// `RegionLayout`, so rules, solvers, and generators share one notion of peers.
+/// [`DigitPositions::BOX_POSITIONS`] and [`Position::BOXES`] only describe the
+/// standard layout.
+///
+/// # Examples
+///
+/// ```
+/// use numelace_core::{Position, RegionLayout};
+///
+/// let layout = RegionLayout::standard();
+/// assert!(layout.is_standard());
+/// assert_eq!(layout.region_index(Position::new(4, 4)), 4);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RegionLayout {
+    regions: CellIndexIndexedArray<DigitPositions>,
+    /// Region index of each cell, kept alongside `regions` for fast lookups.
+    region_indices: PositionIndexedArray<u8>,
+}
+
+/// Errors that can occur when constructing a [`RegionLayout`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, derive_more::Display, derive_more::Error)]
+pub enum RegionLayoutError {
+    /// A region does not contain exactly nine cells.
+    #[display("region {index} has {len} cells, expected 9")]
+    InvalidRegionSize {
+        /// Region index (0-8).
+        index: u8,
+        /// Number of cells in the region.
+        len: usize,
+    },
+    /// A region is not orthogonally connected.
+    #[display("region {_0} is not connected")]
+    Disconnected(#[error(not(source))] u8),
+    /// The input contains a character that is not a region number (1-9).
+    #[display("invalid region character '{_0}'")]
+    InvalidCharacter(#[error(not(source))] char),
+    /// The input does not contain exactly 81 non-whitespace characters.
+    #[display("invalid layout length: expected 81, got {_0}")]
+    InvalidLength(#[error(not(source))] usize),
+}
+
+impl Default for RegionLayout {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+impl RegionLayout {
+    /// Returns the standard layout made of 3×3 boxes.
+    #[must_use]
+    pub const fn standard() -> Self {
+        let mut region_indices = [0; 81];
+        let mut i = 0;
+        while i < 81 {
+            let pos = Position::from_index(i);
+            region_indices[i as usize] = (pos.row() / 3) * 3 + pos.col() / 3;
+            i += 1;
+        }
+        Self {
+            regions: DigitPositions::BOX_POSITIONS,
+            region_indices: PositionIndexedArray::from_array(region_indices),
+        }
+    }
+
+    /// Creates a layout from nine regions.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RegionLayoutError`] if a region does not have nine cells or
+    /// is not orthogonally connected. Regions of nine cells each that are
+    /// pairwise disjoint necessarily cover the whole grid, so overlaps are
+    /// reported as size errors of the region that ends up short.
+    pub fn from_regions(regions: [DigitPositions; 9]) -> Result<Self, RegionLayoutError> {
+        let mut covered = DigitPositions::EMPTY;
+        let mut region_indices = PositionIndexedArray::from_array([0; 81]);
+        for (index, region) in (0..).zip(regions) {
+            let len = (region & !covered).len();
+            if len != 9 {
+                return Err(RegionLayoutError::InvalidRegionSize { index, len });
+            }
+            if !is_connected(region) {
+                return Err(RegionLayoutError::Disconnected(index));
+            }
+            covered |= region;
+            for pos in region {
+                region_indices[pos] = index;
+            }
+        }
+        Ok(Self {
+            regions: CellIndexIndexedArray::from_array(regions),
+            region_indices,
+        })
+    }
+
+    /// Returns `true` if this is the standard 3×3 box layout.
+    #[must_use]
+    pub fn is_standard(&self) -> bool {
+        *self == Self::standard()
+    }
+
+    /// Returns the cells of the region with the given index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than or equal to 9.
+    #[must_use]
+    pub fn region_positions(&self, index: u8) -> DigitPositions {
+        self.regions[index]
+    }
+
+    /// Returns the index (0-8) of the region containing `pos`.
+    #[must_use]
+    #[inline]
+    pub fn region_index(&self, pos: Position) -> u8 {
+        self.region_indices[pos]
+    }
+
+    /// Returns the cell with the given index (0-8) within a region, counting
+    /// the region's cells in row-major order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` or `cell_index` is greater than or equal to 9.
+    #[must_use]
+    pub fn region_cell(&self, index: u8, cell_index: u8) -> Position {
+        assert!(index < 9 && cell_index < 9);
+        self.regions[index]
+            .iter()
+            .nth(usize::from(cell_index))
+            .expect("every region has nine cells")
+    }
+
+    /// Returns the index (0-8) of `pos` within its region, counting the
+    /// region's cells in row-major order.
+    #[must_use]
+    pub fn cell_index_in_region(&self, pos: Position) -> u8 {
+        let region = self.regions[self.region_index(pos)];
+        let mut before = 0;
+        for cell in region {
+            if cell == pos {
+                break;
+            }
+            before += 1;
+        }
+        before
+    }
+
+    /// Returns all cells sharing a row, column, or region with `pos`, excluding `pos`.
+    #[must_use]
+    #[inline]
+    pub fn house_peers(&self, pos: Position) -> DigitPositions {
+        let mut peers = self.house_positions(pos);
+        peers.remove(pos);
+        peers
+    }
+
+    /// Returns all cells sharing a row, column, or region with `pos`, including `pos`.
+    #[must_use]
+    #[inline]
+    pub fn house_positions(&self, pos: Position) -> DigitPositions {
+        DigitPositions::ROW_POSITIONS[pos.row()]
+            | DigitPositions::COL_POSITIONS[pos.col()]
+            | self.regions[self.region_index(pos)]
+    }
+
+    /// Returns the cells of `positions` that share a row, column, or region
+    /// with another cell of `positions`.
+    ///
+    /// Passing the cells holding one digit yields the cells where that digit
+    /// is repeated within a house.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use numelace_core::{DigitPositions, Position, RegionLayout};
+    ///
+    /// let layout = RegionLayout::standard();
+    /// let mut positions = DigitPositions::EMPTY;
+    /// positions.insert(Position::new(0, 0));
+    /// positions.insert(Position::new(0, 8));
+    /// positions.insert(Position::new(4, 4));
+    ///
+    /// let duplicated = layout.duplicated_positions(positions);
+    /// assert_eq!(duplicated.len(), 2);
+    /// assert!(!duplicated.contains(Position::new(4, 4)));
+    /// ```
+    #[must_use]
+    pub fn duplicated_positions(&self, positions: DigitPositions) -> DigitPositions {
+        self.houses()
+            .map(|house| house & positions)
+            .filter(|in_house| in_house.len() > 1)
+            .fold(DigitPositions::EMPTY, |acc, in_house| acc | in_house)
+    }
+
+    /// Returns `true` if `grid` is completely filled and every row, column,
+    /// and region contains each digit exactly once.
+    #[must_use]
+    pub fn is_solved(&self, grid: &DigitGrid) -> bool {
+        self.houses().all(|house| {
+            house
+                .iter()
+                .map(|pos| grid.get(pos))
+                .collect::<Option<Vec<_>>>()
+                .is_some_and(|digits| digits.into_iter().collect::<DigitSet>() == DigitSet::FULL)
+        })
+    }
+
+    /// Iterates over all 27 houses: rows, columns, and regions.
+    fn houses(&self) -> impl Iterator<Item = DigitPositions> + '_ {
+        (0..9).flat_map(|i| {
+            [
+                DigitPositions::ROW_POSITIONS[i],
+                DigitPositions::COL_POSITIONS[i],
+                self.regions[i],
+            ]
+        })
+    }
+}
+
+fn is_connected(region: DigitPositions) -> bool {
+    let Some(start) = region.first() else {
+        return true;
+    };
+    let mut visited = DigitPositions::from_elem(start);
+    let mut stack = vec![start];
+    while let Some(pos) = stack.pop() {
+        for next in [pos.up(), pos.down(), pos.left(), pos.right()]
+            .into_iter()
+            .flatten()
+        {
+            if region.contains(next) && visited.insert(next) {
+                stack.push(next);
+            }
+        }
+    }
+    visited == region
+}
+
+impl FromStr for RegionLayout {
+    type Err = RegionLayoutError;
+
+    /// Parses a layout from 81 region numbers (1-9) in row-major order.
+    ///
+    /// Whitespace is ignored.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut regions = [DigitPositions::EMPTY; 9];
+        let mut len = 0;
+        for ch in s.chars().filter(|ch| !ch.is_whitespace()) {
+            let index = match ch {
+                '1'..='9' => ch as u8 - b'1',
+                _ => return Err(RegionLayoutError::InvalidCharacter(ch)),
+            };
+            if len < 81 {
+                #[expect(clippy::cast_possible_truncation)]
+                regions[usize::from(index)].insert(Position::from_index(len as u8));
+            }
+            len += 1;
+        }
+        if len != 81 {
+            return Err(RegionLayoutError::InvalidLength(len));
+        }
+        Self::from_regions(regions)
+    }
+}
+
+impl fmt::Display for RegionLayout {
+    /// Formats the layout as 81 region numbers (1-9) in row-major order.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for pos in Position::ALL {
+            write!(f, "{}", self.region_index(pos) + 1)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Standard boxes with (0, 3) and (2, 2) swapped between the first two regions.
+    const JIGSAW: &str = "\
+        111122333\
+        111222333\
+        112222333\
+        444555666\
+        444555666\
+        444555666\
+        777888999\
+        777888999\
+        777888999";
+
+    #[test]
+    fn test_standard_matches_boxes() {
+        let layout = RegionLayout::standard();
+        for pos in Position::ALL {
+            let standard_box = (pos.row() / 3) * 3 + pos.col() / 3;
+            assert_eq!(layout.region_index(pos), standard_box);
+            assert_eq!(layout.house_peers(pos).len(), 20);
+            assert_eq!(
+                layout.region_cell(standard_box, layout.cell_index_in_region(pos)),
+                pos
+            );
+        }
+        let parsed: RegionLayout = layout.to_string().parse().unwrap();
+        assert_eq!(parsed, layout);
+    }
+
+    #[test]
+    fn test_parse_jigsaw() {
+        let layout: RegionLayout = JIGSAW.parse().unwrap();
+        assert!(!layout.is_standard());
+        assert_eq!(layout.region_index(Position::new(0, 3)), 0);
+        assert_eq!(layout.region_index(Position::new(2, 2)), 1);
+        assert_eq!(
+            layout.to_string(),
+            JIGSAW.split_whitespace().collect::<String>()
+        );
+
+        let peers = layout.house_peers(Position::new(2, 2));
+        assert!(peers.contains(Position::new(0, 4)));
+        assert!(!peers.contains(Position::new(0, 0)));
+    }
+
+    #[test]
+    fn test_duplicated_positions_follow_layout() {
+        let positions = DigitPositions::from_iter([Position::new(2, 2), Position::new(0, 4)]);
+
+        assert!(
+            RegionLayout::standard()
+                .duplicated_positions(positions)
+                .is_empty()
+        );
+        let layout: RegionLayout = JIGSAW.parse().unwrap();
+        assert_eq!(layout.duplicated_positions(positions), positions);
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_layouts() {
+        assert_eq!(
+            "1".parse::<RegionLayout>(),
+            Err(RegionLayoutError::InvalidLength(1))
+        );
+        assert_eq!(
+            format!("0{}", &JIGSAW[1..]).parse::<RegionLayout>(),
+            Err(RegionLayoutError::InvalidCharacter('0'))
+        );
+        assert_eq!(
+            format!("2{}", &JIGSAW[1..]).parse::<RegionLayout>(),
+            Err(RegionLayoutError::InvalidRegionSize { index: 0, len: 8 })
+        );
+        // Swap two cells so region 1 is split in two.
+        let mut split = JIGSAW.to_owned();
+        split.replace_range(0..1, "3");
+        split.replace_range(8..9, "1");
+        assert_eq!(
+            split.parse::<RegionLayout>(),
+            Err(RegionLayoutError::Disconnected(0))
+        );
+    }
+}
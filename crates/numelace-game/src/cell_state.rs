@@ -1,9 +1,6 @@
 use numelace_core::{Digit, DigitSet};
 
-use crate::{
-    error::GameError,
-    input::{InputBlockReason, InputOperation},
-};
+use crate::input::{InputBlockReason, InputOperation};
 
 /// The state of a cell in the game.
 ///
@@ -67,10 +64,10 @@ impl CellState {
     ///
     /// # Errors
     ///
-    /// Returns [`GameError::CannotModifyGivenCell`] if this is a given cell.
-    pub(crate) fn set_filled(&mut self, digit: Digit) -> Result<(), GameError> {
+    /// Returns [`InputBlockReason::GivenCell`] if this is a given cell.
+    pub(crate) fn set_filled(&mut self, digit: Digit) -> Result<(), InputBlockReason> {
         match self {
-            CellState::Given(_) => Err(GameError::CannotModifyGivenCell),
+            CellState::Given(_) => Err(InputBlockReason::GivenCell),
             CellState::Filled(d) => {
                 *d = digit;
                 Ok(())
@@ -156,10 +153,10 @@ impl CellState {
     ///
     /// # Errors
     ///
-    /// Returns [`GameError::CannotModifyGivenCell`] if this is a given cell.
-    pub(crate) fn clear(&mut self) -> Result<(), GameError> {
+    /// Returns [`InputBlockReason::GivenCell`] if this is a given cell.
+    pub(crate) fn clear(&mut self) -> Result<(), InputBlockReason> {
         match self {
-            CellState::Given(_) => Err(GameError::CannotModifyGivenCell),
+            CellState::Given(_) => Err(InputBlockReason::GivenCell),
             CellState::Filled(_) | CellState::Notes(_) => {
                 *self = CellState::Empty;
                 Ok(())
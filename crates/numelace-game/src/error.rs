@@ -1,38 +1,208 @@
-use derive_more::{Display, Error};
+use derive_more::{Display, Error, From};
+use numelace_core::{
+    CageError, ConsistencyError, Digit, EdgeConstraintError, Position, RegionLayoutError,
+};
 
 use crate::input::InputBlockReason;
 
 /// Errors that can occur during game operations.
-#[derive(Debug, Display, Error)]
+///
+/// Every variant records the position it refers to, so callers can point the
+/// player at the offending cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, Error)]
 pub enum GameError {
     /// Attempted to modify a given (initial) cell.
     ///
     /// Given cells are part of the initial puzzle and cannot be edited by the player.
-    #[display("cannot modify a given cell")]
-    CannotModifyGivenCell,
+    #[display("cannot modify the given cell at {pos}")]
+    CannotModifyGivenCell {
+        /// The given cell.
+        #[error(not(source))]
+        pos: Position,
+    },
     /// Attempted to add notes to a filled cell.
     ///
     /// Notes can only be added to notes or empty cells.
-    #[display("cannot add notes to a filled cell")]
-    CannotAddNoteToFilledCell,
+    #[display("cannot add notes to the filled cell at {pos}")]
+    CannotAddNoteToFilledCell {
+        /// The filled cell.
+        #[error(not(source))]
+        pos: Position,
+    },
     /// Invalid notes data provided.
     ///
     /// The notes data must be a valid bitmask representing digits 1-9.
-    #[display("invalid notes data: {_0:#x}")]
-    InvalidNotes(#[error(not(source))] u16),
+    #[display("invalid notes data at {pos}: {bits:#x}")]
+    InvalidNotes {
+        /// The cell the notes belong to.
+        #[error(not(source))]
+        pos: Position,
+        /// The rejected bitmask.
+        #[error(not(source))]
+        bits: u16,
+    },
+    /// A given digit differs from the solution the game was created with.
+    ///
+    /// This occurs only when building a game from stored or decoded grids.
+    #[display("the given {digit} at {pos} differs from the solution")]
+    GivenDiffersFromSolution {
+        /// The given cell.
+        #[error(not(source))]
+        pos: Position,
+        /// The given digit.
+        #[error(not(source))]
+        digit: Digit,
+    },
     /// Attempted to apply a digit that conflicts with existing digits.
     ///
     /// This occurs when the digit violates Sudoku rules in strict mode.
-    #[display("given digit causes a conflict with existing digits")]
-    ConflictingDigit,
+    #[display("digit {digit} at {pos} conflicts with existing digits")]
+    ConflictingDigit {
+        /// The target cell.
+        #[error(not(source))]
+        pos: Position,
+        /// The rejected digit.
+        #[error(not(source))]
+        digit: Digit,
+    },
+    /// Attempted to place a digit that differs from the stored solution.
+    ///
+    /// This occurs only under [`RuleCheckPolicy::Solution`](crate::RuleCheckPolicy::Solution).
+    #[display("digit {digit} at {pos} differs from the solution")]
+    WrongDigit {
+        /// The target cell.
+        #[error(not(source))]
+        pos: Position,
+        /// The rejected digit.
+        #[error(not(source))]
+        digit: Digit,
+    },
 }
 
-impl From<InputBlockReason> for GameError {
-    fn from(reason: InputBlockReason) -> Self {
+impl GameError {
+    /// Builds the error for an input of `digit` at `pos` blocked by `reason`.
+    #[must_use]
+    pub fn blocked(reason: InputBlockReason, pos: Position, digit: Digit) -> Self {
         match reason {
-            InputBlockReason::GivenCell => GameError::CannotModifyGivenCell,
-            InputBlockReason::FilledCell => GameError::CannotAddNoteToFilledCell,
-            InputBlockReason::Conflict => GameError::ConflictingDigit,
+            InputBlockReason::GivenCell => GameError::CannotModifyGivenCell { pos },
+            InputBlockReason::FilledCell => GameError::CannotAddNoteToFilledCell { pos },
+            InputBlockReason::Conflict => GameError::ConflictingDigit { pos, digit },
+            InputBlockReason::WrongDigit => GameError::WrongDigit { pos, digit },
+        }
+    }
+
+    /// Returns the position the error refers to.
+    #[must_use]
+    pub fn position(&self) -> Position {
+        match *self {
+            GameError::CannotModifyGivenCell { pos }
+            | GameError::CannotAddNoteToFilledCell { pos }
+            | GameError::InvalidNotes { pos, .. }
+            | GameError::GivenDiffersFromSolution { pos, .. }
+            | GameError::ConflictingDigit { pos, .. }
+            | GameError::WrongDigit { pos, .. } => pos,
         }
     }
 }
+
+/// A broken internal invariant of a [`Game`](crate::Game).
+///
+/// Reported by [`Game::validate_invariants`](crate::Game::validate_invariants).
+/// Every game operation keeps these invariants, so a violation points at a
+/// bug in the operation that ran last.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, Error)]
+pub enum InvariantViolation {
+    /// A given cell was changed, or a cell that was not given became one.
+    #[display("the given at {pos} was changed")]
+    GivenChanged {
+        /// The cell whose given status or digit changed.
+        #[error(not(source))]
+        pos: Position,
+    },
+    /// A given digit differs from the stored solution.
+    #[display("the given {digit} at {pos} differs from the solution")]
+    GivenDiffersFromSolution {
+        /// The given cell.
+        #[error(not(source))]
+        pos: Position,
+        /// The given digit.
+        #[error(not(source))]
+        digit: Digit,
+    },
+    /// A notes cell holds no notes; it should be empty instead.
+    #[display("the notes cell at {pos} has no notes")]
+    EmptyNotes {
+        /// The notes cell.
+        #[error(not(source))]
+        pos: Position,
+    },
+    /// A cell holding a digit also holds center notes.
+    #[display("the cell at {pos} holds a digit and center notes")]
+    CenterNotesOnDigit {
+        /// The cell holding the digit.
+        #[error(not(source))]
+        pos: Position,
+    },
+    /// The per-digit cell index disagrees with the grid.
+    #[display("the cell index of digit {digit} is out of sync with the grid")]
+    DigitIndexOutOfSync {
+        /// The digit whose index is wrong.
+        #[error(not(source))]
+        digit: Digit,
+    },
+}
+
+/// Errors that can occur when decoding a state code.
+///
+/// See [`Game::from_state_code`](crate::Game::from_state_code).
+#[derive(Debug, Clone, PartialEq, Eq, Display, Error, From)]
+pub enum StateCodeError {
+    /// The code contains a character outside the base64url alphabet.
+    #[display("state code contains an invalid character: {_0:?}")]
+    #[from(skip)]
+    InvalidCharacter(#[error(not(source))] char),
+    /// The code ends before all announced data was read.
+    #[display("state code is truncated")]
+    #[from(skip)]
+    Truncated,
+    /// The code was written by a format version this build cannot read.
+    #[display("unsupported state code version {_0}")]
+    #[from(skip)]
+    UnsupportedVersion(#[error(not(source))] u32),
+    /// The code holds a value out of range for its field.
+    #[display("state code contains an invalid value {_0}")]
+    #[from(skip)]
+    InvalidValue(#[error(not(source))] u32),
+    /// The decoded grids do not form a valid game.
+    #[display("failed to restore the game: {_0}")]
+    Game(GameError),
+    /// The decoded killer cages are invalid.
+    #[display("failed to restore the killer cages: {_0}")]
+    Cage(CageError),
+    /// The decoded region layout is invalid.
+    #[display("failed to restore the region layout: {_0}")]
+    RegionLayout(RegionLayoutError),
+    /// The decoded edge constraints are invalid.
+    #[display("failed to restore the edge constraints: {_0}")]
+    EdgeConstraint(EdgeConstraintError),
+}
+
+/// Errors that can occur when searching for a hint.
+///
+/// See [`Game::find_hint`](crate::Game::find_hint).
+#[derive(Debug, Clone, PartialEq, Eq, Display, Error, From)]
+pub enum HintError {
+    /// The placed digits break the rules, or leave a cell or a variant rule
+    /// without any way to be satisfied.
+    #[display("inconsistency detected: {_0}")]
+    Inconsistent(ConsistencyError),
+    /// The step found places a digit that differs from the solution, so some
+    /// placed digit is wrong.
+    #[display("hint step conflicts with solution")]
+    #[from(skip)]
+    SolutionMismatch,
+    /// The solver ran out of budget before finding a step.
+    #[display("the solver ran out of budget before finding a step")]
+    #[from(skip)]
+    Undetermined,
+}
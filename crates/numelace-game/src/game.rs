@@ -1,12 +1,13 @@
 use numelace_core::{
-    CandidateGrid, Digit, DigitGrid, DigitIndexedArray, DigitPositions, DigitSet, Position,
-    PositionIndexedArray,
+    CageSet, CandidateGrid, ChessRules, Constraint, Digit, DigitGrid, DigitIndexedArray,
+    DigitPositions, DigitSet, EdgeConstraintSet, Position, PositionIndexedArray, RegionLayout,
 };
 use numelace_generator::GeneratedPuzzle;
 use numelace_solver::{TechniqueApplication, TechniqueStep};
 
 use crate::{
-    CellState, GameError, InputBlockReason, InputDigitOptions, InputOperation, RuleCheckPolicy,
+    CellState, GameError, InputBlockReason, InputDigitOptions, InputOperation, InvariantViolation,
+    RuleCheckPolicy,
 };
 
 /// A Sudoku game session.
@@ -33,8 +34,23 @@ use crate::{
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Game {
     grid: PositionIndexedArray<CellState>,
+    /// Center notes of each cell, kept apart from the corner notes in `grid`.
+    ///
+    /// Only cells without a digit hold center notes.
+    center_notes: PositionIndexedArray<DigitSet>,
+    /// The puzzle's given digits, kept to check that no operation touches them.
+    givens: DigitGrid,
     solution: DigitGrid,
+    cages: CageSet,
+    edges: EdgeConstraintSet,
+    chess: ChessRules,
+    regions: RegionLayout,
     initialized: bool,
+    /// Cells holding each digit as a given or filled digit.
+    ///
+    /// Kept in sync with `grid` on every set and clear so that conflict checks
+    /// and digit counts do not need to scan the board.
+    digit_positions: DigitIndexedArray<DigitPositions>,
 }
 
 impl Game {
@@ -61,6 +77,7 @@ impl Game {
         let GeneratedPuzzle {
             problem,
             solution,
+            regions,
             seed: _,
         } = puzzle;
         let mut grid = PositionIndexedArray::from_array([const { CellState::Empty }; 81]);
@@ -69,11 +86,20 @@ impl Game {
                 grid[pos] = CellState::Given(digit);
             }
         }
-        Self {
+        let this = Self {
+            digit_positions: index_digit_positions(&grid),
             grid,
+            center_notes: PositionIndexedArray::from_array([DigitSet::EMPTY; 81]),
+            givens: problem,
             solution,
+            cages: CageSet::new(),
+            edges: EdgeConstraintSet::new(),
+            chess: ChessRules::new(),
+            regions,
             initialized: true,
-        }
+        };
+        this.debug_check_invariants();
+        this
     }
 
     /// Creates an uninitialized game placeholder with an empty grid.
@@ -84,8 +110,15 @@ impl Game {
     pub fn new_empty() -> Self {
         Self {
             grid: PositionIndexedArray::from_array([CellState::Empty; 81]),
+            center_notes: PositionIndexedArray::from_array([DigitSet::EMPTY; 81]),
+            givens: DigitGrid::new(),
             solution: DigitGrid::new(),
+            cages: CageSet::new(),
+            edges: EdgeConstraintSet::new(),
+            chess: ChessRules::new(),
+            regions: RegionLayout::standard(),
             initialized: false,
+            digit_positions: DigitIndexedArray::from_fn(|_| DigitPositions::EMPTY),
         }
     }
 
@@ -105,7 +138,9 @@ impl Game {
     /// # Errors
     ///
     /// Returns [`GameError::CannotModifyGivenCell`] if `filled` contains a digit
-    /// in a position that is a given in `problem`.
+    /// in a position that is a given in `problem`, and
+    /// [`GameError::GivenDiffersFromSolution`] if a digit of `problem` contradicts
+    /// a known digit of `solution`.
     pub fn from_problem_filled_notes(
         problem: &DigitGrid,
         solution: &DigitGrid,
@@ -120,8 +155,15 @@ impl Game {
         }
 
         let mut this = Self {
+            digit_positions: index_digit_positions(&grid),
             grid,
+            center_notes: PositionIndexedArray::from_array([DigitSet::EMPTY; 81]),
+            givens: problem.clone(),
             solution: solution.clone(),
+            cages: CageSet::new(),
+            edges: EdgeConstraintSet::new(),
+            chess: ChessRules::new(),
+            regions: RegionLayout::standard(),
             initialized: true,
         };
         for pos in Position::ALL {
@@ -133,17 +175,123 @@ impl Game {
         for (row_index, row) in (0..9).zip(notes) {
             for (col_index, bits) in (0..9).zip(row) {
                 let pos = Position::new(row_index, col_index);
-                let digits =
-                    DigitSet::try_from_bits(*bits).ok_or(GameError::InvalidNotes(*bits))?;
+                let digits = DigitSet::try_from_bits(*bits)
+                    .ok_or(GameError::InvalidNotes { pos, bits: *bits })?;
                 for d in digits {
                     this.toggle_note(pos, d, RuleCheckPolicy::Permissive)?;
                 }
             }
         }
 
+        // The grids come from saves and share codes, so a broken invariant is
+        // bad input rather than a bug and must not panic.
+        this.validate_invariants().map_err(|err| match err {
+            InvariantViolation::GivenDiffersFromSolution { pos, digit } => {
+                GameError::GivenDiffersFromSolution { pos, digit }
+            }
+            // The loops above keep the remaining invariants for any input.
+            err => unreachable!("game invariant violated: {err}"),
+        })?;
         Ok(this)
     }
 
+    /// Returns the game with the center notes of `notes` added.
+    ///
+    /// `notes` holds a digit bitmask per cell, laid out like the notes of
+    /// [`Game::from_problem_filled_notes`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GameError::InvalidNotes`] if a bitmask is not a set of digits,
+    /// and [`GameError::CannotModifyGivenCell`] or
+    /// [`GameError::CannotAddNoteToFilledCell`] for notes on a cell holding a
+    /// digit.
+    pub fn with_center_notes(mut self, notes: &[[u16; 9]; 9]) -> Result<Self, GameError> {
+        for (row_index, row) in (0..9).zip(notes) {
+            for (col_index, bits) in (0..9).zip(row) {
+                let pos = Position::new(row_index, col_index);
+                let digits = DigitSet::try_from_bits(*bits)
+                    .ok_or(GameError::InvalidNotes { pos, bits: *bits })?;
+                for digit in digits {
+                    self.toggle_center_note(pos, digit, RuleCheckPolicy::Permissive)?;
+                }
+            }
+        }
+        Ok(self)
+    }
+
+    /// Returns the game with the given killer cages attached.
+    ///
+    /// Cages add sum and no-repeat rules on top of the classic rules; they
+    /// are taken into account by conflict checks and [`Game::is_solved`].
+    #[must_use]
+    pub fn with_cages(mut self, cages: CageSet) -> Self {
+        self.cages = cages;
+        self
+    }
+
+    /// Returns the killer cages of this game (empty for classic puzzles).
+    #[must_use]
+    pub fn cages(&self) -> &CageSet {
+        &self.cages
+    }
+
+    /// Returns the game with the given greater-than and consecutive markers attached.
+    ///
+    /// Markers constrain pairs of adjacent cells on top of the classic rules;
+    /// they are taken into account by conflict checks and [`Game::is_solved`].
+    #[must_use]
+    pub fn with_edge_constraints(mut self, edges: EdgeConstraintSet) -> Self {
+        self.edges = edges;
+        self
+    }
+
+    /// Returns the edge constraints of this game (empty for classic puzzles).
+    #[must_use]
+    pub fn edge_constraints(&self) -> &EdgeConstraintSet {
+        &self.edges
+    }
+
+    /// Returns the game with the given anti-knight / anti-king rules enabled.
+    ///
+    /// The rules forbid repeated digits a chess move apart; they are taken
+    /// into account by conflict checks and [`Game::is_solved`].
+    #[must_use]
+    pub fn with_chess_rules(mut self, chess: ChessRules) -> Self {
+        self.chess = chess;
+        self
+    }
+
+    /// Returns the anti-knight / anti-king rules of this game.
+    #[must_use]
+    pub fn chess_rules(&self) -> ChessRules {
+        self.chess
+    }
+
+    /// Returns the variant constraints applied on top of the classic rules.
+    ///
+    /// Conflict checks and [`Game::is_solved`] consult every constraint in
+    /// this list; empty constraint sets never report violations.
+    pub fn constraints(&self) -> impl Iterator<Item = &dyn Constraint> {
+        [&self.cages as &dyn Constraint, &self.edges, &self.chess].into_iter()
+    }
+
+    /// Returns the game with the given region layout.
+    ///
+    /// Jigsaw puzzles replace the 3×3 boxes with irregular regions; conflict
+    /// checks, note auto-fill, and [`Game::is_solved`] follow the layout.
+    #[must_use]
+    pub fn with_regions(mut self, regions: RegionLayout) -> Self {
+        self.regions = regions;
+        self
+    }
+
+    /// Returns the region layout of this game (3×3 boxes for classic puzzles).
+    #[must_use]
+    pub fn regions(&self) -> &RegionLayout {
+        &self.regions
+    }
+
     /// Returns the state of the cell at the given position.
     ///
     /// # Example
@@ -172,17 +320,94 @@ impl Game {
         &self.grid[pos]
     }
 
+    /// Returns the center notes of the cell at `pos`.
+    ///
+    /// Center notes are kept apart from the corner notes of [`CellState::Notes`];
+    /// cells holding a digit never have any.
+    #[must_use]
+    pub fn center_notes(&self, pos: Position) -> DigitSet {
+        self.center_notes[pos]
+    }
+
+    /// Returns every cell with its position, in [`Position::ALL`] order.
+    pub fn iter_cells(&self) -> impl Iterator<Item = (Position, &CellState)> {
+        Position::ALL.into_iter().map(|pos| (pos, &self.grid[pos]))
+    }
+
+    /// Returns the positions of the given cells.
+    #[must_use]
+    pub fn given_positions(&self) -> DigitPositions {
+        self.positions_where(CellState::is_given)
+    }
+
+    /// Returns the positions of the cells filled by the player.
+    #[must_use]
+    pub fn filled_positions(&self) -> DigitPositions {
+        self.positions_where(CellState::is_filled)
+    }
+
+    /// Returns the positions of the cells without a digit.
+    ///
+    /// Cells holding only notes count as empty here, unlike
+    /// [`CellState::is_empty`]; these are the cells still to be solved.
+    #[must_use]
+    pub fn empty_positions(&self) -> DigitPositions {
+        self.positions_where(|cell| cell.as_digit().is_none())
+    }
+
+    fn positions_where(&self, f: impl Fn(&CellState) -> bool) -> DigitPositions {
+        self.iter_cells()
+            .filter(|(_, cell)| f(cell))
+            .map(|(pos, _)| pos)
+            .collect()
+    }
+
+    /// Returns the solution digit for the cell at `pos`, if the solution is known.
+    ///
+    /// Games created without a solution, such as [`Game::new_empty`], return `None`.
+    #[must_use]
+    pub fn solution_digit_at(&self, pos: Position) -> Option<Digit> {
+        self.solution.get(pos)
+    }
+
     /// Returns the stored solution grid for this puzzle.
     #[must_use]
     pub fn solution(&self) -> &DigitGrid {
         &self.solution
     }
 
+    /// Returns whether the solution digit of every cell is known.
+    #[must_use]
+    pub fn has_solution(&self) -> bool {
+        Position::ALL
+            .into_iter()
+            .all(|pos| self.solution_digit_at(pos).is_some())
+    }
+
+    /// Returns the cells filled by the player with a digit other than the
+    /// solution's.
+    ///
+    /// Only digits are compared, so this is much cheaper than running the
+    /// solver. Cells without a known solution digit never count as mistakes.
+    #[must_use]
+    pub fn mistake_positions(&self) -> DigitPositions {
+        self.iter_cells()
+            .filter(|&(pos, cell)| {
+                cell.as_filled()
+                    .zip(self.solution_digit_at(pos))
+                    .is_some_and(|(digit, expected)| digit != expected)
+            })
+            .map(|(pos, _)| pos)
+            .collect()
+    }
+
     /// Checks if the game is solved.
     ///
     /// A game is considered solved when:
     /// - All cells are filled (no empty cells)
-    /// - There are no rule violations (no duplicate digits in rows, columns, or boxes)
+    /// - There are no rule violations (no duplicate digits in rows, columns, or regions)
+    /// - Every killer cage adds up to its sum without repeating digits
+    /// - Every greater-than and consecutive marker is satisfied
     ///
     /// This accepts any valid solution, not just the original solution from the generator.
     /// This handles puzzles with multiple solutions correctly.
@@ -213,27 +438,36 @@ impl Game {
     /// ```
     #[must_use]
     pub fn is_solved(&self) -> bool {
-        let grid = self.to_candidate_grid();
-        grid.is_solved().unwrap_or_default()
+        let grid = self.to_digit_grid();
+        self.regions.is_solved(&grid)
+            && self
+                .constraints()
+                .all(|constraint| constraint.check(&grid).is_ok())
+    }
+
+    fn to_digit_grid(&self) -> DigitGrid {
+        let mut grid = DigitGrid::new();
+        for pos in Position::ALL {
+            grid.set(pos, self.grid[pos].as_digit());
+        }
+        grid
     }
 
     /// Returns a candidate grid derived from givens and filled digits.
     ///
-    /// Notes are ignored; empty cells exclude digits already present in peers.
+    /// Notes are ignored; cells without a digit exclude digits already present in peers.
+    /// The grid takes its boxes from the game's [`RegionLayout`].
     #[must_use]
     pub fn to_candidate_grid(&self) -> CandidateGrid {
-        let mut candidate_grid = CandidateGrid::new();
+        let mut candidate_grid = CandidateGrid::new().with_regions(self.regions);
         for pos in Position::ALL {
             match &self.grid[pos] {
                 CellState::Given(digit) | CellState::Filled(digit) => {
                     candidate_grid.place(pos, *digit);
                 }
-                CellState::Notes(_) => {}
-                CellState::Empty => {
-                    for peer_pos in pos.house_peers() {
-                        if let Some(digit) = self.grid[peer_pos].as_digit() {
-                            candidate_grid.remove_candidate(pos, digit);
-                        }
+                CellState::Notes(_) | CellState::Empty => {
+                    for digit in !self.legal_digits_at(pos) {
+                        candidate_grid.remove_candidate(pos, digit);
                     }
                 }
             }
@@ -244,10 +478,11 @@ impl Game {
     /// Returns a candidate grid derived from givens, filled digits, and notes.
     ///
     /// Notes are treated as the authoritative candidate set for that cell, while empty
-    /// cells exclude digits already present in peers.
+    /// cells exclude digits already present in peers. The grid takes its boxes
+    /// from the game's [`RegionLayout`].
     #[must_use]
     pub fn to_candidate_grid_with_notes(&self) -> CandidateGrid {
-        let mut candidate_grid = CandidateGrid::new();
+        let mut candidate_grid = CandidateGrid::new().with_regions(self.regions);
         for pos in Position::ALL {
             match &self.grid[pos] {
                 CellState::Given(digit) | CellState::Filled(digit) => {
@@ -261,10 +496,8 @@ impl Game {
                     }
                 }
                 CellState::Empty => {
-                    for peer_pos in pos.house_peers() {
-                        if let Some(digit) = self.grid[peer_pos].as_digit() {
-                            candidate_grid.remove_candidate(pos, digit);
-                        }
+                    for digit in !self.legal_digits_at(pos) {
+                        candidate_grid.remove_candidate(pos, digit);
                     }
                 }
             }
@@ -272,13 +505,35 @@ impl Game {
         candidate_grid
     }
 
+    /// Returns the digits that no peer of `pos` holds yet.
+    ///
+    /// Peers are the cells of the same houses plus those reached by the chess
+    /// rules. The cell's own digit and notes are ignored, as are cage and edge
+    /// constraints. This is the single-cell counterpart of
+    /// [`Game::to_candidate_grid`].
+    #[must_use]
+    pub fn legal_digits_at(&self, pos: Position) -> DigitSet {
+        let peers = self.peers(pos);
+        Digit::ALL
+            .into_iter()
+            .filter(|&digit| (peers & self.digit_positions[digit]).is_empty())
+            .collect()
+    }
+
+    /// Returns the cells that cannot share a digit with `pos`: its house peers
+    /// plus any cells reached by the anti-knight / anti-king rules.
+    fn peers(&self, pos: Position) -> DigitPositions {
+        self.regions.house_peers(pos) | self.chess.peers(pos)
+    }
+
     fn is_conflicting(&self, pos: Position, digit: Digit) -> bool {
-        for peer_pos in pos.house_peers() {
-            if self.grid[peer_pos].as_digit() == Some(digit) {
-                return true;
-            }
+        if !(self.peers(pos) & self.digit_positions[digit]).is_empty() {
+            return true;
         }
-        false
+        let mut grid = self.to_digit_grid();
+        grid.set(pos, Some(digit));
+        self.constraints()
+            .any(|constraint| constraint.check_cell(&grid, pos).is_err())
     }
 
     /// Places a digit at the given position.
@@ -291,6 +546,8 @@ impl Game {
     /// Returns [`GameError::CannotModifyGivenCell`] if the position contains a given cell.
     /// Returns [`GameError::ConflictingDigit`] if strict rule checks are enabled and
     /// the digit conflicts with existing digits.
+    /// Returns [`GameError::WrongDigit`] under [`RuleCheckPolicy::Solution`] if the
+    /// digit differs from the stored solution.
     ///
     /// # Example
     ///
@@ -322,7 +579,10 @@ impl Game {
         digit: Digit,
         options: &InputDigitOptions,
     ) -> Result<InputOperation, GameError> {
-        let operation = self.cell(pos).set_digit_capability(digit)?;
+        let operation = self
+            .cell(pos)
+            .set_digit_capability(digit)
+            .map_err(|reason| GameError::blocked(reason, pos, digit))?;
 
         match operation {
             InputOperation::NoOp => return Ok(InputOperation::NoOp),
@@ -332,18 +592,27 @@ impl Game {
             InputOperation::Set => {}
         }
 
-        if options.rule_check_policy.is_strict() && self.is_conflicting(pos, digit) {
-            return Err(GameError::ConflictingDigit);
-        }
+        self.check_digit(pos, digit, options.rule_check_policy)
+            .map_err(|reason| GameError::blocked(reason, pos, digit))?;
 
-        self.grid[pos].set_filled(digit)?;
+        let previous = self.grid[pos].as_digit();
+        self.grid[pos]
+            .set_filled(digit)
+            .map_err(|reason| GameError::blocked(reason, pos, digit))?;
+        if let Some(previous) = previous {
+            self.digit_positions[previous].remove(pos);
+        }
+        self.digit_positions[digit].insert(pos);
+        self.center_notes[pos] = DigitSet::EMPTY;
 
         if options.note_cleanup_policy.is_remove_peers() {
-            for peer_pos in pos.house_peers() {
+            for peer_pos in self.peers(pos) {
                 self.grid[peer_pos].drop_note_digit(digit);
+                self.center_notes[peer_pos].remove(digit);
             }
         }
 
+        self.debug_check_invariants();
         Ok(InputOperation::Set)
     }
 
@@ -357,6 +626,8 @@ impl Game {
     /// Returns [`InputBlockReason::GivenCell`] if the cell is a given cell.
     /// Returns [`InputBlockReason::Conflict`] if strict rule checks are enabled and
     /// the digit conflicts with existing digits.
+    /// Returns [`InputBlockReason::WrongDigit`] under [`RuleCheckPolicy::Solution`]
+    /// if the digit differs from the stored solution.
     pub fn set_digit_capability(
         &self,
         pos: Position,
@@ -365,16 +636,32 @@ impl Game {
     ) -> Result<InputOperation, InputBlockReason> {
         let operation = self.cell(pos).set_digit_capability(digit)?;
 
-        if matches!(operation, InputOperation::Set)
-            && policy.is_strict()
-            && self.is_conflicting(pos, digit)
-        {
-            return Err(InputBlockReason::Conflict);
+        if matches!(operation, InputOperation::Set) {
+            self.check_digit(pos, digit, policy)?;
         }
 
         Ok(operation)
     }
 
+    fn check_digit(
+        &self,
+        pos: Position,
+        digit: Digit,
+        policy: RuleCheckPolicy,
+    ) -> Result<(), InputBlockReason> {
+        if policy.checks_rules() && self.is_conflicting(pos, digit) {
+            return Err(InputBlockReason::Conflict);
+        }
+        if policy.checks_solution()
+            && self
+                .solution_digit_at(pos)
+                .is_some_and(|expected| expected != digit)
+        {
+            return Err(InputBlockReason::WrongDigit);
+        }
+        Ok(())
+    }
+
     /// Toggles a candidate note at the given position.
     ///
     /// If the cell is empty, it becomes a notes cell with the digit. If the cell already
@@ -393,22 +680,27 @@ impl Game {
         digit: Digit,
         policy: RuleCheckPolicy,
     ) -> Result<InputOperation, GameError> {
-        let operation = self.cell(pos).toggle_note_capability(digit)?;
+        let operation = self
+            .cell(pos)
+            .toggle_note_capability(digit)
+            .map_err(|reason| GameError::blocked(reason, pos, digit))?;
 
         match operation {
             InputOperation::NoOp => return Ok(InputOperation::NoOp),
             InputOperation::Removed => {
                 self.grid[pos].drop_note_digit(digit);
+                self.debug_check_invariants();
                 return Ok(InputOperation::Removed);
             }
             InputOperation::Set => {}
         }
 
-        if policy.is_strict() && self.is_conflicting(pos, digit) {
-            return Err(GameError::ConflictingDigit);
+        if policy.checks_rules() && self.is_conflicting(pos, digit) {
+            return Err(GameError::ConflictingDigit { pos, digit });
         }
 
         self.grid[pos].add_note_digit(digit);
+        self.debug_check_invariants();
         Ok(InputOperation::Set)
     }
 
@@ -433,7 +725,7 @@ impl Game {
         let operation = self.cell(pos).toggle_note_capability(digit)?;
 
         if matches!(operation, InputOperation::Set)
-            && policy.is_strict()
+            && policy.checks_rules()
             && self.is_conflicting(pos, digit)
         {
             return Err(InputBlockReason::Conflict);
@@ -442,6 +734,67 @@ impl Game {
         Ok(operation)
     }
 
+    /// Toggles a center note at the given position.
+    ///
+    /// Center notes follow the rules of the corner notes toggled by
+    /// [`Game::toggle_note`]: only cells without a digit take them, and only
+    /// adding a note is checked against `policy`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GameError::CannotModifyGivenCell`] if the position contains a given cell.
+    /// Returns [`GameError::CannotAddNoteToFilledCell`] if the position contains a filled cell.
+    /// Returns [`GameError::ConflictingDigit`] if strict rule checks are enabled and
+    /// the digit conflicts with existing digits.
+    pub fn toggle_center_note(
+        &mut self,
+        pos: Position,
+        digit: Digit,
+        policy: RuleCheckPolicy,
+    ) -> Result<InputOperation, GameError> {
+        let operation = self
+            .toggle_center_note_capability(pos, digit, policy)
+            .map_err(|reason| GameError::blocked(reason, pos, digit))?;
+        match operation {
+            InputOperation::NoOp => {}
+            InputOperation::Removed => {
+                self.center_notes[pos].remove(digit);
+            }
+            InputOperation::Set => {
+                self.center_notes[pos].insert(digit);
+            }
+        }
+        self.debug_check_invariants();
+        Ok(operation)
+    }
+
+    /// Returns the toggle capability for center notes at the given position.
+    ///
+    /// See [`Game::toggle_note_capability`]; the result describes the center
+    /// notes instead of the corner notes.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InputBlockReason::GivenCell`] if the cell is a given cell.
+    /// Returns [`InputBlockReason::FilledCell`] if the cell is filled.
+    /// Returns [`InputBlockReason::Conflict`] if strict rule checks are enabled and
+    /// the digit conflicts with existing digits when adding a note.
+    pub fn toggle_center_note_capability(
+        &self,
+        pos: Position,
+        digit: Digit,
+        policy: RuleCheckPolicy,
+    ) -> Result<InputOperation, InputBlockReason> {
+        self.cell(pos).can_set_notes()?;
+        if self.center_notes[pos].contains(digit) {
+            return Ok(InputOperation::Removed);
+        }
+        if policy.checks_rules() && self.is_conflicting(pos, digit) {
+            return Err(InputBlockReason::Conflict);
+        }
+        Ok(InputOperation::Set)
+    }
+
     /// Returns the note auto-fill capability for a single cell.
     ///
     /// This computes candidate notes by excluding digits already present in peers,
@@ -456,12 +809,7 @@ impl Game {
         pos: Position,
     ) -> Result<InputOperation, InputBlockReason> {
         self.cell(pos).can_set_notes()?;
-        let mut notes = DigitSet::FULL;
-        for peer_pos in pos.house_peers() {
-            if let Some(digit) = self.grid[peer_pos].as_digit() {
-                notes.remove(digit);
-            }
-        }
+        let notes = self.legal_digits_at(pos);
         self.cell(pos).set_notes_capability(notes)
     }
 
@@ -479,17 +827,13 @@ impl Game {
         pos: Position,
     ) -> Result<InputOperation, InputBlockReason> {
         self.cell(pos).can_set_notes()?;
-        let mut notes = DigitSet::FULL;
-        for peer_pos in pos.house_peers() {
-            if let Some(digit) = self.grid[peer_pos].as_digit() {
-                notes.remove(digit);
-            }
-        }
+        let notes = self.legal_digits_at(pos);
         let operation = self.cell(pos).set_notes_capability(notes)?;
         match operation {
             InputOperation::NoOp => {}
             InputOperation::Set => {
                 self.grid[pos].set_notes(notes);
+                self.debug_check_invariants();
             }
             InputOperation::Removed => unreachable!(""),
         }
@@ -557,16 +901,172 @@ impl Game {
     /// assert!(game.cell(empty_pos).is_empty());
     /// ```
     pub fn clear_cell(&mut self, pos: Position) -> Result<(), GameError> {
-        self.grid[pos].clear()?;
+        let previous = self.grid[pos].as_digit();
+        self.grid[pos]
+            .clear()
+            .map_err(|_| GameError::CannotModifyGivenCell { pos })?;
+        if let Some(previous) = previous {
+            self.digit_positions[previous].remove(pos);
+        }
+        self.center_notes[pos] = DigitSet::EMPTY;
+        self.debug_check_invariants();
         Ok(())
     }
 
+    /// Clears every filled digit and note, keeping the givens.
+    pub fn clear_inputs(&mut self) {
+        for pos in Position::ALL {
+            if self.grid[pos].has_removable_input() {
+                let _ = self.grid[pos].clear();
+            }
+        }
+        self.center_notes = PositionIndexedArray::from_array([DigitSet::EMPTY; 81]);
+        self.digit_positions = index_digit_positions(&self.grid);
+        self.debug_check_invariants();
+    }
+
     /// Returns whether the cell currently contains removable player input.
     ///
-    /// This is `true` for filled (player-entered) digits or notes.
+    /// This is `true` for filled (player-entered) digits, corner notes, or
+    /// center notes.
     #[must_use]
     pub fn has_removable_input(&self, pos: Position) -> bool {
-        self.cell(pos).has_removable_input()
+        self.cell(pos).has_removable_input() || !self.center_notes[pos].is_empty()
+    }
+
+    /// Clears player-filled digits that differ from the stored solution.
+    ///
+    /// Correct digits and notes are kept. Cells without a known solution digit
+    /// are left untouched. Returns the number of cleared cells.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use numelace_core::Position;
+    /// use numelace_game::{Game, InputDigitOptions};
+    /// use numelace_generator::PuzzleGenerator;
+    /// use numelace_solver::TechniqueSolver;
+    ///
+    /// let solver = TechniqueSolver::with_all_techniques();
+    /// let puzzle = PuzzleGenerator::new(&solver).generate();
+    /// let mut game = Game::new(puzzle);
+    ///
+    /// let empty_pos = *Position::ALL
+    ///     .iter()
+    ///     .find(|&&pos| game.cell(pos).is_empty())
+    ///     .expect("puzzle has empty cells");
+    /// let correct = game.solution()[empty_pos].expect("solution is complete");
+    /// game.set_digit(empty_pos, correct, &InputDigitOptions::default())
+    ///     .unwrap();
+    ///
+    /// // Correct digits survive
+    /// assert_eq!(game.clear_mistakes(), 0);
+    /// assert_eq!(game.cell(empty_pos).as_digit(), Some(correct));
+    /// ```
+    pub fn clear_mistakes(&mut self) -> usize {
+        let mistakes = self.mistake_positions();
+        for pos in mistakes {
+            if let Some(digit) = self.grid[pos].as_filled() {
+                self.digit_positions[digit].remove(pos);
+            }
+            self.grid[pos] = CellState::Empty;
+        }
+        self.debug_check_invariants();
+        mistakes.len()
+    }
+
+    /// Returns the notes that cannot be right because a peer already holds the digit.
+    ///
+    /// Peers are the cells of the same houses plus those reached by the chess
+    /// rules. Only cells with illegal notes are listed, in position order.
+    /// Unlike [`Game::auto_fill_notes_all_cells`], this only audits what the
+    /// player wrote and never adds notes.
+    #[must_use]
+    pub fn illegal_notes(&self) -> Vec<(Position, DigitSet)> {
+        Position::ALL
+            .into_iter()
+            .filter_map(|pos| {
+                let notes = self.grid[pos].as_notes()?;
+                let illegal = notes & !self.legal_digits_at(pos);
+                (!illegal.is_empty()).then_some((pos, illegal))
+            })
+            .collect()
+    }
+
+    /// Removes the notes reported by [`Game::illegal_notes`].
+    ///
+    /// Returns the number of removed note digits.
+    pub fn remove_illegal_notes(&mut self) -> usize {
+        let mut removed = 0;
+        for (pos, illegal) in self.illegal_notes() {
+            for digit in illegal {
+                self.grid[pos].drop_note_digit(digit);
+            }
+            removed += illegal.len();
+        }
+        self.debug_check_invariants();
+        removed
+    }
+
+    /// Returns the reveal capability for a single cell.
+    ///
+    /// Reports whether [`Game::reveal_cell`] would be a no-op or a set.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InputBlockReason::GivenCell`] if the cell is a given cell.
+    pub fn reveal_cell_capability(
+        &self,
+        pos: Position,
+    ) -> Result<InputOperation, InputBlockReason> {
+        if self.grid[pos].is_given() {
+            return Err(InputBlockReason::GivenCell);
+        }
+        match self.solution_digit_at(pos) {
+            Some(digit) => self.cell(pos).set_digit_capability(digit),
+            None => Ok(InputOperation::NoOp),
+        }
+    }
+
+    /// Fills the cell at `pos` with its digit from the stored solution.
+    ///
+    /// A wrong digit or notes in the cell are replaced. The digit is placed
+    /// even when it conflicts with wrong digits elsewhere, whatever the rule
+    /// check policy of `options`; its note cleanup policy still applies.
+    /// Returns [`InputOperation::NoOp`] if the cell already holds the digit or
+    /// the solution does not know it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GameError::CannotModifyGivenCell`] if the cell is a given cell.
+    pub fn reveal_cell(
+        &mut self,
+        pos: Position,
+        options: &InputDigitOptions,
+    ) -> Result<InputOperation, GameError> {
+        if self.grid[pos].is_given() {
+            return Err(GameError::CannotModifyGivenCell { pos });
+        }
+        let Some(digit) = self.solution_digit_at(pos) else {
+            return Ok(InputOperation::NoOp);
+        };
+        let options = options
+            .clone()
+            .rule_check_policy(RuleCheckPolicy::Permissive);
+        self.set_digit(pos, digit, &options)
+    }
+
+    /// Fills every non-given cell from the stored solution.
+    ///
+    /// Returns the number of cells that changed; see [`Game::reveal_cell`].
+    pub fn reveal_all(&mut self, options: &InputDigitOptions) -> usize {
+        let mut revealed = 0;
+        for pos in Position::ALL {
+            if let Ok(InputOperation::Set) = self.reveal_cell(pos, options) {
+                revealed += 1;
+            }
+        }
+        revealed
     }
 
     /// Returns the count of each decided digit (given or filled) on the board.
@@ -575,20 +1075,106 @@ impl Game {
     /// player-filled cells.
     #[must_use]
     pub fn decided_digit_count(&self) -> DigitIndexedArray<usize> {
-        let mut counts = DigitIndexedArray::from_array([0; 9]);
+        DigitIndexedArray::from_fn(|digit| self.digit_positions[digit].len())
+    }
+
+    /// Returns the digits placed in all nine of their cells, each matching the
+    /// stored solution.
+    ///
+    /// A digit on a cell without a known solution digit is never counted as
+    /// complete, so games without a solution have no complete digits.
+    #[must_use]
+    pub fn completed_digits(&self) -> DigitSet {
+        Digit::ALL
+            .into_iter()
+            .filter(|&digit| {
+                let positions = self.digit_positions[digit];
+                positions.len() == 9
+                    && positions
+                        .into_iter()
+                        .all(|pos| self.solution_digit_at(pos) == Some(digit))
+            })
+            .collect()
+    }
+
+    /// Returns the cells holding `digit` as a given or filled digit.
+    #[must_use]
+    pub fn digit_positions(&self, digit: Digit) -> DigitPositions {
+        self.digit_positions[digit]
+    }
+
+    /// Returns, for each digit, how many cells without a digit can still take it.
+    ///
+    /// Candidates are computed from decided digits only (see
+    /// [`Game::to_candidate_grid`]), so notes do not affect the counts.
+    #[must_use]
+    pub fn candidate_cell_count(&self) -> DigitIndexedArray<usize> {
+        let candidates = self.to_candidate_grid();
+        DigitIndexedArray::from_fn(|digit| {
+            candidates
+                .digit_positions(digit)
+                .into_iter()
+                .filter(|&pos| self.cell(pos).as_digit().is_none())
+                .count()
+        })
+    }
+
+    /// Checks the invariants every game operation keeps.
+    ///
+    /// Givens match the puzzle the game was created from and the stored
+    /// solution (where it knows the cell), notes cells hold at least one note,
+    /// cells holding a digit have no center notes, and the per-digit cell index
+    /// agrees with the grid. Debug builds check this after every mutation; it
+    /// is public so tests and fuzzers can check games they build or mutate
+    /// themselves.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first [`InvariantViolation`] found.
+    pub fn validate_invariants(&self) -> Result<(), InvariantViolation> {
         for pos in Position::ALL {
-            if let Some(digit) = self.cell(pos).as_digit() {
-                counts[digit] += 1;
+            let cell = self.grid[pos];
+            if cell.as_given() != self.givens[pos] {
+                return Err(InvariantViolation::GivenChanged { pos });
+            }
+            if let Some(digit) = cell.as_given()
+                && self
+                    .solution_digit_at(pos)
+                    .is_some_and(|expected| expected != digit)
+            {
+                return Err(InvariantViolation::GivenDiffersFromSolution { pos, digit });
+            }
+            if cell.as_notes().is_some_and(DigitSet::is_empty) {
+                return Err(InvariantViolation::EmptyNotes { pos });
             }
+            if cell.as_digit().is_some() && !self.center_notes[pos].is_empty() {
+                return Err(InvariantViolation::CenterNotesOnDigit { pos });
+            }
+        }
+        let expected = index_digit_positions(&self.grid);
+        for digit in Digit::ALL {
+            if self.digit_positions[digit] != expected[digit] {
+                return Err(InvariantViolation::DigitIndexOutOfSync { digit });
+            }
+        }
+        Ok(())
+    }
+
+    /// Panics if an invariant is broken; a no-op in release builds.
+    #[track_caller]
+    fn debug_check_invariants(&self) {
+        if cfg!(debug_assertions)
+            && let Err(err) = self.validate_invariants()
+        {
+            panic!("game invariant violated: {err}");
         }
-        counts
     }
 
     fn apply_candidate_elimination(&mut self, positions: DigitPositions, digits: DigitSet) {
         for pos in positions {
             if self.grid[pos].is_empty() {
                 let mut digits = DigitSet::FULL;
-                for peer_pos in pos.house_peers() {
+                for peer_pos in self.peers(pos) {
                     if let Some(digit) = self.grid[peer_pos].as_digit() {
                         digits.remove(digit);
                     }
@@ -599,6 +1185,7 @@ impl Game {
                 self.grid[pos].drop_note_digit(digit);
             }
         }
+        self.debug_check_invariants();
     }
 
     /// Returns whether all placements in the technique step match the stored solution.
@@ -611,7 +1198,7 @@ impl Game {
     {
         for app in step.application() {
             if let TechniqueApplication::Placement { position, digit } = app
-                && self.solution.get(position) != Some(digit)
+                && self.solution_digit_at(position) != Some(digit)
             {
                 return false;
             }
@@ -649,9 +1236,24 @@ impl Game {
     }
 }
 
+/// Builds the per-digit cell index for `grid`.
+fn index_digit_positions(
+    grid: &PositionIndexedArray<CellState>,
+) -> DigitIndexedArray<DigitPositions> {
+    let mut positions = DigitIndexedArray::from_fn(|_| DigitPositions::EMPTY);
+    for pos in Position::ALL {
+        if let Some(digit) = grid[pos].as_digit() {
+            positions[digit].insert(pos);
+        }
+    }
+    positions
+}
+
 #[cfg(test)]
 mod tests {
-    use numelace_core::{Digit, DigitGrid, DigitPositions, DigitSet, Position};
+    use numelace_core::{
+        Cage, Digit, DigitGrid, DigitPositions, DigitSet, EdgeConstraint, EdgeKind, Position,
+    };
     use numelace_generator::PuzzleGenerator;
     use numelace_solver::{BoxedTechniqueStep, ConditionDigitPositions};
 
@@ -710,10 +1312,36 @@ mod tests {
             .expect("valid filled grid");
         assert!(matches!(
             Game::from_problem_filled_notes(&problem, &solution, &conflict, &[[0; 9]; 9]),
-            Err(GameError::CannotModifyGivenCell)
+            Err(GameError::CannotModifyGivenCell { .. })
         ));
     }
 
+    #[test]
+    fn test_from_problem_rejects_givens_contradicting_solution() {
+        let problem: DigitGrid = format!("2{}", ".".repeat(80))
+            .parse()
+            .expect("valid problem grid");
+        let solution = test_solution_grid();
+
+        assert_eq!(
+            Game::from_problem_filled_notes(&problem, &solution, &DigitGrid::new(), &[[0; 9]; 9]),
+            Err(GameError::GivenDiffersFromSolution {
+                pos: Position::new(0, 0),
+                digit: Digit::D2,
+            })
+        );
+        // Without a known solution, any givens are accepted.
+        assert!(
+            Game::from_problem_filled_notes(
+                &problem,
+                &DigitGrid::new(),
+                &DigitGrid::new(),
+                &[[0; 9]; 9]
+            )
+            .is_ok()
+        );
+    }
+
     #[test]
     fn test_set_digit_basic_operations() {
         use numelace_solver::TechniqueSolver;
@@ -761,8 +1389,9 @@ mod tests {
             .iter()
             .find(|&&pos| game.cell(pos).is_empty())
             .expect("puzzle has empty cells");
-        let peer_pos = empty_pos
-            .house_peers()
+        let peer_pos = game
+            .regions()
+            .house_peers(empty_pos)
             .into_iter()
             .find(|pos| game.cell(*pos).is_empty())
             .expect("house has an empty peer");
@@ -796,8 +1425,9 @@ mod tests {
             .iter()
             .find(|&&pos| game.cell(pos).is_empty())
             .expect("puzzle has empty cells");
-        let peer_pos = empty_pos
-            .house_peers()
+        let peer_pos = game
+            .regions()
+            .house_peers(empty_pos)
             .into_iter()
             .find(|pos| game.cell(*pos).is_empty())
             .expect("house has an empty peer");
@@ -834,8 +1464,9 @@ mod tests {
             .iter()
             .find(|&&pos| game.cell(pos).is_empty())
             .expect("puzzle has empty cells");
-        let mut peer_positions = empty_pos
-            .house_peers()
+        let mut peer_positions = game
+            .regions()
+            .house_peers(empty_pos)
             .into_iter()
             .filter(|pos| game.cell(*pos).is_empty());
         let conflict_pos = peer_positions
@@ -863,7 +1494,13 @@ mod tests {
                 .note_cleanup_policy(NoteCleanupPolicy::RemovePeers),
         );
 
-        assert!(matches!(result, Err(GameError::ConflictingDigit)));
+        assert_eq!(
+            result,
+            Err(GameError::ConflictingDigit {
+                pos: empty_pos,
+                digit: Digit::D5
+            })
+        );
         assert!(matches!(
             game.cell(note_pos),
             CellState::Notes(notes) if notes.contains(Digit::D5)
@@ -897,6 +1534,61 @@ mod tests {
         assert_eq!(game.cell(empty_pos), &CellState::Empty);
     }
 
+    #[test]
+    fn test_center_notes_are_kept_apart_from_corner_notes() {
+        let problem: DigitGrid = format!("1{}", ".".repeat(80)).parse().unwrap();
+        let solution = test_solution_grid();
+        let mut game =
+            Game::from_problem_filled_notes(&problem, &solution, &DigitGrid::new(), &[[0; 9]; 9])
+                .unwrap();
+        let pos = Position::new(0, 1);
+        let peer = Position::new(0, 2);
+
+        game.toggle_note(pos, Digit::D3, RuleCheckPolicy::Permissive)
+            .unwrap();
+        for cell in [pos, peer] {
+            game.toggle_center_note(cell, Digit::D2, RuleCheckPolicy::Permissive)
+                .unwrap();
+        }
+        assert_eq!(game.center_notes(pos), DigitSet::from_elem(Digit::D2));
+        assert_eq!(
+            game.cell(pos).as_notes(),
+            Some(DigitSet::from_elem(Digit::D3))
+        );
+        assert!(game.has_removable_input(peer));
+
+        // Givens and strict conflicts are rejected like corner notes.
+        assert_eq!(
+            game.toggle_center_note(Position::new(0, 0), Digit::D2, RuleCheckPolicy::Permissive),
+            Err(GameError::CannotModifyGivenCell {
+                pos: Position::new(0, 0)
+            })
+        );
+        assert_eq!(
+            game.toggle_center_note(pos, Digit::D1, RuleCheckPolicy::Strict),
+            Err(GameError::ConflictingDigit {
+                pos,
+                digit: Digit::D1
+            })
+        );
+
+        // Placing a digit drops the cell's center notes and the peers' copies.
+        let options =
+            InputDigitOptions::default().note_cleanup_policy(NoteCleanupPolicy::RemovePeers);
+        game.set_digit(pos, Digit::D2, &options).unwrap();
+        assert!(game.center_notes(pos).is_empty());
+        assert!(game.center_notes(peer).is_empty());
+        assert_eq!(
+            game.toggle_center_note(pos, Digit::D2, RuleCheckPolicy::Permissive),
+            Err(GameError::CannotAddNoteToFilledCell { pos })
+        );
+
+        game.toggle_center_note(peer, Digit::D4, RuleCheckPolicy::Permissive)
+            .unwrap();
+        game.clear_cell(peer).unwrap();
+        assert!(!game.has_removable_input(peer));
+    }
+
     #[test]
     fn test_auto_fill_cell_notes_sets_candidates() {
         let problem: DigitGrid = "\
@@ -926,7 +1618,8 @@ mod tests {
         .parse()
         .expect("valid filled grid");
 
-        let solution = test_solution_grid();
+        // The givens do not follow any solution, so leave it unknown.
+        let solution = DigitGrid::new();
         let mut game =
             Game::from_problem_filled_notes(&problem, &solution, &filled, &[[0; 9]; 9]).unwrap();
         let pos = Position::new(0, 0);
@@ -945,6 +1638,133 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_clear_mistakes_keeps_correct_digits_and_notes() {
+        let problem = DigitGrid::new();
+        let mut filled = DigitGrid::new();
+        filled.set(Position::new(0, 0), Some(Digit::D1));
+        filled.set(Position::new(0, 1), Some(Digit::D9));
+        let solution = test_solution_grid();
+        let mut game =
+            Game::from_problem_filled_notes(&problem, &solution, &filled, &[[0; 9]; 9]).unwrap();
+        game.toggle_note(Position::new(0, 2), Digit::D5, RuleCheckPolicy::Permissive)
+            .unwrap();
+
+        assert!(game.has_solution());
+        assert_eq!(
+            game.mistake_positions(),
+            DigitPositions::from_elem(Position::new(0, 1))
+        );
+        assert_eq!(game.clear_mistakes(), 1);
+        assert_eq!(
+            game.cell(Position::new(0, 0)),
+            &CellState::Filled(Digit::D1)
+        );
+        assert_eq!(game.cell(Position::new(0, 1)), &CellState::Empty);
+        assert!(game.cell(Position::new(0, 2)).as_notes().is_some());
+        assert!(
+            !game
+                .digit_positions(Digit::D9)
+                .contains(Position::new(0, 1))
+        );
+        assert_eq!(game.clear_mistakes(), 0);
+        assert!(game.mistake_positions().is_empty());
+        assert!(!Game::new_empty().has_solution());
+    }
+
+    #[test]
+    fn test_legal_digits_at_excludes_house_and_chess_peers() {
+        let problem = DigitGrid::new();
+        let mut filled = DigitGrid::new();
+        filled.set(Position::new(0, 8), Some(Digit::D1));
+        filled.set(Position::new(8, 0), Some(Digit::D2));
+        filled.set(Position::new(2, 1), Some(Digit::D3));
+        let solution = test_solution_grid();
+        let game = Game::from_problem_filled_notes(&problem, &solution, &filled, &[[0; 9]; 9])
+            .unwrap()
+            .with_chess_rules(ChessRules::new().with_anti_knight(true));
+
+        let pos = Position::new(0, 0);
+        // Row, column, and box peers hold 1, 2, and 3.
+        assert_eq!(
+            game.legal_digits_at(pos),
+            !DigitSet::from_iter([Digit::D1, Digit::D2, Digit::D3])
+        );
+        // A knight's move away from (2, 1), outside its houses.
+        assert!(
+            !game
+                .legal_digits_at(Position::new(4, 2))
+                .contains(Digit::D3)
+        );
+        assert_eq!(
+            game.legal_digits_at(pos),
+            game.to_candidate_grid().candidates_at(pos)
+        );
+
+        assert_eq!(game.solution_digit_at(pos), Some(Digit::D1));
+        assert_eq!(Game::new_empty().solution_digit_at(pos), None);
+    }
+
+    #[test]
+    fn test_illegal_notes_lists_notes_seen_by_peers() {
+        let mut problem = DigitGrid::new();
+        problem.set(Position::new(0, 0), Some(Digit::D1));
+        let mut filled = DigitGrid::new();
+        filled.set(Position::new(4, 4), Some(Digit::D2));
+        let mut notes = [[0; 9]; 9];
+        // Row peer of the given 1, column peer of the filled 2, and an unrelated cell.
+        notes[0][8] = 0b0000_0011;
+        notes[8][4] = 0b0000_0110;
+        notes[8][8] = 0b0000_0011;
+        let mut game =
+            Game::from_problem_filled_notes(&problem, &DigitGrid::new(), &filled, &notes).unwrap();
+
+        assert_eq!(
+            game.illegal_notes(),
+            vec![
+                (Position::new(0, 8), DigitSet::from_iter([Digit::D1])),
+                (Position::new(8, 4), DigitSet::from_iter([Digit::D2])),
+            ]
+        );
+        assert_eq!(game.remove_illegal_notes(), 2);
+        assert_eq!(game.illegal_notes(), vec![]);
+        assert_eq!(
+            game.cell(Position::new(8, 8)).as_notes(),
+            Some(DigitSet::from_iter([Digit::D1, Digit::D2]))
+        );
+    }
+
+    #[test]
+    fn test_reveal_cell_replaces_wrong_digit_and_skips_givens() {
+        let mut problem = DigitGrid::new();
+        problem.set(Position::new(8, 8), Some(Digit::D2));
+        let mut filled = DigitGrid::new();
+        filled.set(Position::new(0, 1), Some(Digit::D9));
+        let solution = test_solution_grid();
+        let mut game =
+            Game::from_problem_filled_notes(&problem, &solution, &filled, &[[0; 9]; 9]).unwrap();
+        let options = InputDigitOptions::default().rule_check_policy(RuleCheckPolicy::Strict);
+
+        let pos = Position::new(0, 1);
+        assert_eq!(game.reveal_cell_capability(pos), Ok(InputOperation::Set));
+        assert_eq!(game.reveal_cell(pos, &options), Ok(InputOperation::Set));
+        assert_eq!(game.cell(pos).as_digit(), solution[pos]);
+        assert!(!game.digit_positions(Digit::D9).contains(pos));
+        assert_eq!(game.reveal_cell(pos, &options), Ok(InputOperation::NoOp));
+        assert_eq!(game.reveal_cell_capability(pos), Ok(InputOperation::NoOp));
+        assert_eq!(
+            game.reveal_cell_capability(Position::new(8, 8)),
+            Err(InputBlockReason::GivenCell)
+        );
+        assert!(matches!(
+            game.reveal_cell(Position::new(8, 8), &options),
+            Err(GameError::CannotModifyGivenCell { .. })
+        ));
+
+        assert_eq!(game.reveal_all(&options), 79);
+        assert!(game.is_solved());
+    }
+
     #[test]
     fn test_auto_fill_cell_notes_clears_when_no_candidates() {
         let problem: DigitGrid = "\
@@ -974,7 +1794,8 @@ mod tests {
         .parse()
         .expect("valid filled grid");
 
-        let solution = test_solution_grid();
+        // The givens do not follow any solution, so leave it unknown.
+        let solution = DigitGrid::new();
         let mut game =
             Game::from_problem_filled_notes(&problem, &solution, &filled, &[[0; 9]; 9]).unwrap();
         let pos = Position::new(0, 0);
@@ -998,6 +1819,10 @@ mod tests {
                 "MatchStep"
             }
 
+            fn technique_key(&self) -> &'static str {
+                "match_step"
+            }
+
             fn clone_box(&self) -> BoxedTechniqueStep {
                 Box::new(Self)
             }
@@ -1026,6 +1851,10 @@ mod tests {
                 "MismatchStep"
             }
 
+            fn technique_key(&self) -> &'static str {
+                "mismatch_step"
+            }
+
             fn clone_box(&self) -> BoxedTechniqueStep {
                 Box::new(Self)
             }
@@ -1069,6 +1898,10 @@ mod tests {
                 "Test"
             }
 
+            fn technique_key(&self) -> &'static str {
+                "test"
+            }
+
             fn clone_box(&self) -> BoxedTechniqueStep {
                 Box::new(Self)
             }
@@ -1113,6 +1946,57 @@ mod tests {
         assert_eq!(game.cell(Position::new(0, 1)), &CellState::Empty);
     }
 
+    #[test]
+    fn test_solution_policy_rejects_wrong_digits_but_not_notes() {
+        let solution = test_solution_grid();
+        let mut game = Game::from_problem_filled_notes(
+            &DigitGrid::new(),
+            &solution,
+            &DigitGrid::new(),
+            &[[0; 9]; 9],
+        )
+        .unwrap();
+        let options = InputDigitOptions::default().rule_check_policy(RuleCheckPolicy::Solution);
+        let pos = Position::new(0, 0);
+        let answer = solution[pos].unwrap();
+        let wrong = if answer == Digit::D9 {
+            Digit::D8
+        } else {
+            Digit::D9
+        };
+
+        assert_eq!(
+            game.set_digit_capability(pos, wrong, RuleCheckPolicy::Solution),
+            Err(InputBlockReason::WrongDigit)
+        );
+        assert_eq!(
+            game.set_digit_capability(pos, wrong, RuleCheckPolicy::Solution.rules_only()),
+            Ok(InputOperation::Set)
+        );
+        assert_eq!(
+            game.set_digit(pos, wrong, &options),
+            Err(GameError::WrongDigit { pos, digit: wrong })
+        );
+        assert_eq!(
+            game.toggle_note(pos, wrong, RuleCheckPolicy::Solution),
+            Ok(InputOperation::Set)
+        );
+        assert_eq!(
+            game.set_digit(pos, answer, &options),
+            Ok(InputOperation::Set)
+        );
+
+        // Rule conflicts are still reported as such.
+        let peer = Position::new(0, 1);
+        assert_eq!(
+            game.set_digit(peer, answer, &options),
+            Err(GameError::ConflictingDigit {
+                pos: peer,
+                digit: answer
+            })
+        );
+    }
+
     #[test]
     fn test_strict_conflict_rejects_inputs() {
         use numelace_solver::TechniqueSolver;
@@ -1144,11 +2028,11 @@ mod tests {
             Digit::D5,
             &InputDigitOptions::default().rule_check_policy(RuleCheckPolicy::Strict),
         );
-        assert!(matches!(result, Err(GameError::ConflictingDigit)));
+        assert!(matches!(result, Err(GameError::ConflictingDigit { .. })));
 
         // Notes also rejected under strict conflict when adding
         let result = game.toggle_note(second, Digit::D5, RuleCheckPolicy::Strict);
-        assert!(matches!(result, Err(GameError::ConflictingDigit)));
+        assert!(matches!(result, Err(GameError::ConflictingDigit { .. })));
 
         // Removing note is always allowed
         game.toggle_note(second, Digit::D4, RuleCheckPolicy::Permissive)
@@ -1157,6 +2041,162 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_region_layout_drives_conflicts() {
+        let layout: RegionLayout = "\
+            111122333\
+            111222333\
+            112222333\
+            444555666\
+            444555666\
+            444555666\
+            777888999\
+            777888999\
+            777888999"
+            .parse()
+            .unwrap();
+        let empty = DigitGrid::new();
+        let mut game =
+            Game::from_problem_filled_notes(&empty, &test_solution_grid(), &empty, &[[0; 9]; 9])
+                .unwrap()
+                .with_regions(layout);
+        let strict = InputDigitOptions::default().rule_check_policy(RuleCheckPolicy::Strict);
+
+        // (0, 0) and (2, 2) share a standard box but not a jigsaw region.
+        game.set_digit(Position::new(0, 0), Digit::D1, &strict)
+            .unwrap();
+        game.set_digit(Position::new(2, 2), Digit::D1, &strict)
+            .unwrap();
+
+        // (0, 4) and (2, 2) share a jigsaw region.
+        game.set_digit(Position::new(0, 4), Digit::D5, &strict)
+            .unwrap();
+        assert!(matches!(
+            game.set_digit(Position::new(2, 2), Digit::D5, &strict),
+            Err(GameError::ConflictingDigit { .. })
+        ));
+    }
+
+    #[test]
+    fn test_cage_rules_apply_to_conflicts_and_solved() {
+        let solution = test_solution_grid();
+        let empty = DigitGrid::new();
+        let cage_positions: DigitPositions = [Position::new(0, 0), Position::new(4, 4)]
+            .into_iter()
+            .collect();
+        let cage_game = |sum| {
+            let cages = CageSet::from_cages([Cage::new(cage_positions, sum).unwrap()]).unwrap();
+            Game::from_problem_filled_notes(&empty, &solution, &empty, &[[0; 9]; 9])
+                .unwrap()
+                .with_cages(cages)
+        };
+        let strict = InputDigitOptions::default().rule_check_policy(RuleCheckPolicy::Strict);
+
+        // Solution digits are 1 at (0, 0) and 7 at (4, 4).
+        let mut game = cage_game(8);
+        game.set_digit(Position::new(0, 0), Digit::D1, &strict)
+            .unwrap();
+        // The cells share no house, so only the cage rejects a repeated digit.
+        assert!(matches!(
+            game.set_digit(Position::new(4, 4), Digit::D1, &strict),
+            Err(GameError::ConflictingDigit { .. })
+        ));
+        assert!(matches!(
+            game.set_digit(Position::new(4, 4), Digit::D6, &strict),
+            Err(GameError::ConflictingDigit { .. })
+        ));
+        assert!(matches!(
+            game.toggle_note(Position::new(4, 4), Digit::D9, RuleCheckPolicy::Strict),
+            Err(GameError::ConflictingDigit { .. })
+        ));
+        game.set_digit(Position::new(4, 4), Digit::D7, &strict)
+            .unwrap();
+
+        let fill = |mut game: Game| {
+            for pos in Position::ALL {
+                game.set_digit(pos, solution[pos].unwrap(), &InputDigitOptions::default())
+                    .unwrap();
+            }
+            game
+        };
+        assert!(fill(cage_game(8)).is_solved());
+        assert!(!fill(cage_game(9)).is_solved());
+    }
+
+    #[test]
+    fn test_edge_constraints_apply_to_conflicts_and_solved() {
+        let solution = test_solution_grid();
+        let empty = DigitGrid::new();
+        let edge_game = |kind| {
+            let edge = EdgeConstraint::new(Position::new(0, 0), Position::new(0, 1), kind).unwrap();
+            Game::from_problem_filled_notes(&empty, &solution, &empty, &[[0; 9]; 9])
+                .unwrap()
+                .with_edge_constraints(EdgeConstraintSet::from_constraints([edge]).unwrap())
+        };
+        let strict = InputDigitOptions::default().rule_check_policy(RuleCheckPolicy::Strict);
+
+        let mut game = edge_game(EdgeKind::LessThan);
+        game.set_digit(Position::new(0, 1), Digit::D2, &strict)
+            .unwrap();
+        assert!(matches!(
+            game.set_digit(Position::new(0, 0), Digit::D3, &strict),
+            Err(GameError::ConflictingDigit { .. })
+        ));
+        game.set_digit(Position::new(0, 0), Digit::D1, &strict)
+            .unwrap();
+
+        // Solution digits are 1 at (0, 0) and 8 at (0, 1).
+        let fill = |mut game: Game| {
+            for pos in Position::ALL {
+                game.set_digit(pos, solution[pos].unwrap(), &InputDigitOptions::default())
+                    .unwrap();
+            }
+            game
+        };
+        assert!(fill(edge_game(EdgeKind::LessThan)).is_solved());
+        assert!(!fill(edge_game(EdgeKind::GreaterThan)).is_solved());
+    }
+
+    #[test]
+    fn test_chess_rules_apply_to_conflicts() {
+        let solution = test_solution_grid();
+        let empty = DigitGrid::new();
+        let mut game = Game::from_problem_filled_notes(&empty, &solution, &empty, &[[0; 9]; 9])
+            .unwrap()
+            .with_chess_rules(ChessRules::new().with_anti_knight(true));
+        let strict = InputDigitOptions::default().rule_check_policy(RuleCheckPolicy::Strict);
+
+        game.set_digit(Position::new(2, 2), Digit::D5, &strict)
+            .unwrap();
+        // (4, 3) is a knight's move away from (2, 2).
+        assert!(matches!(
+            game.set_digit(Position::new(4, 3), Digit::D5, &strict),
+            Err(GameError::ConflictingDigit { .. })
+        ));
+        // (3, 3) is only a king's move away.
+        game.set_digit(Position::new(3, 3), Digit::D5, &strict)
+            .unwrap();
+
+        // Notes a knight's move away are cleaned up and excluded from auto-fill.
+        game.toggle_note(Position::new(4, 1), Digit::D5, RuleCheckPolicy::Permissive)
+            .unwrap();
+        let cleanup =
+            InputDigitOptions::default().note_cleanup_policy(NoteCleanupPolicy::RemovePeers);
+        game.set_digit(Position::new(2, 2), Digit::D6, &cleanup)
+            .unwrap();
+        game.set_digit(Position::new(2, 2), Digit::D5, &cleanup)
+            .unwrap();
+        assert!(game.cell(Position::new(4, 1)).is_empty());
+        game.auto_fill_cell_notes(Position::new(1, 4)).unwrap();
+        assert!(
+            !game
+                .cell(Position::new(1, 4))
+                .as_notes()
+                .unwrap()
+                .contains(Digit::D5)
+        );
+    }
+
     #[test]
     fn test_cannot_modify_given_cells() {
         use numelace_solver::TechniqueSolver;
@@ -1170,17 +2210,17 @@ mod tests {
             .find(|&pos| game.cell(pos).is_given())
             .expect("puzzle has given cells");
 
-        assert!(matches!(
+        assert_eq!(
             game.set_digit(given_pos, Digit::D1, &InputDigitOptions::default()),
-            Err(GameError::CannotModifyGivenCell)
-        ));
+            Err(GameError::CannotModifyGivenCell { pos: given_pos })
+        );
         assert!(matches!(
             game.toggle_note(given_pos, Digit::D1, RuleCheckPolicy::Permissive),
-            Err(GameError::CannotModifyGivenCell)
+            Err(GameError::CannotModifyGivenCell { .. })
         ));
         assert!(matches!(
             game.clear_cell(given_pos),
-            Err(GameError::CannotModifyGivenCell)
+            Err(GameError::CannotModifyGivenCell { .. })
         ));
     }
 
@@ -1285,6 +2325,186 @@ mod tests {
         assert_eq!(counts[Digit::D5], d5_before + 2);
     }
 
+    #[test]
+    fn test_completed_digits_require_nine_correct_cells() {
+        let solution = test_solution_grid();
+        let mut problem = solution.clone();
+        let ones = Position::ALL
+            .into_iter()
+            .filter(|&pos| solution[pos] == Some(Digit::D1));
+        let twos = Position::ALL
+            .into_iter()
+            .filter(|&pos| solution[pos] == Some(Digit::D2));
+        for pos in ones.clone().chain(twos.clone()) {
+            problem.set(pos, None);
+        }
+        let mut game =
+            Game::from_problem_filled_notes(&problem, &solution, &DigitGrid::new(), &[[0; 9]; 9])
+                .expect("compatible grids");
+        let options = InputDigitOptions::default();
+        assert!(!game.completed_digits().contains(Digit::D1));
+        assert!(game.completed_digits().contains(Digit::D9));
+
+        let ones: Vec<_> = ones.collect();
+        for &pos in &ones[..8] {
+            game.set_digit(pos, Digit::D1, &options).unwrap();
+        }
+        // A ninth 1 in the wrong cell does not complete the digit.
+        let wrong = twos.clone().next().unwrap();
+        game.set_digit(wrong, Digit::D1, &options).unwrap();
+        assert_eq!(game.decided_digit_count()[Digit::D1], 9);
+        assert!(!game.completed_digits().contains(Digit::D1));
+
+        game.clear_cell(wrong).unwrap();
+        game.set_digit(ones[8], Digit::D1, &options).unwrap();
+        assert!(game.completed_digits().contains(Digit::D1));
+        assert!(!game.completed_digits().contains(Digit::D2));
+        assert!(Game::new_empty().completed_digits().is_empty());
+    }
+
+    #[test]
+    fn test_candidate_cell_count_excludes_decided_cells_and_peers() {
+        let problem: DigitGrid = format!("1{}", ".".repeat(80))
+            .parse()
+            .expect("valid problem grid");
+        let solution = test_solution_grid();
+        let filled: DigitGrid = format!(".2{}", ".".repeat(79))
+            .parse()
+            .expect("valid filled grid");
+        let game = Game::from_problem_filled_notes(&problem, &solution, &filled, &[[0; 9]; 9])
+            .expect("compatible grids");
+
+        let counts = game.candidate_cell_count();
+        // 79 undecided cells, minus the 19 undecided peers of the given 1.
+        assert_eq!(counts[Digit::D1], 60);
+        assert_eq!(counts[Digit::D9], 79);
+    }
+
+    #[test]
+    fn test_digit_positions_follow_set_and_clear() {
+        let problem: DigitGrid = format!("1{}", ".".repeat(80))
+            .parse()
+            .expect("valid problem grid");
+        let mut game = Game::from_problem_filled_notes(
+            &problem,
+            &test_solution_grid(),
+            &DigitGrid::new(),
+            &[[0; 9]; 9],
+        )
+        .expect("compatible grids");
+        let given = Position::new(0, 0);
+        let same_row = Position::new(0, 5);
+        let elsewhere = Position::new(4, 4);
+        let options = InputDigitOptions::default();
+
+        game.set_digit(same_row, Digit::D1, &options).unwrap();
+        game.set_digit(elsewhere, Digit::D1, &options).unwrap();
+        assert_eq!(
+            game.digit_positions(Digit::D1),
+            DigitPositions::from_iter([given, same_row, elsewhere])
+        );
+        assert_eq!(game.decided_digit_count()[Digit::D1], 3);
+
+        game.set_digit(same_row, Digit::D2, &options).unwrap();
+        assert_eq!(
+            game.digit_positions(Digit::D1),
+            DigitPositions::from_iter([given, elsewhere])
+        );
+        assert_eq!(
+            game.digit_positions(Digit::D2),
+            DigitPositions::from_elem(same_row)
+        );
+
+        game.clear_cell(same_row).unwrap();
+        assert!(game.digit_positions(Digit::D2).is_empty());
+        assert_eq!(game.decided_digit_count()[Digit::D2], 0);
+    }
+
+    #[test]
+    fn test_cell_iteration_and_position_sets() {
+        let problem: DigitGrid = format!("1{}", ".".repeat(80))
+            .parse()
+            .expect("valid problem grid");
+        let mut game = Game::from_problem_filled_notes(
+            &problem,
+            &test_solution_grid(),
+            &DigitGrid::new(),
+            &[[0; 9]; 9],
+        )
+        .expect("compatible grids");
+        let given = Position::new(0, 0);
+        let filled = Position::new(4, 4);
+        let noted = Position::new(8, 8);
+        game.set_digit(filled, Digit::D3, &InputDigitOptions::default())
+            .unwrap();
+        game.toggle_note(noted, Digit::D2, RuleCheckPolicy::Permissive)
+            .unwrap();
+
+        let cells = game.iter_cells().collect::<Vec<_>>();
+        assert_eq!(cells.len(), 81);
+        assert_eq!(cells[0], (given, &CellState::Given(Digit::D1)));
+        assert_eq!(cells[40], (filled, &CellState::Filled(Digit::D3)));
+
+        assert_eq!(game.given_positions(), DigitPositions::from_elem(given));
+        assert_eq!(game.filled_positions(), DigitPositions::from_elem(filled));
+        let empty = game.empty_positions();
+        assert_eq!(empty.len(), 79);
+        assert!(empty.contains(noted));
+        assert!(!empty.contains(given) && !empty.contains(filled));
+    }
+
+    #[test]
+    fn test_validate_invariants_reports_broken_state() {
+        let problem: DigitGrid = format!("1{}", ".".repeat(80))
+            .parse()
+            .expect("valid problem grid");
+        let game = Game::from_problem_filled_notes(
+            &problem,
+            &test_solution_grid(),
+            &DigitGrid::new(),
+            &[[0; 9]; 9],
+        )
+        .expect("compatible grids");
+        assert_eq!(game.validate_invariants(), Ok(()));
+        assert_eq!(Game::new_empty().validate_invariants(), Ok(()));
+
+        let given = Position::new(0, 0);
+        let empty = Position::new(4, 4);
+
+        let mut broken = game.clone();
+        broken.grid[given] = CellState::Filled(Digit::D1);
+        assert_eq!(
+            broken.validate_invariants(),
+            Err(InvariantViolation::GivenChanged { pos: given })
+        );
+
+        let mut broken = game.clone();
+        broken.givens.set(empty, Some(Digit::D2));
+        broken.grid[empty] = CellState::Given(Digit::D2);
+        broken.digit_positions[Digit::D2].insert(empty);
+        assert_eq!(
+            broken.validate_invariants(),
+            Err(InvariantViolation::GivenDiffersFromSolution {
+                pos: empty,
+                digit: Digit::D2
+            })
+        );
+
+        let mut broken = game.clone();
+        broken.grid[empty] = CellState::Notes(DigitSet::new());
+        assert_eq!(
+            broken.validate_invariants(),
+            Err(InvariantViolation::EmptyNotes { pos: empty })
+        );
+
+        let mut broken = game;
+        broken.grid[empty] = CellState::Filled(Digit::D3);
+        assert_eq!(
+            broken.validate_invariants(),
+            Err(InvariantViolation::DigitIndexOutOfSync { digit: Digit::D3 })
+        );
+    }
+
     #[test]
     fn test_is_solved_with_complete_solution() {
         use numelace_solver::TechniqueSolver;
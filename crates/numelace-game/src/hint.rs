@@ -0,0 +1,256 @@
+//! Hint search for the next step of a game.
+
+use numelace_core::{DigitPositions, Position};
+use numelace_solver::{
+    BoxedTechniqueStep, SolverBudget, SolverError, TechniqueGrid, TechniquePriority,
+    TechniqueSolver, TechniqueTier,
+    technique::{CageCombination, EdgeRelation, FullHouse, NakedSingle, TechniqueId},
+};
+
+use crate::{Game, HintError};
+
+/// Next step found by [`Game::find_hint`].
+#[derive(Debug, Clone)]
+pub struct Hint {
+    /// The step to show.
+    pub step: BoxedTechniqueStep,
+    /// Whether the step was found from the player's notes.
+    ///
+    /// When the notes are stale or wrong, the step is found from the placed
+    /// digits alone, and the player should refill the notes before using it.
+    pub with_notes: bool,
+}
+
+impl Game {
+    /// Finds the next step to show as a hint, trying techniques in the order
+    /// of `priority`.
+    ///
+    /// The search first uses the player's notes, and falls back to the
+    /// candidates of the placed digits when the notes are inconsistent or
+    /// lead to a wrong digit. Killer cages and edge markers add their variant
+    /// techniques right after the fundamental ones. Steps that place a digit
+    /// are checked against the solution.
+    ///
+    /// Returns `Ok(None)` if no technique applies.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`HintError::Inconsistent`] or [`HintError::SolutionMismatch`]
+    /// if the placed digits are wrong, or [`HintError::Undetermined`] if the
+    /// search runs out of `budget`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use numelace_core::DigitGrid;
+    /// use numelace_game::Game;
+    /// use numelace_solver::{SolverBudget, TechniquePriority};
+    ///
+    /// let solution: DigitGrid =
+    ///     "185362947793148526246795183564239871931874265827516394318427659672951438459683712"
+    ///         .parse()?;
+    /// let problem: DigitGrid =
+    ///     ".85362947793148526246795183564239871931874265827516394318427659672951438459683712"
+    ///         .parse()?;
+    /// let game =
+    ///     Game::from_problem_filled_notes(&problem, &solution, &DigitGrid::new(), &[[0; 9]; 9])?;
+    ///
+    /// let hint = game.find_hint(&TechniquePriority::new(), SolverBudget::UNLIMITED)?;
+    /// assert!(hint.is_some_and(|hint| hint.with_notes));
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn find_hint(
+        &self,
+        priority: &TechniquePriority,
+        budget: SolverBudget,
+    ) -> Result<Option<Hint>, HintError> {
+        let solver = hint_solver(self, priority).with_budget(budget);
+        let grid_with_notes = TechniqueGrid::from(self.to_candidate_grid_with_notes());
+
+        // Notes-derived grids can be stale; treat inconsistency or solution
+        // mismatch as a signal to fall back to the no-notes grid before
+        // surfacing an error.
+        match find_hint_step_from_grid(self, &grid_with_notes, &solver) {
+            Ok(Some(step)) => {
+                return Ok(Some(Hint {
+                    step,
+                    with_notes: true,
+                }));
+            }
+            Ok(None) | Err(HintError::Inconsistent(_) | HintError::SolutionMismatch) => {}
+            Err(HintError::Undetermined) => return Err(HintError::Undetermined),
+        }
+
+        let grid = TechniqueGrid::from(self.to_candidate_grid());
+        let step = find_hint_step_from_grid(self, &grid, &solver)?;
+        Ok(step.map(|step| Hint {
+            step,
+            with_notes: false,
+        }))
+    }
+}
+
+fn find_full_house_hint(game: &Game, grid: &TechniqueGrid) -> Option<BoxedTechniqueStep> {
+    // The candidate grid cannot tell placed digits from cells with one candidate left,
+    // and it already narrows the last cell of a full house down to a single candidate.
+    let decided: DigitPositions = Position::ALL
+        .into_iter()
+        .filter(|&pos| game.cell(pos).as_digit().is_some())
+        .collect();
+    Position::ALL
+        .into_iter()
+        .filter(|&pos| !decided.contains(pos))
+        .find_map(|pos| FullHouse::build_step(grid, decided, pos))
+}
+
+fn find_naked_single_hint(game: &Game, grid: &TechniqueGrid) -> Option<BoxedTechniqueStep> {
+    // Naked single hints must consider placement validity even when no eliminations occur.
+    // The solver's NakedSingle::find_step intentionally gates on eliminations, which can
+    // skip valid placements once peers already lack that candidate.
+    Position::ALL
+        .into_iter()
+        .filter(|&pos| game.cell(pos).as_digit().is_none())
+        .find_map(|pos| NakedSingle::build_step(grid, pos))
+}
+
+fn find_hint_step_from_grid(
+    game: &Game,
+    grid: &TechniqueGrid,
+    solver: &TechniqueSolver,
+) -> Result<Option<BoxedTechniqueStep>, HintError> {
+    grid.check_consistency()?;
+    // The candidate grid only follows the classic rules, so variant rules that
+    // can no longer be met, like cages that cannot add up, are found by
+    // pruning a scratch copy.
+    let mut pruned = grid.clone();
+    for constraint in game.constraints() {
+        pruned.prune_with(constraint)?;
+    }
+
+    // Only take the single shortcuts when the priority still tries them first.
+    let leading: Vec<_> = solver
+        .techniques()
+        .iter()
+        .take(2)
+        .map(|tech| tech.technique_id())
+        .collect();
+    let full_house_first = leading.first() == Some(&Some(TechniqueId::FullHouse));
+    let naked_single_next =
+        leading.get(usize::from(full_house_first)) == Some(&Some(TechniqueId::NakedSingle));
+    let shortcut = full_house_first
+        .then(|| find_full_house_hint(game, grid))
+        .flatten()
+        .or_else(|| {
+            naked_single_next
+                .then(|| find_naked_single_hint(game, grid))
+                .flatten()
+        });
+    let step = match shortcut {
+        Some(step) => Some(step),
+        None => solver.find_step(grid).map_err(|err| match err {
+            SolverError::Inconsistent(consistency) => HintError::Inconsistent(consistency),
+            SolverError::BudgetExceeded => HintError::Undetermined,
+        })?,
+    };
+
+    match step {
+        Some(step) if !game.verify_hint_step(step.as_ref()) => Err(HintError::SolutionMismatch),
+        step => Ok(step),
+    }
+}
+
+fn hint_solver(game: &Game, priority: &TechniquePriority) -> TechniqueSolver {
+    if game.cages().is_empty() && game.edge_constraints().is_empty() {
+        return TechniqueSolver::with_priority(priority);
+    }
+    // Variant techniques are cheap and basic, so try them right after the leading singles.
+    let mut techniques = priority.techniques();
+    let index = techniques
+        .iter()
+        .take_while(|tech| tech.tier() == TechniqueTier::Fundamental)
+        .count();
+    if !game.edge_constraints().is_empty() {
+        techniques.insert(
+            index,
+            Box::new(EdgeRelation::new(game.edge_constraints().clone())),
+        );
+    }
+    if !game.cages().is_empty() {
+        techniques.insert(index, Box::new(CageCombination::new(game.cages().clone())));
+    }
+    TechniqueSolver::new(techniques)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use numelace_core::{Cage, CageSet, DigitGrid};
+
+    use super::*;
+
+    fn game_with_notes(notes: &[[u16; 9]; 9]) -> Game {
+        let solution: DigitGrid =
+            "185362947793148526246795183564239871931874265827516394318427659672951438459683712"
+                .parse()
+                .unwrap();
+        let mut problem = solution.clone();
+        problem.set(Position::new(0, 1), None);
+        let empty = DigitGrid::new();
+        Game::from_problem_filled_notes(&problem, &solution, &empty, notes).unwrap()
+    }
+
+    fn game() -> Game {
+        game_with_notes(&[[0; 9]; 9])
+    }
+
+    #[test]
+    fn hint_reports_an_exhausted_budget() {
+        let game = game();
+        // Leading with a hidden single skips the single shortcuts, so the solver runs.
+        let priority = TechniquePriority::new().with_order([TechniqueId::HiddenSingle]);
+
+        assert!(game.find_hint(&priority, SolverBudget::UNLIMITED).is_ok());
+        assert!(matches!(
+            game.find_hint(
+                &priority,
+                SolverBudget::new().with_max_duration(Duration::ZERO)
+            ),
+            Err(HintError::Undetermined)
+        ));
+    }
+
+    #[test]
+    fn hint_reports_a_cage_that_cannot_add_up() {
+        // r1c1 is a given 1, so the cage needs a 9 in r1c2, where only 8 fits.
+        let cells = DigitPositions::from_iter([Position::new(0, 0), Position::new(0, 1)]);
+        let cages = CageSet::from_cages([Cage::new(cells, 10).unwrap()]).unwrap();
+        let priority = TechniquePriority::new();
+
+        assert!(matches!(
+            game().find_hint(&priority, SolverBudget::UNLIMITED),
+            Ok(Some(_))
+        ));
+        assert!(matches!(
+            game()
+                .with_cages(cages)
+                .find_hint(&priority, SolverBudget::UNLIMITED),
+            Err(HintError::Inconsistent(_))
+        ));
+    }
+
+    #[test]
+    fn hint_falls_back_from_wrong_notes() {
+        // A note that leaves only a wrong digit, 9, in the open cell r1c2.
+        let mut notes = [[0; 9]; 9];
+        notes[0][1] = 1 << 8;
+        let game = game_with_notes(&notes);
+
+        let hint = game
+            .find_hint(&TechniquePriority::new(), SolverBudget::UNLIMITED)
+            .unwrap()
+            .unwrap();
+        assert!(!hint.with_notes);
+        assert!(game.verify_hint_step(hint.step.as_ref()));
+    }
+}
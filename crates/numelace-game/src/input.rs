@@ -42,6 +42,38 @@ pub enum RuleCheckPolicy {
     Permissive,
     /// Reject inputs that conflict with existing digits.
     Strict,
+    /// Reject inputs that conflict with existing digits, and digits that differ
+    /// from the stored solution.
+    ///
+    /// Notes are only checked against the rules, since they record candidates
+    /// rather than answers.
+    Solution,
+}
+
+impl RuleCheckPolicy {
+    /// Returns `true` if inputs conflicting with existing digits are rejected.
+    #[must_use]
+    pub const fn checks_rules(self) -> bool {
+        !self.is_permissive()
+    }
+
+    /// Returns `true` if digits differing from the stored solution are rejected.
+    #[must_use]
+    pub const fn checks_solution(self) -> bool {
+        self.is_solution()
+    }
+
+    /// Returns this policy without the solution check.
+    ///
+    /// Useful for showing which inputs the rules allow without giving away
+    /// the answer.
+    #[must_use]
+    pub const fn rules_only(self) -> Self {
+        match self {
+            Self::Solution => Self::Strict,
+            policy => policy,
+        }
+    }
 }
 
 /// Controls how notes are cleaned up after digit entry.
@@ -74,4 +106,7 @@ pub enum InputBlockReason {
     FilledCell,
     /// The input conflicts with an existing digit under strict rules.
     Conflict,
+    /// The digit differs from the stored solution under
+    /// [`RuleCheckPolicy::Solution`].
+    WrongDigit,
 }
@@ -23,6 +23,8 @@
 //!   and there are no rule violations (accepts any valid solution).
 //! - **Cell state tracking**: Uses [`CellState`] enum to distinguish between given,
 //!   filled, and empty cells at the type level.
+//! - **Invariant checks**: Debug builds run [`Game::validate_invariants`] after
+//!   every mutation, so an operation that corrupts the state fails where it happens.
 //!
 //! # Examples
 //!
@@ -61,11 +63,14 @@
 mod cell_state;
 mod error;
 mod game;
+mod hint;
 mod input;
+mod state_code;
 
 pub use cell_state::CellState;
-pub use error::GameError;
+pub use error::{GameError, HintError, InvariantViolation, StateCodeError};
 pub use game::Game;
+pub use hint::Hint;
 pub use input::{
     InputBlockReason, InputDigitOptions, InputOperation, NoteCleanupPolicy, RuleCheckPolicy,
 };
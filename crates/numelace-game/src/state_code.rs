@@ -0,0 +1,466 @@
+//! Compact, URL-safe string encoding of a whole game.
+//!
+//! The game is packed into a bit stream and encoded with the unpadded
+//! base64url alphabet, so a classic puzzle fits in about 70 characters. The
+//! same code backs saved games, share links, and copied puzzles.
+//!
+//! Layout (all values most-significant bit first):
+//!
+//! - version (4 bits), then flags (6 bits): anti-knight, anti-king, progress,
+//!   irregular regions, cages, edges
+//! - solution: 81 digits, 4 bits each, 0 where the solution is unknown
+//! - givens: 81-bit mask, then a digit (4 bits) for every given whose
+//!   solution digit is unknown
+//! - regions (if flagged): 81 region indices, 4 bits each
+//! - cages (if flagged): count (7 bits), then per cage a sum (6 bits) and an
+//!   81-bit position mask
+//! - edges (if flagged): count (8 bits), then per edge the first position
+//!   (7 bits), whether the second cell is below (1 bit), and the kind (2 bits)
+//! - progress (if flagged): per non-given cell a tag (2 bits) for empty,
+//!   filled (+ digit, 4 bits), notes (+ 9-bit digit mask), or notes with
+//!   center notes (+ 9-bit corner mask, possibly empty, and 9-bit center mask)
+
+use numelace_core::{
+    Cage, CageSet, ChessRules, Digit, DigitGrid, DigitPositions, EdgeConstraint, EdgeConstraintSet,
+    EdgeKind, Position, RegionLayout,
+};
+
+use crate::{CellState, Game, StateCodeError};
+
+const VERSION: u32 = 1;
+
+const FLAG_ANTI_KNIGHT: u32 = 1 << 5;
+const FLAG_ANTI_KING: u32 = 1 << 4;
+const FLAG_PROGRESS: u32 = 1 << 3;
+const FLAG_REGIONS: u32 = 1 << 2;
+const FLAG_CAGES: u32 = 1 << 1;
+const FLAG_EDGES: u32 = 1;
+
+const TAG_EMPTY: u32 = 0;
+const TAG_FILLED: u32 = 1;
+const TAG_NOTES: u32 = 2;
+const TAG_CENTER_NOTES: u32 = 3;
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+impl Game {
+    /// Encodes the game as a compact, URL-safe string.
+    ///
+    /// The code holds the givens, the solution, the variant rules, and the
+    /// player's filled digits, corner notes and center notes. Progress is only written when there
+    /// is any, so a fresh puzzle gets the shortest code.
+    /// An uninitialized game encodes as an empty puzzle.
+    #[must_use]
+    pub fn to_state_code(&self) -> String {
+        let mut writer = BitWriter::default();
+        let regions = self.regions();
+        let cages = self.cages();
+        let edges = self.edge_constraints();
+        let chess = self.chess_rules();
+        let has_progress = Position::ALL
+            .into_iter()
+            .any(|pos| self.has_removable_input(pos));
+
+        let mut flags = 0;
+        for (enabled, flag) in [
+            (chess.anti_knight(), FLAG_ANTI_KNIGHT),
+            (chess.anti_king(), FLAG_ANTI_KING),
+            (has_progress, FLAG_PROGRESS),
+            (!regions.is_standard(), FLAG_REGIONS),
+            (!cages.is_empty(), FLAG_CAGES),
+            (!edges.is_empty(), FLAG_EDGES),
+        ] {
+            if enabled {
+                flags |= flag;
+            }
+        }
+        writer.write(VERSION, 4);
+        writer.write(flags, 6);
+
+        for pos in Position::ALL {
+            writer.write(digit_value(self.solution_digit_at(pos)), 4);
+        }
+        let givens = self.given_positions();
+        write_positions(&mut writer, givens);
+        for pos in givens {
+            if self.solution_digit_at(pos).is_none() {
+                writer.write(digit_value(self.cell(pos).as_given()), 4);
+            }
+        }
+        if flags & FLAG_REGIONS != 0 {
+            for pos in Position::ALL {
+                writer.write(regions.region_index(pos).into(), 4);
+            }
+        }
+        if flags & FLAG_CAGES != 0 {
+            writer.write(u32::try_from(cages.len()).unwrap_or(0), 7);
+            for cage in cages {
+                writer.write(cage.sum().into(), 6);
+                write_positions(&mut writer, cage.positions());
+            }
+        }
+        if flags & FLAG_EDGES != 0 {
+            writer.write(u32::try_from(edges.len()).unwrap_or(0), 8);
+            for edge in edges {
+                writer.write(edge.first().index().into(), 7);
+                writer.write((!edge.is_vertical()).into(), 1);
+                let kind = match edge.kind() {
+                    EdgeKind::Consecutive => 0,
+                    EdgeKind::LessThan => 1,
+                    EdgeKind::GreaterThan => 2,
+                };
+                writer.write(kind, 2);
+            }
+        }
+        if has_progress {
+            for (pos, cell) in self.iter_cells() {
+                let center_notes = self.center_notes(pos);
+                if !center_notes.is_empty() {
+                    writer.write(TAG_CENTER_NOTES, 2);
+                    writer.write(cell.as_notes().unwrap_or_default().bits().into(), 9);
+                    writer.write(center_notes.bits().into(), 9);
+                    continue;
+                }
+                match cell {
+                    CellState::Given(_) => {}
+                    CellState::Filled(digit) => {
+                        writer.write(TAG_FILLED, 2);
+                        writer.write(digit.value().into(), 4);
+                    }
+                    CellState::Notes(digits) => {
+                        writer.write(TAG_NOTES, 2);
+                        writer.write(digits.bits().into(), 9);
+                    }
+                    CellState::Empty => writer.write(TAG_EMPTY, 2),
+                }
+            }
+        }
+        writer.finish()
+    }
+
+    /// Decodes a code produced by [`Game::to_state_code`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`StateCodeError`] if the code is malformed, was written by
+    /// an unsupported format version, or describes an invalid game.
+    pub fn from_state_code(code: &str) -> Result<Self, StateCodeError> {
+        let mut reader = BitReader::new(code)?;
+        let version = reader.read(4)?;
+        if version != VERSION {
+            return Err(StateCodeError::UnsupportedVersion(version));
+        }
+        let flags = reader.read(6)?;
+
+        let mut solution = DigitGrid::new();
+        for pos in Position::ALL {
+            solution.set(pos, read_optional_digit(&mut reader)?);
+        }
+        let mut problem = DigitGrid::new();
+        for pos in read_positions(&mut reader)? {
+            let digit = match solution.get(pos) {
+                Some(digit) => digit,
+                None => read_digit(&mut reader)?,
+            };
+            problem.set(pos, Some(digit));
+        }
+        let regions = if flags & FLAG_REGIONS == 0 {
+            RegionLayout::standard()
+        } else {
+            let mut regions = [DigitPositions::new(); 9];
+            for pos in Position::ALL {
+                let index = reader.read(4)?;
+                let region = regions
+                    .get_mut(index as usize)
+                    .ok_or(StateCodeError::InvalidValue(index))?;
+                region.insert(pos);
+            }
+            RegionLayout::from_regions(regions)?
+        };
+        let mut cages = Vec::new();
+        if flags & FLAG_CAGES != 0 {
+            for _ in 0..reader.read(7)? {
+                let sum = reader.read(6)?;
+                let positions = read_positions(&mut reader)?;
+                cages.push(Cage::new(positions, u8::try_from(sum).unwrap_or(0))?);
+            }
+        }
+        let mut edges = Vec::new();
+        if flags & FLAG_EDGES != 0 {
+            for _ in 0..reader.read(8)? {
+                let first = read_position(&mut reader)?;
+                let is_down = reader.read(1)? == 1;
+                let second = if is_down { first.down() } else { first.right() };
+                let second = second.ok_or(StateCodeError::InvalidValue(first.index().into()))?;
+                let kind = match reader.read(2)? {
+                    0 => EdgeKind::Consecutive,
+                    1 => EdgeKind::LessThan,
+                    2 => EdgeKind::GreaterThan,
+                    value => return Err(StateCodeError::InvalidValue(value)),
+                };
+                edges.push(EdgeConstraint::new(first, second, kind)?);
+            }
+        }
+
+        let mut filled = DigitGrid::new();
+        let mut notes = [[0; 9]; 9];
+        let mut center_notes = [[0; 9]; 9];
+        if flags & FLAG_PROGRESS != 0 {
+            for pos in Position::ALL {
+                if problem.get(pos).is_some() {
+                    continue;
+                }
+                match reader.read(2)? {
+                    TAG_EMPTY => {}
+                    TAG_FILLED => filled.set(pos, Some(read_digit(&mut reader)?)),
+                    TAG_NOTES => {
+                        notes[usize::from(pos.row())][usize::from(pos.col())] =
+                            u16::try_from(reader.read(9)?).unwrap_or(0);
+                    }
+                    TAG_CENTER_NOTES => {
+                        let (row, col) = (usize::from(pos.row()), usize::from(pos.col()));
+                        notes[row][col] = u16::try_from(reader.read(9)?).unwrap_or(0);
+                        center_notes[row][col] = u16::try_from(reader.read(9)?).unwrap_or(0);
+                    }
+                    value => return Err(StateCodeError::InvalidValue(value)),
+                }
+            }
+        }
+
+        Ok(
+            Game::from_problem_filled_notes(&problem, &solution, &filled, &notes)?
+                .with_center_notes(&center_notes)?
+                .with_cages(CageSet::from_cages(cages)?)
+                .with_regions(regions)
+                .with_edge_constraints(EdgeConstraintSet::from_constraints(edges)?)
+                .with_chess_rules(
+                    ChessRules::new()
+                        .with_anti_knight(flags & FLAG_ANTI_KNIGHT != 0)
+                        .with_anti_king(flags & FLAG_ANTI_KING != 0),
+                ),
+        )
+    }
+}
+
+fn digit_value(digit: Option<Digit>) -> u32 {
+    digit.map_or(0, |digit| digit.value().into())
+}
+
+fn write_positions(writer: &mut BitWriter, positions: DigitPositions) {
+    for pos in Position::ALL {
+        writer.write(positions.contains(pos).into(), 1);
+    }
+}
+
+fn read_positions(reader: &mut BitReader) -> Result<DigitPositions, StateCodeError> {
+    let mut positions = DigitPositions::new();
+    for pos in Position::ALL {
+        if reader.read(1)? == 1 {
+            positions.insert(pos);
+        }
+    }
+    Ok(positions)
+}
+
+fn read_position(reader: &mut BitReader) -> Result<Position, StateCodeError> {
+    let index = reader.read(7)?;
+    u8::try_from(index)
+        .ok()
+        .and_then(|index| Position::try_from_index(index).ok())
+        .ok_or(StateCodeError::InvalidValue(index))
+}
+
+fn read_digit(reader: &mut BitReader) -> Result<Digit, StateCodeError> {
+    read_optional_digit(reader)?.ok_or(StateCodeError::InvalidValue(0))
+}
+
+/// Reads a digit, where 0 stands for an unknown one.
+fn read_optional_digit(reader: &mut BitReader) -> Result<Option<Digit>, StateCodeError> {
+    let value = reader.read(4)?;
+    match value {
+        0 => Ok(None),
+        1..=9 => Ok(Some(Digit::from_value(u8::try_from(value).unwrap_or(0)))),
+        _ => Err(StateCodeError::InvalidValue(value)),
+    }
+}
+
+/// Collects bits and emits them six at a time as base64url characters.
+#[derive(Debug, Default)]
+struct BitWriter {
+    output: String,
+    buffer: u32,
+    len: u32,
+}
+
+impl BitWriter {
+    fn write(&mut self, value: u32, bits: u32) {
+        for i in (0..bits).rev() {
+            self.buffer = (self.buffer << 1) | ((value >> i) & 1);
+            self.len += 1;
+            if self.len == 6 {
+                self.output.push(char::from(ALPHABET[self.buffer as usize]));
+                self.buffer = 0;
+                self.len = 0;
+            }
+        }
+    }
+
+    fn finish(mut self) -> String {
+        if self.len > 0 {
+            self.write(0, 6 - self.len);
+        }
+        self.output
+    }
+}
+
+#[derive(Debug)]
+struct BitReader {
+    sextets: Vec<u8>,
+    position: usize,
+}
+
+impl BitReader {
+    fn new(code: &str) -> Result<Self, StateCodeError> {
+        let sextets = code
+            .chars()
+            .map(|ch| {
+                ALPHABET
+                    .iter()
+                    .position(|&c| char::from(c) == ch)
+                    .and_then(|index| u8::try_from(index).ok())
+                    .ok_or(StateCodeError::InvalidCharacter(ch))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            sextets,
+            position: 0,
+        })
+    }
+
+    fn read(&mut self, bits: u32) -> Result<u32, StateCodeError> {
+        let mut value = 0;
+        for _ in 0..bits {
+            let sextet = self
+                .sextets
+                .get(self.position / 6)
+                .ok_or(StateCodeError::Truncated)?;
+            let bit = (sextet >> (5 - self.position % 6)) & 1;
+            value = (value << 1) | u32::from(bit);
+            self.position += 1;
+        }
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use numelace_core::{
+        Cage, CageSet, ChessRules, Digit, DigitGrid, DigitSet, EdgeConstraint, EdgeConstraintSet,
+        EdgeKind, Position,
+    };
+
+    use crate::{CellState, Game, StateCodeError};
+
+    const SOLUTION: &str =
+        "185362947793148526246795183564239871931874265827516394318427659672951438459683712";
+
+    fn game() -> Game {
+        let problem: DigitGrid = format!("1{}", ".".repeat(80)).parse().unwrap();
+        let solution: DigitGrid = SOLUTION.parse().unwrap();
+        let filled: DigitGrid = format!(".8{}", ".".repeat(79)).parse().unwrap();
+        let mut notes = [[0u16; 9]; 9];
+        notes[8][8] = 0b0_0000_0110;
+        Game::from_problem_filled_notes(&problem, &solution, &filled, &notes).unwrap()
+    }
+
+    #[test]
+    fn classic_puzzle_code_is_compact_and_url_safe() {
+        let mut game = game();
+        game.clear_inputs();
+        let code = game.to_state_code();
+
+        assert_eq!(code.len(), 70);
+        assert!(
+            code.chars()
+                .all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_')
+        );
+        assert_eq!(Game::from_state_code(&code).unwrap(), game);
+    }
+
+    #[test]
+    fn round_trip_with_progress_and_variants() {
+        let cage = Cage::new(
+            [Position::new(4, 4), Position::new(4, 5)]
+                .into_iter()
+                .collect(),
+            11,
+        )
+        .unwrap();
+        let edge =
+            EdgeConstraint::new(Position::new(2, 2), Position::new(3, 2), EdgeKind::LessThan)
+                .unwrap();
+        let game = game()
+            .with_cages(CageSet::from_cages([cage]).unwrap())
+            .with_edge_constraints(EdgeConstraintSet::from_constraints([edge]).unwrap())
+            .with_chess_rules(ChessRules::new().with_anti_king(true));
+
+        let decoded = Game::from_state_code(&game.to_state_code()).unwrap();
+
+        assert_eq!(decoded, game);
+        assert_eq!(
+            *decoded.cell(Position::new(8, 8)),
+            CellState::Notes(DigitSet::from_iter([Digit::D2, Digit::D3]))
+        );
+    }
+
+    #[test]
+    fn round_trip_keeps_center_notes() {
+        let game = game()
+            .with_center_notes(&{
+                let mut center_notes = [[0u16; 9]; 9];
+                center_notes[8][8] = 0b1_0000_0000;
+                center_notes[5][5] = 0b0_0001_0001;
+                center_notes
+            })
+            .unwrap();
+
+        let decoded = Game::from_state_code(&game.to_state_code()).unwrap();
+
+        assert_eq!(decoded, game);
+        assert_eq!(
+            decoded.center_notes(Position::new(5, 5)),
+            DigitSet::from_iter([Digit::D1, Digit::D5])
+        );
+        assert!(decoded.cell(Position::new(5, 5)).is_empty());
+    }
+
+    #[test]
+    fn round_trip_without_known_solution_keeps_givens() {
+        let problem: DigitGrid = format!("..5{}", ".".repeat(78)).parse().unwrap();
+        let game = Game::from_problem_filled_notes(
+            &problem,
+            &DigitGrid::new(),
+            &DigitGrid::new(),
+            &[[0; 9]; 9],
+        )
+        .unwrap();
+
+        assert_eq!(Game::from_state_code(&game.to_state_code()).unwrap(), game);
+    }
+
+    #[test]
+    fn rejects_malformed_codes() {
+        assert!(matches!(
+            Game::from_state_code("abc!"),
+            Err(StateCodeError::InvalidCharacter('!'))
+        ));
+        assert!(matches!(
+            Game::from_state_code("E"),
+            Err(StateCodeError::Truncated)
+        ));
+        assert!(matches!(
+            Game::from_state_code("AAAA"),
+            Err(StateCodeError::UnsupportedVersion(0))
+        ));
+    }
+}
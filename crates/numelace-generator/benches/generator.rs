@@ -16,6 +16,11 @@
 //!   (intermediate + wings).
 //! - **`generator_advanced`**: Generates puzzles using advanced techniques
 //!   (including chain techniques).
+//! - **`generator_dig_order`**: Generates puzzles using intermediate techniques
+//!   with each [`DigOrder`] of the `DigHoleCarve` strategy, to compare carve strategies.
+//! - **`generator_fill`**: Generates puzzles using fundamental techniques with
+//!   `BacktrackingFill` and `TransformFill`, to compare fill strategies where
+//!   the fill step weighs the most.
 //!
 //! # Test Data
 //!
@@ -39,7 +44,9 @@ use std::{str::FromStr as _, time::Duration};
 use criterion::{
     BatchSize, BenchmarkId, Criterion, PlottingBackend, criterion_group, criterion_main,
 };
-use numelace_generator::{PuzzleGenerator, PuzzleSeed};
+use numelace_generator::{
+    BacktrackingFill, DigHoleCarve, DigOrder, PuzzleGenerator, PuzzleSeed, TransformFill,
+};
 use numelace_solver::{TechniqueSolver, technique};
 
 const SEEDS: [&str; 3] = [
@@ -99,6 +106,26 @@ fn bench_generator_advanced(c: &mut Criterion) {
     bench_generator_cases(c, "generator_advanced", &generator);
 }
 
+fn bench_generator_dig_order(c: &mut Criterion) {
+    let solver = TechniqueSolver::new(technique::intermediate_techniques());
+    for order in DigOrder::ALL {
+        let generator = PuzzleGenerator::new(&solver).with_carve_strategy(DigHoleCarve { order });
+        let bench_name = match order {
+            DigOrder::Random => "generator_dig_order/random",
+            DigOrder::Snake => "generator_dig_order/snake",
+        };
+        bench_generator_cases(c, bench_name, &generator);
+    }
+}
+
+fn bench_generator_fill(c: &mut Criterion) {
+    let solver = TechniqueSolver::new(technique::fundamental_techniques());
+    let generator = PuzzleGenerator::new(&solver).with_fill_strategy(BacktrackingFill);
+    bench_generator_cases(c, "generator_fill/backtracking", &generator);
+    let generator = PuzzleGenerator::new(&solver).with_fill_strategy(TransformFill);
+    bench_generator_cases(c, "generator_fill/transform", &generator);
+}
+
 criterion_group!(
     name = benches_fundamental;
     config =
@@ -145,10 +172,30 @@ criterion_group!(
         bench_generator_advanced,
 );
 
+criterion_group!(
+    name = benches_dig_order;
+    config =
+        Criterion::default()
+            .plotting_backend(PlottingBackend::Plotters);
+    targets =
+        bench_generator_dig_order,
+);
+
+criterion_group!(
+    name = benches_fill;
+    config =
+        Criterion::default()
+            .plotting_backend(PlottingBackend::Plotters);
+    targets =
+        bench_generator_fill,
+);
+
 criterion_main!(
     benches_fundamental,
     benches_basic,
     benches_intermediate,
     benches_upper_intermediate,
     benches_advanced,
+    benches_dig_order,
+    benches_fill,
 );
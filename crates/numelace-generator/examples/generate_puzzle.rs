@@ -31,6 +31,25 @@
 //! cargo run --example generate_puzzle -- --solver fundamental
 //! ```
 //!
+//! Select how the solution grid is filled (backtracking or transform):
+//!
+//! ```sh
+//! cargo run --example generate_puzzle -- --fill transform
+//! ```
+//!
+//! Select the order in which cells are dug out of the solution (random or snake):
+//!
+//! ```sh
+//! cargo run --example generate_puzzle -- --dig-order snake
+//! ```
+//!
+//! Print the generator trace: every uniqueness check of the carve strategy,
+//! with the cells it tried to remove and the candidate entropy of the board:
+//!
+//! ```sh
+//! cargo run --example generate_puzzle -- --trace
+//! ```
+//!
 //! Multiple techniques can be required (case-insensitive), including tiers:
 //!
 //! ```sh
@@ -44,7 +63,10 @@ use std::{
 };
 
 use clap::{Parser, ValueEnum};
-use numelace_generator::{GeneratedPuzzle, PuzzleGenerator};
+use numelace_generator::{
+    BacktrackingFill, DigHoleCarve, DigOrder, GeneratedPuzzle, GenerationTrace, PuzzleGenerator,
+    TransformFill,
+};
 use numelace_solver::{
     BoxedTechnique, TechniqueGrid, TechniqueSolver, TechniqueSolverStats, TechniqueTier, technique,
 };
@@ -57,6 +79,27 @@ enum SolverKind {
     Basic,
 }
 
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum FillKind {
+    Backtracking,
+    Transform,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum DigOrderKind {
+    Random,
+    Snake,
+}
+
+impl From<DigOrderKind> for DigOrder {
+    fn from(kind: DigOrderKind) -> Self {
+        match kind {
+            DigOrderKind::Random => Self::Random,
+            DigOrderKind::Snake => Self::Snake,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 enum TechniqueSelector {
     Technique(BoxedTechnique),
@@ -107,6 +150,18 @@ struct Args {
     #[arg(short, long = "technique", value_name = "TECHNIQUE", num_args = 1..)]
     techniques: Vec<String>,
 
+    /// How the solution grid is filled.
+    #[arg(long, value_name = "KIND", default_value = "backtracking")]
+    fill: FillKind,
+
+    /// Order in which cells are dug out of the solution.
+    #[arg(long, value_name = "ORDER", default_value = "random")]
+    dig_order: DigOrderKind,
+
+    /// Print the generator trace of the puzzle.
+    #[arg(long)]
+    trace: bool,
+
     /// Maximum puzzles to sample when filtering.
     #[arg(long, value_name = "COUNT", default_value_t = NonZero::new(10_000).unwrap())]
     max_tries: NonZero<usize>,
@@ -115,7 +170,13 @@ struct Args {
 fn main() {
     let args = Args::parse();
     let solver = build_solver(args.solver);
-    let generator = PuzzleGenerator::new(&solver);
+    let generator = PuzzleGenerator::new(&solver).with_carve_strategy(DigHoleCarve {
+        order: args.dig_order.into(),
+    });
+    let generator = match args.fill {
+        FillKind::Backtracking => generator.with_fill_strategy(BacktrackingFill),
+        FillKind::Transform => generator.with_fill_strategy(TransformFill),
+    };
 
     let (selectors, unknown) = parse_technique_selectors(&args.techniques, solver.techniques());
 
@@ -138,9 +199,16 @@ fn main() {
     }
 
     if selectors.is_empty() {
-        let puzzle = generator.generate();
-        let stats = solve_stats(&solver, &puzzle);
-        print_puzzle(&puzzle, &solver, &stats, None, &[]);
+        if args.trace {
+            let (puzzle, trace) = generator.generate_with_trace(rand::random());
+            let stats = solve_stats(&solver, &puzzle);
+            print_puzzle(&puzzle, &solver, &stats, None, &[]);
+            print_trace(&trace);
+        } else {
+            let puzzle = generator.generate();
+            let stats = solve_stats(&solver, &puzzle);
+            print_puzzle(&puzzle, &solver, &stats, None, &[]);
+        }
         return;
     }
 
@@ -322,3 +390,31 @@ fn print_puzzle(
         println!("  {}: {total}", tier_label(tier));
     }
 }
+
+fn print_trace(trace: &GenerationTrace) {
+    println!();
+    println!(
+        "Trace ({} fill, {} carve):",
+        trace.fill_strategy, trace.carve_strategy
+    );
+    for (index, step) in trace.steps.iter().enumerate() {
+        let cells = step
+            .removed
+            .into_iter()
+            .map(|pos| pos.to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let outcome = if step.kept { "kept" } else { "rejected" };
+        println!(
+            "  {:>3}: {outcome:<8} {:>6.1} bits  {cells}",
+            index + 1,
+            step.entropy
+        );
+    }
+    println!(
+        "Uniqueness checks: {} ({} kept), solver runs: {}",
+        trace.uniqueness_checks(),
+        trace.kept_removals(),
+        trace.solver_runs
+    );
+}
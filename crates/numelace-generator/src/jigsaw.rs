@@ -0,0 +1,122 @@
+//! Random region layouts for jigsaw puzzles.
+
+use numelace_core::{DigitPositions, Position, RegionLayout};
+use numelace_solver::{BacktrackSolver, SolverBudget, TechniqueGrid, technique};
+use rand::{Rng, RngExt as _};
+
+/// Number of cell moves tried when reshaping the standard boxes.
+const RESHAPE_ATTEMPTS: usize = 300;
+
+/// Assumptions allowed when checking that a layout can be filled.
+///
+/// Fillable layouts are filled with a handful of assumptions; layouts that
+/// take more are dropped rather than searched exhaustively.
+const FILL_CHECK_STEPS: usize = 2_000;
+
+/// Returns a random jigsaw layout that admits a complete solution grid.
+///
+/// The layout starts from the standard boxes and reshapes them at random: a
+/// cell moves into a neighbouring region, which hands one of its own cells on
+/// their border back, and the move is kept only if both regions stay
+/// connected. Some layouts have no solution at all, so each candidate is
+/// checked by filling an empty grid, and a new one is drawn until a grid
+/// exists. All randomness comes from `rng`, so a seeded generator always
+/// draws the same layout.
+///
+/// # Examples
+///
+/// ```
+/// use numelace_generator::random_jigsaw_layout;
+/// use rand::SeedableRng as _;
+/// use rand_pcg::Pcg64;
+///
+/// let mut rng = Pcg64::from_seed([3; 32]);
+/// let layout = random_jigsaw_layout(&mut rng);
+/// assert!(!layout.is_standard());
+/// ```
+pub fn random_jigsaw_layout(rng: &mut dyn Rng) -> RegionLayout {
+    loop {
+        let mut layout = RegionLayout::standard();
+        for _ in 0..RESHAPE_ATTEMPTS {
+            if let Some(reshaped) = reshape(rng, &layout) {
+                layout = reshaped;
+            }
+        }
+        if !layout.is_standard() && has_solution(layout) {
+            return layout;
+        }
+    }
+}
+
+/// Moves a random border cell into the neighbouring region and a cell of that
+/// region back, returning `None` if a region would be disconnected.
+fn reshape(rng: &mut dyn Rng, layout: &RegionLayout) -> Option<RegionLayout> {
+    let a = Position::from_index(rng.random_range(0..81));
+    let a_neighbors = neighbors(a).collect::<Vec<_>>();
+    let b = a_neighbors[rng.random_range(0..a_neighbors.len())];
+    let (ra, rb) = (
+        usize::from(layout.region_index(a)),
+        usize::from(layout.region_index(b)),
+    );
+    if ra == rb {
+        return None;
+    }
+
+    let mut regions = [DigitPositions::EMPTY; 9];
+    for (index, region) in (0..).zip(&mut regions) {
+        *region = layout.region_positions(index);
+    }
+    regions[ra].remove(a);
+    regions[rb].insert(a);
+    let back = regions[rb]
+        .into_iter()
+        .filter(|&c| c != a && neighbors(c).any(|n| regions[ra].contains(n)))
+        .collect::<Vec<_>>();
+    if back.is_empty() {
+        return None;
+    }
+    let c = back[rng.random_range(0..back.len())];
+    regions[rb].remove(c);
+    regions[ra].insert(c);
+    RegionLayout::from_regions(regions).ok()
+}
+
+/// Returns the orthogonal neighbors of `pos`.
+fn neighbors(pos: Position) -> impl Iterator<Item = Position> {
+    let (row, col) = (pos.row(), pos.col());
+    [
+        (row.wrapping_sub(1), col),
+        (row + 1, col),
+        (row, col.wrapping_sub(1)),
+        (row, col + 1),
+    ]
+    .into_iter()
+    .filter(|&(row, col)| row < 9 && col < 9)
+    .map(|(row, col)| Position::new(row, col))
+}
+
+/// Returns whether an empty grid with `layout` can be completed.
+fn has_solution(layout: RegionLayout) -> bool {
+    let solver = BacktrackSolver::with_techniques(technique::fundamental_techniques())
+        .with_budget(SolverBudget::new().with_max_steps(FILL_CHECK_STEPS));
+    solver
+        .solve_with_pass(TechniqueGrid::new().with_regions(layout))
+        .is_ok_and(|mut solutions| solutions.next().is_some())
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng as _;
+    use rand_pcg::Pcg64;
+
+    use super::*;
+
+    #[test]
+    fn test_layout_is_reproducible() {
+        let layout1 = random_jigsaw_layout(&mut Pcg64::from_seed([1; 32]));
+        let layout2 = random_jigsaw_layout(&mut Pcg64::from_seed([1; 32]));
+        let layout3 = random_jigsaw_layout(&mut Pcg64::from_seed([2; 32]));
+        assert_eq!(layout1, layout2);
+        assert_ne!(layout1, layout3);
+    }
+}
@@ -23,6 +23,14 @@
 //!    `numelace-solver` crate, which ensures the puzzle can be solved using only human-like
 //!    logical deduction techniques.
 //!
+//! Each step is a strategy: a [`FillStrategy`] produces the solution and a
+//! [`CarveStrategy`] removes cells from it. The defaults, [`BacktrackingFill`]
+//! and [`DigHoleCarve`], implement the steps above; others can be plugged in
+//! with [`PuzzleGenerator::with_fill_strategy`] and
+//! [`PuzzleGenerator::with_carve_strategy`] to compare algorithms without
+//! changing the generator. [`TransformFill`], for one, skips the search for a
+//! solution by shuffling a stored grid.
+//!
 //! ## Design Rationale
 //!
 //! ### Why Removal Method?
@@ -54,9 +62,10 @@
 //!   specific difficulty levels requires additional mechanisms to filter out puzzles
 //!   that are too easy (e.g., rejecting puzzles that don't require advanced techniques).
 //!
-//! - **Aesthetic Patterns**: The initial implementation doesn't control symmetry or
-//!   aesthetic patterns in the generated puzzles. This could be added as a future
-//!   enhancement by constraining which cells are removed.
+//! - **Aesthetic Patterns**: Symmetric givens are produced by removing the cells
+//!   mapped onto each other by a [`ClueSymmetry`] together. Since a group is only
+//!   removed when the puzzle stays solvable without all of its cells, symmetric
+//!   puzzles tend to keep more givens.
 //!
 //! Despite these limitations, the removal method provides a solid foundation: it's
 //! simple, reliable, and produces human-solvable puzzles that can be solved using
@@ -106,23 +115,34 @@
 //! assert_eq!(puzzle1.solution, puzzle2.solution);
 //! ```
 
-use std::{fmt::Display, str::FromStr};
+use std::{
+    fmt::Display,
+    str::FromStr,
+    sync::{Arc, Mutex, PoisonError},
+};
 
-use numelace_core::{CandidateGrid, Digit, DigitGrid, Position};
-use numelace_solver::{TechniqueGrid, TechniqueSolver, backtrack};
+use numelace_core::{BoxedConstraint, Constraint, DigitGrid, RegionLayout};
+use numelace_solver::{SolverError, TechniqueGrid, TechniqueSolver};
 use rand::{
     Rng, RngExt as _, SeedableRng,
     distr::{Distribution, StandardUniform},
-    seq::SliceRandom,
 };
 use rand_pcg::Pcg64;
 use sha2::{Digest as _, Sha256};
 
+pub use self::{jigsaw::*, strategy::*, symmetry::*, trace::*};
+
+mod jigsaw;
+mod strategy;
+mod symmetry;
+mod trace;
+
 /// A Sudoku puzzle generator that creates puzzles with unique solutions.
 ///
 /// The generator uses the removal method: it first generates a complete solution grid,
 /// then removes cells one by one while verifying that the puzzle remains solvable
-/// using only logical deduction techniques.
+/// using only logical deduction techniques. Both steps are pluggable strategies;
+/// see [`FillStrategy`] and [`CarveStrategy`].
 ///
 /// # Examples
 ///
@@ -138,13 +158,256 @@ use sha2::{Digest as _, Sha256};
 #[derive(Debug, Clone)]
 pub struct PuzzleGenerator<'a> {
     solver: &'a TechniqueSolver,
+    constraints: Vec<BoxedConstraint>,
+    regions: RegionLayout,
+    /// Whether every puzzle draws its own jigsaw layout from its seed.
+    jigsaw: bool,
+    symmetry: ClueSymmetry,
+    fill: BoxedFillStrategy,
+    carve: BoxedCarveStrategy,
+    /// Trace being recorded by [`generate_with_trace`](Self::generate_with_trace).
+    trace: Option<Arc<Mutex<GenerationTrace>>>,
 }
 
 impl<'a> PuzzleGenerator<'a> {
     /// Create a new generator with a solver
     #[must_use]
     pub fn new(solver: &'a TechniqueSolver) -> Self {
-        Self { solver }
+        Self {
+            solver,
+            constraints: Vec::new(),
+            regions: RegionLayout::standard(),
+            jigsaw: false,
+            symmetry: ClueSymmetry::None,
+            fill: Box::new(BacktrackingFill),
+            carve: Box::new(DigHoleCarve::default()),
+            trace: None,
+        }
+    }
+
+    /// Returns a generator whose givens follow the given symmetry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use numelace_generator::{ClueSymmetry, PuzzleGenerator};
+    /// use numelace_solver::TechniqueSolver;
+    ///
+    /// let solver = TechniqueSolver::with_all_techniques();
+    /// let generator = PuzzleGenerator::new(&solver).with_symmetry(ClueSymmetry::Rotational);
+    ///
+    /// let puzzle = generator.generate();
+    /// for pos in numelace_core::Position::ALL {
+    ///     let image = ClueSymmetry::Rotational.image(pos);
+    ///     assert_eq!(puzzle.problem.get(pos).is_some(), puzzle.problem.get(image).is_some());
+    /// }
+    /// ```
+    #[must_use]
+    pub fn with_symmetry(mut self, symmetry: ClueSymmetry) -> Self {
+        self.symmetry = symmetry;
+        self
+    }
+
+    /// Returns a generator whose puzzles also follow the given variant constraint.
+    ///
+    /// The solution grid satisfies the constraint, and cells are only removed
+    /// while the techniques, together with the constraint's candidate pruning,
+    /// still solve the puzzle. Generated puzzles are therefore unique only
+    /// under the constraint.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use numelace_core::{ChessRules, Constraint as _};
+    /// use numelace_generator::PuzzleGenerator;
+    /// use numelace_solver::TechniqueSolver;
+    ///
+    /// let solver = TechniqueSolver::with_all_techniques();
+    /// let rules = ChessRules::new().with_anti_king(true);
+    /// let generator = PuzzleGenerator::new(&solver).with_constraint(rules);
+    ///
+    /// let puzzle = generator.generate();
+    /// assert!(rules.check(&puzzle.solution).is_ok());
+    /// ```
+    #[must_use]
+    pub fn with_constraint<C>(mut self, constraint: C) -> Self
+    where
+        C: Constraint + 'static,
+    {
+        self.constraints.push(Box::new(constraint));
+        self
+    }
+
+    /// Returns a generator whose puzzles use the regions of `regions` as
+    /// their boxes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use numelace_core::RegionLayout;
+    /// use numelace_generator::PuzzleGenerator;
+    /// use numelace_solver::TechniqueSolver;
+    ///
+    /// let regions: RegionLayout = "\
+    ///     111122333\
+    ///     111222333\
+    ///     112222333\
+    ///     444555666\
+    ///     444555666\
+    ///     444555666\
+    ///     777888999\
+    ///     777888999\
+    ///     777888999"
+    ///     .parse()
+    ///     .unwrap();
+    /// let solver = TechniqueSolver::with_all_techniques();
+    /// let generator = PuzzleGenerator::new(&solver).with_regions(regions);
+    ///
+    /// let puzzle = generator.generate();
+    /// assert_eq!(puzzle.regions, regions);
+    /// assert!(regions.is_solved(&puzzle.solution));
+    /// ```
+    #[must_use]
+    pub fn with_regions(mut self, regions: RegionLayout) -> Self {
+        self.regions = regions;
+        self.jigsaw = false;
+        self
+    }
+
+    /// Returns a generator that gives every puzzle a new random jigsaw layout.
+    ///
+    /// The layout is drawn with [`random_jigsaw_layout`] from the puzzle's
+    /// seed, so [`generate_with_seed`](Self::generate_with_seed) still
+    /// reproduces the same puzzle, layout included.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use numelace_generator::{PuzzleGenerator, PuzzleSeed};
+    /// use numelace_solver::TechniqueSolver;
+    ///
+    /// let solver = TechniqueSolver::with_all_techniques();
+    /// let generator = PuzzleGenerator::new(&solver).with_jigsaw_regions();
+    ///
+    /// let puzzle = generator.generate_with_seed(PuzzleSeed::from([5; 32]));
+    /// assert!(!puzzle.regions.is_standard());
+    /// assert!(puzzle.regions.is_solved(&puzzle.solution));
+    /// ```
+    #[must_use]
+    pub fn with_jigsaw_regions(mut self) -> Self {
+        self.jigsaw = true;
+        self
+    }
+
+    /// Returns a generator that fills solution grids with `strategy`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use numelace_generator::{BacktrackingFill, PuzzleGenerator};
+    /// use numelace_solver::TechniqueSolver;
+    ///
+    /// let solver = TechniqueSolver::with_all_techniques();
+    /// let generator = PuzzleGenerator::new(&solver).with_fill_strategy(BacktrackingFill);
+    ///
+    /// let puzzle = generator.generate();
+    /// ```
+    #[must_use]
+    pub fn with_fill_strategy<S>(mut self, strategy: S) -> Self
+    where
+        S: FillStrategy + 'static,
+    {
+        self.fill = Box::new(strategy);
+        self
+    }
+
+    /// Returns a generator that removes givens with `strategy`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use numelace_generator::{DigHoleCarve, DigOrder, PuzzleGenerator};
+    /// use numelace_solver::TechniqueSolver;
+    ///
+    /// let solver = TechniqueSolver::with_all_techniques();
+    /// let carve = DigHoleCarve {
+    ///     order: DigOrder::Snake,
+    /// };
+    /// let generator = PuzzleGenerator::new(&solver).with_carve_strategy(carve);
+    ///
+    /// let puzzle = generator.generate();
+    /// ```
+    #[must_use]
+    pub fn with_carve_strategy<S>(mut self, strategy: S) -> Self
+    where
+        S: CarveStrategy + 'static,
+    {
+        self.carve = Box::new(strategy);
+        self
+    }
+
+    /// Returns the variant constraints generated puzzles follow.
+    #[must_use]
+    pub fn constraints(&self) -> &[BoxedConstraint] {
+        &self.constraints
+    }
+
+    /// Returns the region layout of generated puzzles.
+    ///
+    /// For a generator with [`with_jigsaw_regions`](Self::with_jigsaw_regions),
+    /// strategies see the layout drawn for the puzzle being generated.
+    #[must_use]
+    pub fn regions(&self) -> &RegionLayout {
+        &self.regions
+    }
+
+    /// Returns the symmetry the givens follow.
+    #[must_use]
+    pub fn symmetry(&self) -> ClueSymmetry {
+        self.symmetry
+    }
+
+    /// Returns the strategy filling solution grids.
+    #[must_use]
+    pub fn fill_strategy(&self) -> &dyn FillStrategy {
+        self.fill.as_ref()
+    }
+
+    /// Returns the strategy removing givens.
+    #[must_use]
+    pub fn carve_strategy(&self) -> &dyn CarveStrategy {
+        self.carve.as_ref()
+    }
+
+    /// Runs the technique solver, interleaved with constraint pruning, until
+    /// neither makes progress.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SolverError`] if the grid contradicts the rules.
+    pub fn solve(&self, grid: &mut TechniqueGrid) -> Result<bool, SolverError> {
+        self.with_trace(|trace| trace.solver_runs += 1);
+        loop {
+            let (solved, _) = self.solver.solve_with_pass(grid)?;
+            let mut changed = false;
+            for constraint in &self.constraints {
+                changed |= grid.prune_with(constraint.as_ref())?;
+            }
+            if !changed {
+                return Ok(solved);
+            }
+        }
+    }
+
+    /// Returns whether the techniques and constraints solve `problem`.
+    ///
+    /// Carve strategies check this before keeping a removal.
+    #[must_use]
+    pub fn is_solvable(&self, problem: &DigitGrid) -> bool {
+        let mut grid = TechniqueGrid::from_digit_grid_with_regions(problem, self.regions);
+        let solvable = self.solve(&mut grid).is_ok_and(|solved| solved);
+        self.with_trace(|trace| trace.record_check(problem, solvable));
+        solvable
     }
 
     /// Generates a puzzle with a random seed.
@@ -194,107 +457,92 @@ impl<'a> PuzzleGenerator<'a> {
     #[must_use]
     pub fn generate_with_seed(&self, seed: PuzzleSeed) -> GeneratedPuzzle {
         let mut rng = Pcg64::from_seed(seed.0);
-        let solution = self.generate_solution(&mut rng);
-        let problem = self.remove_cells(&mut rng, &solution);
+        if self.jigsaw {
+            let generator = Self {
+                regions: random_jigsaw_layout(&mut rng),
+                jigsaw: false,
+                ..self.clone()
+            };
+            return generator.generate_with_rng(seed, &mut rng);
+        }
+        self.generate_with_rng(seed, &mut rng)
+    }
+
+    /// Generates the puzzle for `seed` with the fill and carve strategies,
+    /// drawing from `rng`.
+    fn generate_with_rng<R>(&self, seed: PuzzleSeed, rng: &mut R) -> GeneratedPuzzle
+    where
+        R: Rng,
+    {
+        self.with_trace(|trace| trace.regions = self.regions);
+        let solution = self.generate_solution(rng);
+        self.with_trace(|trace| trace.solution = solution.clone());
+        let problem = self.remove_cells(rng, &solution);
         GeneratedPuzzle {
             problem,
             solution,
+            regions: self.regions,
             seed,
         }
     }
 
-    /// Generates a complete, valid Sudoku solution grid.
+    /// Generates the puzzle for `seed` and traces what the strategies did.
+    ///
+    /// The puzzle is the same as [`generate_with_seed`](Self::generate_with_seed)
+    /// gives for the seed. Tracing computes the candidate entropy of every
+    /// checked problem, so it is meant for debugging and tuning strategies
+    /// rather than for routine generation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use numelace_generator::{PuzzleGenerator, PuzzleSeed};
+    /// use numelace_solver::TechniqueSolver;
     ///
-    /// This method uses a hybrid approach:
-    /// 1. Fill the first row with shuffled digits 1-9
-    /// 2. Fill the remaining cells in the top-left box (avoiding the first row)
-    /// 3. Use backtracking with solver assistance to fill the remaining cells
+    /// let solver = TechniqueSolver::with_all_techniques();
+    /// let generator = PuzzleGenerator::new(&solver);
     ///
-    /// The backtracking uses [`numelace_solver::backtrack::find_best_assumption`] to
-    /// select cells with minimum candidates (MRV heuristic), and the solver is used
-    /// to eliminate obviously impossible candidates, making the search more efficient.
+    /// let seed = PuzzleSeed::from([7; 32]);
+    /// let (puzzle, trace) = generator.generate_with_trace(seed);
+    /// assert_eq!(trace.problem_after(trace.steps.len()), puzzle.problem);
+    /// assert!(trace.kept_removals() <= trace.uniqueness_checks());
+    /// ```
+    #[must_use]
+    pub fn generate_with_trace(&self, seed: PuzzleSeed) -> (GeneratedPuzzle, GenerationTrace) {
+        let trace = Arc::new(Mutex::new(GenerationTrace::new(
+            self.fill.name(),
+            self.carve.name(),
+        )));
+        let tracing = Self {
+            trace: Some(Arc::clone(&trace)),
+            ..self.clone()
+        };
+        let puzzle = tracing.generate_with_seed(seed);
+        let trace = trace.lock().unwrap_or_else(PoisonError::into_inner).clone();
+        (puzzle, trace)
+    }
+
+    /// Runs `f` on the trace being recorded, if any.
+    fn with_trace(&self, f: impl FnOnce(&mut GenerationTrace)) {
+        if let Some(trace) = &self.trace {
+            f(&mut trace.lock().unwrap_or_else(PoisonError::into_inner));
+        }
+    }
+
+    /// Generates a complete, valid Sudoku solution grid with the fill strategy.
     fn generate_solution<R>(&self, rng: &mut R) -> DigitGrid
     where
         R: Rng,
     {
-        let mut grid = CandidateGrid::new();
-
-        // Step 1: Fill the entire first row with shuffled digits 1-9
-        let mut top_row = Digit::ALL;
-        top_row.shuffle(rng);
-        for (col, digit) in (0..9).zip(top_row) {
-            let pos = Position::new(0, col);
-            grid.place(pos, digit);
-        }
-
-        // Step 2: Fill the remaining 6 cells in the top-left box (3x3)
-        // top_row[0..3] are already used in the first row of the top-left box
-        // top_row[3..9] contains the 6 digits unused in the top-left box
-        let mut remaining: [Digit; 6] = top_row[3..9].try_into().unwrap();
-        remaining.shuffle(rng);
-        for (i, digit) in (3..9).zip(remaining) {
-            let pos = Position::from_box(0, i); // Box 0, positions 3-8
-            grid.place(pos, digit);
-        }
-
-        let grid = TechniqueGrid::from(grid);
-
-        // Step 3: Fill the rest of the grid using backtracking with solver assistance
-        let mut stack = vec![];
-        let assumption = backtrack::find_best_assumption(&grid);
-        stack.push((grid, assumption));
-
-        while let Some((mut grid, (pos, mut digits))) = stack.pop() {
-            if digits.is_empty() {
-                continue;
-            }
-            // Pick a random candidate digit and try it
-            let digit = digits.pop_nth(rng.random_range(0..digits.len())).unwrap();
-            stack.push((grid.clone(), (pos, digits)));
-            grid.place(pos, digit);
-            // Use the solver to fill in cells that can be determined logically
-            let Ok((solved, _)) = self.solver.solve_with_pass(&mut grid) else {
-                continue; // Contradiction found, backtrack
-            };
-            if solved {
-                return grid.to_digit_grid();
-            }
-            // Pick the next cell to fill
-            let assumption = backtrack::find_best_assumption(&grid);
-            stack.push((grid, assumption));
-        }
-        unreachable!("Failed to generate complete grid - this should never happen");
+        self.fill.fill(self, rng)
     }
 
-    /// Removes cells from a complete solution to create a puzzle.
-    ///
-    /// This method attempts to remove as many cells as possible while ensuring
-    /// the puzzle remains solvable using only logical deduction:
-    ///
-    /// 1. Shuffle all 81 cell positions
-    /// 2. For each position, try removing the cell
-    /// 3. Verify the puzzle is still solvable using `TechniqueSolver`
-    /// 4. If solvable, keep the cell removed; otherwise, restore it
-    ///
-    /// The resulting puzzle has the maximum number of removed cells while
-    /// maintaining a unique solution that can be found using human-like techniques.
+    /// Removes cells from a complete solution with the carve strategy.
     fn remove_cells<R>(&self, rng: &mut R, solution: &DigitGrid) -> DigitGrid
     where
         R: Rng,
     {
-        let mut problem = solution.clone();
-        let mut positions = Position::ALL;
-        positions.shuffle(rng);
-        for pos in positions {
-            let mut removed = problem.clone();
-            removed.set(pos, None);
-            let mut test_grid = TechniqueGrid::from_digit_grid(&removed);
-            let result = self.solver.solve_with_pass(&mut test_grid);
-            if result.is_ok_and(|(solved, _)| solved) {
-                problem = removed;
-            }
-        }
-        problem
+        self.carve.carve(self, rng, solution)
     }
 }
 
@@ -391,6 +639,9 @@ pub struct GeneratedPuzzle {
     /// The complete solution grid.
     pub solution: DigitGrid,
 
+    /// The region layout whose regions are the boxes of the puzzle.
+    pub regions: RegionLayout,
+
     /// The seed used to generate this puzzle.
     ///
     /// Can be used with [`PuzzleGenerator::generate_with_seed`] to regenerate
@@ -400,7 +651,7 @@ pub struct GeneratedPuzzle {
 
 #[cfg(test)]
 mod tests {
-    use numelace_core::DigitSet;
+    use numelace_core::{CandidateGrid, ChessRules, DigitPositions, DigitSet, Position};
 
     use super::*;
 
@@ -550,6 +801,184 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_constraint_is_respected() {
+        let solver = TechniqueSolver::with_all_techniques();
+        let rules = ChessRules::new().with_anti_knight(true);
+        let generator = PuzzleGenerator::new(&solver).with_constraint(rules);
+        let puzzle = generator.generate_with_seed(PuzzleSeed::from([7; 32]));
+
+        assert!(rules.check(&puzzle.solution).is_ok());
+        let mut grid = TechniqueGrid::from_digit_grid(&puzzle.problem);
+        assert!(generator.solve(&mut grid).unwrap());
+        assert_eq!(grid.to_digit_grid(), puzzle.solution);
+    }
+
+    #[test]
+    fn test_jigsaw_regions_are_respected() {
+        let solver = TechniqueSolver::with_all_techniques();
+        let generator = PuzzleGenerator::new(&solver)
+            .with_jigsaw_regions()
+            .with_fill_strategy(TransformFill);
+        let seed = PuzzleSeed::from([8; 32]);
+        let (puzzle, trace) = generator.generate_with_trace(seed);
+
+        assert!(!puzzle.regions.is_standard());
+        assert!(puzzle.regions.is_solved(&puzzle.solution));
+        assert_eq!(trace.regions, puzzle.regions);
+        let again = generator.generate_with_seed(seed);
+        assert_eq!(again.regions, puzzle.regions);
+        assert_eq!(again.problem, puzzle.problem);
+
+        let generator = PuzzleGenerator::new(&solver).with_regions(puzzle.regions);
+        let mut grid = TechniqueGrid::from_digit_grid_with_regions(&puzzle.problem, puzzle.regions);
+        assert!(generator.solve(&mut grid).unwrap());
+        assert_eq!(grid.to_digit_grid(), puzzle.solution);
+    }
+
+    #[test]
+    fn test_symmetry_is_respected() {
+        let solver = TechniqueSolver::with_all_techniques();
+        for symmetry in ClueSymmetry::ALL {
+            let generator = PuzzleGenerator::new(&solver).with_symmetry(symmetry);
+            let puzzle = generator.generate_with_seed(PuzzleSeed::from([3; 32]));
+
+            for pos in Position::ALL {
+                assert_eq!(
+                    puzzle.problem.get(pos).is_some(),
+                    puzzle.problem.get(symmetry.image(pos)).is_some(),
+                    "{symmetry}: {pos:?}"
+                );
+            }
+            let mut grid = TechniqueGrid::from_digit_grid(&puzzle.problem);
+            assert!(generator.solve(&mut grid).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_strategies_are_pluggable() {
+        /// Fills every grid with the same solution, ignoring the seed.
+        #[derive(Debug, Clone, Copy)]
+        struct FixedFill;
+
+        impl FillStrategy for FixedFill {
+            fn name(&self) -> &'static str {
+                "Fixed"
+            }
+
+            fn clone_box(&self) -> BoxedFillStrategy {
+                Box::new(*self)
+            }
+
+            fn fill(&self, _generator: &PuzzleGenerator<'_>, _rng: &mut dyn Rng) -> DigitGrid {
+                "483921657967345821251876493548132976729564138136798245372689514814253769695417382"
+                    .parse()
+                    .unwrap()
+            }
+        }
+
+        let solver = TechniqueSolver::with_all_techniques();
+        let generator = PuzzleGenerator::new(&solver)
+            .with_fill_strategy(FixedFill)
+            .with_carve_strategy(DigHoleCarve {
+                order: DigOrder::Snake,
+            });
+        assert_eq!(generator.fill_strategy().name(), "Fixed");
+        assert_eq!(generator.carve_strategy().name(), "Dig holes (snake)");
+
+        // Snake order does not use the seed, so the fixed solution always
+        // yields the same puzzle.
+        let puzzle1 = generator.generate_with_seed(PuzzleSeed::from([1; 32]));
+        let puzzle2 = generator
+            .clone()
+            .generate_with_seed(PuzzleSeed::from([2; 32]));
+        assert_eq!(
+            puzzle1.solution,
+            FixedFill.fill(&generator, &mut rand::rng())
+        );
+        assert_eq!(puzzle1.problem, puzzle2.problem);
+        assert!(generator.is_solvable(&puzzle1.problem));
+    }
+
+    #[test]
+    fn test_transform_fill_produces_valid_varied_grids() {
+        let solver = TechniqueSolver::with_all_techniques();
+        let generator = PuzzleGenerator::new(&solver).with_fill_strategy(TransformFill);
+        let mut rng = Pcg64::from_seed([9u8; 32]);
+
+        let solutions = (0..20)
+            .map(|_| generator.generate_solution(&mut rng))
+            .collect::<Vec<_>>();
+        for solution in &solutions {
+            let mut candidate_grid = CandidateGrid::new();
+            for pos in Position::ALL {
+                candidate_grid.place(pos, solution.get(pos).unwrap());
+            }
+            assert!(candidate_grid.is_solved().unwrap(), "{solution}");
+        }
+        assert!(
+            solutions[1..]
+                .iter()
+                .any(|solution| *solution != solutions[0])
+        );
+
+        let puzzle1 = generator.generate_with_seed(PuzzleSeed::from([4; 32]));
+        let puzzle2 = generator.generate_with_seed(PuzzleSeed::from([4; 32]));
+        assert_eq!(puzzle1.solution, puzzle2.solution);
+        assert!(generator.is_solvable(&puzzle1.problem));
+    }
+
+    #[test]
+    fn test_transform_fill_respects_constraints() {
+        let solver = TechniqueSolver::with_all_techniques();
+        let rules = ChessRules::new().with_anti_king(true);
+        let generator = PuzzleGenerator::new(&solver)
+            .with_fill_strategy(TransformFill)
+            .with_constraint(rules);
+        let mut rng = Pcg64::from_seed([6u8; 32]);
+
+        let solution = generator.generate_solution(&mut rng);
+        assert!(rules.check(&solution).is_ok());
+    }
+
+    #[test]
+    fn test_dig_orders_keep_puzzles_solvable() {
+        let solver = TechniqueSolver::with_all_techniques();
+        for order in DigOrder::ALL {
+            let generator =
+                PuzzleGenerator::new(&solver).with_carve_strategy(DigHoleCarve { order });
+            let puzzle = generator.generate_with_seed(PuzzleSeed::from([11; 32]));
+            let mut grid = TechniqueGrid::from_digit_grid(&puzzle.problem);
+            assert!(generator.solve(&mut grid).unwrap(), "{order:?}");
+            assert_eq!(grid.to_digit_grid(), puzzle.solution, "{order:?}");
+        }
+    }
+
+    #[test]
+    fn test_trace_follows_the_seeded_puzzle() {
+        let solver = TechniqueSolver::with_all_techniques();
+        let generator = PuzzleGenerator::new(&solver);
+        let seed = PuzzleSeed::from([3; 32]);
+
+        let (puzzle, trace) = generator.generate_with_trace(seed);
+        let untraced = generator.generate_with_seed(seed);
+
+        assert_eq!(puzzle.problem, untraced.problem);
+        assert_eq!(trace.solution, puzzle.solution);
+        assert_eq!(trace.carve_strategy, generator.carve_strategy().name());
+        assert!(trace.solver_runs >= trace.uniqueness_checks());
+        let removed: usize = trace
+            .removal_order()
+            .into_iter()
+            .map(DigitPositions::len)
+            .sum();
+        let givens = Position::ALL
+            .into_iter()
+            .filter(|&pos| puzzle.problem.get(pos).is_some())
+            .count();
+        assert_eq!(removed + givens, 81);
+    }
+
     #[test]
     fn test_remove_cells_removes_at_least_some_cells() {
         let solver = TechniqueSolver::with_all_techniques();
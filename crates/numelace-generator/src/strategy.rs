@@ -0,0 +1,338 @@
+use std::fmt::Debug;
+
+use numelace_core::{CandidateGrid, Digit, DigitGrid, Position};
+use numelace_solver::{TechniqueGrid, backtrack};
+use rand::{Rng, RngExt as _, seq::SliceRandom as _};
+
+use crate::PuzzleGenerator;
+
+/// Produces the complete solution grid a puzzle is carved from.
+///
+/// Implementations must return a grid that satisfies the classic rules and
+/// every constraint of the generator, and must draw all randomness from `rng`
+/// so that seeds stay reproducible.
+pub trait FillStrategy: Debug + Send + Sync {
+    /// Returns a short, human-readable name of the strategy.
+    fn name(&self) -> &'static str;
+
+    /// Returns a boxed clone of the strategy.
+    fn clone_box(&self) -> BoxedFillStrategy;
+
+    /// Generates a complete solution grid.
+    fn fill(&self, generator: &PuzzleGenerator<'_>, rng: &mut dyn Rng) -> DigitGrid;
+}
+
+/// A boxed [`FillStrategy`].
+pub type BoxedFillStrategy = Box<dyn FillStrategy>;
+
+impl Clone for BoxedFillStrategy {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+/// Removes givens from a solution grid to produce the problem.
+///
+/// Implementations must only remove cells while
+/// [`PuzzleGenerator::is_solvable`] holds, so that puzzles keep a unique
+/// solution reachable by the generator's techniques, and must respect
+/// [`PuzzleGenerator::symmetry`]. All randomness must come from `rng`.
+pub trait CarveStrategy: Debug + Send + Sync {
+    /// Returns a short, human-readable name of the strategy.
+    fn name(&self) -> &'static str;
+
+    /// Returns a boxed clone of the strategy.
+    fn clone_box(&self) -> BoxedCarveStrategy;
+
+    /// Returns the problem left after removing cells from `solution`.
+    fn carve(
+        &self,
+        generator: &PuzzleGenerator<'_>,
+        rng: &mut dyn Rng,
+        solution: &DigitGrid,
+    ) -> DigitGrid;
+}
+
+/// A boxed [`CarveStrategy`].
+pub type BoxedCarveStrategy = Box<dyn CarveStrategy>;
+
+impl Clone for BoxedCarveStrategy {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+/// Fills the grid by randomized backtracking with solver assistance.
+///
+/// This is the default [`FillStrategy`]:
+/// 1. Fill the first row with shuffled digits 1-9
+/// 2. Fill the remaining cells in the top-left box (avoiding the first row)
+/// 3. Use backtracking with solver assistance to fill the remaining cells
+///
+/// Steps 1 and 2 are skipped when variant constraints are set, since the
+/// random prefix might break them; the randomized backtracking alone then
+/// produces the grid. Step 2 is also skipped for jigsaw layouts, where the
+/// digits left for the first region can clash with the first row in other
+/// columns.
+///
+/// The backtracking uses [`numelace_solver::backtrack::find_best_assumption`] to
+/// select cells with minimum candidates (MRV heuristic), and the solver is used
+/// to eliminate obviously impossible candidates, making the search more efficient.
+/// An attempt that keeps running into contradictions starts over from step 1.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct BacktrackingFill;
+
+impl FillStrategy for BacktrackingFill {
+    fn name(&self) -> &'static str {
+        "Backtracking"
+    }
+
+    fn clone_box(&self) -> BoxedFillStrategy {
+        Box::new(*self)
+    }
+
+    fn fill(&self, generator: &PuzzleGenerator<'_>, rng: &mut dyn Rng) -> DigitGrid {
+        loop {
+            if let Some(grid) = try_backtracking_fill(generator, rng) {
+                return grid;
+            }
+        }
+    }
+}
+
+/// Contradictions [`BacktrackingFill`] may run into before it starts over.
+///
+/// A grid usually fills after a handful of contradictions, but an unlucky
+/// early guess on a jigsaw layout can lead into a branch with no solution that
+/// takes minutes to exhaust. Starting over with new random guesses is far
+/// quicker.
+const FILL_RESTART_CONTRADICTIONS: usize = 200;
+
+/// Runs one attempt of [`BacktrackingFill`], returning `None` once it hits
+/// [`FILL_RESTART_CONTRADICTIONS`] contradictions.
+fn try_backtracking_fill(generator: &PuzzleGenerator<'_>, rng: &mut dyn Rng) -> Option<DigitGrid> {
+    let mut grid = CandidateGrid::new().with_regions(*generator.regions());
+
+    if generator.constraints().is_empty() {
+        fill_first_row_and_box(rng, &mut grid);
+    }
+
+    let grid = TechniqueGrid::from(grid);
+
+    // Step 3: Fill the rest of the grid using backtracking with solver assistance
+    let mut stack = vec![];
+    let assumption = backtrack::find_best_assumption(&grid);
+    stack.push((grid, assumption));
+
+    let mut contradictions = 0;
+    while let Some((mut grid, (pos, mut digits))) = stack.pop() {
+        if digits.is_empty() {
+            continue;
+        }
+        // Pick a random candidate digit and try it
+        let digit = digits.pop_nth(rng.random_range(0..digits.len())).unwrap();
+        stack.push((grid.clone(), (pos, digits)));
+        grid.place(pos, digit);
+        // Use the solver to fill in cells that can be determined logically
+        let Ok(solved) = generator.solve(&mut grid) else {
+            // Contradiction found, backtrack
+            contradictions += 1;
+            if contradictions >= FILL_RESTART_CONTRADICTIONS {
+                return None;
+            }
+            continue;
+        };
+        if solved {
+            return Some(grid.to_digit_grid());
+        }
+        // Pick the next cell to fill
+        let assumption = backtrack::find_best_assumption(&grid);
+        stack.push((grid, assumption));
+    }
+    unreachable!("Failed to generate complete grid - this should never happen");
+}
+
+fn fill_first_row_and_box(rng: &mut dyn Rng, grid: &mut CandidateGrid) {
+    // Step 1: Fill the entire first row with shuffled digits 1-9
+    let mut top_row = Digit::ALL;
+    top_row.shuffle(rng);
+    for (col, digit) in (0..9).zip(top_row) {
+        let pos = Position::new(0, col);
+        grid.place(pos, digit);
+    }
+    if !grid.regions().is_standard() {
+        return;
+    }
+
+    // Step 2: Fill the remaining 6 cells in the top-left box (3x3)
+    // top_row[0..3] are already used in the first row of the top-left box
+    // top_row[3..9] contains the 6 digits unused in the top-left box
+    let mut remaining: [Digit; 6] = top_row[3..9].try_into().unwrap();
+    remaining.shuffle(rng);
+    for (i, digit) in (3..9).zip(remaining) {
+        let pos = Position::from_box(grid.regions(), 0, i); // Box 0, positions 3-8
+        grid.place(pos, digit);
+    }
+}
+
+/// Solution grids that [`TransformFill`] starts from.
+const SEED_GRIDS: [&str; 4] = [
+    "123456789456789123789123456234567891567891234891234567345678912678912345912345678",
+    "483921657967345821251876493548132976729564138136798245372689514814253769695417382",
+    "732914586645873912981526743359182674478659321126347859564791238813265497297438165",
+    "781596423932174568654382179346758291215943786897261354429815637568437912173629845",
+];
+
+/// Fills the grid by shuffling a stored solution.
+///
+/// A seed grid is picked and transformed in ways that keep every row, column,
+/// and box valid: the digits are relabelled, the bands and stacks are
+/// reordered, the rows within each band and the columns within each stack are
+/// reordered, and the grid is transposed half of the time. Producing a grid
+/// takes no search at all, which makes this cheaper than [`BacktrackingFill`],
+/// though the solutions are limited to those equivalent to a seed grid.
+///
+/// Variant constraints and jigsaw regions are not preserved by these
+/// transformations, so when the generator has any, this falls back to
+/// [`BacktrackingFill`].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct TransformFill;
+
+impl FillStrategy for TransformFill {
+    fn name(&self) -> &'static str {
+        "Transform"
+    }
+
+    fn clone_box(&self) -> BoxedFillStrategy {
+        Box::new(*self)
+    }
+
+    fn fill(&self, generator: &PuzzleGenerator<'_>, rng: &mut dyn Rng) -> DigitGrid {
+        if !generator.constraints().is_empty() || !generator.regions().is_standard() {
+            return BacktrackingFill.fill(generator, rng);
+        }
+
+        let seed: DigitGrid = SEED_GRIDS[rng.random_range(0..SEED_GRIDS.len())]
+            .parse()
+            .expect("seed grids are valid");
+        let mut labels = Digit::ALL;
+        labels.shuffle(rng);
+        let rows = shuffled_lines(rng);
+        let cols = shuffled_lines(rng);
+        let transpose = rng.random_bool(0.5);
+
+        let mut grid = DigitGrid::new();
+        for pos in Position::ALL {
+            let (row, col) = if transpose {
+                (pos.col(), pos.row())
+            } else {
+                (pos.row(), pos.col())
+            };
+            let source = Position::new(rows[usize::from(row)], cols[usize::from(col)]);
+            let digit = seed[source].expect("seed grids are complete");
+            grid.set(pos, Some(labels[usize::from(digit.value() - 1)]));
+        }
+        grid
+    }
+}
+
+/// Returns a line order that keeps every line within a band of three.
+fn shuffled_lines(rng: &mut dyn Rng) -> [u8; 9] {
+    let mut bands = [0, 1, 2];
+    bands.shuffle(rng);
+    let mut lines = [0; 9];
+    for (chunk, band) in lines.chunks_mut(3).zip(bands) {
+        let mut offsets = [0, 1, 2];
+        offsets.shuffle(rng);
+        for (line, offset) in chunk.iter_mut().zip(offsets) {
+            *line = band * 3 + offset;
+        }
+    }
+    lines
+}
+
+/// Order in which [`DigHoleCarve`] visits cells.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DigOrder {
+    /// A shuffled order, different for every seed.
+    #[default]
+    Random,
+    /// Rows top to bottom, alternating left-to-right and right-to-left.
+    ///
+    /// Digging neighbouring cells one after another tends to leave fewer
+    /// givens in the early rows and more work for the later techniques.
+    Snake,
+}
+
+impl DigOrder {
+    /// All dig orders.
+    pub const ALL: [Self; 2] = [Self::Random, Self::Snake];
+
+    fn positions(self, rng: &mut dyn Rng) -> [Position; 81] {
+        match self {
+            Self::Random => {
+                let mut positions = Position::ALL;
+                positions.shuffle(rng);
+                positions
+            }
+            Self::Snake => {
+                let mut positions = Position::ALL;
+                for row in positions.chunks_mut(9).skip(1).step_by(2) {
+                    row.reverse();
+                }
+                positions
+            }
+        }
+    }
+}
+
+/// Digs holes one cell at a time, keeping each removal the puzzle survives.
+///
+/// This is the default [`CarveStrategy`]:
+///
+/// 1. Visit all 81 cell positions in the [`DigOrder`]
+/// 2. For each position, try removing the cell together with its symmetric image
+/// 3. Verify the puzzle is still solvable using the generator's techniques
+/// 4. If solvable, keep the cells removed; otherwise, restore them
+///
+/// The resulting puzzle has the maximum number of removed cells while
+/// maintaining a unique solution that can be found using human-like techniques.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DigHoleCarve {
+    /// Order in which cells are tried.
+    pub order: DigOrder,
+}
+
+impl CarveStrategy for DigHoleCarve {
+    fn name(&self) -> &'static str {
+        match self.order {
+            DigOrder::Random => "Dig holes (random)",
+            DigOrder::Snake => "Dig holes (snake)",
+        }
+    }
+
+    fn clone_box(&self) -> BoxedCarveStrategy {
+        Box::new(*self)
+    }
+
+    fn carve(
+        &self,
+        generator: &PuzzleGenerator<'_>,
+        rng: &mut dyn Rng,
+        solution: &DigitGrid,
+    ) -> DigitGrid {
+        let mut problem = solution.clone();
+        for pos in self.order.positions(rng) {
+            if problem.get(pos).is_none() {
+                continue;
+            }
+            let mut removed = problem.clone();
+            removed.set(pos, None);
+            removed.set(generator.symmetry().image(pos), None);
+            if generator.is_solvable(&removed) {
+                problem = removed;
+            }
+        }
+        problem
+    }
+}
@@ -0,0 +1,110 @@
+//! Symmetry patterns for the givens of generated puzzles.
+
+use std::{fmt::Display, str::FromStr};
+
+use numelace_core::Position;
+
+/// Symmetry that the givens of a generated puzzle follow.
+///
+/// The generator removes the cells mapped onto each other by the symmetry
+/// together, so the remaining givens form a symmetric pattern. Stronger
+/// symmetries leave fewer cells removable, which tends to produce puzzles with
+/// more givens.
+///
+/// # Examples
+///
+/// ```
+/// use numelace_core::Position;
+/// use numelace_generator::ClueSymmetry;
+///
+/// let pos = Position::new(0, 1);
+/// assert_eq!(ClueSymmetry::Rotational.image(pos), Position::new(8, 7));
+/// assert_eq!(ClueSymmetry::None.image(pos), pos);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ClueSymmetry {
+    /// Givens are placed without any pattern.
+    #[default]
+    None,
+    /// Givens are unchanged by a 180-degree rotation about the center cell.
+    Rotational,
+    /// Givens are mirrored across the middle column.
+    Mirror,
+    /// Givens are mirrored across the main (top-left to bottom-right) diagonal.
+    Diagonal,
+}
+
+impl ClueSymmetry {
+    /// All symmetries, in the order they are presented to the user.
+    pub const ALL: [Self; 4] = [Self::None, Self::Rotational, Self::Mirror, Self::Diagonal];
+
+    /// Returns the stable identifier used for serialization.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Rotational => "rotational",
+            Self::Mirror => "mirror",
+            Self::Diagonal => "diagonal",
+        }
+    }
+
+    /// Returns the cell that `pos` is mapped onto by the symmetry.
+    #[must_use]
+    pub const fn image(self, pos: Position) -> Position {
+        let (row, col) = (pos.row(), pos.col());
+        match self {
+            Self::None => pos,
+            Self::Rotational => Position::new(8 - row, 8 - col),
+            Self::Mirror => Position::new(row, 8 - col),
+            Self::Diagonal => Position::new(col, row),
+        }
+    }
+}
+
+impl Display for ClueSymmetry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Error returned when parsing an unknown [`ClueSymmetry`] identifier.
+#[derive(Debug, Clone, PartialEq, Eq, derive_more::Display, derive_more::Error)]
+#[display("unknown clue symmetry: {name}")]
+pub struct UnknownClueSymmetryError {
+    #[error(not(source))]
+    name: String,
+}
+
+impl FromStr for ClueSymmetry {
+    type Err = UnknownClueSymmetryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|symmetry| symmetry.as_str() == s)
+            .ok_or_else(|| UnknownClueSymmetryError { name: s.to_owned() })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_image_is_an_involution() {
+        for symmetry in ClueSymmetry::ALL {
+            for pos in Position::ALL {
+                assert_eq!(symmetry.image(symmetry.image(pos)), pos, "{symmetry}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_str_round_trip() {
+        for symmetry in ClueSymmetry::ALL {
+            assert_eq!(symmetry.as_str().parse(), Ok(symmetry));
+        }
+        assert!("spiral".parse::<ClueSymmetry>().is_err());
+    }
+}
@@ -0,0 +1,202 @@
+//! Traces of the generator's internals, for tuning strategies.
+
+use numelace_core::{
+    Digit, DigitGrid, DigitPositions, DigitSet, Position, PositionIndexedArray, RegionLayout,
+};
+
+/// What the generator did while producing one puzzle.
+///
+/// Returned by [`PuzzleGenerator::generate_with_trace`]. Every solvability
+/// check of the carve strategy becomes a [`CarveStep`], in the order the
+/// strategy made them, so the trace shows the removal order, how many checks
+/// were rejected, and how the board opened up along the way.
+///
+/// [`PuzzleGenerator::generate_with_trace`]: crate::PuzzleGenerator::generate_with_trace
+#[derive(Debug, Clone, PartialEq)]
+pub struct GenerationTrace {
+    /// Name of the fill strategy.
+    pub fill_strategy: String,
+    /// Name of the carve strategy.
+    pub carve_strategy: String,
+    /// The solution the carve strategy started from.
+    pub solution: DigitGrid,
+    /// Region layout of the puzzle.
+    pub regions: RegionLayout,
+    /// Solvability checks of the carve strategy, in order.
+    pub steps: Vec<CarveStep>,
+    /// Runs of [`PuzzleGenerator::solve`](crate::PuzzleGenerator::solve),
+    /// counting both the fill and the carve strategy.
+    pub solver_runs: usize,
+}
+
+/// One solvability check of a carve strategy.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CarveStep {
+    /// Givens removed by this step, relative to the last kept problem.
+    pub removed: DigitPositions,
+    /// Whether the problem stayed solvable, so the removal was kept.
+    pub kept: bool,
+    /// Candidate entropy of the checked problem, in bits.
+    ///
+    /// See [`candidate_entropy`].
+    pub entropy: f64,
+}
+
+impl GenerationTrace {
+    pub(crate) fn new(fill_strategy: &str, carve_strategy: &str) -> Self {
+        Self {
+            fill_strategy: fill_strategy.to_owned(),
+            carve_strategy: carve_strategy.to_owned(),
+            solution: DigitGrid::new(),
+            regions: RegionLayout::standard(),
+            steps: Vec::new(),
+            solver_runs: 0,
+        }
+    }
+
+    /// Records a solvability check of `problem`.
+    pub(crate) fn record_check(&mut self, problem: &DigitGrid, kept: bool) {
+        let current = self.problem_after(self.steps.len());
+        let removed = Position::ALL
+            .into_iter()
+            .filter(|&pos| current.get(pos).is_some() && problem.get(pos).is_none())
+            .collect();
+        self.steps.push(CarveStep {
+            removed,
+            kept,
+            entropy: candidate_entropy(&self.regions, problem),
+        });
+    }
+
+    /// Returns the number of solvability checks, which is the number of
+    /// times the carve strategy asked whether a removal keeps the solution
+    /// unique.
+    #[must_use]
+    pub fn uniqueness_checks(&self) -> usize {
+        self.steps.len()
+    }
+
+    /// Returns the number of checks whose removal was kept.
+    #[must_use]
+    pub fn kept_removals(&self) -> usize {
+        self.steps.iter().filter(|step| step.kept).count()
+    }
+
+    /// Returns the givens removed by each kept step, in removal order.
+    #[must_use]
+    pub fn removal_order(&self) -> Vec<DigitPositions> {
+        self.steps
+            .iter()
+            .filter(|step| step.kept)
+            .map(|step| step.removed)
+            .collect()
+    }
+
+    /// Returns the problem after the first `step_count` steps, keeping the
+    /// removals that were kept.
+    ///
+    /// After all steps this is the generated problem.
+    #[must_use]
+    pub fn problem_after(&self, step_count: usize) -> DigitGrid {
+        let mut problem = self.solution.clone();
+        for step in self.steps.iter().take(step_count).filter(|step| step.kept) {
+            for pos in step.removed {
+                problem.clear(pos);
+            }
+        }
+        problem
+    }
+}
+
+/// Returns the candidates of each cell of `problem` left by its givens.
+///
+/// Only the rows, columns and the boxes of `regions` are taken into account,
+/// so the counts measure how open the board is before any technique runs.
+/// Givens count as a single candidate.
+#[must_use]
+pub fn candidate_counts(regions: &RegionLayout, problem: &DigitGrid) -> PositionIndexedArray<u8> {
+    let mut counts = PositionIndexedArray::default();
+    for pos in Position::ALL {
+        counts[pos] = if problem.get(pos).is_some() {
+            1
+        } else {
+            let seen: DigitSet = pos
+                .house_peers(regions)
+                .into_iter()
+                .filter_map(|peer| problem.get(peer))
+                .collect();
+            #[expect(clippy::cast_possible_truncation)]
+            let count = (Digit::ALL.len() - seen.len()) as u8;
+            count
+        };
+    }
+    counts
+}
+
+/// Returns the candidate entropy of `problem`, in bits.
+///
+/// This sums `log2` of the [`candidate_counts`] of every cell: the number of
+/// bits needed to pick one candidate per cell, ignoring that the picks must
+/// agree. It is 0 for a complete grid and grows as givens are removed.
+#[must_use]
+pub fn candidate_entropy(regions: &RegionLayout, problem: &DigitGrid) -> f64 {
+    candidate_counts(regions, problem)
+        .iter()
+        .filter(|&&count| count > 1)
+        .map(|&count| f64::from(count).log2())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOLUTION: &str =
+        "185362947793148526246795183564239871931874265827516394318427659672951438459683712";
+
+    #[test]
+    fn test_candidate_entropy_grows_as_givens_are_removed() {
+        let regions = RegionLayout::standard();
+        let solution: DigitGrid = SOLUTION.parse().unwrap();
+        assert!(candidate_entropy(&regions, &solution).abs() < f64::EPSILON);
+
+        let mut problem = solution.clone();
+        problem.clear(Position::new(0, 0));
+        assert_eq!(candidate_counts(&regions, &problem)[Position::new(0, 0)], 1);
+        assert!(candidate_entropy(&regions, &problem).abs() < f64::EPSILON);
+
+        let empty = DigitGrid::new();
+        assert_eq!(candidate_counts(&regions, &empty)[Position::new(4, 4)], 9);
+        assert!((candidate_entropy(&regions, &empty) - 81.0 * 9.0_f64.log2()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_steps_track_removals_from_the_last_kept_problem() {
+        let solution: DigitGrid = SOLUTION.parse().unwrap();
+        let mut trace = GenerationTrace::new("fill", "carve");
+        trace.solution = solution.clone();
+
+        let mut first = solution.clone();
+        first.clear(Position::new(0, 0));
+        trace.record_check(&first, true);
+        let mut rejected = first.clone();
+        rejected.clear(Position::new(1, 1));
+        rejected.clear(Position::new(7, 7));
+        trace.record_check(&rejected, false);
+        let mut second = first.clone();
+        second.clear(Position::new(2, 2));
+        trace.record_check(&second, true);
+
+        assert_eq!(trace.uniqueness_checks(), 3);
+        assert_eq!(trace.kept_removals(), 2);
+        assert_eq!(trace.steps[1].removed.len(), 2);
+        assert_eq!(
+            trace.removal_order(),
+            [
+                DigitPositions::from_elem(Position::new(0, 0)),
+                DigitPositions::from_elem(Position::new(2, 2)),
+            ]
+        );
+        assert_eq!(trace.problem_after(3), second);
+    }
+}
@@ -19,6 +19,9 @@
 //! - **`backtrack_solver_fundamental`**: Solves puzzles using backtracking with fundamental
 //!   techniques. Includes all puzzles, even those requiring backtracking. For puzzles
 //!   with multiple solutions (`empty`, `ultra_sparse`), limits to first 100 solutions.
+//! - **`backtrack_solver_hard`**: Solves well-known 17-clue and hard puzzles using
+//!   backtracking with all techniques. `first` stops at the first solution, as the
+//!   app's solvability check does; `unique` also proves there is no second one.
 //!
 //! # Test Data
 //!
@@ -32,6 +35,10 @@
 //! - **`dense`** (60 given): Nearly solved puzzle with many clues (unique solution)
 //! - **`solution`** (81 given): Fully solved grid
 //!
+//! The hard backtracking benchmark uses published puzzles: four 17-clue puzzles,
+//! which the techniques solve on their own, and five puzzles known for needing
+//! deep search (`escargot` and `hard_1` to `hard_4`).
+//!
 //! The intermediate-technique benchmark uses a puzzle generated by the
 //! `generate_puzzle` example (seed: `68962c30a990480c830cba1f9d75d677f28c744ad95c060224465902ce8f4f4c`).
 //! The upper-intermediate benchmark uses a puzzle generated by the
@@ -98,6 +105,37 @@ const ADVANCED_PROBLEM: &str =
 const ADVANCED_SOLUTION: &str =
     "972516348453879216816243957368751429745928163129364785531487692294635871687192534";
 
+// 17-clue puzzles (solvable by all techniques without backtracking)
+const HARD_17_CLUE_PROBLEMS: [&str; 4] = [
+    "000000010400000000020000000000050407008000300001090000300400200050100000000806000",
+    "000000012003600000000007000410020000000500300700000600280000040000300500000000000",
+    "000000012008030000000000040120500000000004700060000000507000300000620000000100000",
+    "000000012050400000000000030700600400001000000000080000920000800000510700000003000",
+];
+// puzzles that need several assumptions even with all techniques
+const HARD_PROBLEMS: [(&str, &str); 5] = [
+    (
+        "escargot",
+        "100007090030020008009600500005300900010080002600004000300000010040000007007000300",
+    ),
+    (
+        "hard_1",
+        "000000039000001005003050800008090006070002000100400000009080050020000600400700000",
+    ),
+    (
+        "hard_2",
+        "100000002090400050006000700050903000000070000000850040700000600030009080002000001",
+    ),
+    (
+        "hard_3",
+        "000000012000000003002300400001800005060070800000009000008500000900040500470006000",
+    ),
+    (
+        "hard_4",
+        "800000000003600000070090200050007000000045700000100030001000068008500010090000400",
+    ),
+];
+
 fn bench_technique_solver_cases(
     c: &mut Criterion,
     bench_name: &'static str,
@@ -289,6 +327,55 @@ fn bench_backtrack_solver_fundamental(c: &mut Criterion) {
     }
 }
 
+fn bench_backtrack_solver_hard(c: &mut Criterion) {
+    let puzzles = HARD_17_CLUE_PROBLEMS
+        .iter()
+        .enumerate()
+        .map(|(i, grid)| (format!("17_clue_{}", i + 1), *grid))
+        .chain(
+            HARD_PROBLEMS
+                .iter()
+                .map(|(param, grid)| ((*param).to_owned(), *grid)),
+        );
+
+    let solver = BacktrackSolver::with_all_techniques();
+
+    for (param, grid) in puzzles {
+        let grid = TechniqueGrid::from(DigitGrid::from_str(grid).unwrap());
+        // ensure that the puzzle has a unique solution
+        let solutions = solver
+            .solve_with_step(grid.clone())
+            .unwrap()
+            .take(2)
+            .count();
+        assert_eq!(solutions, 1, "{param} should have a unique solution");
+
+        c.bench_with_input(
+            BenchmarkId::new("backtrack_solver_hard", format!("{param}_first")),
+            &grid,
+            |b, grid| {
+                b.iter_batched(
+                    || grid.clone(),
+                    |grid| solver.solve_with_step(grid).unwrap().next(),
+                    BatchSize::SmallInput,
+                );
+            },
+        );
+
+        c.bench_with_input(
+            BenchmarkId::new("backtrack_solver_hard", format!("{param}_unique")),
+            &grid,
+            |b, grid| {
+                b.iter_batched(
+                    || grid.clone(),
+                    |grid| solver.solve_with_step(grid).unwrap().take(2).count(),
+                    BatchSize::SmallInput,
+                );
+            },
+        );
+    }
+}
+
 criterion_group!(
     name = benches_technique_fundamental;
     config =
@@ -343,6 +430,7 @@ criterion_group!(
             .measurement_time(Duration::from_secs(12));
     targets =
         bench_backtrack_solver_fundamental,
+        bench_backtrack_solver_hard,
 );
 
 criterion_main!(
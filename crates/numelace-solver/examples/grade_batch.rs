@@ -0,0 +1,134 @@
+//! Grades a batch of puzzles and prints a CSV report.
+//!
+//! Puzzles are read one per line in the 81-character format, where `.` or `0`
+//! marks an empty cell. Empty lines and lines starting with `#` are skipped.
+//! The report has one row per puzzle with its clue count, solution count,
+//! need for backtracking, difficulty tier, and per-technique counts.
+//!
+//! # Usage
+//!
+//! ```sh
+//! cargo run --release -p numelace-solver --example grade_batch -- puzzles.txt > report.csv
+//! ```
+//!
+//! Without a file, puzzles are read from standard input:
+//!
+//! ```sh
+//! cat puzzles.txt | cargo run --release -p numelace-solver --example grade_batch
+//! ```
+//!
+//! Select the technique set (all, fundamental, or basic) and the thread count:
+//!
+//! ```sh
+//! cargo run --release -p numelace-solver --example grade_batch -- --solver basic --threads 4 puzzles.txt
+//! ```
+//!
+//! Give up on puzzles whose backtracking needs more assumptions than allowed:
+//!
+//! ```sh
+//! cargo run --release -p numelace-solver --example grade_batch -- --max-assumptions 1000 puzzles.txt
+//! ```
+
+use std::{
+    fs,
+    io::{self, Read as _},
+    num::NonZero,
+    path::PathBuf,
+    process::ExitCode,
+    str::FromStr as _,
+};
+
+use clap::{Parser, ValueEnum};
+use numelace_core::DigitGrid;
+use numelace_solver::{BatchGrader, SolverBudget, TechniqueSolver, technique};
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum SolverKind {
+    All,
+    Fundamental,
+    Basic,
+}
+
+#[derive(Debug, Parser)]
+#[command(author, version, about)]
+struct Args {
+    /// File with one puzzle per line; standard input when omitted.
+    #[arg(value_name = "FILE")]
+    input: Option<PathBuf>,
+
+    /// Solver technique set to grade with.
+    #[arg(long, value_name = "KIND", default_value = "all")]
+    solver: SolverKind,
+
+    /// Number of grading threads; the available parallelism when omitted.
+    #[arg(long, value_name = "COUNT")]
+    threads: Option<NonZero<usize>>,
+
+    /// Assumptions after which a puzzle's solution count is reported as undetermined.
+    #[arg(long, value_name = "COUNT")]
+    max_assumptions: Option<usize>,
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    let input = match read_input(args.input.as_ref()) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("failed to read puzzles: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let puzzles = match parse_puzzles(&input) {
+        Ok(puzzles) => puzzles,
+        Err(message) => {
+            eprintln!("{message}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let mut grader = BatchGrader::with_solver(build_solver(args.solver));
+    if let Some(threads) = args.threads {
+        grader = grader.with_threads(threads);
+    }
+    if let Some(max_assumptions) = args.max_assumptions {
+        grader = grader.with_budget(SolverBudget::new().with_max_steps(max_assumptions));
+    }
+
+    let reports = grader.grade_batch(&puzzles);
+    if let Err(err) = grader.write_csv(&mut io::stdout().lock(), &reports) {
+        eprintln!("failed to write report: {err}");
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+fn read_input(path: Option<&PathBuf>) -> io::Result<String> {
+    if let Some(path) = path {
+        return fs::read_to_string(path);
+    }
+    let mut input = String::new();
+    io::stdin().read_to_string(&mut input)?;
+    Ok(input)
+}
+
+/// Parses one puzzle per line, naming the first line that is not a puzzle.
+fn parse_puzzles(input: &str) -> Result<Vec<DigitGrid>, String> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(number, line)| {
+            DigitGrid::from_str(line).map_err(|err| format!("line {number}: {err}"))
+        })
+        .collect()
+}
+
+fn build_solver(kind: SolverKind) -> TechniqueSolver {
+    let techniques = match kind {
+        SolverKind::All => technique::all_techniques(),
+        SolverKind::Fundamental => technique::fundamental_techniques(),
+        SolverKind::Basic => technique::basic_techniques(),
+    };
+    TechniqueSolver::new(techniques)
+}
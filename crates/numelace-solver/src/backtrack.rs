@@ -15,6 +15,22 @@
 //! - Reducing the number of branches explored
 //! - Improving pruning effectiveness
 //!
+//! [`find_best_assumption_by_degree`] breaks ties between positions with the
+//! same number of candidates by the degree heuristic: the position with the
+//! most undecided peers constrains the most cells once decided, so conflicts
+//! surface sooner. This only pays off when techniques propagate each
+//! assumption; without them, the chosen positions scatter across the grid and
+//! dead ends are found late, so the plain row-major tie-break works better.
+//!
+//! # Value Ordering
+//!
+//! Once a position is chosen, [`least_constraining_order`] orders its
+//! candidates so that the digit ruling out the fewest candidates of the
+//! undecided peers is tried first. This leaves the most room for the rest of
+//! the grid, so the first branch is more likely to lead to a solution. It
+//! does not shrink the search when every branch has to be explored, such as
+//! when proving a puzzle has no solution or a unique one.
+//!
 //! # Usage
 //!
 //! This module is used by both [`BacktrackSolver`](crate::BacktrackSolver) for
@@ -35,7 +51,9 @@
 //! println!("Candidates: {:?}", candidates);
 //! ```
 
-use numelace_core::{DigitSet, Position};
+use std::cmp::Reverse;
+
+use numelace_core::{Digit, DigitSet, Position};
 
 use crate::TechniqueGrid;
 
@@ -93,6 +111,89 @@ pub fn find_best_assumption(grid: &TechniqueGrid) -> (Position, DigitSet) {
     (pos, grid.candidates_at(pos))
 }
 
+/// Finds the best position to make an assumption for, breaking ties by degree.
+///
+/// Like [`find_best_assumption`], this selects a position with the minimum
+/// number of remaining candidates. Among those, it picks the one with the
+/// most undecided peers, then the first in row-major order.
+/// [`BacktrackSolver`](crate::BacktrackSolver) uses this when it has
+/// techniques; puzzle generation keeps [`find_best_assumption`] so that seeds
+/// keep producing the same grids.
+///
+/// # Panics
+///
+/// Panics under the same conditions as [`find_best_assumption`].
+///
+/// # Examples
+///
+/// ```
+/// use numelace_core::{Digit, Position};
+/// use numelace_solver::{TechniqueGrid, backtrack};
+///
+/// let mut grid = TechniqueGrid::new();
+/// grid.place(Position::new(0, 0), Digit::D1);
+///
+/// let (pos, candidates) = backtrack::find_best_assumption_by_degree(&grid);
+/// assert_eq!(grid.candidates_at(pos), candidates);
+/// ```
+#[must_use]
+pub fn find_best_assumption_by_degree(grid: &TechniqueGrid) -> (Position, DigitSet) {
+    let [empty, univalue_positions, positions @ ..] = grid.classify_positions::<10>();
+    assert!(empty.is_empty() && univalue_positions.len() < 81);
+
+    let undecided = !univalue_positions;
+    let pos = positions
+        .iter()
+        .find(|positions| !positions.is_empty())
+        .unwrap()
+        .iter()
+        .max_by_key(|pos| {
+            (
+                (grid.house_peers(*pos) & undecided).len(),
+                Reverse(pos.index()),
+            )
+        })
+        .unwrap();
+    (pos, grid.candidates_at(pos))
+}
+
+/// Orders candidate digits by the Least Constraining Value (LCV) heuristic.
+///
+/// Each digit is scored by how many undecided peers of `pos` still have it as
+/// a candidate, i.e. how many candidates placing it would remove. Digits with
+/// lower scores come first; ties keep the digit order.
+///
+/// # Examples
+///
+/// ```
+/// use numelace_core::{Digit, DigitSet, Position};
+/// use numelace_solver::{TechniqueGrid, backtrack};
+///
+/// let mut grid = TechniqueGrid::new();
+/// let pos = Position::new(0, 0);
+/// // Only r1c2 still allows 2 among the peers of r1c1 that can hold 1 or 2.
+/// for peer in grid.house_peers(pos) {
+///     if peer != Position::new(0, 1) {
+///         grid.remove_candidate(peer, Digit::D2);
+///     }
+/// }
+///
+/// let candidates = DigitSet::from_iter([Digit::D1, Digit::D2]);
+/// let order = backtrack::least_constraining_order(&grid, pos, candidates);
+/// assert_eq!(order, [Digit::D2, Digit::D1]);
+/// ```
+#[must_use]
+pub fn least_constraining_order(
+    grid: &TechniqueGrid,
+    pos: Position,
+    candidates: DigitSet,
+) -> Vec<Digit> {
+    let undecided_peers = grid.house_peers(pos) & !grid.univalue_positions();
+    let mut digits = candidates.iter().collect::<Vec<_>>();
+    digits.sort_by_cached_key(|digit| (grid.digit_positions(*digit) & undecided_peers).len());
+    digits
+}
+
 #[cfg(test)]
 mod tests {
     use std::str::FromStr as _;
@@ -217,4 +318,42 @@ mod tests {
         assert_eq!(candidates, actual_candidates);
         assert!(!candidates.is_empty());
     }
+
+    #[test]
+    fn test_find_best_assumption_by_degree_prefers_most_undecided_peers() {
+        let mut grid = CandidateGrid::new();
+        grid.place(Position::new(0, 0), Digit::D1);
+        grid.place(Position::new(0, 1), Digit::D2);
+
+        // Every undecided cell keeps 9 candidates except some peers of the
+        // placed digits; remove candidates so that r1c3 and r9c9 tie at two.
+        for digit in Digit::ALL.into_iter().skip(2) {
+            grid.remove_candidate(Position::new(0, 2), digit);
+            grid.remove_candidate(Position::new(8, 8), digit);
+        }
+
+        let grid = TechniqueGrid::from(grid);
+        assert_eq!(find_best_assumption(&grid).0, Position::new(0, 2));
+        // r9c9 has 20 undecided peers, r1c3 only 18.
+        let (pos, candidates) = find_best_assumption_by_degree(&grid);
+        assert_eq!(pos, Position::new(8, 8));
+        assert_eq!(candidates, grid.candidates_at(pos));
+    }
+
+    #[test]
+    fn test_least_constraining_order_keeps_all_candidates() {
+        let mut grid = CandidateGrid::new();
+        let pos = Position::new(4, 4);
+        // Only one peer still allows 9, all of them allow 1.
+        for peer in grid.house_peers(pos) {
+            if peer != Position::new(4, 0) {
+                grid.remove_candidate(peer, Digit::D9);
+            }
+        }
+
+        let grid = TechniqueGrid::from(grid);
+        let candidates = DigitSet::from_iter([Digit::D1, Digit::D5, Digit::D9]);
+        let order = least_constraining_order(&grid, pos, candidates);
+        assert_eq!(order, [Digit::D9, Digit::D1, Digit::D5]);
+    }
 }
@@ -4,20 +4,23 @@
 //! When techniques cannot make further progress, the solver makes assumptions and explores
 //! the search space to find solutions.
 
-use numelace_core::{Digit, DigitSet, Position};
+use numelace_core::{BoxedConstraint, CageSet, Constraint, Digit, EdgeConstraintSet, Position};
 
 use crate::{
-    BoxedTechnique, SolverError, TechniqueGrid, TechniqueSolver, TechniqueSolverStats, backtrack,
+    BoxedTechnique, SolverBudget, SolverError, TechniqueGrid, TechniqueSolver,
+    TechniqueSolverStats, backtrack, budget::BudgetTracker,
 };
 
 /// Statistics collected during backtracking solving.
 ///
-/// Tracks technique applications, assumptions made, and backtrack events.
+/// Tracks technique applications, assumptions made, backtrack events, and
+/// guesses avoided by techniques.
 #[derive(Debug, Clone)]
 pub struct BacktrackSolverStats {
     technique: TechniqueSolverStats,
     assumptions: Vec<(Position, Digit)>,
     backtrack_count: usize,
+    guesses_avoided: usize,
 }
 
 impl BacktrackSolverStats {
@@ -28,6 +31,7 @@ impl BacktrackSolverStats {
             technique,
             assumptions: vec![],
             backtrack_count: 0,
+            guesses_avoided: 0,
         }
     }
 
@@ -62,6 +66,17 @@ impl BacktrackSolverStats {
     pub fn backtrack_count(&self) -> usize {
         self.backtrack_count
     }
+
+    /// Returns the number of cells the techniques decided between assumptions.
+    ///
+    /// Each of these cells would have needed an assumption of its own without
+    /// the techniques interleaved in the search. Like [`assumptions`](Self::assumptions),
+    /// this counts along the path to the current solution; cells decided
+    /// before the first assumption are not included.
+    #[must_use]
+    pub fn guesses_avoided(&self) -> usize {
+        self.guesses_avoided
+    }
 }
 
 /// A solver that combines technique-based solving with backtracking.
@@ -109,13 +124,90 @@ impl BacktrackSolverStats {
 #[derive(Debug, Clone)]
 pub struct BacktrackSolver {
     technique: TechniqueSolver,
+    constraints: Vec<BoxedConstraint>,
+    budget: SolverBudget,
 }
 
 impl BacktrackSolver {
     /// Creates a new backtracking solver with the specified technique solver.
     #[must_use]
     pub fn new(technique: TechniqueSolver) -> Self {
-        Self { technique }
+        Self {
+            technique,
+            constraints: Vec::new(),
+            budget: SolverBudget::UNLIMITED,
+        }
+    }
+
+    /// Returns a solver that gives up once `budget` is spent.
+    ///
+    /// The budget covers each search started by `solve_with_step` or
+    /// `solve_with_pass`, counting assumptions as steps across all branches
+    /// and time from the start of the call. Once it is spent,
+    /// [`Solutions::try_next`] returns [`SolverError::BudgetExceeded`].
+    #[must_use]
+    pub fn with_budget(mut self, budget: SolverBudget) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    /// Returns the budget of each search.
+    #[must_use]
+    pub fn budget(&self) -> SolverBudget {
+        self.budget
+    }
+
+    /// Returns a solver that also enforces the given variant constraint.
+    ///
+    /// Constraints are pruned after every technique run until neither the
+    /// techniques nor the constraints make progress, and branches that break a
+    /// constraint are discarded, so every yielded solution satisfies it.
+    #[must_use]
+    pub fn with_constraint<C>(mut self, constraint: C) -> Self
+    where
+        C: Constraint + 'static,
+    {
+        self.constraints.push(Box::new(constraint));
+        self
+    }
+
+    /// Returns a solver that also enforces the given killer cages.
+    ///
+    /// This is a shorthand for [`with_constraint`](Self::with_constraint).
+    #[must_use]
+    pub fn with_cages(self, cages: CageSet) -> Self {
+        self.with_constraint(cages)
+    }
+
+    /// Returns a solver that also enforces the given greater-than and
+    /// consecutive markers.
+    ///
+    /// This is a shorthand for [`with_constraint`](Self::with_constraint).
+    #[must_use]
+    pub fn with_edge_constraints(self, constraints: EdgeConstraintSet) -> Self {
+        self.with_constraint(constraints)
+    }
+
+    /// Returns the variant constraints enforced by this solver.
+    #[must_use]
+    pub fn constraints(&self) -> &[BoxedConstraint] {
+        &self.constraints
+    }
+
+    /// Chooses where to branch next.
+    ///
+    /// The degree tie-break needs techniques to propagate each assumption, so
+    /// it is skipped by a solver without any.
+    fn assumption(&self, grid: &TechniqueGrid) -> Assumption {
+        Assumption::new(grid, !self.technique.techniques().is_empty())
+    }
+
+    fn prune_constraints(&self, grid: &mut TechniqueGrid) -> Result<bool, SolverError> {
+        let mut changed = false;
+        for constraint in &self.constraints {
+            changed |= grid.prune_with(constraint.as_ref())?;
+        }
+        Ok(changed)
     }
 
     /// Creates a solver with all available techniques enabled.
@@ -125,6 +217,11 @@ impl BacktrackSolver {
     }
 
     /// Creates a solver with only the specified techniques.
+    ///
+    /// The techniques are applied before the first assumption and again after
+    /// each one, until they make no more progress. A larger set decides more
+    /// cells between assumptions at a higher cost per search node; see
+    /// [`BacktrackSolverStats::guesses_avoided`].
     #[must_use]
     pub fn with_techniques(techniques: Vec<BoxedTechnique>) -> Self {
         Self::new(TechniqueSolver::new(techniques))
@@ -149,14 +246,16 @@ impl BacktrackSolver {
     /// # Errors
     ///
     /// Returns [`SolverError::Inconsistent`] if the initial grid is inconsistent.
+    /// Budget overruns are reported by [`Solutions::try_next`].
     pub fn solve_with_step(&self, mut grid: TechniqueGrid) -> Result<Solutions<'_>, SolverError> {
+        let budget = self.budget.start();
         let mut stats = self.new_stats();
         let solved = self.solve_by_technique_with_step(&mut grid, &mut stats)?;
         let solutions = if solved {
-            Solutions::solved(self, grid, stats)
+            Solutions::solved(self, budget, grid, stats)
         } else {
-            let assumption = backtrack::find_best_assumption(&grid);
-            Solutions::with_assumptions(self, grid, stats, assumption)
+            let assumption = self.assumption(&grid);
+            Solutions::with_assumptions(self, budget, grid, stats, assumption)
         };
         Ok(solutions)
     }
@@ -166,10 +265,14 @@ impl BacktrackSolver {
         grid: &mut TechniqueGrid,
         stats: &mut BacktrackSolverStats,
     ) -> Result<bool, SolverError> {
-        let solved = self
-            .technique
-            .solve_with_step_stats(grid, &mut stats.technique)?;
-        Ok(solved)
+        loop {
+            let solved = self
+                .technique
+                .solve_with_step_stats(grid, &mut stats.technique)?;
+            if !self.prune_constraints(grid)? {
+                return Ok(solved);
+            }
+        }
     }
 
     /// Solves using pass-based technique application before backtracking.
@@ -177,14 +280,16 @@ impl BacktrackSolver {
     /// # Errors
     ///
     /// Returns [`SolverError::Inconsistent`] if the initial grid is inconsistent.
+    /// Budget overruns are reported by [`Solutions::try_next`].
     pub fn solve_with_pass(&self, mut grid: TechniqueGrid) -> Result<Solutions<'_>, SolverError> {
+        let budget = self.budget.start();
         let mut stats = self.new_stats();
         let solved = self.solve_by_technique_with_pass(&mut grid, &mut stats)?;
         let solutions = if solved {
-            Solutions::solved(self, grid, stats)
+            Solutions::solved(self, budget, grid, stats)
         } else {
-            let assumption = backtrack::find_best_assumption(&grid);
-            Solutions::with_assumptions(self, grid, stats, assumption)
+            let assumption = self.assumption(&grid);
+            Solutions::with_assumptions(self, budget, grid, stats, assumption)
         };
         Ok(solutions)
     }
@@ -194,10 +299,14 @@ impl BacktrackSolver {
         grid: &mut TechniqueGrid,
         stats: &mut BacktrackSolverStats,
     ) -> Result<bool, SolverError> {
-        let solved = self
-            .technique
-            .solve_with_pass_stats(grid, &mut stats.technique)?;
-        Ok(solved)
+        loop {
+            let solved = self
+                .technique
+                .solve_with_pass_stats(grid, &mut stats.technique)?;
+            if !self.prune_constraints(grid)? {
+                return Ok(solved);
+            }
+        }
     }
 }
 
@@ -209,6 +318,8 @@ impl BacktrackSolver {
 #[derive(Debug, Clone)]
 pub struct Solutions<'a> {
     solver: &'a BacktrackSolver,
+    budget: BudgetTracker,
+    steps: usize,
     stack: Vec<SearchState>,
 }
 
@@ -216,7 +327,33 @@ pub struct Solutions<'a> {
 struct SearchState {
     grid: TechniqueGrid,
     stats: BacktrackSolverStats,
-    assumption: Option<(Position, DigitSet)>,
+    assumption: Option<Assumption>,
+}
+
+/// A cell to branch on and the digits not yet tried there.
+#[derive(Debug, Clone)]
+struct Assumption {
+    pos: Position,
+    /// Remaining digits, the next one to try last.
+    digits: Vec<Digit>,
+}
+
+impl Assumption {
+    /// Picks the cell with the fewest candidates and orders its digits by
+    /// least constraining value.
+    ///
+    /// With `by_degree`, ties between cells prefer the one with the most
+    /// undecided peers; otherwise the first cell in row-major order is taken.
+    fn new(grid: &TechniqueGrid, by_degree: bool) -> Self {
+        let (pos, candidates) = if by_degree {
+            backtrack::find_best_assumption_by_degree(grid)
+        } else {
+            backtrack::find_best_assumption(grid)
+        };
+        let mut digits = backtrack::least_constraining_order(grid, pos, candidates);
+        digits.reverse();
+        Self { pos, digits }
+    }
 }
 
 impl SearchState {
@@ -231,7 +368,7 @@ impl SearchState {
     fn with_assumption(
         grid: TechniqueGrid,
         stats: BacktrackSolverStats,
-        assumption: (Position, DigitSet),
+        assumption: Assumption,
     ) -> Self {
         Self {
             grid,
@@ -244,67 +381,112 @@ impl SearchState {
 impl<'a> Solutions<'a> {
     fn solved(
         solver: &'a BacktrackSolver,
+        budget: BudgetTracker,
         grid: TechniqueGrid,
         stats: BacktrackSolverStats,
     ) -> Self {
         Self {
             solver,
+            budget,
+            steps: 0,
             stack: vec![SearchState::solved(grid, stats)],
         }
     }
 
     fn with_assumptions(
         solver: &'a BacktrackSolver,
+        budget: BudgetTracker,
         grid: TechniqueGrid,
         stats: BacktrackSolverStats,
-        assumption: (Position, DigitSet),
+        assumption: Assumption,
     ) -> Self {
         Self {
             solver,
+            budget,
+            steps: 0,
             stack: vec![SearchState::with_assumption(grid, stats, assumption)],
         }
     }
-}
-
-impl Iterator for Solutions<'_> {
-    type Item = (TechniqueGrid, BacktrackSolverStats);
 
-    fn next(&mut self) -> Option<Self::Item> {
+    /// Returns the next solution, or an error once the budget is spent.
+    ///
+    /// Returns `Ok(None)` when there are no more solutions. Unlike
+    /// [`next`](Iterator::next), which simply ends the iteration, this tells
+    /// an exhausted search apart from one cut short by the solver's
+    /// [`SolverBudget`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SolverError::BudgetExceeded`] if the budget runs out before
+    /// the next solution is found. Later calls keep returning the error.
+    pub fn try_next(
+        &mut self,
+    ) -> Result<Option<(TechniqueGrid, BacktrackSolverStats)>, SolverError> {
         while let Some(mut state) = self.stack.pop() {
-            let Some((pos, remaining_digits)) = &mut state.assumption else {
-                return Some((state.grid, state.stats));
+            let Some(assumption) = &mut state.assumption else {
+                return Ok(Some((state.grid, state.stats)));
             };
-            let Some(digit) = remaining_digits.pop_first() else {
+            let Some(digit) = assumption.digits.pop() else {
                 continue;
             };
-            let pos = *pos;
+            let pos = assumption.pos;
+            self.steps += 1;
+            if let Err(err) = self.budget.check(self.steps) {
+                self.steps -= 1;
+                assumption.digits.push(digit);
+                self.stack.push(state);
+                return Err(err);
+            }
             let mut grid = state.grid.clone();
             let mut stats = state.stats.clone();
             self.stack.push(state);
 
             stats.assumptions.push((pos, digit));
             grid.place(pos, digit);
-            let Ok(solved) = self
+            let decided = grid.univalue_positions().len();
+            let solved = match self
                 .solver
                 .solve_by_technique_with_pass(&mut grid, &mut stats)
-            else {
-                stats.backtrack_count += 1;
-                continue;
+            {
+                Ok(solved) => solved,
+                Err(SolverError::Inconsistent(_)) => {
+                    stats.backtrack_count += 1;
+                    continue;
+                }
+                Err(err) => return Err(err),
             };
+            stats.guesses_avoided += grid.univalue_positions().len() - decided;
             if solved {
-                return Some((grid, stats));
+                return Ok(Some((grid, stats)));
             }
-            let assumption = backtrack::find_best_assumption(&grid);
+            let assumption = self.solver.assumption(&grid);
             self.stack
                 .push(SearchState::with_assumption(grid, stats, assumption));
         }
-        None
+        Ok(None)
+    }
+}
+
+impl Iterator for Solutions<'_> {
+    type Item = (TechniqueGrid, BacktrackSolverStats);
+
+    /// Returns the next solution.
+    ///
+    /// If the solver's budget runs out, the iteration ends early; use
+    /// [`Solutions::try_next`] to tell that apart from an exhausted search.
+    fn next(&mut self) -> Option<Self::Item> {
+        self.try_next().ok().flatten()
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use numelace_core::{CandidateGrid, Digit, Position};
+    use std::str::FromStr as _;
+
+    use numelace_core::{
+        Cage, CandidateGrid, ConsistencyError, ConstraintViolation, Digit, DigitGrid,
+        DigitPositions, DigitSet, EdgeConstraint, EdgeKind, Position, RegionLayout,
+    };
 
     use super::*;
 
@@ -460,7 +642,7 @@ mod tests {
         let (_, stats) = solutions.next().unwrap();
 
         // Should have made assumptions
-        assert!(!stats.assumptions().is_empty());
+        assert_ne!(stats.assumptions(), []);
     }
 
     #[test]
@@ -513,6 +695,27 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_solves_jigsaw_layout() {
+        let regions: RegionLayout = "\
+            111122333\
+            111222333\
+            112222333\
+            444555666\
+            444555666\
+            444555666\
+            777888999\
+            777888999\
+            777888999"
+            .parse()
+            .unwrap();
+        let solver = BacktrackSolver::with_all_techniques();
+        let grid = TechniqueGrid::new().with_regions(regions);
+        let (solution, _) = solver.solve_with_pass(grid).unwrap().next().unwrap();
+        assert_eq!(solution.regions(), &regions);
+        assert!(regions.is_solved(&solution.to_digit_grid()));
+    }
+
     #[test]
     fn test_backtrack_count() {
         let solver = BacktrackSolver::with_all_techniques();
@@ -522,6 +725,38 @@ mod tests {
         assert_eq!(stats.backtrack_count(), 0);
     }
 
+    #[test]
+    fn test_budget_stops_search() {
+        // Escargot: needs assumptions even with all techniques.
+        let grid = TechniqueGrid::from_digit_grid(
+            &DigitGrid::from_str(
+                "1....7.9..3..2...8..96..5....53..9...1..8...26....4...3......1..4......7..7...3..",
+            )
+            .unwrap(),
+        );
+
+        let solver = BacktrackSolver::with_all_techniques();
+        let (_, stats) = solver
+            .solve_with_pass(grid.clone())
+            .unwrap()
+            .next()
+            .unwrap();
+        assert!(!stats.solved_without_assumptions());
+
+        let solver = BacktrackSolver::with_all_techniques()
+            .with_budget(SolverBudget::new().with_max_steps(1));
+        let mut solutions = solver.solve_with_pass(grid.clone()).unwrap();
+        assert!(matches!(
+            solutions.try_next(),
+            Err(SolverError::BudgetExceeded)
+        ));
+        assert!(matches!(
+            solutions.try_next(),
+            Err(SolverError::BudgetExceeded)
+        ));
+        assert!(solver.solve_with_pass(grid).unwrap().next().is_none());
+    }
+
     #[test]
     fn test_with_techniques_constructor() {
         use crate::technique::NakedSingle;
@@ -533,4 +768,176 @@ mod tests {
         let result = solver.solve_with_pass(grid);
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_guesses_avoided() {
+        use crate::technique::{HiddenSingle, NakedSingle};
+
+        // Escargot: needs assumptions even with all techniques.
+        let problem = DigitGrid::from_str(
+            "1....7.9..3..2...8..96..5....53..9...1..8...26....4...3......1..4......7..7...3..",
+        )
+        .unwrap();
+        let grid = TechniqueGrid::from_digit_grid(&problem);
+
+        let solver = BacktrackSolver::without_techniques();
+        let (_, plain) = solver
+            .solve_with_pass(grid.clone())
+            .unwrap()
+            .next()
+            .unwrap();
+        assert_eq!(plain.guesses_avoided(), 0);
+
+        let techniques: Vec<BoxedTechnique> =
+            vec![Box::new(NakedSingle::new()), Box::new(HiddenSingle::new())];
+        let solver = BacktrackSolver::with_techniques(techniques);
+        let (_, stats) = solver.solve_with_pass(grid).unwrap().next().unwrap();
+        assert!(stats.guesses_avoided() > 0);
+        assert!(stats.assumptions().len() < plain.assumptions().len());
+    }
+
+    #[test]
+    fn test_cages_prune_solutions() {
+        let solution = DigitGrid::from_str(
+            "534678912672195348198342567859761423426853791713924856961537284287419635345286179",
+        )
+        .unwrap();
+        // With the top two rows empty, the puzzle has several solutions.
+        let mut grid = TechniqueGrid::new();
+        for pos in Position::ALL.into_iter().filter(|pos| pos.row() >= 2) {
+            grid.place(pos, solution[pos].unwrap());
+        }
+        let solver = BacktrackSolver::with_all_techniques();
+        let solutions = solver
+            .solve_with_pass(grid.clone())
+            .unwrap()
+            .take(2)
+            .count();
+        assert_eq!(solutions, 2);
+
+        // Horizontal triples of the top row pin down the original solution.
+        let cages = CageSet::from_cages((0..3).map(|band| {
+            let positions = (0..3)
+                .map(|offset| Position::new(0, band * 3 + offset))
+                .collect::<DigitPositions>();
+            let sum = positions
+                .iter()
+                .map(|pos| solution[pos].unwrap().value())
+                .sum();
+            Cage::new(positions, sum).unwrap()
+        }))
+        .unwrap();
+        let solver = solver.with_cages(cages);
+        let solutions: Vec<_> = solver.solve_with_pass(grid).unwrap().take(2).collect();
+        assert_eq!(solutions.len(), 1);
+        assert_eq!(solutions[0].0.to_digit_grid(), solution);
+    }
+
+    #[test]
+    fn test_edge_constraints_prune_solutions() {
+        let solution = DigitGrid::from_str(
+            "534678912672195348198342567859761423426853791713924856961537284287419635345286179",
+        )
+        .unwrap();
+        let mut grid = TechniqueGrid::new();
+        for pos in Position::ALL.into_iter().filter(|pos| pos.row() >= 2) {
+            grid.place(pos, solution[pos].unwrap());
+        }
+
+        // Greater-than markers between horizontal neighbors of the top two rows.
+        let constraints = EdgeConstraintSet::from_constraints(
+            Position::ALL
+                .into_iter()
+                .filter(|pos| pos.row() < 2)
+                .filter_map(|pos| Some((pos, pos.right()?)))
+                .map(|(a, b)| {
+                    let kind = if solution[a] < solution[b] {
+                        EdgeKind::LessThan
+                    } else {
+                        EdgeKind::GreaterThan
+                    };
+                    EdgeConstraint::new(a, b, kind).unwrap()
+                }),
+        )
+        .unwrap();
+        let solver = BacktrackSolver::with_all_techniques().with_edge_constraints(constraints);
+        let solutions: Vec<_> = solver.solve_with_pass(grid).unwrap().take(2).collect();
+        assert_eq!(solutions.len(), 1);
+        assert_eq!(solutions[0].0.to_digit_grid(), solution);
+    }
+
+    /// Test-only variant rule: digits on the main diagonal do not repeat.
+    #[derive(Debug, Clone)]
+    struct MainDiagonal;
+
+    impl MainDiagonal {
+        fn positions() -> DigitPositions {
+            (0..9).map(|i| Position::new(i, i)).collect()
+        }
+
+        fn violation() -> ConstraintViolation {
+            ConstraintViolation {
+                name: "Main diagonal",
+                positions: Self::positions(),
+            }
+        }
+    }
+
+    impl Constraint for MainDiagonal {
+        fn name(&self) -> &'static str {
+            "Main diagonal"
+        }
+
+        fn clone_box(&self) -> BoxedConstraint {
+            Box::new(self.clone())
+        }
+
+        fn check(&self, grid: &DigitGrid) -> Result<(), ConstraintViolation> {
+            let mut seen = DigitSet::EMPTY;
+            for pos in Self::positions() {
+                if let Some(digit) = grid[pos]
+                    && !seen.insert(digit)
+                {
+                    return Err(Self::violation());
+                }
+            }
+            Ok(())
+        }
+
+        fn check_cell(&self, grid: &DigitGrid, pos: Position) -> Result<(), ConstraintViolation> {
+            if Self::positions().contains(pos) {
+                self.check(grid)
+            } else {
+                Ok(())
+            }
+        }
+
+        fn prune(&self, candidates: &mut CandidateGrid) -> Result<bool, ConsistencyError> {
+            let mut changed = false;
+            let diagonal = Self::positions();
+            for pos in diagonal & candidates.univalue_positions() {
+                let digits = candidates.candidates_at(pos);
+                for peer in diagonal {
+                    if peer != pos {
+                        changed |= candidates.remove_candidate_set(peer, digits);
+                    }
+                }
+            }
+            Ok(changed)
+        }
+    }
+
+    #[test]
+    fn test_custom_constraint_is_enforced() {
+        let solver = BacktrackSolver::with_all_techniques().with_constraint(MainDiagonal);
+        assert_eq!(solver.constraints().len(), 1);
+        let (solution, _) = solver
+            .solve_with_pass(TechniqueGrid::new())
+            .unwrap()
+            .next()
+            .unwrap();
+        assert!(solution.is_solved().unwrap());
+        let grid = solution.to_digit_grid();
+        assert_eq!(MainDiagonal.check(&grid), Ok(()));
+    }
 }
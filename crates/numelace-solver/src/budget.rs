@@ -0,0 +1,143 @@
+//! Limits on the work a solver may do.
+
+use std::time::Duration;
+
+use web_time::Instant;
+
+use crate::SolverError;
+
+/// Limits on how much work a solver may do before giving up.
+///
+/// A solver with a budget returns [`SolverError::BudgetExceeded`] once it
+/// needs more steps or more time than allowed, instead of running for as long
+/// as the input demands. What counts as a step depends on the solver:
+/// [`TechniqueSolver`](crate::TechniqueSolver) counts technique applications,
+/// and [`BacktrackSolver`](crate::BacktrackSolver) counts assumptions.
+///
+/// The default budget is unlimited.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use numelace_solver::{BacktrackSolver, SolverBudget};
+///
+/// let budget = SolverBudget::new()
+///     .with_max_steps(10_000)
+///     .with_max_duration(Duration::from_secs(5));
+/// let solver = BacktrackSolver::with_all_techniques().with_budget(budget);
+/// assert_eq!(solver.budget().max_steps(), Some(10_000));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SolverBudget {
+    max_steps: Option<usize>,
+    max_duration: Option<Duration>,
+}
+
+impl SolverBudget {
+    /// A budget without any limit.
+    pub const UNLIMITED: Self = Self {
+        max_steps: None,
+        max_duration: None,
+    };
+
+    /// Creates an unlimited budget.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self::UNLIMITED
+    }
+
+    /// Returns a budget that allows at most `max_steps` steps.
+    #[must_use]
+    pub const fn with_max_steps(mut self, max_steps: usize) -> Self {
+        self.max_steps = Some(max_steps);
+        self
+    }
+
+    /// Returns a budget that allows solving for at most `max_duration`.
+    ///
+    /// The time is measured from the start of each solve call.
+    #[must_use]
+    pub const fn with_max_duration(mut self, max_duration: Duration) -> Self {
+        self.max_duration = Some(max_duration);
+        self
+    }
+
+    /// Returns the step limit, if any.
+    #[must_use]
+    pub const fn max_steps(&self) -> Option<usize> {
+        self.max_steps
+    }
+
+    /// Returns the time limit, if any.
+    #[must_use]
+    pub const fn max_duration(&self) -> Option<Duration> {
+        self.max_duration
+    }
+
+    /// Returns `true` if the budget has no limit.
+    #[must_use]
+    pub const fn is_unlimited(&self) -> bool {
+        self.max_steps.is_none() && self.max_duration.is_none()
+    }
+
+    /// Starts measuring a solve call against this budget.
+    pub(crate) fn start(self) -> BudgetTracker {
+        BudgetTracker {
+            max_steps: self.max_steps,
+            deadline: self
+                .max_duration
+                .map(|max_duration| (Instant::now(), max_duration)),
+        }
+    }
+}
+
+/// A [`SolverBudget`] being spent by one solve call.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct BudgetTracker {
+    max_steps: Option<usize>,
+    deadline: Option<(Instant, Duration)>,
+}
+
+impl BudgetTracker {
+    /// Fails once `steps` exceeds the step limit or the time limit is reached.
+    pub(crate) fn check(&self, steps: usize) -> Result<(), SolverError> {
+        let over_steps = self.max_steps.is_some_and(|max_steps| steps > max_steps);
+        let over_time = self
+            .deadline
+            .is_some_and(|(started, max_duration)| started.elapsed() >= max_duration);
+        if over_steps || over_time {
+            return Err(SolverError::BudgetExceeded);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unlimited_budget_never_fails() {
+        let tracker = SolverBudget::new().start();
+        assert!(SolverBudget::default().is_unlimited());
+        assert!(tracker.check(usize::MAX).is_ok());
+    }
+
+    #[test]
+    fn test_step_limit_allows_exactly_max_steps() {
+        let tracker = SolverBudget::new().with_max_steps(3).start();
+        assert!(tracker.check(3).is_ok());
+        assert!(matches!(tracker.check(4), Err(SolverError::BudgetExceeded)));
+    }
+
+    #[test]
+    fn test_time_limit_fails_after_deadline() {
+        let tracker = SolverBudget::new()
+            .with_max_duration(Duration::ZERO)
+            .start();
+        std::thread::sleep(Duration::from_millis(1));
+        assert!(matches!(tracker.check(0), Err(SolverError::BudgetExceeded)));
+    }
+}
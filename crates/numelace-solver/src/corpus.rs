@@ -0,0 +1,338 @@
+//! Example positions for the built-in techniques.
+//!
+//! Each example is a candidate grid in which its technique finds a step and no
+//! simpler technique of
+//! [`all_techniques_assuming_unique_solution`](crate::technique::all_techniques_assuming_unique_solution)
+//! does. The examples follow the textbook patterns of each technique on an
+//! otherwise open grid, so every step they produce is the pattern itself.
+//!
+//! The technique benchmarks run on these examples, and the tests of this module
+//! check that the technique ordering still holds whenever a technique is added
+//! or changed.
+
+use numelace_core::{Digit, DigitSet, Position};
+
+use crate::{TechniqueGrid, technique::TechniqueId};
+
+/// Returns the example position for the technique with the given ID.
+///
+/// Returns `None` for variant techniques such as
+/// [`CageCombination`](crate::technique::CageCombination), which need puzzle
+/// data beyond the candidates.
+#[must_use]
+pub fn example(id: TechniqueId) -> Option<TechniqueGrid> {
+    let grid = match id {
+        TechniqueId::FullHouse => full_house(),
+        TechniqueId::NakedSingle => naked_single(),
+        TechniqueId::HiddenSingle => hidden_single(),
+        TechniqueId::LockedCandidates => locked_candidates(),
+        TechniqueId::NakedPair => naked_subset(&[0, 1], &[Digit::D1, Digit::D2]),
+        TechniqueId::HiddenPair => hidden_subset(&[0, 3], &[Digit::D1, Digit::D2]),
+        TechniqueId::NakedTriple => naked_subset(&[0, 3, 6], &[Digit::D1, Digit::D2, Digit::D3]),
+        TechniqueId::HiddenTriple => hidden_subset(&[0, 3, 6], &[Digit::D1, Digit::D2, Digit::D3]),
+        TechniqueId::NakedQuad => {
+            naked_subset(&[0, 2, 4, 6], &[Digit::D1, Digit::D2, Digit::D3, Digit::D4])
+        }
+        TechniqueId::HiddenQuad => {
+            hidden_subset(&[0, 2, 4, 6], &[Digit::D1, Digit::D2, Digit::D3, Digit::D4])
+        }
+        TechniqueId::XWing => fish(&[0, 4], &[1, 7]),
+        TechniqueId::Skyscraper => skyscraper(),
+        TechniqueId::TwoStringKite => two_string_kite(),
+        TechniqueId::YWing => y_wing(),
+        TechniqueId::UniqueRectangle => unique_rectangle(),
+        TechniqueId::Swordfish => fish(&[0, 4, 8], &[1, 4, 7]),
+        TechniqueId::Jellyfish => fish(&[0, 2, 5, 8], &[1, 4, 6, 8]),
+        TechniqueId::RemotePair => remote_pair(),
+        TechniqueId::WWing => w_wing(),
+        TechniqueId::XChain => x_chain(),
+        TechniqueId::XyChain => xy_chain(),
+        TechniqueId::XyzWing => xyz_wing(),
+        TechniqueId::WxyzWing => wxyz_wing(),
+        TechniqueId::Aic => aic(),
+        TechniqueId::CageCombination | TechniqueId::EdgeRelation => return None,
+    };
+    Some(grid)
+}
+
+/// Restricts the candidates of `pos` to `digits`.
+fn keep_only(grid: &mut TechniqueGrid, pos: Position, digits: &[Digit]) {
+    grid.remove_candidate_set(pos, !digits.iter().copied().collect::<DigitSet>());
+}
+
+/// Removes `digit` from every cell of `positions` except `keep`.
+fn remove_except(
+    grid: &mut TechniqueGrid,
+    positions: impl IntoIterator<Item = Position>,
+    keep: &[Position],
+    digit: Digit,
+) {
+    for pos in positions {
+        if !keep.contains(&pos) {
+            grid.remove_candidate(pos, digit);
+        }
+    }
+}
+
+fn full_house() -> TechniqueGrid {
+    let mut grid = TechniqueGrid::new();
+    for (pos, digit) in Position::ROWS[0][..8].iter().zip(Digit::ALL) {
+        keep_only(&mut grid, *pos, &[digit]);
+    }
+    grid
+}
+
+fn naked_single() -> TechniqueGrid {
+    let mut grid = TechniqueGrid::new();
+    keep_only(&mut grid, Position::new(0, 0), &[Digit::D1]);
+    grid
+}
+
+fn hidden_single() -> TechniqueGrid {
+    let mut grid = TechniqueGrid::new();
+    remove_except(
+        &mut grid,
+        Position::ROWS[0],
+        &[Position::new(0, 1)],
+        Digit::D2,
+    );
+    grid
+}
+
+fn locked_candidates() -> TechniqueGrid {
+    let mut grid = TechniqueGrid::new();
+    for pos in Position::BOXES[0] {
+        if pos.row() != 0 {
+            grid.remove_candidate(pos, Digit::D5);
+        }
+    }
+    grid
+}
+
+/// Limits the cells of row 0 at `cols` to `digits`.
+fn naked_subset(cols: &[u8], digits: &[Digit]) -> TechniqueGrid {
+    let mut grid = TechniqueGrid::new();
+    for &col in cols {
+        keep_only(&mut grid, Position::new(0, col), digits);
+    }
+    grid
+}
+
+/// Limits `digits` in row 0 to the cells at `cols`.
+fn hidden_subset(cols: &[u8], digits: &[Digit]) -> TechniqueGrid {
+    let mut grid = TechniqueGrid::new();
+    let keep: Vec<_> = cols.iter().map(|&col| Position::new(0, col)).collect();
+    for &digit in digits {
+        remove_except(&mut grid, Position::ROWS[0], &keep, digit);
+    }
+    grid
+}
+
+/// Limits digit 1 in `rows` to the cells at `cols`.
+fn fish(rows: &[u8], cols: &[u8]) -> TechniqueGrid {
+    let mut grid = TechniqueGrid::new();
+    for &row in rows {
+        let keep: Vec<_> = cols.iter().map(|&col| Position::new(row, col)).collect();
+        remove_except(&mut grid, Position::ROWS[row], &keep, Digit::D1);
+    }
+    grid
+}
+
+fn skyscraper() -> TechniqueGrid {
+    let mut grid = TechniqueGrid::new();
+    remove_except(
+        &mut grid,
+        Position::COLS[1],
+        &[Position::new(0, 1), Position::new(3, 1)],
+        Digit::D1,
+    );
+    remove_except(
+        &mut grid,
+        Position::COLS[7],
+        &[Position::new(0, 7), Position::new(4, 7)],
+        Digit::D1,
+    );
+    grid
+}
+
+fn two_string_kite() -> TechniqueGrid {
+    let mut grid = TechniqueGrid::new();
+    remove_except(
+        &mut grid,
+        Position::ROWS[0],
+        &[Position::new(0, 1), Position::new(0, 4)],
+        Digit::D1,
+    );
+    remove_except(
+        &mut grid,
+        Position::COLS[2],
+        &[Position::new(1, 2), Position::new(4, 2)],
+        Digit::D1,
+    );
+    grid
+}
+
+fn y_wing() -> TechniqueGrid {
+    let mut grid = TechniqueGrid::new();
+    keep_only(&mut grid, Position::new(1, 1), &[Digit::D1, Digit::D2]);
+    keep_only(&mut grid, Position::new(5, 1), &[Digit::D1, Digit::D3]);
+    keep_only(&mut grid, Position::new(1, 5), &[Digit::D2, Digit::D3]);
+    grid
+}
+
+fn unique_rectangle() -> TechniqueGrid {
+    let mut grid = TechniqueGrid::new();
+    // The floor r1c1-r1c2-r4c1 holds 1 and 2 nowhere else in row 1, column 1
+    // and box 1, so no naked pair has anything to remove.
+    let floor = [
+        Position::new(0, 0),
+        Position::new(0, 1),
+        Position::new(3, 0),
+    ];
+    for digit in [Digit::D1, Digit::D2] {
+        remove_except(&mut grid, Position::ROWS[0], &floor, digit);
+        remove_except(&mut grid, Position::COLS[0], &floor, digit);
+        remove_except(&mut grid, Position::BOXES[0], &floor, digit);
+    }
+    for pos in floor {
+        keep_only(&mut grid, pos, &[Digit::D1, Digit::D2]);
+    }
+    grid
+}
+
+fn remote_pair() -> TechniqueGrid {
+    let mut grid = TechniqueGrid::new();
+    // The chain r1c1-r1c5-r5c5-r4c4 runs through row 1, column 5 and box 5,
+    // which hold 1 and 2 nowhere else, so no naked pair has anything to remove.
+    let chain = [
+        Position::new(0, 0),
+        Position::new(0, 4),
+        Position::new(4, 4),
+        Position::new(3, 3),
+    ];
+    for digit in [Digit::D1, Digit::D2] {
+        remove_except(&mut grid, Position::ROWS[0], &chain, digit);
+        remove_except(&mut grid, Position::COLS[4], &chain, digit);
+        remove_except(&mut grid, Position::BOXES[4], &chain, digit);
+    }
+    for pos in chain {
+        keep_only(&mut grid, pos, &[Digit::D1, Digit::D2]);
+    }
+    grid
+}
+
+fn w_wing() -> TechniqueGrid {
+    let mut grid = TechniqueGrid::new();
+    // The 1s of row 3 link the wings, and the wings share no house, so no
+    // naked pair or chain of bivalue cells forms.
+    keep_only(&mut grid, Position::new(0, 0), &[Digit::D1, Digit::D2]);
+    keep_only(&mut grid, Position::new(4, 4), &[Digit::D1, Digit::D2]);
+    remove_except(
+        &mut grid,
+        Position::ROWS[2],
+        &[Position::new(2, 1), Position::new(2, 4)],
+        Digit::D1,
+    );
+    grid
+}
+
+fn x_chain() -> TechniqueGrid {
+    let mut grid = TechniqueGrid::new();
+    // Strong links of 1 in row 1, column 5 and box 5: a row, a column and a
+    // box, which no skyscraper or two-string kite covers.
+    let chain = [
+        Position::new(0, 0),
+        Position::new(0, 4),
+        Position::new(4, 4),
+        Position::new(3, 3),
+    ];
+    remove_except(&mut grid, Position::ROWS[0], &chain, Digit::D1);
+    remove_except(&mut grid, Position::COLS[4], &chain, Digit::D1);
+    remove_except(&mut grid, Position::BOXES[4], &chain, Digit::D1);
+    grid
+}
+
+fn xy_chain() -> TechniqueGrid {
+    let mut grid = TechniqueGrid::new();
+    // Four cells, since a chain of three would be a Y-Wing.
+    keep_only(&mut grid, Position::new(0, 0), &[Digit::D1, Digit::D2]);
+    keep_only(&mut grid, Position::new(0, 4), &[Digit::D2, Digit::D3]);
+    keep_only(&mut grid, Position::new(4, 4), &[Digit::D3, Digit::D4]);
+    keep_only(&mut grid, Position::new(4, 1), &[Digit::D4, Digit::D1]);
+    grid
+}
+
+fn xyz_wing() -> TechniqueGrid {
+    let mut grid = TechniqueGrid::new();
+    // One wing shares the pivot's box and the other its row, so the three cells
+    // never form a naked triple.
+    keep_only(
+        &mut grid,
+        Position::new(1, 1),
+        &[Digit::D1, Digit::D2, Digit::D3],
+    );
+    keep_only(&mut grid, Position::new(2, 2), &[Digit::D1, Digit::D2]);
+    keep_only(&mut grid, Position::new(1, 6), &[Digit::D1, Digit::D3]);
+    grid
+}
+
+fn wxyz_wing() -> TechniqueGrid {
+    let mut grid = TechniqueGrid::new();
+    // A hinge without the digit 4, one wing in its row and two in its box, so
+    // the cells never form a naked quad.
+    keep_only(
+        &mut grid,
+        Position::new(1, 1),
+        &[Digit::D1, Digit::D2, Digit::D3],
+    );
+    keep_only(&mut grid, Position::new(1, 6), &[Digit::D1, Digit::D4]);
+    keep_only(&mut grid, Position::new(0, 0), &[Digit::D2, Digit::D4]);
+    keep_only(&mut grid, Position::new(2, 2), &[Digit::D3, Digit::D4]);
+    grid
+}
+
+fn aic() -> TechniqueGrid {
+    let mut grid = TechniqueGrid::new();
+    // r1c1 1=2, then the 2s of column 5 and the 3s of row 5, so the chain
+    // mixes a bivalue cell with two houses and r5c1 cannot be 1.
+    keep_only(&mut grid, Position::new(0, 0), &[Digit::D1, Digit::D2]);
+    remove_except(
+        &mut grid,
+        Position::COLS[4],
+        &[Position::new(0, 4), Position::new(4, 4)],
+        Digit::D2,
+    );
+    remove_except(
+        &mut grid,
+        Position::ROWS[4],
+        &[Position::new(4, 0), Position::new(4, 4)],
+        Digit::D3,
+    );
+    grid
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::technique::all_techniques_assuming_unique_solution;
+
+    #[test]
+    fn test_every_technique_finds_its_example_first() {
+        let techniques = all_techniques_assuming_unique_solution();
+        for (i, technique) in techniques.iter().enumerate() {
+            let id = technique.technique_id().unwrap();
+            let grid = example(id).unwrap_or_else(|| panic!("no example for {id}"));
+            assert!(
+                technique.find_step(&grid).unwrap().is_some(),
+                "{id} does not apply to its example"
+            );
+            for simpler in &techniques[..i] {
+                assert!(
+                    simpler.find_step(&grid).unwrap().is_none(),
+                    "{} preempts {id} on its example",
+                    simpler.id()
+                );
+            }
+        }
+    }
+}
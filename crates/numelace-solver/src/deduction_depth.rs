@@ -0,0 +1,359 @@
+//! Trial depth analysis of a position.
+
+use numelace_core::{BoxedConstraint, Constraint, DigitPositions, Position, PositionIndexedArray};
+
+use crate::{
+    BoxedTechnique, BudgetTracker, SolverBudget, SolverError, TechniqueGrid, TechniqueSolver,
+    technique::{FullHouse, HiddenSingle, NakedSingle},
+};
+
+/// Measures how deep a trial is needed to decide each unsolved cell.
+///
+/// At depth 0 the solver's techniques run until they are stuck. At depth `n`,
+/// every remaining candidate is tried in turn: it is placed on a copy of the
+/// grid, which is then propagated at depth `n - 1`, and the candidate is
+/// removed if that copy turns out inconsistent. This is a forcing chain of
+/// nested trials; a cell's depth is the smallest `n` at which it ends up with
+/// a single candidate.
+///
+/// The analysis works from the current candidates only and never uses a
+/// solution, so a cell stays undecided when the position has several
+/// solutions or when deciding it needs trials deeper than
+/// [`max_depth`](Self::max_depth).
+///
+/// # Examples
+///
+/// ```
+/// use std::str::FromStr;
+///
+/// use numelace_core::DigitGrid;
+/// use numelace_solver::{DeductionDepthAnalyzer, TechniqueGrid};
+///
+/// let problem = DigitGrid::from_str(
+///     "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79",
+/// )?;
+/// let depths = DeductionDepthAnalyzer::new().analyze(&TechniqueGrid::from_digit_grid(&problem))?;
+/// // Singles solve this puzzle without any trial.
+/// assert_eq!(depths.max_depth(), Some(0));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct DeductionDepthAnalyzer {
+    solver: TechniqueSolver,
+    constraints: Vec<BoxedConstraint>,
+    max_depth: usize,
+    budget: SolverBudget,
+}
+
+impl Default for DeductionDepthAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DeductionDepthAnalyzer {
+    /// Default limit on the nesting of trials.
+    pub const DEFAULT_MAX_DEPTH: usize = 2;
+
+    /// Creates an analyzer that propagates with singles only, up to
+    /// [`DEFAULT_MAX_DEPTH`](Self::DEFAULT_MAX_DEPTH).
+    #[must_use]
+    pub fn new() -> Self {
+        let techniques: Vec<BoxedTechnique> = vec![
+            Box::new(FullHouse::new()),
+            Box::new(NakedSingle::new()),
+            Box::new(HiddenSingle::new()),
+        ];
+        Self::with_solver(TechniqueSolver::new(techniques))
+    }
+
+    /// Creates an analyzer that propagates with the techniques of `solver`.
+    ///
+    /// Stronger techniques decide more cells at each depth, so the depths
+    /// measure what is left beyond them.
+    #[must_use]
+    pub fn with_solver(solver: TechniqueSolver) -> Self {
+        Self {
+            solver,
+            constraints: Vec::new(),
+            max_depth: Self::DEFAULT_MAX_DEPTH,
+            budget: SolverBudget::UNLIMITED,
+        }
+    }
+
+    /// Returns an analyzer that nests trials at most `max_depth` deep.
+    ///
+    /// Each extra level multiplies the work by the number of candidates, so
+    /// a limit above 2 is rarely affordable.
+    #[must_use]
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Returns an analyzer that gives up once `budget` is spent.
+    ///
+    /// Each trial counts as a step, at any depth.
+    #[must_use]
+    pub fn with_budget(mut self, budget: SolverBudget) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    /// Returns an analyzer that also enforces the given variant constraint.
+    ///
+    /// The constraint is pruned after every technique run, so trials that
+    /// break it are refuted like any other contradiction.
+    #[must_use]
+    pub fn with_constraint<C>(mut self, constraint: C) -> Self
+    where
+        C: Constraint + 'static,
+    {
+        self.constraints.push(Box::new(constraint));
+        self
+    }
+
+    /// Returns the limit on the nesting of trials.
+    #[must_use]
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    /// Returns the budget of each analysis.
+    #[must_use]
+    pub fn budget(&self) -> SolverBudget {
+        self.budget
+    }
+
+    /// Computes the depth of every cell of `grid` with more than one candidate.
+    ///
+    /// Cells decided at a depth stay decided at deeper ones, so the depths
+    /// only grow as the analysis goes on.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SolverError::Inconsistent`] if `grid` is inconsistent or the
+    /// trials refute every candidate of a cell, or
+    /// [`SolverError::BudgetExceeded`] if the budget runs out.
+    pub fn analyze(&self, grid: &TechniqueGrid) -> Result<DeductionDepths, SolverError> {
+        grid.check_consistency()?;
+        let mut trials = Trials {
+            budget: self.budget.start(),
+            count: 0,
+        };
+        let mut current = grid.clone();
+        let mut depths = PositionIndexedArray::default();
+        let mut undecided = undecided_positions(grid);
+        for depth in 0..=self.max_depth {
+            self.propagate(&mut current, depth, &mut trials)?;
+            let decided = current.univalue_positions() & undecided;
+            for pos in decided {
+                depths[pos] = Some(depth);
+            }
+            undecided = undecided.difference(decided);
+            if undecided.is_empty() {
+                break;
+            }
+        }
+        Ok(DeductionDepths { depths, undecided })
+    }
+
+    /// Removes every candidate of `grid` that a trial at `depth` refutes,
+    /// until no more can be removed.
+    fn propagate(
+        &self,
+        grid: &mut TechniqueGrid,
+        depth: usize,
+        trials: &mut Trials,
+    ) -> Result<(), SolverError> {
+        loop {
+            let solved = self.solve_by_technique(grid)?;
+            if solved || depth == 0 {
+                return Ok(());
+            }
+            let mut progress = false;
+            for pos in undecided_positions(grid) {
+                for digit in grid.candidates_at(pos) {
+                    trials.spend()?;
+                    let mut trial = grid.clone();
+                    trial.place(pos, digit);
+                    match self.propagate(&mut trial, depth - 1, trials) {
+                        Ok(()) => {}
+                        Err(SolverError::Inconsistent(_)) => {
+                            grid.remove_candidate(pos, digit);
+                            progress = true;
+                        }
+                        Err(err) => return Err(err),
+                    }
+                }
+            }
+            if !progress {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Runs the techniques and prunes the constraints until neither makes
+    /// progress.
+    fn solve_by_technique(&self, grid: &mut TechniqueGrid) -> Result<bool, SolverError> {
+        loop {
+            let (solved, _stats) = self.solver.solve_with_pass(grid)?;
+            let mut changed = false;
+            for constraint in &self.constraints {
+                changed |= grid.prune_with(constraint.as_ref())?;
+            }
+            if !changed {
+                return Ok(solved);
+            }
+        }
+    }
+}
+
+/// Counts the trials of one analysis against its budget.
+#[derive(Debug)]
+struct Trials {
+    budget: BudgetTracker,
+    count: usize,
+}
+
+impl Trials {
+    fn spend(&mut self) -> Result<(), SolverError> {
+        self.count += 1;
+        self.budget.check(self.count)
+    }
+}
+
+/// Returns the positions of `grid` with more than one candidate.
+fn undecided_positions(grid: &TechniqueGrid) -> DigitPositions {
+    Position::ALL
+        .into_iter()
+        .filter(|&pos| grid.candidates_at(pos).len() > 1)
+        .collect()
+}
+
+/// Trial depths computed by [`DeductionDepthAnalyzer::analyze`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeductionDepths {
+    depths: PositionIndexedArray<Option<usize>>,
+    undecided: DigitPositions,
+}
+
+impl DeductionDepths {
+    /// Returns the depth at which the cell at `pos` was decided.
+    ///
+    /// Returns `None` for cells that already had a single candidate and for
+    /// cells left undecided.
+    #[must_use]
+    pub fn depth(&self, pos: Position) -> Option<usize> {
+        self.depths[pos]
+    }
+
+    /// Returns the cells that no trial within the depth limit decided.
+    #[must_use]
+    pub fn undecided(&self) -> DigitPositions {
+        self.undecided
+    }
+
+    /// Returns the deepest trial needed to decide every cell, which grades
+    /// the position beyond the techniques used to propagate.
+    ///
+    /// Returns `None` if some cell was left undecided.
+    #[must_use]
+    pub fn max_depth(&self) -> Option<usize> {
+        if !self.undecided.is_empty() {
+            return None;
+        }
+        Some(self.depths.iter().flatten().copied().max().unwrap_or(0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use numelace_core::{ChessRules, DigitGrid};
+
+    use super::*;
+
+    // Escargot: needs assumptions even with all techniques.
+    const ESCARGOT: &str =
+        "1....7.9..3..2...8..96..5....53..9...1..8...26....4...3......1..4......7..7...3..";
+
+    fn grid(problem: &str) -> TechniqueGrid {
+        TechniqueGrid::from_digit_grid(&DigitGrid::from_str(problem).unwrap())
+    }
+
+    #[test]
+    fn test_givens_have_no_depth() {
+        let grid = grid(ESCARGOT);
+        let depths = DeductionDepthAnalyzer::new()
+            .with_max_depth(0)
+            .analyze(&grid)
+            .unwrap();
+
+        let given = Position::new(0, 0);
+        assert_eq!(depths.depth(given), None);
+        assert!(!depths.undecided().contains(given));
+    }
+
+    #[test]
+    fn test_depths_grow_with_limit() {
+        // A 17-clue puzzle that singles cannot start but one trial settles.
+        let grid = grid(
+            "4.....8.5.3..........7......2.....6.....8.4......1.......6.3.7.5..2.....1.4......",
+        );
+        let shallow = DeductionDepthAnalyzer::new()
+            .with_max_depth(0)
+            .analyze(&grid)
+            .unwrap();
+        let deep = DeductionDepthAnalyzer::new()
+            .with_max_depth(1)
+            .analyze(&grid)
+            .unwrap();
+
+        assert_eq!(shallow.max_depth(), None);
+        assert_eq!(deep.max_depth(), Some(1));
+        for pos in shallow.undecided() {
+            assert_eq!(deep.depth(pos), Some(1));
+        }
+    }
+
+    #[test]
+    fn test_constraints_decide_cells() {
+        // An anti-knight solution with a unique rectangle cleared: only the
+        // knight's moves rule out swapping its digits.
+        let grid = grid(
+            "839246571512..9648764518932345961827281..5469976482315157693284628154793493827156",
+        );
+        let corner = Position::new(1, 3);
+        let plain = DeductionDepthAnalyzer::new().analyze(&grid).unwrap();
+        let chess = DeductionDepthAnalyzer::new()
+            .with_constraint(ChessRules::new().with_anti_knight(true))
+            .analyze(&grid)
+            .unwrap();
+
+        assert!(plain.undecided().contains(corner));
+        assert_eq!(chess.depth(corner), Some(0));
+        assert_eq!(chess.max_depth(), Some(0));
+    }
+
+    #[test]
+    fn test_inconsistent_grid_is_rejected() {
+        let grid = grid(&format!("11{}", ".".repeat(79)));
+        assert!(matches!(
+            DeductionDepthAnalyzer::new().analyze(&grid),
+            Err(SolverError::Inconsistent(_))
+        ));
+    }
+
+    #[test]
+    fn test_budget_stops_trials() {
+        let analyzer = DeductionDepthAnalyzer::new()
+            .with_max_depth(1)
+            .with_budget(SolverBudget::new().with_max_steps(1));
+        assert!(matches!(
+            analyzer.analyze(&grid(ESCARGOT)),
+            Err(SolverError::BudgetExceeded)
+        ));
+    }
+}
@@ -10,33 +10,60 @@ pub enum SolverError {
     /// of inconsistencies that can occur.
     #[display("inconsistency detected: {_0}")]
     Inconsistent(ConsistencyError),
+    /// The solver ran out of the steps or time allowed by its
+    /// [`SolverBudget`](crate::SolverBudget) before finishing.
+    #[display("solver budget exceeded")]
+    #[from(ignore)]
+    BudgetExceeded,
 }
 
 #[cfg(test)]
 mod tests {
+    use std::error::Error as _;
+
+    use numelace_core::{Digit, DigitPositions, House, Position};
+
     use super::*;
 
     #[test]
     fn test_consistency_error_converts_to_solver_error() {
-        let consistency_error = ConsistencyError::NoCandidates;
+        let pos = Position::new(2, 3);
+        let consistency_error = ConsistencyError::NoCandidates { pos };
         let solver_error: SolverError = consistency_error.into();
         assert!(matches!(
             solver_error,
-            SolverError::Inconsistent(ConsistencyError::NoCandidates)
+            SolverError::Inconsistent(ConsistencyError::NoCandidates { pos: p }) if p == pos
         ));
     }
 
     #[test]
     fn test_consistency_error_conversion_with_question_mark() {
-        fn check() -> Result<(), SolverError> {
-            Err(ConsistencyError::DuplicatedUnivalueDigits)?
-        }
+        let error = ConsistencyError::DuplicatedUnivalueDigits {
+            digit: Digit::D4,
+            house: House::Row { row: 0 },
+            positions: DigitPositions::from_position(Position::new(0, 0))
+                | DigitPositions::from_position(Position::new(0, 5)),
+        };
+        let check = || -> Result<(), SolverError> { Err(error)? };
 
         let result = check();
         assert!(result.is_err());
         assert!(matches!(
             result.unwrap_err(),
-            SolverError::Inconsistent(ConsistencyError::DuplicatedUnivalueDigits)
+            SolverError::Inconsistent(ConsistencyError::DuplicatedUnivalueDigits {
+                digit: Digit::D4,
+                ..
+            })
         ));
     }
+
+    #[test]
+    fn test_solver_error_chains_consistency_error_as_source() {
+        let error = SolverError::from(ConsistencyError::NoCandidates {
+            pos: Position::new(0, 8),
+        });
+        let source = error.source().expect("consistency error is the source");
+        assert_eq!(source.to_string(), "no candidates remain at r1c9");
+        assert!(source.source().is_none());
+    }
 }
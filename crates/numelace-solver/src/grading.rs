@@ -0,0 +1,513 @@
+//! Batch grading of puzzles with a CSV report.
+
+use std::{
+    io::{self, Write},
+    iter,
+    num::NonZero,
+    panic,
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
+
+use numelace_core::DigitGrid;
+
+use crate::{
+    BacktrackSolver, SolverBudget, SolverError, TechniqueGrid, TechniqueSolver,
+    TechniqueSolverStats, TechniqueTier,
+};
+
+/// Grades many puzzles at once, for triaging candidate puzzles.
+///
+/// Each puzzle is solved with the techniques alone, which gives the technique
+/// counts and the difficulty tier, and then with backtracking, which tells how
+/// many solutions it has and how many assumptions the first one needed.
+///
+/// # Examples
+///
+/// ```
+/// use std::str::FromStr;
+///
+/// use numelace_core::DigitGrid;
+/// use numelace_solver::{BatchGrader, SolutionCount, TechniqueTier};
+///
+/// let puzzles = [DigitGrid::from_str(
+///     "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79",
+/// )?];
+/// let grader = BatchGrader::new();
+/// let reports = grader.grade_batch(&puzzles);
+/// assert_eq!(reports[0].solutions, SolutionCount::Unique);
+/// assert_eq!(reports[0].tier, Some(TechniqueTier::Fundamental));
+///
+/// let mut csv = Vec::new();
+/// grader.write_csv(&mut csv, &reports)?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct BatchGrader {
+    solver: TechniqueSolver,
+    budget: SolverBudget,
+    threads: Option<NonZero<usize>>,
+}
+
+impl Default for BatchGrader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BatchGrader {
+    /// Creates a grader with all available techniques.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_solver(TechniqueSolver::with_all_techniques())
+    }
+
+    /// Creates a grader with the techniques of `solver`.
+    ///
+    /// The technique counts of the reports follow the order of
+    /// [`TechniqueSolver::techniques`].
+    #[must_use]
+    pub fn with_solver(solver: TechniqueSolver) -> Self {
+        Self {
+            solver,
+            budget: SolverBudget::UNLIMITED,
+            threads: None,
+        }
+    }
+
+    /// Returns a grader whose backtracking gives up on a puzzle once `budget`
+    /// is spent, reporting its solutions as [`SolutionCount::Undetermined`].
+    #[must_use]
+    pub fn with_budget(mut self, budget: SolverBudget) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    /// Returns a grader that uses `threads` threads for a batch.
+    ///
+    /// By default a batch uses the available parallelism of the machine.
+    #[must_use]
+    pub fn with_threads(mut self, threads: NonZero<usize>) -> Self {
+        self.threads = Some(threads);
+        self
+    }
+
+    /// Returns the technique solver used for grading.
+    #[must_use]
+    pub fn solver(&self) -> &TechniqueSolver {
+        &self.solver
+    }
+
+    /// Returns the backtracking budget of each puzzle.
+    #[must_use]
+    pub fn budget(&self) -> SolverBudget {
+        self.budget
+    }
+
+    /// Grades a single puzzle.
+    #[must_use]
+    pub fn grade(&self, puzzle: &DigitGrid) -> GradeReport {
+        let mut grid = TechniqueGrid::from_digit_grid(puzzle);
+        let mut technique = self.solver.new_stats();
+        // An inconsistent puzzle is simply not solved; the backtracking below
+        // reports it as having no solution.
+        let solved_by_techniques = self
+            .solver
+            .solve_with_step_stats(&mut grid, &mut technique)
+            .unwrap_or(false);
+        let tier = solved_by_techniques.then(|| hardest_tier(&self.solver, &technique));
+        let (solutions, assumptions) = self.count_solutions(puzzle);
+        GradeReport {
+            puzzle: puzzle.clone(),
+            clues: puzzle.iter().flatten().count(),
+            solutions,
+            solved_by_techniques,
+            assumptions,
+            tier,
+            technique,
+        }
+    }
+
+    /// Grades `puzzles` in parallel, returning the reports in the same order.
+    ///
+    /// Threads take the next puzzle as soon as they finish one, so a few hard
+    /// puzzles do not hold up the rest. Where threads are unavailable, as on
+    /// the web, the puzzles are graded one by one.
+    #[must_use]
+    pub fn grade_batch(&self, puzzles: &[DigitGrid]) -> Vec<GradeReport> {
+        let threads = self
+            .threads
+            .or_else(|| thread::available_parallelism().ok())
+            .map_or(1, NonZero::get)
+            .min(puzzles.len());
+        if threads <= 1 {
+            return puzzles.iter().map(|puzzle| self.grade(puzzle)).collect();
+        }
+
+        let next = AtomicUsize::new(0);
+        let mut reports = thread::scope(|scope| {
+            let workers = (0..threads)
+                .map(|_| {
+                    scope.spawn(|| {
+                        iter::from_fn(|| {
+                            let index = next.fetch_add(1, Ordering::Relaxed);
+                            puzzles.get(index).map(|puzzle| (index, self.grade(puzzle)))
+                        })
+                        .collect::<Vec<_>>()
+                    })
+                })
+                .collect::<Vec<_>>();
+            workers
+                .into_iter()
+                .flat_map(|worker| {
+                    worker
+                        .join()
+                        .unwrap_or_else(|payload| panic::resume_unwind(payload))
+                })
+                .collect::<Vec<_>>()
+        });
+        reports.sort_by_key(|(index, _)| *index);
+        reports.into_iter().map(|(_, report)| report).collect()
+    }
+
+    /// Writes `reports` as CSV, one row per puzzle after a header row.
+    ///
+    /// The columns are the puzzle, its clue count, its solution count,
+    /// whether the techniques solve it, the assumptions of the first solution,
+    /// the difficulty tier, the total technique steps, and then one count per
+    /// technique, headed by the technique ID. Empty fields mean "not
+    /// applicable": no solution was found, or the techniques got stuck.
+    ///
+    /// The reports must come from this grader, so that the technique counts
+    /// line up with the header.
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from writing to `writer`.
+    pub fn write_csv<W>(&self, writer: &mut W, reports: &[GradeReport]) -> io::Result<()>
+    where
+        W: Write + ?Sized,
+    {
+        write!(
+            writer,
+            "puzzle,clues,solutions,solved_by_techniques,assumptions,tier,total_steps"
+        )?;
+        for technique in self.solver.techniques() {
+            write!(writer, ",{}", technique.id())?;
+        }
+        writeln!(writer)?;
+
+        for report in reports {
+            write!(
+                writer,
+                "{},{},{},{},{},{},{}",
+                report.puzzle,
+                report.clues,
+                report.solutions.as_str(),
+                report.solved_by_techniques,
+                report
+                    .assumptions
+                    .map_or_else(String::new, |count| count.to_string()),
+                report.tier.map_or("", TechniqueTier::as_str),
+                report.technique.total_steps(),
+            )?;
+            for count in report.technique.applications() {
+                write!(writer, ",{count}")?;
+            }
+            writeln!(writer)?;
+        }
+        Ok(())
+    }
+
+    /// Looks for up to two solutions, returning how many there are and the
+    /// assumptions of the first one.
+    fn count_solutions(&self, puzzle: &DigitGrid) -> (SolutionCount, Option<usize>) {
+        let solver = BacktrackSolver::new(self.solver.clone()).with_budget(self.budget);
+        let mut solutions = match solver.solve_with_step(TechniqueGrid::from_digit_grid(puzzle)) {
+            Ok(solutions) => solutions,
+            Err(SolverError::Inconsistent(_)) => return (SolutionCount::NoSolution, None),
+            Err(SolverError::BudgetExceeded) => return (SolutionCount::Undetermined, None),
+        };
+        let assumptions = match solutions.try_next() {
+            Ok(Some((_grid, stats))) => stats.assumptions().len(),
+            Ok(None) | Err(SolverError::Inconsistent(_)) => {
+                return (SolutionCount::NoSolution, None);
+            }
+            Err(SolverError::BudgetExceeded) => return (SolutionCount::Undetermined, None),
+        };
+        let count = match solutions.try_next() {
+            Ok(None) | Err(SolverError::Inconsistent(_)) => SolutionCount::Unique,
+            Ok(Some(_)) => SolutionCount::Multiple,
+            Err(SolverError::BudgetExceeded) => SolutionCount::Undetermined,
+        };
+        (count, Some(assumptions))
+    }
+}
+
+/// Returns the tier of the hardest technique applied, or the lowest tier when
+/// none had to be applied.
+fn hardest_tier(solver: &TechniqueSolver, stats: &TechniqueSolverStats) -> TechniqueTier {
+    iter::zip(solver.techniques(), stats.applications())
+        .filter(|(_technique, applications)| **applications > 0)
+        .map(|(technique, _applications)| technique.tier())
+        .max()
+        .unwrap_or(TechniqueTier::Fundamental)
+}
+
+/// How many solutions a graded puzzle has.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, derive_more::IsVariant)]
+pub enum SolutionCount {
+    /// The puzzle contradicts itself.
+    NoSolution,
+    /// The puzzle has exactly one solution.
+    Unique,
+    /// The puzzle has more than one solution.
+    Multiple,
+    /// The backtracking budget ran out before the count was known.
+    Undetermined,
+}
+
+impl SolutionCount {
+    /// Returns the stable string form used in CSV reports.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::NoSolution => "none",
+            Self::Unique => "unique",
+            Self::Multiple => "multiple",
+            Self::Undetermined => "undetermined",
+        }
+    }
+}
+
+/// Grading result of one puzzle, produced by [`BatchGrader`].
+#[derive(Debug, Clone)]
+pub struct GradeReport {
+    /// The graded puzzle.
+    pub puzzle: DigitGrid,
+    /// Number of given digits.
+    pub clues: usize,
+    /// How many solutions the puzzle has.
+    pub solutions: SolutionCount,
+    /// Whether the techniques alone solve the puzzle, without backtracking.
+    pub solved_by_techniques: bool,
+    /// Assumptions the backtracking made on the way to the first solution;
+    /// `None` when no solution was found.
+    pub assumptions: Option<usize>,
+    /// Tier of the hardest technique needed; `None` when the techniques get
+    /// stuck.
+    pub tier: Option<TechniqueTier>,
+    /// Technique applications until the techniques solved the puzzle or got
+    /// stuck, aligned with the grader's techniques.
+    pub technique: TechniqueSolverStats,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr as _;
+
+    use super::*;
+    use crate::TechniquePriority;
+
+    /// Puzzles with a published rating; see the file header for the format.
+    const RATED_PUZZLES: &str = include_str!("../calibration/rated_puzzles.txt");
+    /// Generated puzzles filed under the band they were graded in.
+    const REGRESSION_PUZZLES: &str = include_str!("../calibration/regression_puzzles.txt");
+
+    /// Rating bands from easiest to hardest: the technique tiers, then puzzles
+    /// beyond the techniques.
+    const BANDS: [&str; 7] = [
+        "fundamental",
+        "basic",
+        "intermediate",
+        "upper_intermediate",
+        "advanced",
+        "expert",
+        "beyond",
+    ];
+
+    const EASY: &str =
+        "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79";
+    // Escargot: needs assumptions even with all techniques.
+    const ESCARGOT: &str =
+        "1....7.9..3..2...8..96..5....53..9...1..8...26....4...3......1..4......7..7...3..";
+
+    fn puzzle(problem: &str) -> DigitGrid {
+        DigitGrid::from_str(problem).unwrap()
+    }
+
+    #[test]
+    fn test_grade_reports_easy_puzzle() {
+        let report = BatchGrader::new().grade(&puzzle(EASY));
+
+        assert_eq!(report.clues, 30);
+        assert_eq!(report.solutions, SolutionCount::Unique);
+        assert!(report.solved_by_techniques);
+        assert_eq!(report.assumptions, Some(0));
+        assert_eq!(report.tier, Some(TechniqueTier::Fundamental));
+        assert!(report.technique.has_progress());
+    }
+
+    #[test]
+    fn test_grade_reports_backtracking_need() {
+        let report = BatchGrader::new().grade(&puzzle(ESCARGOT));
+
+        assert_eq!(report.solutions, SolutionCount::Unique);
+        assert!(!report.solved_by_techniques);
+        assert!(report.assumptions.is_some_and(|count| count > 0));
+        assert_eq!(report.tier, None);
+    }
+
+    #[test]
+    fn test_grade_reports_solution_count() {
+        let grader = BatchGrader::new();
+
+        let empty = grader.grade(&DigitGrid::new());
+        assert_eq!(empty.clues, 0);
+        assert_eq!(empty.solutions, SolutionCount::Multiple);
+
+        let conflict = grader.grade(&puzzle(&format!("11{}", ".".repeat(79))));
+        assert_eq!(conflict.solutions, SolutionCount::NoSolution);
+        assert_eq!(conflict.assumptions, None);
+        assert!(!conflict.solved_by_techniques);
+
+        let budgeted = grader
+            .with_budget(SolverBudget::new().with_max_steps(0))
+            .grade(&puzzle(ESCARGOT));
+        assert_eq!(budgeted.solutions, SolutionCount::Undetermined);
+    }
+
+    #[test]
+    fn test_grade_batch_keeps_order() {
+        let puzzles = [puzzle(ESCARGOT), puzzle(EASY), DigitGrid::new()];
+        let grader = BatchGrader::new().with_threads(NonZero::new(2).unwrap());
+
+        let reports = grader.grade_batch(&puzzles);
+
+        let order = reports
+            .iter()
+            .map(|report| report.puzzle.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(order, puzzles);
+        assert_eq!(reports[1].tier, Some(TechniqueTier::Fundamental));
+    }
+
+    #[test]
+    fn test_write_csv_has_header_and_rows() {
+        let grader = BatchGrader::new();
+        let reports = grader.grade_batch(&[puzzle(EASY), puzzle(ESCARGOT)]);
+
+        let mut csv = Vec::new();
+        grader.write_csv(&mut csv, &reports).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        let lines = csv.lines().collect::<Vec<_>>();
+
+        let columns = 7 + grader.solver().techniques().len();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("puzzle,clues,solutions,"));
+        assert!(lines[0].ends_with(grader.solver().techniques().last().unwrap().id()));
+        assert!(lines[1].starts_with(&format!("{EASY},30,unique,true,0,fundamental,")));
+        assert!(lines[2].starts_with(&format!("{ESCARGOT},")));
+        assert!(lines[2].contains(",false,"));
+        for line in lines {
+            assert_eq!(line.split(',').count(), columns);
+        }
+    }
+
+    /// A puzzle of a calibration file with the band it is filed under.
+    struct BandedPuzzle<'a> {
+        name: &'a str,
+        band: usize,
+        problem: DigitGrid,
+    }
+
+    fn parse_banded_puzzles(text: &str) -> Vec<BandedPuzzle<'_>> {
+        text.lines()
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| {
+                let [problem, band, name] = line.split_whitespace().collect::<Vec<_>>()[..] else {
+                    panic!("malformed calibration line: {line}");
+                };
+                let band = BANDS
+                    .iter()
+                    .position(|&known| known == band)
+                    .unwrap_or_else(|| panic!("unknown band `{band}` for {name}"));
+                BandedPuzzle {
+                    name,
+                    band,
+                    problem: puzzle(problem),
+                }
+            })
+            .collect()
+    }
+
+    /// Grades `puzzles` with the default priority, as the app does, and
+    /// returns each band with a line per puzzle for failure messages.
+    fn grade_bands(puzzles: &[BandedPuzzle<'_>]) -> (Vec<usize>, String) {
+        let solver = TechniqueSolver::with_priority(&TechniquePriority::new());
+        let problems = puzzles
+            .iter()
+            .map(|puzzle| puzzle.problem.clone())
+            .collect::<Vec<_>>();
+        let graded = BatchGrader::with_solver(solver)
+            .grade_batch(&problems)
+            .iter()
+            .map(|report| {
+                report.tier.map_or(BANDS.len() - 1, |tier| {
+                    BANDS
+                        .iter()
+                        .position(|&band| band == tier.as_str())
+                        .unwrap()
+                })
+            })
+            .collect::<Vec<_>>();
+        let report = iter::zip(puzzles, &graded)
+            .map(|(puzzle, &graded)| {
+                format!(
+                    "{}: filed {}, graded {}",
+                    puzzle.name, BANDS[puzzle.band], BANDS[graded]
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        (graded, report)
+    }
+
+    #[test]
+    fn test_grades_stay_calibrated_against_rated_puzzles() {
+        let puzzles = parse_banded_puzzles(RATED_PUZZLES);
+        let (graded, report) = grade_bands(&puzzles);
+
+        let exact = iter::zip(&puzzles, &graded)
+            .filter(|(puzzle, graded)| puzzle.band == **graded)
+            .count();
+        // Every puzzle within one band, and at least 80% in the rated band.
+        assert!(
+            iter::zip(&puzzles, &graded).all(|(puzzle, &graded)| puzzle.band.abs_diff(graded) <= 1),
+            "a puzzle is graded more than one band off:\n{report}"
+        );
+        assert!(
+            exact * 5 >= puzzles.len() * 4,
+            "too few puzzles graded in their rated band:\n{report}"
+        );
+    }
+
+    #[test]
+    fn test_generated_puzzles_keep_their_graded_band() {
+        let puzzles = parse_banded_puzzles(REGRESSION_PUZZLES);
+        for band in &BANDS[..BANDS.len() - 1] {
+            let count = puzzles
+                .iter()
+                .filter(|puzzle| BANDS[puzzle.band] == *band)
+                .count();
+            assert!(count >= 5, "band `{band}` has only {count} puzzles");
+        }
+
+        let (graded, report) = grade_bands(&puzzles);
+
+        assert!(
+            iter::zip(&puzzles, &graded).all(|(puzzle, &graded)| puzzle.band == graded),
+            "a puzzle moved to another band:\n{report}"
+        );
+    }
+}
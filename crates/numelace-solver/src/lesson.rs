@@ -0,0 +1,284 @@
+//! Lessons teaching the built-in techniques.
+//!
+//! A lesson pairs a short summary and a longer explanation with worked
+//! examples. The examples are generated from the [`corpus`]
+//! position of the technique: besides the position itself, a transposed copy
+//! and a copy rotated by 180 degrees, each with its digits relabelled. These
+//! transformations map rows, columns and boxes onto rows, columns and boxes,
+//! so every technique finds the same pattern in each copy, and the examples
+//! never go stale when a technique changes.
+
+use numelace_core::{Digit, DigitSet, Position};
+
+use crate::{BoxedTechniqueStep, TechniqueGrid, corpus, technique::TechniqueId};
+
+/// A lesson about one technique.
+#[derive(Debug, Clone)]
+pub struct Lesson {
+    /// The technique taught.
+    pub id: TechniqueId,
+    /// The English name of the technique.
+    pub name: &'static str,
+    /// One sentence on what the technique does.
+    pub summary: &'static str,
+    /// How to spot the pattern and why its conclusion holds.
+    pub explanation: &'static str,
+    /// Positions in which the technique is the simplest one to apply.
+    pub examples: Vec<LessonExample>,
+}
+
+/// A position with the step its technique finds there.
+#[derive(Debug, Clone)]
+pub struct LessonExample {
+    /// The candidates before the step.
+    pub grid: TechniqueGrid,
+    /// The step the technique finds in [`grid`](Self::grid).
+    pub step: BoxedTechniqueStep,
+}
+
+/// Returns the lesson for the technique with the given ID.
+///
+/// Returns `None` for variant techniques such as
+/// [`CageCombination`](crate::technique::CageCombination), whose examples
+/// need puzzle data beyond the candidates.
+#[must_use]
+pub fn lesson(id: TechniqueId) -> Option<Lesson> {
+    let technique = crate::technique::by_id(id)?;
+    let base = corpus::example(id)?;
+    let examples = [
+        base.clone(),
+        transformed(
+            &base,
+            |pos| Position::new(pos.col(), pos.row()),
+            |value| value % 9 + 1,
+        ),
+        transformed(
+            &base,
+            |pos| Position::new(8 - pos.row(), 8 - pos.col()),
+            |value| 10 - value,
+        ),
+    ]
+    .into_iter()
+    .filter_map(|grid| {
+        let step = technique.find_step(&grid).ok()??;
+        Some(LessonExample { grid, step })
+    })
+    .collect();
+    let (summary, explanation) = text(id);
+    Some(Lesson {
+        id,
+        name: technique.name(),
+        summary,
+        explanation,
+        examples,
+    })
+}
+
+/// Returns lessons for all built-in techniques, from easiest to hardest.
+#[must_use]
+pub fn all_lessons() -> Vec<Lesson> {
+    TechniqueId::ALL.into_iter().filter_map(lesson).collect()
+}
+
+/// Copies the candidates of `grid`, moving cells by `map_pos` and renaming
+/// digit values by `map_value`.
+fn transformed(
+    grid: &TechniqueGrid,
+    map_pos: impl Fn(Position) -> Position,
+    map_value: impl Fn(u8) -> u8,
+) -> TechniqueGrid {
+    let mut out = TechniqueGrid::new();
+    for pos in Position::ALL {
+        let digits: DigitSet = grid
+            .candidates_at(pos)
+            .iter()
+            .map(|digit| Digit::from_value(map_value(digit.value())))
+            .collect();
+        out.remove_candidate_set(map_pos(pos), !digits);
+    }
+    out
+}
+
+#[expect(clippy::too_many_lines)]
+fn text(id: TechniqueId) -> (&'static str, &'static str) {
+    match id {
+        TechniqueId::FullHouse => (
+            "The last open cell of a house takes the one digit the house is missing.",
+            "Look for a row, column or box with eight cells already filled. Only one digit is \
+             left for the house, and only one cell is left to hold it, so place it there.",
+        ),
+        TechniqueId::NakedSingle => (
+            "A cell with only one candidate left must hold that digit.",
+            "Look for a cell whose row, column and box together already rule out eight digits. \
+             The remaining digit is the only one the cell can take, so place it.",
+        ),
+        TechniqueId::HiddenSingle => (
+            "A digit with only one possible cell in a house must go there.",
+            "Pick a row, column or box and a digit it still needs. If every cell of the house \
+             but one is ruled out for that digit, the digit goes in that cell, even if the cell \
+             has other candidates.",
+        ),
+        TechniqueId::LockedCandidates => (
+            "A digit confined to one line of a box, or to one box of a line, is removed from \
+             the rest of the other house.",
+            "Pointing: if a digit's candidates in a box all lie in one row or column, the box's \
+             copy of the digit is in that line, so no other cell of the line can hold it. \
+             Claiming works the other way round: if a digit's candidates in a row or column all \
+             lie in one box, no other cell of that box can hold it.",
+        ),
+        TechniqueId::NakedPair => (
+            "Two cells of a house with the same two candidates take those digits from the rest \
+             of the house.",
+            "The two cells must hold the two digits between them, in some order. Whatever the \
+             order, no other cell of the house can hold either digit.",
+        ),
+        TechniqueId::HiddenPair => (
+            "Two digits that fit only in the same two cells of a house fill those cells.",
+            "Each of the two digits must go in one of the two cells, so the cells hold exactly \
+             those digits. Every other candidate of the two cells can be removed.",
+        ),
+        TechniqueId::NakedTriple => (
+            "Three cells of a house holding only three digits between them take those digits \
+             from the rest of the house.",
+            "The cells need not all hold all three digits; it is enough that their candidates \
+             together are just three digits. The three cells use up those digits, so the other \
+             cells of the house cannot hold them.",
+        ),
+        TechniqueId::HiddenTriple => (
+            "Three digits that fit only in the same three cells of a house fill those cells.",
+            "Within the house, the three digits have nowhere to go but the three cells, so the \
+             cells hold exactly those digits. Remove their other candidates.",
+        ),
+        TechniqueId::NakedQuad => (
+            "Four cells of a house holding only four digits between them take those digits \
+             from the rest of the house.",
+            "This is the naked triple with one more cell: the four cells use up the four \
+             digits, so no other cell of the house can hold any of them.",
+        ),
+        TechniqueId::HiddenQuad => (
+            "Four digits that fit only in the same four cells of a house fill those cells.",
+            "The four digits must share the four cells, so the cells have no room for anything \
+             else. Remove their other candidates.",
+        ),
+        TechniqueId::XWing => (
+            "A digit limited to the same two columns in two rows is removed from the rest of \
+             those columns.",
+            "In each of the two rows the digit sits in one of the two columns. The two rows \
+             therefore use up the digit in both columns, so no other cell of those columns can \
+             hold it. The same works with rows and columns swapped.",
+        ),
+        TechniqueId::Skyscraper => (
+            "Two strong links on a digit sharing one end line leave it in one of the other two \
+             ends.",
+            "A strong link is a house with only two cells for a digit. Take two parallel links \
+             whose ends line up on one side. At most one of those aligned ends holds the digit, \
+             so one of the other ends does, and cells seeing both of those ends cannot hold it.",
+        ),
+        TechniqueId::TwoStringKite => (
+            "A row link and a column link on a digit meeting in a box leave it in one of their \
+             free ends.",
+            "Take a row and a column that each have two cells for a digit, with one end of each \
+             in the same box. Those two ends cannot both hold the digit, so one of the free ends \
+             does, and cells seeing both free ends cannot hold it.",
+        ),
+        TechniqueId::YWing => (
+            "A pivot and two wings force a digit into one of the wings.",
+            "The pivot has candidates XY; one wing it sees has XZ and another has YZ. Whichever \
+             digit the pivot takes, one wing is left with Z. Cells seeing both wings cannot hold \
+             Z.",
+        ),
+        TechniqueId::UniqueRectangle => (
+            "Four corners of a rectangle in two boxes cannot all end up with the same two \
+             digits, since a unique puzzle could then swap them.",
+            "If three corners hold only the same two candidates, the fourth cannot be either \
+             digit. If two corners hold only the pair and the other two hold the pair plus the \
+             same extra digit, one of those two must take the extra digit, so cells seeing both \
+             cannot hold it. This only works for puzzles with a unique solution.",
+        ),
+        TechniqueId::Swordfish => (
+            "A digit limited to the same three columns in three rows is removed from the rest \
+             of those columns.",
+            "This is the X-Wing with three lines: the three rows place the digit in three \
+             different columns among the three, so the columns have no other place for it.",
+        ),
+        TechniqueId::Jellyfish => (
+            "A digit limited to the same four columns in four rows is removed from the rest of \
+             those columns.",
+            "This is the X-Wing with four lines: the four rows use up the digit in all four \
+             columns, so no other cell of those columns can hold it.",
+        ),
+        TechniqueId::RemotePair => (
+            "A chain of cells with the same two candidates alternates them along the chain.",
+            "Neighbouring cells of the chain see each other, so they hold different digits of \
+             the pair. Two ends an odd number of links apart therefore hold both digits \
+             between them, and cells seeing both ends can hold neither.",
+        ),
+        TechniqueId::WWing => (
+            "Two cells with the same two candidates, joined by a strong link on one of them, \
+             take the other digit from the cells seeing both.",
+            "Take two cells holding only A and B that do not see each other. If a house has A \
+             in just two cells, one seeing each of the two, one end of the link is A, so one of \
+             the two cells cannot be A and must be B. Any cell seeing both of them cannot be B.",
+        ),
+        TechniqueId::XChain => (
+            "An alternating chain of links on one digit proves that one of its ends holds it.",
+            "The chain alternates strong links, where one of two cells must hold the digit, and \
+             weak links, where at most one can. If the first end does not hold the digit, the \
+             chain forces it into the last end. Cells seeing both ends cannot hold it.",
+        ),
+        TechniqueId::XyChain => (
+            "A chain of two-candidate cells proves that one of its ends holds a digit.",
+            "Each cell has two candidates and shares one with the next cell. If the first cell \
+             is not the starting digit, each cell in turn is forced, until the last cell holds \
+             that same digit. Cells seeing both ends cannot hold it.",
+        ),
+        TechniqueId::XyzWing => (
+            "A three-candidate pivot and two wings force a digit into one of three cells.",
+            "The pivot has XYZ, one wing XZ and the other YZ. Whatever the pivot holds, Z ends \
+             up in the pivot or a wing, so cells seeing all three cells cannot hold Z.",
+        ),
+        TechniqueId::WxyzWing => (
+            "Four cells holding four digits force a shared digit into one of them.",
+            "Four cells that see a hinge cell hold only four digits between them, and only one \
+             digit can repeat among cells that do not see each other. That digit must be in one \
+             of the cells holding it, so cells seeing all of those cannot hold it.",
+        ),
+        TechniqueId::Aic => (
+            "A chain of strong and weak links proves that one of its two ends is true.",
+            "A strong link joins two candidates of which one must be true: the two candidates of \
+             a cell, or the two places for a digit in a house. A weak link joins two candidates \
+             that cannot both be true. Alternating them, if the first end is false the last end \
+             is true, so candidates that conflict with both ends can be removed.",
+        ),
+        TechniqueId::CageCombination | TechniqueId::EdgeRelation => {
+            unreachable!("variant techniques have no lessons")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::technique::all_techniques_assuming_unique_solution;
+
+    #[test]
+    fn every_technique_has_three_examples_it_finds_first() {
+        let techniques = all_techniques_assuming_unique_solution();
+        let lessons = all_lessons();
+        assert_eq!(lessons.len(), techniques.len());
+        for (i, lesson) in lessons.iter().enumerate() {
+            assert_eq!(lesson.examples.len(), 3, "{}", lesson.id);
+            for example in &lesson.examples {
+                assert_eq!(example.step.technique_id(), Some(lesson.id));
+                for simpler in &techniques[..i] {
+                    assert!(
+                        simpler.find_step(&example.grid).unwrap().is_none(),
+                        "{} preempts {} on its example",
+                        simpler.id(),
+                        lesson.id
+                    );
+                }
+            }
+        }
+    }
+}
@@ -163,6 +163,7 @@
 //!
 //! Currently implemented techniques (in order of difficulty):
 //!
+//! - [`FullHouse`](technique::FullHouse): The last open cell of a house
 //! - [`NakedSingle`](technique::NakedSingle): A cell with only one candidate
 //! - [`HiddenSingle`](technique::HiddenSingle): A digit that can only go in one cell in a house
 //!
@@ -236,19 +237,32 @@
 //! - A cell with no remaining candidates
 //! - Contradictory placements
 //!
-//! This typically indicates the input puzzle is invalid or unsolvable.
+//! This typically indicates the input puzzle is invalid or unsolvable. The wrapped
+//! [`ConsistencyError`](numelace_core::ConsistencyError) is also returned by
+//! [`source()`](std::error::Error::source) and records the cells involved, so callers
+//! can point the user at them.
+//!
+//! A solver given a [`SolverBudget`] returns [`SolverError::BudgetExceeded`]
+//! once it runs out of steps or time, so that callers can give up on
+//! adversarial inputs instead of hanging.
 
 pub use self::{
-    backtrack_solver::*, error::*, technique::traits::*, technique_grid::*, technique_solver::*,
-    technique_step::*,
+    backtrack_solver::*, budget::*, deduction_depth::*, error::*, grading::*, technique::traits::*,
+    technique_grid::*, technique_priority::*, technique_solver::*, technique_step::*,
 };
 
 mod axis;
 pub mod backtrack;
 mod backtrack_solver;
+mod budget;
+pub mod corpus;
+mod deduction_depth;
 mod error;
+mod grading;
+pub mod lesson;
 pub mod technique;
 mod technique_grid;
+mod technique_priority;
 mod technique_solver;
 mod technique_step;
 
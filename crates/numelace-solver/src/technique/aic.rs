@@ -0,0 +1,389 @@
+use std::ops::ControlFlow;
+
+use numelace_core::{Digit, DigitPositions, House, RegionLayout};
+
+use crate::{
+    BoxedTechnique, BoxedTechniqueStep, ChainNode, SolverError, Technique, TechniqueGrid,
+    TechniqueStepData, TechniqueTier, technique::TechniqueId,
+};
+
+const ID: TechniqueId = TechniqueId::Aic;
+const NAME: &str = "Alternating Inference Chain";
+
+/// A technique that removes candidates using an alternating inference chain.
+///
+/// The candidates of the grid are linked in two ways:
+///
+/// - A strong link joins two candidates of which at least one must be true:
+///   the two candidates of a bivalue cell, or the two cells where a digit
+///   fits in a house.
+/// - A weak link joins two candidates of which at most one can be true: two
+///   candidates of the same cell, or the same digit in two cells that see
+///   each other.
+///
+/// An "alternating inference chain" (AIC) starts and ends with a strong link
+/// and alternates between strong and weak links. If the first candidate is
+/// false the last one must be true, so at least one of the two ends is true,
+/// and any candidate weakly linked to both ends can be removed. X-Chains and
+/// XY-Chains are the special cases that use only one kind of strong link.
+///
+/// The search finds the shortest chains first and gives up on chains longer
+/// than [`max_nodes`](Self::max_nodes) candidates.
+#[derive(Debug, Clone, Copy)]
+pub struct Aic {
+    max_nodes: usize,
+}
+
+impl Default for Aic {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+struct Condition {
+    chain: Vec<ChainNode>,
+    /// Candidates weakly linked to both ends, as (positions, digit) pairs.
+    eliminations: Vec<(DigitPositions, Digit)>,
+}
+
+impl Condition {
+    fn build_step(
+        &self,
+        before_grid: &TechniqueGrid,
+        after_grid: &TechniqueGrid,
+    ) -> BoxedTechniqueStep {
+        TechniqueStepData::from_chain_diff(ID, NAME, self.chain.clone(), before_grid, after_grid)
+    }
+}
+
+/// Strong links between the candidates of a grid.
+struct LinkGraph {
+    /// Candidates with at least one strong link.
+    nodes: Vec<ChainNode>,
+    /// Strong link partners of each node, as indices into `nodes`.
+    strong: Vec<Vec<usize>>,
+}
+
+impl LinkGraph {
+    fn new(grid: &TechniqueGrid) -> Self {
+        let open = !grid.univalue_positions();
+        let mut links = vec![];
+        for pos in open {
+            if let Some([d1, d2]) = grid.candidates_at(pos).as_double() {
+                links.push((ChainNode::new(pos, d1), ChainNode::new(pos, d2)));
+            }
+        }
+        for digit in Digit::ALL {
+            for house in House::ALL {
+                let cells = grid.house_positions(house) & grid.digit_positions(digit);
+                if let Some([pos1, pos2]) = cells.as_double()
+                    && cells.is_subset(open)
+                {
+                    links.push((ChainNode::new(pos1, digit), ChainNode::new(pos2, digit)));
+                }
+            }
+        }
+
+        let mut graph = Self {
+            nodes: vec![],
+            strong: vec![],
+        };
+        let mut indices = [None; 81 * 9];
+        for (node1, node2) in links {
+            let index1 = graph.index_of(&mut indices, node1);
+            let index2 = graph.index_of(&mut indices, node2);
+            // A pair of cells can share both a line and a box.
+            if !graph.strong[index1].contains(&index2) {
+                graph.strong[index1].push(index2);
+                graph.strong[index2].push(index1);
+            }
+        }
+        graph
+    }
+
+    fn index_of(&mut self, indices: &mut [Option<usize>], node: ChainNode) -> usize {
+        let key = usize::from(node.position.index()) * 9 + usize::from(node.digit.value() - 1);
+        *indices[key].get_or_insert_with(|| {
+            self.nodes.push(node);
+            self.strong.push(vec![]);
+            self.nodes.len() - 1
+        })
+    }
+
+    /// Finds the shortest chain of at most `max_nodes` candidates starting at
+    /// `start` that removes a candidate.
+    fn find_chain(
+        &self,
+        grid: &TechniqueGrid,
+        start: usize,
+        max_nodes: usize,
+    ) -> Option<Condition> {
+        // For each reached chain end, the node strongly linked to it and the
+        // end of the chain before the weak link (`None` for the first link).
+        let mut from: Vec<Option<(usize, Option<usize>)>> = vec![None; self.nodes.len()];
+        let mut reached = vec![false; self.nodes.len()];
+        reached[start] = true;
+        let mut ends = vec![];
+        for &end in &self.strong[start] {
+            reached[end] = true;
+            from[end] = Some((start, None));
+            ends.push(end);
+        }
+
+        let mut len = 2;
+        while len <= max_nodes && !ends.is_empty() {
+            for &end in &ends {
+                let eliminations = eliminations(grid, self.nodes[start], self.nodes[end]);
+                if !eliminations.is_empty() {
+                    return Some(Condition {
+                        chain: self.chain_to(&from, end),
+                        eliminations,
+                    });
+                }
+            }
+            let mut next_ends = vec![];
+            for &prev in &ends {
+                for (next, &node) in self.nodes.iter().enumerate() {
+                    if !is_weak_link(grid.regions(), self.nodes[prev], node) {
+                        continue;
+                    }
+                    for &end in &self.strong[next] {
+                        if !reached[end] {
+                            reached[end] = true;
+                            from[end] = Some((next, Some(prev)));
+                            next_ends.push(end);
+                        }
+                    }
+                }
+            }
+            ends = next_ends;
+            len += 2;
+        }
+        None
+    }
+
+    fn chain_to(&self, from: &[Option<(usize, Option<usize>)>], end: usize) -> Vec<ChainNode> {
+        let mut chain = vec![self.nodes[end]];
+        let mut current = end;
+        while let Some((linked, prev)) = from[current] {
+            chain.push(self.nodes[linked]);
+            let Some(prev) = prev else {
+                break;
+            };
+            chain.push(self.nodes[prev]);
+            current = prev;
+        }
+        chain.reverse();
+        chain
+    }
+}
+
+/// Returns `true` if at most one of `node1` and `node2` can be true.
+fn is_weak_link(regions: &RegionLayout, node1: ChainNode, node2: ChainNode) -> bool {
+    if node1.position == node2.position {
+        node1.digit != node2.digit
+    } else {
+        node1.digit == node2.digit && regions.house_peers(node1.position).contains(node2.position)
+    }
+}
+
+/// Returns the candidates weakly linked to both ends of a chain.
+fn eliminations(
+    grid: &TechniqueGrid,
+    first: ChainNode,
+    last: ChainNode,
+) -> Vec<(DigitPositions, Digit)> {
+    let mut eliminations = vec![];
+    if first.digit == last.digit {
+        let positions = grid.house_peers(first.position)
+            & grid.house_peers(last.position)
+            & grid.digit_positions(first.digit);
+        if !positions.is_empty() {
+            eliminations.push((positions, first.digit));
+        }
+    } else if first.position == last.position {
+        for digit in grid.candidates_at(first.position) {
+            if digit != first.digit && digit != last.digit {
+                eliminations.push((DigitPositions::from_elem(first.position), digit));
+            }
+        }
+    } else if grid.house_peers(first.position).contains(last.position) {
+        for (pos, digit) in [(last.position, first.digit), (first.position, last.digit)] {
+            if grid.candidates_at(pos).contains(digit) {
+                eliminations.push((DigitPositions::from_elem(pos), digit));
+            }
+        }
+    }
+    eliminations
+}
+
+impl Aic {
+    /// Chain length, in candidates, searched by [`Aic::new`].
+    pub const DEFAULT_MAX_NODES: usize = 12;
+
+    /// Creates a new `Aic` technique that searches chains of up to
+    /// [`DEFAULT_MAX_NODES`](Self::DEFAULT_MAX_NODES) candidates.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self::with_max_nodes(Self::DEFAULT_MAX_NODES)
+    }
+
+    /// Creates a new `Aic` technique that searches chains of up to
+    /// `max_nodes` candidates.
+    ///
+    /// Longer chains find more eliminations but are slower to search and
+    /// harder to follow in a hint.
+    #[must_use]
+    pub const fn with_max_nodes(max_nodes: usize) -> Self {
+        Self { max_nodes }
+    }
+
+    /// Returns the longest chain, in candidates, that the technique searches.
+    #[must_use]
+    pub const fn max_nodes(self) -> usize {
+        self.max_nodes
+    }
+
+    #[inline]
+    fn apply_with_control_flow<T, F>(
+        self,
+        grid: &mut TechniqueGrid,
+        mut on_condition: F,
+    ) -> Option<T>
+    where
+        F: for<'a> FnMut(&'a mut TechniqueGrid, &'a Condition) -> ControlFlow<T>,
+    {
+        // Every removal changes the links, so rebuild the graph after each one.
+        loop {
+            let graph = LinkGraph::new(grid);
+            let mut best: Option<Condition> = None;
+            for start in 0..graph.nodes.len() {
+                let max_nodes = best
+                    .as_ref()
+                    .map_or(self.max_nodes, |found| found.chain.len() - 2);
+                if let Some(found) = graph.find_chain(grid, start, max_nodes) {
+                    best = Some(found);
+                }
+            }
+            let condition = best?;
+            for &(positions, digit) in &condition.eliminations {
+                grid.remove_candidate_with_mask(positions, digit);
+            }
+            if let ControlFlow::Break(value) = on_condition(grid, &condition) {
+                return Some(value);
+            }
+        }
+    }
+}
+
+impl Technique for Aic {
+    fn id(&self) -> &'static str {
+        ID.as_str()
+    }
+
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    fn tier(&self) -> TechniqueTier {
+        TechniqueTier::Expert
+    }
+
+    fn clone_box(&self) -> BoxedTechnique {
+        Box::new(*self)
+    }
+
+    fn find_step(&self, grid: &TechniqueGrid) -> Result<Option<BoxedTechniqueStep>, SolverError> {
+        let mut after_grid = grid.clone();
+        let step = self.apply_with_control_flow(&mut after_grid, |after_grid, condition| {
+            ControlFlow::Break(condition.build_step(grid, after_grid))
+        });
+        Ok(step)
+    }
+
+    fn apply_step(&self, grid: &mut TechniqueGrid) -> Result<bool, SolverError> {
+        let changed = self
+            .apply_with_control_flow(grid, |_, _| ControlFlow::Break(()))
+            .is_some();
+        Ok(changed)
+    }
+
+    fn apply_pass(&self, grid: &mut TechniqueGrid) -> Result<usize, SolverError> {
+        let mut changed = 0;
+        self.apply_with_control_flow(grid, |_, _| {
+            changed += 1;
+            ControlFlow::<()>::Continue(())
+        });
+        Ok(changed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use numelace_core::{CandidateGrid, Digit, Position};
+
+    use super::*;
+    use crate::testing;
+
+    const TECHNIQUE: Aic = Aic::new();
+
+    /// A chain mixing a bivalue cell and two houses:
+    /// r1c1 1=2, r1c5 2=r5c5 2 (column 5), r5c5 3=r5c1 3 (row 5).
+    fn mixed_chain_grid() -> CandidateGrid {
+        let mut grid = CandidateGrid::new();
+        for digit in Digit::ALL {
+            if digit != Digit::D1 && digit != Digit::D2 {
+                grid.remove_candidate(Position::new(0, 0), digit);
+            }
+        }
+        for row in [1, 2, 3, 5, 6, 7, 8] {
+            grid.remove_candidate(Position::new(row, 4), Digit::D2);
+        }
+        for col in [1, 2, 3, 5, 6, 7, 8] {
+            grid.remove_candidate(Position::new(4, col), Digit::D3);
+        }
+        grid
+    }
+
+    #[test]
+    fn test_eliminates_candidate_seeing_both_ends() {
+        // Either r1c1 is 1 or r5c1 is 3, so r5c1 cannot be 1.
+        testing::test_technique_apply_pass(mixed_chain_grid(), &TECHNIQUE, |t| {
+            t.assert_removed_exact(Position::new(4, 0), [Digit::D1]);
+        });
+    }
+
+    #[test]
+    fn test_step_lists_alternating_chain() {
+        let grid = TechniqueGrid::from(mixed_chain_grid());
+        let step = TECHNIQUE.find_step(&grid).unwrap().unwrap();
+        let chain = step.chain();
+        let regions = RegionLayout::standard();
+        assert_eq!(chain.len(), 6);
+        for pair in chain.windows(2).skip(1).step_by(2) {
+            assert!(is_weak_link(&regions, pair[0], pair[1]), "{pair:?}");
+        }
+        let first = chain[0];
+        let last = chain[chain.len() - 1];
+        assert!(is_weak_link(
+            &regions,
+            first,
+            ChainNode::new(Position::new(4, 0), Digit::D1)
+        ));
+        assert!(is_weak_link(
+            &regions,
+            last,
+            ChainNode::new(Position::new(4, 0), Digit::D1)
+        ));
+    }
+
+    #[test]
+    fn test_no_change_beyond_length_limit() {
+        testing::test_technique_apply_pass_no_changes(mixed_chain_grid(), &Aic::with_max_nodes(4));
+    }
+
+    #[test]
+    fn test_no_change_without_strong_links() {
+        testing::test_technique_apply_pass_no_changes(CandidateGrid::new(), &TECHNIQUE);
+    }
+}
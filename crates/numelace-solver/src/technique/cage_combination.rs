@@ -0,0 +1,238 @@
+use std::ops::ControlFlow;
+
+use numelace_core::{Cage, CageSet, ConsistencyError, DigitSet};
+
+use crate::{
+    BoxedTechnique, BoxedTechniqueStep, SolverError, Technique, TechniqueGrid, TechniqueStepData,
+    TechniqueTier, technique::TechniqueId,
+};
+
+const ID: TechniqueId = TechniqueId::CageCombination;
+const NAME: &str = "Cage Combination";
+
+/// A technique that restricts killer cage cells to digits of valid sum combinations.
+///
+/// For each cage, the digits already decided inside it are fixed. The open
+/// cells can only hold digits that complete a set of distinct digits adding up
+/// to the cage sum, so every other candidate is removed from them. A cage with
+/// no valid combination left is a contradiction.
+///
+/// This technique is not part of [`all_techniques`](crate::technique::all_techniques)
+/// because it only applies to puzzles that carry cages.
+#[derive(Debug, Default, Clone)]
+pub struct CageCombination {
+    cages: CageSet,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Condition {
+    cage: Cage,
+    digits: DigitSet,
+}
+
+impl Condition {
+    fn build_step(
+        &self,
+        before_grid: &TechniqueGrid,
+        after_grid: &TechniqueGrid,
+    ) -> BoxedTechniqueStep {
+        let condition_positions = self.cage.positions();
+        let open_positions = condition_positions & !before_grid.univalue_positions();
+        let condition_digit_positions = vec![(open_positions, self.digits)];
+        TechniqueStepData::from_diff(
+            ID,
+            NAME,
+            condition_positions,
+            condition_digit_positions,
+            before_grid,
+            after_grid,
+        )
+    }
+}
+
+impl CageCombination {
+    /// Creates a new `CageCombination` technique for the given cages.
+    #[must_use]
+    pub fn new(cages: CageSet) -> Self {
+        Self { cages }
+    }
+
+    /// Returns the cages this technique reasons about.
+    #[must_use]
+    pub fn cages(&self) -> &CageSet {
+        &self.cages
+    }
+
+    #[inline]
+    fn apply_with_control_flow<T, F>(
+        &self,
+        grid: &mut TechniqueGrid,
+        mut on_condition: F,
+    ) -> Result<Option<T>, SolverError>
+    where
+        F: for<'a> FnMut(&'a mut TechniqueGrid, &'a Condition) -> ControlFlow<T>,
+    {
+        for cage in &self.cages {
+            let Some(digits) = cage.open_cell_digits(|pos| grid.candidates_at(pos)) else {
+                return Err(ConsistencyError::CandidateConstraintViolation.into());
+            };
+            let open = cage.positions() & !grid.univalue_positions();
+            let mut changed = false;
+            for pos in open {
+                changed |= grid.remove_candidate_set(pos, !digits);
+            }
+            if changed
+                && let ControlFlow::Break(value) = on_condition(
+                    grid,
+                    &Condition {
+                        cage: *cage,
+                        digits,
+                    },
+                )
+            {
+                return Ok(Some(value));
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl Technique for CageCombination {
+    fn id(&self) -> &'static str {
+        ID.as_str()
+    }
+
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    fn tier(&self) -> TechniqueTier {
+        TechniqueTier::Basic
+    }
+
+    fn clone_box(&self) -> BoxedTechnique {
+        Box::new(self.clone())
+    }
+
+    fn find_step(&self, grid: &TechniqueGrid) -> Result<Option<BoxedTechniqueStep>, SolverError> {
+        let mut after_grid = grid.clone();
+        let step = self.apply_with_control_flow(&mut after_grid, |after_grid, condition| {
+            ControlFlow::Break(condition.build_step(grid, after_grid))
+        })?;
+        Ok(step)
+    }
+
+    fn apply_step(&self, grid: &mut TechniqueGrid) -> Result<bool, SolverError> {
+        let changed = self
+            .apply_with_control_flow(grid, |_, _| ControlFlow::Break(()))?
+            .is_some();
+        Ok(changed)
+    }
+
+    fn apply_pass(&self, grid: &mut TechniqueGrid) -> Result<usize, SolverError> {
+        let mut changed = 0;
+        self.apply_with_control_flow(grid, |_, _| {
+            changed += 1;
+            ControlFlow::<()>::Continue(())
+        })?;
+        Ok(changed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use numelace_core::{Digit, DigitPositions, Position};
+
+    use super::*;
+
+    fn cage(cells: &[(u8, u8)], sum: u8) -> Cage {
+        let positions = cells
+            .iter()
+            .map(|&(row, col)| Position::new(row, col))
+            .collect::<DigitPositions>();
+        Cage::new(positions, sum).unwrap()
+    }
+
+    fn technique(cages: impl IntoIterator<Item = Cage>) -> CageCombination {
+        CageCombination::new(CageSet::from_cages(cages).unwrap())
+    }
+
+    fn technique_with_sum(sum: u8) -> CageCombination {
+        technique([cage(&[(0, 0), (0, 1), (1, 0)], sum)])
+    }
+
+    // Cages do not follow grid symmetries, so these tests apply the technique
+    // directly instead of going through `crate::testing`.
+
+    #[test]
+    fn test_restricts_cells_to_combination_digits() {
+        let technique = technique([cage(&[(0, 0), (1, 3)], 3)]);
+        let mut grid = TechniqueGrid::new();
+
+        assert_eq!(technique.apply_pass(&mut grid).unwrap(), 1);
+        let expected = DigitSet::from_iter([Digit::D1, Digit::D2]);
+        assert_eq!(grid.candidates_at(Position::new(0, 0)), expected);
+        assert_eq!(grid.candidates_at(Position::new(1, 3)), expected);
+        assert_eq!(grid.candidates_at(Position::new(0, 1)), DigitSet::FULL);
+    }
+
+    #[test]
+    fn test_uses_decided_digits() {
+        let technique = technique_with_sum(10);
+        let mut grid = TechniqueGrid::new();
+        grid.place(Position::new(0, 0), Digit::D9);
+        // The remaining two cells must sum to 1, which is impossible.
+        assert!(matches!(
+            technique.apply_pass(&mut grid),
+            Err(SolverError::Inconsistent(_))
+        ));
+
+        let technique = technique_with_sum(17);
+        let mut grid = TechniqueGrid::new();
+        grid.place(Position::new(0, 0), Digit::D9);
+        assert_eq!(technique.apply_pass(&mut grid).unwrap(), 1);
+        // 9 + {1,7} / {2,6} / {3,5}
+        let expected = DigitSet::from_iter([
+            Digit::D1,
+            Digit::D2,
+            Digit::D3,
+            Digit::D5,
+            Digit::D6,
+            Digit::D7,
+        ]);
+        assert_eq!(grid.candidates_at(Position::new(0, 1)), expected);
+        assert_eq!(grid.candidates_at(Position::new(1, 0)), expected);
+    }
+
+    #[test]
+    fn test_no_change_when_all_digits_possible() {
+        let technique = technique_with_sum(15);
+        let mut grid = TechniqueGrid::new();
+        assert_eq!(technique.apply_pass(&mut grid).unwrap(), 0);
+        assert!(technique.find_step(&grid).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_fails_on_completed_cage_with_wrong_sum() {
+        let technique = technique([cage(&[(0, 0), (4, 4)], 5)]);
+        let mut grid = TechniqueGrid::new();
+        grid.place(Position::new(0, 0), Digit::D1);
+        grid.place(Position::new(4, 4), Digit::D2);
+        assert!(matches!(
+            technique.apply_pass(&mut grid),
+            Err(SolverError::Inconsistent(_))
+        ));
+    }
+
+    #[test]
+    fn test_fails_on_duplicated_digit_across_houses() {
+        let technique = technique([cage(&[(0, 0), (4, 4)], 4)]);
+        let mut grid = TechniqueGrid::new();
+        grid.place(Position::new(0, 0), Digit::D2);
+        grid.place(Position::new(4, 4), Digit::D2);
+        assert!(matches!(
+            technique.apply_pass(&mut grid),
+            Err(SolverError::Inconsistent(_))
+        ));
+    }
+}
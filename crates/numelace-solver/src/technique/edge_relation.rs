@@ -0,0 +1,196 @@
+use std::ops::ControlFlow;
+
+use numelace_core::{
+    ConsistencyError, DigitPositions, DigitSet, EdgeConstraint, EdgeConstraintSet,
+};
+
+use crate::{
+    BoxedTechnique, BoxedTechniqueStep, SolverError, Technique, TechniqueGrid, TechniqueStepData,
+    TechniqueTier, technique::TechniqueId,
+};
+
+const ID: TechniqueId = TechniqueId::EdgeRelation;
+const NAME: &str = "Edge Relation";
+
+/// A technique that enforces greater-than and consecutive markers between cells.
+///
+/// For each marked edge, a candidate of one cell is removed when no candidate
+/// of the neighboring cell satisfies the relation with it. An edge with no
+/// supported candidate left on either side is a contradiction.
+///
+/// This technique is not part of [`all_techniques`](crate::technique::all_techniques)
+/// because it only applies to puzzles that carry edge constraints.
+#[derive(Debug, Default, Clone)]
+pub struct EdgeRelation {
+    constraints: EdgeConstraintSet,
+}
+
+#[derive(Debug, Clone)]
+struct Condition {
+    constraint: EdgeConstraint,
+    first_digits: DigitSet,
+    second_digits: DigitSet,
+}
+
+impl Condition {
+    fn build_step(
+        &self,
+        before_grid: &TechniqueGrid,
+        after_grid: &TechniqueGrid,
+    ) -> BoxedTechniqueStep {
+        let first = DigitPositions::from_elem(self.constraint.first());
+        let second = DigitPositions::from_elem(self.constraint.second());
+        let condition_digit_positions =
+            vec![(first, self.first_digits), (second, self.second_digits)];
+        TechniqueStepData::from_diff(
+            ID,
+            NAME,
+            first | second,
+            condition_digit_positions,
+            before_grid,
+            after_grid,
+        )
+    }
+}
+
+impl EdgeRelation {
+    /// Creates a new `EdgeRelation` technique for the given constraints.
+    #[must_use]
+    pub fn new(constraints: EdgeConstraintSet) -> Self {
+        Self { constraints }
+    }
+
+    /// Returns the edge constraints this technique reasons about.
+    #[must_use]
+    pub fn constraints(&self) -> &EdgeConstraintSet {
+        &self.constraints
+    }
+
+    #[inline]
+    fn apply_with_control_flow<T, F>(
+        &self,
+        grid: &mut TechniqueGrid,
+        mut on_condition: F,
+    ) -> Result<Option<T>, SolverError>
+    where
+        F: for<'a> FnMut(&'a mut TechniqueGrid, &'a Condition) -> ControlFlow<T>,
+    {
+        for &constraint in &self.constraints {
+            let (first, second) = (constraint.first(), constraint.second());
+            let (first_digits, second_digits) =
+                constraint.supported_digits(grid.candidates_at(first), grid.candidates_at(second));
+            if first_digits.is_empty() || second_digits.is_empty() {
+                return Err(ConsistencyError::CandidateConstraintViolation.into());
+            }
+            let mut changed = grid.remove_candidate_set(first, !first_digits);
+            changed |= grid.remove_candidate_set(second, !second_digits);
+            if changed
+                && let ControlFlow::Break(value) = on_condition(
+                    grid,
+                    &Condition {
+                        constraint,
+                        first_digits,
+                        second_digits,
+                    },
+                )
+            {
+                return Ok(Some(value));
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl Technique for EdgeRelation {
+    fn id(&self) -> &'static str {
+        ID.as_str()
+    }
+
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    fn tier(&self) -> TechniqueTier {
+        TechniqueTier::Basic
+    }
+
+    fn clone_box(&self) -> BoxedTechnique {
+        Box::new(self.clone())
+    }
+
+    fn find_step(&self, grid: &TechniqueGrid) -> Result<Option<BoxedTechniqueStep>, SolverError> {
+        let mut after_grid = grid.clone();
+        let step = self.apply_with_control_flow(&mut after_grid, |after_grid, condition| {
+            ControlFlow::Break(condition.build_step(grid, after_grid))
+        })?;
+        Ok(step)
+    }
+
+    fn apply_step(&self, grid: &mut TechniqueGrid) -> Result<bool, SolverError> {
+        let changed = self
+            .apply_with_control_flow(grid, |_, _| ControlFlow::Break(()))?
+            .is_some();
+        Ok(changed)
+    }
+
+    fn apply_pass(&self, grid: &mut TechniqueGrid) -> Result<usize, SolverError> {
+        let mut changed = 0;
+        self.apply_with_control_flow(grid, |_, _| {
+            changed += 1;
+            ControlFlow::<()>::Continue(())
+        })?;
+        Ok(changed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use numelace_core::{Digit, EdgeKind, Position};
+
+    use super::*;
+
+    fn technique(a: Position, b: Position, kind: EdgeKind) -> EdgeRelation {
+        let constraint = EdgeConstraint::new(a, b, kind).unwrap();
+        EdgeRelation::new(EdgeConstraintSet::from_constraints([constraint]).unwrap())
+    }
+
+    // Edge markers do not follow grid symmetries, so these tests apply the
+    // technique directly instead of going through `crate::testing`.
+
+    #[test]
+    fn test_less_than_removes_extremes() {
+        let (a, b) = (Position::new(0, 0), Position::new(0, 1));
+        let technique = technique(a, b, EdgeKind::LessThan);
+        let mut grid = TechniqueGrid::new();
+
+        assert_eq!(technique.apply_pass(&mut grid).unwrap(), 1);
+        assert!(!grid.candidates_at(a).contains(Digit::D9));
+        assert!(!grid.candidates_at(b).contains(Digit::D1));
+        assert_eq!(technique.apply_pass(&mut grid).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_consecutive_uses_decided_digit() {
+        let (a, b) = (Position::new(0, 0), Position::new(1, 0));
+        let technique = technique(a, b, EdgeKind::Consecutive);
+        let mut grid = TechniqueGrid::new();
+        grid.place(a, Digit::D1);
+
+        let step = technique.find_step(&grid).unwrap();
+        assert!(step.is_some());
+        assert_eq!(technique.apply_pass(&mut grid).unwrap(), 1);
+        assert_eq!(grid.candidates_at(b), DigitSet::from_elem(Digit::D2));
+    }
+
+    #[test]
+    fn test_fails_on_unsatisfiable_edge() {
+        let (a, b) = (Position::new(0, 0), Position::new(0, 1));
+        let technique = technique(a, b, EdgeKind::GreaterThan);
+        let mut grid = TechniqueGrid::new();
+        grid.place(a, Digit::D1);
+        assert!(matches!(
+            technique.apply_pass(&mut grid),
+            Err(SolverError::Inconsistent(_))
+        ));
+    }
+}
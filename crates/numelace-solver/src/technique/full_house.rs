@@ -0,0 +1,264 @@
+use std::ops::ControlFlow;
+
+use numelace_core::{Digit, DigitPositions, DigitSet, House, Position};
+
+use crate::{
+    BoxedTechnique, BoxedTechniqueStep, SolverError, Technique, TechniqueApplication,
+    TechniqueGrid, TechniqueStepData, TechniqueTier, technique::TechniqueId,
+};
+
+const ID: TechniqueId = TechniqueId::FullHouse;
+const NAME: &str = "Full House";
+
+struct Condition {
+    house: House,
+    digit: Digit,
+    position: Position,
+}
+
+impl Condition {
+    fn build_step(
+        &self,
+        before_grid: &TechniqueGrid,
+        after_grid: &TechniqueGrid,
+    ) -> BoxedTechniqueStep {
+        TechniqueStepData::from_diff(
+            ID,
+            NAME,
+            before_grid.house_positions(self.house),
+            self.condition_digit_positions(),
+            before_grid,
+            after_grid,
+        )
+    }
+
+    fn condition_digit_positions(&self) -> Vec<(DigitPositions, DigitSet)> {
+        vec![(
+            DigitPositions::from_elem(self.position),
+            DigitSet::from_elem(self.digit),
+        )]
+    }
+}
+
+/// A technique that fills the last open cell of a house.
+///
+/// A "full house" occurs when eight cells of a row, column, or box are decided,
+/// so the remaining cell must hold the one digit the house is missing. This is
+/// the simplest case of both the naked single and the hidden single, and is
+/// tried before them so that grading and hints can name it.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct FullHouse {}
+
+impl FullHouse {
+    /// Creates a new `FullHouse` technique.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {}
+    }
+
+    /// Builds a full house step for `pos` when every other cell of one of its
+    /// houses is in `decided`, whatever the candidates of `pos`.
+    ///
+    /// The solver only sees candidates, so it cannot tell a placed digit from a
+    /// cell that has one candidate left. Hint systems that know which cells
+    /// hold digits use this to name a full house that the candidates alone
+    /// would show as a naked single. Like
+    /// [`NakedSingle::build_step`](super::NakedSingle::build_step), the step
+    /// also removes the digit from the peers of `pos`.
+    #[must_use]
+    pub fn build_step(
+        grid: &TechniqueGrid,
+        decided: DigitPositions,
+        pos: Position,
+    ) -> Option<BoxedTechniqueStep> {
+        let [row, col, box_] = [
+            House::Row { row: pos.row() },
+            House::Column { col: pos.col() },
+            House::Box {
+                index: pos.box_index(grid.regions()),
+            },
+        ];
+        let condition = [row, col, box_].into_iter().find_map(|house| {
+            let mut others = grid.house_positions(house);
+            others.remove(pos);
+            if !others.is_subset(decided) {
+                return None;
+            }
+            let digit = missing_digit(grid, others)?;
+            grid.candidates_at(pos)
+                .contains(digit)
+                .then_some(Condition {
+                    house,
+                    digit,
+                    position: pos,
+                })
+        })?;
+        let mut affected_pos = grid.house_peers(pos) & grid.digit_positions(condition.digit);
+        affected_pos.remove(pos);
+        let application = vec![
+            TechniqueApplication::CandidateElimination {
+                positions: affected_pos,
+                digits: DigitSet::from_elem(condition.digit),
+            },
+            TechniqueApplication::Placement {
+                position: pos,
+                digit: condition.digit,
+            },
+        ];
+        Some(TechniqueStepData::new_boxed(
+            ID,
+            NAME,
+            grid.house_positions(condition.house),
+            condition.condition_digit_positions(),
+            application,
+        ))
+    }
+
+    #[inline]
+    fn apply_with_control_flow<T, F>(grid: &mut TechniqueGrid, mut on_condition: F) -> Option<T>
+    where
+        F: for<'a> FnMut(&'a mut TechniqueGrid, &'a Condition) -> ControlFlow<T>,
+    {
+        for house in House::ALL {
+            let positions = grid.house_positions(house);
+            let open = positions & !grid.univalue_positions();
+            let Some(pos) = open.as_single() else {
+                continue;
+            };
+            let Some(digit) = missing_digit(grid, positions & !open) else {
+                continue;
+            };
+            if grid.candidates_at(pos).contains(digit)
+                && grid.place(pos, digit)
+                && let ControlFlow::Break(value) = on_condition(
+                    grid,
+                    &Condition {
+                        house,
+                        digit,
+                        position: pos,
+                    },
+                )
+            {
+                return Some(value);
+            }
+        }
+        None
+    }
+}
+
+/// Returns the digit missing from eight decided cells, if their digits differ.
+fn missing_digit(grid: &TechniqueGrid, decided: DigitPositions) -> Option<Digit> {
+    let digits: DigitSet = decided
+        .into_iter()
+        .filter_map(|pos| grid.candidates_at(pos).as_single())
+        .collect();
+    (!digits).as_single()
+}
+
+impl Technique for FullHouse {
+    fn id(&self) -> &'static str {
+        ID.as_str()
+    }
+
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    fn tier(&self) -> TechniqueTier {
+        TechniqueTier::Fundamental
+    }
+
+    fn clone_box(&self) -> BoxedTechnique {
+        Box::new(*self)
+    }
+
+    fn find_step(&self, grid: &TechniqueGrid) -> Result<Option<BoxedTechniqueStep>, SolverError> {
+        let mut after_grid = grid.clone();
+        let step = Self::apply_with_control_flow(&mut after_grid, |after_grid, condition| {
+            ControlFlow::Break(condition.build_step(grid, after_grid))
+        });
+        Ok(step)
+    }
+
+    fn apply_step(&self, grid: &mut TechniqueGrid) -> Result<bool, SolverError> {
+        let changed = Self::apply_with_control_flow(grid, |_, _| ControlFlow::Break(())).is_some();
+        Ok(changed)
+    }
+
+    fn apply_pass(&self, grid: &mut TechniqueGrid) -> Result<usize, SolverError> {
+        let mut changed = 0;
+        Self::apply_with_control_flow(grid, |_, _| {
+            changed += 1;
+            ControlFlow::<()>::Continue(())
+        });
+        Ok(changed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use numelace_core::{CandidateGrid, Digit, Position};
+
+    use super::*;
+    use crate::testing;
+
+    const TECHNIQUE: FullHouse = FullHouse::new();
+
+    /// Decides every cell of `positions` but the last one with digits from 1 up.
+    fn decide_all_but_last(grid: &mut CandidateGrid, positions: [Position; 9]) {
+        for (pos, digit) in positions[..8].iter().zip(Digit::ALL) {
+            grid.place(*pos, digit);
+        }
+    }
+
+    #[test]
+    fn test_full_house_in_row() {
+        let mut grid = CandidateGrid::new();
+        decide_all_but_last(&mut grid, Position::ROWS[2]);
+
+        testing::test_technique_apply_pass(grid, &TECHNIQUE, |t| {
+            t.assert_placed(Position::new(2, 8), Digit::D9);
+        });
+    }
+
+    #[test]
+    fn test_full_house_in_box() {
+        let mut grid = CandidateGrid::new();
+        decide_all_but_last(&mut grid, Position::BOXES[4]);
+
+        testing::test_technique_apply_pass(grid, &TECHNIQUE, |t| {
+            t.assert_placed(Position::new(5, 5), Digit::D9);
+        });
+    }
+
+    #[test]
+    fn test_no_change_with_two_open_cells() {
+        let mut grid = CandidateGrid::new();
+        for (pos, digit) in Position::COLS[0][..7].iter().zip(Digit::ALL) {
+            grid.place(*pos, digit);
+        }
+        testing::test_technique_apply_pass_no_changes(grid, &TECHNIQUE);
+    }
+
+    #[test]
+    fn test_build_step_uses_decided_cells() {
+        let mut grid = CandidateGrid::new();
+        for (pos, digit) in Position::ROWS[0].iter().zip(Digit::ALL) {
+            grid.place(*pos, digit);
+        }
+        let grid = TechniqueGrid::from(grid);
+        let mut decided = DigitPositions::ROW_POSITIONS[0];
+        decided.remove(Position::new(0, 4));
+
+        let step = FullHouse::build_step(&grid, decided, Position::new(0, 4)).unwrap();
+        assert_eq!(step.technique_id(), Some(TechniqueId::FullHouse));
+        assert!(
+            step.application()
+                .contains(&TechniqueApplication::Placement {
+                    position: Position::new(0, 4),
+                    digit: Digit::D5,
+                })
+        );
+        assert!(FullHouse::build_step(&grid, decided, Position::new(1, 4)).is_none());
+    }
+}
@@ -4,10 +4,10 @@ use numelace_core::{ConsistencyError, DigitPositions, DigitSet, House};
 
 use crate::{
     BoxedTechnique, BoxedTechniqueStep, SolverError, Technique, TechniqueGrid, TechniqueStepData,
-    TechniqueTier,
+    TechniqueTier, technique::TechniqueId,
 };
 
-const ID: &str = "hidden_pair";
+const ID: TechniqueId = TechniqueId::HiddenPair;
 const NAME: &str = "Hidden Pair";
 
 /// A technique that removes candidates using a hidden pair within a house.
@@ -29,9 +29,10 @@ impl Condition {
         before_grid: &TechniqueGrid,
         after_grid: &TechniqueGrid,
     ) -> BoxedTechniqueStep {
-        let condition_positions = self.house.positions();
+        let condition_positions = before_grid.house_positions(self.house);
         let condition_digit_positions = vec![(self.positions, self.digits)];
         TechniqueStepData::from_diff(
+            ID,
             NAME,
             condition_positions,
             condition_digit_positions,
@@ -56,7 +57,7 @@ impl HiddenPair {
         F: for<'a> FnMut(&'a mut TechniqueGrid, &'a Condition) -> ControlFlow<T>,
     {
         for house in House::ALL {
-            let house_positions = house.positions();
+            let house_positions = grid.house_positions(house);
             for (d1, remaining_digits) in DigitSet::FULL.pivots_with_following().take(8) {
                 let d1_positions = grid.digit_positions(d1) & house_positions;
                 if d1_positions.len() != 2 {
@@ -97,7 +98,7 @@ impl HiddenPair {
 
 impl Technique for HiddenPair {
     fn id(&self) -> &'static str {
-        ID
+        ID.as_str()
     }
 
     fn name(&self) -> &'static str {
@@ -4,10 +4,10 @@ use numelace_core::{Digit, DigitPositions, DigitSet, House, Position};
 
 use crate::{
     BoxedTechnique, BoxedTechniqueStep, SolverError, Technique, TechniqueGrid, TechniqueStepData,
-    TechniqueTier,
+    TechniqueTier, technique::TechniqueId,
 };
 
-const ID: &str = "hidden_single";
+const ID: TechniqueId = TechniqueId::HiddenSingle;
 const NAME: &str = "Hidden Single";
 
 struct Condition {
@@ -22,12 +22,13 @@ impl Condition {
         before_grid: &TechniqueGrid,
         after_grid: &TechniqueGrid,
     ) -> BoxedTechniqueStep {
-        let condition_positions = self.house.positions();
+        let condition_positions = before_grid.house_positions(self.house);
         let condition_digit_positions = vec![(
             DigitPositions::from_elem(self.position),
             DigitSet::from_elem(self.digit),
         )];
         TechniqueStepData::from_diff(
+            ID,
             NAME,
             condition_positions,
             condition_digit_positions,
@@ -62,9 +63,10 @@ impl HiddenSingle {
         for digit in Digit::ALL {
             let undecided_digit_positions = grid.digit_positions(digit) & !univalue_positions;
             for house in House::ALL {
-                let positions_in_house = undecided_digit_positions.positions_in_house(house);
+                let positions_in_house =
+                    undecided_digit_positions.positions_in_house(grid.regions(), house);
                 if let Some(x) = positions_in_house.as_single() {
-                    let pos = house.position_from_cell_index(x);
+                    let pos = house.position_from_cell_index(grid.regions(), x);
                     if grid.place(pos, digit)
                         && let ControlFlow::Break(value) = on_condition(
                             grid,
@@ -86,7 +88,7 @@ impl HiddenSingle {
 
 impl Technique for HiddenSingle {
     fn id(&self) -> &'static str {
-        ID
+        ID.as_str()
     }
 
     fn name(&self) -> &'static str {
@@ -179,7 +181,7 @@ mod tests {
         // Box 4 is the center box (rows 3-5, columns 3-5)
         // Remove D9 from all cells in box 4 except the center cell (4, 4)
         for pos in Position::BOXES[4] {
-            if pos.box_cell_index() != 4 {
+            if pos.box_cell_index(grid.regions()) != 4 {
                 grid.remove_candidate(pos, Digit::D9);
             }
         }
@@ -4,10 +4,10 @@ use numelace_core::{ConsistencyError, DigitPositions, DigitSet, House};
 
 use crate::{
     BoxedTechnique, BoxedTechniqueStep, SolverError, Technique, TechniqueGrid, TechniqueStepData,
-    TechniqueTier,
+    TechniqueTier, technique::TechniqueId,
 };
 
-const ID: &str = "hidden_triple";
+const ID: TechniqueId = TechniqueId::HiddenTriple;
 const NAME: &str = "Hidden Triple";
 
 /// A technique that removes candidates using a hidden triple within a house.
@@ -30,9 +30,10 @@ impl Condition {
         before_grid: &TechniqueGrid,
         after_grid: &TechniqueGrid,
     ) -> BoxedTechniqueStep {
-        let condition_positions = self.house.positions();
+        let condition_positions = before_grid.house_positions(self.house);
         let condition_digit_positions = vec![(self.positions, self.digits)];
         TechniqueStepData::from_diff(
+            ID,
             NAME,
             condition_positions,
             condition_digit_positions,
@@ -57,7 +58,7 @@ impl HiddenTriple {
         F: for<'a> FnMut(&'a mut TechniqueGrid, &'a Condition) -> ControlFlow<T>,
     {
         for house in House::ALL {
-            let house_positions = house.positions();
+            let house_positions = grid.house_positions(house);
             for (d1, remaining_digits1) in DigitSet::FULL.pivots_with_following().take(7) {
                 let d1_positions = grid.digit_positions(d1) & house_positions;
                 if d1_positions.is_empty() || d1_positions.len() > 3 {
@@ -131,7 +132,7 @@ impl HiddenTriple {
 
 impl Technique for HiddenTriple {
     fn id(&self) -> &'static str {
-        ID
+        ID.as_str()
     }
 
     fn name(&self) -> &'static str {
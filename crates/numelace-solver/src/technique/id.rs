@@ -0,0 +1,238 @@
+use std::str::FromStr;
+
+use super::{
+    Aic, FullHouse, HiddenPair, HiddenQuad, HiddenSingle, HiddenTriple, Jellyfish,
+    LockedCandidates, NakedPair, NakedQuad, NakedSingle, NakedTriple, RemotePair, Skyscraper,
+    Swordfish, TwoStringKite, UniqueRectangle, WWing, WxyzWing, XChain, XWing, XyChain, XyzWing,
+    YWing,
+};
+use crate::BoxedTechnique;
+
+/// Stable identifier of a built-in technique.
+///
+/// The string form returned by [`TechniqueId::as_str`] matches
+/// [`Technique::id`](crate::Technique::id) and is safe to persist.
+///
+/// # Examples
+///
+/// ```
+/// use numelace_solver::technique::{self, TechniqueId};
+///
+/// let id: TechniqueId = "x_wing".parse()?;
+/// assert_eq!(id, TechniqueId::XWing);
+/// assert_eq!(technique::by_id(id).unwrap().id(), "x_wing");
+/// # Ok::<(), numelace_solver::technique::UnknownTechniqueIdError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, derive_more::Display)]
+#[display("{}", self.as_str())]
+pub enum TechniqueId {
+    /// [`FullHouse`].
+    FullHouse,
+    /// [`NakedSingle`].
+    NakedSingle,
+    /// [`HiddenSingle`].
+    HiddenSingle,
+    /// [`LockedCandidates`].
+    LockedCandidates,
+    /// [`NakedPair`].
+    NakedPair,
+    /// [`HiddenPair`].
+    HiddenPair,
+    /// [`NakedTriple`].
+    NakedTriple,
+    /// [`HiddenTriple`].
+    HiddenTriple,
+    /// [`NakedQuad`].
+    NakedQuad,
+    /// [`HiddenQuad`].
+    HiddenQuad,
+    /// [`XWing`].
+    XWing,
+    /// [`Skyscraper`].
+    Skyscraper,
+    /// [`TwoStringKite`].
+    TwoStringKite,
+    /// [`YWing`].
+    YWing,
+    /// [`UniqueRectangle`].
+    UniqueRectangle,
+    /// [`Swordfish`].
+    Swordfish,
+    /// [`Jellyfish`].
+    Jellyfish,
+    /// [`RemotePair`].
+    RemotePair,
+    /// [`WWing`].
+    WWing,
+    /// [`XChain`].
+    XChain,
+    /// [`XyChain`].
+    XyChain,
+    /// [`XyzWing`].
+    XyzWing,
+    /// [`WxyzWing`].
+    WxyzWing,
+    /// [`Aic`].
+    Aic,
+    /// [`CageCombination`](super::CageCombination).
+    CageCombination,
+    /// [`EdgeRelation`](super::EdgeRelation).
+    EdgeRelation,
+}
+
+impl TechniqueId {
+    /// All technique IDs, ordered from easiest to hardest and followed by the
+    /// variant techniques that need puzzle data.
+    pub const ALL: [Self; 26] = [
+        Self::FullHouse,
+        Self::NakedSingle,
+        Self::HiddenSingle,
+        Self::LockedCandidates,
+        Self::NakedPair,
+        Self::HiddenPair,
+        Self::NakedTriple,
+        Self::HiddenTriple,
+        Self::NakedQuad,
+        Self::HiddenQuad,
+        Self::XWing,
+        Self::Skyscraper,
+        Self::TwoStringKite,
+        Self::YWing,
+        Self::UniqueRectangle,
+        Self::Swordfish,
+        Self::Jellyfish,
+        Self::RemotePair,
+        Self::WWing,
+        Self::XChain,
+        Self::XyChain,
+        Self::XyzWing,
+        Self::WxyzWing,
+        Self::Aic,
+        Self::CageCombination,
+        Self::EdgeRelation,
+    ];
+
+    /// Returns `true` for techniques that are only valid for puzzles with a
+    /// unique solution.
+    ///
+    /// These techniques rule out patterns that would allow a second solution.
+    /// On a grid with several solutions they can remove correct candidates, so
+    /// [`all_techniques`](super::all_techniques) leaves them out.
+    #[must_use]
+    pub const fn assumes_unique_solution(self) -> bool {
+        matches!(self, Self::UniqueRectangle)
+    }
+
+    /// Returns the stable string form of the ID.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::FullHouse => "full_house",
+            Self::NakedSingle => "naked_single",
+            Self::HiddenSingle => "hidden_single",
+            Self::LockedCandidates => "locked_candidates",
+            Self::NakedPair => "naked_pair",
+            Self::HiddenPair => "hidden_pair",
+            Self::NakedTriple => "naked_triple",
+            Self::HiddenTriple => "hidden_triple",
+            Self::NakedQuad => "naked_quad",
+            Self::HiddenQuad => "hidden_quad",
+            Self::XWing => "x_wing",
+            Self::Skyscraper => "skyscraper",
+            Self::TwoStringKite => "two_string_kite",
+            Self::YWing => "y_wing",
+            Self::UniqueRectangle => "unique_rectangle",
+            Self::Swordfish => "swordfish",
+            Self::Jellyfish => "jellyfish",
+            Self::RemotePair => "remote_pair",
+            Self::WWing => "w_wing",
+            Self::XChain => "x_chain",
+            Self::XyChain => "xy_chain",
+            Self::XyzWing => "xyz_wing",
+            Self::WxyzWing => "wxyz_wing",
+            Self::Aic => "aic",
+            Self::CageCombination => "cage_combination",
+            Self::EdgeRelation => "edge_relation",
+        }
+    }
+}
+
+/// Error returned when parsing an unknown technique ID.
+#[derive(Debug, Clone, PartialEq, Eq, derive_more::Display, derive_more::Error)]
+#[display("unknown technique ID: {id}")]
+pub struct UnknownTechniqueIdError {
+    #[error(not(source))]
+    id: String,
+}
+
+impl FromStr for TechniqueId {
+    type Err = UnknownTechniqueIdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|id| id.as_str() == s)
+            .ok_or_else(|| UnknownTechniqueIdError { id: s.to_owned() })
+    }
+}
+
+/// Creates the technique with the given ID.
+///
+/// Returns `None` for variant techniques such as
+/// [`CageCombination`](super::CageCombination) that need puzzle data to construct.
+#[must_use]
+pub fn by_id(id: TechniqueId) -> Option<BoxedTechnique> {
+    let technique: BoxedTechnique = match id {
+        TechniqueId::FullHouse => Box::new(FullHouse::new()),
+        TechniqueId::NakedSingle => Box::new(NakedSingle::new()),
+        TechniqueId::HiddenSingle => Box::new(HiddenSingle::new()),
+        TechniqueId::LockedCandidates => Box::new(LockedCandidates::new()),
+        TechniqueId::NakedPair => Box::new(NakedPair::new()),
+        TechniqueId::HiddenPair => Box::new(HiddenPair::new()),
+        TechniqueId::NakedTriple => Box::new(NakedTriple::new()),
+        TechniqueId::HiddenTriple => Box::new(HiddenTriple::new()),
+        TechniqueId::NakedQuad => Box::new(NakedQuad::new()),
+        TechniqueId::HiddenQuad => Box::new(HiddenQuad::new()),
+        TechniqueId::XWing => Box::new(XWing::new()),
+        TechniqueId::Skyscraper => Box::new(Skyscraper::new()),
+        TechniqueId::TwoStringKite => Box::new(TwoStringKite::new()),
+        TechniqueId::YWing => Box::new(YWing::new()),
+        TechniqueId::UniqueRectangle => Box::new(UniqueRectangle::new()),
+        TechniqueId::Swordfish => Box::new(Swordfish::new()),
+        TechniqueId::Jellyfish => Box::new(Jellyfish::new()),
+        TechniqueId::RemotePair => Box::new(RemotePair::new()),
+        TechniqueId::WWing => Box::new(WWing::new()),
+        TechniqueId::XChain => Box::new(XChain::new()),
+        TechniqueId::XyChain => Box::new(XyChain::new()),
+        TechniqueId::XyzWing => Box::new(XyzWing::new()),
+        TechniqueId::WxyzWing => Box::new(WxyzWing::new()),
+        TechniqueId::Aic => Box::new(Aic::new()),
+        TechniqueId::CageCombination | TechniqueId::EdgeRelation => return None,
+    };
+    Some(technique)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ids_round_trip_through_strings() {
+        for id in TechniqueId::ALL {
+            assert_eq!(id.as_str().parse::<TechniqueId>(), Ok(id));
+            assert_eq!(id.to_string(), id.as_str());
+        }
+        assert!("fish".parse::<TechniqueId>().is_err());
+    }
+
+    #[test]
+    fn test_by_id_constructs_matching_technique() {
+        for id in TechniqueId::ALL {
+            if let Some(technique) = by_id(id) {
+                assert_eq!(technique.id(), id.as_str());
+                assert_eq!(technique.technique_id(), Some(id));
+            }
+        }
+        assert!(by_id(TechniqueId::CageCombination).is_none());
+    }
+}
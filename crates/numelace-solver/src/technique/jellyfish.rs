@@ -7,9 +7,10 @@ use crate::{
     BoxedTechnique, BoxedTechniqueStep, SolverError, Technique, TechniqueGrid, TechniqueStepData,
     TechniqueTier,
     axis::{AxisOps, ColumnAxis, RowAxis},
+    technique::TechniqueId,
 };
 
-const ID: &str = "jellyfish";
+const ID: TechniqueId = TechniqueId::Jellyfish;
 const NAME: &str = "Jellyfish";
 
 /// A technique that removes candidates using a Jellyfish pattern.
@@ -33,18 +34,27 @@ impl Condition {
         before_grid: &TechniqueGrid,
         after_grid: &TechniqueGrid,
     ) -> BoxedTechniqueStep {
-        let condition_positions = self.base_houses.into_iter().map(House::positions).sum();
-        let crosses = self.cover_houses.into_iter().map(House::positions).sum();
+        let condition_positions = self
+            .base_houses
+            .into_iter()
+            .map(|house| before_grid.house_positions(house))
+            .sum();
+        let crosses = self
+            .cover_houses
+            .into_iter()
+            .map(|house| before_grid.house_positions(house))
+            .sum();
         let cross_positions = self
             .base_houses
             .into_iter()
-            .map(|house| house.positions() & crosses)
+            .map(|house| before_grid.house_positions(house) & crosses)
             .sum::<DigitPositions>();
         let condition_digit_positions = vec![(
             cross_positions & before_grid.digit_positions(self.digit),
             DigitSet::from_elem(self.digit),
         )];
         TechniqueStepData::from_diff(
+            ID,
             NAME,
             condition_positions,
             condition_digit_positions,
@@ -169,7 +179,7 @@ impl Jellyfish {
 
 impl Technique for Jellyfish {
     fn id(&self) -> &'static str {
-        ID
+        ID.as_str()
     }
 
     fn name(&self) -> &'static str {
@@ -1,16 +1,25 @@
 use std::ops::ControlFlow;
 
-use numelace_core::{Digit, DigitSet, House, Position};
+use numelace_core::{Digit, DigitSet, House};
 
 use crate::{
     BoxedTechnique, BoxedTechniqueStep, SolverError, Technique, TechniqueGrid, TechniqueStepData,
-    TechniqueTier,
+    TechniqueTier, technique::TechniqueId,
 };
 
-const ID: &str = "locked_candidates";
+const ID: TechniqueId = TechniqueId::LockedCandidates;
 const NAME: &str = "Locked Candidates";
 const NAME_POINTING: &str = "Locked Candidates (Pointing)";
 const NAME_CLAIMING: &str = "Locked Candidates (Claiming)";
+const KEY_POINTING: &str = "locked_candidates_pointing";
+const KEY_CLAIMING: &str = "locked_candidates_claiming";
+
+/// Resolves the key of a pointing or claiming step to its key and display name.
+pub(super) fn resolve_locked_candidates_key(key: &str) -> Option<(&'static str, &'static str)> {
+    [(KEY_POINTING, NAME_POINTING), (KEY_CLAIMING, NAME_CLAIMING)]
+        .into_iter()
+        .find(|(variant_key, _)| *variant_key == key)
+}
 
 /// A technique that removes candidates using locked candidates (pointing/claiming).
 ///
@@ -40,16 +49,21 @@ impl Condition {
         before_grid: &TechniqueGrid,
         after_grid: &TechniqueGrid,
     ) -> BoxedTechniqueStep {
-        let condition_positions = self.box_.positions() | self.line.positions();
+        let box_positions = before_grid.house_positions(self.box_);
+        let line_positions = before_grid.house_positions(self.line);
+        let condition_positions = box_positions | line_positions;
         let condition_digit_positions = vec![(
-            self.box_.positions() & self.line.positions(),
+            box_positions & line_positions,
             DigitSet::from_elem(self.digit),
         )];
-        TechniqueStepData::from_diff(
-            match self.kind {
-                LockedCandidatesKind::Pointing => NAME_POINTING,
-                LockedCandidatesKind::Claiming => NAME_CLAIMING,
-            },
+        let (key, name) = match self.kind {
+            LockedCandidatesKind::Pointing => (KEY_POINTING, NAME_POINTING),
+            LockedCandidatesKind::Claiming => (KEY_CLAIMING, NAME_CLAIMING),
+        };
+        TechniqueStepData::from_diff_with_key(
+            ID,
+            key,
+            name,
             condition_positions,
             condition_digit_positions,
             before_grid,
@@ -73,32 +87,27 @@ impl LockedCandidates {
         let univalue_positions = grid.univalue_positions();
         for box_index in 0..9 {
             let box_ = House::Box { index: box_index };
-            let origin = Position::box_origin(box_index);
-            let lines = [
-                House::Row { row: origin.row() },
-                House::Row {
-                    row: origin.row() + 1,
-                },
-                House::Row {
-                    row: origin.row() + 2,
-                },
-                House::Column { col: origin.col() },
-                House::Column {
-                    col: origin.col() + 1,
-                },
-                House::Column {
-                    col: origin.col() + 2,
-                },
-            ];
-
-            for line in lines {
-                let intersection = box_.positions() & line.positions();
+            let box_positions = grid.house_positions(box_);
+            // Every row and column the box touches; irregular boxes can touch
+            // more than three of each.
+            let rows = box_positions
+                .rows_set()
+                .into_iter()
+                .map(|row| House::Row { row });
+            let cols = box_positions
+                .cols_set()
+                .into_iter()
+                .map(|col| House::Column { col });
+
+            for line in rows.chain(cols) {
+                let line_positions = grid.house_positions(line);
+                let intersection = box_positions & line_positions;
                 if (intersection & !univalue_positions).is_empty() {
                     continue;
                 }
 
-                let rest_in_box = box_.positions() & !intersection;
-                let rest_in_line = line.positions() & !intersection;
+                let rest_in_box = box_positions & !intersection;
+                let rest_in_line = line_positions & !intersection;
                 for digit in Digit::ALL {
                     let undecided_positions = grid.digit_positions(digit) & !univalue_positions;
                     if (undecided_positions & intersection).is_empty() {
@@ -146,7 +155,7 @@ impl LockedCandidates {
 
 impl Technique for LockedCandidates {
     fn id(&self) -> &'static str {
-        ID
+        ID.as_str()
     }
 
     fn name(&self) -> &'static str {
@@ -186,7 +195,7 @@ impl Technique for LockedCandidates {
 
 #[cfg(test)]
 mod tests {
-    use numelace_core::{CandidateGrid, Digit, Position};
+    use numelace_core::{CandidateGrid, Digit, Position, RegionLayout};
 
     use super::*;
     use crate::testing;
@@ -211,6 +220,39 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_pointing_follows_jigsaw_boxes() {
+        // The first region takes (0, 3) from the second one and gives up (2, 2).
+        let regions: RegionLayout = "\
+            111122333\
+            111222333\
+            112222333\
+            444555666\
+            444555666\
+            444555666\
+            777888999\
+            777888999\
+            777888999"
+            .parse()
+            .unwrap();
+        let mut grid = TechniqueGrid::new().with_regions(regions);
+        for pos in regions.region_positions(0) {
+            if pos.row() != 0 {
+                grid.remove_candidate(pos, Digit::D5);
+            }
+        }
+
+        testing::test_technique_apply_pass(grid, &TECHNIQUE, |t| {
+            t
+                // D5 removed from row 0 outside the region, which starts at column 4.
+                .assert_no_change(Position::new(0, 3))
+                .assert_removed_includes(Position::new(0, 4), [Digit::D5])
+                .assert_removed_includes(Position::new(0, 8), [Digit::D5])
+                // (2, 2) is no longer in the region, so it keeps D5.
+                .assert_no_change(Position::new(2, 2));
+        });
+    }
+
     #[test]
     fn test_claiming_eliminates_from_box() {
         // Row 0: limit D7 candidates to box 0 cells in row 0.
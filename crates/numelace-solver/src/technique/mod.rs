@@ -7,17 +7,23 @@
 //! [`TechniqueGrid`]: crate::TechniqueGrid
 
 pub use self::{
-    hidden_pair::*, hidden_quad::*, hidden_single::*, hidden_triple::*, jellyfish::*,
-    locked_candidates::*, naked_pair::*, naked_quad::*, naked_single::*, naked_triple::*,
-    remote_pair::*, skyscraper::*, swordfish::*, two_string_kite::*, wxyz_wing::*, x_chain::*,
-    x_wing::*, xy_chain::*, xyz_wing::*, y_wing::*,
+    aic::*, cage_combination::*, edge_relation::*, full_house::*, hidden_pair::*, hidden_quad::*,
+    hidden_single::*, hidden_triple::*, id::*, jellyfish::*, locked_candidates::*, naked_pair::*,
+    naked_quad::*, naked_single::*, naked_triple::*, remote_pair::*, skyscraper::*, swordfish::*,
+    two_string_kite::*, unique_rectangle::*, w_wing::*, wxyz_wing::*, x_chain::*, x_wing::*,
+    xy_chain::*, xyz_wing::*, y_wing::*,
 };
-use crate::{BoxedTechnique, TechniqueTier};
+use crate::{BoxedTechnique, Technique as _, TechniqueTier};
 
+mod aic;
+mod cage_combination;
+mod edge_relation;
+mod full_house;
 mod hidden_pair;
 mod hidden_quad;
 mod hidden_single;
 mod hidden_triple;
+mod id;
 mod jellyfish;
 mod locked_candidates;
 mod naked_pair;
@@ -29,6 +35,8 @@ mod skyscraper;
 mod swordfish;
 pub(crate) mod traits;
 mod two_string_kite;
+mod unique_rectangle;
+mod w_wing;
 mod wxyz_wing;
 mod x_chain;
 mod x_wing;
@@ -36,37 +44,49 @@ mod xy_chain;
 mod xyz_wing;
 mod y_wing;
 
-/// Finds a technique by its stable ID.
+/// Finds a technique by the string form of its stable ID.
+///
+/// See [`by_id`] for the typed lookup.
 #[must_use]
 pub fn find_technique_by_id(id: &str) -> Option<BoxedTechnique> {
-    all_techniques().into_iter().find(|tech| tech.id() == id)
+    by_id(id.parse().ok()?)
+}
+
+/// Resolves a step key to the ID, key and display name of the technique that produces it.
+///
+/// Besides the technique IDs, this accepts the keys of technique variants such
+/// as locked candidates pointing. Returns `None` for keys of custom techniques.
+pub(crate) fn resolve_step_key(key: &str) -> Option<(TechniqueId, &'static str, &'static str)> {
+    if let Some((key, name)) = resolve_locked_candidates_key(key) {
+        return Some((TechniqueId::LockedCandidates, key, name));
+    }
+    let id: TechniqueId = key.parse().ok()?;
+    let name = match id {
+        TechniqueId::CageCombination => CageCombination::default().name(),
+        _ => by_id(id)?.name(),
+    };
+    Some((id, id.as_str(), name))
 }
 
 /// Returns all available techniques, ordered from easiest to hardest.
+///
+/// Techniques that [assume a unique solution](TechniqueId::assumes_unique_solution)
+/// are left out, so the solver still gets stuck on grids with several
+/// solutions. See [`all_techniques_assuming_unique_solution`] to include them.
 #[must_use]
 pub fn all_techniques() -> Vec<BoxedTechnique> {
-    vec![
-        Box::new(NakedSingle::new()),
-        Box::new(HiddenSingle::new()),
-        Box::new(LockedCandidates::new()),
-        Box::new(NakedPair::new()),
-        Box::new(HiddenPair::new()),
-        Box::new(NakedTriple::new()),
-        Box::new(HiddenTriple::new()),
-        Box::new(NakedQuad::new()),
-        Box::new(HiddenQuad::new()),
-        Box::new(XWing::new()),
-        Box::new(Skyscraper::new()),
-        Box::new(TwoStringKite::new()),
-        Box::new(YWing::new()),
-        Box::new(Swordfish::new()),
-        Box::new(Jellyfish::new()),
-        Box::new(RemotePair::new()),
-        Box::new(XChain::new()),
-        Box::new(XyChain::new()),
-        Box::new(XyzWing::new()),
-        Box::new(WxyzWing::new()),
-    ]
+    TechniqueId::ALL
+        .into_iter()
+        .filter(|id| !id.assumes_unique_solution())
+        .filter_map(by_id)
+        .collect()
+}
+
+/// Returns all available techniques, including those that assume a unique
+/// solution, ordered from easiest to hardest.
+#[must_use]
+pub fn all_techniques_assuming_unique_solution() -> Vec<BoxedTechnique> {
+    TechniqueId::ALL.into_iter().filter_map(by_id).collect()
 }
 
 /// Returns the fundamental techniques at or below the fundamental tier.
@@ -124,4 +144,16 @@ mod tests {
             assert!(tech1.tier() <= tech2.tier());
         }
     }
+
+    #[test]
+    fn test_step_keys_resolve_to_their_technique() {
+        for technique in all_techniques() {
+            let (id, key, name) = resolve_step_key(technique.id()).unwrap();
+            assert_eq!(Some(id), technique.technique_id());
+            assert_eq!(key, technique.id());
+            assert_eq!(name, technique.name());
+        }
+        assert!(resolve_step_key("locked_candidates_claiming").is_some());
+        assert!(resolve_step_key("custom").is_none());
+    }
 }
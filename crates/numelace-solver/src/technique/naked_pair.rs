@@ -4,10 +4,10 @@ use numelace_core::{ConsistencyError, DigitPositions, DigitSet, House};
 
 use crate::{
     BoxedTechnique, BoxedTechniqueStep, SolverError, Technique, TechniqueGrid, TechniqueStepData,
-    TechniqueTier,
+    TechniqueTier, technique::TechniqueId,
 };
 
-const ID: &str = "naked_pair";
+const ID: TechniqueId = TechniqueId::NakedPair;
 const NAME: &str = "Naked Pair";
 
 /// A technique that removes candidates using a naked pair within a house.
@@ -30,9 +30,10 @@ impl Condition {
         before_grid: &TechniqueGrid,
         after_grid: &TechniqueGrid,
     ) -> BoxedTechniqueStep {
-        let condition_positions = self.house.positions();
+        let condition_positions = before_grid.house_positions(self.house);
         let condition_digit_positions = vec![(self.positions, self.digits)];
         TechniqueStepData::from_diff(
+            ID,
             NAME,
             condition_positions,
             condition_digit_positions,
@@ -62,7 +63,7 @@ impl NakedPair {
         }
 
         for house in House::ALL {
-            let pair_in_house = bivalue_positions & house.positions();
+            let pair_in_house = bivalue_positions & grid.house_positions(house);
             if pair_in_house.len() < 2 {
                 continue;
             }
@@ -84,7 +85,7 @@ impl NakedPair {
                     continue;
                 };
 
-                let mut eliminate_positions = house.positions();
+                let mut eliminate_positions = grid.house_positions(house);
                 eliminate_positions.remove(pos1);
                 eliminate_positions.remove(pos2);
                 if grid.remove_candidate_set_with_mask(eliminate_positions, pair_digits)
@@ -107,7 +108,7 @@ impl NakedPair {
 
 impl Technique for NakedPair {
     fn id(&self) -> &'static str {
-        ID
+        ID.as_str()
     }
 
     fn name(&self) -> &'static str {
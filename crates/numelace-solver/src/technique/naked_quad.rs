@@ -4,10 +4,10 @@ use numelace_core::{ConsistencyError, DigitPositions, DigitSet, House};
 
 use crate::{
     BoxedTechnique, BoxedTechniqueStep, SolverError, Technique, TechniqueGrid, TechniqueStepData,
-    TechniqueTier,
+    TechniqueTier, technique::TechniqueId,
 };
 
-const ID: &str = "naked_quad";
+const ID: TechniqueId = TechniqueId::NakedQuad;
 const NAME: &str = "Naked Quad";
 
 /// A technique that removes candidates using a naked quad within a house.
@@ -30,9 +30,10 @@ impl Condition {
         before_grid: &TechniqueGrid,
         after_grid: &TechniqueGrid,
     ) -> BoxedTechniqueStep {
-        let condition_positions = self.house.positions();
+        let condition_positions = before_grid.house_positions(self.house);
         let condition_digit_positions = vec![(self.positions, self.digits)];
         TechniqueStepData::from_diff(
+            ID,
             NAME,
             condition_positions,
             condition_digit_positions,
@@ -62,7 +63,7 @@ impl NakedQuad {
             return Ok(None);
         }
         for house in House::ALL {
-            let quad_in_house = quadvalue_positions & house.positions();
+            let quad_in_house = quadvalue_positions & grid.house_positions(house);
             if quad_in_house.len() < 4 {
                 continue;
             }
@@ -109,7 +110,7 @@ impl NakedQuad {
                                 return Err(ConsistencyError::CandidateConstraintViolation.into());
                             }
 
-                            let mut eliminate_positions = house.positions();
+                            let mut eliminate_positions = grid.house_positions(house);
                             eliminate_positions.remove(pos1);
                             eliminate_positions.remove(pos2);
                             eliminate_positions.remove(pos3);
@@ -139,7 +140,7 @@ impl NakedQuad {
 
 impl Technique for NakedQuad {
     fn id(&self) -> &'static str {
-        ID
+        ID.as_str()
     }
 
     fn name(&self) -> &'static str {
@@ -4,10 +4,10 @@ use numelace_core::{Digit, DigitPositions, DigitSet, Position};
 
 use crate::{
     BoxedTechnique, BoxedTechniqueStep, SolverError, Technique, TechniqueApplication,
-    TechniqueGrid, TechniqueStepData, TechniqueTier,
+    TechniqueGrid, TechniqueStepData, TechniqueTier, technique::TechniqueId,
 };
 
-const ID: &str = "naked_single";
+const ID: TechniqueId = TechniqueId::NakedSingle;
 const NAME: &str = "Naked Single";
 
 /// A technique that finds cells with only one remaining candidate and propagates constraints.
@@ -40,6 +40,7 @@ impl Condition {
             DigitSet::from_elem(self.digit),
         )];
         TechniqueStepData::from_diff(
+            ID,
             NAME,
             condition_positions,
             condition_digit_positions,
@@ -63,11 +64,7 @@ impl NakedSingle {
     #[must_use]
     pub fn build_step(grid: &TechniqueGrid, pos: Position) -> Option<BoxedTechniqueStep> {
         let digit = grid.candidates_at(pos).as_single()?;
-        let mut affected_pos = (DigitPositions::ROW_POSITIONS[pos.row()]
-            | DigitPositions::COL_POSITIONS[pos.col()]
-            | DigitPositions::BOX_POSITIONS[pos.box_index()])
-            & grid.digit_positions(digit);
-        affected_pos.remove(pos);
+        let affected_pos = grid.house_peers(pos) & grid.digit_positions(digit);
         let mut application = vec![TechniqueApplication::CandidateElimination {
             positions: affected_pos,
             digits: DigitSet::from_elem(digit),
@@ -77,6 +74,7 @@ impl NakedSingle {
             digit,
         });
         Some(TechniqueStepData::new_boxed(
+            ID,
             NAME,
             DigitPositions::from_elem(pos),
             vec![(DigitPositions::from_elem(pos), DigitSet::from_elem(digit))],
@@ -93,10 +91,7 @@ impl NakedSingle {
         for digit in Digit::ALL {
             let univalue_positions = grid.digit_positions(digit) & univalue_positions;
             for pos in univalue_positions {
-                let mut affected_pos = DigitPositions::ROW_POSITIONS[pos.row()]
-                    | DigitPositions::COL_POSITIONS[pos.col()]
-                    | DigitPositions::BOX_POSITIONS[pos.box_index()];
-                affected_pos.remove(pos);
+                let affected_pos = grid.house_peers(pos);
                 grid.insert_univalue_propagated(pos);
                 if grid.remove_candidate_with_mask(affected_pos, digit)
                     && let ControlFlow::Break(value) = on_condition(
@@ -117,7 +112,7 @@ impl NakedSingle {
 
 impl Technique for NakedSingle {
     fn id(&self) -> &'static str {
-        ID
+        ID.as_str()
     }
 
     fn name(&self) -> &'static str {
@@ -4,10 +4,10 @@ use numelace_core::{ConsistencyError, DigitPositions, DigitSet, House};
 
 use crate::{
     BoxedTechnique, BoxedTechniqueStep, SolverError, Technique, TechniqueGrid, TechniqueStepData,
-    TechniqueTier,
+    TechniqueTier, technique::TechniqueId,
 };
 
-const ID: &str = "naked_triple";
+const ID: TechniqueId = TechniqueId::NakedTriple;
 const NAME: &str = "Naked Triple";
 
 /// A technique that removes candidates using a naked triple within a house.
@@ -30,9 +30,10 @@ impl Condition {
         before_grid: &TechniqueGrid,
         after_grid: &TechniqueGrid,
     ) -> BoxedTechniqueStep {
-        let condition_positions = self.house.positions();
+        let condition_positions = before_grid.house_positions(self.house);
         let condition_digit_positions = vec![(self.positions, self.digits)];
         TechniqueStepData::from_diff(
+            ID,
             NAME,
             condition_positions,
             condition_digit_positions,
@@ -63,7 +64,7 @@ impl NakedTriple {
         }
 
         for house in House::ALL {
-            let triple_in_house = trivalue_positions & house.positions();
+            let triple_in_house = trivalue_positions & grid.house_positions(house);
             if triple_in_house.len() < 3 {
                 continue;
             }
@@ -104,7 +105,7 @@ impl NakedTriple {
                             return Err(ConsistencyError::CandidateConstraintViolation.into());
                         }
 
-                        let mut eliminate_positions = house.positions();
+                        let mut eliminate_positions = grid.house_positions(house);
                         eliminate_positions.remove(pos1);
                         eliminate_positions.remove(pos2);
                         eliminate_positions.remove(pos3);
@@ -130,7 +131,7 @@ impl NakedTriple {
 
 impl Technique for NakedTriple {
     fn id(&self) -> &'static str {
-        ID
+        ID.as_str()
     }
 
     fn name(&self) -> &'static str {
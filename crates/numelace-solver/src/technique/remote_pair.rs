@@ -5,10 +5,10 @@ use tinyvec::{ArrayVec, array_vec};
 
 use crate::{
     BoxedTechnique, BoxedTechniqueStep, SolverError, Technique, TechniqueGrid, TechniqueStepData,
-    TechniqueTier,
+    TechniqueTier, technique::TechniqueId,
 };
 
-const ID: &str = "remote_pair";
+const ID: TechniqueId = TechniqueId::RemotePair;
 const NAME: &str = "Remote Pair";
 
 /// A technique that removes candidates using a Remote Pair pattern.
@@ -49,9 +49,22 @@ impl Condition<'_> {
             {
                 condition_positions |= DigitPositions::COL_POSITIONS[pos1.col()];
             } else {
-                debug_assert_eq!(pos1.box_index(), pos2.box_index());
-                debug_assert_eq!(digit_positions.positions_in_box(pos1.box_index()).len(), 2);
-                condition_positions |= DigitPositions::BOX_POSITIONS[pos1.box_index()];
+                debug_assert_eq!(
+                    pos1.box_index(before_grid.regions()),
+                    pos2.box_index(before_grid.regions())
+                );
+                debug_assert_eq!(
+                    digit_positions
+                        .positions_in_box(
+                            before_grid.regions(),
+                            pos1.box_index(before_grid.regions())
+                        )
+                        .len(),
+                    2
+                );
+                condition_positions |= before_grid.house_positions(House::Box {
+                    index: pos1.box_index(before_grid.regions()),
+                });
             }
         }
         let condition_digit_positions = vec![(
@@ -59,6 +72,7 @@ impl Condition<'_> {
             DigitSet::from_iter([self.digit1, self.digit2]),
         )];
         TechniqueStepData::from_diff(
+            ID,
             NAME,
             condition_positions,
             condition_digit_positions,
@@ -141,7 +155,7 @@ impl RemotePair {
                 let mut link_peers =
                     PositionIndexedArray::from_array([array_vec!([Position; 3]); 81]);
                 for house in House::ALL {
-                    let house_positions = digit_positions12 & house.positions();
+                    let house_positions = digit_positions12 & grid.house_positions(house);
                     let Some([pos1, pos2]) = house_positions.as_double() else {
                         continue;
                     };
@@ -165,7 +179,7 @@ impl RemotePair {
                             stack.push(next_item);
                             if stack.len() % 2 == 0 {
                                 let elimination =
-                                    chain_start.house_peers() & chain_end.house_peers();
+                                    grid.house_peers(chain_start) & grid.house_peers(chain_end);
                                 if grid.remove_candidate_set_with_mask(elimination, digits12) {
                                     let condition = &Condition {
                                         digit1,
@@ -192,7 +206,7 @@ impl RemotePair {
 
 impl Technique for RemotePair {
     fn id(&self) -> &'static str {
-        ID
+        ID.as_str()
     }
 
     fn name(&self) -> &'static str {
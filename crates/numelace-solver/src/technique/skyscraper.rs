@@ -4,12 +4,13 @@ use numelace_core::{Digit, DigitPositions, DigitSet, House, Position};
 use tinyvec::array_vec;
 
 use crate::{
-    BoxedTechnique, BoxedTechniqueStep, SolverError, Technique, TechniqueGrid, TechniqueStepData,
-    TechniqueTier,
+    BoxedTechnique, BoxedTechniqueStep, ChainNode, SolverError, Technique, TechniqueGrid,
+    TechniqueLink, TechniqueStepData, TechniqueTier,
     axis::{AxisOps, ColumnAxis, RowAxis},
+    technique::TechniqueId,
 };
 
-const ID: &str = "skyscraper";
+const ID: TechniqueId = TechniqueId::Skyscraper;
 const NAME: &str = "Skyscraper";
 
 /// A technique that removes candidates using a Skyscraper pattern.
@@ -34,16 +35,30 @@ impl Condition {
         before_grid: &TechniqueGrid,
         after_grid: &TechniqueGrid,
     ) -> BoxedTechniqueStep {
-        let condition_positions = self.lines.into_iter().map(House::positions).sum();
+        let condition_positions = self
+            .lines
+            .into_iter()
+            .map(|house| before_grid.house_positions(house))
+            .sum();
         let condition_digit_positions = vec![(
             DigitPositions::from_iter(self.base_positions)
                 | DigitPositions::from_iter(self.roof_positions),
             DigitSet::from_elem(self.digit),
         )];
-        TechniqueStepData::from_diff(
+        let [base1, base2] = self.base_positions;
+        let [roof1, roof2] = self.roof_positions;
+        let links = TechniqueLink::along_chain(&[
+            ChainNode::new(roof1, self.digit),
+            ChainNode::new(base1, self.digit),
+            ChainNode::new(base2, self.digit),
+            ChainNode::new(roof2, self.digit),
+        ]);
+        TechniqueStepData::from_diff_with_links(
+            ID,
             NAME,
             condition_positions,
             condition_digit_positions,
+            links,
             before_grid,
             after_grid,
         )
@@ -75,23 +90,14 @@ impl Skyscraper {
             let Some([pos_a, pos_b]) = positions.as_double() else {
                 continue;
             };
-            let cross_a = A::cross_index(pos_a);
-            let cross_b = A::cross_index(pos_b);
-            if cross_a / 3 == cross_b / 3 {
+            if pos_a.box_index(grid.regions()) == pos_b.box_index(grid.regions()) {
                 continue;
             }
-            lines_with_two.push((line, cross_a, cross_b));
+            lines_with_two.push((line, A::cross_index(pos_a), A::cross_index(pos_b)));
         }
         let mut lines_with_two = lines_with_two.iter();
         while let Some(&(line1, line1_cross_a, line1_cross_b)) = lines_with_two.next() {
             for &(line2, line2_cross_a, line2_cross_b) in lines_with_two.as_slice() {
-                if line1 / 3 == line2 / 3 {
-                    continue;
-                }
-                if line1_cross_a / 3 != line2_cross_a / 3 || line1_cross_b / 3 != line2_cross_b / 3
-                {
-                    continue;
-                }
                 let (base_cross, line1_roof_cross, line2_roof_cross) =
                     if line1_cross_a == line2_cross_a && line1_cross_b != line2_cross_b {
                         (line1_cross_a, line1_cross_b, line2_cross_b)
@@ -100,26 +106,22 @@ impl Skyscraper {
                     } else {
                         continue;
                     };
-                let line1_roof_box = A::make_pos(line1, line1_roof_cross).box_index();
-                let line2_roof_box = A::make_pos(line2, line2_roof_cross).box_index();
-                let eliminations = (A::CROSS_POSITIONS[line2_roof_cross]
-                    & DigitPositions::BOX_POSITIONS[line1_roof_box])
-                    | (A::CROSS_POSITIONS[line1_roof_cross]
-                        & DigitPositions::BOX_POSITIONS[line2_roof_box]);
+                let base1 = A::make_pos(line1, base_cross);
+                let base2 = A::make_pos(line2, base_cross);
+                if base1.box_index(grid.regions()) == base2.box_index(grid.regions()) {
+                    continue;
+                }
+                let roof1 = A::make_pos(line1, line1_roof_cross);
+                let roof2 = A::make_pos(line2, line2_roof_cross);
+                let eliminations = grid.house_peers(roof1) & grid.house_peers(roof2);
                 if grid.remove_candidate_with_mask(eliminations, digit)
                     && let ControlFlow::Break(step) = on_condition(
                         grid,
                         &Condition {
                             digit,
                             lines: [A::LINE_HOUSES[line1], A::LINE_HOUSES[line2]],
-                            base_positions: [
-                                A::make_pos(line1, base_cross),
-                                A::make_pos(line2, base_cross),
-                            ],
-                            roof_positions: [
-                                A::make_pos(line1, line1_roof_cross),
-                                A::make_pos(line2, line2_roof_cross),
-                            ],
+                            base_positions: [base1, base2],
+                            roof_positions: [roof1, roof2],
                         },
                     )
                 {
@@ -155,7 +157,7 @@ impl Skyscraper {
 
 impl Technique for Skyscraper {
     fn id(&self) -> &'static str {
-        ID
+        ID.as_str()
     }
 
     fn name(&self) -> &'static str {
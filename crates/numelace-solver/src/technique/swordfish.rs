@@ -7,9 +7,10 @@ use crate::{
     BoxedTechnique, BoxedTechniqueStep, SolverError, Technique, TechniqueGrid, TechniqueStepData,
     TechniqueTier,
     axis::{AxisOps, ColumnAxis, RowAxis},
+    technique::TechniqueId,
 };
 
-const ID: &str = "swordfish";
+const ID: TechniqueId = TechniqueId::Swordfish;
 const NAME: &str = "Swordfish";
 
 /// A technique that removes candidates using a Swordfish pattern.
@@ -33,18 +34,27 @@ impl Condition {
         before_grid: &TechniqueGrid,
         after_grid: &TechniqueGrid,
     ) -> BoxedTechniqueStep {
-        let condition_positions = self.base_houses.into_iter().map(House::positions).sum();
-        let crosses = self.cover_houses.into_iter().map(House::positions).sum();
+        let condition_positions = self
+            .base_houses
+            .into_iter()
+            .map(|house| before_grid.house_positions(house))
+            .sum();
+        let crosses = self
+            .cover_houses
+            .into_iter()
+            .map(|house| before_grid.house_positions(house))
+            .sum();
         let cross_positions = self
             .base_houses
             .into_iter()
-            .map(|house| house.positions() & crosses)
+            .map(|house| before_grid.house_positions(house) & crosses)
             .sum::<DigitPositions>();
         let condition_digit_positions = vec![(
             cross_positions & before_grid.digit_positions(self.digit),
             DigitSet::from_elem(self.digit),
         )];
         TechniqueStepData::from_diff(
+            ID,
             NAME,
             condition_positions,
             condition_digit_positions,
@@ -98,14 +108,16 @@ impl Swordfish {
                     let Some([cross1, cross2, cross3]) = crosses123.as_triple() else {
                         return Err(ConsistencyError::CandidateConstraintViolation.into());
                     };
-                    // If all base houses are in one band and all cover houses in one stack,
-                    // each box would require a placement while the box allows only one.
-                    // This is a candidate constraint violation.
-                    if line1 / 3 == line2 / 3
-                        && line2 / 3 == line3 / 3
-                        && cross1 / 3 == cross2 / 3
-                        && cross2 / 3 == cross3 / 3
-                    {
+                    // If all intersections of the base and cover houses are in one box,
+                    // each base house would require a placement while the box allows only
+                    // one. This is a candidate constraint violation.
+                    let intersections = (A::LINE_POSITIONS[line1]
+                        | A::LINE_POSITIONS[line2]
+                        | A::LINE_POSITIONS[line3])
+                        & (A::CROSS_POSITIONS[cross1]
+                            | A::CROSS_POSITIONS[cross2]
+                            | A::CROSS_POSITIONS[cross3]);
+                    if intersections.boxes_set(grid.regions()).len() == 1 {
                         return Err(ConsistencyError::CandidateConstraintViolation.into());
                     }
                     let eliminations = (A::CROSS_POSITIONS[cross1]
@@ -168,7 +180,7 @@ impl Swordfish {
 
 impl Technique for Swordfish {
     fn id(&self) -> &'static str {
-        ID
+        ID.as_str()
     }
 
     fn name(&self) -> &'static str {
@@ -1,6 +1,8 @@
 use std::fmt::Debug;
 
-use crate::{BoxedTechniqueStep, SolverError, TechniqueApplication, TechniqueGrid};
+use crate::{
+    BoxedTechniqueStep, SolverError, TechniqueApplication, TechniqueGrid, technique::TechniqueId,
+};
 
 /// A trait representing a Sudoku solving technique.
 ///
@@ -12,6 +14,13 @@ pub trait Technique: Debug + Send + Sync {
     /// Returns the name of the technique.
     fn name(&self) -> &'static str;
 
+    /// Returns the ID of the built-in technique, or `None` for custom techniques.
+    ///
+    /// The default implementation parses [`Technique::id`].
+    fn technique_id(&self) -> Option<TechniqueId> {
+        self.id().parse().ok()
+    }
+
     /// Returns the technique tier used for difficulty ordering.
     fn tier(&self) -> TechniqueTier;
 
@@ -101,6 +110,21 @@ pub enum TechniqueTier {
     Expert,
 }
 
+impl TechniqueTier {
+    /// Returns the stable string form of the tier.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Fundamental => "fundamental",
+            Self::Basic => "basic",
+            Self::Intermediate => "intermediate",
+            Self::UpperIntermediate => "upper_intermediate",
+            Self::Advanced => "advanced",
+            Self::Expert => "expert",
+        }
+    }
+}
+
 /// A boxed technique.
 pub type BoxedTechnique = Box<dyn Technique>;
 
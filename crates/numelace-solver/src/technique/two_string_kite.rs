@@ -6,19 +6,20 @@ use numelace_core::{
 use tinyvec::ArrayVec;
 
 use crate::{
-    BoxedTechnique, BoxedTechniqueStep, SolverError, Technique, TechniqueGrid, TechniqueStepData,
-    TechniqueTier,
+    BoxedTechnique, BoxedTechniqueStep, ChainNode, SolverError, Technique, TechniqueGrid,
+    TechniqueLink, TechniqueStepData, TechniqueTier,
     axis::{AxisOps, ColumnAxis, RowAxis},
+    technique::TechniqueId,
 };
 
-const ID: &str = "two_string_kite";
+const ID: TechniqueId = TechniqueId::TwoStringKite;
 const NAME: &str = "2-String Kite";
 
 /// A technique that removes candidates using a 2-String Kite pattern.
 ///
 /// A "2-String Kite" occurs when a digit appears exactly twice in a row and
 /// exactly twice in a column, and one candidate from each lies in the same
-/// 3x3 box. The digit can then be eliminated from the cell at the intersection
+/// box. The digit can then be eliminated from the cell at the intersection
 /// of the other row candidate and the other column candidate.
 #[derive(Debug, Default, Clone, Copy)]
 pub struct TwoStringKite {}
@@ -37,15 +38,27 @@ impl Condition {
         before_grid: &TechniqueGrid,
         after_grid: &TechniqueGrid,
     ) -> BoxedTechniqueStep {
-        let condition_positions = self.row.positions() | self.col.positions();
+        let condition_positions =
+            before_grid.house_positions(self.row) | before_grid.house_positions(self.col);
         let condition_digit_positions = vec![(
             DigitPositions::from_iter(self.positions),
             DigitSet::from_elem(self.digit),
         )];
-        TechniqueStepData::from_diff(
+        // The row's far end, its end in the box, the column's end in the box
+        // and the column's far end.
+        let [row_box, row_other, col_box, col_other] = self.positions;
+        let links = TechniqueLink::along_chain(&[
+            ChainNode::new(row_other, self.digit),
+            ChainNode::new(row_box, self.digit),
+            ChainNode::new(col_box, self.digit),
+            ChainNode::new(col_other, self.digit),
+        ]);
+        TechniqueStepData::from_diff_with_links(
+            ID,
             NAME,
             condition_positions,
             condition_digit_positions,
+            links,
             before_grid,
             after_grid,
         )
@@ -53,7 +66,8 @@ impl Condition {
 }
 
 type LinePair = (u8, (u8, u8));
-type LinePairs = ArrayVec<[LinePair; 3]>;
+// An irregular box can touch every line, not just three.
+type LinePairs = ArrayVec<[LinePair; 9]>;
 
 impl TwoStringKite {
     /// Creates a new `TwoStringKite` instance.
@@ -75,14 +89,16 @@ impl TwoStringKite {
             let Some([pos_a, pos_b]) = positions.as_double() else {
                 continue;
             };
-            let cross_a = A::cross_index(pos_a);
-            let cross_b = A::cross_index(pos_b);
-            if cross_a / 3 == cross_b / 3 {
+            let box_a = pos_a.box_index(grid.regions());
+            let box_b = pos_b.box_index(grid.regions());
+            if box_a == box_b {
                 continue;
             }
+            let cross_a = A::cross_index(pos_a);
+            let cross_b = A::cross_index(pos_b);
             found_lines += 1;
-            line_pairs[pos_a.box_index()].push((line, (cross_a, cross_b)));
-            line_pairs[pos_b.box_index()].push((line, (cross_b, cross_a)));
+            line_pairs[box_a].push((line, (cross_a, cross_b)));
+            line_pairs[box_b].push((line, (cross_b, cross_a)));
         }
         (line_pairs, found_lines)
     }
@@ -107,6 +123,11 @@ impl TwoStringKite {
                         if row == col_box_row && row_box_col == col {
                             continue;
                         }
+                        // With irregular boxes the far ends can share a row
+                        // or column, and the target would be one of them.
+                        if row == col_other_row || row_other_col == col {
+                            continue;
+                        }
                         let eliminate_pos = Position::new(col_other_row, row_other_col);
                         if grid.remove_candidate(eliminate_pos, digit)
                             && let ControlFlow::Break(step) = on_condition(
@@ -136,7 +157,7 @@ impl TwoStringKite {
 
 impl Technique for TwoStringKite {
     fn id(&self) -> &'static str {
-        ID
+        ID.as_str()
     }
 
     fn name(&self) -> &'static str {
@@ -0,0 +1,258 @@
+use std::ops::ControlFlow;
+
+use numelace_core::{DigitPositions, DigitSet, Position, RegionLayout};
+
+use crate::{
+    BoxedTechnique, BoxedTechniqueStep, SolverError, Technique, TechniqueGrid, TechniqueStepData,
+    TechniqueTier, technique::TechniqueId,
+};
+
+const ID: TechniqueId = TechniqueId::UniqueRectangle;
+const NAME: &str = "Unique Rectangle";
+
+/// A technique that removes candidates which would leave a deadly pattern.
+///
+/// Four cells at the corners of a rectangle spanning exactly two boxes, all
+/// holding the same two candidates A/B, form a "deadly pattern": A and B could
+/// be swapped between the corners, so the puzzle would have two solutions.
+/// Assuming the puzzle has a unique solution, the pattern cannot occur:
+///
+/// - Type 1: three corners hold only A/B. The fourth corner cannot be A or B.
+/// - Type 2: two corners hold only A/B, and the other two hold A/B plus the same
+///   extra digit C. One of them must be C, so cells seeing both cannot be C.
+///
+/// The eliminations are only valid for puzzles with a unique solution, so
+/// [`all_techniques`](super::all_techniques) leaves this technique out; see
+/// [`TechniqueId::assumes_unique_solution`].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct UniqueRectangle {}
+
+struct Condition {
+    pair: DigitSet,
+    floor: DigitPositions,
+    roof: DigitPositions,
+    /// Candidates of the roof cells for type 2, `None` for type 1.
+    roof_digits: Option<DigitSet>,
+}
+
+impl Condition {
+    fn build_step(
+        &self,
+        before_grid: &TechniqueGrid,
+        after_grid: &TechniqueGrid,
+    ) -> BoxedTechniqueStep {
+        let mut condition_digit_positions = vec![(self.floor, self.pair)];
+        if let Some(roof_digits) = self.roof_digits {
+            condition_digit_positions.push((self.roof, roof_digits));
+        }
+        TechniqueStepData::from_diff(
+            ID,
+            NAME,
+            self.floor | self.roof,
+            condition_digit_positions,
+            before_grid,
+            after_grid,
+        )
+    }
+}
+
+impl UniqueRectangle {
+    /// Creates a new `UniqueRectangle` technique.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {}
+    }
+
+    #[inline]
+    fn apply_with_control_flow<T, F>(grid: &mut TechniqueGrid, mut on_condition: F) -> Option<T>
+    where
+        F: for<'a> FnMut(&'a mut TechniqueGrid, &'a Condition) -> ControlFlow<T>,
+    {
+        for corners in rectangles(*grid.regions()) {
+            let common = corners
+                .into_iter()
+                .fold(DigitSet::FULL, |acc, pos| acc & grid.candidates_at(pos));
+            for (i, d1) in common.iter().enumerate() {
+                for d2 in common.iter().skip(i + 1) {
+                    let pair = DigitSet::from_iter([d1, d2]);
+                    if let Some(condition) = eliminate(grid, corners, pair)
+                        && let ControlFlow::Break(value) = on_condition(grid, &condition)
+                    {
+                        return Some(value);
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Returns the corners of every rectangle that spans exactly two boxes of
+/// `regions`, two corners in each.
+fn rectangles(regions: RegionLayout) -> impl Iterator<Item = [Position; 4]> {
+    (0..9).flat_map(move |r1| {
+        (r1 + 1..9).flat_map(move |r2| {
+            (0..9).flat_map(move |c1| {
+                (c1 + 1..9)
+                    .map(move |c2| {
+                        [
+                            Position::new(r1, c1),
+                            Position::new(r1, c2),
+                            Position::new(r2, c1),
+                            Position::new(r2, c2),
+                        ]
+                    })
+                    .filter(move |corners| {
+                        corners.iter().all(|&a| {
+                            let index = regions.region_index(a);
+                            corners
+                                .iter()
+                                .filter(|&&b| regions.region_index(b) == index)
+                                .count()
+                                == 2
+                        })
+                    })
+            })
+        })
+    })
+}
+
+/// Applies the type 1 or type 2 elimination for the rectangle `corners` on
+/// the digits in `pair`, returning the condition if any candidate was removed.
+fn eliminate(
+    grid: &mut TechniqueGrid,
+    corners: [Position; 4],
+    pair: DigitSet,
+) -> Option<Condition> {
+    let (floor, roof): (Vec<_>, Vec<_>) = corners
+        .into_iter()
+        .partition(|&pos| grid.candidates_at(pos) == pair);
+    match (floor.as_slice(), roof.as_slice()) {
+        (_, &[target]) => grid.remove_candidate_set(target, pair).then(|| Condition {
+            pair,
+            floor: floor.iter().copied().collect(),
+            roof: DigitPositions::from_elem(target),
+            roof_digits: None,
+        }),
+        (_, &[roof1, roof2]) => {
+            let roof_digits = grid.candidates_at(roof1);
+            let extra = (roof_digits & !pair).as_single()?;
+            if grid.candidates_at(roof2) != roof_digits {
+                return None;
+            }
+            let eliminations =
+                grid.house_peers(roof1) & grid.house_peers(roof2) & grid.digit_positions(extra);
+            grid.remove_candidate_with_mask(eliminations, extra)
+                .then(|| Condition {
+                    pair,
+                    floor: floor.iter().copied().collect(),
+                    roof: DigitPositions::from_iter([roof1, roof2]),
+                    roof_digits: Some(roof_digits),
+                })
+        }
+        _ => None,
+    }
+}
+
+impl Technique for UniqueRectangle {
+    fn id(&self) -> &'static str {
+        ID.as_str()
+    }
+
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    fn tier(&self) -> TechniqueTier {
+        TechniqueTier::UpperIntermediate
+    }
+
+    fn clone_box(&self) -> BoxedTechnique {
+        Box::new(*self)
+    }
+
+    fn find_step(&self, grid: &TechniqueGrid) -> Result<Option<BoxedTechniqueStep>, SolverError> {
+        let mut after_grid = grid.clone();
+        let step = Self::apply_with_control_flow(&mut after_grid, |after_grid, condition| {
+            ControlFlow::Break(condition.build_step(grid, after_grid))
+        });
+        Ok(step)
+    }
+
+    fn apply_step(&self, grid: &mut TechniqueGrid) -> Result<bool, SolverError> {
+        let changed = Self::apply_with_control_flow(grid, |_, _| ControlFlow::Break(())).is_some();
+        Ok(changed)
+    }
+
+    fn apply_pass(&self, grid: &mut TechniqueGrid) -> Result<usize, SolverError> {
+        let mut changed = 0;
+        Self::apply_with_control_flow(grid, |_, _| {
+            changed += 1;
+            ControlFlow::<()>::Continue(())
+        });
+        Ok(changed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use numelace_core::{CandidateGrid, Digit, Position};
+
+    use super::*;
+    use crate::testing;
+
+    const TECHNIQUE: UniqueRectangle = UniqueRectangle::new();
+
+    fn keep_only(grid: &mut CandidateGrid, pos: Position, digits: &[Digit]) {
+        for digit in Digit::ALL {
+            if !digits.contains(&digit) {
+                grid.remove_candidate(pos, digit);
+            }
+        }
+    }
+
+    #[test]
+    fn test_type_1_eliminates_pair_from_fourth_corner() {
+        let mut grid = CandidateGrid::new();
+        for pos in [
+            Position::new(0, 0),
+            Position::new(0, 1),
+            Position::new(3, 0),
+        ] {
+            keep_only(&mut grid, pos, &[Digit::D1, Digit::D2]);
+        }
+
+        testing::test_technique_apply_pass(grid, &TECHNIQUE, |t| {
+            t.assert_removed_exact(Position::new(3, 1), [Digit::D1, Digit::D2]);
+        });
+    }
+
+    #[test]
+    fn test_type_2_eliminates_extra_digit_seen_by_roof() {
+        let mut grid = CandidateGrid::new();
+        for pos in [Position::new(0, 0), Position::new(3, 0)] {
+            keep_only(&mut grid, pos, &[Digit::D1, Digit::D2]);
+        }
+        for pos in [Position::new(0, 1), Position::new(3, 1)] {
+            keep_only(&mut grid, pos, &[Digit::D1, Digit::D2, Digit::D3]);
+        }
+
+        testing::test_technique_apply_pass(grid, &TECHNIQUE, |t| {
+            t.assert_removed_exact(Position::new(6, 1), [Digit::D3])
+                .assert_no_change(Position::new(6, 0));
+        });
+    }
+
+    #[test]
+    fn test_no_change_for_rectangle_in_four_boxes() {
+        let mut grid = CandidateGrid::new();
+        for pos in [
+            Position::new(0, 0),
+            Position::new(0, 4),
+            Position::new(4, 0),
+        ] {
+            keep_only(&mut grid, pos, &[Digit::D1, Digit::D2]);
+        }
+        testing::test_technique_apply_pass_no_changes(grid, &TECHNIQUE);
+    }
+}
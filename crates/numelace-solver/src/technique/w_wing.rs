@@ -0,0 +1,252 @@
+use std::ops::ControlFlow;
+
+use numelace_core::{Digit, DigitPositions, DigitSet, House, Position};
+
+use crate::{
+    BoxedTechnique, BoxedTechniqueStep, ChainNode, SolverError, Technique, TechniqueGrid,
+    TechniqueLink, TechniqueStepData, TechniqueTier, technique::TechniqueId,
+};
+
+const ID: TechniqueId = TechniqueId::WWing;
+const NAME: &str = "W-Wing";
+
+/// A technique that removes candidates using a W-Wing pattern.
+///
+/// A "W-Wing" occurs when two cells that do not see each other both hold
+/// exactly the candidates A/B, and a strong link on A (a house where A fits
+/// in only two cells) has one end seeing each of them. One end of the link
+/// must be A, so one of the two cells is not A and must be B. B can then be
+/// eliminated from any cell that sees both cells.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct WWing {}
+
+struct Condition {
+    wing1: Position,
+    wing2: Position,
+    link: DigitPositions,
+    link_digit: Digit,
+    pair: DigitSet,
+}
+
+impl Condition {
+    fn build_step(
+        &self,
+        before_grid: &TechniqueGrid,
+        after_grid: &TechniqueGrid,
+    ) -> BoxedTechniqueStep {
+        let wings = DigitPositions::from_iter([self.wing1, self.wing2]);
+        let condition_digit_positions = vec![
+            (wings, self.pair),
+            (self.link, DigitSet::from_elem(self.link_digit)),
+        ];
+        let digit = (self.pair & !DigitSet::from_elem(self.link_digit))
+            .as_single()
+            .unwrap();
+        let [mut end1, mut end2] = self.link.as_double().unwrap();
+        if !before_grid.house_peers(self.wing1).contains(end1) {
+            (end1, end2) = (end2, end1);
+        }
+        let links = TechniqueLink::along_chain(&[
+            ChainNode::new(self.wing1, digit),
+            ChainNode::new(self.wing1, self.link_digit),
+            ChainNode::new(end1, self.link_digit),
+            ChainNode::new(end2, self.link_digit),
+            ChainNode::new(self.wing2, self.link_digit),
+            ChainNode::new(self.wing2, digit),
+        ]);
+        TechniqueStepData::from_diff_with_links(
+            ID,
+            NAME,
+            wings | self.link,
+            condition_digit_positions,
+            links,
+            before_grid,
+            after_grid,
+        )
+    }
+}
+
+impl WWing {
+    /// Creates a new `WWing` technique.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {}
+    }
+
+    #[inline]
+    fn apply_with_control_flow<T, F>(grid: &mut TechniqueGrid, mut on_condition: F) -> Option<T>
+    where
+        F: for<'a> FnMut(&'a mut TechniqueGrid, &'a Condition) -> ControlFlow<T>,
+    {
+        let bivalue_positions = grid.classify_positions::<3>()[2];
+        for wing1 in bivalue_positions {
+            let pair = grid.candidates_at(wing1);
+            let Some([d1, d2]) = pair.as_double() else {
+                // Earlier eliminations may have changed the candidates at wing1.
+                continue;
+            };
+            let unseen = bivalue_positions & !grid.house_peers(wing1);
+            for wing2 in unseen.into_iter().filter(|&pos| pos > wing1) {
+                if grid.candidates_at(wing2) != pair {
+                    continue;
+                }
+                for (link_digit, digit) in [(d1, d2), (d2, d1)] {
+                    let eliminations = grid.house_peers(wing1)
+                        & grid.house_peers(wing2)
+                        & grid.digit_positions(digit);
+                    if eliminations.is_empty() {
+                        continue;
+                    }
+                    let Some(link) = find_link(grid, wing1, wing2, link_digit) else {
+                        continue;
+                    };
+                    if grid.remove_candidate_with_mask(eliminations, digit)
+                        && let ControlFlow::Break(value) = on_condition(
+                            grid,
+                            &Condition {
+                                wing1,
+                                wing2,
+                                link,
+                                link_digit,
+                                pair,
+                            },
+                        )
+                    {
+                        return Some(value);
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Finds a strong link on `digit` with one end seeing `wing1` and the other
+/// seeing `wing2`.
+fn find_link(
+    grid: &TechniqueGrid,
+    wing1: Position,
+    wing2: Position,
+    digit: Digit,
+) -> Option<DigitPositions> {
+    let both = DigitPositions::from_iter([wing1, wing2]);
+    House::ALL.into_iter().find_map(|house| {
+        let link = grid.house_positions(house) & grid.digit_positions(digit) & !both;
+        let [end1, end2] = link.as_double()?;
+        if (grid.house_positions(house) & grid.digit_positions(digit)).len() != 2 {
+            return None;
+        }
+        let sees = |end: Position, wing: Position| grid.house_peers(wing).contains(end);
+        ((sees(end1, wing1) && sees(end2, wing2)) || (sees(end1, wing2) && sees(end2, wing1)))
+            .then_some(link)
+    })
+}
+
+impl Technique for WWing {
+    fn id(&self) -> &'static str {
+        ID.as_str()
+    }
+
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    fn tier(&self) -> TechniqueTier {
+        TechniqueTier::Advanced
+    }
+
+    fn clone_box(&self) -> BoxedTechnique {
+        Box::new(*self)
+    }
+
+    fn find_step(&self, grid: &TechniqueGrid) -> Result<Option<BoxedTechniqueStep>, SolverError> {
+        let mut after_grid = grid.clone();
+        let step = Self::apply_with_control_flow(&mut after_grid, |after_grid, condition| {
+            ControlFlow::Break(condition.build_step(grid, after_grid))
+        });
+        Ok(step)
+    }
+
+    fn apply_step(&self, grid: &mut TechniqueGrid) -> Result<bool, SolverError> {
+        let changed = Self::apply_with_control_flow(grid, |_, _| ControlFlow::Break(())).is_some();
+        Ok(changed)
+    }
+
+    fn apply_pass(&self, grid: &mut TechniqueGrid) -> Result<usize, SolverError> {
+        let mut changed = 0;
+        Self::apply_with_control_flow(grid, |_, _| {
+            changed += 1;
+            ControlFlow::<()>::Continue(())
+        });
+        Ok(changed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use numelace_core::{CandidateGrid, Digit, Position};
+
+    use super::*;
+    use crate::{LinkKind, testing};
+
+    const TECHNIQUE: WWing = WWing::new();
+
+    /// Two {1,2} cells at r1c1 and r5c5, without any strong link on 1 or 2.
+    fn wings_grid() -> CandidateGrid {
+        let mut grid = CandidateGrid::new();
+        for pos in [Position::new(0, 0), Position::new(4, 4)] {
+            for digit in Digit::ALL {
+                if digit != Digit::D1 && digit != Digit::D2 {
+                    grid.remove_candidate(pos, digit);
+                }
+            }
+        }
+        grid
+    }
+
+    #[test]
+    fn test_eliminates_w_wing_candidates() {
+        // Link the wings by the 1s of row 3 at r3c2 and r3c5.
+        let mut grid = wings_grid();
+        for col in [0, 2, 3, 5, 6, 7, 8] {
+            grid.remove_candidate(Position::new(2, col), Digit::D1);
+        }
+        testing::test_technique_apply_pass(grid, &TECHNIQUE, |t| {
+            t.assert_removed_exact(Position::new(0, 4), [Digit::D2])
+                .assert_removed_exact(Position::new(4, 0), [Digit::D2]);
+        });
+    }
+
+    #[test]
+    fn test_step_links_wings_through_strong_link() {
+        let mut grid = wings_grid();
+        for col in [0, 2, 3, 5, 6, 7, 8] {
+            grid.remove_candidate(Position::new(2, col), Digit::D1);
+        }
+        let step = TECHNIQUE.find_step(&grid.into()).unwrap().unwrap();
+        let links = step.links();
+        let kinds: Vec<_> = links.iter().map(|link| link.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                LinkKind::Strong,
+                LinkKind::Weak,
+                LinkKind::Strong,
+                LinkKind::Weak,
+                LinkKind::Strong,
+            ]
+        );
+        assert_eq!(
+            links[2],
+            TechniqueLink::strong(
+                ChainNode::new(Position::new(2, 1), Digit::D1),
+                ChainNode::new(Position::new(2, 4), Digit::D1),
+            )
+        );
+    }
+
+    #[test]
+    fn test_no_change_without_strong_link() {
+        testing::test_technique_apply_pass_no_changes(wings_grid(), &TECHNIQUE);
+    }
+}
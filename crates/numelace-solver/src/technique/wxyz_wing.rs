@@ -1,12 +1,13 @@
 use std::ops::ControlFlow;
 
-use numelace_core::{Digit, DigitPositions, DigitSet, Position};
+use numelace_core::{Digit, DigitPositions, DigitSet, RegionLayout};
 
 use crate::{
     BoxedTechniqueStep, SolverError, Technique, TechniqueGrid, TechniqueStepData, TechniqueTier,
+    technique::TechniqueId,
 };
 
-const ID: &str = "wxyz_wing";
+const ID: TechniqueId = TechniqueId::WxyzWing;
 const NAME: &str = "WXYZ-Wing";
 
 /// A technique that removes candidates using a WXYZ-Wing pattern.
@@ -39,6 +40,7 @@ impl Condition {
             condition_digit_positions.push((DigitPositions::from_elem(pos), pos_digits));
         }
         TechniqueStepData::from_diff(
+            ID,
             NAME,
             condition_positions,
             condition_digit_positions,
@@ -48,10 +50,10 @@ impl Condition {
     }
 }
 
-fn is_in_single_house(digit_positions: DigitPositions) -> bool {
+fn is_in_single_house(regions: &RegionLayout, digit_positions: DigitPositions) -> bool {
     digit_positions.rows_set().len() <= 1
         || digit_positions.cols_set().len() <= 1
-        || digit_positions.boxes_set().len() <= 1
+        || digit_positions.boxes_set(regions).len() <= 1
 }
 
 impl WxyzWing {
@@ -90,13 +92,12 @@ impl WxyzWing {
                         continue;
                     }
                     let wxy = digits12 & !DigitSet::from_elem(z);
-                    if wxy
-                        .iter()
-                        .any(|d| !is_in_single_house(positions12 & grid.digit_positions(d)))
-                    {
+                    if wxy.iter().any(|d| {
+                        !is_in_single_house(grid.regions(), positions12 & grid.digit_positions(d))
+                    }) {
                         continue;
                     }
-                    let mut other_positions = (pos1.house_peers() | pos2.house_peers())
+                    let mut other_positions = (grid.house_peers(pos1) | grid.house_peers(pos2))
                         & candidate_positions
                         & !DigitPositions::from_iter([pos1, pos2]);
                     if other_positions.len() < 2 {
@@ -113,10 +114,12 @@ impl WxyzWing {
                             continue;
                         }
                         let wxy = digits123 & !DigitSet::from_elem(z);
-                        if wxy
-                            .iter()
-                            .any(|d| !is_in_single_house(positions123 & grid.digit_positions(d)))
-                        {
+                        if wxy.iter().any(|d| {
+                            !is_in_single_house(
+                                grid.regions(),
+                                positions123 & grid.digit_positions(d),
+                            )
+                        }) {
                             continue;
                         }
                         for pos4 in other_positions.as_set() {
@@ -127,7 +130,10 @@ impl WxyzWing {
                             }
                             let wxy = digits1234 & !DigitSet::from_elem(z);
                             if wxy.iter().any(|d| {
-                                !is_in_single_house(positions1234 & grid.digit_positions(d))
+                                !is_in_single_house(
+                                    grid.regions(),
+                                    positions1234 & grid.digit_positions(d),
+                                )
                             }) {
                                 continue;
                             }
@@ -138,7 +144,7 @@ impl WxyzWing {
                             }
                             let elimination = z_positions
                                 .iter()
-                                .map(Position::house_peers)
+                                .map(|pos| grid.house_peers(pos))
                                 .product::<DigitPositions>();
                             if grid.remove_candidate_with_mask(elimination, z)
                                 && let ControlFlow::Break(step) = on_condition(
@@ -164,7 +170,7 @@ impl WxyzWing {
 
 impl Technique for WxyzWing {
     fn id(&self) -> &'static str {
-        ID
+        ID.as_str()
     }
 
     fn name(&self) -> &'static str {
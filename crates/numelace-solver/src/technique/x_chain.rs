@@ -1,13 +1,16 @@
 use std::ops::ControlFlow;
 
-use numelace_core::{Digit, DigitPositions, DigitSet, House, Position, PositionIndexedArray};
+use numelace_core::{
+    Digit, DigitPositions, DigitSet, House, Position, PositionIndexedArray, RegionLayout,
+};
 use tinyvec::{ArrayVec, array_vec};
 
 use crate::{
-    BoxedTechniqueStep, SolverError, Technique, TechniqueGrid, TechniqueStepData, TechniqueTier,
+    BoxedTechniqueStep, ChainNode, SolverError, Technique, TechniqueGrid, TechniqueLink,
+    TechniqueStepData, TechniqueTier, technique::TechniqueId,
 };
 
-const ID: &str = "x_chain";
+const ID: TechniqueId = TechniqueId::XChain;
 const NAME: &str = "X-Chain";
 
 /// A technique that applies X-Chain and X-Cycle rules for a single digit.
@@ -54,19 +57,40 @@ impl Condition<'_> {
             {
                 condition_positions |= DigitPositions::COL_POSITIONS[pos1.col()];
             } else {
-                debug_assert_eq!(pos1.box_index(), pos2.box_index());
-                debug_assert_eq!(digit_positions.positions_in_box(pos1.box_index()).len(), 2);
-                condition_positions |= DigitPositions::BOX_POSITIONS[pos1.box_index()];
+                debug_assert_eq!(
+                    pos1.box_index(before_grid.regions()),
+                    pos2.box_index(before_grid.regions())
+                );
+                debug_assert_eq!(
+                    digit_positions
+                        .positions_in_box(
+                            before_grid.regions(),
+                            pos1.box_index(before_grid.regions())
+                        )
+                        .len(),
+                    2
+                );
+                condition_positions |= before_grid.house_positions(House::Box {
+                    index: pos1.box_index(before_grid.regions()),
+                });
             }
         }
         let condition_digit_positions = vec![(
             condition_digit_position_mask,
             DigitSet::from_elem(self.digit),
         )];
-        TechniqueStepData::from_diff(
+        let chain: Vec<_> = self
+            .stack
+            .iter()
+            .flat_map(|item| [item.strong_link_start, item.strong_link_end])
+            .map(|pos| ChainNode::new(pos, self.digit))
+            .collect();
+        TechniqueStepData::from_diff_with_links(
+            ID,
             NAME,
             condition_positions,
             condition_digit_positions,
+            TechniqueLink::along_chain(&chain),
             before_grid,
             after_grid,
         )
@@ -75,6 +99,7 @@ impl Condition<'_> {
 
 #[derive(Debug)]
 struct TraversalGraph {
+    regions: RegionLayout,
     strong_link_positions: DigitPositions,
     strong_link_peers: PositionIndexedArray<ArrayVec<[Position; 3]>>,
 }
@@ -121,7 +146,7 @@ impl TraversalStackItem {
     fn visit_next_strong_link_end(&mut self, graph: &TraversalGraph) -> bool {
         if let Some(strong_link_end) = self.remaining_strong_link_ends.pop_first() {
             self.strong_link_end = strong_link_end;
-            self.remaining_strong_link_starts = strong_link_end.house_peers()
+            self.remaining_strong_link_starts = graph.regions.house_peers(strong_link_end)
                 & graph.strong_link_positions
                 & !self.visited_strong_link_starts;
             return true;
@@ -170,14 +195,14 @@ impl XChain {
         // X-Chain endpoint elimination (discontinuous weak-weak X-Cycle).
         // This also captures weak-weak-equivalent eliminations in the same
         // common-peers form.
-        let mut elimination = chain_start.house_peers() & chain_end.house_peers();
+        let mut elimination = grid.house_peers(chain_start) & grid.house_peers(chain_end);
 
-        if chain_start.house_peers().contains(chain_end) {
+        if grid.house_peers(chain_start).contains(chain_end) {
             // X-Cycle (continuous): if endpoints are peers, the chain can be closed
             // by a weak link; add eliminations from each internal weak junction.
             for [item1, item2] in stack.array_windows() {
-                elimination |=
-                    item1.strong_link_end.house_peers() & item2.strong_link_start.house_peers();
+                elimination |= grid.house_peers(item1.strong_link_end)
+                    & grid.house_peers(item2.strong_link_start);
             }
         }
 
@@ -195,7 +220,7 @@ impl XChain {
             let mut strong_link_peers =
                 PositionIndexedArray::from_array([array_vec!([Position; 3]); 81]);
             for house in House::ALL {
-                let house_positions = digit_positions & house.positions();
+                let house_positions = digit_positions & grid.house_positions(house);
                 let Some([pos1, pos2]) = house_positions.as_double() else {
                     continue;
                 };
@@ -206,6 +231,7 @@ impl XChain {
             }
 
             let graph = TraversalGraph {
+                regions: *grid.regions(),
                 strong_link_positions,
                 strong_link_peers,
             };
@@ -255,7 +281,7 @@ impl XChain {
 
 impl Technique for XChain {
     fn id(&self) -> &'static str {
-        ID
+        ID.as_str()
     }
 
     fn name(&self) -> &'static str {
@@ -4,12 +4,13 @@ use numelace_core::{ConsistencyError, Digit, DigitPositions, DigitSet, House};
 use tinyvec::array_vec;
 
 use crate::{
-    BoxedTechnique, BoxedTechniqueStep, SolverError, Technique, TechniqueGrid, TechniqueStepData,
-    TechniqueTier,
+    BoxedTechnique, BoxedTechniqueStep, ChainNode, SolverError, Technique, TechniqueGrid,
+    TechniqueLink, TechniqueStepData, TechniqueTier,
     axis::{AxisOps, ColumnAxis, RowAxis},
+    technique::TechniqueId,
 };
 
-const ID: &str = "x_wing";
+const ID: TechniqueId = TechniqueId::XWing;
 const NAME: &str = "X-Wing";
 
 /// A technique that removes candidates using an X-Wing pattern.
@@ -34,21 +35,46 @@ impl Condition {
         before_grid: &TechniqueGrid,
         after_grid: &TechniqueGrid,
     ) -> BoxedTechniqueStep {
-        let condition_positions = self.base_houses.into_iter().map(House::positions).sum();
-        let crosses = self.cover_houses.into_iter().map(House::positions).sum();
+        let condition_positions = self
+            .base_houses
+            .into_iter()
+            .map(|house| before_grid.house_positions(house))
+            .sum();
+        let crosses = self
+            .cover_houses
+            .into_iter()
+            .map(|house| before_grid.house_positions(house))
+            .sum();
         let cross_positions = self
             .base_houses
             .into_iter()
-            .map(|house| house.positions() & crosses)
+            .map(|house| before_grid.house_positions(house) & crosses)
             .sum::<DigitPositions>();
         let condition_digit_positions = vec![(
             cross_positions & before_grid.digit_positions(self.digit),
             DigitSet::from_elem(self.digit),
         )];
-        TechniqueStepData::from_diff(
+        // Each base line holds the digit only at its two crossings.
+        let links = self
+            .base_houses
+            .into_iter()
+            .filter_map(|house| {
+                let [pos1, pos2] = (before_grid.house_positions(house)
+                    & crosses
+                    & before_grid.digit_positions(self.digit))
+                .as_double()?;
+                Some(TechniqueLink::strong(
+                    ChainNode::new(pos1, self.digit),
+                    ChainNode::new(pos2, self.digit),
+                ))
+            })
+            .collect();
+        TechniqueStepData::from_diff_with_links(
+            ID,
             NAME,
             condition_positions,
             condition_digit_positions,
+            links,
             before_grid,
             after_grid,
         )
@@ -87,7 +113,9 @@ impl XWing {
                 }
                 // If all four corners land in one box, each row would require a placement
                 // while the box allows only one. This is a candidate constraint violation.
-                if line1 / 3 == line2 / 3 && cross1 / 3 == cross2 / 3 {
+                let corners = (A::LINE_POSITIONS[line1] | A::LINE_POSITIONS[line2])
+                    & (A::CROSS_POSITIONS[cross1] | A::CROSS_POSITIONS[cross2]);
+                if corners.boxes_set(grid.regions()).len() == 1 {
                     return Err(ConsistencyError::CandidateConstraintViolation.into());
                 }
                 let eliminations = (A::CROSS_POSITIONS[cross1] | A::CROSS_POSITIONS[cross2])
@@ -137,7 +165,7 @@ impl XWing {
 
 impl Technique for XWing {
     fn id(&self) -> &'static str {
-        ID
+        ID.as_str()
     }
 
     fn name(&self) -> &'static str {
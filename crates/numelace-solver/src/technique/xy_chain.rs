@@ -6,11 +6,11 @@ use numelace_core::{
 use tinyvec::{ArrayVec, array_vec};
 
 use crate::{
-    BoxedTechnique, BoxedTechniqueStep, SolverError, Technique, TechniqueGrid, TechniqueStepData,
-    TechniqueTier,
+    BoxedTechnique, BoxedTechniqueStep, ChainNode, SolverError, Technique, TechniqueGrid,
+    TechniqueLink, TechniqueStepData, TechniqueTier, technique::TechniqueId,
 };
 
-const ID: &str = "xy_chain";
+const ID: TechniqueId = TechniqueId::XyChain;
 const NAME: &str = "XY-Chain";
 
 /// A technique that removes candidates using an XY-Chain pattern.
@@ -38,17 +38,22 @@ impl Condition<'_> {
     ) -> BoxedTechniqueStep {
         let mut condition_positions = DigitPositions::new();
         let mut condition_digit_positions = vec![];
+        let mut chain = vec![];
         for item in self.stack {
+            chain.push(ChainNode::new(item.position, item.incoming_digit));
+            chain.push(ChainNode::new(item.position, item.outgoing_digit));
             condition_positions.insert(item.position);
             condition_digit_positions.push((
                 DigitPositions::from_elem(item.position),
                 DigitSet::from_iter([item.incoming_digit, item.outgoing_digit]),
             ));
         }
-        TechniqueStepData::from_diff(
+        TechniqueStepData::from_diff_with_links(
+            ID,
             NAME,
             condition_positions,
             condition_digit_positions,
+            TechniqueLink::along_chain(&chain),
             before_grid,
             after_grid,
         )
@@ -132,7 +137,7 @@ impl XyChain {
         for position in bivalue_positions {
             let [d1, d2] = grid.candidates_at(position).as_double().unwrap();
             graph.link_map[d1][position].0 = d2;
-            let bivalue_positions_in_house_peers = position.house_peers() & bivalue_positions;
+            let bivalue_positions_in_house_peers = grid.house_peers(position) & bivalue_positions;
             for peer_pos in bivalue_positions_in_house_peers
                 & grid.digit_positions(d2)
                 & !grid.digit_positions(d1)
@@ -166,13 +171,14 @@ impl XyChain {
                         stack.push(next_item);
                         if end_digit == start_digit {
                             let mut changed = false;
-                            let elimination = start_pos.house_peers() & end_pos.house_peers();
+                            let elimination =
+                                grid.house_peers(start_pos) & grid.house_peers(end_pos);
                             changed |= grid.remove_candidate_with_mask(elimination, start_digit);
-                            if start_pos.house_peers().contains(end_pos) {
+                            if grid.house_peers(start_pos).contains(end_pos) {
                                 for [item1, item2] in stack.array_windows() {
                                     let digit = item1.outgoing_digit;
-                                    let elimination =
-                                        item1.position.house_peers() & item2.position.house_peers();
+                                    let elimination = grid.house_peers(item1.position)
+                                        & grid.house_peers(item2.position);
                                     changed |= grid.remove_candidate_with_mask(elimination, digit);
                                 }
                             }
@@ -195,7 +201,7 @@ impl XyChain {
 
 impl Technique for XyChain {
     fn id(&self) -> &'static str {
-        ID
+        ID.as_str()
     }
 
     fn name(&self) -> &'static str {
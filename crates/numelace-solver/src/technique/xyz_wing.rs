@@ -4,9 +4,10 @@ use numelace_core::{Digit, DigitPositions, DigitSet, Position};
 
 use crate::{
     BoxedTechniqueStep, SolverError, Technique, TechniqueGrid, TechniqueStepData, TechniqueTier,
+    technique::TechniqueId,
 };
 
-const ID: &str = "xyz_wing";
+const ID: TechniqueId = TechniqueId::XyzWing;
 const NAME: &str = "XYZ-Wing";
 
 /// A technique that removes candidates using an XYZ-Wing pattern.
@@ -49,6 +50,7 @@ impl Condition {
             ),
         ];
         TechniqueStepData::from_diff(
+            ID,
             NAME,
             condition_positions,
             condition_digit_positions,
@@ -80,7 +82,7 @@ impl XyzWing {
             let pd1_mask = grid.digit_positions(pd1);
             let pd2_mask = grid.digit_positions(pd2);
             let pd3_mask = grid.digit_positions(pd3);
-            let wing_candidates = pivot.house_peers()
+            let wing_candidates = grid.house_peers(pivot)
                 & bivalue_positions
                 & ((pd1_mask & pd2_mask) | (pd2_mask & pd3_mask) | (pd3_mask & pd1_mask));
             for wing1 in wing_candidates {
@@ -107,7 +109,7 @@ impl XyzWing {
                             .as_single()
                             .unwrap();
                     let elimination_mask =
-                        pivot.house_peers() & wing1.house_peers() & wing2.house_peers();
+                        grid.house_peers(pivot) & grid.house_peers(wing1) & grid.house_peers(wing2);
                     if grid.remove_candidate_with_mask(elimination_mask, common_digit)
                         && let ControlFlow::Break(value) = on_condition(
                             grid,
@@ -132,7 +134,7 @@ impl XyzWing {
 
 impl Technique for XyzWing {
     fn id(&self) -> &'static str {
-        ID
+        ID.as_str()
     }
 
     fn name(&self) -> &'static str {
@@ -3,11 +3,11 @@ use std::ops::ControlFlow;
 use numelace_core::{Digit, DigitPositions, DigitSet, Position};
 
 use crate::{
-    BoxedTechnique, BoxedTechniqueStep, SolverError, Technique, TechniqueGrid, TechniqueStepData,
-    TechniqueTier,
+    BoxedTechnique, BoxedTechniqueStep, ChainNode, SolverError, Technique, TechniqueGrid,
+    TechniqueLink, TechniqueStepData, TechniqueTier, technique::TechniqueId,
 };
 
-const ID: &str = "y_wing";
+const ID: TechniqueId = TechniqueId::YWing;
 const NAME: &str = "Y-Wing";
 
 /// A technique that removes candidates using a Y-Wing pattern.
@@ -49,10 +49,20 @@ impl Condition {
                 DigitSet::from_iter([self.d2, self.d3]),
             ),
         ];
-        TechniqueStepData::from_diff(
+        let links = TechniqueLink::along_chain(&[
+            ChainNode::new(self.wing1, self.d3),
+            ChainNode::new(self.wing1, self.d1),
+            ChainNode::new(self.pivot, self.d1),
+            ChainNode::new(self.pivot, self.d2),
+            ChainNode::new(self.wing2, self.d2),
+            ChainNode::new(self.wing2, self.d3),
+        ]);
+        TechniqueStepData::from_diff_with_links(
+            ID,
             NAME,
             condition_positions,
             condition_digit_positions,
+            links,
             before_grid,
             after_grid,
         )
@@ -73,7 +83,7 @@ impl YWing {
     {
         let bivalue_positions = grid.classify_positions::<3>()[2];
         for pivot in bivalue_positions {
-            let pivot_peers = pivot.house_peers() & bivalue_positions;
+            let pivot_peers = grid.house_peers(pivot) & bivalue_positions;
             let pivot_digits = grid.candidates_at(pivot);
             let Some([d1, d2]) = pivot_digits.as_double() else {
                 // `grid.remove_candidate_with_mask` may have changed the candidates at pivot, so we need to check again
@@ -85,8 +95,8 @@ impl YWing {
                     continue;
                 };
                 for wing2 in pivot_peers & grid.digit_positions(d2) & grid.digit_positions(d3) {
-                    let elimination_cells =
-                        (wing1.house_peers() & wing2.house_peers()) & grid.digit_positions(d3);
+                    let elimination_cells = (grid.house_peers(wing1) & grid.house_peers(wing2))
+                        & grid.digit_positions(d3);
                     if grid.remove_candidate_with_mask(elimination_cells, d3)
                         && let ControlFlow::Break(value) = on_condition(
                             grid,
@@ -111,7 +121,7 @@ impl YWing {
 
 impl Technique for YWing {
     fn id(&self) -> &'static str {
-        ID
+        ID.as_str()
     }
 
     fn name(&self) -> &'static str {
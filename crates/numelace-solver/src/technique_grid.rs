@@ -1,5 +1,6 @@
 use numelace_core::{
-    CandidateGrid, ConsistencyError, Digit, DigitGrid, DigitPositions, DigitSet, Position,
+    CandidateGrid, ConsistencyError, Constraint, Digit, DigitGrid, DigitPositions, DigitSet, House,
+    Position, RegionLayout,
 };
 
 /// Solver state for technique-based solving.
@@ -74,6 +75,56 @@ impl TechniqueGrid {
         Self::from(CandidateGrid::from_digit_grid(grid))
     }
 
+    /// Builds a technique grid from a digit grid whose boxes are the regions
+    /// of `regions`.
+    ///
+    /// This is a convenience wrapper around
+    /// [`CandidateGrid::from_digit_grid_with_regions`].
+    #[inline]
+    #[must_use]
+    pub fn from_digit_grid_with_regions(grid: &DigitGrid, regions: RegionLayout) -> Self {
+        Self::from(CandidateGrid::from_digit_grid_with_regions(grid, regions))
+    }
+
+    /// Sets the region layout whose regions act as the boxes of the grid.
+    ///
+    /// This mirrors [`CandidateGrid::with_regions`].
+    #[inline]
+    #[must_use]
+    pub fn with_regions(mut self, regions: RegionLayout) -> Self {
+        self.candidates = self.candidates.with_regions(regions);
+        self
+    }
+
+    /// Returns the region layout whose regions act as the boxes of the grid.
+    ///
+    /// This mirrors [`CandidateGrid::regions`].
+    #[inline]
+    #[must_use]
+    pub fn regions(&self) -> &RegionLayout {
+        self.candidates.regions()
+    }
+
+    /// Returns the positions of `house`, with boxes taken from the grid's
+    /// region layout.
+    ///
+    /// This mirrors [`CandidateGrid::house_positions`].
+    #[inline]
+    #[must_use]
+    pub fn house_positions(&self, house: House) -> DigitPositions {
+        self.candidates.house_positions(house)
+    }
+
+    /// Returns all positions sharing a row, column, or box with `pos`,
+    /// excluding `pos`.
+    ///
+    /// This mirrors [`CandidateGrid::house_peers`].
+    #[inline]
+    #[must_use]
+    pub fn house_peers(&self, pos: Position) -> DigitPositions {
+        self.candidates.house_peers(pos)
+    }
+
     /// Consumes the wrapper and returns the underlying candidate grid.
     ///
     /// This is intended for interoperability with APIs that operate directly
@@ -159,6 +210,20 @@ impl TechniqueGrid {
         self.candidates.remove_candidate_set_with_mask(mask, digits)
     }
 
+    /// Removes candidates ruled out by a variant constraint.
+    ///
+    /// Returns `true` if any candidate was removed.
+    ///
+    /// This mirrors [`Constraint::prune`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConsistencyError`] if the constraint can no longer be satisfied.
+    #[inline]
+    pub fn prune_with(&mut self, constraint: &dyn Constraint) -> Result<bool, ConsistencyError> {
+        constraint.prune(&mut self.candidates)
+    }
+
     /// Returns the set of all positions where the specified digit can be placed.
     ///
     /// This mirrors [`CandidateGrid::digit_positions`].
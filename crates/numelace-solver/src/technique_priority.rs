@@ -0,0 +1,214 @@
+use std::collections::BTreeSet;
+
+use crate::{
+    BoxedTechnique,
+    technique::{self, TechniqueId},
+};
+
+/// Ordering and enablement of techniques, keyed by [`TechniqueId`].
+///
+/// Techniques listed in the preferred order are tried first, in that order.
+/// All other techniques, including custom ones without an ID, keep their
+/// original relative order and follow afterwards, so the resulting order is
+/// deterministic for any input list. Disabled techniques are dropped.
+///
+/// Techniques that [assume a unique solution](TechniqueId::assumes_unique_solution)
+/// are only included by [`techniques`](Self::techniques) once the priority
+/// [assumes uniqueness](Self::with_unique_solution_assumed).
+///
+/// # Examples
+///
+/// ```
+/// use numelace_solver::{TechniquePriority, TechniqueSolver, technique::TechniqueId};
+///
+/// let priority = TechniquePriority::new()
+///     .with_order([TechniqueId::HiddenSingle])
+///     .with_disabled([TechniqueId::XWing, TechniqueId::Swordfish, TechniqueId::Jellyfish]);
+/// let solver = TechniqueSolver::with_priority(&priority);
+///
+/// assert_eq!(solver.techniques()[0].id(), "hidden_single");
+/// assert_eq!(solver.techniques()[1].id(), "full_house");
+/// assert!(solver.techniques().iter().all(|t| t.id() != "x_wing"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TechniquePriority {
+    order: Vec<TechniqueId>,
+    disabled: BTreeSet<TechniqueId>,
+    unique_solution_assumed: bool,
+}
+
+impl TechniquePriority {
+    /// Creates a priority that keeps the default order and enables every technique.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the priority with `ids` as the preferred order.
+    #[must_use]
+    pub fn with_order(mut self, ids: impl IntoIterator<Item = TechniqueId>) -> Self {
+        self.set_order(ids);
+        self
+    }
+
+    /// Returns the priority with the techniques in `ids` disabled.
+    #[must_use]
+    pub fn with_disabled(mut self, ids: impl IntoIterator<Item = TechniqueId>) -> Self {
+        self.disabled.extend(ids);
+        self
+    }
+
+    /// Returns the priority with techniques that assume a unique solution
+    /// included or left out.
+    #[must_use]
+    pub fn with_unique_solution_assumed(mut self, assumed: bool) -> Self {
+        self.unique_solution_assumed = assumed;
+        self
+    }
+
+    /// Sets whether techniques that assume a unique solution are included.
+    pub fn set_unique_solution_assumed(&mut self, assumed: bool) {
+        self.unique_solution_assumed = assumed;
+    }
+
+    /// Returns `true` if techniques that assume a unique solution are included.
+    #[must_use]
+    pub fn unique_solution_assumed(&self) -> bool {
+        self.unique_solution_assumed
+    }
+
+    /// Replaces the preferred order. Duplicate IDs keep their first position.
+    pub fn set_order(&mut self, ids: impl IntoIterator<Item = TechniqueId>) {
+        self.order.clear();
+        for id in ids {
+            if !self.order.contains(&id) {
+                self.order.push(id);
+            }
+        }
+    }
+
+    /// Enables or disables the technique with the given ID.
+    pub fn set_enabled(&mut self, id: TechniqueId, enabled: bool) {
+        if enabled {
+            self.disabled.remove(&id);
+        } else {
+            self.disabled.insert(id);
+        }
+    }
+
+    /// Returns `true` unless the technique with the given ID is disabled.
+    #[must_use]
+    pub fn is_enabled(&self, id: TechniqueId) -> bool {
+        !self.disabled.contains(&id)
+    }
+
+    /// Returns the preferred order.
+    #[must_use]
+    pub fn order(&self) -> &[TechniqueId] {
+        &self.order
+    }
+
+    /// Returns the IDs of the disabled techniques, sorted.
+    pub fn disabled(&self) -> impl Iterator<Item = TechniqueId> {
+        self.disabled.iter().copied()
+    }
+
+    /// Reorders `techniques` by the preferred order without dropping any of them.
+    pub fn sort(&self, techniques: &mut [BoxedTechnique]) {
+        // Stable, so unlisted techniques keep their relative order.
+        techniques.sort_by_key(|tech| {
+            tech.technique_id()
+                .and_then(|id| self.order.iter().position(|ordered| *ordered == id))
+                .unwrap_or(self.order.len())
+        });
+    }
+
+    /// Reorders `techniques` and drops the disabled ones.
+    #[must_use]
+    pub fn apply(&self, mut techniques: Vec<BoxedTechnique>) -> Vec<BoxedTechnique> {
+        techniques.retain(|tech| tech.technique_id().is_none_or(|id| self.is_enabled(id)));
+        self.sort(&mut techniques);
+        techniques
+    }
+
+    /// Returns all available techniques with this priority applied.
+    #[must_use]
+    pub fn techniques(&self) -> Vec<BoxedTechnique> {
+        if self.unique_solution_assumed {
+            self.apply(technique::all_techniques_assuming_unique_solution())
+        } else {
+            self.apply(technique::all_techniques())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(techniques: &[BoxedTechnique]) -> Vec<&'static str> {
+        techniques.iter().map(|tech| tech.id()).collect()
+    }
+
+    #[test]
+    fn test_default_keeps_all_techniques_in_order() {
+        let priority = TechniquePriority::new();
+        assert_eq!(
+            ids(&priority.techniques()),
+            ids(&technique::all_techniques())
+        );
+    }
+
+    #[test]
+    fn test_preferred_techniques_come_first() {
+        let priority = TechniquePriority::new().with_order([
+            TechniqueId::XWing,
+            TechniqueId::HiddenSingle,
+            TechniqueId::CageCombination,
+        ]);
+        let techniques = priority.techniques();
+        let ids = ids(&techniques);
+
+        assert_eq!(ids[..3], ["x_wing", "hidden_single", "full_house"]);
+        assert_eq!(ids.len(), technique::all_techniques().len());
+    }
+
+    #[test]
+    fn test_uniqueness_techniques_need_opt_in() {
+        let priority = TechniquePriority::new();
+        assert!(!ids(&priority.techniques()).contains(&"unique_rectangle"));
+
+        let priority = priority.with_unique_solution_assumed(true);
+        assert!(ids(&priority.techniques()).contains(&"unique_rectangle"));
+    }
+
+    #[test]
+    fn test_disabled_techniques_are_dropped() {
+        let mut priority =
+            TechniquePriority::new().with_disabled([TechniqueId::NakedPair, TechniqueId::XWing]);
+        priority.set_enabled(TechniqueId::XWing, true);
+
+        let techniques = priority.techniques();
+        assert!(!priority.is_enabled(TechniqueId::NakedPair));
+        assert!(!ids(&techniques).contains(&"naked_pair"));
+        assert!(ids(&techniques).contains(&"x_wing"));
+        assert_eq!(
+            priority.disabled().collect::<Vec<_>>(),
+            [TechniqueId::NakedPair]
+        );
+    }
+
+    #[test]
+    fn test_set_order_removes_duplicates() {
+        let mut priority = TechniquePriority::new();
+        priority.set_order([
+            TechniqueId::HiddenSingle,
+            TechniqueId::NakedSingle,
+            TechniqueId::HiddenSingle,
+        ]);
+        assert_eq!(
+            priority.order(),
+            [TechniqueId::HiddenSingle, TechniqueId::NakedSingle]
+        );
+    }
+}
@@ -1,4 +1,7 @@
-use crate::{BoxedTechnique, BoxedTechniqueStep, SolverError, TechniqueGrid, technique};
+use crate::{
+    BoxedTechnique, BoxedTechniqueStep, SolverBudget, SolverError, TechniqueGrid,
+    TechniquePriority, technique,
+};
 
 /// Statistics collected during technique-based solving.
 ///
@@ -129,6 +132,7 @@ impl TechniqueSolverStats {
 #[derive(Debug, Clone)]
 pub struct TechniqueSolver {
     techniques: Vec<BoxedTechnique>,
+    budget: SolverBudget,
 }
 
 impl TechniqueSolver {
@@ -148,7 +152,10 @@ impl TechniqueSolver {
     /// ```
     #[must_use]
     pub fn new(techniques: Vec<BoxedTechnique>) -> Self {
-        Self { techniques }
+        Self {
+            techniques,
+            budget: SolverBudget::UNLIMITED,
+        }
     }
 
     /// Creates a new solver with all available techniques.
@@ -165,9 +172,42 @@ impl TechniqueSolver {
     /// ```
     #[must_use]
     pub fn with_all_techniques() -> Self {
-        Self {
-            techniques: technique::all_techniques(),
-        }
+        Self::new(technique::all_techniques())
+    }
+
+    /// Creates a new solver with all available techniques, reordered and
+    /// filtered by `priority`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use numelace_solver::{TechniquePriority, TechniqueSolver, technique::TechniqueId};
+    ///
+    /// let priority = TechniquePriority::new().with_order([TechniqueId::HiddenSingle]);
+    /// let solver = TechniqueSolver::with_priority(&priority);
+    /// assert_eq!(solver.techniques()[0].id(), "hidden_single");
+    /// ```
+    #[must_use]
+    pub fn with_priority(priority: &TechniquePriority) -> Self {
+        Self::new(priority.techniques())
+    }
+
+    /// Returns a solver that gives up once `budget` is spent.
+    ///
+    /// The budget applies to each call of the `solve_*` methods, counting
+    /// technique applications as steps; they then return
+    /// [`SolverError::BudgetExceeded`]. Single steps and passes are limited by
+    /// time only, checked before each technique.
+    #[must_use]
+    pub fn with_budget(mut self, budget: SolverBudget) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    /// Returns the budget of each solve call.
+    #[must_use]
+    pub fn budget(&self) -> SolverBudget {
+        self.budget
     }
 
     /// Creates a statistics object aligned with this solver's technique order.
@@ -194,13 +234,16 @@ impl TechniqueSolver {
     ///
     /// # Errors
     ///
-    /// Returns [`SolverError::Inconsistent`] if the grid is inconsistent.
+    /// Returns [`SolverError::Inconsistent`] if the grid is inconsistent, or
+    /// [`SolverError::BudgetExceeded`] if the solver's time limit passes.
     pub fn find_step(
         &self,
         grid: &TechniqueGrid,
     ) -> Result<Option<BoxedTechniqueStep>, SolverError> {
         grid.check_consistency()?;
+        let budget = self.budget.start();
         for technique in &self.techniques {
+            budget.check(0)?;
             if let Some(step) = technique.find_step(grid)? {
                 return Ok(Some(step));
             }
@@ -214,10 +257,13 @@ impl TechniqueSolver {
     ///
     /// # Errors
     ///
-    /// Returns [`SolverError::Inconsistent`] if the grid is inconsistent.
+    /// Returns [`SolverError::Inconsistent`] if the grid is inconsistent, or
+    /// [`SolverError::BudgetExceeded`] if the solver's time limit passes.
     pub fn find_pass(&self, grid: &TechniqueGrid) -> Result<Vec<BoxedTechniqueStep>, SolverError> {
         grid.check_consistency()?;
+        let budget = self.budget.start();
         for technique in &self.techniques {
+            budget.check(0)?;
             let steps = technique.find_pass(grid)?;
             if !steps.is_empty() {
                 return Ok(steps);
@@ -284,7 +330,8 @@ impl TechniqueSolver {
     ///
     /// # Errors
     ///
-    /// Returns [`SolverError::Inconsistent`] if the grid becomes inconsistent.
+    /// Returns [`SolverError::Inconsistent`] if the grid becomes inconsistent, or
+    /// [`SolverError::BudgetExceeded`] if the solver's budget runs out.
     pub fn solve_with_step(
         &self,
         grid: &mut TechniqueGrid,
@@ -298,7 +345,8 @@ impl TechniqueSolver {
     ///
     /// # Errors
     ///
-    /// Returns [`SolverError::Inconsistent`] if the grid becomes inconsistent.
+    /// Returns [`SolverError::Inconsistent`] if the grid becomes inconsistent, or
+    /// [`SolverError::BudgetExceeded`] if the solver's budget runs out.
     pub fn solve_with_pass(
         &self,
         grid: &mut TechniqueGrid,
@@ -312,13 +360,17 @@ impl TechniqueSolver {
     ///
     /// # Errors
     ///
-    /// Returns [`SolverError::Inconsistent`] if the grid becomes inconsistent.
+    /// Returns [`SolverError::Inconsistent`] if the grid becomes inconsistent, or
+    /// [`SolverError::BudgetExceeded`] if the solver's budget runs out.
     pub fn solve_with_step_stats(
         &self,
         grid: &mut TechniqueGrid,
         stats: &mut TechniqueSolverStats,
     ) -> Result<bool, SolverError> {
+        let budget = self.budget.start();
+        let start_steps = stats.total_steps;
         while self.apply_step(grid, stats)? {
+            budget.check(stats.total_steps - start_steps)?;
             if grid.is_solved()? {
                 return Ok(true);
             }
@@ -330,13 +382,17 @@ impl TechniqueSolver {
     ///
     /// # Errors
     ///
-    /// Returns [`SolverError::Inconsistent`] if the grid becomes inconsistent.
+    /// Returns [`SolverError::Inconsistent`] if the grid becomes inconsistent, or
+    /// [`SolverError::BudgetExceeded`] if the solver's budget runs out.
     pub fn solve_with_pass_stats(
         &self,
         grid: &mut TechniqueGrid,
         stats: &mut TechniqueSolverStats,
     ) -> Result<bool, SolverError> {
+        let budget = self.budget.start();
+        let start_steps = stats.total_steps;
         while self.apply_pass(grid, stats)? > 0 {
+            budget.check(stats.total_steps - start_steps)?;
             if grid.is_solved()? {
                 return Ok(true);
             }
@@ -347,7 +403,9 @@ impl TechniqueSolver {
 
 #[cfg(test)]
 mod tests {
-    use numelace_core::{CandidateGrid, Digit, Position};
+    use std::time::Duration;
+
+    use numelace_core::{CandidateGrid, Digit, DigitGrid, Position};
 
     use super::*;
     use crate::{
@@ -627,4 +685,54 @@ mod tests {
 
         assert!(stats.total_steps() >= first_steps);
     }
+
+    #[test]
+    fn test_solve_stops_when_budget_is_spent() {
+        // Solvable by singles, one step per cell
+        let problem: DigitGrid =
+            "...36..4..9.....2...67..1..5....987..31..............4..8...65.67.....3......3..2"
+                .parse()
+                .unwrap();
+
+        let solver = create_test_solver();
+        let mut grid = TechniqueGrid::from_digit_grid(&problem);
+        let (_, stats) = solver.solve_with_step(&mut grid).unwrap();
+        assert!(grid.is_solved().unwrap());
+
+        let solver = create_test_solver()
+            .with_budget(SolverBudget::new().with_max_steps(stats.total_steps()));
+        let mut grid = TechniqueGrid::from_digit_grid(&problem);
+        assert!(solver.solve_with_step(&mut grid).unwrap().0);
+
+        let solver = create_test_solver()
+            .with_budget(SolverBudget::new().with_max_steps(stats.total_steps() - 1));
+        let mut grid = TechniqueGrid::from_digit_grid(&problem);
+        assert!(matches!(
+            solver.solve_with_step(&mut grid),
+            Err(SolverError::BudgetExceeded)
+        ));
+    }
+
+    #[test]
+    fn test_find_step_stops_after_time_limit() {
+        let problem: DigitGrid =
+            "...36..4..9.....2...67..1..5....987..31..............4..8...65.67.....3......3..2"
+                .parse()
+                .unwrap();
+        let grid = TechniqueGrid::from_digit_grid(&problem);
+
+        let solver = create_test_solver();
+        assert!(solver.find_step(&grid).unwrap().is_some());
+
+        let solver =
+            create_test_solver().with_budget(SolverBudget::new().with_max_duration(Duration::ZERO));
+        assert!(matches!(
+            solver.find_step(&grid),
+            Err(SolverError::BudgetExceeded)
+        ));
+        assert!(matches!(
+            solver.find_pass(&grid),
+            Err(SolverError::BudgetExceeded)
+        ));
+    }
 }
@@ -6,8 +6,12 @@
 use std::fmt::Debug;
 
 use numelace_core::{Digit, DigitPositions, DigitSet, Position};
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error as _};
 
-use crate::TechniqueGrid;
+use crate::{
+    TechniqueGrid,
+    technique::{self, TechniqueId},
+};
 
 /// Positions involved in a technique's applicability conditions.
 pub type ConditionPositions = DigitPositions;
@@ -20,6 +24,20 @@ pub trait TechniqueStep: Debug + Send + Sync {
     /// Returns the name of the technique that produced this step.
     fn technique_name(&self) -> &'static str;
 
+    /// Returns a stable, non-display key for the technique (and variant) that produced this step.
+    ///
+    /// User interfaces can use this key to look up localized names and descriptions
+    /// instead of relying on [`TechniqueStep::technique_name`].
+    fn technique_key(&self) -> &'static str;
+
+    /// Returns the ID of the built-in technique that produced this step.
+    ///
+    /// The default implementation parses [`TechniqueStep::technique_key`] and
+    /// returns `None` for steps of custom techniques.
+    fn technique_id(&self) -> Option<TechniqueId> {
+        self.technique_key().parse().ok()
+    }
+
     /// Returns a boxed clone of the step.
     fn clone_box(&self) -> BoxedTechniqueStep;
 
@@ -36,32 +54,140 @@ pub trait TechniqueStep: Debug + Send + Sync {
     /// explanation of the underlying logic.
     fn condition_digit_positions(&self) -> ConditionDigitPositions;
 
+    /// Returns the candidates of the chain the step follows, in chain order.
+    ///
+    /// The links between consecutive nodes alternate between strong and weak,
+    /// starting and ending with a strong link. Hint systems may draw them as
+    /// arrows. The default implementation returns an empty chain, for steps
+    /// that are not chains.
+    fn chain(&self) -> Vec<ChainNode> {
+        Vec::new()
+    }
+
+    /// Returns the links between candidates that the step relies on.
+    ///
+    /// Hint systems may draw them as arrows, styled by [`LinkKind`]. The
+    /// default implementation links the consecutive nodes of
+    /// [`TechniqueStep::chain`].
+    fn links(&self) -> Vec<TechniqueLink> {
+        TechniqueLink::along_chain(&self.chain())
+    }
+
     /// Returns the concrete changes produced by applying the technique.
     fn application(&self) -> Vec<TechniqueApplication>;
 }
 
+/// A candidate in the chain of a [`TechniqueStep`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ChainNode {
+    /// Cell of the candidate.
+    pub position: Position,
+    /// Digit of the candidate.
+    pub digit: Digit,
+}
+
+impl ChainNode {
+    /// Creates a chain node for `digit` in the cell at `position`.
+    #[must_use]
+    pub const fn new(position: Position, digit: Digit) -> Self {
+        Self { position, digit }
+    }
+}
+
+/// Kind of a [`TechniqueLink`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LinkKind {
+    /// At least one of the two candidates is true.
+    Strong,
+    /// At most one of the two candidates is true.
+    Weak,
+}
+
+/// A link between two candidates, read from `from` to `to`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TechniqueLink {
+    /// Candidate the link starts from.
+    pub from: ChainNode,
+    /// Candidate the link leads to.
+    pub to: ChainNode,
+    /// Whether the link is strong or weak.
+    pub kind: LinkKind,
+}
+
+impl TechniqueLink {
+    /// Creates a strong link from `from` to `to`.
+    #[must_use]
+    pub const fn strong(from: ChainNode, to: ChainNode) -> Self {
+        Self {
+            from,
+            to,
+            kind: LinkKind::Strong,
+        }
+    }
+
+    /// Creates a weak link from `from` to `to`.
+    #[must_use]
+    pub const fn weak(from: ChainNode, to: ChainNode) -> Self {
+        Self {
+            from,
+            to,
+            kind: LinkKind::Weak,
+        }
+    }
+
+    /// Links the consecutive nodes of an alternating chain, starting with a
+    /// strong link.
+    #[must_use]
+    pub fn along_chain(chain: &[ChainNode]) -> Vec<Self> {
+        chain
+            .windows(2)
+            .enumerate()
+            .map(|(i, pair)| {
+                if i % 2 == 0 {
+                    Self::strong(pair[0], pair[1])
+                } else {
+                    Self::weak(pair[0], pair[1])
+                }
+            })
+            .collect()
+    }
+}
+
 /// Shared data for technique steps without technique-specific payloads.
-#[derive(Debug, Clone)]
+///
+/// This is also the serializable form of a step: it can be serialized with
+/// serde and sent to another thread or worker, and its key and name are
+/// resolved back to the built-in technique when deserialized.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TechniqueStepData {
+    technique_id: TechniqueId,
+    technique_key: &'static str,
     technique_name: &'static str,
     condition_positions: ConditionPositions,
     condition_digit_positions: ConditionDigitPositions,
+    chain: Vec<ChainNode>,
+    links: Vec<TechniqueLink>,
     application: Vec<TechniqueApplication>,
 }
 
 impl TechniqueStepData {
-    /// Creates a new boxed `TechniqueStepData`.
+    /// Creates a new boxed `TechniqueStepData` keyed by the technique ID.
     #[must_use]
     pub fn new_boxed(
+        technique_id: TechniqueId,
         technique_name: &'static str,
         condition_positions: ConditionPositions,
         condition_digit_positions: ConditionDigitPositions,
         application: Vec<TechniqueApplication>,
     ) -> BoxedTechniqueStep {
         Box::new(Self {
+            technique_id,
+            technique_key: technique_id.as_str(),
             technique_name,
             condition_positions,
             condition_digit_positions,
+            chain: Vec::new(),
+            links: Vec::new(),
             application,
         })
     }
@@ -69,20 +195,124 @@ impl TechniqueStepData {
     /// Creates a new boxed `TechniqueStepData` from a before/after grid diff.
     #[must_use]
     pub fn from_diff(
+        technique_id: TechniqueId,
         technique_name: &'static str,
         condition_positions: ConditionPositions,
         condition_digit_positions: ConditionDigitPositions,
         before: &TechniqueGrid,
         after: &TechniqueGrid,
     ) -> BoxedTechniqueStep {
-        let application = collect_applications_from_diff(before, after);
-        Self::new_boxed(
+        Self::from_diff_with_key(
+            technique_id,
+            technique_id.as_str(),
             technique_name,
             condition_positions,
             condition_digit_positions,
-            application,
+            before,
+            after,
         )
     }
+
+    /// Like [`Self::from_diff`], with a step key distinguishing a variant of the technique.
+    #[must_use]
+    pub fn from_diff_with_key(
+        technique_id: TechniqueId,
+        technique_key: &'static str,
+        technique_name: &'static str,
+        condition_positions: ConditionPositions,
+        condition_digit_positions: ConditionDigitPositions,
+        before: &TechniqueGrid,
+        after: &TechniqueGrid,
+    ) -> BoxedTechniqueStep {
+        Box::new(Self {
+            technique_id,
+            technique_key,
+            technique_name,
+            condition_positions,
+            condition_digit_positions,
+            chain: Vec::new(),
+            links: Vec::new(),
+            application: collect_applications_from_diff(before, after),
+        })
+    }
+
+    /// Like [`Self::from_diff`], with the links between candidates that the
+    /// step relies on.
+    #[must_use]
+    pub fn from_diff_with_links(
+        technique_id: TechniqueId,
+        technique_name: &'static str,
+        condition_positions: ConditionPositions,
+        condition_digit_positions: ConditionDigitPositions,
+        links: Vec<TechniqueLink>,
+        before: &TechniqueGrid,
+        after: &TechniqueGrid,
+    ) -> BoxedTechniqueStep {
+        Box::new(Self {
+            technique_id,
+            technique_key: technique_id.as_str(),
+            technique_name,
+            condition_positions,
+            condition_digit_positions,
+            chain: Vec::new(),
+            links,
+            application: collect_applications_from_diff(before, after),
+        })
+    }
+
+    /// Creates a new boxed `TechniqueStepData` for a step following `chain`
+    /// from a before/after grid diff.
+    ///
+    /// The cells and candidates of the chain become the condition positions.
+    #[must_use]
+    pub fn from_chain_diff(
+        technique_id: TechniqueId,
+        technique_name: &'static str,
+        chain: Vec<ChainNode>,
+        before: &TechniqueGrid,
+        after: &TechniqueGrid,
+    ) -> BoxedTechniqueStep {
+        let condition_positions = chain.iter().map(|node| node.position).collect();
+        let condition_digit_positions = chain
+            .iter()
+            .map(|node| {
+                (
+                    DigitPositions::from_elem(node.position),
+                    DigitSet::from_elem(node.digit),
+                )
+            })
+            .collect();
+        Box::new(Self {
+            technique_id,
+            technique_key: technique_id.as_str(),
+            technique_name,
+            condition_positions,
+            condition_digit_positions,
+            links: TechniqueLink::along_chain(&chain),
+            chain,
+            application: collect_applications_from_diff(before, after),
+        })
+    }
+
+    /// Copies the data of a step produced by a built-in technique.
+    ///
+    /// Returns `None` if the step's key does not belong to a built-in technique,
+    /// since such a step could not be resolved again after deserialization.
+    #[must_use]
+    pub fn from_step(step: &dyn TechniqueStep) -> Option<Self> {
+        let (technique_id, technique_key, technique_name) =
+            technique::resolve_step_key(step.technique_key())?;
+        Some(Self {
+            technique_id,
+            technique_key,
+            technique_name,
+            condition_positions: step.condition_positions(),
+            condition_digit_positions: step.condition_digit_positions(),
+            chain: step.chain(),
+            links: step.links(),
+            application: step.application(),
+        })
+    }
 }
 
 impl TechniqueStep for TechniqueStepData {
@@ -90,6 +320,14 @@ impl TechniqueStep for TechniqueStepData {
         self.technique_name
     }
 
+    fn technique_key(&self) -> &'static str {
+        self.technique_key
+    }
+
+    fn technique_id(&self) -> Option<TechniqueId> {
+        Some(self.technique_id)
+    }
+
     fn clone_box(&self) -> BoxedTechniqueStep {
         Box::new(self.clone())
     }
@@ -102,6 +340,14 @@ impl TechniqueStep for TechniqueStepData {
         self.condition_digit_positions.clone()
     }
 
+    fn chain(&self) -> Vec<ChainNode> {
+        self.chain.clone()
+    }
+
+    fn links(&self) -> Vec<TechniqueLink> {
+        self.links.clone()
+    }
+
     fn application(&self) -> Vec<TechniqueApplication> {
         self.application.clone()
     }
@@ -126,6 +372,193 @@ pub enum TechniqueApplication {
     },
 }
 
+/// Wire form of [`TechniqueStepData`], with positions as cell indices and digit
+/// sets as bit masks.
+#[derive(Serialize, Deserialize)]
+struct StepRepr {
+    technique: String,
+    condition_positions: Vec<u8>,
+    condition_digit_positions: Vec<(Vec<u8>, u16)>,
+    /// Absent for steps that are not chains.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    chain: Vec<NodeRepr>,
+    /// Links as (from, to, strong), absent for steps without links.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    links: Vec<(NodeRepr, NodeRepr, bool)>,
+    application: Vec<ApplicationRepr>,
+}
+
+/// Wire form of a [`ChainNode`], as the cell index and the digit.
+type NodeRepr = (u8, u8);
+
+#[derive(Serialize, Deserialize)]
+enum ApplicationRepr {
+    Placement { position: u8, digit: u8 },
+    CandidateElimination { positions: Vec<u8>, digits: u16 },
+}
+
+impl Serialize for TechniqueStepData {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        StepRepr {
+            technique: self.technique_key.to_owned(),
+            condition_positions: position_indices(self.condition_positions),
+            condition_digit_positions: self
+                .condition_digit_positions
+                .iter()
+                .map(|(positions, digits)| (position_indices(*positions), digits.bits()))
+                .collect(),
+            chain: self.chain.iter().map(|node| node_repr(*node)).collect(),
+            links: self
+                .links
+                .iter()
+                .map(|link| {
+                    (
+                        node_repr(link.from),
+                        node_repr(link.to),
+                        link.kind == LinkKind::Strong,
+                    )
+                })
+                .collect(),
+            application: self
+                .application
+                .iter()
+                .map(|app| match *app {
+                    TechniqueApplication::Placement { position, digit } => {
+                        ApplicationRepr::Placement {
+                            position: position.index(),
+                            digit: digit.value(),
+                        }
+                    }
+                    TechniqueApplication::CandidateElimination { positions, digits } => {
+                        ApplicationRepr::CandidateElimination {
+                            positions: position_indices(positions),
+                            digits: digits.bits(),
+                        }
+                    }
+                })
+                .collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for TechniqueStepData {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let repr = StepRepr::deserialize(deserializer)?;
+        let (technique_id, technique_key, technique_name) =
+            technique::resolve_step_key(&repr.technique).ok_or_else(|| {
+                D::Error::custom(format_args!("unknown technique key `{}`", repr.technique))
+            })?;
+        let condition_digit_positions = repr
+            .condition_digit_positions
+            .into_iter()
+            .map(|(positions, digits)| {
+                Ok((
+                    positions_from_indices(&positions)?,
+                    digits_from_bits(digits)?,
+                ))
+            })
+            .collect::<Result<_, &str>>()
+            .map_err(D::Error::custom)?;
+        let chain = repr
+            .chain
+            .into_iter()
+            .map(node_from_repr)
+            .collect::<Result<_, &str>>()
+            .map_err(D::Error::custom)?;
+        let links = repr
+            .links
+            .into_iter()
+            .map(|(from, to, strong)| {
+                Ok(TechniqueLink {
+                    from: node_from_repr(from)?,
+                    to: node_from_repr(to)?,
+                    kind: if strong {
+                        LinkKind::Strong
+                    } else {
+                        LinkKind::Weak
+                    },
+                })
+            })
+            .collect::<Result<_, &str>>()
+            .map_err(D::Error::custom)?;
+        let application = repr
+            .application
+            .into_iter()
+            .map(|app| match app {
+                ApplicationRepr::Placement { position, digit } => {
+                    Ok(TechniqueApplication::Placement {
+                        position: position_from_index(position)?,
+                        digit: digit_from_value(digit)?,
+                    })
+                }
+                ApplicationRepr::CandidateElimination { positions, digits } => {
+                    Ok(TechniqueApplication::CandidateElimination {
+                        positions: positions_from_indices(&positions)?,
+                        digits: digits_from_bits(digits)?,
+                    })
+                }
+            })
+            .collect::<Result<_, &str>>()
+            .map_err(D::Error::custom)?;
+        Ok(Self {
+            technique_id,
+            technique_key,
+            technique_name,
+            condition_positions: positions_from_indices(&repr.condition_positions)
+                .map_err(D::Error::custom)?,
+            condition_digit_positions,
+            chain,
+            links,
+            application,
+        })
+    }
+}
+
+fn node_repr(node: ChainNode) -> NodeRepr {
+    (node.position.index(), node.digit.value())
+}
+
+fn node_from_repr((position, digit): NodeRepr) -> Result<ChainNode, &'static str> {
+    Ok(ChainNode::new(
+        position_from_index(position)?,
+        digit_from_value(digit)?,
+    ))
+}
+
+fn position_indices(positions: DigitPositions) -> Vec<u8> {
+    positions.into_iter().map(Position::index).collect()
+}
+
+fn position_from_index(index: u8) -> Result<Position, &'static str> {
+    Position::try_from_index(index).map_err(|_| "cell index out of range")
+}
+
+fn positions_from_indices(indices: &[u8]) -> Result<DigitPositions, &'static str> {
+    indices
+        .iter()
+        .map(|&index| position_from_index(index))
+        .collect()
+}
+
+fn digit_from_value(value: u8) -> Result<Digit, &'static str> {
+    if (1..=9).contains(&value) {
+        Ok(Digit::from_value(value))
+    } else {
+        Err("digit out of range")
+    }
+}
+
+fn digits_from_bits(bits: u16) -> Result<DigitSet, &'static str> {
+    DigitSet::try_from_bits(bits).ok_or("invalid digit set")
+}
+
 /// A boxed technique step.
 pub type BoxedTechniqueStep = Box<dyn TechniqueStep>;
 
@@ -162,3 +595,76 @@ fn collect_applications_from_diff(
     }
     app
 }
+
+#[cfg(test)]
+mod tests {
+    use numelace_core::CandidateGrid;
+
+    use super::*;
+    use crate::{
+        Technique as _,
+        technique::{self, LockedCandidates},
+    };
+
+    fn pointing_step() -> BoxedTechniqueStep {
+        let mut grid = CandidateGrid::new();
+        for pos in Position::BOXES[0] {
+            if pos.row() != 0 {
+                grid.remove_candidate(pos, Digit::D5);
+            }
+        }
+        LockedCandidates::new()
+            .find_step(&grid.into())
+            .unwrap()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_step_round_trips_through_serde() {
+        let step = pointing_step();
+        let data = TechniqueStepData::from_step(&*step).unwrap();
+
+        let json = serde_json::to_string(&data).unwrap();
+        let restored: TechniqueStepData = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(restored, data);
+        assert_eq!(restored.technique_key(), "locked_candidates_pointing");
+        assert_eq!(restored.technique_name(), step.technique_name());
+        assert_eq!(restored.technique_id(), Some(TechniqueId::LockedCandidates));
+        assert_eq!(restored.application(), step.application());
+    }
+
+    #[test]
+    fn test_invalid_steps_are_rejected() {
+        let step = TechniqueStepData::from_step(&*pointing_step()).unwrap();
+        let json = serde_json::to_string(&step).unwrap();
+
+        let unknown_key = json.replace("locked_candidates_pointing", "no_such_technique");
+        assert!(serde_json::from_str::<TechniqueStepData>(&unknown_key).is_err());
+
+        let bad_digits = json.replace(
+            r#""condition_digit_positions":[[[0,1,2],16]]"#,
+            r#""condition_digit_positions":[[[0,1,2],1024]]"#,
+        );
+        assert_ne!(bad_digits, json);
+        assert!(serde_json::from_str::<TechniqueStepData>(&bad_digits).is_err());
+    }
+
+    #[test]
+    fn test_chain_round_trips_through_serde() {
+        let grid = crate::corpus::example(TechniqueId::Aic).unwrap();
+        let step = technique::Aic::new().find_step(&grid).unwrap().unwrap();
+        let data = TechniqueStepData::from_step(&*step).unwrap();
+        assert_eq!(data.chain().len(), 6);
+
+        let json = serde_json::to_string(&data).unwrap();
+        let restored: TechniqueStepData = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.chain(), step.chain());
+        assert_eq!(restored.links(), step.links());
+
+        // Steps of other techniques carry no chain on the wire.
+        let json = serde_json::to_string(&TechniqueStepData::from_step(&*pointing_step()).unwrap())
+            .unwrap();
+        assert!(!json.contains("chain") && !json.contains("links"));
+    }
+}
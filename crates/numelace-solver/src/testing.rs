@@ -1,6 +1,8 @@
 use std::str::FromStr as _;
 
-use numelace_core::{ConsistencyError, Digit, DigitGrid, DigitSet, Position};
+use numelace_core::{
+    ConsistencyError, Digit, DigitGrid, DigitPositions, DigitSet, Position, RegionLayout,
+};
 
 use crate::{BoxedTechniqueStep, SolverError, Technique, TechniqueApplication, TechniqueGrid};
 
@@ -165,6 +167,11 @@ impl TechniqueTester {
                     changed, 0,
                     "Expected {name} to report a change when find_step returned a step{context}"
                 );
+                assert_eq!(
+                    step.technique_id(),
+                    technique.technique_id(),
+                    "Expected the {name} step to carry the technique ID{context}"
+                );
                 self.assert_step_application_applied(before, &step);
             }
         }
@@ -356,8 +363,21 @@ impl GridSymmetry {
         }
     }
 
+    pub fn apply_regions(self, regions: &RegionLayout) -> RegionLayout {
+        let mut transformed = [DigitPositions::EMPTY; 9];
+        for (index, region) in (0..9).zip(&mut transformed) {
+            for pos in regions.region_positions(index) {
+                region.insert(self.apply_position(pos));
+            }
+        }
+        // Number the regions by their first cell, so the standard layout maps
+        // onto itself.
+        transformed.sort_by_key(|region| region.iter().next());
+        RegionLayout::from_regions(transformed).unwrap()
+    }
+
     pub fn apply_grid(self, grid: &TechniqueGrid) -> TechniqueGrid {
-        let mut transformed = TechniqueGrid::new();
+        let mut transformed = TechniqueGrid::new().with_regions(self.apply_regions(grid.regions()));
         for pos in Position::ALL {
             let digits = grid.candidates_at(pos);
             let transformed_pos = self.apply_position(pos);
@@ -570,6 +590,10 @@ mod tests {
             "place-d1-at-00"
         }
 
+        fn technique_key(&self) -> &'static str {
+            "place_d1_at_00"
+        }
+
         fn clone_box(&self) -> BoxedTechniqueStep {
             Box::new(self.clone())
         }
@@ -0,0 +1,215 @@
+use std::time::Duration;
+
+use numelace_core::Digit;
+use numelace_game::{
+    Game, GameError, HintError, InputDigitOptions, NoteCleanupPolicy, RuleCheckPolicy,
+};
+use numelace_generator::PuzzleGenerator;
+use numelace_solver::{SolverBudget, TechniquePriority, TechniqueSolver};
+
+use crate::state::{Hint, HintStage, InputMode, TuiState};
+
+/// Time limit of a hint search, as in the GUI.
+const HINT_BUDGET: SolverBudget = SolverBudget::new().with_max_duration(Duration::from_secs(5));
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum MoveDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+/// A request from the keyboard, applied to [`TuiState`] by [`Action::apply`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Action {
+    MoveSelection(MoveDirection),
+    /// Enters a digit or toggles a note, depending on the input mode.
+    Digit(Digit),
+    ClearCell,
+    ToggleInputMode,
+    AutoFillNotes,
+    /// Shows a hint, or more of the hint already shown.
+    Hint,
+    ApplyHint,
+    Undo,
+    NewGame,
+    Quit,
+}
+
+impl Action {
+    pub(crate) fn apply(self, state: &mut TuiState) {
+        state.message = None;
+        match self {
+            Action::MoveSelection(direction) => {
+                let pos = state.selected;
+                let moved = match direction {
+                    MoveDirection::Up => pos.up(),
+                    MoveDirection::Down => pos.down(),
+                    MoveDirection::Left => pos.left(),
+                    MoveDirection::Right => pos.right(),
+                };
+                state.selected = moved.unwrap_or(pos);
+            }
+            Action::Digit(digit) => {
+                let pos = state.selected;
+                let input_mode = state.input_mode;
+                change_board(state, |game| match input_mode {
+                    InputMode::Fill => game.set_digit(pos, digit, &input_options()),
+                    InputMode::Notes => game.toggle_note(pos, digit, RuleCheckPolicy::Permissive),
+                });
+            }
+            Action::ClearCell => {
+                let pos = state.selected;
+                if state.game.has_removable_input(pos) {
+                    change_board(state, |game| game.clear_cell(pos));
+                }
+            }
+            Action::ToggleInputMode => {
+                state.input_mode = match state.input_mode {
+                    InputMode::Fill => InputMode::Notes,
+                    InputMode::Notes => InputMode::Fill,
+                };
+            }
+            Action::AutoFillNotes => change_board(state, |game| {
+                game.auto_fill_notes_empty_cells();
+                Ok(())
+            }),
+            Action::Hint => show_hint(state),
+            Action::ApplyHint => {
+                if let Some(hint) = state.hint.take() {
+                    change_board(state, |game| {
+                        game.apply_technique_step(hint.step.as_ref(), &input_options())
+                    });
+                }
+            }
+            Action::Undo => {
+                if state.undo() {
+                    state.hint = None;
+                } else {
+                    state.message = Some("Nothing to undo.".to_owned());
+                }
+            }
+            Action::NewGame => {
+                let solver = TechniqueSolver::with_all_techniques();
+                let puzzle = PuzzleGenerator::new(&solver).generate();
+                state.start(Game::new(puzzle));
+            }
+            Action::Quit => state.quit = true,
+        }
+    }
+}
+
+fn input_options() -> InputDigitOptions {
+    InputDigitOptions::new(RuleCheckPolicy::Permissive, NoteCleanupPolicy::RemovePeers)
+}
+
+/// Applies `change` to the board, keeping the previous board for undo.
+fn change_board<T>(state: &mut TuiState, change: impl FnOnce(&mut Game) -> Result<T, GameError>) {
+    let previous = state.game.clone();
+    match change(&mut state.game) {
+        Ok(_) => {
+            state.push_history(previous);
+            state.hint = None;
+            if state.game.is_solved() {
+                state.message = Some("Solved! Press N for a new puzzle.".to_owned());
+            }
+        }
+        Err(err) => {
+            state.game = previous;
+            state.message = Some(err.to_string());
+        }
+    }
+}
+
+fn show_hint(state: &mut TuiState) {
+    if let Some(hint) = &mut state.hint {
+        hint.stage = HintStage::Step;
+        return;
+    }
+    if state.game.is_solved() {
+        state.message = Some("The puzzle is already solved.".to_owned());
+        return;
+    }
+    let message = match state.game.find_hint(&TechniquePriority::new(), HINT_BUDGET) {
+        Ok(Some(hint)) => {
+            state.hint = Some(Hint {
+                stage: HintStage::Area,
+                step: hint.step,
+            });
+            return;
+        }
+        Ok(None) => "No technique applies to this board.",
+        Err(HintError::Inconsistent(_) | HintError::SolutionMismatch) => {
+            "Some placed digits are wrong; undo or clear them first."
+        }
+        Err(HintError::Undetermined) => {
+            "The search for the next step took too long; fill in more cells first."
+        }
+    };
+    state.message = Some(message.to_owned());
+}
+
+#[cfg(test)]
+mod tests {
+    use numelace_core::{DigitGrid, DigitSet, Position};
+    use numelace_game::CellState;
+    use numelace_solver::technique::TechniqueId;
+
+    use super::*;
+
+    const SOLUTION: &str =
+        "185362947793148526246795183564239871931874265827516394318427659672951438459683712";
+
+    /// A puzzle whose first row is empty, with every other cell given.
+    fn state() -> TuiState {
+        let solution: DigitGrid = SOLUTION.parse().unwrap();
+        let problem: DigitGrid = format!("{}{}", ".".repeat(9), &SOLUTION[9..])
+            .parse()
+            .unwrap();
+        let game =
+            Game::from_problem_filled_notes(&problem, &solution, &DigitGrid::new(), &[[0; 9]; 9])
+                .unwrap();
+        TuiState::new(game)
+    }
+
+    #[test]
+    fn digits_fill_cells_or_toggle_notes_and_undo() {
+        let mut state = state();
+        Action::Digit(Digit::D1).apply(&mut state);
+        assert_eq!(
+            state.game.cell(Position::new(0, 0)),
+            &CellState::Filled(Digit::D1)
+        );
+
+        Action::MoveSelection(MoveDirection::Right).apply(&mut state);
+        Action::ToggleInputMode.apply(&mut state);
+        Action::Digit(Digit::D8).apply(&mut state);
+        assert_eq!(
+            state.game.cell(Position::new(0, 1)).as_notes(),
+            Some(DigitSet::from_elem(Digit::D8))
+        );
+
+        Action::Undo.apply(&mut state);
+        Action::Undo.apply(&mut state);
+        assert_eq!(state.game.cell(Position::new(0, 0)), &CellState::Empty);
+        Action::Undo.apply(&mut state);
+        assert_eq!(state.message.as_deref(), Some("Nothing to undo."));
+    }
+
+    #[test]
+    fn hints_are_shown_in_stages_and_applied() {
+        let mut state = state();
+        Action::Hint.apply(&mut state);
+        let hint = state.hint.as_ref().unwrap();
+        assert_eq!(hint.stage, HintStage::Area);
+        assert_eq!(hint.step.technique_id(), Some(TechniqueId::FullHouse));
+        Action::Hint.apply(&mut state);
+        assert_eq!(state.hint.as_ref().unwrap().stage, HintStage::Step);
+
+        let before = state.game.filled_positions().len();
+        Action::ApplyHint.apply(&mut state);
+        assert!(state.hint.is_none());
+        assert_eq!(state.game.filled_positions().len(), before + 1);
+    }
+}
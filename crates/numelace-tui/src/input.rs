@@ -0,0 +1,57 @@
+use numelace_core::Digit;
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+
+use crate::action::{Action, MoveDirection};
+
+/// Key bindings shown in the help line.
+pub(crate) const HELP: &str = "arrows/hjkl move  1-9 digit  n notes  0/del clear  f fill notes  \
+                               ? hint  enter apply  u undo  N new  q quit";
+
+/// Maps a key press to an action.
+#[must_use]
+pub(crate) fn action_for_key(key: KeyEvent) -> Option<Action> {
+    if key.kind == KeyEventKind::Release {
+        return None;
+    }
+    if key.modifiers.contains(KeyModifiers::CONTROL) {
+        return (key.code == KeyCode::Char('c')).then_some(Action::Quit);
+    }
+    let action = match key.code {
+        KeyCode::Up | KeyCode::Char('k') => Action::MoveSelection(MoveDirection::Up),
+        KeyCode::Down | KeyCode::Char('j') => Action::MoveSelection(MoveDirection::Down),
+        KeyCode::Left | KeyCode::Char('h') => Action::MoveSelection(MoveDirection::Left),
+        KeyCode::Right | KeyCode::Char('l') => Action::MoveSelection(MoveDirection::Right),
+        KeyCode::Char(ch @ '1'..='9') => Action::Digit(Digit::from_value(ch as u8 - b'0')),
+        KeyCode::Char('0' | 'x') | KeyCode::Delete | KeyCode::Backspace => Action::ClearCell,
+        KeyCode::Char('n') => Action::ToggleInputMode,
+        KeyCode::Char('f') => Action::AutoFillNotes,
+        KeyCode::Char('?') => Action::Hint,
+        KeyCode::Enter => Action::ApplyHint,
+        KeyCode::Char('u') => Action::Undo,
+        KeyCode::Char('N') => Action::NewGame,
+        KeyCode::Char('q') | KeyCode::Esc => Action::Quit,
+        _ => return None,
+    };
+    Some(action)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_map_to_actions() {
+        let press = |code| action_for_key(KeyEvent::from(code));
+        assert_eq!(press(KeyCode::Char('7')), Some(Action::Digit(Digit::D7)));
+        assert_eq!(
+            press(KeyCode::Char('h')),
+            Some(Action::MoveSelection(MoveDirection::Left))
+        );
+        assert_eq!(press(KeyCode::Backspace), Some(Action::ClearCell));
+        assert_eq!(press(KeyCode::Char('z')), None);
+        assert_eq!(
+            action_for_key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+            Some(Action::Quit)
+        );
+    }
+}
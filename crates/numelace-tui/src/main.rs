@@ -0,0 +1,90 @@
+//! Numelace terminal application using ratatui.
+//!
+//! Plays classic puzzles in a terminal, e.g. over SSH. The board is drawn with
+//! box-drawing characters, three lines per cell with notes when the terminal is
+//! tall enough and one line per cell otherwise. Like the GUI, key presses turn
+//! into actions that are applied to the state, and hints come from the
+//! technique solver, shown in stages.
+//!
+//! # Usage
+//!
+//! ```sh
+//! cargo run -p numelace-tui [STATE_CODE]
+//! ```
+//!
+//! Without a state code, a new puzzle is generated. A state code, as found in
+//! the share links of the GUI, opens that puzzle instead.
+
+use std::{env, io, process::ExitCode};
+
+use numelace_game::Game;
+use numelace_generator::PuzzleGenerator;
+use numelace_solver::TechniqueSolver;
+use ratatui::{
+    DefaultTerminal,
+    crossterm::event::{self, Event},
+};
+
+use crate::{action::Action, state::TuiState};
+
+mod action;
+mod input;
+mod render;
+mod state;
+
+fn main() -> ExitCode {
+    let game = if let Some(code) = env::args().nth(1) {
+        match load_game(&code) {
+            Ok(game) => game,
+            Err(message) => {
+                eprintln!("{message}");
+                return ExitCode::FAILURE;
+            }
+        }
+    } else {
+        let solver = TechniqueSolver::with_all_techniques();
+        Game::new(PuzzleGenerator::new(&solver).generate())
+    };
+
+    let terminal = ratatui::init();
+    let result = run(terminal, TuiState::new(game));
+    ratatui::restore();
+    if let Err(err) = result {
+        eprintln!("terminal error: {err}");
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+/// Decodes a state code, accepting only classic puzzles, whose constraints
+/// the board can show.
+fn load_game(code: &str) -> Result<Game, String> {
+    let game = Game::from_state_code(code).map_err(|err| format!("invalid state code: {err}"))?;
+    let classic = game.regions().is_standard()
+        && game.cages().is_empty()
+        && game.edge_constraints().is_empty()
+        && game.chess_rules().is_empty();
+    if !classic {
+        return Err("variant puzzles are not supported in the terminal".to_owned());
+    }
+    Ok(game)
+}
+
+fn run(mut terminal: DefaultTerminal, mut state: TuiState) -> io::Result<()> {
+    while !state.quit {
+        terminal.draw(|frame| render::draw(frame, &state))?;
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        let Some(action) = input::action_for_key(key) else {
+            continue;
+        };
+        if action == Action::NewGame {
+            // Generating takes a moment, so say so first.
+            state.message = Some("Generating a new puzzle...".to_owned());
+            terminal.draw(|frame| render::draw(frame, &state))?;
+        }
+        action.apply(&mut state);
+    }
+    Ok(())
+}
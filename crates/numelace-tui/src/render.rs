@@ -0,0 +1,314 @@
+use numelace_core::{Digit, DigitPositions, DigitSet, Position};
+use numelace_game::CellState;
+use numelace_solver::TechniqueApplication;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+use crate::{
+    input,
+    state::{HintStage, InputMode, TuiState},
+};
+
+/// Terminal lines below the board: mode, hint, message and key help.
+const STATUS_HEIGHT: u16 = 4;
+
+/// How many terminal lines a cell takes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CellLayout {
+    /// One line per cell; the notes of the selected cell are listed below
+    /// the board.
+    Compact,
+    /// Three lines per cell, with notes drawn as a 3x3 block.
+    Large,
+}
+
+impl CellLayout {
+    const fn cell_height(self) -> u16 {
+        match self {
+            Self::Compact => 1,
+            Self::Large => 3,
+        }
+    }
+
+    /// Returns the height of the board, including its frame.
+    const fn board_height(self) -> u16 {
+        9 * self.cell_height() + 10
+    }
+
+    /// Returns the largest layout whose board and status fit in `height` lines.
+    fn fitting(height: u16) -> Self {
+        if height >= Self::Large.board_height() + STATUS_HEIGHT {
+            Self::Large
+        } else {
+            Self::Compact
+        }
+    }
+}
+
+pub(crate) fn draw(frame: &mut Frame, state: &TuiState) {
+    let layout = CellLayout::fitting(frame.area().height);
+    let [board, status] = Layout::vertical([
+        Constraint::Length(layout.board_height()),
+        Constraint::Length(STATUS_HEIGHT),
+    ])
+    .areas(frame.area());
+    frame.render_widget(Paragraph::new(board_lines(state, layout)), board);
+    frame.render_widget(Paragraph::new(status_lines(state, layout)), status);
+}
+
+/// Where a frame line runs, which decides its junction characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FrameRow {
+    Top,
+    BoxBoundary,
+    CellBoundary,
+    Bottom,
+}
+
+impl FrameRow {
+    fn before_row(row: u8) -> Self {
+        match row {
+            0 => Self::Top,
+            9 => Self::Bottom,
+            row if row % 3 == 0 => Self::BoxBoundary,
+            _ => Self::CellBoundary,
+        }
+    }
+
+    /// Returns the junction before column `col`, or the right end for 9.
+    fn junction(self, col: u8) -> char {
+        let index = match col {
+            0 => 0,
+            9 => 3,
+            col if col % 3 == 0 => 1,
+            _ => 2,
+        };
+        let junctions = match self {
+            Self::Top => ['┏', '┳', '┯', '┓'],
+            Self::BoxBoundary => ['┣', '╋', '┿', '┫'],
+            Self::CellBoundary => ['┠', '╂', '┼', '┨'],
+            Self::Bottom => ['┗', '┻', '┷', '┛'],
+        };
+        junctions[index]
+    }
+
+    fn line(self) -> &'static str {
+        match self {
+            Self::CellBoundary => "───",
+            _ => "━━━",
+        }
+    }
+}
+
+/// Draws the board with box-drawing characters, heavy around the boxes.
+#[must_use]
+pub(crate) fn board_lines(state: &TuiState, layout: CellLayout) -> Vec<Line<'static>> {
+    let frame_style = Style::new().fg(Color::DarkGray);
+    let mut lines = vec![];
+    for row in 0..=9 {
+        let frame_row = FrameRow::before_row(row);
+        let mut frame_line = String::new();
+        for col in 0..=9 {
+            frame_line.push(frame_row.junction(col));
+            if col < 9 {
+                frame_line.push_str(frame_row.line());
+            }
+        }
+        lines.push(Line::styled(frame_line, frame_style));
+        if row == 9 {
+            break;
+        }
+        for sub_row in 0..layout.cell_height() {
+            let mut spans = vec![];
+            for col in 0..9 {
+                let separator = if col % 3 == 0 { "┃" } else { "│" };
+                spans.push(Span::styled(separator, frame_style));
+                let pos = Position::new(row, col);
+                spans.push(Span::styled(
+                    cell_text(state, pos, layout, sub_row),
+                    cell_style(state, pos),
+                ));
+            }
+            spans.push(Span::styled("┃", frame_style));
+            lines.push(Line::from(spans));
+        }
+    }
+    lines
+}
+
+/// Returns the three characters of `pos` on line `sub_row` of the cell.
+fn cell_text(state: &TuiState, pos: Position, layout: CellLayout, sub_row: u16) -> String {
+    let middle = sub_row == layout.cell_height() / 2;
+    match state.game.cell(pos) {
+        CellState::Given(digit) | CellState::Filled(digit) if middle => {
+            format!(" {} ", digit.value())
+        }
+        CellState::Notes(notes) => match layout {
+            CellLayout::Compact => " · ".to_owned(),
+            CellLayout::Large => Digit::ALL[usize::from(sub_row) * 3..][..3]
+                .iter()
+                .map(|&digit| {
+                    if notes.contains(digit) {
+                        char::from(b'0' + digit.value())
+                    } else {
+                        ' '
+                    }
+                })
+                .collect(),
+        },
+        _ => "   ".to_owned(),
+    }
+}
+
+fn cell_style(state: &TuiState, pos: Position) -> Style {
+    let cell = state.game.cell(pos);
+    let mut style = match cell {
+        CellState::Given(_) => Style::new().add_modifier(Modifier::BOLD),
+        CellState::Filled(digit) if conflicts(state, pos, *digit) => Style::new().fg(Color::Red),
+        CellState::Filled(_) => Style::new().fg(Color::Cyan),
+        CellState::Notes(_) | CellState::Empty => Style::new().fg(Color::Gray),
+    };
+    if let Some(hint) = &state.hint {
+        if hint.stage == HintStage::Step && hint_targets(hint.step.application()).contains(pos) {
+            style = style.bg(Color::Green).fg(Color::Black);
+        } else if hint.step.condition_positions().contains(pos) {
+            style = style.bg(Color::Yellow).fg(Color::Black);
+        }
+    }
+    if pos == state.selected {
+        style = style.add_modifier(Modifier::REVERSED);
+    }
+    style
+}
+
+/// Returns whether `digit` at `pos` repeats in a row, column or box.
+fn conflicts(state: &TuiState, pos: Position, digit: Digit) -> bool {
+    state
+        .game
+        .regions()
+        .house_peers(pos)
+        .iter()
+        .any(|peer| state.game.cell(peer).as_digit() == Some(digit))
+}
+
+fn hint_targets(applications: Vec<TechniqueApplication>) -> DigitPositions {
+    let mut targets = DigitPositions::new();
+    for application in applications {
+        match application {
+            TechniqueApplication::Placement { position, .. } => {
+                targets.insert(position);
+            }
+            TechniqueApplication::CandidateElimination { positions, .. } => {
+                targets |= positions;
+            }
+        }
+    }
+    targets
+}
+
+fn status_lines(state: &TuiState, layout: CellLayout) -> Vec<Line<'static>> {
+    let mode = match state.input_mode {
+        InputMode::Fill => "fill",
+        InputMode::Notes => "notes",
+    };
+    let mut first = format!("Mode: {mode}  Cell: {}", state.selected);
+    if layout == CellLayout::Compact
+        && let Some(notes) = state.game.cell(state.selected).as_notes()
+    {
+        first.push_str("  Notes: ");
+        first.push_str(&digits(notes));
+    }
+
+    let hint = state.hint.as_ref().map_or_else(String::new, |hint| {
+        let name = hint.step.technique_name();
+        match hint.stage {
+            HintStage::Area => format!("Hint: {name} in the yellow cells (? for more)"),
+            HintStage::Step => format!(
+                "Hint: {name}: {} (enter to apply)",
+                describe(hint.step.application())
+            ),
+        }
+    });
+
+    vec![
+        Line::raw(first),
+        Line::styled(hint, Style::new().fg(Color::Yellow)),
+        Line::raw(state.message.clone().unwrap_or_default()),
+        Line::styled(input::HELP, Style::new().fg(Color::DarkGray)),
+    ]
+}
+
+fn digits(set: DigitSet) -> String {
+    set.iter()
+        .map(|digit| char::from(b'0' + digit.value()))
+        .collect()
+}
+
+fn describe(applications: Vec<TechniqueApplication>) -> String {
+    applications
+        .into_iter()
+        .map(|application| match application {
+            TechniqueApplication::Placement { position, digit } => {
+                format!("{position} = {}", digit.value())
+            }
+            TechniqueApplication::CandidateElimination {
+                positions,
+                digits: removed,
+            } => {
+                let cells: Vec<_> = positions.iter().map(|pos| pos.to_string()).collect();
+                format!("remove {} from {}", digits(removed), cells.join(" "))
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+#[cfg(test)]
+mod tests {
+    use numelace_core::DigitGrid;
+    use numelace_game::Game;
+
+    use super::*;
+
+    #[test]
+    fn board_is_framed_with_box_drawing_characters() {
+        let solution: DigitGrid =
+            "185362947793148526246795183564239871931874265827516394318427659672951438459683712"
+                .parse()
+                .unwrap();
+        let mut problem = solution.clone();
+        problem.clear(Position::new(0, 0));
+        let notes = {
+            let mut notes = [[0; 9]; 9];
+            notes[0][0] = 0b1_0000_0001;
+            notes
+        };
+        let game = Game::from_problem_filled_notes(&problem, &solution, &DigitGrid::new(), &notes)
+            .unwrap();
+        let state = TuiState::new(game);
+
+        let text = |line: &Line| {
+            line.spans
+                .iter()
+                .map(|span| &*span.content)
+                .collect::<String>()
+        };
+        let large = board_lines(&state, CellLayout::Large);
+        assert_eq!(large.len(), 37);
+        assert_eq!(text(&large[0]).chars().count(), 37);
+        assert!(text(&large[0]).starts_with("┏━━━┯━━━┯━━━┳"));
+        assert_eq!(&text(&large[1])[..9], "┃1  │");
+        assert!(text(&large[2]).ends_with("│ 7 ┃"));
+        assert_eq!(&text(&large[3])[..9], "┃  9│");
+
+        let compact = board_lines(&state, CellLayout::Compact);
+        assert_eq!(compact.len(), 19);
+        assert!(text(&compact[1]).starts_with("┃ · │ 8 │"));
+        assert!(text(&compact[6]).starts_with("┣━━━┿"));
+    }
+}
@@ -0,0 +1,81 @@
+use numelace_core::Position;
+use numelace_game::Game;
+use numelace_solver::BoxedTechniqueStep;
+
+/// Maximum number of boards kept for undo.
+const HISTORY_CAPACITY: usize = 200;
+
+/// What digit keys write into the selected cell.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum InputMode {
+    #[default]
+    Fill,
+    Notes,
+}
+
+/// How much of the current hint is shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum HintStage {
+    /// The technique and the cells it looks at.
+    Area,
+    /// Also what applying the step changes.
+    Step,
+}
+
+#[derive(Debug)]
+pub(crate) struct Hint {
+    pub(crate) stage: HintStage,
+    pub(crate) step: BoxedTechniqueStep,
+}
+
+#[derive(Debug)]
+pub(crate) struct TuiState {
+    pub(crate) game: Game,
+    pub(crate) selected: Position,
+    pub(crate) input_mode: InputMode,
+    pub(crate) hint: Option<Hint>,
+    /// Status message shown below the board until the next action.
+    pub(crate) message: Option<String>,
+    pub(crate) quit: bool,
+    history: Vec<Game>,
+}
+
+impl TuiState {
+    #[must_use]
+    pub(crate) fn new(game: Game) -> Self {
+        Self {
+            game,
+            selected: Position::new(0, 0),
+            input_mode: InputMode::default(),
+            hint: None,
+            message: None,
+            quit: false,
+            history: vec![],
+        }
+    }
+
+    /// Replaces the board with a new puzzle, forgetting the undo history.
+    pub(crate) fn start(&mut self, game: Game) {
+        *self = Self {
+            input_mode: self.input_mode,
+            ..Self::new(game)
+        };
+    }
+
+    /// Remembers `previous` as the board before the latest change.
+    pub(crate) fn push_history(&mut self, previous: Game) {
+        if self.history.len() == HISTORY_CAPACITY {
+            self.history.remove(0);
+        }
+        self.history.push(previous);
+    }
+
+    /// Restores the board before the latest change.
+    pub(crate) fn undo(&mut self) -> bool {
+        let Some(previous) = self.history.pop() else {
+            return false;
+        };
+        self.game = previous;
+        true
+    }
+}
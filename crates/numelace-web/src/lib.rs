@@ -0,0 +1,336 @@
+//! JavaScript API for embedding Numelace in a DOM-based page.
+//!
+//! The GUI in `numelace-app` draws everything onto an egui canvas, which
+//! screen readers cannot see into and which costs more on phones than plain
+//! HTML. This crate exposes the game logic through `wasm-bindgen` instead, so
+//! a page can render the board with its own elements and call into Rust only
+//! to change it.
+//!
+//! # API
+//!
+//! [`WebGame`] is the only exported class. Its methods are the stable surface
+//! for pages: new methods may be added, but existing ones keep their names,
+//! arguments and meaning.
+//!
+//! - Rows and columns are numbered 0-8 and digits 1-9, as plain numbers.
+//! - Failing methods throw an `Error` whose message comes from
+//!   [`WebGameError`].
+//! - Only classic puzzles are supported, since the API has no way to describe
+//!   regions, cages or edge marks.
+//!
+//! # Usage
+//!
+//! ```sh
+//! wasm-pack build crates/numelace-web --target web --out-dir www/pkg
+//! ```
+//!
+//! Then serve `crates/numelace-web/www/`, which holds a small example page.
+//!
+//! ```js
+//! import init, { WebGame } from "./pkg/numelace_web.js";
+//!
+//! await init();
+//! const game = new WebGame();
+//! game.setDigit(0, 0, 5);
+//! console.log(game.digit(0, 0), game.isSolved());
+//! ```
+
+use derive_more::{Display, Error, From};
+use numelace_core::{Digit, Position, PositionNewError};
+use numelace_game::{
+    Game, GameError, InputDigitOptions, NoteCleanupPolicy, RuleCheckPolicy, StateCodeError,
+};
+use numelace_generator::PuzzleGenerator;
+use numelace_solver::TechniqueSolver;
+use wasm_bindgen::prelude::*;
+
+/// Maximum number of boards kept for undo.
+const HISTORY_CAPACITY: usize = 200;
+
+/// Errors thrown to JavaScript by [`WebGame`] methods.
+#[derive(Debug, Display, Error, From)]
+pub enum WebGameError {
+    /// A row or column is outside 0-8.
+    #[display("{_0}")]
+    InvalidPosition(PositionNewError),
+    /// A digit is outside 1-9.
+    #[display("invalid digit value: {_0}")]
+    #[from(ignore)]
+    InvalidDigit(#[error(not(source))] u8),
+    /// The state code could not be decoded.
+    #[display("invalid state code: {_0}")]
+    StateCode(StateCodeError),
+    /// The state code describes a variant puzzle.
+    #[display("variant puzzles are not supported")]
+    UnsupportedVariant,
+    /// The game rejected the input.
+    #[display("{_0}")]
+    Game(GameError),
+}
+
+impl From<WebGameError> for JsValue {
+    fn from(err: WebGameError) -> Self {
+        JsError::new(&err.to_string()).into()
+    }
+}
+
+/// A game session driven from JavaScript.
+#[wasm_bindgen]
+#[derive(Debug)]
+pub struct WebGame {
+    game: Game,
+    history: Vec<Game>,
+}
+
+impl Default for WebGame {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[wasm_bindgen]
+impl WebGame {
+    /// Starts a game with a newly generated puzzle.
+    #[wasm_bindgen(constructor)]
+    #[must_use]
+    pub fn new() -> Self {
+        let solver = TechniqueSolver::with_all_techniques();
+        Self::from_game(Game::new(PuzzleGenerator::new(&solver).generate()))
+    }
+
+    /// Starts a game from a state code, as found in the GUI's share links.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the code is invalid or describes a variant puzzle.
+    #[wasm_bindgen(js_name = fromStateCode)]
+    pub fn from_state_code(code: &str) -> Result<WebGame, WebGameError> {
+        let game = Game::from_state_code(code)?;
+        let classic = game.regions().is_standard()
+            && game.cages().is_empty()
+            && game.edge_constraints().is_empty()
+            && game.chess_rules().is_empty();
+        if !classic {
+            return Err(WebGameError::UnsupportedVariant);
+        }
+        Ok(Self::from_game(game))
+    }
+
+    /// Returns the state code of the board, including inputs and notes.
+    #[wasm_bindgen(js_name = stateCode)]
+    #[must_use]
+    pub fn state_code(&self) -> String {
+        self.game.to_state_code()
+    }
+
+    /// Returns whether the cell holds a digit given by the puzzle.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the position is out of range.
+    pub fn given(&self, row: u8, col: u8) -> Result<bool, WebGameError> {
+        Ok(self
+            .game
+            .cell(Position::try_new(row, col)?)
+            .as_given()
+            .is_some())
+    }
+
+    /// Returns the digit in the cell, given or filled, if any.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the position is out of range.
+    pub fn digit(&self, row: u8, col: u8) -> Result<Option<u8>, WebGameError> {
+        let cell = self.game.cell(Position::try_new(row, col)?);
+        Ok(cell.as_digit().map(u8::from))
+    }
+
+    /// Returns the notes in the cell in ascending order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the position is out of range.
+    pub fn notes(&self, row: u8, col: u8) -> Result<Vec<u8>, WebGameError> {
+        let cell = self.game.cell(Position::try_new(row, col)?);
+        let notes = cell.as_notes().unwrap_or_default();
+        Ok(notes.iter().map(u8::from).collect())
+    }
+
+    /// Returns whether the cell's digit repeats in its row, column or box.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the position is out of range.
+    #[wasm_bindgen(js_name = hasConflict)]
+    pub fn has_conflict(&self, row: u8, col: u8) -> Result<bool, WebGameError> {
+        let pos = Position::try_new(row, col)?;
+        let Some(digit) = self.game.cell(pos).as_digit() else {
+            return Ok(false);
+        };
+        Ok(self
+            .game
+            .regions()
+            .house_peers(pos)
+            .iter()
+            .any(|peer| self.game.cell(peer).as_digit() == Some(digit)))
+    }
+
+    /// Fills the cell with a digit, removing that digit from the notes of its
+    /// peers.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the position or digit is out of range, or if the
+    /// cell is given.
+    #[wasm_bindgen(js_name = setDigit)]
+    pub fn set_digit(&mut self, row: u8, col: u8, digit: u8) -> Result<(), WebGameError> {
+        let pos = Position::try_new(row, col)?;
+        let digit = parse_digit(digit)?;
+        let options =
+            InputDigitOptions::new(RuleCheckPolicy::Permissive, NoteCleanupPolicy::RemovePeers);
+        self.change(|game| game.set_digit(pos, digit, &options))
+    }
+
+    /// Adds or removes a note in the cell.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the position or digit is out of range, or if the
+    /// cell holds a digit.
+    #[wasm_bindgen(js_name = toggleNote)]
+    pub fn toggle_note(&mut self, row: u8, col: u8, digit: u8) -> Result<(), WebGameError> {
+        let pos = Position::try_new(row, col)?;
+        let digit = parse_digit(digit)?;
+        self.change(|game| game.toggle_note(pos, digit, RuleCheckPolicy::Permissive))
+    }
+
+    /// Removes the digit or notes from the cell.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the position is out of range or the cell is given.
+    #[wasm_bindgen(js_name = clearCell)]
+    pub fn clear_cell(&mut self, row: u8, col: u8) -> Result<(), WebGameError> {
+        let pos = Position::try_new(row, col)?;
+        self.change(|game| game.clear_cell(pos))
+    }
+
+    /// Fills the notes of every empty cell with its legal digits.
+    #[wasm_bindgen(js_name = autoFillNotes)]
+    pub fn auto_fill_notes(&mut self) {
+        self.push_history(self.game.clone());
+        self.game.auto_fill_notes_empty_cells();
+    }
+
+    /// Restores the board before the latest change, returning `false` if
+    /// there is nothing to undo.
+    pub fn undo(&mut self) -> bool {
+        let Some(previous) = self.history.pop() else {
+            return false;
+        };
+        self.game = previous;
+        true
+    }
+
+    /// Returns whether every cell is filled without conflicts.
+    #[wasm_bindgen(js_name = isSolved)]
+    #[must_use]
+    pub fn is_solved(&self) -> bool {
+        self.game.is_solved()
+    }
+}
+
+impl WebGame {
+    fn from_game(game: Game) -> Self {
+        Self {
+            game,
+            history: vec![],
+        }
+    }
+
+    fn push_history(&mut self, previous: Game) {
+        if self.history.len() == HISTORY_CAPACITY {
+            self.history.remove(0);
+        }
+        self.history.push(previous);
+    }
+
+    /// Applies `change`, keeping the previous board for undo if it succeeds.
+    fn change<T>(
+        &mut self,
+        change: impl FnOnce(&mut Game) -> Result<T, GameError>,
+    ) -> Result<(), WebGameError> {
+        let previous = self.game.clone();
+        change(&mut self.game)?;
+        self.push_history(previous);
+        Ok(())
+    }
+}
+
+fn parse_digit(value: u8) -> Result<Digit, WebGameError> {
+    if (1..=9).contains(&value) {
+        Ok(Digit::from_value(value))
+    } else {
+        Err(WebGameError::InvalidDigit(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use numelace_core::DigitGrid;
+
+    use super::*;
+
+    const SOLUTION: &str =
+        "185362947793148526246795183564239871931874265827516394318427659672951438459683712";
+
+    fn web_game() -> WebGame {
+        let solution: DigitGrid = SOLUTION.parse().unwrap();
+        let problem: DigitGrid = format!("{}{}", ".".repeat(9), &SOLUTION[9..])
+            .parse()
+            .unwrap();
+        let game =
+            Game::from_problem_filled_notes(&problem, &solution, &DigitGrid::new(), &[[0; 9]; 9])
+                .unwrap();
+        WebGame::from_game(game)
+    }
+
+    #[test]
+    fn inputs_round_trip_through_state_codes_and_undo() {
+        let mut game = web_game();
+        assert!(game.given(1, 0).unwrap());
+        assert_eq!(game.digit(1, 0).unwrap(), Some(7));
+
+        game.set_digit(0, 0, 7).unwrap();
+        assert!(game.has_conflict(0, 0).unwrap());
+        game.toggle_note(0, 1, 8).unwrap();
+        game.toggle_note(0, 1, 2).unwrap();
+        assert_eq!(game.notes(0, 1).unwrap(), [2, 8]);
+
+        let restored = WebGame::from_state_code(&game.state_code()).unwrap();
+        assert_eq!(restored.digit(0, 0).unwrap(), Some(7));
+        assert_eq!(restored.notes(0, 1).unwrap(), [2, 8]);
+
+        assert!(game.undo());
+        assert!(game.undo());
+        assert!(game.undo());
+        assert_eq!(game.digit(0, 0).unwrap(), None);
+        assert!(!game.undo());
+    }
+
+    #[test]
+    fn invalid_arguments_are_rejected() {
+        let mut game = web_game();
+        assert!(matches!(
+            game.digit(9, 0),
+            Err(WebGameError::InvalidPosition(_))
+        ));
+        assert!(matches!(
+            game.set_digit(0, 0, 0),
+            Err(WebGameError::InvalidDigit(0))
+        ));
+        assert!(matches!(game.clear_cell(1, 0), Err(WebGameError::Game(_))));
+        assert!(!game.undo());
+    }
+}
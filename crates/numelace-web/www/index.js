@@ -0,0 +1,139 @@
+// Example page rendering the board with DOM elements.
+// Build the package first; see the crate documentation of `numelace-web`.
+import init, { WebGame } from "./pkg/numelace_web.js";
+
+await init();
+
+const params = new URLSearchParams(location.search);
+let game = params.has("state") ? WebGame.fromStateCode(params.get("state")) : new WebGame();
+let selected = { row: 0, col: 0 };
+
+const board = document.getElementById("board");
+const keypad = document.getElementById("keypad");
+const notesMode = document.getElementById("notes-mode");
+const status = document.getElementById("status");
+
+const cells = [];
+for (let row = 0; row < 9; row++) {
+  const rowElement = document.createElement("div");
+  rowElement.setAttribute("role", "row");
+  rowElement.style.display = "contents";
+  for (let col = 0; col < 9; col++) {
+    const cell = document.createElement("button");
+    cell.type = "button";
+    cell.className = "cell";
+    cell.setAttribute("role", "gridcell");
+    cell.classList.toggle("box-right", col % 3 === 2 && col < 8);
+    cell.classList.toggle("box-bottom", row % 3 === 2 && row < 8);
+    cell.addEventListener("click", () => select(row, col));
+    rowElement.append(cell);
+    cells.push(cell);
+  }
+  board.append(rowElement);
+}
+
+for (let digit = 1; digit <= 9; digit++) {
+  const key = document.createElement("button");
+  key.type = "button";
+  key.textContent = digit;
+  key.addEventListener("click", () => input(digit));
+  keypad.append(key);
+}
+
+document.getElementById("clear").addEventListener("click", () => {
+  run(() => game.clearCell(selected.row, selected.col));
+});
+document.getElementById("fill-notes").addEventListener("click", () => {
+  run(() => game.autoFillNotes());
+});
+document.getElementById("undo").addEventListener("click", () => {
+  run(() => game.undo() || (status.textContent = "Nothing to undo."));
+});
+document.getElementById("new-game").addEventListener("click", () => {
+  run(() => {
+    game.free();
+    game = new WebGame();
+  });
+});
+
+document.addEventListener("keydown", (event) => {
+  const moves = {
+    ArrowUp: [-1, 0],
+    ArrowDown: [1, 0],
+    ArrowLeft: [0, -1],
+    ArrowRight: [0, 1],
+  };
+  if (event.key in moves) {
+    const [dr, dc] = moves[event.key];
+    select(Math.min(8, Math.max(0, selected.row + dr)), Math.min(8, Math.max(0, selected.col + dc)));
+  } else if (/^[1-9]$/.test(event.key)) {
+    input(Number(event.key));
+  } else if (event.key === "Delete" || event.key === "Backspace") {
+    run(() => game.clearCell(selected.row, selected.col));
+  } else if (event.key === "s") {
+    notesMode.checked = !notesMode.checked;
+  } else {
+    return;
+  }
+  event.preventDefault();
+});
+
+function select(row, col) {
+  selected = { row, col };
+  cells[row * 9 + col].focus();
+  render();
+}
+
+function input(digit) {
+  const { row, col } = selected;
+  run(() => (notesMode.checked ? game.toggleNote(row, col, digit) : game.setDigit(row, col, digit)));
+}
+
+function run(change) {
+  status.textContent = "";
+  try {
+    change();
+  } catch (error) {
+    status.textContent = error.message;
+  }
+  render();
+}
+
+function render() {
+  for (let row = 0; row < 9; row++) {
+    for (let col = 0; col < 9; col++) {
+      const cell = cells[row * 9 + col];
+      const digit = game.digit(row, col);
+      const given = game.given(row, col);
+      const notes = digit === undefined ? Array.from(game.notes(row, col)) : [];
+      cell.classList.toggle("given", given);
+      cell.classList.toggle("filled", digit !== undefined && !given);
+      cell.classList.toggle("conflict", game.hasConflict(row, col));
+      cell.classList.toggle("notes", notes.length > 0);
+      cell.textContent = digit ?? noteGrid(notes);
+      cell.setAttribute("aria-selected", row === selected.row && col === selected.col);
+      cell.setAttribute("aria-label", cellLabel(row, col, digit, given, notes));
+    }
+  }
+  if (game.isSolved()) {
+    status.textContent = "Solved!";
+  }
+}
+
+function noteGrid(notes) {
+  let text = "";
+  for (let digit = 1; digit <= 9; digit++) {
+    text += notes.includes(digit) ? digit : " ";
+    if (digit % 3 === 0 && digit < 9) text += "\n";
+  }
+  return notes.length > 0 ? text : "";
+}
+
+function cellLabel(row, col, digit, given, notes) {
+  const position = `row ${row + 1}, column ${col + 1}`;
+  if (digit !== undefined) return `${position}: ${digit}${given ? ", given" : ""}`;
+  if (notes.length > 0) return `${position}: notes ${notes.join(" ")}`;
+  return `${position}: empty`;
+}
+
+render();
@@ -0,0 +1,41 @@
+//! Solves arbitrary candidate grids with the backtracking solver.
+//!
+//! Each cell takes its candidates from two input bytes (missing bytes leave all
+//! candidates). Solving must never panic and may only fail with a consistency
+//! error; any solution found must be complete, consistent, and drawn from the
+//! input candidates.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use numelace_core::{CandidateGrid, Digit, DigitSet, Position};
+use numelace_solver::{BacktrackSolver, SolverError, TechniqueGrid};
+
+fuzz_target!(|data: &[u8]| {
+    let mut grid = CandidateGrid::new();
+    for (pos, bytes) in Position::ALL.into_iter().zip(data.chunks_exact(2)) {
+        let bits = u16::from_le_bytes([bytes[0], bytes[1]]) & 0x1ff;
+        let candidates = DigitSet::from_bits(bits);
+        for digit in Digit::ALL {
+            if !candidates.contains(digit) {
+                grid.remove_candidate(pos, digit);
+            }
+        }
+    }
+
+    let solver = BacktrackSolver::with_all_techniques();
+    match solver.solve_with_step(TechniqueGrid::from(grid.clone())) {
+        Ok(mut solutions) => {
+            let Some((solution, _stats)) = solutions.next() else {
+                return;
+            };
+            assert!(matches!(solution.is_solved(), Ok(true)));
+            let digits = solution.to_digit_grid();
+            for pos in Position::ALL {
+                let digit = digits.get(pos).expect("solved grids are complete");
+                assert!(grid.candidates_at(pos).contains(digit));
+            }
+        }
+        Err(SolverError::Inconsistent(_)) => {}
+    }
+});
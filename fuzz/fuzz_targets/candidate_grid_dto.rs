@@ -0,0 +1,30 @@
+//! Converts arbitrary candidate bitmasks the way worker requests do.
+//!
+//! Conversion must never panic. It succeeds exactly when there are 81 masks
+//! within the 9-bit range, and then keeps every mask unchanged.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use numelace_app::worker_api::candidate_grid_from_bits;
+use numelace_core::Position;
+
+fuzz_target!(|data: &[u8]| {
+    let candidates = data
+        .chunks_exact(2)
+        .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]))
+        .collect::<Vec<_>>();
+    let valid = candidates.len() == 81 && candidates.iter().all(|&bits| bits < 1 << 9);
+
+    match candidate_grid_from_bits(candidates.clone()) {
+        Ok(grid) => {
+            assert!(valid, "accepted invalid candidates: {candidates:?}");
+            for (pos, bits) in Position::ALL.into_iter().zip(candidates) {
+                assert_eq!(grid.candidates_at(pos).bits(), bits);
+            }
+            // Inconsistent grids are expected here; they must only be reported.
+            let _ = grid.check_consistency();
+        }
+        Err(_) => assert!(!valid, "rejected valid candidates: {candidates:?}"),
+    }
+});
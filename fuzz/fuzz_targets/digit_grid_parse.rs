@@ -0,0 +1,23 @@
+//! Parses arbitrary text as a [`DigitGrid`], as puzzle imports do.
+//!
+//! Parsing must never panic, and every accepted grid must survive a
+//! display/parse round trip.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use numelace_core::DigitGrid;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(text) = std::str::from_utf8(data) else {
+        return;
+    };
+    let Ok(grid) = text.parse::<DigitGrid>() else {
+        return;
+    };
+    let reparsed = grid
+        .to_string()
+        .parse::<DigitGrid>()
+        .expect("a displayed grid parses back");
+    assert_eq!(grid, reparsed);
+});